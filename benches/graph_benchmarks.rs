@@ -10,7 +10,7 @@ use graphina::core::generators::{
     barabasi_albert_graph, complete_graph, erdos_renyi_graph, watts_strogatz_graph,
 };
 use graphina::core::paths::dijkstra;
-use graphina::core::types::Undirected;
+use graphina::core::types::{Directed, Undirected};
 use graphina::traversal::{bfs, bidis, dfs};
 use std::hint::black_box;
 
@@ -249,6 +249,59 @@ fn bench_node_removal(c: &mut Criterion) {
     group.finish();
 }
 
+// ============================================================================
+// Adjacency Query Benchmarks
+// ============================================================================
+
+// One query per node walks every incident edge once, so a sweep costs O(V + E) when the
+// queries follow the adjacency lists and O(V * E) if any of them scans the edge list.
+// Throughput is per edge, so a flat rate across sizes confirms O(degree) queries.
+fn bench_neighbor_queries(c: &mut Criterion) {
+    let mut group = c.benchmark_group("neighbor_queries");
+
+    for size in [1000, 5000, 20000].iter() {
+        let graph = barabasi_albert_graph::<Directed>(*size, 3, 42).unwrap();
+        let nodes: Vec<_> = graph.node_ids().collect();
+        group.throughput(Throughput::Elements(graph.edge_count() as u64));
+
+        group.bench_with_input(BenchmarkId::new("outgoing_edges", size), size, |b, _| {
+            b.iter(|| {
+                let total: usize = nodes.iter().map(|&n| graph.outgoing_edges(n).count()).sum();
+                black_box(total)
+            });
+        });
+        group.bench_with_input(BenchmarkId::new("in_degree", size), size, |b, _| {
+            b.iter(|| {
+                let total: usize = nodes.iter().filter_map(|&n| graph.in_degree(n)).sum();
+                black_box(total)
+            });
+        });
+        group.bench_with_input(
+            BenchmarkId::new("incoming_neighbors", size),
+            size,
+            |b, _| {
+                b.iter(|| {
+                    let total: usize = nodes
+                        .iter()
+                        .map(|&n| graph.incoming_neighbors(n).count())
+                        .sum();
+                    black_box(total)
+                });
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("find_edge", size), size, |b, _| {
+            b.iter(|| {
+                let found = nodes
+                    .windows(2)
+                    .filter(|pair| graph.find_edge(pair[1], pair[0]).is_some())
+                    .count();
+                black_box(found)
+            });
+        });
+    }
+    group.finish();
+}
+
 // ============================================================================
 // Graph Density Benchmarks
 // ============================================================================
@@ -349,7 +402,8 @@ criterion_group!(
     operations,
     bench_add_nodes,
     bench_add_edges,
-    bench_node_removal
+    bench_node_removal,
+    bench_neighbor_queries
 );
 
 criterion_group!(
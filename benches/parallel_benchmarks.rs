@@ -0,0 +1,63 @@
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use graphina::core::generators::barabasi_albert_graph;
+use graphina::core::types::{NodeId, Undirected};
+use graphina::metrics::clustering_coefficient;
+use graphina::parallel::{
+    clustering_coefficients_parallel, core_number_parallel, triangles_parallel,
+};
+use std::hint::black_box;
+
+fn bench_parallel_metrics(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parallel_metrics");
+    group.sample_size(10);
+
+    for size in [10_000, 100_000].iter() {
+        let graph = barabasi_albert_graph::<Undirected>(*size, 5, 42).unwrap();
+        let nodes: Vec<NodeId> = graph.node_ids().collect();
+        group.throughput(Throughput::Elements(*size as u64));
+
+        group.bench_with_input(
+            BenchmarkId::new("clustering_sequential", size),
+            &graph,
+            |b, g| {
+                b.iter(|| {
+                    black_box(
+                        nodes
+                            .iter()
+                            .map(|&n| clustering_coefficient(g, n))
+                            .collect::<Vec<f64>>(),
+                    )
+                });
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("clustering_parallel", size),
+            &graph,
+            |b, g| {
+                b.iter(|| black_box(clustering_coefficients_parallel(g)));
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("triangles_parallel", size),
+            &graph,
+            |b, g| {
+                b.iter(|| black_box(triangles_parallel(g)));
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("core_number_parallel", size),
+            &graph,
+            |b, g| {
+                b.iter(|| black_box(core_number_parallel(g)));
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_parallel_metrics);
+criterion_main!(benches);
@@ -0,0 +1,161 @@
+/*!
+Reading and writing the graph file formats the tool understands.
+
+Every graph is loaded as `BaseGraph<i32, f64, Ty>`: nodes carry the integer label from the
+input file, and edges carry an `f64` weight. Edge lists are written with `f32` weights by the
+library's edge-list writer, so weights are narrowed on write.
+*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use graphina::core::io::{read_edge_list, write_edge_list};
+use graphina::core::types::{BaseGraph, GraphConstructor};
+
+use crate::Result;
+
+/// A graph file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// One `source target [weight]` line per edge.
+    EdgeList,
+    /// Graphina's JSON serialization.
+    Json,
+    /// Graphina's versioned binary serialization.
+    Binary,
+    /// GraphML, write only.
+    GraphMl,
+    /// D3 node-link JSON, write only.
+    D3,
+}
+
+impl Format {
+    /// Names accepted on the command line, in the order shown in `--help`.
+    pub const NAMES: [&'static str; 5] = ["edgelist", "json", "binary", "graphml", "d3"];
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> std::result::Result<Self, Self::Err> {
+        match name {
+            "edgelist" => Ok(Format::EdgeList),
+            "json" => Ok(Format::Json),
+            "binary" => Ok(Format::Binary),
+            "graphml" => Ok(Format::GraphMl),
+            "d3" => Ok(Format::D3),
+            other => Err(format!(
+                "unknown format '{other}', expected one of {}",
+                Format::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Format::EdgeList => "edgelist",
+            Format::Json => "json",
+            Format::Binary => "binary",
+            Format::GraphMl => "graphml",
+            Format::D3 => "d3",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Loads the graph at `path`. `sep` separates tokens in the text formats.
+pub fn load<Ty>(path: &str, format: Format, sep: char) -> Result<BaseGraph<i32, f64, Ty>>
+where
+    Ty: GraphConstructor<i32, f64>,
+{
+    let graph = match format {
+        Format::EdgeList => {
+            let mut graph = BaseGraph::<i32, f64, Ty>::new();
+            read_edge_list(path, &mut graph, sep)?;
+            graph
+        }
+        Format::Json => BaseGraph::load_json_strict(path)?,
+        Format::Binary => BaseGraph::load_binary_strict(path)?,
+        Format::GraphMl | Format::D3 => {
+            return Err(format!("the {format} format cannot be read, only written").into());
+        }
+    };
+    Ok(graph)
+}
+
+/// Writes `graph` to `path`. `sep` separates tokens in the text formats.
+pub fn save<Ty>(
+    graph: &BaseGraph<i32, f64, Ty>,
+    path: &str,
+    format: Format,
+    sep: char,
+) -> Result<()>
+where
+    Ty: GraphConstructor<i32, f64> + GraphConstructor<i32, f32>,
+{
+    match format {
+        Format::EdgeList => write_edge_list(path, &narrow(graph), sep)?,
+        Format::Json => graph.save_json(path)?,
+        Format::Binary => graph.save_binary(path)?,
+        Format::GraphMl => graph.save_graphml(path)?,
+        Format::D3 => graph.save_d3_json(path)?,
+    }
+    Ok(())
+}
+
+/// The `f32`-weighted copy the edge-list writer expects.
+fn narrow<Ty>(graph: &BaseGraph<i32, f64, Ty>) -> BaseGraph<i32, f32, Ty>
+where
+    Ty: GraphConstructor<i32, f64> + GraphConstructor<i32, f32>,
+{
+    graph.map_edge_weights(|_, w| *w as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphina::core::types::{Directed, Undirected};
+
+    #[test]
+    fn test_formats_round_trip() {
+        let dir = std::env::temp_dir().join(format!("graphina-cli-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("edges.txt");
+        std::fs::write(&input, "# comment\n1,2,0.5\n2,3\n3,1,2\n").unwrap();
+        let input = input.to_str().unwrap();
+
+        let graph = load::<Directed>(input, Format::EdgeList, ',').unwrap();
+        assert_eq!((graph.node_count(), graph.edge_count()), (3, 3));
+        for format in [Format::EdgeList, Format::Json, Format::Binary] {
+            let path = dir.join(format!("graph.{format}"));
+            let path = path.to_str().unwrap();
+            save(&graph, path, format, ',').unwrap();
+            let back = load::<Directed>(path, format, ',').unwrap();
+            let mut weights: Vec<f64> = back.edges().map(|(_, _, w)| *w).collect();
+            weights.sort_by(f64::total_cmp);
+            assert_eq!(weights, vec![0.5, 1.0, 2.0], "{format}");
+        }
+
+        // A directed JSON file is rejected as undirected input, and write-only formats
+        // cannot be read back.
+        let json = dir.join("graph.json");
+        assert!(load::<Undirected>(json.to_str().unwrap(), Format::Json, ',').is_err());
+        save(
+            &graph,
+            dir.join("graph.graphml").to_str().unwrap(),
+            Format::GraphMl,
+            ',',
+        )
+        .unwrap();
+        assert!(load::<Directed>(input, Format::GraphMl, ',').is_err());
+        assert!("xml".parse::<Format>().is_err());
+        assert!(
+            Format::NAMES
+                .iter()
+                .all(|name| name.parse::<Format>().is_ok())
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
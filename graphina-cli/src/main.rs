@@ -0,0 +1,447 @@
+/*!
+# graphina-cli
+
+A command-line tool for quick dataset triage with Graphina: summary statistics, PageRank,
+community detection, format conversion, and an HTML preview, without writing code.
+
+```text
+graphina-cli stats edges.txt --sep ' '
+graphina-cli pagerank edges.txt --directed --top 20
+graphina-cli communities edges.txt --method label-propagation --seed 42
+graphina-cli convert edges.txt --to graphml --output graph.graphml
+graphina-cli render graph.json --from json --html --overlay pagerank --overlay communities --output graph.html
+```
+
+Graphs are read as `i32`-labelled nodes with `f64` edge weights, undirected unless
+`--directed` is given. Results go to standard output as tab-separated lines.
+*/
+
+mod formats;
+mod render;
+
+use std::process::ExitCode;
+
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use graphina::centrality::betweenness::betweenness_centrality;
+use graphina::centrality::closeness::closeness_centrality;
+use graphina::centrality::degree::degree_centrality;
+use graphina::centrality::pagerank::pagerank;
+use graphina::community::connected_components::connected_components;
+use graphina::community::label_propagation_map;
+use graphina::community::louvain::louvain;
+use graphina::community::quality::modularity;
+use graphina::core::types::{
+    BaseGraph, Directed, GraphConstructor, GraphinaGraph, NodeId, Undirected,
+};
+use graphina::metrics::{average_clustering_coefficient, transitivity};
+use serde_json::json;
+
+use formats::Format;
+
+/// Errors from the library, from I/O, and from argument checks all end the run the same way.
+pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+fn cli() -> Command {
+    let input = Arg::new("input")
+        .required(true)
+        .help("Path of the graph file to read");
+    let output = Arg::new("output")
+        .short('o')
+        .long("output")
+        .required(true)
+        .help("Path of the file to write");
+    Command::new("graphina-cli")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Quick graph analyses with Graphina")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .arg(
+            Arg::new("directed")
+                .long("directed")
+                .global(true)
+                .action(ArgAction::SetTrue)
+                .help("Read the input as a directed graph"),
+        )
+        .arg(
+            Arg::new("from")
+                .long("from")
+                .global(true)
+                .default_value("edgelist")
+                .value_parser(value_parser!(Format))
+                .help(format!("Input format: {}", Format::NAMES[..3].join(", "))),
+        )
+        .arg(
+            Arg::new("sep")
+                .long("sep")
+                .global(true)
+                .default_value(",")
+                .value_parser(value_parser!(char))
+                .help("Token separator of edge-list files"),
+        )
+        .subcommand(
+            Command::new("stats")
+                .about("Print node, edge, density, component, and clustering statistics")
+                .arg(input.clone())
+                .arg(
+                    Arg::new("json")
+                        .long("json")
+                        .action(ArgAction::SetTrue)
+                        .help("Print the statistics as one JSON object"),
+                ),
+        )
+        .subcommand(
+            Command::new("pagerank")
+                .about("Print the highest PageRank scores")
+                .arg(input.clone())
+                .arg(
+                    Arg::new("damping")
+                        .long("damping")
+                        .default_value("0.85")
+                        .value_parser(value_parser!(f64)),
+                )
+                .arg(
+                    Arg::new("max-iter")
+                        .long("max-iter")
+                        .default_value("100")
+                        .value_parser(value_parser!(usize)),
+                )
+                .arg(
+                    Arg::new("tolerance")
+                        .long("tolerance")
+                        .default_value("1e-6")
+                        .value_parser(value_parser!(f64)),
+                )
+                .arg(
+                    Arg::new("top")
+                        .long("top")
+                        .default_value("10")
+                        .value_parser(value_parser!(usize))
+                        .help("Number of nodes to print, or 0 for all of them"),
+                ),
+        )
+        .subcommand(
+            Command::new("communities")
+                .about("Print each node's community and the partition's modularity")
+                .arg(input.clone())
+                .arg(
+                    Arg::new("method")
+                        .long("method")
+                        .default_value("louvain")
+                        .value_parser(["louvain", "label-propagation"]),
+                )
+                .arg(
+                    Arg::new("seed")
+                        .long("seed")
+                        .value_parser(value_parser!(u64))
+                        .help("Seed for a reproducible partition"),
+                ),
+        )
+        .subcommand(
+            Command::new("convert")
+                .about("Convert a graph file to another format")
+                .arg(input.clone())
+                .arg(
+                    Arg::new("to")
+                        .long("to")
+                        .required(true)
+                        .value_parser(value_parser!(Format))
+                        .help(format!("Output format: {}", Format::NAMES.join(", "))),
+                )
+                .arg(output.clone()),
+        )
+        .subcommand(
+            Command::new("render")
+                .about("Render the graph as an interactive page")
+                .arg(input)
+                .arg(
+                    Arg::new("html")
+                        .long("html")
+                        .required(true)
+                        .action(ArgAction::SetTrue)
+                        .help("Write a standalone HTML page (the only renderer for now)"),
+                )
+                .arg(
+                    Arg::new("title")
+                        .long("title")
+                        .default_value("Graphina")
+                        .help("Page title"),
+                )
+                .arg(
+                    Arg::new("overlay")
+                        .long("overlay")
+                        .action(ArgAction::Append)
+                        .value_parser(OVERLAYS)
+                        .help("Per-node values to color by in the page; may be repeated"),
+                )
+                .arg(output),
+        )
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    let result = if matches.get_flag("directed") {
+        execute::<Directed>(&matches)
+    } else {
+        execute::<Undirected>(&matches)
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Loads the input and runs the chosen subcommand on it.
+fn execute<Ty>(matches: &ArgMatches) -> Result<()>
+where
+    Ty: GraphConstructor<i32, f64> + GraphConstructor<i32, f32>,
+    BaseGraph<i32, f64, Ty>: GraphinaGraph<i32, f64>,
+{
+    let (name, args) = matches.subcommand().ok_or("no subcommand given")?;
+    let input = args.get_one::<String>("input").ok_or("no input given")?;
+    let from = args
+        .get_one::<Format>("from")
+        .copied()
+        .unwrap_or(Format::EdgeList);
+    let sep = args.get_one::<char>("sep").copied().unwrap_or(',');
+    let graph = formats::load::<Ty>(input, from, sep)?;
+
+    match name {
+        "stats" => stats(&graph, args.get_flag("json")),
+        "pagerank" => {
+            let scores = pagerank(
+                &graph,
+                arg(args, "damping")?,
+                arg(args, "max-iter")?,
+                arg(args, "tolerance")?,
+                None,
+            )?;
+            let mut ranked: Vec<(NodeId, f64)> = scores.into_iter().collect();
+            ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.index().cmp(&b.0.index())));
+            let top: usize = arg(args, "top")?;
+            let shown = if top == 0 { ranked.len() } else { top };
+            for (node, score) in ranked.into_iter().take(shown) {
+                println!("{}\t{score:.6}", label(&graph, node));
+            }
+            Ok(())
+        }
+        "communities" => {
+            let seed = args.get_one::<u64>("seed").copied();
+            let method = args.get_one::<String>("method").map(String::as_str);
+            let communities = match method {
+                Some("label-propagation") => {
+                    let labels = label_propagation_map(&graph, 100, seed)?;
+                    group(&graph, |node| labels.get(&node).copied())
+                }
+                _ => louvain(&graph, seed)?,
+            };
+            println!(
+                "# {} communities, modularity {:.6}",
+                communities.len(),
+                modularity(&graph, &communities)?
+            );
+            let mut rows: Vec<(NodeId, usize)> = communities
+                .iter()
+                .enumerate()
+                .flat_map(|(c, members)| members.iter().map(move |&node| (node, c)))
+                .collect();
+            rows.sort_by_key(|&(node, _)| node.index());
+            for (node, c) in rows {
+                println!("{}\t{c}", label(&graph, node));
+            }
+            Ok(())
+        }
+        "convert" => {
+            let to = args
+                .get_one::<Format>("to")
+                .copied()
+                .ok_or("no output format")?;
+            let output = args.get_one::<String>("output").ok_or("no output given")?;
+            formats::save(&graph, output, to, sep)
+        }
+        "render" => {
+            let title = args
+                .get_one::<String>("title")
+                .map_or("Graphina", String::as_str);
+            let output = args.get_one::<String>("output").ok_or("no output given")?;
+            let overlays = args
+                .get_many::<String>("overlay")
+                .into_iter()
+                .flatten()
+                .map(|name| overlay(&graph, name))
+                .collect::<Result<Vec<_>>>()?;
+            std::fs::write(output, render::html(&graph, title, &overlays)?)?;
+            Ok(())
+        }
+        other => Err(format!("unknown subcommand '{other}'").into()),
+    }
+}
+
+/// Summary statistics, as aligned `name value` lines or one JSON object.
+fn stats<Ty>(graph: &BaseGraph<i32, f64, Ty>, as_json: bool) -> Result<()>
+where
+    Ty: GraphConstructor<i32, f64>,
+{
+    let components = connected_components(graph);
+    let largest = components.iter().map(Vec::len).max().unwrap_or(0);
+    let n = graph.node_count();
+    let average_degree = if n == 0 {
+        0.0
+    } else {
+        let ends = if graph.is_directed() { 1.0 } else { 2.0 };
+        ends * graph.edge_count() as f64 / n as f64
+    };
+    let rows = [
+        ("nodes", json!(n)),
+        ("edges", json!(graph.edge_count())),
+        ("directed", json!(graph.is_directed())),
+        ("density", json!(graph.density())),
+        ("average_degree", json!(average_degree)),
+        ("components", json!(components.len())),
+        ("largest_component", json!(largest)),
+        (
+            "average_clustering",
+            json!(average_clustering_coefficient(graph)),
+        ),
+        ("transitivity", json!(transitivity(graph))),
+    ];
+    if as_json {
+        let object: serde_json::Map<String, serde_json::Value> = rows
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect();
+        println!("{}", serde_json::Value::Object(object));
+    } else {
+        for (name, value) in rows {
+            println!("{name:<20}{value}");
+        }
+    }
+    Ok(())
+}
+
+/// The values `render --overlay` can add to the page.
+const OVERLAYS: [&str; 5] = [
+    "degree",
+    "pagerank",
+    "betweenness",
+    "closeness",
+    "communities",
+];
+
+/// Computes the overlay called `name`, one of [`OVERLAYS`], with default parameters.
+fn overlay<Ty>(graph: &BaseGraph<i32, f64, Ty>, name: &str) -> Result<render::Overlay>
+where
+    Ty: GraphConstructor<i32, f64>,
+    BaseGraph<i32, f64, Ty>: GraphinaGraph<i32, f64>,
+{
+    let values = match name {
+        "degree" => degree_centrality(graph)?,
+        "pagerank" => pagerank(graph, 0.85, 100, 1e-6, None)?,
+        "betweenness" => betweenness_centrality(graph, true)?,
+        "closeness" => closeness_centrality(graph)?,
+        "communities" => {
+            return Ok(render::Overlay::communities(
+                name,
+                &louvain(graph, Some(0))?,
+            ));
+        }
+        other => return Err(format!("unknown overlay '{other}'").into()),
+    };
+    Ok(render::Overlay::scores(name, values))
+}
+
+/// The value of argument `name`, which has a default.
+fn arg<T: Clone + Send + Sync + 'static>(args: &ArgMatches, name: &str) -> Result<T> {
+    args.get_one::<T>(name)
+        .cloned()
+        .ok_or_else(|| format!("missing value for --{name}").into())
+}
+
+/// The node's label from the input file.
+fn label<Ty>(graph: &BaseGraph<i32, f64, Ty>, node: NodeId) -> String
+where
+    Ty: GraphConstructor<i32, f64>,
+{
+    graph
+        .node_attr(node)
+        .map_or_else(|| format!("#{}", node.index()), i32::to_string)
+}
+
+/// Groups the nodes by `community`, numbering the groups in order of first appearance.
+fn group<Ty>(
+    graph: &BaseGraph<i32, f64, Ty>,
+    community: impl Fn(NodeId) -> Option<usize>,
+) -> Vec<Vec<NodeId>>
+where
+    Ty: GraphConstructor<i32, f64>,
+{
+    let mut slot = std::collections::HashMap::new();
+    let mut groups: Vec<Vec<NodeId>> = Vec::new();
+    for node in graph.node_ids() {
+        if let Some(c) = community(node) {
+            let i = *slot.entry(c).or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+            groups[i].push(node);
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        cli().debug_assert();
+        let matches = cli()
+            .try_get_matches_from([
+                "graphina-cli",
+                "convert",
+                "edges.txt",
+                "--directed",
+                "--to",
+                "graphml",
+                "-o",
+                "out.graphml",
+            ])
+            .unwrap();
+        assert!(matches.get_flag("directed"));
+        let (name, args) = matches.subcommand().unwrap();
+        assert_eq!(name, "convert");
+        assert_eq!(args.get_one::<Format>("to"), Some(&Format::GraphMl));
+        assert_eq!(args.get_one::<Format>("from"), Some(&Format::EdgeList));
+
+        assert!(
+            cli()
+                .try_get_matches_from(["graphina-cli", "render", "g.txt", "-o", "g.html"])
+                .is_err()
+        );
+        let matches = cli()
+            .try_get_matches_from([
+                "graphina-cli",
+                "render",
+                "g.txt",
+                "--html",
+                "--overlay",
+                "pagerank",
+                "--overlay",
+                "communities",
+                "-o",
+                "g.html",
+            ])
+            .unwrap();
+        let (_, args) = matches.subcommand().unwrap();
+        let overlays: Vec<&String> = args.get_many("overlay").unwrap().collect();
+        assert_eq!(overlays, ["pagerank", "communities"]);
+        assert!(
+            cli()
+                .try_get_matches_from(["graphina-cli", "stats", "g.txt", "--from", "xml"])
+                .is_err()
+        );
+    }
+}
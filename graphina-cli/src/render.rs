@@ -0,0 +1,237 @@
+/*!
+Standalone HTML rendering.
+
+The page embeds the graph's D3 node-link JSON (see `BaseGraph::to_d3_json`) and draws it
+with a D3 force layout loaded from a CDN, so it opens in any browser without a server.
+Nodes can be dragged, and the view pans and zooms.
+
+Per-node scores and community ids can ride along as [`Overlay`]s. A dropdown picks the one
+that colors the nodes, and clicking a node opens a panel with its label, degree, and value
+under every overlay.
+*/
+
+use graphina::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use serde_json::json;
+
+use crate::Result;
+
+const TEMPLATE: &str = r##"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>__TITLE__</title>
+<style>
+  html, body { margin: 0; height: 100%; font-family: sans-serif; }
+  svg { width: 100%; height: 100%; display: block; }
+  .link { stroke: #999; stroke-opacity: 0.6; }
+  .node { fill: #4c78a8; stroke: #fff; stroke-width: 1px; cursor: grab; }
+  .node.selected { stroke: #222; stroke-width: 2px; }
+  #info { position: absolute; top: 8px; left: 8px; color: #555; font-size: 13px; }
+  #color { margin-left: 8px; }
+  #legend { margin-top: 4px; }
+  #details { position: absolute; top: 8px; right: 8px; min-width: 160px; padding: 8px;
+    background: #fff; border: 1px solid #ccc; font-size: 13px; display: none; }
+  #details td { padding: 1px 6px 1px 0; }
+</style>
+</head>
+<body>
+<div id="info"><span id="summary"></span><select id="color"></select><div id="legend"></div></div>
+<div id="details"></div>
+<svg></svg>
+<script src="https://cdn.jsdelivr.net/npm/d3@7"></script>
+<script>
+const graph = __GRAPH__;
+const overlays = __OVERLAYS__;
+document.getElementById("summary").textContent =
+  `${graph.nodes.length} nodes, ${graph.links.length} edges` + (graph.directed ? ", directed" : "");
+const svg = d3.select("svg");
+const view = svg.append("g");
+svg.call(d3.zoom().on("zoom", (event) => view.attr("transform", event.transform)));
+if (graph.directed) {
+  svg.append("defs").append("marker")
+    .attr("id", "arrow").attr("viewBox", "0 -4 8 8").attr("refX", 14)
+    .attr("markerWidth", 6).attr("markerHeight", 6).attr("orient", "auto")
+    .append("path").attr("d", "M0,-4L8,0L0,4").attr("fill", "#999");
+}
+const link = view.append("g").selectAll("line").data(graph.links).join("line")
+  .attr("class", "link")
+  .attr("marker-end", graph.directed ? "url(#arrow)" : null);
+const node = view.append("g").selectAll("circle").data(graph.nodes).join("circle")
+  .attr("class", "node").attr("r", 5);
+node.append("title").text((d) => d.attr ?? d.id);
+const degree = new Map(graph.nodes.map((d) => [d.id, 0]));
+for (const l of graph.links) {
+  degree.set(l.source, degree.get(l.source) + 1);
+  degree.set(l.target, degree.get(l.target) + 1);
+}
+const select = d3.select("#color");
+select.style("display", overlays.length ? null : "none");
+select.selectAll("option").data(["none", ...overlays.map((o) => o.name)]).join("option")
+  .attr("value", (d, i) => i - 1).text((d) => d);
+function recolor() {
+  const overlay = overlays[+select.property("value")];
+  const legend = d3.select("#legend").text("");
+  if (!overlay) {
+    node.style("fill", null);
+    return;
+  }
+  const values = Object.values(overlay.values);
+  let scale;
+  if (overlay.categorical) {
+    scale = d3.scaleOrdinal(d3.schemeTableau10);
+    legend.text(`${new Set(values).size} groups`);
+  } else {
+    const [lo, hi] = d3.extent(values);
+    scale = d3.scaleSequential(d3.interpolateViridis).domain([lo ?? 0, hi > lo ? hi : (lo ?? 0) + 1]);
+    legend.text(`${d3.format(".4~g")(lo ?? 0)} to ${d3.format(".4~g")(hi ?? 0)}`);
+  }
+  node.style("fill", (d) => (d.id in overlay.values ? scale(overlay.values[d.id]) : "#ccc"));
+}
+select.on("change", recolor);
+function show(d) {
+  node.classed("selected", (n) => n === d);
+  const rows = [["id", d.id], ["label", d.attr ?? ""], ["degree", degree.get(d.id)]];
+  for (const o of overlays) {
+    const value = o.values[d.id];
+    rows.push([o.name, value === undefined ? "" : o.categorical ? value : d3.format(".6~g")(value)]);
+  }
+  const table = d3.select("#details").style("display", "block").text("").append("table");
+  table.selectAll("tr").data(rows).join("tr").selectAll("td").data((r) => r).join("td").text((v) => v);
+}
+node.on("click", (event, d) => { event.stopPropagation(); show(d); });
+svg.on("click", () => {
+  node.classed("selected", false);
+  d3.select("#details").style("display", "none");
+});
+const simulation = d3.forceSimulation(graph.nodes)
+  .force("link", d3.forceLink(graph.links).id((d) => d.id))
+  .force("charge", d3.forceManyBody().strength(-40))
+  .force("center", d3.forceCenter(window.innerWidth / 2, window.innerHeight / 2))
+  .on("tick", () => {
+    link.attr("x1", (d) => d.source.x).attr("y1", (d) => d.source.y)
+      .attr("x2", (d) => d.target.x).attr("y2", (d) => d.target.y);
+    node.attr("cx", (d) => d.x).attr("cy", (d) => d.y);
+  });
+node.call(d3.drag()
+  .on("start", (event, d) => { if (!event.active) simulation.alphaTarget(0.3).restart(); d.fx = d.x; d.fy = d.y; })
+  .on("drag", (event, d) => { d.fx = event.x; d.fy = event.y; })
+  .on("end", (event, d) => { if (!event.active) simulation.alphaTarget(0); d.fx = null; d.fy = null; }));
+</script>
+</body>
+</html>
+"##;
+
+/// Per-node values shown in the page, such as centrality scores or community ids.
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    /// Name shown in the coloring dropdown and the details panel.
+    pub name: String,
+    /// The value of each node; nodes without one are drawn in grey.
+    pub values: NodeMap<f64>,
+    /// Whether the values are labels, colored by a categorical palette, rather than
+    /// magnitudes on a continuous scale.
+    pub categorical: bool,
+}
+
+impl Overlay {
+    /// An overlay of scores, colored from the smallest value to the largest.
+    pub fn scores(name: &str, values: NodeMap<f64>) -> Self {
+        Self {
+            name: name.to_string(),
+            values,
+            categorical: false,
+        }
+    }
+
+    /// An overlay with one group per partition member, numbered in partition order.
+    pub fn communities(name: &str, communities: &[Vec<NodeId>]) -> Self {
+        let values = communities
+            .iter()
+            .enumerate()
+            .flat_map(|(c, members)| members.iter().map(move |&node| (node, c as f64)))
+            .collect();
+        Self {
+            name: name.to_string(),
+            values,
+            categorical: true,
+        }
+    }
+}
+
+/// Renders `graph` as a standalone HTML page titled `title`, with `overlays` selectable
+/// for coloring the nodes.
+pub fn html<Ty>(
+    graph: &BaseGraph<i32, f64, Ty>,
+    title: &str,
+    overlays: &[Overlay],
+) -> Result<String>
+where
+    Ty: GraphConstructor<i32, f64>,
+{
+    let overlays: Vec<serde_json::Value> = overlays
+        .iter()
+        .map(|overlay| {
+            // JSON has no NaN or infinity, so such values are left out like missing ones.
+            let values: serde_json::Map<String, serde_json::Value> = overlay
+                .values
+                .iter()
+                .filter(|(_, value)| value.is_finite())
+                .map(|(node, &value)| (node.index().to_string(), json!(value)))
+                .collect();
+            json!({
+                "name": overlay.name,
+                "categorical": overlay.categorical,
+                "values": values,
+            })
+        })
+        .collect();
+    // `</` would end the script element early, wherever it appears in the data.
+    let data = graph.to_d3_json()?.to_string().replace("</", "<\\/");
+    let overlays = serde_json::Value::from(overlays)
+        .to_string()
+        .replace("</", "<\\/");
+    let title = title
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;");
+    Ok(TEMPLATE
+        .replace("__TITLE__", &title)
+        .replace("__GRAPH__", &data)
+        .replace("__OVERLAYS__", &overlays))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use graphina::core::types::Digraph;
+
+    #[test]
+    fn test_html_embeds_graph_data() {
+        let mut g = Digraph::<i32, f64>::new();
+        let a = g.add_node(7);
+        let b = g.add_node(8);
+        g.add_edge(a, b, 1.5);
+
+        let scores: NodeMap<f64> = [(a, 0.25), (b, f64::NAN)].into_iter().collect();
+        let overlays = [
+            Overlay::scores("pagerank", scores),
+            Overlay::communities("louvain", &[vec![b], vec![a]]),
+        ];
+        let page = html(&g, "<triage>", &overlays).unwrap();
+        assert!(page.contains("<title>&lt;triage&gt;</title>"));
+        let embedded = |name: &str| -> serde_json::Value {
+            let start = page.find(name).unwrap() + name.len();
+            let end = start + page[start..].find(";\n").unwrap();
+            serde_json::from_str(&page[start..end]).unwrap()
+        };
+        assert_eq!(embedded("const graph = "), g.to_d3_json().unwrap());
+        assert_eq!(
+            embedded("const overlays = "),
+            json!([
+                {"name": "pagerank", "categorical": false, "values": {"0": 0.25}},
+                {"name": "louvain", "categorical": true, "values": {"0": 1.0, "1": 0.0}},
+            ])
+        );
+        assert!(!page.contains("__"));
+    }
+}
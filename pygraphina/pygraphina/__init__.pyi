@@ -22,11 +22,15 @@ from . import traversal as traversal
 __all__ = [
     "PyGraph",
     "PyDiGraph",
+    "PyAttrGraph",
     "Graph",
     "DiGraph",
+    "AttrGraph",
     "GraphinaError",
     "ConvergenceError",
     "NodeNotFoundError",
+    "GraphinaFeatureError",
+    "build_info",
     "erdos_renyi",
     "complete_graph",
     "bipartite",
@@ -69,6 +73,10 @@ __all__ = [
     "EdgeDataIterator",
     "DegreeView",
     "DegreeIterator",
+    "NodeStream",
+    "EdgeStream",
+    "AttrNodeView",
+    "AttrEdgeView",
 ]
 
 @final
@@ -173,6 +181,18 @@ class PyGraph:
         """
         ...
 
+    def remove_nodes(self, py_nodes: List[int]) -> int:
+        """Remove several nodes in place; raises ValueError without changes if any is missing."""
+        ...
+
+    def remove_edges(self, edges: List[Tuple[int, int]]) -> int:
+        """Remove one edge per (source, target) pair in place; atomic on error."""
+        ...
+
+    def retain_edges_above(self, threshold: float) -> int:
+        """Remove edges with weight <= threshold in place and return how many were removed."""
+        ...
+
     def update_edge_weight(self, source: int, target: int, new_weight: float) -> None:
         """Update the weight of an existing edge, raising an error if the edge doesn't exist."""
         ...
@@ -253,6 +273,28 @@ class PyGraph:
         """Compute shortest-path distances from start using Bellman-Ford, mapping each node to its distance (None if unreachable). Returns None on a negative cycle."""
         ...
 
+    def a_star(
+        self,
+        source: int,
+        target: int,
+        heuristic: Optional[Callable[[int], float]] = None,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> Optional[Tuple[float, List[int]]]:
+        """Shortest path by A* with an optional heuristic and edge-cost callback."""
+        ...
+
+    def all_simple_paths(
+        self, source: int, target: int, cutoff: Optional[int] = None
+    ) -> List[List[int]]:
+        """List every simple path between two nodes, optionally limited to cutoff edges."""
+        ...
+
+    def k_shortest_paths(
+        self, source: int, target: int, k: int
+    ) -> List[Tuple[float, List[int]]]:
+        """Rank the k cheapest loopless paths between two nodes, cheapest first."""
+        ...
+
     def floyd_warshall(self) -> Optional[Dict[int, Dict[int, Optional[float]]]]:
         """Compute all-pairs shortest-path distances using Floyd-Warshall. Returns None on a negative cycle."""
         ...
@@ -322,6 +364,24 @@ class PyGraph:
         """Get all node IDs within k hops of the start node (k of 0 returns just the start)."""
         ...
 
+    def weighted_ego_graph(
+        self,
+        center: int,
+        max_distance: float,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> "PyGraph":
+        """Extract the ego graph of all nodes within a total edge cost of the center."""
+        ...
+
+    def weighted_k_hop_neighbors(
+        self,
+        start: int,
+        max_distance: float,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> Dict[int, float]:
+        """Map every node within a total edge cost of the start node to its distance."""
+        ...
+
     def connected_component(self, start: int) -> List[int]:
         """Get the node IDs in the connected component containing the start node."""
         ...
@@ -384,6 +444,30 @@ class PyGraph:
         """Compute the degree assortativity coefficient."""
         ...
 
+    def degree_histogram(self) -> List[int]:
+        """Count nodes by degree; entry d is the number of nodes with degree d."""
+        ...
+
+    def weighted_degree_histogram(self, bins: int = 10) -> Tuple[List[int], List[float]]:
+        """Equal-width histogram of weighted degrees as (counts, bin_edges)."""
+        ...
+
+    def summary(self, clustering_samples: Optional[int] = None) -> Dict[str, Any]:
+        """Return size, density, degree, component, and clustering statistics in one dict."""
+        ...
+
+    def laplacian_spectrum(self, k: Optional[int] = None) -> List[float]:
+        """Return the k smallest Laplacian eigenvalues in ascending order (default: all)."""
+        ...
+
+    def adjacency_spectrum(self, k: Optional[int] = None) -> List[float]:
+        """Return the k largest adjacency eigenvalues in descending order (default: all)."""
+        ...
+
+    def fiedler_vector(self) -> Tuple[float, Dict[int, float]]:
+        """Return the algebraic connectivity and the Fiedler vector of a connected graph."""
+        ...
+
     @property
     def nodes(self) -> "NodeView":
         """Get a view of all nodes in the graph."""
@@ -399,6 +483,14 @@ class PyGraph:
         """Get a view of all edges in the graph as (source, target, weight) tuples."""
         ...
 
+    def iter_nodes(self) -> "NodeStream":
+        """Lazily iterate over (node_id, attr) tuples without building a list."""
+        ...
+
+    def iter_edges(self) -> "EdgeStream":
+        """Lazily iterate over (source, target, weight) tuples without building a list."""
+        ...
+
     def __len__(self) -> int:
         """Return the number of nodes in the graph."""
         ...
@@ -475,6 +567,18 @@ class PyDiGraph:
         """Update a node's attribute, raising an error if the node doesn't exist."""
         ...
 
+    def remove_nodes(self, py_nodes: List[int]) -> int:
+        """Remove several nodes in place; raises ValueError without changes if any is missing."""
+        ...
+
+    def remove_edges(self, edges: List[Tuple[int, int]]) -> int:
+        """Remove one edge per (source, target) pair in place; atomic on error."""
+        ...
+
+    def retain_edges_above(self, threshold: float) -> int:
+        """Remove edges with weight <= threshold in place and return how many were removed."""
+        ...
+
     def update_edge_weight(self, source: int, target: int, new_weight: float) -> None:
         """Update the weight of an existing edge, raising an error if the edge doesn't exist."""
         ...
@@ -551,6 +655,28 @@ class PyDiGraph:
         """Compute shortest-path distances from start using Bellman-Ford, mapping each node to its distance (None if unreachable). Returns None on a negative cycle."""
         ...
 
+    def a_star(
+        self,
+        source: int,
+        target: int,
+        heuristic: Optional[Callable[[int], float]] = None,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> Optional[Tuple[float, List[int]]]:
+        """Shortest path by A* with an optional heuristic and edge-cost callback."""
+        ...
+
+    def all_simple_paths(
+        self, source: int, target: int, cutoff: Optional[int] = None
+    ) -> List[List[int]]:
+        """List every simple path between two nodes, optionally limited to cutoff edges."""
+        ...
+
+    def k_shortest_paths(
+        self, source: int, target: int, k: int
+    ) -> List[Tuple[float, List[int]]]:
+        """Rank the k cheapest loopless paths between two nodes, cheapest first."""
+        ...
+
     def floyd_warshall(self) -> Optional[Dict[int, Dict[int, Optional[float]]]]:
         """Compute all-pairs shortest-path distances using Floyd-Warshall. Returns None on a negative cycle."""
         ...
@@ -621,6 +747,24 @@ class PyDiGraph:
         """Get all node IDs within k hops of the start node (k of 0 returns just the start)."""
         ...
 
+    def weighted_ego_graph(
+        self,
+        center: int,
+        max_distance: float,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> "PyDiGraph":
+        """Extract the ego graph of all nodes within a total edge cost of the center."""
+        ...
+
+    def weighted_k_hop_neighbors(
+        self,
+        start: int,
+        max_distance: float,
+        weight: Optional[Callable[[int, int, float], Optional[float]]] = None,
+    ) -> Dict[int, float]:
+        """Map every node within a total edge cost of the start node to its distance."""
+        ...
+
     def connected_component(self, start: int) -> List[int]:
         """Get the node IDs in the weakly connected component containing the start node."""
         ...
@@ -669,6 +813,18 @@ class PyDiGraph:
         """Compute the degree assortativity coefficient."""
         ...
 
+    def degree_histogram(self) -> List[int]:
+        """Count nodes by degree; entry d is the number of nodes with degree d."""
+        ...
+
+    def weighted_degree_histogram(self, bins: int = 10) -> Tuple[List[int], List[float]]:
+        """Equal-width histogram of weighted degrees as (counts, bin_edges)."""
+        ...
+
+    def summary(self, clustering_samples: Optional[int] = None) -> Dict[str, Any]:
+        """Return size, density, degree, component, and clustering statistics in one dict."""
+        ...
+
     @property
     def nodes(self) -> "NodeView":
         """Get a view of all nodes in the graph."""
@@ -684,6 +840,14 @@ class PyDiGraph:
         """Get a view of all edges in the graph as (source, target, weight) tuples."""
         ...
 
+    def iter_nodes(self) -> "NodeStream":
+        """Lazily iterate over (node_id, attr) tuples without building a list."""
+        ...
+
+    def iter_edges(self) -> "EdgeStream":
+        """Lazily iterate over (source, target, weight) tuples without building a list."""
+        ...
+
     def __len__(self) -> int:
         """Return the number of nodes in the graph."""
         ...
@@ -700,6 +864,146 @@ class PyDiGraph:
         """Return a string representation of the graph."""
         ...
 
+@final
+class PyAttrGraph:
+    """
+    An undirected graph with a dict of arbitrary attributes on every node and edge.
+
+    `g.nodes[n]` and `g.edges[u, v]` return the stored dicts, so assigning into them
+    changes the graph. There is at most one edge per pair of nodes. Use `to_graph()`
+    to run algorithms.
+    """
+
+    def __init__(self) -> None:
+        """Creates a new, empty graph."""
+        ...
+
+    def add_node(self, **attrs: Any) -> int:
+        """Add a node with the given attributes and return its ID."""
+        ...
+
+    def add_edge(self, source: int, target: int, **attrs: Any) -> int:
+        """Add an edge, or merge the attributes into the existing edge, and return its ID."""
+        ...
+
+    def remove_node(self, py_node: int) -> Dict[str, Any]:
+        """Remove a node and its edges, returning the node's attributes."""
+        ...
+
+    def remove_edge(self, source: int, target: int) -> Dict[str, Any]:
+        """Remove the edge between two nodes, returning its attributes."""
+        ...
+
+    def node_count(self) -> int:
+        """Get the number of nodes in the graph."""
+        ...
+
+    def edge_count(self) -> int:
+        """Get the number of edges in the graph."""
+        ...
+
+    def contains_node(self, py_node: int) -> bool:
+        """Check if a node exists in the graph."""
+        ...
+
+    def contains_edge(self, source: int, target: int) -> bool:
+        """Check if an edge exists between two nodes."""
+        ...
+
+    def neighbors(self, py_node: int) -> List[int]:
+        """Get the neighbors of a node, in ascending ID order."""
+        ...
+
+    @property
+    def nodes(self) -> "AttrNodeView":
+        """A view of the nodes, indexable by node ID."""
+        ...
+
+    @property
+    def edges(self) -> "AttrEdgeView":
+        """A view of the edges, indexable by (source, target)."""
+        ...
+
+    def to_graph(self, weight: str = "weight", default: float = 1.0) -> PyGraph:
+        """
+        Copy the structure into a PyGraph, keeping node IDs.
+
+        Args:
+            weight: The edge attribute holding the weight
+            default: The weight of edges without that attribute
+
+        Returns:
+            A PyGraph whose node attributes are all 0
+
+        Raises:
+            ValueError: If a weight is not a finite number
+        """
+        ...
+
+    def __len__(self) -> int:
+        """Get the number of nodes."""
+        ...
+
+    def __contains__(self, py_node: int) -> bool:
+        """Check if a node exists."""
+        ...
+
+    def __iter__(self) -> Iterator[int]:
+        """Iterate over node IDs in ascending order."""
+        ...
+
+    def __repr__(self) -> str:
+        """Return a string representation of the graph."""
+        ...
+
+@final
+class AttrNodeView:
+    """The nodes of a PyAttrGraph, mapping each node ID to its attribute dict."""
+
+    def __iter__(self) -> Iterator[int]:
+        """Iterate over node IDs."""
+        ...
+
+    def __len__(self) -> int:
+        """Get the number of nodes."""
+        ...
+
+    def __contains__(self, node: int, /) -> bool:
+        """Check if a node exists."""
+        ...
+
+    def __getitem__(self, node: int, /) -> Dict[str, Any]:
+        """Get the node's attribute dict; changes to it are kept."""
+        ...
+
+    def data(self, data: Any = None, default: Any = None) -> List[Tuple[int, Any]]:
+        """List (node, attrs) pairs, or (node, value) pairs for a single attribute."""
+        ...
+
+@final
+class AttrEdgeView:
+    """The edges of a PyAttrGraph, mapping each (source, target) pair to its attribute dict."""
+
+    def __iter__(self) -> Iterator[Tuple[int, int]]:
+        """Iterate over (source, target) pairs with source < target."""
+        ...
+
+    def __len__(self) -> int:
+        """Get the number of edges."""
+        ...
+
+    def __contains__(self, edge: Tuple[int, int], /) -> bool:
+        """Check if an edge exists, in either orientation."""
+        ...
+
+    def __getitem__(self, edge: Tuple[int, int], /) -> Dict[str, Any]:
+        """Get the edge's attribute dict; changes to it are kept."""
+        ...
+
+    def data(self, data: Any = None, default: Any = None) -> List[Tuple[int, int, Any]]:
+        """List (source, target, attrs) triples, or (source, target, value) for a single attribute."""
+        ...
+
 @final
 class NodeView:
     """A view for iterating over graph nodes."""
@@ -832,6 +1136,26 @@ class EdgeIterator:
     def __next__(self) -> Tuple[int, int, float]:
         ...
 
+@final
+class NodeStream:
+    """A lazy iterator over (node_id, attr) tuples, returned by iter_nodes()."""
+
+    def __iter__(self) -> "NodeStream":
+        ...
+
+    def __next__(self) -> Tuple[int, int]:
+        ...
+
+@final
+class EdgeStream:
+    """A lazy iterator over (source, target, weight) tuples, returned by iter_edges()."""
+
+    def __iter__(self) -> "EdgeStream":
+        ...
+
+    def __next__(self) -> Tuple[int, int, float]:
+        ...
+
 def from_networkx(nx_graph: Any) -> Union[PyGraph, PyDiGraph]:
     """
     Convert a NetworkX graph to PyGraphina.
@@ -844,6 +1168,17 @@ def from_networkx(nx_graph: Any) -> Union[PyGraph, PyDiGraph]:
     """
     ...
 
+def build_info() -> Dict[str, Any]:
+    """
+    Report how this build of PyGraphina was compiled.
+
+    Returns:
+        A dict with "version" (str), "features" (the optional Cargo features compiled in),
+        and "missing_features" (the optional features left out). Functions that need a
+        missing feature raise GraphinaFeatureError.
+    """
+    ...
+
 def to_networkx(obj: Union[PyGraph, PyDiGraph]) -> Any:
     """
     Convert a PyGraphina graph to NetworkX.
@@ -944,6 +1279,8 @@ Graph = PyGraph
 
 DiGraph = PyDiGraph
 
+AttrGraph = PyAttrGraph
+
 class GraphinaError(Exception):
     """Base exception for Graphina errors."""
     ...
@@ -955,3 +1292,7 @@ class ConvergenceError(GraphinaError):
 class NodeNotFoundError(GraphinaError):
     """Raised when a referenced node does not exist in the graph."""
     ...
+
+class GraphinaFeatureError(GraphinaError):
+    """Raised when a function needs an optional feature this build was compiled without."""
+    ...
@@ -14,6 +14,7 @@ __all__ = [
     "triangles_parallel",
     "clustering_coefficients_parallel",
     "shortest_paths_parallel",
+    "run_batch",
 ]
 
 
@@ -59,3 +60,14 @@ def shortest_paths_parallel(
 ) -> List[Dict[int, int]]:
     """Compute unweighted shortest path distances (hop counts) from multiple sources in parallel."""
     ...
+
+
+def run_batch(
+    graph: Union[PyGraph, PyDiGraph],
+    func_name: str,
+    nodes: Optional[List[int]] = None,
+    n_threads: Optional[int] = None,
+    distance: int = 1,
+) -> Dict[int, Union[int, float]]:
+    """Evaluate a named per-node algorithm over many nodes in parallel inside Rust."""
+    ...
@@ -5,6 +5,7 @@ use graphina::approximation::clique::{
 };
 use pyo3::prelude::*;
 
+/// Find a large clique using a greedy heuristic.
 #[pyfunction]
 pub fn max_clique(py_graph: &PyGraph) -> Vec<usize> {
     let clique = max_clique_core(&py_graph.graph);
@@ -14,6 +15,7 @@ pub fn max_clique(py_graph: &PyGraph) -> Vec<usize> {
         .collect()
 }
 
+/// Partition the graph into cliques by repeated greedy clique removal.
 #[pyfunction]
 pub fn clique_removal(py_graph: &PyGraph) -> Vec<Vec<usize>> {
     let cliques = clique_removal_core(&py_graph.graph);
@@ -28,6 +30,7 @@ pub fn clique_removal(py_graph: &PyGraph) -> Vec<Vec<usize>> {
         .collect()
 }
 
+/// Estimate the size of a large clique in the graph.
 #[pyfunction]
 pub fn large_clique_size(py_graph: &PyGraph) -> usize {
     large_clique_size_core(&py_graph.graph)
@@ -2,6 +2,7 @@ use crate::PyGraph;
 use graphina::approximation::clustering::average_clustering as average_clustering_core;
 use pyo3::prelude::*;
 
+/// Estimate the average clustering coefficient by sampling.
 #[pyfunction]
 pub fn average_clustering_approx(py_graph: &PyGraph) -> f64 {
     average_clustering_core(&py_graph.graph)
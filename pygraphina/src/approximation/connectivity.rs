@@ -4,6 +4,7 @@ use graphina::approximation::connectivity::local_node_connectivity as local_node
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 
+/// Approximate the local node connectivity between source and target.
 #[pyfunction]
 pub fn local_node_connectivity(
     py_graph: &PyGraph,
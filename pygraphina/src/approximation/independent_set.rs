@@ -2,6 +2,7 @@ use crate::PyGraph;
 use graphina::approximation::independent_set::maximum_independent_set as maximum_independent_set_core;
 use pyo3::prelude::*;
 
+/// Find a maximal independent set using a greedy heuristic.
 #[pyfunction]
 pub fn maximum_independent_set(py_graph: &PyGraph) -> Vec<usize> {
     let mis = maximum_independent_set_core(&py_graph.graph);
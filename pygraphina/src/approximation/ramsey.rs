@@ -2,6 +2,7 @@ use crate::PyGraph;
 use graphina::approximation::ramsey::ramsey_r2 as ramsey_r2_core;
 use pyo3::prelude::*;
 
+/// Return a clique and an independent set via the Ramsey R(2, t) heuristic.
 #[pyfunction]
 pub fn ramsey_r2(py_graph: &PyGraph) -> (Vec<usize>, Vec<usize>) {
     let (clique, independent_set) = ramsey_r2_core(&py_graph.graph);
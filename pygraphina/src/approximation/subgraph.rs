@@ -2,6 +2,7 @@ use crate::PyGraph;
 use graphina::approximation::subgraph::densest_subgraph as densest_subgraph_core;
 use pyo3::prelude::*;
 
+/// Find an approximately densest subgraph by greedy peeling.
 #[pyfunction]
 pub fn densest_subgraph(py_graph: &PyGraph) -> Vec<usize> {
     let subgraph = densest_subgraph_core(&py_graph.graph);
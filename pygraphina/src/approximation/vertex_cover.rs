@@ -6,6 +6,7 @@ use crate::PyGraph;
 use graphina::approximation::vertex_cover::min_weighted_vertex_cover as min_weighted_vertex_cover_core;
 use graphina::core::types::NodeId;
 
+/// Find a vertex cover using a greedy 2-approximation.
 #[pyfunction]
 pub fn min_weighted_vertex_cover(py_graph: &PyGraph) -> PyResult<Vec<usize>> {
     let cover: HashSet<NodeId> = min_weighted_vertex_cover_core(&py_graph.graph);
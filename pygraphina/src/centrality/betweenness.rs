@@ -42,7 +42,7 @@ pub fn betweenness(
             new_to_old.insert(*new, *old);
         }
 
-        match betweenness_centrality(&og, normalized) {
+        match py.detach(|| betweenness_centrality(&og, normalized)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
@@ -69,7 +69,7 @@ pub fn betweenness(
             new_to_old.insert(*new, *old);
         }
 
-        match betweenness_centrality(&og, normalized) {
+        match py.detach(|| betweenness_centrality(&og, normalized)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
@@ -118,6 +118,7 @@ pub fn betweenness(
 ///     If graph is not PyGraph or PyDiGraph.
 #[pyfunction]
 pub fn edge_betweenness(
+    py: Python<'_>,
     graph: &Bound<'_, PyAny>,
     normalized: bool,
 ) -> PyResult<HashMap<(usize, usize), f64>> {
@@ -129,7 +130,7 @@ pub fn edge_betweenness(
             new_to_old.insert(*new, *old);
         }
 
-        match edge_betweenness_centrality(&og, normalized) {
+        match py.detach(|| edge_betweenness_centrality(&og, normalized)) {
             Ok(map) => {
                 let mut out = HashMap::new();
                 for ((nu, nv), val) in map.into_iter() {
@@ -162,7 +163,7 @@ pub fn edge_betweenness(
             new_to_old.insert(*new, *old);
         }
 
-        match edge_betweenness_centrality(&og, normalized) {
+        match py.detach(|| edge_betweenness_centrality(&og, normalized)) {
             Ok(map) => {
                 let mut out = HashMap::new();
                 for ((nu, nv), val) in map.into_iter() {
@@ -34,7 +34,7 @@ pub fn closeness(py: Python<'_>, graph: &Bound<'_, PyAny>) -> PyResult<Py<PyDict
             new_to_old.insert(*new, *old);
         }
 
-        match closeness_centrality_parallel(&og) {
+        match py.detach(|| closeness_centrality_parallel(&og)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
@@ -61,7 +61,7 @@ pub fn closeness(py: Python<'_>, graph: &Bound<'_, PyAny>) -> PyResult<Py<PyDict
             new_to_old.insert(*new, *old);
         }
 
-        match closeness_centrality_parallel(&og) {
+        match py.detach(|| closeness_centrality_parallel(&og)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
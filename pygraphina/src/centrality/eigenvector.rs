@@ -34,7 +34,8 @@ pub fn eigenvector(
     tolerance: f64,
 ) -> PyResult<Py<PyDict>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
-        match eigenvector_centrality(&py_graph.graph, max_iter, tolerance) {
+        let g = &py_graph.graph;
+        match py.detach(|| eigenvector_centrality(g, max_iter, tolerance)) {
             Ok(map) => crate::nodemap_to_pydict(py, map, &py_graph.mapper),
             Err(e) => Err(crate::GraphinaError::new_err(format!(
                 "eigenvector failed: {}",
@@ -42,7 +43,8 @@ pub fn eigenvector(
             ))),
         }
     } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
-        match eigenvector_centrality(&py_graph.graph, max_iter, tolerance) {
+        let g = &py_graph.graph;
+        match py.detach(|| eigenvector_centrality(g, max_iter, tolerance)) {
             Ok(map) => crate::nodemap_to_pydict(py, map, &py_graph.mapper),
             Err(e) => Err(crate::GraphinaError::new_err(format!(
                 "eigenvector failed: {}",
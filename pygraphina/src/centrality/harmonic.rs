@@ -34,7 +34,7 @@ pub fn harmonic(py: Python<'_>, graph: &Bound<'_, PyAny>) -> PyResult<Py<PyDict>
             new_to_old.insert(*new, *old);
         }
 
-        match harmonic_centrality(&og) {
+        match py.detach(|| harmonic_centrality(&og)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
@@ -61,7 +61,7 @@ pub fn harmonic(py: Python<'_>, graph: &Bound<'_, PyAny>) -> PyResult<Py<PyDict>
             new_to_old.insert(*new, *old);
         }
 
-        match harmonic_centrality(&og) {
+        match py.detach(|| harmonic_centrality(&og)) {
             Ok(map) => crate::f64_entries_to_pydict(py, map, |new_nid| {
                 let old_nid = new_to_old.get(&new_nid).ok_or_else(|| {
                     crate::GraphinaError::new_err("missing mapping back to original node")
@@ -38,11 +38,15 @@ pub fn katz(
 ) -> PyResult<Py<PyDict>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
         // We don't support a beta callback from Python; pass None
-        let res = katz_centrality(&py_graph.graph, alpha, None, max_iter, tolerance)
+        let g = &py_graph.graph;
+        let res = py
+            .detach(|| katz_centrality(g, alpha, None, max_iter, tolerance))
             .map_err(|e| crate::GraphinaError::new_err(format!("Katz centrality failed: {}", e)))?;
         crate::nodemap_to_pydict(py, res, &py_graph.mapper)
     } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
-        let res = katz_centrality(&py_graph.graph, alpha, None, max_iter, tolerance)
+        let g = &py_graph.graph;
+        let res = py
+            .detach(|| katz_centrality(g, alpha, None, max_iter, tolerance))
             .map_err(|e| crate::GraphinaError::new_err(format!("Katz centrality failed: {}", e)))?;
         crate::nodemap_to_pydict(py, res, &py_graph.mapper)
     } else {
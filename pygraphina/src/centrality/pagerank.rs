@@ -56,7 +56,9 @@ pub fn pagerank(
             None
         };
 
-        let res = pagerank_core(&g.graph, damping, max_iter, tolerance, nstart_map.as_ref())
+        let graph = &g.graph;
+        let res = py
+            .detach(|| pagerank_core(graph, damping, max_iter, tolerance, nstart_map.as_ref()))
             .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
         crate::nodemap_to_pydict(py, res, &g.mapper)
     } else if let Ok(g) = graph.extract::<PyRef<PyDiGraph>>() {
@@ -72,7 +74,9 @@ pub fn pagerank(
             None
         };
 
-        let res = pagerank_core(&g.graph, damping, max_iter, tolerance, nstart_map.as_ref())
+        let graph = &g.graph;
+        let res = py
+            .detach(|| pagerank_core(graph, damping, max_iter, tolerance, nstart_map.as_ref()))
             .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
         crate::nodemap_to_pydict(py, res, &g.mapper)
     } else {
@@ -128,14 +132,20 @@ pub fn personalized_pagerank(
     let _ = nstart;
 
     if let Ok(g) = graph.extract::<PyRef<PyGraph>>() {
-        let res =
-            personalized_pagerank_core(&g.graph, personalization, damping, tolerance, max_iter)
-                .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
+        let graph = &g.graph;
+        let res = py
+            .detach(|| {
+                personalized_pagerank_core(graph, personalization, damping, tolerance, max_iter)
+            })
+            .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
         crate::nodemap_to_pydict(py, res, &g.mapper)
     } else if let Ok(g) = graph.extract::<PyRef<PyDiGraph>>() {
-        let res =
-            personalized_pagerank_core(&g.graph, personalization, damping, tolerance, max_iter)
-                .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
+        let graph = &g.graph;
+        let res = py
+            .detach(|| {
+                personalized_pagerank_core(graph, personalization, damping, tolerance, max_iter)
+            })
+            .map_err(|e| crate::GraphinaError::new_err(e.to_string()))?;
         crate::nodemap_to_pydict(py, res, &g.mapper)
     } else {
         Err(pyo3::exceptions::PyTypeError::new_err(
@@ -29,12 +29,15 @@ use std::collections::HashMap;
 #[pyfunction]
 #[pyo3(signature = (graph, distance))]
 pub fn local_reaching_centrality(
+    py: Python<'_>,
     graph: &Bound<'_, PyAny>,
     distance: usize,
 ) -> PyResult<HashMap<usize, f64>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
-        let centrality =
-            local_reaching_centrality_core(&py_graph.graph, distance).map_err(|e| {
+        let g = &py_graph.graph;
+        let centrality = py
+            .detach(|| local_reaching_centrality_core(g, distance))
+            .map_err(|e| {
                 crate::GraphinaError::new_err(format!(
                     "Failed to compute local reaching centrality: {}",
                     e
@@ -51,8 +54,10 @@ pub fn local_reaching_centrality(
             })
             .collect())
     } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
-        let centrality =
-            local_reaching_centrality_core(&py_graph.graph, distance).map_err(|e| {
+        let g = &py_graph.graph;
+        let centrality = py
+            .detach(|| local_reaching_centrality_core(g, distance))
+            .map_err(|e| {
                 crate::GraphinaError::new_err(format!(
                     "Failed to compute local reaching centrality: {}",
                     e
@@ -110,14 +115,20 @@ pub fn local_reaching_centrality(
 /// TypeError
 ///     If graph is not PyGraph or PyDiGraph.
 #[pyfunction]
-pub fn global_reaching_centrality(graph: &Bound<'_, PyAny>) -> PyResult<HashMap<usize, f64>> {
+pub fn global_reaching_centrality(
+    py: Python<'_>,
+    graph: &Bound<'_, PyAny>,
+) -> PyResult<HashMap<usize, f64>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
-        let centrality = global_reaching_centrality_core(&py_graph.graph).map_err(|e| {
-            crate::GraphinaError::new_err(format!(
-                "Failed to compute global reaching centrality: {}",
-                e
-            ))
-        })?;
+        let g = &py_graph.graph;
+        let centrality = py
+            .detach(|| global_reaching_centrality_core(g))
+            .map_err(|e| {
+                crate::GraphinaError::new_err(format!(
+                    "Failed to compute global reaching centrality: {}",
+                    e
+                ))
+            })?;
         Ok(centrality
             .into_iter()
             .filter_map(|(node_id, score)| {
@@ -129,12 +140,15 @@ pub fn global_reaching_centrality(graph: &Bound<'_, PyAny>) -> PyResult<HashMap<
             })
             .collect())
     } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
-        let centrality = global_reaching_centrality_core(&py_graph.graph).map_err(|e| {
-            crate::GraphinaError::new_err(format!(
-                "Failed to compute global reaching centrality: {}",
-                e
-            ))
-        })?;
+        let g = &py_graph.graph;
+        let centrality = py
+            .detach(|| global_reaching_centrality_core(g))
+            .map_err(|e| {
+                crate::GraphinaError::new_err(format!(
+                    "Failed to compute global reaching centrality: {}",
+                    e
+                ))
+            })?;
         Ok(centrality
             .into_iter()
             .filter_map(|(node_id, score)| {
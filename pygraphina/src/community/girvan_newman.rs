@@ -23,8 +23,12 @@ use pyo3::prelude::*;
 /// TypeError
 ///     If graph is not PyGraph.
 #[pyfunction]
-pub fn girvan_newman(py_graph: &PyGraph, target_communities: usize) -> PyResult<Vec<Vec<usize>>> {
-    match girvan_newman_core(&py_graph.graph, target_communities) {
+pub fn girvan_newman(
+    py: Python<'_>,
+    py_graph: &PyGraph,
+    target_communities: usize,
+) -> PyResult<Vec<Vec<usize>>> {
+    match py.detach(|| girvan_newman_core(&py_graph.graph, target_communities)) {
         Ok(communities) => Ok(communities
             .into_iter()
             .map(|community| {
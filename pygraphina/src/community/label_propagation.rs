@@ -28,11 +28,12 @@ use std::collections::HashMap;
 #[pyfunction]
 #[pyo3(signature = (py_graph, max_iter, seed=None))]
 pub fn label_propagation(
+    py: Python<'_>,
     py_graph: &PyGraph,
     max_iter: usize,
     seed: Option<u64>,
 ) -> PyResult<HashMap<usize, usize>> {
-    match label_propagation_core(&py_graph.graph, max_iter, seed) {
+    match py.detach(|| label_propagation_core(&py_graph.graph, max_iter, seed)) {
         Ok(labels) => {
             let mut result = HashMap::new();
             for (py_id, internal_id) in &py_graph.mapper.py_to_internal {
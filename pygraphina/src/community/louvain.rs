@@ -24,8 +24,8 @@ use pyo3::prelude::*;
 ///     If graph is not PyGraph.
 #[pyfunction]
 #[pyo3(signature = (py_graph, seed=None))]
-pub fn louvain(py_graph: &PyGraph, seed: Option<u64>) -> PyResult<Vec<Vec<usize>>> {
-    match louvain_core(&py_graph.graph, seed) {
+pub fn louvain(py: Python<'_>, py_graph: &PyGraph, seed: Option<u64>) -> PyResult<Vec<Vec<usize>>> {
+    match py.detach(|| louvain_core(&py_graph.graph, seed)) {
         Ok(communities) => Ok(communities
             .into_iter()
             .map(|community| {
@@ -27,11 +27,12 @@ use pyo3::prelude::*;
 #[pyfunction]
 #[pyo3(signature = (py_graph, k, seed=None))]
 pub fn spectral_clustering(
+    py: Python<'_>,
     py_graph: &PyGraph,
     k: usize,
     seed: Option<u64>,
 ) -> PyResult<Vec<Vec<usize>>> {
-    match spectral_clustering_core(&py_graph.graph, k, seed) {
+    match py.detach(|| spectral_clustering_core(&py_graph.graph, k, seed)) {
         Ok(communities) => Ok(communities
             .into_iter()
             .map(|community| {
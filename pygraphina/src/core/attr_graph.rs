@@ -0,0 +1,462 @@
+//! PyAttrGraph - an undirected graph with arbitrary Python attributes on nodes and edges.
+
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+use crate::core::graph::PyGraph;
+use crate::core::id_map::IdMapper;
+use graphina::core::types::{BaseGraph, EdgeId, NodeId, Undirected};
+
+/// An undirected graph whose nodes and edges each carry a dict of Python attributes.
+///
+/// `g.nodes[n]` and `g.edges[u, v]` return the stored dicts themselves, so
+/// `g.nodes[n]["color"] = "red"` changes the graph. As in NetworkX, there is at most one
+/// edge per pair of nodes, and adding it again updates its attributes. Algorithms run on
+/// the `PyGraph` returned by `to_graph`, which keeps the node IDs and reads each edge's
+/// weight from one of its attributes.
+#[pyclass]
+pub struct PyAttrGraph {
+    pub(crate) graph: BaseGraph<Py<PyDict>, Py<PyDict>, Undirected>,
+    pub(crate) mapper: IdMapper,
+}
+
+impl Default for PyAttrGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[pymethods]
+impl PyAttrGraph {
+    /// Creates a new, empty graph.
+    #[new]
+    pub fn new() -> Self {
+        PyAttrGraph {
+            graph: BaseGraph::new(),
+            mapper: IdMapper::new(),
+        }
+    }
+
+    /// Add a node with the given keyword attributes.
+    ///
+    /// Parameters
+    /// ----------
+    /// **attrs
+    ///     Attributes stored in the node's dict
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The node ID (non-negative integer, starts from 0)
+    ///
+    /// Examples
+    /// --------
+    /// >>> g = PyAttrGraph()
+    /// >>> n = g.add_node(color="red")
+    /// >>> g.nodes[n]["color"]
+    /// 'red'
+    #[pyo3(signature = (**attrs))]
+    pub fn add_node(
+        &mut self,
+        py: Python<'_>,
+        attrs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<usize> {
+        let dict = copy_attrs(py, attrs)?;
+        let internal = self.graph.add_node(dict);
+        Ok(self.mapper.add(internal))
+    }
+
+    /// Add an edge between two nodes, or update its attributes if it already exists.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The first node ID
+    /// target : int
+    ///     The second node ID
+    /// **attrs
+    ///     Attributes stored in, or merged into, the edge's dict
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The edge ID
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist
+    #[pyo3(signature = (source, target, **attrs))]
+    pub fn add_edge(
+        &mut self,
+        py: Python<'_>,
+        source: usize,
+        target: usize,
+        attrs: Option<&Bound<'_, PyDict>>,
+    ) -> PyResult<usize> {
+        let (u, v) = (self.internal(source)?, self.internal(target)?);
+        if let Some(edge) = self.graph.find_edge(u, v) {
+            if let (Some(existing), Some(attrs)) = (self.graph.edge_weight(edge), attrs) {
+                existing.bind(py).update(attrs.as_mapping())?;
+            }
+            return Ok(edge.index());
+        }
+        let dict = copy_attrs(py, attrs)?;
+        Ok(self.graph.add_edge(u, v, dict).index())
+    }
+
+    /// Remove a node and its edges.
+    ///
+    /// Parameters
+    /// ----------
+    /// py_node : int
+    ///     The node ID to remove
+    ///
+    /// Returns
+    /// -------
+    /// dict
+    ///     The node's attributes
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If the node doesn't exist
+    pub fn remove_node(&mut self, py_node: usize) -> PyResult<Py<PyDict>> {
+        let internal = self.internal(py_node)?;
+        let attrs = self
+            .graph
+            .try_remove_node(internal)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        self.mapper.remove_by_py_id(py_node);
+        Ok(attrs)
+    }
+
+    /// Remove the edge between two nodes.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The first node ID
+    /// target : int
+    ///     The second node ID
+    ///
+    /// Returns
+    /// -------
+    /// dict
+    ///     The edge's attributes
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node or the edge doesn't exist
+    pub fn remove_edge(&mut self, source: usize, target: usize) -> PyResult<Py<PyDict>> {
+        let edge = self.edge(source, target)?.ok_or_else(|| {
+            PyValueError::new_err(format!("No edge between {} and {}", source, target))
+        })?;
+        self.graph
+            .try_remove_edge(edge)
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Get the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Get the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Check if a node exists in the graph.
+    pub fn contains_node(&self, py_node: usize) -> bool {
+        self.mapper.contains_py(py_node)
+    }
+
+    /// Check if an edge exists between two nodes; False if either node doesn't exist.
+    pub fn contains_edge(&self, source: usize, target: usize) -> bool {
+        matches!(self.edge(source, target), Ok(Some(_)))
+    }
+
+    /// Get the neighbors of a node, in ascending ID order.
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If the node doesn't exist
+    pub fn neighbors(&self, py_node: usize) -> PyResult<Vec<usize>> {
+        let internal = self.internal(py_node)?;
+        let mut neighbors: Vec<usize> = self
+            .graph
+            .neighbors(internal)
+            .filter_map(|nid| self.mapper.get_py(nid))
+            .collect();
+        neighbors.sort_unstable();
+        neighbors.dedup();
+        Ok(neighbors)
+    }
+
+    /// Get a view of the nodes, indexable by node ID to reach each node's attributes.
+    #[getter]
+    pub fn nodes(slf: PyRef<'_, Self>) -> AttrNodeView {
+        AttrNodeView { graph: slf.into() }
+    }
+
+    /// Get a view of the edges, indexable by `(source, target)` to reach each edge's
+    /// attributes.
+    #[getter]
+    pub fn edges(slf: PyRef<'_, Self>) -> AttrEdgeView {
+        AttrEdgeView { graph: slf.into() }
+    }
+
+    /// Copy the structure into a PyGraph for running algorithms.
+    ///
+    /// Node IDs are kept, and every node's integer attribute is 0. Each edge's weight is
+    /// its `weight` attribute, or `default` when the edge has none.
+    ///
+    /// Parameters
+    /// ----------
+    /// weight : str, optional
+    ///     The edge attribute holding the weight (default: "weight")
+    /// default : float, optional
+    ///     The weight of edges without that attribute (default: 1.0)
+    ///
+    /// Returns
+    /// -------
+    /// PyGraph
+    ///     A graph with the same nodes and edges
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If a weight is not a finite number
+    #[pyo3(signature = (weight="weight", default=1.0))]
+    pub fn to_graph(&self, py: Python<'_>, weight: &str, default: f64) -> PyResult<PyGraph> {
+        let mut out = PyGraph::new();
+        for (py_id, _) in self.sorted_nodes() {
+            let internal = out.graph.add_node(0);
+            out.mapper.add_with_id(internal, py_id);
+        }
+        for (u, v, attrs) in self.graph.edges() {
+            let value = match attrs.bind(py).get_item(weight)? {
+                Some(value) => value.extract::<f64>()?,
+                None => default,
+            };
+            let (Some(pu), Some(pv)) = (self.mapper.get_py(u), self.mapper.get_py(v)) else {
+                continue;
+            };
+            out.add_edge_impl(pu, pv, value)?;
+        }
+        Ok(out)
+    }
+
+    fn __len__(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn __contains__(&self, py_node: usize) -> bool {
+        self.mapper.contains_py(py_node)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let ids: Vec<usize> = self.sorted_nodes().into_iter().map(|(id, _)| id).collect();
+        Ok(PyList::new(py, ids)?.try_iter()?.into_any().unbind())
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyAttrGraph(nodes={}, edges={})",
+            self.graph.node_count(),
+            self.graph.edge_count()
+        )
+    }
+}
+
+// Internal helpers (not exposed to Python)
+impl PyAttrGraph {
+    fn internal(&self, py_node: usize) -> PyResult<NodeId> {
+        self.mapper
+            .get_internal(py_node)
+            .ok_or_else(|| PyValueError::new_err(format!("Invalid node id: {}", py_node)))
+    }
+
+    fn edge(&self, source: usize, target: usize) -> PyResult<Option<EdgeId>> {
+        let (u, v) = (self.internal(source)?, self.internal(target)?);
+        Ok(self.graph.find_edge(u, v))
+    }
+
+    /// Public IDs with their internal IDs, in ascending public ID order.
+    fn sorted_nodes(&self) -> Vec<(usize, NodeId)> {
+        let mut nodes: Vec<(usize, NodeId)> = self
+            .graph
+            .node_ids()
+            .filter_map(|nid| self.mapper.get_py(nid).map(|py_id| (py_id, nid)))
+            .collect();
+        nodes.sort_unstable_by_key(|&(py_id, _)| py_id);
+        nodes
+    }
+
+    /// Edges as `(source, target, attrs)` with public IDs, sorted by their endpoints.
+    fn sorted_edges(&self, py: Python<'_>) -> Vec<(usize, usize, Py<PyDict>)> {
+        let mut edges: Vec<(usize, usize, Py<PyDict>)> = self
+            .graph
+            .edges()
+            .filter_map(|(u, v, attrs)| {
+                let (pu, pv) = (self.mapper.get_py(u)?, self.mapper.get_py(v)?);
+                Some((pu.min(pv), pu.max(pv), attrs.clone_ref(py)))
+            })
+            .collect();
+        edges.sort_unstable_by_key(|&(u, v, _)| (u, v));
+        edges
+    }
+}
+
+/// A fresh dict holding the keyword attributes, so the caller's dict is never shared.
+fn copy_attrs(py: Python<'_>, attrs: Option<&Bound<'_, PyDict>>) -> PyResult<Py<PyDict>> {
+    match attrs {
+        Some(attrs) => Ok(attrs.copy()?.unbind()),
+        None => Ok(PyDict::new(py).unbind()),
+    }
+}
+
+/// Looks up `key` in `attrs`, or returns all of `attrs` when `key` is None.
+fn select(
+    py: Python<'_>,
+    attrs: &Py<PyDict>,
+    key: Option<&Bound<'_, PyAny>>,
+    default: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Py<PyAny>> {
+    let Some(key) = key else {
+        return Ok(attrs.clone_ref(py).into_any());
+    };
+    match attrs.bind(py).get_item(key)? {
+        Some(value) => Ok(value.unbind()),
+        None => Ok(default.map_or_else(|| py.None(), |d| d.clone().unbind())),
+    }
+}
+
+/// The nodes of a PyAttrGraph, with `view[n]` giving node `n`'s attribute dict.
+#[pyclass]
+pub struct AttrNodeView {
+    graph: Py<PyAttrGraph>,
+}
+
+#[pymethods]
+impl AttrNodeView {
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.graph.borrow(py).graph.node_count()
+    }
+
+    fn __contains__(&self, py: Python<'_>, node: usize) -> bool {
+        self.graph.borrow(py).mapper.contains_py(node)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        self.graph.borrow(py).__iter__(py)
+    }
+
+    fn __getitem__(&self, py: Python<'_>, node: usize) -> PyResult<Py<PyDict>> {
+        let graph = self.graph.borrow(py);
+        graph
+            .mapper
+            .get_internal(node)
+            .and_then(|nid| graph.graph.node_attr(nid))
+            .map(|attrs| attrs.clone_ref(py))
+            .ok_or_else(|| PyKeyError::new_err(format!("Node {} not found", node)))
+    }
+
+    /// List `(node, attrs)` pairs, or `(node, value)` pairs for a single attribute `data`,
+    /// using `default` where a node lacks it.
+    #[pyo3(signature = (data=None, default=None))]
+    fn data(
+        &self,
+        py: Python<'_>,
+        data: Option<&Bound<'_, PyAny>>,
+        default: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<(usize, Py<PyAny>)>> {
+        let graph = self.graph.borrow(py);
+        graph
+            .sorted_nodes()
+            .into_iter()
+            .filter_map(|(id, nid)| graph.graph.node_attr(nid).map(|attrs| (id, attrs)))
+            .map(|(id, attrs)| Ok((id, select(py, attrs, data, default)?)))
+            .collect()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> String {
+        let ids: Vec<String> = self
+            .graph
+            .borrow(py)
+            .sorted_nodes()
+            .iter()
+            .map(|(id, _)| id.to_string())
+            .collect();
+        format!("AttrNodeView(({}))", ids.join(", "))
+    }
+}
+
+/// The edges of a PyAttrGraph, with `view[u, v]` giving that edge's attribute dict.
+#[pyclass]
+pub struct AttrEdgeView {
+    graph: Py<PyAttrGraph>,
+}
+
+#[pymethods]
+impl AttrEdgeView {
+    fn __len__(&self, py: Python<'_>) -> usize {
+        self.graph.borrow(py).graph.edge_count()
+    }
+
+    fn __contains__(&self, py: Python<'_>, edge: (usize, usize)) -> bool {
+        self.graph.borrow(py).contains_edge(edge.0, edge.1)
+    }
+
+    fn __iter__(&self, py: Python<'_>) -> PyResult<Py<PyAny>> {
+        let pairs: Vec<(usize, usize)> = self
+            .graph
+            .borrow(py)
+            .sorted_edges(py)
+            .into_iter()
+            .map(|(u, v, _)| (u, v))
+            .collect();
+        Ok(PyList::new(py, pairs)?.try_iter()?.into_any().unbind())
+    }
+
+    fn __getitem__(&self, py: Python<'_>, edge: (usize, usize)) -> PyResult<Py<PyDict>> {
+        let graph = self.graph.borrow(py);
+        let missing = || PyKeyError::new_err(format!("Edge {:?} not found", edge));
+        let id = graph.edge(edge.0, edge.1).map_err(|_| missing())?;
+        id.and_then(|id| graph.graph.edge_weight(id))
+            .map(|attrs| attrs.clone_ref(py))
+            .ok_or_else(missing)
+    }
+
+    /// List `(source, target, attrs)` triples, or `(source, target, value)` triples for a
+    /// single attribute `data`, using `default` where an edge lacks it.
+    #[pyo3(signature = (data=None, default=None))]
+    fn data(
+        &self,
+        py: Python<'_>,
+        data: Option<&Bound<'_, PyAny>>,
+        default: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Vec<(usize, usize, Py<PyAny>)>> {
+        self.graph
+            .borrow(py)
+            .sorted_edges(py)
+            .into_iter()
+            .map(|(u, v, attrs)| Ok((u, v, select(py, &attrs, data, default)?)))
+            .collect()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> String {
+        let pairs: Vec<String> = self
+            .graph
+            .borrow(py)
+            .sorted_edges(py)
+            .iter()
+            .map(|(u, v, _)| format!("({}, {})", u, v))
+            .collect();
+        format!("AttrEdgeView([{}])", pairs.join(", "))
+    }
+}
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use crate::core::views::degree::DegreeView;
 use crate::core::views::edge::EdgeView;
 use crate::core::views::node::NodeView;
+use crate::core::views::stream::{EdgeStream, GraphHandle, NodeStream};
 use graphina::core::types::{Digraph, NodeId};
 
 /// A Python-accessible DiGraph class wrapping Graphina's core directed graph.
@@ -38,6 +39,7 @@ impl PyDiGraph {
     }
 
     // Basic node operations
+    /// Add a node with an integer attribute to the directed graph.
     pub fn add_node(&mut self, attr: i64) -> usize {
         let nid = self.graph.add_node(attr);
         self.mapper.add(nid)
@@ -50,17 +52,21 @@ impl PyDiGraph {
     pub fn remove_node(&mut self, py_node: usize) -> PyResult<i64> {
         self.try_remove_node_impl(py_node)
     }
+    /// Get the attribute value of a node.
     pub fn get_node_attr(&self, py_node: usize) -> Option<i64> {
         self.get_node_attr_impl(py_node)
     }
+    /// Check if a node exists in the graph.
     pub fn contains_node(&self, py_node: usize) -> bool {
         self.contains_node_impl(py_node)
     }
+    /// Remove all nodes and edges from the graph.
     pub fn clear(&mut self) {
         self.clear_impl()
     }
 
     // Edge operations
+    /// Add a directed edge from source to target with a weight.
     pub fn add_edge(&mut self, source: usize, target: usize, weight: f64) -> PyResult<usize> {
         // Validate weight is finite
         if !weight.is_finite() {
@@ -86,6 +92,7 @@ impl PyDiGraph {
         self.try_remove_edge_impl(source, target)
     }
 
+    /// Get the weight of an edge, or None if it doesn't exist.
     pub fn get_edge_weight(&self, source: usize, target: usize) -> PyResult<Option<f64>> {
         self.get_edge_weight_impl(source, target)
     }
@@ -100,6 +107,69 @@ impl PyDiGraph {
         self.try_update_edge_weight_impl(source, target, new_weight)
     }
 
+    /// Remove several nodes (and their incident edges) in place.
+    ///
+    /// Parameters
+    /// ----------
+    /// py_nodes : list of int
+    ///     Node IDs to remove; duplicates are ignored
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of nodes removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If any node doesn't exist; the graph is left unchanged
+    pub fn remove_nodes(&mut self, py_nodes: Vec<usize>) -> PyResult<usize> {
+        self.remove_nodes_impl(py_nodes)
+    }
+
+    /// Remove several edges in place, one per ``(source, target)`` pair.
+    ///
+    /// Parameters
+    /// ----------
+    /// edges : list of tuple of (int, int)
+    ///     Node pairs identifying the edges to remove
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of edges removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If any node or edge doesn't exist; the graph is left unchanged
+    pub fn remove_edges(&mut self, edges: Vec<(usize, usize)>) -> PyResult<usize> {
+        self.remove_edges_impl(edges)
+    }
+
+    /// Keep only edges heavier than a threshold, removing the rest in place.
+    ///
+    /// Unlike ``filter_edges``, this does not copy the graph, and node IDs are unchanged.
+    ///
+    /// Parameters
+    /// ----------
+    /// threshold : float
+    ///     Edges with weight less than or equal to this value are removed
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of edges removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If threshold is NaN
+    pub fn retain_edges_above(&mut self, threshold: f64) -> PyResult<usize> {
+        self.retain_edges_above_impl(threshold)
+    }
+
+    /// Check if a directed edge exists from source to target.
     pub fn contains_edge(&self, source: usize, target: usize) -> PyResult<bool> {
         let src = self
             .mapper
@@ -113,12 +183,14 @@ impl PyDiGraph {
     }
 
     // Node queries
+    /// Get a view of all nodes in the graph.
     #[getter]
     pub fn nodes(slf: PyRef<'_, Self>) -> PyResult<NodeView> {
         let py = slf.py();
         Ok(NodeView::new(slf.into_pyobject(py)?.into_any().unbind()))
     }
 
+    /// Get the neighbors of a node.
     pub fn neighbors(&self, py_node: usize) -> PyResult<Vec<usize>> {
         let nid = self
             .mapper
@@ -132,16 +204,19 @@ impl PyDiGraph {
     }
 
     // Edges view
+    /// Get a view of all edges in the graph as (source, target, weight) tuples.
     #[getter]
     pub fn edges(slf: PyRef<'_, Self>) -> PyResult<EdgeView> {
         let py = slf.py();
         Ok(EdgeView::new(slf.into_pyobject(py)?.into_any().unbind()))
     }
 
+    /// Get nodes that this node has edges pointing to.
     pub fn out_neighbors(&self, py_node: usize) -> PyResult<Vec<usize>> {
         self.out_neighbors_impl(py_node)
     }
 
+    /// Get nodes that have edges pointing to this node.
     pub fn in_neighbors(&self, py_node: usize) -> PyResult<Vec<usize>> {
         self.in_neighbors_impl(py_node)
     }
@@ -151,58 +226,73 @@ impl PyDiGraph {
         self.degree_impl(py_node) // Helper that sums in/out
     }
 
+    /// Get a view for accessing total node degrees (in + out).
     #[getter]
     pub fn degree(slf: PyRef<'_, Self>) -> PyResult<DegreeView> {
         let py = slf.py();
         Ok(DegreeView::new(slf.into_pyobject(py)?.into_any().unbind()))
     }
 
+    /// Get the in-degree (number of incoming edges) of a node.
     pub fn in_degree(&self, py_node: usize) -> Option<usize> {
         self.in_degree_impl(py_node)
     }
 
+    /// Get the out-degree (number of outgoing edges) of a node.
     pub fn out_degree(&self, py_node: usize) -> Option<usize> {
         self.out_degree_impl(py_node)
     }
 
     // Stats
+    /// Get the number of nodes in the graph.
     pub fn node_count(&self) -> usize {
         self.graph.node_count()
     }
+    /// Get the number of edges in the graph.
     pub fn edge_count(&self) -> usize {
         self.graph.edge_count()
     }
+    /// Calculate the density of the graph.
     pub fn density(&self) -> f64 {
         self.graph.density()
     }
+    /// Check if the graph is directed (always True for PyDiGraph).
     pub fn is_directed(&self) -> bool {
         self.graph.is_directed()
     }
+    /// Check whether the graph has no nodes.
     pub fn is_empty(&self) -> bool {
         self.graph.is_empty()
     }
 
     // Validation
+    /// Check whether the graph is connected.
     pub fn is_connected(&self) -> bool {
         self.is_connected_impl()
     }
+    /// Check whether any edge has a negative weight.
     pub fn has_negative_weights(&self) -> bool {
         self.has_negative_weights_impl()
     }
+    /// Check whether any node has an edge to itself.
     pub fn has_self_loops(&self) -> bool {
         self.has_self_loops_impl()
     }
+    /// Check whether the graph is bipartite.
     pub fn is_bipartite(&self) -> bool {
         self.is_bipartite_impl()
     }
+    /// Count the number of weakly connected components.
     pub fn count_components(&self) -> usize {
         self.count_components_impl()
     }
 
     // Traversal
+    /// Perform breadth-first search from a starting node.
     pub fn bfs(&self, start: usize) -> PyResult<Vec<usize>> {
         self.bfs_impl(start)
     }
+    /// Perform depth-first search from a starting node.
     pub fn dfs(&self, start: usize) -> PyResult<Vec<usize>> {
         self.dfs_impl(start)
     }
@@ -222,32 +312,100 @@ impl PyDiGraph {
     }
 
     // Subgraph operations
+    /// Create a subgraph containing the specified nodes.
     pub fn subgraph(&self, nodes: Vec<usize>) -> PyResult<PyDiGraph> {
         self.subgraph_impl(nodes)
     }
+    /// Create an induced subgraph containing the specified nodes.
     pub fn induced_subgraph(&self, nodes: Vec<usize>) -> PyResult<PyDiGraph> {
         self.induced_subgraph_impl(nodes)
     }
+    /// Extract the ego graph centered at a node within a given radius.
     pub fn ego_graph(&self, center: usize, radius: usize) -> PyResult<PyDiGraph> {
         self.ego_graph_impl(center, radius)
     }
+    /// Get all node IDs within k hops of the start node (k of 0 returns just the start).
     pub fn k_hop_neighbors(&self, start: usize, k: usize) -> PyResult<Vec<usize>> {
         self.k_hop_neighbors_impl(start, k)
     }
+    /// Extract the ego graph of every node within a total edge cost of ``center``.
+    ///
+    /// Parameters
+    /// ----------
+    /// center : int
+    ///     The center node ID
+    /// max_distance : float
+    ///     Maximum summed edge cost of a shortest path from ``center``
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to
+    ///     block it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// PyDiGraph
+    ///     The reached nodes and all edges among them
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If ``center`` doesn't exist, or ``max_distance`` or an edge cost is negative or NaN
+    #[pyo3(signature = (center, max_distance, weight=None))]
+    pub fn weighted_ego_graph(
+        &self,
+        center: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyDiGraph> {
+        self.weighted_ego_graph_impl(center, max_distance, weight)
+    }
+    /// Find every node within a total edge cost of ``start``.
+    ///
+    /// Parameters
+    /// ----------
+    /// start : int
+    ///     The start node ID
+    /// max_distance : float
+    ///     Maximum summed edge cost of a shortest path from ``start``
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to
+    ///     block it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// dict of int to float
+    ///     Each reached node mapped to its distance from ``start``, including ``start`` at 0.0
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If ``start`` doesn't exist, or ``max_distance`` or an edge cost is negative or NaN
+    #[pyo3(signature = (start, max_distance, weight=None))]
+    pub fn weighted_k_hop_neighbors(
+        &self,
+        start: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<HashMap<usize, f64>> {
+        self.weighted_k_hop_neighbors_impl(start, max_distance, weight)
+    }
+    /// Get the node IDs in the weakly connected component containing the start node.
     pub fn connected_component(&self, start: usize) -> PyResult<Vec<usize>> {
         self.connected_component_impl(start)
     }
+    /// Extract the subgraph for the weakly connected component containing the start node.
     pub fn component_subgraph(&self, start: usize) -> PyResult<PyDiGraph> {
         self.component_subgraph_impl(start)
     }
 
     // Filter operations
+    /// Return a new graph keeping only nodes for which predicate(node_id, attr) is true.
     pub fn filter_nodes(
         &self,
         predicate: &pyo3::prelude::Bound<'_, pyo3::PyAny>,
     ) -> PyResult<PyDiGraph> {
         self.filter_nodes_py_impl(predicate)
     }
+    /// Return a new graph keeping only edges for which predicate(u, v, weight) is true.
     pub fn filter_edges(
         &self,
         predicate: &pyo3::prelude::Bound<'_, pyo3::PyAny>,
@@ -256,60 +414,136 @@ impl PyDiGraph {
     }
 
     // Metrics
+    /// Compute the diameter (longest shortest path). None if the graph is empty or disconnected.
     pub fn diameter(&self) -> Option<usize> {
         self.diameter_impl()
     }
+    /// Compute the radius (minimum eccentricity). None if the graph is empty or disconnected.
     pub fn radius(&self) -> Option<usize> {
         self.radius_impl()
     }
+    /// Compute the average clustering coefficient over all nodes.
     pub fn average_clustering(&self) -> f64 {
         self.average_clustering_impl()
     }
+    /// Compute the local clustering coefficient for a node.
     pub fn clustering_of(&self, py_node: usize) -> PyResult<f64> {
         self.clustering_of_impl(py_node)
     }
+    /// Compute the transitivity (global clustering coefficient).
     pub fn transitivity(&self) -> f64 {
         self.transitivity_impl()
     }
+    /// Count the triangles containing a node.
     pub fn triangles_of(&self, py_node: usize) -> PyResult<usize> {
         self.triangles_of_impl(py_node)
     }
+    /// Compute the average shortest path length. None if the graph is empty or disconnected.
     pub fn average_path_length(&self) -> Option<f64> {
         self.average_path_length_impl()
     }
+    /// Compute the degree assortativity coefficient.
     pub fn assortativity(&self) -> f64 {
         self.assortativity_impl()
     }
 
+    /// Number of nodes with each degree.
+    ///
+    /// Returns
+    /// -------
+    /// list of int
+    ///     Entry ``d`` is the number of nodes with degree ``d``
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        self.degree_histogram_impl()
+    }
+
+    /// Equal-width histogram of weighted degrees (sum of incident edge weights).
+    ///
+    /// Parameters
+    /// ----------
+    /// bins : int, optional
+    ///     Number of bins (default 10)
+    ///
+    /// Returns
+    /// -------
+    /// tuple of (list of int, list of float)
+    ///     Counts per bin and the ``bins + 1`` bin edges, as in ``numpy.histogram``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If bins is zero
+    #[pyo3(signature = (bins = 10))]
+    pub fn weighted_degree_histogram(&self, bins: usize) -> PyResult<(Vec<usize>, Vec<f64>)> {
+        self.weighted_degree_histogram_impl(bins)
+    }
+
+    /// Summary statistics of the graph, computed in a single call.
+    ///
+    /// Parameters
+    /// ----------
+    /// clustering_samples : int, optional
+    ///     Estimate average clustering from this many evenly spaced nodes instead of all of
+    ///     them (default: exact)
+    ///
+    /// Returns
+    /// -------
+    /// dict
+    ///     Keys ``nodes``, ``edges``, ``directed``, ``density``, ``min_degree``, ``max_degree``,
+    ///     ``mean_degree``, ``std_degree``, ``mean_weighted_degree``, ``components``, and
+    ///     ``average_clustering``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If clustering_samples is zero
+    #[pyo3(signature = (clustering_samples = None))]
+    pub fn summary(
+        &self,
+        py: Python<'_>,
+        clustering_samples: Option<usize>,
+    ) -> PyResult<Py<pyo3::types::PyDict>> {
+        self.summary_impl(py, clustering_samples)
+    }
+
     // I/O operations
+    /// Load the graph from an edge list at the given path; returns the (node_count, edge_count) read.
     #[pyo3(signature = (path, sep = " "))]
     pub fn load_edge_list(&mut self, path: &str, sep: &str) -> PyResult<(usize, usize)> {
         self.load_edge_list_impl(path, sep)
     }
+    /// Write the graph as an edge list to the given path, using sep as the field separator.
     #[pyo3(signature = (path, sep = " "))]
     pub fn save_edge_list(&self, path: &str, sep: &str) -> PyResult<()> {
         self.save_edge_list_impl(path, sep)
     }
+    /// Serialize the graph to JSON at the given path.
     pub fn save_json(&self, path: &str) -> PyResult<()> {
         self.save_json_impl(path)
     }
+    /// Load the graph from a JSON file at the given path.
     pub fn load_json(&mut self, path: &str) -> PyResult<()> {
         self.load_json_impl(path)
     }
+    /// Serialize the graph to a binary file at the given path.
     pub fn save_binary(&self, path: &str) -> PyResult<()> {
         self.save_binary_impl(path)
     }
+    /// Load the graph from a binary file at the given path.
     pub fn load_binary(&mut self, path: &str) -> PyResult<()> {
         self.load_binary_impl(path)
     }
+    /// Serialize the graph to GraphML at the given path.
     pub fn save_graphml(&self, path: &str) -> PyResult<()> {
         self.save_graphml_impl(path)
     }
 
     // Paths
+    /// Compute shortest paths from start using Dijkstra's algorithm, mapping each node to its distance (None if unreachable).
     #[pyo3(signature = (start, cutoff=None))]
     pub fn dijkstra(
         &self,
+        py: Python<'_>,
         start: usize,
         cutoff: Option<f64>,
     ) -> PyResult<std::collections::HashMap<usize, Option<f64>>> {
@@ -317,9 +551,9 @@ impl PyDiGraph {
             *self.mapper.py_to_internal.get(&start).ok_or_else(|| {
                 PyValueError::new_err(format!("Invalid start node id: {}", start))
             })?;
-        let (costs, _trace) =
-            graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, cutoff)
-                .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
+        let (costs, _trace) = py
+            .detach(|| graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, cutoff))
+            .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         let mut out = std::collections::HashMap::new();
         for (nid, dist) in costs.into_iter() {
             if let Some(pyid) = self.mapper.get_py(nid) {
@@ -328,8 +562,10 @@ impl PyDiGraph {
         }
         Ok(out)
     }
+    /// Find the shortest path between two nodes using Dijkstra's algorithm. Returns (distance, path) or None.
     pub fn shortest_path(
         &self,
+        py: Python<'_>,
         start: usize,
         target: usize,
     ) -> PyResult<Option<(f64, Vec<usize>)>> {
@@ -341,7 +577,8 @@ impl PyDiGraph {
             .mapper
             .get_internal(target)
             .ok_or_else(|| PyValueError::new_err(format!("Invalid target node id: {}", target)))?;
-        let (costs, prev) = graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, None)
+        let (costs, prev) = py
+            .detach(|| graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, None))
             .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         match costs[&target_id] {
             Some(total) => {
@@ -371,12 +608,17 @@ impl PyDiGraph {
         }
     }
 
-    pub fn bellman_ford(&self, start: usize) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
+    /// Compute shortest-path distances from start using Bellman-Ford, mapping each node to its distance (None if unreachable). Returns None on a negative cycle.
+    pub fn bellman_ford(
+        &self,
+        py: Python<'_>,
+        start: usize,
+    ) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
         let start_id = self
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err(format!("Invalid start node id: {}", start)))?;
-        let costs = graphina::core::paths::bellman_ford(&self.graph, start_id);
+        let costs = py.detach(|| graphina::core::paths::bellman_ford(&self.graph, start_id));
         Ok(costs.map(|map| {
             map.into_iter()
                 .filter_map(|(nid, d)| self.mapper.get_py(nid).map(|py| (py, d)))
@@ -384,15 +626,119 @@ impl PyDiGraph {
         }))
     }
 
-    pub fn floyd_warshall(&self) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
-        let all_pairs = graphina::core::paths::floyd_warshall(&self.graph);
+    /// Find a shortest path with A* search.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The goal node ID
+    /// heuristic : callable, optional
+    ///     ``heuristic(node) -> float`` estimating the remaining cost to ``target``. It must
+    ///     never overestimate for the result to be optimal. Defaults to zero (plain Dijkstra).
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to skip
+    ///     it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// tuple of (float, list of int) or None
+    ///     The total cost and the node path, or None if ``target`` is unreachable
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist or an edge cost is negative or not finite
+    #[pyo3(signature = (source, target, heuristic=None, weight=None))]
+    pub fn a_star(
+        &self,
+        source: usize,
+        target: usize,
+        heuristic: Option<&Bound<'_, PyAny>>,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Option<(f64, Vec<usize>)>> {
+        self.a_star_impl(source, target, heuristic, weight)
+    }
+
+    /// List every simple path (no repeated nodes) between two nodes.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The end node ID
+    /// cutoff : int, optional
+    ///     Only return paths with at most this many edges
+    ///
+    /// Returns
+    /// -------
+    /// list of list of int
+    ///     Each path as a list of node IDs, starting at ``source``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist
+    #[pyo3(signature = (source, target, cutoff=None))]
+    pub fn all_simple_paths(
+        &self,
+        py: Python<'_>,
+        source: usize,
+        target: usize,
+        cutoff: Option<usize>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        self.all_simple_paths_impl(py, source, target, cutoff)
+    }
+
+    /// Rank the ``k`` cheapest loopless paths between two nodes (Yen's algorithm).
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The end node ID
+    /// k : int
+    ///     How many paths to return at most
+    ///
+    /// Returns
+    /// -------
+    /// list of tuple of (float, list of int)
+    ///     ``(cost, path)`` pairs, cheapest first; fewer than ``k`` if fewer paths exist
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist, ``k`` is 0, or an edge weight is negative or NaN
+    pub fn k_shortest_paths(
+        &self,
+        py: Python<'_>,
+        source: usize,
+        target: usize,
+        k: usize,
+    ) -> PyResult<Vec<(f64, Vec<usize>)>> {
+        self.k_shortest_paths_impl(py, source, target, k)
+    }
+
+    /// Compute all-pairs shortest-path distances using Floyd-Warshall. Returns None on a negative cycle.
+    pub fn floyd_warshall(
+        &self,
+        py: Python<'_>,
+    ) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
+        let all_pairs = py.detach(|| graphina::core::paths::floyd_warshall_matrix(&self.graph));
         all_pairs.map(|m| {
-            m.into_iter()
-                .filter_map(|(u, inner)| {
+            let ids: Vec<Option<usize>> = m.nodes.iter().map(|&v| self.mapper.get_py(v)).collect();
+            m.nodes
+                .iter()
+                .zip(&m.distances)
+                .filter_map(|(&u, row)| {
                     self.mapper.get_py(u).map(|pu| {
-                        let inner_map: HashMap<usize, Option<f64>> = inner
-                            .into_iter()
-                            .filter_map(|(v, d)| self.mapper.get_py(v).map(|pv| (pv, d)))
+                        let inner_map: HashMap<usize, Option<f64>> = ids
+                            .iter()
+                            .zip(row)
+                            .filter_map(|(pv, &d)| pv.map(|pv| (pv, d)))
                             .collect();
                         (pu, inner_map)
                     })
@@ -402,6 +748,7 @@ impl PyDiGraph {
     }
 
     // Bulk operations
+    /// Add several nodes at once and return their node IDs in order.
     pub fn add_nodes_from(&mut self, attrs: Vec<i64>) -> Vec<usize> {
         let mut ids = Vec::with_capacity(attrs.len());
         for a in attrs.into_iter() {
@@ -412,6 +759,7 @@ impl PyDiGraph {
         ids
     }
 
+    /// Add several edges at once as (source, target, weight) triples (weight defaults to 1.0 if None); returns the edge IDs.
     pub fn add_edges_from(
         &mut self,
         edges: Vec<(usize, usize, Option<f64>)>,
@@ -479,6 +827,30 @@ impl PyDiGraph {
             })
             .collect()
     }
+
+    /// Lazily iterate over ``(node_id, attr)`` tuples.
+    ///
+    /// No list is built up front, so memory stays flat even on very large graphs.
+    ///
+    /// Returns
+    /// -------
+    /// NodeStream
+    ///     An iterator yielding ``(node_id, attr)``
+    pub fn iter_nodes(slf: PyRef<'_, Self>) -> NodeStream {
+        NodeStream::new(GraphHandle::Directed(slf.into()))
+    }
+
+    /// Lazily iterate over ``(source, target, weight)`` tuples.
+    ///
+    /// No list is built up front, so memory stays flat even on very large graphs.
+    ///
+    /// Returns
+    /// -------
+    /// EdgeStream
+    ///     An iterator yielding ``(source, target, weight)``
+    pub fn iter_edges(slf: PyRef<'_, Self>) -> EdgeStream {
+        EdgeStream::new(GraphHandle::Directed(slf.into()))
+    }
 }
 
 impl PyDiGraph {
@@ -7,6 +7,7 @@ use std::collections::HashMap;
 use crate::core::views::degree::DegreeView;
 use crate::core::views::edge::EdgeView;
 use crate::core::views::node::NodeView;
+use crate::core::views::stream::{EdgeStream, GraphHandle, NodeStream};
 use graphina::core::types::{BaseGraph, NodeId, Undirected};
 
 /// A Python-accessible Graph class wrapping Graphina's core undirected graph.
@@ -344,6 +345,68 @@ impl PyGraph {
         self.try_update_edge_weight_impl(source, target, new_weight)
     }
 
+    /// Remove several nodes (and their incident edges) in place.
+    ///
+    /// Parameters
+    /// ----------
+    /// py_nodes : list of int
+    ///     Node IDs to remove; duplicates are ignored
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of nodes removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If any node doesn't exist; the graph is left unchanged
+    pub fn remove_nodes(&mut self, py_nodes: Vec<usize>) -> PyResult<usize> {
+        self.remove_nodes_impl(py_nodes)
+    }
+
+    /// Remove several edges in place, one per ``(source, target)`` pair.
+    ///
+    /// Parameters
+    /// ----------
+    /// edges : list of tuple of (int, int)
+    ///     Node pairs identifying the edges to remove
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of edges removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If any node or edge doesn't exist; the graph is left unchanged
+    pub fn remove_edges(&mut self, edges: Vec<(usize, usize)>) -> PyResult<usize> {
+        self.remove_edges_impl(edges)
+    }
+
+    /// Keep only edges heavier than a threshold, removing the rest in place.
+    ///
+    /// Unlike ``filter_edges``, this does not copy the graph, and node IDs are unchanged.
+    ///
+    /// Parameters
+    /// ----------
+    /// threshold : float
+    ///     Edges with weight less than or equal to this value are removed
+    ///
+    /// Returns
+    /// -------
+    /// int
+    ///     The number of edges removed
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If threshold is NaN
+    pub fn retain_edges_above(&mut self, threshold: f64) -> PyResult<usize> {
+        self.retain_edges_above_impl(threshold)
+    }
+
     // Traversal
     /// Perform breadth-first search from a starting node.
     ///
@@ -400,32 +463,100 @@ impl PyGraph {
     }
 
     // Subgraph operations
+    /// Create a subgraph containing the specified nodes.
     pub fn subgraph(&self, nodes: Vec<usize>) -> PyResult<PyGraph> {
         self.subgraph_impl(nodes)
     }
+    /// Create an induced subgraph containing the specified nodes.
     pub fn induced_subgraph(&self, nodes: Vec<usize>) -> PyResult<PyGraph> {
         self.induced_subgraph_impl(nodes)
     }
+    /// Extract the ego graph centered at a node within a given radius.
     pub fn ego_graph(&self, center: usize, radius: usize) -> PyResult<PyGraph> {
         self.ego_graph_impl(center, radius)
     }
+    /// Get all node IDs within k hops of the start node (k of 0 returns just the start).
     pub fn k_hop_neighbors(&self, start: usize, k: usize) -> PyResult<Vec<usize>> {
         self.k_hop_neighbors_impl(start, k)
     }
+    /// Extract the ego graph of every node within a total edge cost of ``center``.
+    ///
+    /// Parameters
+    /// ----------
+    /// center : int
+    ///     The center node ID
+    /// max_distance : float
+    ///     Maximum summed edge cost of a shortest path from ``center``
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to
+    ///     block it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// PyGraph
+    ///     The reached nodes and all edges among them
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If ``center`` doesn't exist, or ``max_distance`` or an edge cost is negative or NaN
+    #[pyo3(signature = (center, max_distance, weight=None))]
+    pub fn weighted_ego_graph(
+        &self,
+        center: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyGraph> {
+        self.weighted_ego_graph_impl(center, max_distance, weight)
+    }
+    /// Find every node within a total edge cost of ``start``.
+    ///
+    /// Parameters
+    /// ----------
+    /// start : int
+    ///     The start node ID
+    /// max_distance : float
+    ///     Maximum summed edge cost of a shortest path from ``start``
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to
+    ///     block it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// dict of int to float
+    ///     Each reached node mapped to its distance from ``start``, including ``start`` at 0.0
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If ``start`` doesn't exist, or ``max_distance`` or an edge cost is negative or NaN
+    #[pyo3(signature = (start, max_distance, weight=None))]
+    pub fn weighted_k_hop_neighbors(
+        &self,
+        start: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<HashMap<usize, f64>> {
+        self.weighted_k_hop_neighbors_impl(start, max_distance, weight)
+    }
+    /// Get the node IDs in the connected component containing the start node.
     pub fn connected_component(&self, start: usize) -> PyResult<Vec<usize>> {
         self.connected_component_impl(start)
     }
+    /// Extract the subgraph for the connected component containing the start node.
     pub fn component_subgraph(&self, start: usize) -> PyResult<PyGraph> {
         self.component_subgraph_impl(start)
     }
 
     // Filter operations
+    /// Return a new graph keeping only nodes for which predicate(node_id, attr) is true.
     pub fn filter_nodes(
         &self,
         predicate: &pyo3::prelude::Bound<'_, pyo3::PyAny>,
     ) -> PyResult<PyGraph> {
         self.filter_nodes_py_impl(predicate)
     }
+    /// Return a new graph keeping only edges for which predicate(u, v, weight) is true.
     pub fn filter_edges(
         &self,
         predicate: &pyo3::prelude::Bound<'_, pyo3::PyAny>,
@@ -434,60 +565,194 @@ impl PyGraph {
     }
 
     // Metrics
+    /// Compute the diameter (longest shortest path). None if the graph is empty or disconnected.
     pub fn diameter(&self) -> Option<usize> {
         self.diameter_impl()
     }
+    /// Compute the radius (minimum eccentricity). None if the graph is empty or disconnected.
     pub fn radius(&self) -> Option<usize> {
         self.radius_impl()
     }
+    /// Compute the average clustering coefficient over all nodes.
     pub fn average_clustering(&self) -> f64 {
         self.average_clustering_impl()
     }
+    /// Compute the local clustering coefficient for a node.
     pub fn clustering_of(&self, py_node: usize) -> PyResult<f64> {
         self.clustering_of_impl(py_node)
     }
+    /// Compute the transitivity (global clustering coefficient).
     pub fn transitivity(&self) -> f64 {
         self.transitivity_impl()
     }
+    /// Count the triangles containing a node.
     pub fn triangles_of(&self, py_node: usize) -> PyResult<usize> {
         self.triangles_of_impl(py_node)
     }
+    /// Compute the average shortest path length. None if the graph is empty or disconnected.
     pub fn average_path_length(&self) -> Option<f64> {
         self.average_path_length_impl()
     }
+    /// Compute the degree assortativity coefficient.
     pub fn assortativity(&self) -> f64 {
         self.assortativity_impl()
     }
 
+    /// Number of nodes with each degree.
+    ///
+    /// Returns
+    /// -------
+    /// list of int
+    ///     Entry ``d`` is the number of nodes with degree ``d``
+    pub fn degree_histogram(&self) -> Vec<usize> {
+        self.degree_histogram_impl()
+    }
+
+    /// Equal-width histogram of weighted degrees (sum of incident edge weights).
+    ///
+    /// Parameters
+    /// ----------
+    /// bins : int, optional
+    ///     Number of bins (default 10)
+    ///
+    /// Returns
+    /// -------
+    /// tuple of (list of int, list of float)
+    ///     Counts per bin and the ``bins + 1`` bin edges, as in ``numpy.histogram``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If bins is zero
+    #[pyo3(signature = (bins = 10))]
+    pub fn weighted_degree_histogram(&self, bins: usize) -> PyResult<(Vec<usize>, Vec<f64>)> {
+        self.weighted_degree_histogram_impl(bins)
+    }
+
+    /// Summary statistics of the graph, computed in a single call.
+    ///
+    /// Parameters
+    /// ----------
+    /// clustering_samples : int, optional
+    ///     Estimate average clustering from this many evenly spaced nodes instead of all of
+    ///     them (default: exact)
+    ///
+    /// Returns
+    /// -------
+    /// dict
+    ///     Keys ``nodes``, ``edges``, ``directed``, ``density``, ``min_degree``, ``max_degree``,
+    ///     ``mean_degree``, ``std_degree``, ``mean_weighted_degree``, ``components``, and
+    ///     ``average_clustering``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If clustering_samples is zero
+    #[pyo3(signature = (clustering_samples = None))]
+    pub fn summary(
+        &self,
+        py: Python<'_>,
+        clustering_samples: Option<usize>,
+    ) -> PyResult<Py<pyo3::types::PyDict>> {
+        self.summary_impl(py, clustering_samples)
+    }
+
+    /// Eigenvalues of the Laplacian ``L = D - A``, smallest first.
+    ///
+    /// Parameters
+    /// ----------
+    /// k : int, optional
+    ///     Number of eigenvalues to return (default: all)
+    ///
+    /// Returns
+    /// -------
+    /// list of float
+    ///     The ``k`` smallest eigenvalues in ascending order; zero appears once per component
+    ///
+    /// Raises
+    /// ------
+    /// GraphinaError
+    ///     If the graph is empty or k is zero or larger than the node count
+    #[pyo3(signature = (k = None))]
+    pub fn laplacian_spectrum(&self, k: Option<usize>) -> PyResult<Vec<f64>> {
+        self.laplacian_spectrum_impl(k)
+    }
+
+    /// Eigenvalues of the weighted adjacency matrix, largest first.
+    ///
+    /// Parameters
+    /// ----------
+    /// k : int, optional
+    ///     Number of eigenvalues to return (default: all)
+    ///
+    /// Returns
+    /// -------
+    /// list of float
+    ///     The ``k`` largest eigenvalues in descending order
+    ///
+    /// Raises
+    /// ------
+    /// GraphinaError
+    ///     If the graph is empty or k is zero or larger than the node count
+    #[pyo3(signature = (k = None))]
+    pub fn adjacency_spectrum(&self, k: Option<usize>) -> PyResult<Vec<f64>> {
+        self.adjacency_spectrum_impl(k)
+    }
+
+    /// Algebraic connectivity and Fiedler vector of a connected graph.
+    ///
+    /// Returns
+    /// -------
+    /// tuple of (float, dict)
+    ///     The second smallest Laplacian eigenvalue and a unit eigenvector for it as a mapping
+    ///     of node ID to value, oriented so its largest-magnitude entry is positive
+    ///
+    /// Raises
+    /// ------
+    /// GraphinaError
+    ///     If the graph has fewer than two nodes or is disconnected
+    pub fn fiedler_vector(&self) -> PyResult<(f64, HashMap<usize, f64>)> {
+        self.fiedler_vector_impl()
+    }
+
     // I/O operations
+    /// Load the graph from an edge list at the given path; returns the (node_count, edge_count) read.
     #[pyo3(signature = (path, sep = " "))]
     pub fn load_edge_list(&mut self, path: &str, sep: &str) -> PyResult<(usize, usize)> {
         self.load_edge_list_impl(path, sep)
     }
+    /// Write the graph as an edge list to the given path, using sep as the field separator.
     #[pyo3(signature = (path, sep = " "))]
     pub fn save_edge_list(&self, path: &str, sep: &str) -> PyResult<()> {
         self.save_edge_list_impl(path, sep)
     }
+    /// Serialize the graph to JSON at the given path.
     pub fn save_json(&self, path: &str) -> PyResult<()> {
         self.save_json_impl(path)
     }
+    /// Load the graph from a JSON file at the given path.
     pub fn load_json(&mut self, path: &str) -> PyResult<()> {
         self.load_json_impl(path)
     }
+    /// Serialize the graph to a binary file at the given path.
     pub fn save_binary(&self, path: &str) -> PyResult<()> {
         self.save_binary_impl(path)
     }
+    /// Load the graph from a binary file at the given path.
     pub fn load_binary(&mut self, path: &str) -> PyResult<()> {
         self.load_binary_impl(path)
     }
+    /// Serialize the graph to GraphML at the given path.
     pub fn save_graphml(&self, path: &str) -> PyResult<()> {
         self.save_graphml_impl(path)
     }
 
     // Paths
+    /// Compute shortest paths from start node using Dijkstra's algorithm.
     #[pyo3(signature = (start, cutoff=None))]
     pub fn dijkstra(
         &self,
+        py: Python<'_>,
         start: usize,
         cutoff: Option<f64>,
     ) -> PyResult<std::collections::HashMap<usize, Option<f64>>> {
@@ -495,9 +760,9 @@ impl PyGraph {
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err(format!("Invalid start node id: {}", start)))?;
-        let (costs, _trace) =
-            graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, cutoff)
-                .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
+        let (costs, _trace) = py
+            .detach(|| graphina::core::paths::dijkstra_path_f64(&self.graph, start_id, cutoff))
+            .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         let mut out = std::collections::HashMap::new();
         for (nid, dist) in costs.into_iter() {
             if let Some(pyid) = self.mapper.get_py(nid) {
@@ -506,25 +771,139 @@ impl PyGraph {
         }
         Ok(out)
     }
+    /// Find the shortest path between two nodes using Dijkstra's algorithm.
+    ///
+    /// Returns:
+    ///     A tuple of (distance, path) if path exists, None otherwise
     pub fn shortest_path(
         &self,
+        py: Python<'_>,
+        start: usize,
+        target: usize,
+    ) -> PyResult<Option<(f64, Vec<usize>)>> {
+        self.shortest_path_impl(py, start, target)
+    }
+    /// Compute shortest-path distances from start using Bellman-Ford, mapping each node to its distance (None if unreachable). Returns None on a negative cycle.
+    pub fn bellman_ford(
+        &self,
+        py: Python<'_>,
         start: usize,
+    ) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
+        self.bellman_ford_impl(py, start)
+    }
+    /// Find a shortest path with A* search.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The goal node ID
+    /// heuristic : callable, optional
+    ///     ``heuristic(node) -> float`` estimating the remaining cost to ``target``. It must
+    ///     never overestimate for the result to be optimal. Defaults to zero (plain Dijkstra).
+    /// weight : callable, optional
+    ///     ``weight(u, v, w) -> float or None`` giving the cost of an edge, or ``None`` to skip
+    ///     it. Defaults to the stored edge weight.
+    ///
+    /// Returns
+    /// -------
+    /// tuple of (float, list of int) or None
+    ///     The total cost and the node path, or None if ``target`` is unreachable
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist or an edge cost is negative or not finite
+    #[pyo3(signature = (source, target, heuristic=None, weight=None))]
+    pub fn a_star(
+        &self,
+        source: usize,
         target: usize,
+        heuristic: Option<&Bound<'_, PyAny>>,
+        weight: Option<&Bound<'_, PyAny>>,
     ) -> PyResult<Option<(f64, Vec<usize>)>> {
-        self.shortest_path_impl(start, target)
+        self.a_star_impl(source, target, heuristic, weight)
     }
-    pub fn bellman_ford(&self, start: usize) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
-        self.bellman_ford_impl(start)
+
+    /// List every simple path (no repeated nodes) between two nodes.
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The end node ID
+    /// cutoff : int, optional
+    ///     Only return paths with at most this many edges
+    ///
+    /// Returns
+    /// -------
+    /// list of list of int
+    ///     Each path as a list of node IDs, starting at ``source``
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist
+    #[pyo3(signature = (source, target, cutoff=None))]
+    pub fn all_simple_paths(
+        &self,
+        py: Python<'_>,
+        source: usize,
+        target: usize,
+        cutoff: Option<usize>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        self.all_simple_paths_impl(py, source, target, cutoff)
     }
-    pub fn floyd_warshall(&self) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
-        let all_pairs = graphina::core::paths::floyd_warshall(&self.graph);
+
+    /// Rank the ``k`` cheapest loopless paths between two nodes (Yen's algorithm).
+    ///
+    /// Parameters
+    /// ----------
+    /// source : int
+    ///     The start node ID
+    /// target : int
+    ///     The end node ID
+    /// k : int
+    ///     How many paths to return at most
+    ///
+    /// Returns
+    /// -------
+    /// list of tuple of (float, list of int)
+    ///     ``(cost, path)`` pairs, cheapest first; fewer than ``k`` if fewer paths exist
+    ///
+    /// Raises
+    /// ------
+    /// ValueError
+    ///     If either node doesn't exist, ``k`` is 0, or an edge weight is negative or NaN
+    pub fn k_shortest_paths(
+        &self,
+        py: Python<'_>,
+        source: usize,
+        target: usize,
+        k: usize,
+    ) -> PyResult<Vec<(f64, Vec<usize>)>> {
+        self.k_shortest_paths_impl(py, source, target, k)
+    }
+
+    /// Compute all-pairs shortest-path distances using Floyd-Warshall. Returns None on a negative cycle.
+    pub fn floyd_warshall(
+        &self,
+        py: Python<'_>,
+    ) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
+        let all_pairs = py.detach(|| graphina::core::paths::floyd_warshall_matrix(&self.graph));
         all_pairs.map(|m| {
-            m.into_iter()
-                .filter_map(|(u, inner)| {
+            let ids: Vec<Option<usize>> = m.nodes.iter().map(|&v| self.mapper.get_py(v)).collect();
+            m.nodes
+                .iter()
+                .zip(&m.distances)
+                .filter_map(|(&u, row)| {
                     self.mapper.get_py(u).map(|pu| {
-                        let inner_map: HashMap<usize, Option<f64>> = inner
-                            .into_iter()
-                            .filter_map(|(v, d)| self.mapper.get_py(v).map(|pv| (pv, d)))
+                        let inner_map: HashMap<usize, Option<f64>> = ids
+                            .iter()
+                            .zip(row)
+                            .filter_map(|(pv, &d)| pv.map(|pv| (pv, d)))
                             .collect();
                         (pu, inner_map)
                     })
@@ -534,26 +913,33 @@ impl PyGraph {
     }
 
     // Validation
+    /// Check whether the graph has no nodes.
     pub fn is_empty(&self) -> bool {
         self.is_empty_impl()
     }
+    /// Check whether the graph is connected.
     pub fn is_connected(&self) -> bool {
         self.is_connected_impl()
     }
+    /// Check whether any edge has a negative weight.
     pub fn has_negative_weights(&self) -> bool {
         self.has_negative_weights_impl()
     }
+    /// Check whether any node has an edge to itself.
     pub fn has_self_loops(&self) -> bool {
         self.has_self_loops_impl()
     }
+    /// Check whether the graph is bipartite.
     pub fn is_bipartite(&self) -> bool {
         self.is_bipartite_impl()
     }
+    /// Count the number of connected components.
     pub fn count_components(&self) -> usize {
         self.count_components_impl()
     }
 
     // Bulk operations
+    /// Add several nodes at once and return their node IDs in order.
     pub fn add_nodes_from(&mut self, attrs: Vec<i64>) -> Vec<usize> {
         let mut ids = Vec::with_capacity(attrs.len());
         for a in attrs.into_iter() {
@@ -563,6 +949,7 @@ impl PyGraph {
         }
         ids
     }
+    /// Add several edges at once as (source, target, weight) triples (weight defaults to 1.0 if None); returns the edge IDs.
     pub fn add_edges_from(
         &mut self,
         edges: Vec<(usize, usize, Option<f64>)>,
@@ -591,6 +978,7 @@ impl PyGraph {
 
     // Edges/nodes view helpers expected by tests
     // Edges/nodes view helpers expected by tests
+    /// Get a view of all edges in the graph as (source, target, weight) tuples.
     #[getter]
     pub fn edges(slf: PyRef<'_, Self>) -> PyResult<EdgeView> {
         let py = slf.py();
@@ -612,6 +1000,7 @@ impl PyGraph {
             .collect()
     }
 
+    /// Return a list of (node_id, attr) pairs for every node.
     pub fn nodes_with_attrs(&self) -> Vec<(usize, i64)> {
         self.graph
             .nodes()
@@ -619,6 +1008,32 @@ impl PyGraph {
             .collect()
     }
 
+    /// Lazily iterate over ``(node_id, attr)`` tuples.
+    ///
+    /// Unlike ``nodes_with_attrs()``, no list is built up front, so memory stays flat on large
+    /// graphs.
+    ///
+    /// Returns
+    /// -------
+    /// NodeStream
+    ///     An iterator yielding ``(node_id, attr)``
+    pub fn iter_nodes(slf: PyRef<'_, Self>) -> NodeStream {
+        NodeStream::new(GraphHandle::Undirected(slf.into()))
+    }
+
+    /// Lazily iterate over ``(source, target, weight)`` tuples.
+    ///
+    /// Unlike ``edges.data("weight")``, no list is built up front, so memory stays flat on
+    /// large graphs.
+    ///
+    /// Returns
+    /// -------
+    /// EdgeStream
+    ///     An iterator yielding ``(source, target, weight)``
+    pub fn iter_edges(slf: PyRef<'_, Self>) -> EdgeStream {
+        EdgeStream::new(GraphHandle::Undirected(slf.into()))
+    }
+
     // Pythonic extras
     fn __len__(&self) -> usize {
         self.graph.node_count()
@@ -198,4 +198,58 @@ impl PyGraph {
             )))
         }
     }
+
+    /// Remove many nodes at once. Every ID is checked before anything is removed, so an
+    /// invalid ID leaves the graph unchanged. Returns the number of nodes removed.
+    pub fn remove_nodes_impl(&mut self, py_nodes: Vec<usize>) -> PyResult<usize> {
+        let mut targets = Vec::with_capacity(py_nodes.len());
+        let mut seen = std::collections::HashSet::new();
+        for py_node in py_nodes {
+            let internal_id = self
+                .mapper
+                .get_internal(py_node)
+                .ok_or_else(|| PyValueError::new_err(format!("Invalid node id: {}", py_node)))?;
+            if seen.insert(py_node) {
+                targets.push((py_node, internal_id));
+            }
+        }
+        for (py_node, internal_id) in &targets {
+            self.graph.remove_node(*internal_id);
+            self.mapper.remove_by_py_id(*py_node);
+        }
+        Ok(targets.len())
+    }
+
+    /// Remove one edge per listed pair. All pairs are resolved before anything is removed, so a
+    /// missing node or edge leaves the graph unchanged. Returns the number of edges removed.
+    pub fn remove_edges_impl(&mut self, pairs: Vec<(usize, usize)>) -> PyResult<usize> {
+        let mut targets = std::collections::HashSet::new();
+        for (source, target) in pairs {
+            let src_id = self.mapper.get_internal(source).ok_or_else(|| {
+                PyValueError::new_err(format!("Invalid source node id: {}", source))
+            })?;
+            let tgt_id = self.mapper.get_internal(target).ok_or_else(|| {
+                PyValueError::new_err(format!("Invalid target node id: {}", target))
+            })?;
+            let edge_id = self.graph.find_edge(src_id, tgt_id).ok_or_else(|| {
+                PyValueError::new_err(format!("Edge not found between {} and {}", source, target))
+            })?;
+            targets.insert(edge_id);
+        }
+        for edge_id in &targets {
+            self.graph.remove_edge(*edge_id);
+        }
+        Ok(targets.len())
+    }
+
+    /// Drop every edge whose weight is not strictly greater than `threshold`, in place.
+    /// Returns the number of edges removed.
+    pub fn retain_edges_above_impl(&mut self, threshold: f64) -> PyResult<usize> {
+        if threshold.is_nan() {
+            return Err(PyValueError::new_err("Threshold must not be NaN"));
+        }
+        let before = self.graph.edge_count();
+        self.graph.retain_edges(|_, _, &w| w > threshold);
+        Ok(before - self.graph.edge_count())
+    }
 }
@@ -3,6 +3,7 @@ pub mod io;
 pub mod paths;
 pub mod validation;
 
+pub mod attr_graph;
 pub mod digraph;
 pub mod digraph_ops;
 pub mod exceptions;
@@ -11,6 +12,7 @@ pub mod graph_ops;
 pub mod id_map;
 pub mod views;
 
+pub use attr_graph::PyAttrGraph;
 pub use digraph::PyDiGraph;
 
 pub use graph::PyGraph;
@@ -1,16 +1,164 @@
+use ordered_float::OrderedFloat;
+use petgraph::EdgeType;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use std::cell::RefCell;
 use std::collections::HashMap;
 
-use graphina::core::paths::{bellman_ford, dijkstra_path_f64, floyd_warshall};
-use graphina::core::types::NodeId;
+use graphina::core::paths::{
+    a_star, all_simple_paths, bellman_ford, dijkstra_path_f64, floyd_warshall, k_shortest_paths,
+};
+use graphina::core::types::{BaseGraph, GraphConstructor, NodeId};
 
+use crate::core::id_map::IdMapper;
 use crate::{PyDiGraph, PyGraph};
 
+fn resolve(mapper: &IdMapper, py_node: usize, role: &str) -> PyResult<NodeId> {
+    mapper
+        .get_internal(py_node)
+        .ok_or_else(|| PyValueError::new_err(format!("Invalid {} node id: {}", role, py_node)))
+}
+
+/// A* search on an `OrderedFloat` copy of `graph`, as the Rust `a_star` needs totally ordered
+/// weights.
+///
+/// `weight(u, v, w)` may rescale an edge or return `None` to drop it; without it the stored
+/// weights are used. `heuristic(node)` estimates the remaining cost to the target and defaults
+/// to zero, which makes the search equivalent to Dijkstra. The first exception raised by the
+/// heuristic aborts the search and is re-raised.
+fn a_star_py<Ty>(
+    graph: &BaseGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    source: usize,
+    target: usize,
+    heuristic: Option<&Bound<'_, PyAny>>,
+    weight: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Option<(f64, Vec<usize>)>>
+where
+    Ty: GraphConstructor<i64, f64> + GraphConstructor<i64, OrderedFloat<f64>> + EdgeType,
+{
+    let source_id = resolve(mapper, source, "source")?;
+    let target_id = resolve(mapper, target, "target")?;
+
+    let mut ordered = BaseGraph::<i64, OrderedFloat<f64>, Ty>::with_capacity(
+        graph.node_count(),
+        graph.edge_count(),
+    );
+    let mut to_ordered: HashMap<NodeId, NodeId> = HashMap::with_capacity(graph.node_count());
+    let mut to_py: HashMap<NodeId, usize> = HashMap::with_capacity(graph.node_count());
+    for (nid, &attr) in graph.nodes() {
+        let new_id = ordered.add_node(attr);
+        to_ordered.insert(nid, new_id);
+        if let Some(py_id) = mapper.get_py(nid) {
+            to_py.insert(new_id, py_id);
+        }
+    }
+    for (u, v, &w) in graph.edges() {
+        let (nu, nv) = (to_ordered[&u], to_ordered[&v]);
+        let cost = match weight {
+            Some(f) => {
+                let (pu, pv) = (to_py[&nu], to_py[&nv]);
+                match f.call1((pu, pv, w))?.extract::<Option<f64>>()? {
+                    Some(c) => c,
+                    None => continue,
+                }
+            }
+            None => w,
+        };
+        if !cost.is_finite() || cost < 0.0 {
+            return Err(PyValueError::new_err(format!(
+                "A* needs finite, nonnegative edge costs, got {} on edge ({}, {})",
+                cost, to_py[&nu], to_py[&nv]
+            )));
+        }
+        ordered.add_edge(nu, nv, OrderedFloat(cost));
+    }
+
+    let failure: RefCell<Option<PyErr>> = RefCell::new(None);
+    let estimate = |n: NodeId| -> OrderedFloat<f64> {
+        let Some(h) = heuristic else {
+            return OrderedFloat(0.0);
+        };
+        if failure.borrow().is_some() {
+            return OrderedFloat(0.0);
+        }
+        match h.call1((to_py[&n],)).and_then(|r| r.extract::<f64>()) {
+            Ok(value) => OrderedFloat(value),
+            Err(e) => {
+                *failure.borrow_mut() = Some(e);
+                OrderedFloat(0.0)
+            }
+        }
+    };
+    let found = a_star(
+        &ordered,
+        to_ordered[&source_id],
+        to_ordered[&target_id],
+        estimate,
+    );
+    if let Some(e) = failure.into_inner() {
+        return Err(e);
+    }
+    let found = found.map_err(|e| PyValueError::new_err(format!("A* error: {}", e)))?;
+    Ok(found.map(|(cost, path)| (cost.0, path.iter().map(|n| to_py[n]).collect())))
+}
+
+/// All simple paths between two nodes, as lists of Python node IDs.
+fn all_simple_paths_py<Ty>(
+    py: Python<'_>,
+    graph: &BaseGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    source: usize,
+    target: usize,
+    cutoff: Option<usize>,
+) -> PyResult<Vec<Vec<usize>>>
+where
+    Ty: GraphConstructor<i64, f64> + EdgeType + Sync,
+{
+    let source_id = resolve(mapper, source, "source")?;
+    let target_id = resolve(mapper, target, "target")?;
+    let paths = py
+        .detach(|| all_simple_paths(graph, source_id, target_id, cutoff))
+        .map_err(|e| PyValueError::new_err(format!("all_simple_paths error: {}", e)))?;
+    Ok(paths
+        .into_iter()
+        .map(|p| p.into_iter().filter_map(|n| mapper.get_py(n)).collect())
+        .collect())
+}
+
+/// The `k` cheapest loopless paths between two nodes, as `(cost, path)` pairs.
+fn k_shortest_paths_py<Ty>(
+    py: Python<'_>,
+    graph: &BaseGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    source: usize,
+    target: usize,
+    k: usize,
+) -> PyResult<Vec<(f64, Vec<usize>)>>
+where
+    Ty: GraphConstructor<i64, f64> + EdgeType + Sync,
+{
+    let source_id = resolve(mapper, source, "source")?;
+    let target_id = resolve(mapper, target, "target")?;
+    let paths = py
+        .detach(|| k_shortest_paths(graph, source_id, target_id, k, |w| Some(*w)))
+        .map_err(|e| PyValueError::new_err(format!("k_shortest_paths error: {}", e)))?;
+    Ok(paths
+        .into_iter()
+        .map(|(cost, p)| {
+            (
+                cost,
+                p.into_iter().filter_map(|n| mapper.get_py(n)).collect(),
+            )
+        })
+        .collect())
+}
+
 impl PyGraph {
     /// Dijkstra shortest paths (f64 weights) from `start` with optional cutoff.
     pub fn dijkstra_impl(
         &self,
+        py: Python<'_>,
         start: usize,
         cutoff: Option<f64>,
     ) -> PyResult<HashMap<usize, Option<f64>>> {
@@ -18,7 +166,8 @@ impl PyGraph {
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err("Invalid start node id"))?;
-        let (costs, _trace) = dijkstra_path_f64(&self.graph, start_id, cutoff)
+        let (costs, _trace) = py
+            .detach(|| dijkstra_path_f64(&self.graph, start_id, cutoff))
             .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         let mut out: HashMap<usize, Option<f64>> = HashMap::new();
         for (node_id, dist_opt) in costs.into_iter() {
@@ -32,6 +181,7 @@ impl PyGraph {
     /// Shortest path from start to target using Dijkstra (f64 weights).
     pub fn shortest_path_impl(
         &self,
+        py: Python<'_>,
         start: usize,
         target: usize,
     ) -> PyResult<Option<(f64, Vec<usize>)>> {
@@ -43,7 +193,8 @@ impl PyGraph {
             .mapper
             .get_internal(target)
             .ok_or_else(|| PyValueError::new_err("Invalid target node id"))?;
-        let (costs, prev) = dijkstra_path_f64(&self.graph, start_id, None)
+        let (costs, prev) = py
+            .detach(|| dijkstra_path_f64(&self.graph, start_id, None))
             .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         match costs[&target_id] {
             Some(total) => {
@@ -80,12 +231,16 @@ impl PyGraph {
     }
 
     /// Bellman-Ford single-source shortest paths. Returns None if negative cycle is detected.
-    pub fn bellman_ford_impl(&self, start: usize) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
+    pub fn bellman_ford_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+    ) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
         let start_id = self
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err("Invalid start node id"))?;
-        let res = bellman_ford(&self.graph, start_id);
+        let res = py.detach(|| bellman_ford(&self.graph, start_id));
         Ok(res.map(|nm| {
             let mut out = HashMap::new();
             for (nid, dist_opt) in nm.into_iter() {
@@ -97,9 +252,45 @@ impl PyGraph {
         }))
     }
 
+    /// A* shortest path from `start` to `target` with optional Python heuristic and cost callbacks.
+    pub fn a_star_impl(
+        &self,
+        start: usize,
+        target: usize,
+        heuristic: Option<&Bound<'_, PyAny>>,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Option<(f64, Vec<usize>)>> {
+        a_star_py(&self.graph, &self.mapper, start, target, heuristic, weight)
+    }
+
+    /// Every simple path from `start` to `target`, with at most `cutoff` edges if given.
+    pub fn all_simple_paths_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+        target: usize,
+        cutoff: Option<usize>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        all_simple_paths_py(py, &self.graph, &self.mapper, start, target, cutoff)
+    }
+
+    /// The `k` cheapest loopless paths from `start` to `target`, cheapest first.
+    pub fn k_shortest_paths_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+        target: usize,
+        k: usize,
+    ) -> PyResult<Vec<(f64, Vec<usize>)>> {
+        k_shortest_paths_py(py, &self.graph, &self.mapper, start, target, k)
+    }
+
     /// Floyd–Warshall all-pairs shortest paths. Returns None if negative cycle.
-    pub fn floyd_warshall_impl(&self) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
-        floyd_warshall(&self.graph).map(|outer| {
+    pub fn floyd_warshall_impl(
+        &self,
+        py: Python<'_>,
+    ) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
+        py.detach(|| floyd_warshall(&self.graph)).map(|outer| {
             let mut out_outer: HashMap<usize, HashMap<usize, Option<f64>>> = HashMap::new();
             for (u, inner) in outer.into_iter() {
                 if let Some(py_u) = self.mapper.get_py(u) {
@@ -121,6 +312,7 @@ impl PyDiGraph {
     /// Dijkstra shortest paths (f64 weights) from `start` with optional cutoff.
     pub fn dijkstra_impl(
         &self,
+        py: Python<'_>,
         start: usize,
         cutoff: Option<f64>,
     ) -> PyResult<HashMap<usize, Option<f64>>> {
@@ -128,7 +320,8 @@ impl PyDiGraph {
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err("Invalid start node id"))?;
-        let (costs, _trace) = dijkstra_path_f64(&self.graph, start_id, cutoff)
+        let (costs, _trace) = py
+            .detach(|| dijkstra_path_f64(&self.graph, start_id, cutoff))
             .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         let mut out: HashMap<usize, Option<f64>> = HashMap::new();
         for (node_id, dist_opt) in costs.into_iter() {
@@ -142,6 +335,7 @@ impl PyDiGraph {
     /// Shortest path from start to target using Dijkstra (f64 weights).
     pub fn shortest_path_impl(
         &self,
+        py: Python<'_>,
         start: usize,
         target: usize,
     ) -> PyResult<Option<(f64, Vec<usize>)>> {
@@ -153,7 +347,8 @@ impl PyDiGraph {
             .mapper
             .get_internal(target)
             .ok_or_else(|| PyValueError::new_err("Invalid target node id"))?;
-        let (costs, prev) = dijkstra_path_f64(&self.graph, start_id, None)
+        let (costs, prev) = py
+            .detach(|| dijkstra_path_f64(&self.graph, start_id, None))
             .map_err(|e| PyValueError::new_err(format!("Dijkstra error: {}", e)))?;
         match costs[&target_id] {
             Some(total) => {
@@ -190,12 +385,16 @@ impl PyDiGraph {
     }
 
     /// Bellman-Ford single-source shortest paths. Returns None if negative cycle is detected.
-    pub fn bellman_ford_impl(&self, start: usize) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
+    pub fn bellman_ford_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+    ) -> PyResult<Option<HashMap<usize, Option<f64>>>> {
         let start_id = self
             .mapper
             .get_internal(start)
             .ok_or_else(|| PyValueError::new_err("Invalid start node id"))?;
-        let res = bellman_ford(&self.graph, start_id);
+        let res = py.detach(|| bellman_ford(&self.graph, start_id));
         Ok(res.map(|nm| {
             let mut out = HashMap::new();
             for (nid, dist_opt) in nm.into_iter() {
@@ -207,9 +406,45 @@ impl PyDiGraph {
         }))
     }
 
+    /// A* shortest path from `start` to `target` with optional Python heuristic and cost callbacks.
+    pub fn a_star_impl(
+        &self,
+        start: usize,
+        target: usize,
+        heuristic: Option<&Bound<'_, PyAny>>,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<Option<(f64, Vec<usize>)>> {
+        a_star_py(&self.graph, &self.mapper, start, target, heuristic, weight)
+    }
+
+    /// Every simple path from `start` to `target`, with at most `cutoff` edges if given.
+    pub fn all_simple_paths_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+        target: usize,
+        cutoff: Option<usize>,
+    ) -> PyResult<Vec<Vec<usize>>> {
+        all_simple_paths_py(py, &self.graph, &self.mapper, start, target, cutoff)
+    }
+
+    /// The `k` cheapest loopless paths from `start` to `target`, cheapest first.
+    pub fn k_shortest_paths_impl(
+        &self,
+        py: Python<'_>,
+        start: usize,
+        target: usize,
+        k: usize,
+    ) -> PyResult<Vec<(f64, Vec<usize>)>> {
+        k_shortest_paths_py(py, &self.graph, &self.mapper, start, target, k)
+    }
+
     /// Floyd–Warshall all-pairs shortest paths. Returns None if negative cycle.
-    pub fn floyd_warshall_impl(&self) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
-        floyd_warshall(&self.graph).map(|outer| {
+    pub fn floyd_warshall_impl(
+        &self,
+        py: Python<'_>,
+    ) -> Option<HashMap<usize, HashMap<usize, Option<f64>>>> {
+        py.detach(|| floyd_warshall(&self.graph)).map(|outer| {
             let mut out_outer: HashMap<usize, HashMap<usize, Option<f64>>> = HashMap::new();
             for (u, inner) in outer.into_iter() {
                 if let Some(py_u) = self.mapper.get_py(u) {
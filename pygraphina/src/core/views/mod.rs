@@ -1,6 +1,7 @@
 pub mod degree;
 pub mod edge;
 pub mod node;
+pub mod stream;
 
 use pyo3::prelude::*;
 
@@ -16,5 +17,8 @@ pub fn register_views(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
     m.add_class::<degree::DegreeView>()?;
     m.add_class::<degree::DegreeIterator>()?;
+
+    m.add_class::<stream::NodeStream>()?;
+    m.add_class::<stream::EdgeStream>()?;
     Ok(())
 }
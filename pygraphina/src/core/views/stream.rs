@@ -0,0 +1,155 @@
+//! Streaming node and edge iterators.
+//!
+//! The `nodes` and `edges` views collect every item into a vector before handing out the first
+//! one. These iterators instead hold the graph and a cursor into its index space and produce one
+//! tuple per `__next__` call, so memory use does not grow with the size of the graph.
+//!
+//! The graph may be modified between calls. Items removed before the cursor reaches them are
+//! skipped. Items added meanwhile may or may not be visited, because new nodes and edges can
+//! reuse the slots of removed ones.
+
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+
+use crate::core::id_map::IdMapper;
+use crate::{PyDiGraph, PyGraph};
+use graphina::core::types::NodeId;
+use petgraph::EdgeType;
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::stable_graph::StableGraph;
+use petgraph::visit::{EdgeIndexable, NodeIndexable};
+
+/// The graph an iterator walks over.
+pub(crate) enum GraphHandle {
+    Undirected(Py<PyGraph>),
+    Directed(Py<PyDiGraph>),
+}
+
+fn borrow_error(e: impl std::fmt::Display) -> PyErr {
+    PyRuntimeError::new_err(format!("Graph is not accessible during iteration: {}", e))
+}
+
+fn next_node<Ty: EdgeType>(
+    graph: &StableGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    cursor: &mut usize,
+) -> Option<(usize, i64)> {
+    while *cursor < graph.node_bound() {
+        let idx = NodeIndex::new(*cursor);
+        *cursor += 1;
+        if let (Some(&attr), Some(py_id)) =
+            (graph.node_weight(idx), mapper.get_py(NodeId::new(idx)))
+        {
+            return Some((py_id, attr));
+        }
+    }
+    None
+}
+
+fn next_edge<Ty: EdgeType>(
+    graph: &StableGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    cursor: &mut usize,
+) -> Option<(usize, usize, f64)> {
+    while *cursor < graph.edge_bound() {
+        let idx = EdgeIndex::new(*cursor);
+        *cursor += 1;
+        let Some((u, v)) = graph.edge_endpoints(idx) else {
+            continue;
+        };
+        if let (Some(&w), Some(pu), Some(pv)) = (
+            graph.edge_weight(idx),
+            mapper.get_py(NodeId::new(u)),
+            mapper.get_py(NodeId::new(v)),
+        ) {
+            return Some((pu, pv, w));
+        }
+    }
+    None
+}
+
+/// A lazy iterator over `(node_id, attr)` tuples, returned by `iter_nodes()`.
+#[pyclass]
+pub struct NodeStream {
+    graph: GraphHandle,
+    cursor: usize,
+}
+
+impl NodeStream {
+    pub(crate) fn new(graph: GraphHandle) -> Self {
+        Self { graph, cursor: 0 }
+    }
+}
+
+#[pymethods]
+impl NodeStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<(usize, i64)>> {
+        let py = slf.py();
+        let this = &mut *slf;
+        match &this.graph {
+            GraphHandle::Undirected(g) => {
+                let g = g.try_borrow(py).map_err(borrow_error)?;
+                Ok(next_node(
+                    g.graph.as_petgraph(),
+                    &g.mapper,
+                    &mut this.cursor,
+                ))
+            }
+            GraphHandle::Directed(g) => {
+                let g = g.try_borrow(py).map_err(borrow_error)?;
+                Ok(next_node(
+                    g.graph.as_petgraph(),
+                    &g.mapper,
+                    &mut this.cursor,
+                ))
+            }
+        }
+    }
+}
+
+/// A lazy iterator over `(source, target, weight)` tuples, returned by `iter_edges()`.
+#[pyclass]
+pub struct EdgeStream {
+    graph: GraphHandle,
+    cursor: usize,
+}
+
+impl EdgeStream {
+    pub(crate) fn new(graph: GraphHandle) -> Self {
+        Self { graph, cursor: 0 }
+    }
+}
+
+#[pymethods]
+impl EdgeStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(mut slf: PyRefMut<'_, Self>) -> PyResult<Option<(usize, usize, f64)>> {
+        let py = slf.py();
+        let this = &mut *slf;
+        match &this.graph {
+            GraphHandle::Undirected(g) => {
+                let g = g.try_borrow(py).map_err(borrow_error)?;
+                Ok(next_edge(
+                    g.graph.as_petgraph(),
+                    &g.mapper,
+                    &mut this.cursor,
+                ))
+            }
+            GraphHandle::Directed(g) => {
+                let g = g.try_borrow(py).map_err(borrow_error)?;
+                Ok(next_edge(
+                    g.graph.as_petgraph(),
+                    &g.mapper,
+                    &mut this.cursor,
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Build introspection and placeholders for functions whose Cargo feature is disabled.
+//!
+//! A function behind a disabled feature is still registered, under its usual name, so calling
+//! it raises `GraphinaFeatureError` naming the feature instead of `AttributeError`.
+
+#[cfg(not(feature = "networkx"))]
+use crate::GraphinaFeatureError;
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+
+/// Every optional feature of this crate and whether it was compiled in.
+const OPTIONAL_FEATURES: &[(&str, bool)] = &[("networkx", cfg!(feature = "networkx"))];
+
+/// Return the version of PyGraphina and the optional features it was built with.
+///
+/// Returns
+/// -------
+/// dict
+///     ``{"version": str, "features": list[str], "missing_features": list[str]}``, where
+///     ``features`` are the optional features compiled in and ``missing_features`` the rest.
+#[pyfunction]
+pub fn build_info(py: Python<'_>) -> PyResult<Py<PyDict>> {
+    let info = PyDict::new(py);
+    info.set_item("version", env!("CARGO_PKG_VERSION"))?;
+    let enabled = OPTIONAL_FEATURES.iter().filter(|(_, on)| *on);
+    let missing = OPTIONAL_FEATURES.iter().filter(|(_, on)| !*on);
+    info.set_item("features", PyList::new(py, enabled.map(|(name, _)| *name))?)?;
+    info.set_item(
+        "missing_features",
+        PyList::new(py, missing.map(|(name, _)| *name))?,
+    )?;
+    Ok(info.unbind())
+}
+
+/// The error raised by a placeholder for `function`, which needs `feature`.
+#[cfg(not(feature = "networkx"))]
+fn missing_feature(function: &str, feature: &str) -> PyErr {
+    GraphinaFeatureError::new_err(format!(
+        "{function} requires the '{feature}' feature, which this build of pygraphina does not \
+         include; rebuild with `--features {feature}`"
+    ))
+}
+
+/// Defines placeholders that accept any arguments and raise `GraphinaFeatureError`.
+#[cfg(not(feature = "networkx"))]
+macro_rules! placeholders {
+    ($feature:literal: $($name:ident),+ $(,)?) => {
+        $(
+            #[pyfunction]
+            #[pyo3(signature = (*_args, **_kwargs))]
+            pub fn $name(
+                _args: &Bound<'_, pyo3::types::PyTuple>,
+                _kwargs: Option<&Bound<'_, PyDict>>,
+            ) -> PyResult<Py<PyAny>> {
+                Err(missing_feature(stringify!($name), $feature))
+            }
+        )+
+    };
+}
+
+#[cfg(not(feature = "networkx"))]
+placeholders!("networkx": to_networkx, from_networkx, to_node_dataframe, to_edge_dataframe);
+
+/// Registers `build_info` and the placeholders for every disabled feature.
+pub fn register_features(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(build_info, m)?)?;
+    #[cfg(not(feature = "networkx"))]
+    {
+        m.add_function(wrap_pyfunction!(to_networkx, m)?)?;
+        m.add_function(wrap_pyfunction!(from_networkx, m)?)?;
+        m.add_function(wrap_pyfunction!(to_node_dataframe, m)?)?;
+        m.add_function(wrap_pyfunction!(to_edge_dataframe, m)?)?;
+    }
+    Ok(())
+}
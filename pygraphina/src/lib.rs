@@ -2,15 +2,18 @@
 //!
 //! This crate provides Python-accessible graph classes and algorithms.
 
+#[cfg(feature = "networkx")]
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
 use pyo3::types::PyDict;
+#[cfg(feature = "networkx")]
 use std::collections::HashMap;
 
 // Core module must be declared first
 mod core;
 pub use core::exceptions::*;
 // Export types from core
+pub use core::PyAttrGraph;
 pub use core::PyDiGraph;
 pub use core::PyGraph;
 
@@ -18,6 +21,7 @@ pub use core::PyGraph;
 mod approximation;
 mod centrality;
 mod community;
+mod features;
 mod links;
 mod metrics;
 mod mst;
@@ -71,16 +75,24 @@ pub(crate) fn nodemap_to_pydict(
 fn pygraphina(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyGraph>()?;
     m.add_class::<PyDiGraph>()?;
+    m.add_class::<PyAttrGraph>()?;
+    m.add_class::<core::attr_graph::AttrNodeView>()?;
+    m.add_class::<core::attr_graph::AttrEdgeView>()?;
 
     // Add Pythonic aliases (without "Py" prefix) for more intuitive API
     // This allows users to write: pg.Graph() instead of pg.PyGraph()
     m.add("Graph", m.getattr("PyGraph")?)?;
     m.add("DiGraph", m.getattr("PyDiGraph")?)?;
+    m.add("AttrGraph", m.getattr("PyAttrGraph")?)?;
 
     // Register custom exceptions
     m.add("GraphinaError", m.py().get_type::<GraphinaError>())?;
     m.add("ConvergenceError", m.py().get_type::<ConvergenceError>())?;
     m.add("NodeNotFoundError", m.py().get_type::<NodeNotFoundError>())?;
+    m.add(
+        "GraphinaFeatureError",
+        m.py().get_type::<GraphinaFeatureError>(),
+    )?;
 
     // Register core generators at top-level for backward compatibility
     core::generators::register_generators(m)?;
@@ -188,6 +200,8 @@ fn pygraphina(m: &Bound<'_, PyModule>) -> PyResult<()> {
         m.add_function(wrap_pyfunction!(to_node_dataframe, m)?)?;
         m.add_function(wrap_pyfunction!(to_edge_dataframe, m)?)?;
     }
+    // `build_info`, and placeholders raising `GraphinaFeatureError` for disabled features
+    features::register_features(m)?;
 
     // Register views
     core::views::register_views(m)?;
@@ -5,6 +5,7 @@ use pyo3::types::PyDict;
 
 use super::similarity::{map_ebunch, map_pair_map_to_py};
 
+/// Compute the resource allocation index for node pairs.
 #[pyfunction]
 #[pyo3(signature = (py_graph, ebunch=None))]
 pub fn resource_allocation_index(
@@ -5,6 +5,7 @@ use pyo3::types::PyDict;
 
 use super::similarity::{map_ebunch, map_pair_map_to_py};
 
+/// Compute common neighbor centrality for node pairs.
 #[pyfunction]
 #[pyo3(signature = (py_graph, alpha, ebunch=None))]
 pub fn common_neighbor_centrality(
@@ -56,6 +56,7 @@ pub(super) fn map_pair_map_to_py(
     Ok(dict.unbind())
 }
 
+/// Compute the Jaccard coefficient for node pairs.
 #[pyfunction]
 #[pyo3(signature = (py_graph, ebunch=None))]
 pub fn jaccard_coefficient(
@@ -73,6 +74,7 @@ pub fn jaccard_coefficient(
     map_pair_map_to_py(py, py_graph, res)
 }
 
+/// Compute the Adamic-Adar index for node pairs.
 #[pyfunction]
 #[pyo3(signature = (py_graph, ebunch=None))]
 pub fn adamic_adar_index(
@@ -90,6 +92,7 @@ pub fn adamic_adar_index(
     map_pair_map_to_py(py, py_graph, res)
 }
 
+/// Count the number of common neighbors between two nodes.
 #[pyfunction]
 pub fn common_neighbors(py_graph: &PyGraph, u: usize, v: usize) -> PyResult<usize> {
     let iu = *py_graph
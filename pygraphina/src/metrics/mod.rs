@@ -1,12 +1,149 @@
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::collections::HashMap;
 
+use graphina::core::types::NodeId;
 use graphina::metrics::{
-    assortativity, average_clustering_coefficient, average_path_length, clustering_coefficient,
-    diameter, radius, transitivity, triangles,
+    adjacency_spectrum, assortativity, average_clustering_coefficient, average_path_length,
+    clustering_coefficient, diameter, fiedler_vector, laplacian_spectrum, radius, transitivity,
+    triangles,
 };
 
-use crate::{PyDiGraph, PyGraph};
+use crate::{GraphinaError, PyDiGraph, PyGraph};
+
+/// Degree and weighted degree of every node, gathered in one pass over the edges.
+///
+/// An undirected self-loop counts once, matching `degree()`; a directed one is both an in- and
+/// an out-edge of its node, so it counts twice.
+fn degree_sequence(
+    nodes: impl Iterator<Item = NodeId>,
+    edges: impl Iterator<Item = (NodeId, NodeId, f64)>,
+    directed: bool,
+) -> Vec<(usize, f64)> {
+    let mut slot: HashMap<NodeId, usize> = HashMap::new();
+    let mut seq = Vec::new();
+    for node in nodes {
+        slot.insert(node, seq.len());
+        seq.push((0, 0.0));
+    }
+    for (u, v, w) in edges {
+        let ends = if u == v && !directed { 1 } else { 2 };
+        for end in [u, v].into_iter().take(ends) {
+            if let Some(&i) = slot.get(&end) {
+                seq[i].0 += 1;
+                seq[i].1 += w;
+            }
+        }
+    }
+    seq
+}
+
+/// Counts of nodes per degree, indexed by degree (`hist[d]` nodes have degree `d`).
+fn degree_histogram_of(seq: &[(usize, f64)]) -> Vec<usize> {
+    let max = seq.iter().map(|(d, _)| *d).max().map_or(0, |m| m + 1);
+    let mut hist = vec![0; max];
+    for (d, _) in seq {
+        hist[*d] += 1;
+    }
+    hist
+}
+
+/// Equal-width histogram of weighted degrees, returned as `(counts, bin_edges)` like
+/// `numpy.histogram`. A constant sequence gets the range `[x - 0.5, x + 0.5]`.
+fn weighted_degree_histogram_of(
+    seq: &[(usize, f64)],
+    bins: usize,
+) -> PyResult<(Vec<usize>, Vec<f64>)> {
+    if bins == 0 {
+        return Err(PyValueError::new_err("bins must be positive"));
+    }
+    let (mut lo, mut hi) = seq
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), (_, w)| {
+            (lo.min(*w), hi.max(*w))
+        });
+    if seq.is_empty() {
+        (lo, hi) = (0.0, 1.0);
+    } else if lo == hi {
+        (lo, hi) = (lo - 0.5, hi + 0.5);
+    }
+    let width = (hi - lo) / bins as f64;
+    let edges: Vec<f64> = (0..=bins).map(|i| lo + width * i as f64).collect();
+    let mut counts = vec![0; bins];
+    for (_, w) in seq {
+        // The last bin is closed on the right so the maximum lands inside it.
+        let bin = (((w - lo) / width) as usize).min(bins - 1);
+        counts[bin] += 1;
+    }
+    Ok((counts, edges))
+}
+
+/// Mean of local clustering over every node, or over `samples` nodes spread evenly through the
+/// node order when a sample size smaller than the graph is given.
+fn clustering_estimate(
+    nodes: Vec<NodeId>,
+    samples: Option<usize>,
+    exact: impl FnOnce() -> f64,
+    local: impl Fn(NodeId) -> f64,
+) -> PyResult<f64> {
+    match samples {
+        Some(0) => Err(PyValueError::new_err("clustering_samples must be positive")),
+        Some(k) if k < nodes.len() => {
+            let stride = nodes.len() / k;
+            let total: f64 = nodes
+                .iter()
+                .step_by(stride)
+                .take(k)
+                .map(|&n| local(n))
+                .sum();
+            Ok(total / k as f64)
+        }
+        _ => Ok(exact()),
+    }
+}
+
+/// Assembles the dict returned by `summary()` on both graph classes.
+fn summary_dict(
+    py: Python<'_>,
+    seq: &[(usize, f64)],
+    edges: usize,
+    directed: bool,
+    density: f64,
+    components: usize,
+    average_clustering: f64,
+) -> PyResult<Py<PyDict>> {
+    let n = seq.len();
+    let (min_degree, max_degree) = seq
+        .iter()
+        .fold((usize::MAX, 0), |(lo, hi), (d, _)| (lo.min(*d), hi.max(*d)));
+    let (mean_degree, std_degree, mean_weighted_degree) = if n == 0 {
+        (0.0, 0.0, 0.0)
+    } else {
+        let mean = seq.iter().map(|(d, _)| *d as f64).sum::<f64>() / n as f64;
+        let var = seq
+            .iter()
+            .map(|(d, _)| (*d as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        let mean_w = seq.iter().map(|(_, w)| *w).sum::<f64>() / n as f64;
+        (mean, var.sqrt(), mean_w)
+    };
+
+    let dict = PyDict::new(py);
+    dict.set_item("nodes", n)?;
+    dict.set_item("edges", edges)?;
+    dict.set_item("directed", directed)?;
+    dict.set_item("density", density)?;
+    dict.set_item("min_degree", if n == 0 { 0 } else { min_degree })?;
+    dict.set_item("max_degree", max_degree)?;
+    dict.set_item("mean_degree", mean_degree)?;
+    dict.set_item("std_degree", std_degree)?;
+    dict.set_item("mean_weighted_degree", mean_weighted_degree)?;
+    dict.set_item("components", components)?;
+    dict.set_item("average_clustering", average_clustering)?;
+    Ok(dict.unbind())
+}
 
 impl PyGraph {
     /// Diameter (longest shortest path). None if graph is empty or disconnected.
@@ -58,6 +195,70 @@ impl PyGraph {
     pub fn assortativity_impl(&self) -> f64 {
         assortativity(&self.graph)
     }
+
+    /// Degree and weighted degree of every node, in node order.
+    fn degree_sequence_impl(&self) -> Vec<(usize, f64)> {
+        degree_sequence(
+            self.graph.node_ids(),
+            self.graph.edges().map(|(u, v, &w)| (u, v, w)),
+            self.graph.is_directed(),
+        )
+    }
+
+    /// Number of nodes per degree.
+    pub fn degree_histogram_impl(&self) -> Vec<usize> {
+        degree_histogram_of(&self.degree_sequence_impl())
+    }
+
+    /// Equal-width histogram of weighted degrees.
+    pub fn weighted_degree_histogram_impl(&self, bins: usize) -> PyResult<(Vec<usize>, Vec<f64>)> {
+        weighted_degree_histogram_of(&self.degree_sequence_impl(), bins)
+    }
+
+    /// Size, density, degree, component, and clustering statistics in one dict.
+    pub fn summary_impl(
+        &self,
+        py: Python<'_>,
+        clustering_samples: Option<usize>,
+    ) -> PyResult<Py<PyDict>> {
+        let seq = self.degree_sequence_impl();
+        let clustering = clustering_estimate(
+            self.graph.node_ids().collect(),
+            clustering_samples,
+            || average_clustering_coefficient(&self.graph),
+            |n| clustering_coefficient(&self.graph, n),
+        )?;
+        summary_dict(
+            py,
+            &seq,
+            self.graph.edge_count(),
+            self.graph.is_directed(),
+            self.graph.density(),
+            self.count_components_impl(),
+            clustering,
+        )
+    }
+
+    /// Smallest `k` Laplacian eigenvalues in ascending order, or all of them.
+    pub fn laplacian_spectrum_impl(&self, k: Option<usize>) -> PyResult<Vec<f64>> {
+        laplacian_spectrum(&self.graph, k).map_err(|e| GraphinaError::new_err(e.to_string()))
+    }
+
+    /// Largest `k` adjacency eigenvalues in descending order, or all of them.
+    pub fn adjacency_spectrum_impl(&self, k: Option<usize>) -> PyResult<Vec<f64>> {
+        adjacency_spectrum(&self.graph, k).map_err(|e| GraphinaError::new_err(e.to_string()))
+    }
+
+    /// Algebraic connectivity and Fiedler vector keyed by Python node ID.
+    pub fn fiedler_vector_impl(&self) -> PyResult<(f64, HashMap<usize, f64>)> {
+        let (value, vector) =
+            fiedler_vector(&self.graph).map_err(|e| GraphinaError::new_err(e.to_string()))?;
+        let by_py_id = vector
+            .into_iter()
+            .filter_map(|(nid, x)| self.mapper.get_py(nid).map(|py_id| (py_id, x)))
+            .collect();
+        Ok((value, by_py_id))
+    }
 }
 
 impl PyDiGraph {
@@ -110,6 +311,49 @@ impl PyDiGraph {
     pub fn assortativity_impl(&self) -> f64 {
         assortativity(&self.graph)
     }
+
+    /// Degree and weighted degree of every node, in node order.
+    fn degree_sequence_impl(&self) -> Vec<(usize, f64)> {
+        degree_sequence(
+            self.graph.node_ids(),
+            self.graph.edges().map(|(u, v, &w)| (u, v, w)),
+            self.graph.is_directed(),
+        )
+    }
+
+    /// Number of nodes per degree.
+    pub fn degree_histogram_impl(&self) -> Vec<usize> {
+        degree_histogram_of(&self.degree_sequence_impl())
+    }
+
+    /// Equal-width histogram of weighted degrees.
+    pub fn weighted_degree_histogram_impl(&self, bins: usize) -> PyResult<(Vec<usize>, Vec<f64>)> {
+        weighted_degree_histogram_of(&self.degree_sequence_impl(), bins)
+    }
+
+    /// Size, density, degree, component, and clustering statistics in one dict.
+    pub fn summary_impl(
+        &self,
+        py: Python<'_>,
+        clustering_samples: Option<usize>,
+    ) -> PyResult<Py<PyDict>> {
+        let seq = self.degree_sequence_impl();
+        let clustering = clustering_estimate(
+            self.graph.node_ids().collect(),
+            clustering_samples,
+            || average_clustering_coefficient(&self.graph),
+            |n| clustering_coefficient(&self.graph, n),
+        )?;
+        summary_dict(
+            py,
+            &seq,
+            self.graph.edge_count(),
+            self.graph.is_directed(),
+            self.graph.density(),
+            self.count_components_impl(),
+            clustering,
+        )
+    }
 }
 
 pub fn register_metrics(_m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -0,0 +1,200 @@
+//! Batch evaluation of per-node algorithms.
+//!
+//! Calling a per-node method such as `clustering_of` in a Python loop crosses the GIL once per
+//! node. `run_batch` instead resolves the node list once, evaluates the named function for every
+//! node on a Rayon pool, and builds a single result dict.
+
+use petgraph::EdgeType;
+use pyo3::exceptions::{PyTypeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use rayon::prelude::*;
+
+use crate::core::id_map::IdMapper;
+use crate::{GraphinaError, PyDiGraph, PyGraph};
+use graphina::centrality::other::local_reaching_centrality_of;
+use graphina::core::types::{BaseGraph, GraphConstructor, NodeId};
+use graphina::metrics::{clustering_coefficient, triangles};
+
+/// A per-node function `run_batch` knows how to evaluate.
+#[derive(Clone, Copy)]
+enum BatchFunc {
+    Clustering,
+    Triangles,
+    Degree,
+    LocalReaching(usize),
+}
+
+impl BatchFunc {
+    const NAMES: &'static str = "clustering_of, triangles_of, degree, local_reaching";
+
+    fn parse(name: &str, distance: usize) -> PyResult<Self> {
+        match name {
+            "clustering_of" => Ok(Self::Clustering),
+            "triangles_of" => Ok(Self::Triangles),
+            "degree" => Ok(Self::Degree),
+            "local_reaching" => Ok(Self::LocalReaching(distance)),
+            other => Err(PyValueError::new_err(format!(
+                "Unknown batch function '{}'; expected one of: {}",
+                other,
+                Self::NAMES
+            ))),
+        }
+    }
+}
+
+/// Results of a batch, kept as integers where the function counts something.
+enum BatchValues {
+    Floats(Vec<f64>),
+    Counts(Vec<usize>),
+}
+
+fn evaluate<Ty>(
+    graph: &BaseGraph<i64, f64, Ty>,
+    nodes: &[NodeId],
+    func: BatchFunc,
+) -> PyResult<BatchValues>
+where
+    Ty: GraphConstructor<i64, f64> + EdgeType + Send + Sync,
+{
+    Ok(match func {
+        BatchFunc::Clustering => BatchValues::Floats(
+            nodes
+                .par_iter()
+                .map(|&n| clustering_coefficient(graph, n))
+                .collect(),
+        ),
+        BatchFunc::Triangles => {
+            BatchValues::Counts(nodes.par_iter().map(|&n| triangles(graph, n)).collect())
+        }
+        BatchFunc::Degree => BatchValues::Counts(
+            nodes
+                .par_iter()
+                .map(|&n| graph.degree(n).unwrap_or(0))
+                .collect(),
+        ),
+        BatchFunc::LocalReaching(distance) => BatchValues::Floats(
+            nodes
+                .par_iter()
+                .map(|&n| local_reaching_centrality_of(graph, n, distance))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| GraphinaError::new_err(format!("local_reaching failed: {}", e)))?,
+        ),
+    })
+}
+
+fn run_batch_on<Ty>(
+    py: Python<'_>,
+    graph: &BaseGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    func: BatchFunc,
+    nodes: Option<Vec<usize>>,
+    n_threads: Option<usize>,
+) -> PyResult<Py<PyDict>>
+where
+    Ty: GraphConstructor<i64, f64> + EdgeType + Send + Sync,
+{
+    let (py_ids, internal): (Vec<usize>, Vec<NodeId>) = match nodes {
+        Some(ids) => {
+            let mut internal = Vec::with_capacity(ids.len());
+            for &py_id in &ids {
+                internal.push(mapper.get_internal(py_id).ok_or_else(|| {
+                    GraphinaError::new_err(format!("Invalid node id in nodes: {}", py_id))
+                })?);
+            }
+            (ids, internal)
+        }
+        None => graph
+            .node_ids()
+            .filter_map(|nid| mapper.get_py(nid).map(|py_id| (py_id, nid)))
+            .unzip(),
+    };
+
+    let values = py.detach(|| match n_threads {
+        None => evaluate(graph, &internal, func),
+        Some(0) => Err(PyValueError::new_err("n_threads must be positive")),
+        Some(n) => rayon::ThreadPoolBuilder::new()
+            .num_threads(n)
+            .build()
+            .map_err(|e| GraphinaError::new_err(format!("Failed to start thread pool: {}", e)))?
+            .install(|| evaluate(graph, &internal, func)),
+    })?;
+
+    let dict = PyDict::new(py);
+    match values {
+        BatchValues::Floats(vals) => {
+            for (py_id, v) in py_ids.into_iter().zip(vals) {
+                dict.set_item(py_id, v)?;
+            }
+        }
+        BatchValues::Counts(vals) => {
+            for (py_id, v) in py_ids.into_iter().zip(vals) {
+                dict.set_item(py_id, v)?;
+            }
+        }
+    }
+    Ok(dict.unbind())
+}
+
+/// Evaluate a per-node algorithm over many nodes in parallel.
+///
+/// Parameters
+/// ----------
+/// graph : PyGraph or PyDiGraph
+///     The input graph.
+/// func_name : str
+///     One of ``"clustering_of"``, ``"triangles_of"``, ``"degree"``, or ``"local_reaching"``.
+/// nodes : list of int, optional
+///     Node IDs to evaluate. Defaults to every node.
+/// n_threads : int, optional
+///     Size of a dedicated thread pool for this call. Defaults to the global Rayon pool.
+/// distance : int
+///     Hop limit used by ``"local_reaching"``; ignored by the other functions.
+///
+/// Returns
+/// -------
+/// dict
+///     Mapping of node ID to value. Counts (``triangles_of``, ``degree``) are ints, the
+///     rest are floats.
+///
+/// Raises
+/// ------
+/// ValueError
+///     If ``func_name`` is unknown or ``n_threads`` is zero.
+/// GraphinaError
+///     If a node ID is invalid.
+/// TypeError
+///     If graph is not PyGraph or PyDiGraph.
+#[pyfunction]
+#[pyo3(signature = (graph, func_name, nodes=None, n_threads=None, distance=1))]
+pub fn run_batch(
+    py: Python<'_>,
+    graph: &Bound<'_, PyAny>,
+    func_name: &str,
+    nodes: Option<Vec<usize>>,
+    n_threads: Option<usize>,
+    distance: usize,
+) -> PyResult<Py<PyDict>> {
+    let func = BatchFunc::parse(func_name, distance)?;
+    if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
+        run_batch_on(
+            py,
+            &py_graph.graph,
+            &py_graph.mapper,
+            func,
+            nodes,
+            n_threads,
+        )
+    } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
+        run_batch_on(
+            py,
+            &py_graph.graph,
+            &py_graph.mapper,
+            func,
+            nodes,
+            n_threads,
+        )
+    } else {
+        Err(PyTypeError::new_err("Expected PyGraph or PyDiGraph"))
+    }
+}
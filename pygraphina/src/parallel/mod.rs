@@ -12,6 +12,9 @@ use graphina::parallel::{
 
 use crate::{PyDiGraph, PyGraph};
 
+mod batch;
+pub use batch::run_batch;
+
 /// Perform parallel BFS from multiple starting nodes.
 ///
 /// Parameters
@@ -33,7 +36,11 @@ use crate::{PyDiGraph, PyGraph};
 /// TypeError
 ///     If graph is not PyGraph or PyDiGraph.
 #[pyfunction]
-pub fn bfs_parallel(graph: &Bound<'_, PyAny>, starts: Vec<usize>) -> PyResult<Vec<Vec<usize>>> {
+pub fn bfs_parallel(
+    py: Python<'_>,
+    graph: &Bound<'_, PyAny>,
+    starts: Vec<usize>,
+) -> PyResult<Vec<Vec<usize>>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
         let internal_starts: Vec<_> = starts
             .iter()
@@ -44,7 +51,8 @@ pub fn bfs_parallel(graph: &Bound<'_, PyAny>, starts: Vec<usize>) -> PyResult<Ve
             return Err(crate::GraphinaError::new_err("Invalid node IDs in starts"));
         }
 
-        let results = bfs_parallel_core(&py_graph.graph, &internal_starts);
+        let g = &py_graph.graph;
+        let results = py.detach(|| bfs_parallel_core(g, &internal_starts));
 
         Ok(results
             .into_iter()
@@ -65,7 +73,8 @@ pub fn bfs_parallel(graph: &Bound<'_, PyAny>, starts: Vec<usize>) -> PyResult<Ve
             return Err(crate::GraphinaError::new_err("Invalid node IDs in starts"));
         }
 
-        let results = bfs_parallel_core(&py_graph.graph, &internal_starts);
+        let g = &py_graph.graph;
+        let results = py.detach(|| bfs_parallel_core(g, &internal_starts));
 
         Ok(results
             .into_iter()
@@ -100,9 +109,13 @@ pub fn bfs_parallel(graph: &Bound<'_, PyAny>, starts: Vec<usize>) -> PyResult<Ve
 /// TypeError
 ///     If graph is not PyGraph or PyDiGraph.
 #[pyfunction]
-pub fn degrees_parallel(graph: &Bound<'_, PyAny>) -> PyResult<HashMap<usize, usize>> {
+pub fn degrees_parallel(
+    py: Python<'_>,
+    graph: &Bound<'_, PyAny>,
+) -> PyResult<HashMap<usize, usize>> {
     if let Ok(py_graph) = graph.extract::<PyRef<PyGraph>>() {
-        let internal_degrees = degrees_parallel_core(&py_graph.graph);
+        let g = &py_graph.graph;
+        let internal_degrees = py.detach(|| degrees_parallel_core(g));
 
         let py_degrees: HashMap<usize, usize> = internal_degrees
             .into_iter()
@@ -114,7 +127,8 @@ pub fn degrees_parallel(graph: &Bound<'_, PyAny>) -> PyResult<HashMap<usize, usi
 
         Ok(py_degrees)
     } else if let Ok(py_graph) = graph.extract::<PyRef<PyDiGraph>>() {
-        let internal_degrees = degrees_parallel_core(&py_graph.graph);
+        let g = &py_graph.graph;
+        let internal_degrees = py.detach(|| degrees_parallel_core(g));
 
         let py_degrees: HashMap<usize, usize> = internal_degrees
             .into_iter()
@@ -149,8 +163,11 @@ pub fn degrees_parallel(graph: &Bound<'_, PyAny>) -> PyResult<HashMap<usize, usi
 /// TypeError
 ///     If graph is not PyGraph.
 #[pyfunction]
-pub fn connected_components_parallel(graph: &PyGraph) -> PyResult<HashMap<usize, usize>> {
-    let component_map = connected_components_parallel_core(&graph.graph);
+pub fn connected_components_parallel(
+    py: Python<'_>,
+    graph: &PyGraph,
+) -> PyResult<HashMap<usize, usize>> {
+    let component_map = py.detach(|| connected_components_parallel_core(&graph.graph));
 
     let py_component_map: HashMap<usize, usize> = component_map
         .into_iter()
@@ -192,6 +209,7 @@ pub fn connected_components_parallel(graph: &PyGraph) -> PyResult<HashMap<usize,
 #[pyfunction]
 #[pyo3(signature = (graph, damping=0.85, max_iterations=100, tolerance=1e-6, nstart=None))]
 pub fn pagerank_parallel(
+    py: Python<'_>,
     graph: &Bound<'_, PyAny>,
     damping: f64,
     max_iterations: usize,
@@ -211,13 +229,10 @@ pub fn pagerank_parallel(
             None
         };
 
-        let ranks = pagerank_parallel_core(
-            &py_graph.graph,
-            damping,
-            max_iterations,
-            tolerance,
-            nstart_map.as_ref(),
-        );
+        let g = &py_graph.graph;
+        let ranks = py.detach(|| {
+            pagerank_parallel_core(g, damping, max_iterations, tolerance, nstart_map.as_ref())
+        });
         let py_ranks: HashMap<usize, f64> = ranks
             .into_iter()
             .filter_map(|(nid, rank)| {
@@ -239,13 +254,10 @@ pub fn pagerank_parallel(
             None
         };
 
-        let ranks = pagerank_parallel_core(
-            &py_graph.graph,
-            damping,
-            max_iterations,
-            tolerance,
-            nstart_map.as_ref(),
-        );
+        let g = &py_graph.graph;
+        let ranks = py.detach(|| {
+            pagerank_parallel_core(g, damping, max_iterations, tolerance, nstart_map.as_ref())
+        });
         let py_ranks: HashMap<usize, f64> = ranks
             .into_iter()
             .filter_map(|(nid, rank)| {
@@ -278,8 +290,8 @@ pub fn pagerank_parallel(
 /// TypeError
 ///     If graph is not PyGraph.
 #[pyfunction]
-pub fn triangles_parallel(graph: &PyGraph) -> PyResult<HashMap<usize, usize>> {
-    let triangles = triangles_parallel_core(&graph.graph);
+pub fn triangles_parallel(py: Python<'_>, graph: &PyGraph) -> PyResult<HashMap<usize, usize>> {
+    let triangles = py.detach(|| triangles_parallel_core(&graph.graph));
 
     let py_triangles: HashMap<usize, usize> = triangles
         .into_iter()
@@ -309,8 +321,11 @@ pub fn triangles_parallel(graph: &PyGraph) -> PyResult<HashMap<usize, usize>> {
 /// TypeError
 ///     If graph is not PyGraph.
 #[pyfunction]
-pub fn clustering_coefficients_parallel(graph: &PyGraph) -> PyResult<HashMap<usize, f64>> {
-    let coeffs = clustering_coefficients_parallel_core(&graph.graph);
+pub fn clustering_coefficients_parallel(
+    py: Python<'_>,
+    graph: &PyGraph,
+) -> PyResult<HashMap<usize, f64>> {
+    let coeffs = py.detach(|| clustering_coefficients_parallel_core(&graph.graph));
 
     let py_coeffs: HashMap<usize, f64> = coeffs
         .into_iter()
@@ -347,6 +362,7 @@ pub fn clustering_coefficients_parallel(graph: &PyGraph) -> PyResult<HashMap<usi
 ///     If graph is not PyGraph or PyDiGraph.
 #[pyfunction]
 pub fn shortest_paths_parallel(
+    py: Python<'_>,
     graph: &Bound<'_, PyAny>,
     sources: Vec<usize>,
 ) -> PyResult<Vec<HashMap<usize, usize>>> {
@@ -360,7 +376,8 @@ pub fn shortest_paths_parallel(
             return Err(crate::GraphinaError::new_err("Invalid node IDs in sources"));
         }
 
-        let paths = shortest_paths_parallel_core(&py_graph.graph, &internal_sources);
+        let g = &py_graph.graph;
+        let paths = py.detach(|| shortest_paths_parallel_core(g, &internal_sources));
 
         Ok(paths
             .into_iter()
@@ -384,7 +401,8 @@ pub fn shortest_paths_parallel(
             return Err(crate::GraphinaError::new_err("Invalid node IDs in sources"));
         }
 
-        let paths = shortest_paths_parallel_core(&py_graph.graph, &internal_sources);
+        let g = &py_graph.graph;
+        let paths = py.detach(|| shortest_paths_parallel_core(g, &internal_sources));
 
         Ok(paths
             .into_iter()
@@ -413,5 +431,6 @@ pub fn register_parallel(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(triangles_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(clustering_coefficients_parallel, m)?)?;
     m.add_function(wrap_pyfunction!(shortest_paths_parallel, m)?)?;
+    m.add_function(wrap_pyfunction!(run_batch, m)?)?;
     Ok(())
 }
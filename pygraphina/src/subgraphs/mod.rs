@@ -1,10 +1,59 @@
+use petgraph::EdgeType;
 use pyo3::exceptions::PyValueError;
 use pyo3::prelude::*;
-use std::collections::HashSet;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 
+use crate::core::id_map::IdMapper;
 use crate::{PyDiGraph, PyGraph};
+use graphina::core::types::{BaseGraph, GraphConstructor, NodeId};
 use graphina::subgraphs::SubgraphOps;
 
+/// Nodes within `max_distance` of `start` by summed edge cost, nearest first.
+///
+/// `weight(u, v, w)` overrides the stored weight of an edge, or returns `None` to make it
+/// impassable. The first exception it raises stops further calls and is re-raised.
+fn weighted_reach<Ty>(
+    graph: &BaseGraph<i64, f64, Ty>,
+    mapper: &IdMapper,
+    start: usize,
+    max_distance: f64,
+    weight: Option<&Bound<'_, PyAny>>,
+) -> PyResult<Vec<(NodeId, f64)>>
+where
+    Ty: GraphConstructor<i64, f64> + EdgeType,
+{
+    let start_id = mapper
+        .get_internal(start)
+        .ok_or_else(|| PyValueError::new_err("Invalid start node id"))?;
+
+    let failure: RefCell<Option<PyErr>> = RefCell::new(None);
+    let cost = |u: NodeId, v: NodeId, w: &f64| -> Option<f64> {
+        let Some(f) = weight else {
+            return Some(*w);
+        };
+        if failure.borrow().is_some() {
+            return None;
+        }
+        let (pu, pv) = (mapper.get_py(u)?, mapper.get_py(v)?);
+        match f
+            .call1((pu, pv, *w))
+            .and_then(|r| r.extract::<Option<f64>>())
+        {
+            Ok(c) => c,
+            Err(e) => {
+                *failure.borrow_mut() = Some(e);
+                None
+            }
+        }
+    };
+    let reached = graph.weighted_k_hop_neighbors(start_id, max_distance, cost);
+    if let Some(e) = failure.into_inner() {
+        return Err(e);
+    }
+    reached.map_err(|e| PyValueError::new_err(format!("Weighted neighborhood failed: {}", e)))
+}
+
 impl PyGraph {
     /// Extract a subgraph containing only the specified nodes.
     ///
@@ -143,6 +192,72 @@ impl PyGraph {
             .collect())
     }
 
+    /// Extract the ego graph of all nodes within a total edge cost of the center.
+    ///
+    /// Args:
+    ///     center: Center node ID
+    ///     max_distance: Maximum summed edge cost from center
+    ///     weight: Optional fn(u:int, v:int, w:float) -> float | None giving edge costs
+    ///
+    /// Returns:
+    ///     PyGraph: Weighted ego graph
+    ///
+    /// Example:
+    ///     >>> g = pygraphina.PyGraph()
+    ///     >>> n0 = g.add_node(0)
+    ///     >>> n1 = g.add_node(1)
+    ///     >>> g.add_edge(n0, n1, 2.5)
+    ///     >>> ego = g.weighted_ego_graph(n0, 3.0)
+    pub(crate) fn weighted_ego_graph_impl(
+        &self,
+        center: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyGraph> {
+        let nodes: Vec<NodeId> =
+            weighted_reach(&self.graph, &self.mapper, center, max_distance, weight)?
+                .into_iter()
+                .map(|(node, _)| node)
+                .collect();
+        let ego = self
+            .graph
+            .subgraph(&nodes)
+            .map_err(|e| PyValueError::new_err(format!("Weighted ego graph failed: {}", e)))?;
+
+        let mut out = PyGraph::new();
+        out.populate_from_internal(ego);
+        Ok(out)
+    }
+
+    /// Get all nodes within a total edge cost of the start node, with their distances.
+    ///
+    /// Args:
+    ///     start: Starting node ID
+    ///     max_distance: Maximum summed edge cost from start
+    ///     weight: Optional fn(u:int, v:int, w:float) -> float | None giving edge costs
+    ///
+    /// Returns:
+    ///     dict: Node ID to shortest distance from start
+    ///
+    /// Example:
+    ///     >>> g = pygraphina.PyGraph()
+    ///     >>> n0 = g.add_node(0)
+    ///     >>> n1 = g.add_node(1)
+    ///     >>> g.add_edge(n0, n1, 2.5)
+    ///     >>> reach = g.weighted_k_hop_neighbors(n0, 3.0)
+    pub(crate) fn weighted_k_hop_neighbors_impl(
+        &self,
+        start: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<HashMap<usize, f64>> {
+        let reached = weighted_reach(&self.graph, &self.mapper, start, max_distance, weight)?;
+        Ok(reached
+            .into_iter()
+            .filter_map(|(nid, d)| self.mapper.get_py(nid).map(|py_id| (py_id, d)))
+            .collect())
+    }
+
     /// Get the connected component containing the start node.
     ///
     /// Args:
@@ -390,6 +505,72 @@ impl PyDiGraph {
             .collect())
     }
 
+    /// Extract the ego graph of all nodes within a total edge cost of the center.
+    ///
+    /// Args:
+    ///     center: Center node ID
+    ///     max_distance: Maximum summed edge cost from center
+    ///     weight: Optional fn(u:int, v:int, w:float) -> float | None giving edge costs
+    ///
+    /// Returns:
+    ///     PyDiGraph: Weighted ego graph
+    ///
+    /// Example:
+    ///     >>> g = pygraphina.PyDiGraph()
+    ///     >>> n0 = g.add_node(0)
+    ///     >>> n1 = g.add_node(1)
+    ///     >>> g.add_edge(n0, n1, 2.5)
+    ///     >>> ego = g.weighted_ego_graph(n0, 3.0)
+    pub(crate) fn weighted_ego_graph_impl(
+        &self,
+        center: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<PyDiGraph> {
+        let nodes: Vec<NodeId> =
+            weighted_reach(&self.graph, &self.mapper, center, max_distance, weight)?
+                .into_iter()
+                .map(|(node, _)| node)
+                .collect();
+        let ego = self
+            .graph
+            .subgraph(&nodes)
+            .map_err(|e| PyValueError::new_err(format!("Weighted ego graph failed: {}", e)))?;
+
+        let mut out = PyDiGraph::new();
+        out.populate_from_internal(ego);
+        Ok(out)
+    }
+
+    /// Get all nodes within a total edge cost of the start node, with their distances.
+    ///
+    /// Args:
+    ///     start: Starting node ID
+    ///     max_distance: Maximum summed edge cost from start
+    ///     weight: Optional fn(u:int, v:int, w:float) -> float | None giving edge costs
+    ///
+    /// Returns:
+    ///     dict: Node ID to shortest distance from start
+    ///
+    /// Example:
+    ///     >>> g = pygraphina.PyDiGraph()
+    ///     >>> n0 = g.add_node(0)
+    ///     >>> n1 = g.add_node(1)
+    ///     >>> g.add_edge(n0, n1, 2.5)
+    ///     >>> reach = g.weighted_k_hop_neighbors(n0, 3.0)
+    pub(crate) fn weighted_k_hop_neighbors_impl(
+        &self,
+        start: usize,
+        max_distance: f64,
+        weight: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<HashMap<usize, f64>> {
+        let reached = weighted_reach(&self.graph, &self.mapper, start, max_distance, weight)?;
+        Ok(reached
+            .into_iter()
+            .filter_map(|(nid, d)| self.mapper.get_py(nid).map(|py_id| (py_id, d)))
+            .collect())
+    }
+
     /// Get the weakly connected component containing the start node.
     ///
     /// Args:
@@ -0,0 +1,42 @@
+import pytest
+
+import pygraphina
+
+
+class TestAttrGraph:
+
+    def setup_method(self):
+        self.g = pygraphina.AttrGraph()
+        self.a = self.g.add_node(color='red')
+        self.b = self.g.add_node()
+        self.c = self.g.add_node(name='c')
+        self.g.add_edge(self.a, self.b, weight=2.0)
+        self.g.add_edge(self.b, self.c)
+
+    def test_node_attributes_are_mutable(self):
+        self.g.nodes[self.b]['color'] = 'blue'
+        assert self.g.nodes[self.b] == {'color': 'blue'}
+        assert self.g.nodes.data('color', 'none') == [(0, 'red'), (1, 'blue'), (2, 'none')]
+        with pytest.raises(KeyError):
+            self.g.nodes[99]
+
+    def test_add_edge_updates_existing_edge(self):
+        self.g.add_edge(self.b, self.a, label='x')
+        assert self.g.edge_count() == 2
+        assert self.g.edges[self.a, self.b] == {'weight': 2.0, 'label': 'x'}
+        assert list(self.g.edges) == [(0, 1), (1, 2)]
+        assert (self.c, self.b) in self.g.edges
+
+    def test_remove_returns_attributes(self):
+        assert self.g.remove_edge(self.a, self.b) == {'weight': 2.0}
+        assert self.g.remove_node(self.c) == {'name': 'c'}
+        assert list(self.g) == [self.a, self.b]
+        assert len(self.g.edges) == 0
+
+    def test_to_graph_reads_weights(self):
+        h = self.g.to_graph()
+        assert h.node_count() == 3
+        assert h.edge_count() == 2
+        self.g.edges[self.b, self.c]['weight'] = float('nan')
+        with pytest.raises(ValueError):
+            self.g.to_graph()
@@ -0,0 +1,21 @@
+import pygraphina as pg
+import pytest
+
+
+def test_build_info_reports_version_and_features():
+    info = pg.build_info()
+    assert isinstance(info['version'], str)
+    assert info['version']
+    assert 'networkx' in info['features'] + info['missing_features']
+    assert not set(info['features']) & set(info['missing_features'])
+
+
+@pytest.mark.skipif('networkx' in pg.build_info()['features'], reason='networkx feature enabled')
+def test_disabled_feature_raises_feature_error():
+    for name in ('to_networkx', 'from_networkx', 'to_node_dataframe', 'to_edge_dataframe'):
+        with pytest.raises(pg.GraphinaFeatureError, match="'networkx' feature"):
+            getattr(pg, name)(pg.Graph())
+
+
+def test_feature_error_is_a_graphina_error():
+    assert issubclass(pg.GraphinaFeatureError, pg.GraphinaError)
@@ -102,3 +102,48 @@ def test_edge_list_roundtrip(tmp_path: 'pytest.TempPathFactory'):
         return sorted(ds)
 
     assert degseq(g2) == degseq(g)
+
+
+def test_summary_and_degree_histograms_match_networkx():
+    g, (a, b, c) = build_triangle_pg()
+    d = g.add_node(4)
+    g.add_edge(c, d, 3.0)
+    H = build_triangle_nx()
+    H.add_edge(2, 3, weight=3.0)
+
+    assert g.degree_histogram() == nx.degree_histogram(H)
+    s = g.summary()
+    assert s["nodes"] == 4 and s["edges"] == 4 and s["directed"] is False
+    assert math.isclose(s["density"], nx.density(H))
+    assert (s["min_degree"], s["max_degree"]) == (1, 3)
+    assert math.isclose(s["mean_degree"], 2.0)
+    assert math.isclose(s["mean_weighted_degree"], 3.0)
+    assert s["components"] == 1
+    assert math.isclose(s["average_clustering"], nx.average_clustering(H))
+    assert 0.0 <= g.summary(clustering_samples=2)["average_clustering"] <= 1.0
+
+    counts, edges = g.weighted_degree_histogram(bins=2)
+    assert sum(counts) == 4 and len(edges) == 3
+    with pytest.raises(ValueError):
+        g.weighted_degree_histogram(bins=0)
+    with pytest.raises(ValueError):
+        g.summary(clustering_samples=0)
+
+
+def test_spectra_and_fiedler_vector():
+    g, (a, b, c) = build_triangle_pg()
+    assert g.laplacian_spectrum() == pytest.approx([0.0, 3.0, 3.0], abs=1e-9)
+    assert g.adjacency_spectrum(k=1) == pytest.approx([2.0])
+    d = g.add_node(4)
+    g.add_edge(c, d, 0.1)
+    value, vector = g.fiedler_vector()
+    assert value == pytest.approx(g.laplacian_spectrum(k=2)[1])
+    assert set(vector) == {a, b, c, d}
+    assert (vector[d] > 0) != (vector[a] > 0)
+    assert (vector[a] > 0) == (vector[b] > 0)
+
+    g.add_node(5)
+    with pytest.raises(pygraphina.GraphinaError):
+        g.fiedler_vector()
+    with pytest.raises(pygraphina.GraphinaError):
+        g.laplacian_spectrum(k=0)
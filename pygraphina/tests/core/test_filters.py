@@ -25,3 +25,37 @@ def test_filter_edges_predicate():
     assert g2.edge_count() == 2
     edge_weights = {w for _, _, w in g2.edges.data('weight')}
     assert edge_weights == {3.0, 4.0}, 'Should have edges with weights 3.0 and 4.0'
+
+
+def test_remove_nodes_in_place_is_atomic():
+    g, (a, b, c, d) = build_graph()
+    assert g.remove_nodes([a, c, a]) == 2
+    assert set(g.nodes) == {b, d}
+    assert g.edge_count() == 0
+    try:
+        g.remove_nodes([b, 999])
+    except ValueError:
+        pass
+    else:
+        raise AssertionError('Expected ValueError for an unknown node')
+    assert set(g.nodes) == {b, d}
+
+
+def test_remove_edges_and_retain_edges_above():
+    g, (a, b, c, d) = build_graph()
+    assert g.remove_edges([(b, a), (c, d)]) == 2
+    assert g.edge_count() == 2
+    try:
+        g.remove_edges([(b, c), (a, c)])
+    except ValueError:
+        pass
+    else:
+        raise AssertionError('Expected ValueError for a missing edge')
+    assert g.contains_edge(b, c)
+
+    h = pg.PyDiGraph()
+    x, y, z = h.add_nodes_from([0, 1, 2])
+    h.add_edges_from([(x, y, 0.5), (y, z, 1.0), (z, x, 2.0)])
+    assert h.retain_edges_above(1.0) == 2
+    assert h.edge_count() == 1 and h.contains_edge(z, x)
+    assert h.node_count() == 3
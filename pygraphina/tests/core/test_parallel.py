@@ -315,3 +315,49 @@ class TestShortestPathsParallel:
         g = pygraphina.complete_graph(5)
         paths = pygraphina.parallel.shortest_paths_parallel(g, [])
         assert len(paths) == 0
+
+
+class TestRunBatch:
+
+    def create_triangle_with_tail(self):
+        g = pygraphina.PyGraph()
+        nodes = [g.add_node(i) for i in range(4)]
+        g.add_edge(nodes[0], nodes[1], 1.0)
+        g.add_edge(nodes[1], nodes[2], 1.0)
+        g.add_edge(nodes[2], nodes[0], 1.0)
+        g.add_edge(nodes[2], nodes[3], 1.0)
+        return g, nodes
+
+    def test_matches_per_node_methods(self):
+        g, nodes = self.create_triangle_with_tail()
+        clustering = pygraphina.parallel.run_batch(g, "clustering_of")
+        triangles = pygraphina.parallel.run_batch(g, "triangles_of", n_threads=2)
+        assert set(clustering) == set(nodes)
+        for n in nodes:
+            assert clustering[n] == pytest.approx(g.clustering_of(n))
+            assert triangles[n] == g.triangles_of(n)
+            assert isinstance(triangles[n], int)
+
+    def test_subset_and_local_reaching(self):
+        g, nodes = self.create_triangle_with_tail()
+        reach = pygraphina.parallel.run_batch(
+            g, "local_reaching", nodes=[nodes[0], nodes[3]], distance=1
+        )
+        assert reach == {nodes[0]: 3.0, nodes[3]: 2.0}
+        degrees = pygraphina.parallel.run_batch(g, "degree", nodes=[nodes[2]])
+        assert degrees == {nodes[2]: 3}
+
+    def test_directed_graph(self):
+        g = pygraphina.PyDiGraph()
+        a, b = g.add_node(0), g.add_node(1)
+        g.add_edge(a, b, 1.0)
+        assert pygraphina.parallel.run_batch(g, "local_reaching") == {a: 2.0, b: 1.0}
+
+    def test_invalid_arguments(self):
+        g, nodes = self.create_triangle_with_tail()
+        with pytest.raises(ValueError):
+            pygraphina.parallel.run_batch(g, "no_such_function")
+        with pytest.raises(ValueError):
+            pygraphina.parallel.run_batch(g, "degree", n_threads=0)
+        with pytest.raises(pygraphina.GraphinaError):
+            pygraphina.parallel.run_batch(g, "degree", nodes=[999])
@@ -0,0 +1,80 @@
+import itertools
+import math
+
+import networkx as nx
+import pygraphina as pg
+import pytest
+
+
+def build_grid(cls):
+    # 3x3 grid with unit weights; node id = row * 3 + col
+    g = cls()
+    ids = [g.add_node(i) for i in range(9)]
+    for r in range(3):
+        for c in range(3):
+            if c < 2:
+                g.add_edge(ids[r * 3 + c], ids[r * 3 + c + 1], 1.0)
+            if r < 2:
+                g.add_edge(ids[r * 3 + c], ids[(r + 1) * 3 + c], 1.0)
+    return g, ids
+
+
+def manhattan(goal):
+    return lambda n: abs(n // 3 - goal // 3) + abs(n % 3 - goal % 3)
+
+
+def test_a_star_matches_dijkstra():
+    for cls in (pg.PyGraph, pg.PyDiGraph):
+        g, ids = build_grid(cls)
+        cost, path = g.a_star(ids[0], ids[8], heuristic=manhattan(8))
+        assert cost == 4.0
+        assert path[0] == ids[0] and path[-1] == ids[8] and len(path) == 5
+        assert g.a_star(ids[0], ids[8])[0] == g.shortest_path(ids[0], ids[8])[0]
+
+
+def test_a_star_weight_callback_and_errors():
+    g, ids = build_grid(pg.PyGraph)
+    # Skip the whole top row except its first edge, doubling the rest
+    blocked = {(ids[1], ids[2])}
+    cost, path = g.a_star(
+        ids[0], ids[2],
+        weight=lambda u, v, w: None if (u, v) in blocked else 2 * w,
+    )
+    assert cost == 8.0 and ids[1] not in path[2:]
+
+    h = pg.PyDiGraph()
+    a, b = h.add_node(0), h.add_node(1)
+    h.add_edge(b, a, 1.0)
+    assert h.a_star(a, b) is None
+
+    with pytest.raises(ValueError):
+        g.a_star(ids[0], 99)
+    with pytest.raises(ValueError):
+        g.a_star(ids[0], ids[8], weight=lambda u, v, w: -1.0)
+    with pytest.raises(ZeroDivisionError):
+        g.a_star(ids[0], ids[8], heuristic=lambda n: 1 / 0)
+
+
+def test_all_simple_paths_matches_networkx():
+    g, ids = build_grid(pg.PyGraph)
+    H = nx.grid_2d_graph(3, 3)
+    H = nx.relabel_nodes(H, {(r, c): r * 3 + c for r, c in H.nodes})
+    ours = sorted(map(tuple, g.all_simple_paths(ids[0], ids[8])))
+    theirs = sorted(map(tuple, nx.all_simple_paths(H, 0, 8)))
+    assert ours == theirs
+    short = g.all_simple_paths(ids[0], ids[8], cutoff=4)
+    assert len(short) == math.comb(4, 2)
+    assert all(len(p) == 5 for p in short)
+
+
+def test_k_shortest_paths_matches_networkx():
+    g, ids = build_grid(pg.PyGraph)
+    H = nx.grid_2d_graph(3, 3)
+    H = nx.relabel_nodes(H, {(r, c): r * 3 + c for r, c in H.nodes})
+    ours = g.k_shortest_paths(ids[0], ids[8], 8)
+    theirs = list(itertools.islice(nx.shortest_simple_paths(H, 0, 8), 8))
+    assert [cost for cost, _ in ours] == [float(len(p) - 1) for p in theirs]
+    assert all(path[0] == ids[0] and path[-1] == ids[8] for _, path in ours)
+    assert len({tuple(path) for _, path in ours}) == 8
+    with pytest.raises(ValueError):
+        g.k_shortest_paths(ids[0], ids[8], 0)
@@ -111,3 +111,52 @@ class TestSubgraphProperties:
             sub = g.subgraph(nodes[:10])
             assert sub.node_count() == 10
             assert sub.edge_count() >= 0
+
+
+class TestWeightedNeighborhoods:
+
+    def test_weighted_k_hop_uses_path_cost(self):
+        g, nodes = create_test_graph()
+        reach = g.weighted_k_hop_neighbors(nodes[0], 3.0)
+        assert reach == {nodes[0]: 0.0, nodes[1]: 1.0, nodes[2]: 3.0}
+
+    def test_weighted_ego_graph(self):
+        g, nodes = create_test_graph()
+        ego = g.weighted_ego_graph(nodes[1], 3.0)
+        assert ego.node_count() == 3
+        assert ego.edge_count() == 2
+
+    def test_weight_callback_can_block_edges(self):
+        g, nodes = create_test_graph()
+        reach = g.weighted_k_hop_neighbors(
+            nodes[1], 6.0, weight=lambda u, v, w: None if nodes[2] in (u, v) else w
+        )
+        assert nodes[2] not in reach
+        assert reach[nodes[3]] == 5.0
+        assert reach[nodes[0]] == 1.0
+
+    def test_directed_follows_outgoing_edges(self):
+        g = pygraphina.PyDiGraph()
+        a, b, c = g.add_node(0), g.add_node(1), g.add_node(2)
+        g.add_edge(a, b, 1.0)
+        g.add_edge(c, a, 1.0)
+        assert g.weighted_k_hop_neighbors(a, 10.0) == {a: 0.0, b: 1.0}
+        assert g.weighted_ego_graph(a, 10.0).node_count() == 2
+
+    def test_invalid_input(self):
+        g, nodes = create_test_graph()
+        with pytest.raises(ValueError):
+            g.weighted_ego_graph(999, 1.0)
+        with pytest.raises(ValueError):
+            g.weighted_k_hop_neighbors(nodes[0], -1.0)
+        with pytest.raises(ValueError):
+            g.weighted_k_hop_neighbors(nodes[0], 5.0, weight=lambda u, v, w: -w)
+
+    def test_callback_exception_propagates(self):
+        g, nodes = create_test_graph()
+
+        def boom(u, v, w):
+            raise KeyError("no cost")
+
+        with pytest.raises(KeyError):
+            g.weighted_ego_graph(nodes[0], 5.0, weight=boom)
@@ -0,0 +1,23 @@
+from concurrent.futures import ThreadPoolExecutor
+
+import pygraphina
+
+
+class TestThreads:
+
+    def setup_method(self):
+        self.g = pygraphina.erdos_renyi(60, 0.1, 7)
+
+    def test_concurrent_calls_match_sequential_results(self):
+        calls = [
+            lambda: pygraphina.centrality.betweenness(self.g, True),
+            lambda: pygraphina.centrality.pagerank(self.g, 0.85, 100, 1e-6),
+            lambda: pygraphina.community.louvain(self.g, 0),
+            lambda: self.g.floyd_warshall(),
+            lambda: pygraphina.parallel.shortest_paths_parallel(self.g, [0, 1]),
+        ]
+        expected = [call() for call in calls]
+        with ThreadPoolExecutor(max_workers=4) as pool:
+            futures = [pool.submit(call) for call in calls * 4]
+            results = [f.result() for f in futures]
+        assert results == expected * 4
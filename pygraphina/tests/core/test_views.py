@@ -66,3 +66,22 @@ class TestDiGraphViews:
         degree = self.g.degree
         assert degree[self.n0] == 1
         assert degree[self.n1] == 1
+
+
+def test_streaming_iterators_match_views():
+    for cls in (pygraphina.PyGraph, pygraphina.PyDiGraph):
+        g = cls()
+        a, b, c = (g.add_node(x) for x in (10, 20, 30))
+        g.add_edge(a, b, 1.5)
+        g.add_edge(b, c, 2.5)
+        g.remove_node(a)
+        nodes = g.iter_nodes()
+        assert iter(nodes) is nodes
+        assert list(nodes) == [(b, 20), (c, 30)]
+        assert list(g.iter_edges()) == [(b, c, 2.5)]
+
+        # Items removed before the cursor reaches them are skipped
+        stream = g.iter_nodes()
+        assert next(stream) == (b, 20)
+        g.remove_node(c)
+        assert list(stream) == []
@@ -0,0 +1,67 @@
+import ast
+import inspect
+from pathlib import Path
+
+import pygraphina
+import pytest
+
+PACKAGE_DIR = Path(pygraphina.__file__).parent
+SUBMODULES = [
+    "approximation",
+    "centrality",
+    "community",
+    "core",
+    "links",
+    "metrics",
+    "mst",
+    "parallel",
+    "subgraphs",
+    "traversal",
+]
+
+
+def stub_members(name):
+    tree = ast.parse((PACKAGE_DIR / f"{name}.pyi").read_text())
+    functions, classes = set(), {}
+    for node in tree.body:
+        if isinstance(node, ast.FunctionDef):
+            functions.add(node.name)
+        elif isinstance(node, ast.ClassDef):
+            classes[node.name] = {m.name for m in node.body if isinstance(m, ast.FunctionDef)}
+    return functions, classes
+
+
+def public_callables(obj):
+    for name in dir(obj):
+        if name.startswith("_"):
+            continue
+        value = getattr(obj, name)
+        if callable(value) and not isinstance(value, type):
+            yield name, value
+
+
+class TestTypeStubs:
+
+    @pytest.mark.parametrize("cls_name", ["PyGraph", "PyDiGraph", "PyAttrGraph"])
+    def test_graph_methods_are_stubbed_and_documented(self, cls_name):
+        _, classes = stub_members("__init__")
+        cls = getattr(pygraphina, cls_name)
+        for name, method in public_callables(cls):
+            assert name in classes[cls_name], f"{cls_name}.{name} missing from __init__.pyi"
+            assert (method.__doc__ or "").strip(), f"{cls_name}.{name} has no docstring"
+            inspect.signature(method)
+
+    @pytest.mark.parametrize("module", SUBMODULES)
+    def test_submodule_functions_are_stubbed_and_documented(self, module):
+        functions, classes = stub_members(module)
+        for name, func in public_callables(getattr(pygraphina, module)):
+            assert name in functions or name in classes, f"{module}.{name} missing from stub"
+            assert (func.__doc__ or "").strip(), f"{module}.{name} has no docstring"
+            inspect.signature(func)
+
+    def test_signatures_expose_parameter_names_and_defaults(self):
+        params = inspect.signature(pygraphina.PyGraph.a_star).parameters
+        assert list(params)[1:] == ["source", "target", "heuristic", "weight"]
+        assert params["heuristic"].default is None
+        sig = inspect.signature(pygraphina.parallel.run_batch)
+        assert sig.parameters["distance"].default == 1
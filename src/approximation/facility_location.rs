@@ -0,0 +1,292 @@
+//! Approximation algorithms for facility location problems.
+//!
+//! Given a graph and a budget of `p` facilities, choose `p` nodes as centers and assign every
+//! node to its nearest center:
+//!
+//! - **p-median** minimizes the sum of the distances from each node to its center.
+//! - **p-center** minimizes the largest such distance.
+//!
+//! Distances are shortest-path lengths over `f64` edge weights. In a directed graph they are
+//! measured from the center to the node it serves.
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::paths::multi_source_dijkstra;
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+
+/// Upper bound on local-search passes over all center swaps.
+const MAX_SWAP_PASSES: usize = 100;
+
+/// Relative improvement a swap must reach to be accepted, so rounding noise cannot cycle.
+const IMPROVEMENT_EPSILON: f64 = 1e-12;
+
+/// The objective optimized by [`facility_location`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FacilityMethod {
+    /// Minimize the total distance from nodes to their centers.
+    PMedian,
+    /// Minimize the maximum distance from a node to its center.
+    PCenter,
+}
+
+/// Objective value of a center set: the number of unreachable nodes first, then the
+/// optimized quantity (sum or maximum distance of the reachable nodes), then the other one
+/// as a tie-breaker. The tie-breaker lets p-center swaps leave the plateaus where moving a
+/// single center cannot lower the maximum on its own.
+type Objective = (usize, f64, f64);
+
+fn below(candidate: f64, current: f64) -> bool {
+    candidate < current - IMPROVEMENT_EPSILON * current.abs().max(1.0)
+}
+
+fn improves(candidate: Objective, current: Objective) -> bool {
+    if candidate.0 != current.0 {
+        return candidate.0 < current.0;
+    }
+    if below(candidate.1, current.1) {
+        return true;
+    }
+    !below(current.1, candidate.1) && below(candidate.2, current.2)
+}
+
+fn evaluate(rows: &[Vec<f64>], centers: &[usize], method: FacilityMethod) -> Objective {
+    let n = rows.len();
+    let mut unreached = 0;
+    let mut sum: f64 = 0.0;
+    let mut max: f64 = 0.0;
+    for v in 0..n {
+        let d = centers
+            .iter()
+            .map(|&c| rows[c][v])
+            .fold(f64::INFINITY, f64::min);
+        if d.is_infinite() {
+            unreached += 1;
+        } else {
+            sum += d;
+            max = max.max(d);
+        }
+    }
+    match method {
+        FacilityMethod::PMedian => (unreached, sum, max),
+        FacilityMethod::PCenter => (unreached, max, sum),
+    }
+}
+
+/// Chooses `p` centers by greedy construction followed by swap-based local search.
+///
+/// For p-median, each greedy step adds the node that lowers the total distance the most.
+/// For p-center, the first center is the node of smallest eccentricity and every later one
+/// is the node farthest from the centers chosen so far (Gonzalez's farthest-first rule,
+/// a 2-approximation on undirected graphs). Both then repeatedly replace a center with a
+/// non-center whenever that improves the objective, breaking ties on the other objective.
+///
+/// The heuristic precomputes all-pairs distances with Dijkstra's algorithm, so it needs
+/// O(V^2) memory and O(V * E log V) time for that step; each local-search pass costs
+/// O(p^2 * V^2).
+///
+/// # Arguments
+///
+/// * `graph` - The graph, with nonnegative `f64` edge weights.
+/// * `p` - The number of centers to choose.
+/// * `method` - Whether to solve p-median or p-center.
+///
+/// # Returns
+///
+/// The chosen centers, the map from every node to its nearest center, and the objective
+/// value (total or maximum distance).
+///
+/// # Errors
+///
+/// * `InvalidGraph` if the graph is empty.
+/// * `InvalidArgument` if `p` is 0 or exceeds the number of nodes, or if an edge weight is
+///   negative or NaN.
+/// * `Unfeasible` if `p` centers cannot reach every node, for example when the graph has more
+///   than `p` connected components.
+pub fn facility_location<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    p: usize,
+    method: FacilityMethod,
+) -> Result<(Vec<NodeId>, NodeMap<NodeId>, f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    if graph.is_empty() {
+        return Err(GraphinaError::invalid_graph(
+            "Cannot place facilities on an empty graph.",
+        ));
+    }
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let n = nodes.len();
+    if p == 0 || p > n {
+        return Err(GraphinaError::invalid_argument(format!(
+            "p must be between 1 and the number of nodes ({}), got {}.",
+            n, p
+        )));
+    }
+
+    // rows[c][v]: distance from candidate center c to node v, by position in `nodes`.
+    let mut rows = Vec::with_capacity(n);
+    for &c in &nodes {
+        let (dist, _) = multi_source_dijkstra(graph, &[c], None)?;
+        rows.push(
+            nodes
+                .iter()
+                .map(|v| dist.get(v).copied().flatten().unwrap_or(f64::INFINITY))
+                .collect::<Vec<f64>>(),
+        );
+    }
+
+    let mut centers = greedy_centers(&rows, p, method);
+    let mut current = evaluate(&rows, &centers, method);
+    for _ in 0..MAX_SWAP_PASSES {
+        let mut improved = false;
+        for slot in 0..p {
+            for candidate in 0..n {
+                if centers.contains(&candidate) {
+                    continue;
+                }
+                let previous = centers[slot];
+                centers[slot] = candidate;
+                let trial = evaluate(&rows, &centers, method);
+                if improves(trial, current) {
+                    current = trial;
+                    improved = true;
+                } else {
+                    centers[slot] = previous;
+                }
+            }
+        }
+        if !improved {
+            break;
+        }
+    }
+
+    let mut chosen: Vec<NodeId> = centers.iter().map(|&c| nodes[c]).collect();
+    chosen.sort();
+    let (dist, nearest) = multi_source_dijkstra(graph, &chosen, None)?;
+
+    let mut assignment = NodeMap::default();
+    let mut value: f64 = 0.0;
+    for &v in &nodes {
+        let (Some(Some(center)), Some(Some(d))) = (nearest.get(&v), dist.get(&v)) else {
+            return Err(GraphinaError::unfeasible(format!(
+                "{} centers cannot reach every node of the graph.",
+                p
+            )));
+        };
+        assignment.insert(v, *center);
+        match method {
+            FacilityMethod::PMedian => value += d,
+            FacilityMethod::PCenter => value = value.max(*d),
+        }
+    }
+    Ok((chosen, assignment, value))
+}
+
+/// Builds the initial center set (as positions into `rows`) for the local search.
+fn greedy_centers(rows: &[Vec<f64>], p: usize, method: FacilityMethod) -> Vec<usize> {
+    let n = rows.len();
+    let mut centers: Vec<usize> = Vec::with_capacity(p);
+    // nearest[v]: distance from v to its closest chosen center so far.
+    let mut nearest = vec![f64::INFINITY; n];
+
+    while centers.len() < p {
+        let next = match method {
+            FacilityMethod::PCenter if !centers.is_empty() => (0..n)
+                .filter(|c| !centers.contains(c))
+                .max_by(|&a, &b| nearest[a].total_cmp(&nearest[b])),
+            _ => (0..n).filter(|c| !centers.contains(c)).min_by_key(|&c| {
+                let mut unreached = 0usize;
+                let mut value: f64 = 0.0;
+                for (v, &cur) in nearest.iter().enumerate() {
+                    let d = cur.min(rows[c][v]);
+                    if d.is_infinite() {
+                        unreached += 1;
+                    } else if method == FacilityMethod::PMedian {
+                        value += d;
+                    } else {
+                        value = value.max(d);
+                    }
+                }
+                (unreached, ordered_float::OrderedFloat(value))
+            }),
+        };
+        let Some(next) = next else {
+            break;
+        };
+        centers.push(next);
+        for (v, cur) in nearest.iter_mut().enumerate() {
+            *cur = cur.min(rows[next][v]);
+        }
+    }
+    centers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+
+    /// Two 4-node paths joined by a long bridge between their ends.
+    fn two_clusters() -> (Graph<i32, f64>, Vec<NodeId>) {
+        let mut g = Graph::<i32, f64>::new();
+        let ids: Vec<_> = (0..8).map(|i| g.add_node(i)).collect();
+        for i in [0, 1, 2, 4, 5, 6] {
+            g.add_edge(ids[i], ids[i + 1], 1.0);
+        }
+        g.add_edge(ids[3], ids[4], 20.0);
+        (g, ids)
+    }
+
+    #[test]
+    fn test_p_median_splits_clusters() {
+        let (g, ids) = two_clusters();
+        let (centers, assignment, cost) =
+            facility_location(&g, 2, FacilityMethod::PMedian).unwrap();
+        assert_eq!(centers.len(), 2);
+        assert_eq!(assignment.len(), 8);
+        // One center inside each path; each path of 4 contributes 1 + 1 + 2 = 4.
+        assert!(centers.iter().any(|c| ids[..4].contains(c)));
+        assert!(centers.iter().any(|c| ids[4..].contains(c)));
+        assert_eq!(cost, 8.0);
+        for &v in &ids[..4] {
+            assert!(ids[..4].contains(&assignment[&v]));
+        }
+    }
+
+    #[test]
+    fn test_p_center_minimizes_radius() {
+        let (g, ids) = two_clusters();
+        let (centers, assignment, radius) =
+            facility_location(&g, 2, FacilityMethod::PCenter).unwrap();
+        assert_eq!(centers.len(), 2);
+        assert_eq!(radius, 2.0);
+        for &c in &centers {
+            assert_eq!(assignment[&c], c);
+        }
+        let (single, _, single_radius) = facility_location(&g, 1, FacilityMethod::PCenter).unwrap();
+        assert_eq!(single.len(), 1);
+        assert!(single_radius >= 20.0);
+        assert!(ids.contains(&single[0]));
+    }
+
+    #[test]
+    fn test_facility_location_errors() {
+        let empty = Graph::<i32, f64>::new();
+        assert!(facility_location(&empty, 1, FacilityMethod::PMedian).is_err());
+
+        let mut g = Graph::<i32, f64>::new();
+        let a = g.add_node(0);
+        let b = g.add_node(1);
+        g.add_node(2);
+        g.add_edge(a, b, 1.0);
+        assert!(matches!(
+            facility_location(&g, 0, FacilityMethod::PMedian),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            facility_location(&g, 1, FacilityMethod::PMedian),
+            Err(GraphinaError::Unfeasible(_))
+        ));
+        assert!(facility_location(&g, 2, FacilityMethod::PCenter).is_ok());
+    }
+}
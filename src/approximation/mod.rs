@@ -1,9 +1,12 @@
 pub mod clique;
 pub mod clustering;
 pub mod connectivity;
+pub mod facility_location;
 pub mod independent_set;
 pub mod matching;
+pub mod min_cut;
 pub mod ramsey;
+pub mod spanning_tree;
 pub mod subgraph;
 pub mod treewidth;
 pub mod tsp;
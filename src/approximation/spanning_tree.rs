@@ -0,0 +1,352 @@
+//! Approximation algorithms for constrained spanning tree problems.
+//!
+//! Both problems are NP-hard in general, so the functions here are greedy heuristics:
+//!
+//! - **Degree-constrained MST:** a spanning tree of minimum weight in which no node has more
+//!   than `max_degree` incident tree edges.
+//! - **k-MST:** a tree of minimum weight that spans at least `k` nodes.
+//!
+//! Edge direction is ignored: an edge `(u, v)` in a directed graph may be used as `{u, v}`.
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use crate::core::union_find::UnionFind;
+use ordered_float::OrderedFloat;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// A tree edge `(u, v, weight)` as returned by the heuristics in this module.
+pub type TreeEdge = (NodeId, NodeId, f64);
+
+/// Returns an upper bound on node indices, suitable for sizing a dense structure
+/// indexed by `NodeId::index()`.
+fn index_bound<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> usize
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    graph
+        .node_ids()
+        .map(|node| node.index())
+        .max()
+        .map_or(0, |m| m + 1)
+}
+
+/// Collects the non-loop edges of the graph, rejecting NaN weights.
+fn collect_edges<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> Result<Vec<TreeEdge>>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let mut edges = Vec::with_capacity(graph.edge_count());
+    for (u, v, &w) in graph.edges() {
+        if w.is_nan() {
+            return Err(GraphinaError::invalid_argument(
+                "Edge weights must not be NaN.",
+            ));
+        }
+        if u != v {
+            edges.push((u, v, w));
+        }
+    }
+    Ok(edges)
+}
+
+/// Approximates a minimum spanning tree in which every node has degree at most `max_degree`.
+///
+/// The heuristic runs Kruskal's algorithm and skips any edge that would push one of its
+/// endpoints past the degree bound. It runs in O(E log E) time. For `max_degree >= 2` the
+/// result is always a valid degree-bounded tree when one is found, but the heuristic can fail
+/// on graphs that admit such a tree only through a non-greedy choice of edges.
+///
+/// # Arguments
+///
+/// * `graph` - The graph to span. Edge direction is ignored.
+/// * `max_degree` - The maximum number of tree edges incident to any node.
+///
+/// # Returns
+///
+/// The tree edges and their total weight.
+///
+/// # Errors
+///
+/// * `InvalidGraph` if the graph is empty.
+/// * `InvalidArgument` if `max_degree` is 0 on a graph with more than one node, or if an edge
+///   weight is NaN.
+/// * `Unfeasible` if the heuristic could not connect every node within the degree bound,
+///   including the case where the graph is disconnected.
+pub fn degree_constrained_mst<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    max_degree: usize,
+) -> Result<(Vec<TreeEdge>, f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    if graph.is_empty() {
+        return Err(GraphinaError::invalid_graph(
+            "Cannot compute a spanning tree of an empty graph.",
+        ));
+    }
+    let n = graph.node_count();
+    if n == 1 {
+        return Ok((Vec::new(), 0.0));
+    }
+    if max_degree == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "max_degree must be at least 1 for a graph with more than one node.",
+        ));
+    }
+
+    let mut edges = collect_edges(graph)?;
+    edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let bound = index_bound(graph);
+    let mut uf = UnionFind::new(bound);
+    let mut degree = vec![0usize; bound];
+    let mut tree = Vec::with_capacity(n - 1);
+    let mut total = 0.0;
+
+    for (u, v, w) in edges {
+        let (ui, vi) = (u.index(), v.index());
+        if degree[ui] >= max_degree || degree[vi] >= max_degree {
+            continue;
+        }
+        if uf.union(ui, vi) {
+            degree[ui] += 1;
+            degree[vi] += 1;
+            tree.push((u, v, w));
+            total += w;
+            if tree.len() == n - 1 {
+                break;
+            }
+        }
+    }
+
+    if tree.len() != n - 1 {
+        return Err(GraphinaError::unfeasible(format!(
+            "Could not find a spanning tree with maximum degree {} (connected {} of {} nodes).",
+            max_degree,
+            tree.len() + 1,
+            n
+        )));
+    }
+    Ok((tree, total))
+}
+
+/// Approximates a minimum-weight tree that spans at least `k` nodes (the k-MST problem).
+///
+/// From every node as a root, the heuristic grows a tree with Prim's algorithm until it
+/// contains `k` nodes, then keeps the cheapest of these trees. It runs in
+/// O(V * (k + E) log E) time in the worst case.
+///
+/// # Arguments
+///
+/// * `graph` - The graph to search. Edge direction is ignored.
+/// * `k` - The number of nodes the tree must span.
+///
+/// # Returns
+///
+/// The nodes of the tree, its edges, and their total weight.
+///
+/// # Errors
+///
+/// * `InvalidGraph` if the graph is empty.
+/// * `InvalidArgument` if `k` is 0 or exceeds the number of nodes, or if an edge weight is
+///   negative or NaN.
+/// * `Unfeasible` if no connected component contains `k` nodes.
+pub fn k_minimum_spanning_tree<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    k: usize,
+) -> Result<(Vec<NodeId>, Vec<TreeEdge>, f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    if graph.is_empty() {
+        return Err(GraphinaError::invalid_graph(
+            "Cannot compute a k-MST of an empty graph.",
+        ));
+    }
+    let n = graph.node_count();
+    if k == 0 || k > n {
+        return Err(GraphinaError::invalid_argument(format!(
+            "k must be between 1 and the number of nodes ({}), got {}.",
+            n, k
+        )));
+    }
+
+    let bound = index_bound(graph);
+    let mut adj: Vec<Vec<(NodeId, f64)>> = vec![Vec::new(); bound];
+    for (u, v, w) in collect_edges(graph)? {
+        if w < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "k-MST requires nonnegative edge weights, got {} on edge ({:?}, {:?}).",
+                w, u, v
+            )));
+        }
+        adj[u.index()].push((v, w));
+        adj[v.index()].push((u, w));
+    }
+
+    let mut best: Option<(Vec<NodeId>, Vec<TreeEdge>, f64)> = None;
+    let mut in_tree = vec![false; bound];
+
+    for root in graph.node_ids() {
+        let mut nodes = vec![root];
+        let mut tree = Vec::with_capacity(k.saturating_sub(1));
+        let mut total = 0.0;
+        in_tree[root.index()] = true;
+
+        let mut heap = BinaryHeap::new();
+        for &(v, w) in &adj[root.index()] {
+            heap.push(Reverse((OrderedFloat(w), root, v)));
+        }
+
+        while nodes.len() < k {
+            // Prune early: with nonnegative weights, a partial tree already at least as heavy
+            // as the best one cannot win.
+            if best.as_ref().is_some_and(|b| total >= b.2) {
+                break;
+            }
+            let Some(Reverse((OrderedFloat(w), from, to))) = heap.pop() else {
+                break;
+            };
+            if in_tree[to.index()] {
+                continue;
+            }
+            in_tree[to.index()] = true;
+            nodes.push(to);
+            tree.push((from, to, w));
+            total += w;
+            for &(next, nw) in &adj[to.index()] {
+                if !in_tree[next.index()] {
+                    heap.push(Reverse((OrderedFloat(nw), to, next)));
+                }
+            }
+        }
+
+        for node in &nodes {
+            in_tree[node.index()] = false;
+        }
+
+        if nodes.len() == k && best.as_ref().is_none_or(|b| total < b.2) {
+            best = Some((nodes, tree, total));
+        }
+    }
+
+    best.ok_or_else(|| {
+        GraphinaError::unfeasible(format!("No connected component contains {} nodes.", k))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    fn max_tree_degree(tree: &[TreeEdge]) -> usize {
+        let mut counts = std::collections::HashMap::new();
+        for &(u, v, _) in tree {
+            *counts.entry(u).or_insert(0) += 1;
+            *counts.entry(v).or_insert(0) += 1;
+        }
+        counts.values().copied().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_degree_constrained_mst_avoids_hub() {
+        // A star with cheap spokes plus a more expensive outer path. The unconstrained MST is
+        // the star; with max_degree = 2 the tree must route around the hub.
+        let mut g = Graph::<i32, f64>::new();
+        let hub = g.add_node(0);
+        let leaves: Vec<_> = (1..=4).map(|i| g.add_node(i)).collect();
+        for &leaf in &leaves {
+            g.add_edge(hub, leaf, 1.0);
+        }
+        for pair in leaves.windows(2) {
+            g.add_edge(pair[0], pair[1], 2.0);
+        }
+
+        let (tree, total) = degree_constrained_mst(&g, 2).unwrap();
+        assert_eq!(tree.len(), 4);
+        assert!(max_tree_degree(&tree) <= 2);
+        assert_eq!(total, 1.0 + 1.0 + 2.0 + 2.0);
+
+        let (star, star_total) = degree_constrained_mst(&g, 4).unwrap();
+        assert_eq!(max_tree_degree(&star), 4);
+        assert_eq!(star_total, 4.0);
+    }
+
+    #[test]
+    fn test_degree_constrained_mst_errors() {
+        let empty = Graph::<i32, f64>::new();
+        assert!(degree_constrained_mst(&empty, 2).is_err());
+
+        let mut g = Graph::<i32, f64>::new();
+        let hub = g.add_node(0);
+        for i in 1..=3 {
+            let leaf = g.add_node(i);
+            g.add_edge(hub, leaf, 1.0);
+        }
+        assert!(matches!(
+            degree_constrained_mst(&g, 0),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        // A star admits no spanning tree with maximum degree 2.
+        assert!(matches!(
+            degree_constrained_mst(&g, 2),
+            Err(GraphinaError::Unfeasible(_))
+        ));
+    }
+
+    #[test]
+    fn test_k_mst_finds_cheap_cluster() {
+        // Two triangles joined by an expensive bridge: the cheapest 3-node tree lies inside
+        // the lighter triangle.
+        let mut g = Digraph::<i32, f64>::new();
+        let a: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+        let b: Vec<_> = (3..6).map(|i| g.add_node(i)).collect();
+        g.add_edge(a[0], a[1], 5.0);
+        g.add_edge(a[1], a[2], 5.0);
+        g.add_edge(a[2], a[0], 5.0);
+        g.add_edge(b[0], b[1], 1.0);
+        g.add_edge(b[1], b[2], 1.5);
+        g.add_edge(b[2], b[0], 2.0);
+        g.add_edge(a[0], b[0], 10.0);
+
+        let (nodes, tree, total) = k_minimum_spanning_tree(&g, 3).unwrap();
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(tree.len(), 2);
+        assert!(nodes.iter().all(|n| b.contains(n)));
+        assert_eq!(total, 2.5);
+
+        let (_, single, zero) = k_minimum_spanning_tree(&g, 1).unwrap();
+        assert!(single.is_empty());
+        assert_eq!(zero, 0.0);
+    }
+
+    #[test]
+    fn test_k_mst_errors() {
+        let mut g = Graph::<i32, f64>::new();
+        let n0 = g.add_node(0);
+        let n1 = g.add_node(1);
+        g.add_node(2);
+        g.add_edge(n0, n1, 1.0);
+        assert!(k_minimum_spanning_tree(&g, 0).is_err());
+        assert!(k_minimum_spanning_tree(&g, 4).is_err());
+        assert!(matches!(
+            k_minimum_spanning_tree(&g, 3),
+            Err(GraphinaError::Unfeasible(_))
+        ));
+
+        // From root 0 the path 0-1-2 costs 2; from root 3 the first edge costs 3, so the
+        // prune would stop it, though its late -5 edge makes 3-4-5 cost -2.
+        let mut g = Graph::<i32, f64>::new();
+        let n: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 1.0);
+        g.add_edge(n[3], n[4], 3.0);
+        g.add_edge(n[4], n[5], -5.0);
+        assert!(matches!(
+            k_minimum_spanning_tree(&g, 3),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+    }
+}
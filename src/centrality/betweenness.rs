@@ -7,7 +7,9 @@
 
 use crate::core::error::{GraphinaError, Result};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
-use std::collections::{HashMap, VecDeque};
+use ordered_float::NotNan;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
 
 /// Returns an upper bound on node indices, for sizing dense `Vec`s indexed by
 /// `NodeId::index()`. Indices are stable but not contiguous after removals, so
@@ -61,6 +63,9 @@ where
     let mut sigma = vec![0.0f64; bound];
     let mut dist = vec![-1.0f64; bound];
     let mut delta = vec![0.0f64; bound];
+    // The node whose neighbors were last scanned when each node was reached, so that
+    // parallel edges count as a single connection.
+    let mut reached_from = vec![usize::MAX; bound];
     let mut stack: Vec<NodeId> = Vec::new();
     let mut queue: VecDeque<NodeId> = VecDeque::new();
 
@@ -72,6 +77,7 @@ where
             sigma[i] = 0.0;
             dist[i] = -1.0;
             delta[i] = 0.0;
+            reached_from[i] = usize::MAX;
         }
         let si = s.index();
         sigma[si] = 1.0;
@@ -86,6 +92,10 @@ where
 
             for w in graph.neighbors(v) {
                 let wi = w.index();
+                if reached_from[wi] == vi {
+                    continue;
+                }
+                reached_from[wi] = vi;
                 // w found for the first time?
                 if dist[wi] < 0.0 {
                     dist[wi] = v_dist + 1.0;
@@ -116,9 +126,22 @@ where
         }
     }
 
+    Ok(scale_node_scores(graph, &centrality_vec, normalized))
+}
+
+/// Converts raw Brandes dependency sums, indexed by `NodeId::index()`, to the returned map.
+fn scale_node_scores<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    raw: &[f64],
+    normalized: bool,
+) -> NodeMap<f64>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let n = graph.node_count();
     let mut centrality = NodeMap::with_capacity_and_hasher(n, rustc_hash::FxBuildHasher);
     for node in graph.node_ids() {
-        centrality.insert(node, centrality_vec[node.index()]);
+        centrality.insert(node, raw[node.index()]);
     }
 
     if normalized {
@@ -139,8 +162,138 @@ where
             *val *= 0.5;
         }
     }
+    centrality
+}
+
+/// Weighted betweenness centrality, by Brandes' algorithm over Dijkstra searches.
+///
+/// Like [`betweenness_centrality`], but a shortest path is the one with the lowest total
+/// cost rather than the fewest hops. `eval_cost` gives each edge's cost, or `None` to leave
+/// the edge out, as in [`dijkstra_path_impl`](crate::core::paths::dijkstra_path_impl).
+/// Between parallel edges only the cheapest counts, and self-loops are ignored. Paths tie
+/// only when their costs are exactly equal, so with fractional costs two routes that are
+/// equal on paper may not split the score.
+///
+/// # Errors
+///
+/// Returns an error if the graph is empty, or if a cost is negative or NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::centrality::betweenness::betweenness_centrality_impl;
+/// use graphina::core::types::Graph;
+///
+/// // A cheap detour a - c - b beats the direct edge a - b.
+/// let mut g = Graph::<&str, f64>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// g.add_edge(a, b, 5.0);
+/// g.add_edge(a, c, 1.0);
+/// g.add_edge(c, b, 1.0);
+///
+/// let bc = betweenness_centrality_impl(&g, false, |w| Some(*w)).unwrap();
+/// assert_eq!(bc[&c], 1.0);
+/// assert_eq!(bc[&a], 0.0);
+/// ```
+pub fn betweenness_centrality_impl<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    normalized: bool,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<NodeMap<f64>>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    if graph.node_count() == 0 {
+        return Err(GraphinaError::invalid_graph(
+            "Cannot compute betweenness centrality on an empty graph.",
+        ));
+    }
+
+    let bound = dist_bound(graph);
+    let mut centrality_vec = vec![0.0f64; bound];
+    let mut preds: Vec<Vec<NodeId>> = vec![Vec::new(); bound];
+    let mut sigma = vec![0.0f64; bound];
+    let mut dist = vec![f64::INFINITY; bound];
+    let mut delta = vec![0.0f64; bound];
+    let mut settled = vec![false; bound];
+    let mut stack: Vec<NodeId> = Vec::new();
+    let mut heap: BinaryHeap<Reverse<(NotNan<f64>, NodeId)>> = BinaryHeap::new();
+    // Cheapest cost to each neighbor of the node being settled.
+    let mut hops: Vec<(NodeId, f64)> = Vec::new();
 
-    Ok(centrality)
+    for s in graph.node_ids() {
+        stack.clear();
+        for i in 0..bound {
+            preds[i].clear();
+            sigma[i] = 0.0;
+            dist[i] = f64::INFINITY;
+            delta[i] = 0.0;
+            settled[i] = false;
+        }
+        sigma[s.index()] = 1.0;
+        dist[s.index()] = 0.0;
+        heap.push(Reverse((NotNan::default(), s)));
+
+        while let Some(Reverse((d, v))) = heap.pop() {
+            let vi = v.index();
+            if settled[vi] || *d > dist[vi] {
+                continue;
+            }
+            settled[vi] = true;
+            stack.push(v);
+
+            hops.clear();
+            for (w, weight) in graph.outgoing_edges(v) {
+                let Some(cost) = eval_cost(weight) else {
+                    continue;
+                };
+                if cost.is_nan() || cost < 0.0 {
+                    return Err(GraphinaError::invalid_argument(format!(
+                        "Weighted betweenness requires nonnegative, non-NaN costs, but found {cost} on an edge from {v:?} to {w:?}"
+                    )));
+                }
+                if w != v {
+                    hops.push((w, cost));
+                }
+            }
+            hops.sort_unstable_by(|x, y| x.0.cmp(&y.0).then(x.1.total_cmp(&y.1)));
+            hops.dedup_by_key(|hop| hop.0);
+
+            for &(w, cost) in &hops {
+                let wi = w.index();
+                if settled[wi] {
+                    continue;
+                }
+                let next = *d + cost;
+                if next < dist[wi] {
+                    dist[wi] = next;
+                    sigma[wi] = sigma[vi];
+                    preds[wi].clear();
+                    preds[wi].push(v);
+                    if let Ok(key) = NotNan::new(next) {
+                        heap.push(Reverse((key, w)));
+                    }
+                } else if next == dist[wi] {
+                    sigma[wi] += sigma[vi];
+                    preds[wi].push(v);
+                }
+            }
+        }
+
+        while let Some(w) = stack.pop() {
+            let wi = w.index();
+            for &v in &preds[wi] {
+                delta[v.index()] += (sigma[v.index()] / sigma[wi]) * (1.0 + delta[wi]);
+            }
+            if w != s {
+                centrality_vec[wi] += delta[wi];
+            }
+        }
+    }
+
+    Ok(scale_node_scores(graph, &centrality_vec, normalized))
 }
 
 /// Edge betweenness centrality: measures the extent to which an edge lies on paths between other nodes.
@@ -189,6 +342,9 @@ where
     let mut sigma = vec![0.0f64; bound];
     let mut dist = vec![-1.0f64; bound];
     let mut delta = vec![0.0f64; bound];
+    // The node whose neighbors were last scanned when each node was reached, so that
+    // parallel edges count as a single connection.
+    let mut reached_from = vec![usize::MAX; bound];
     let mut stack: Vec<NodeId> = Vec::new();
     let mut queue: VecDeque<NodeId> = VecDeque::new();
 
@@ -199,6 +355,7 @@ where
             sigma[i] = 0.0;
             dist[i] = -1.0;
             delta[i] = 0.0;
+            reached_from[i] = usize::MAX;
         }
         let si = s.index();
         sigma[si] = 1.0;
@@ -212,6 +369,10 @@ where
 
             for w in graph.neighbors(v) {
                 let wi = w.index();
+                if reached_from[wi] == vi {
+                    continue;
+                }
+                reached_from[wi] = vi;
                 if dist[wi] < 0.0 {
                     dist[wi] = v_dist + 1.0;
                     queue.push_back(w);
@@ -306,8 +467,62 @@ mod tests {
         );
         assert!((bc[&nodes[3]] - 0.0).abs() < 1e-9);
     }
-    use super::{betweenness_centrality, edge_betweenness_centrality};
-    use crate::core::types::Graph;
+    use super::{betweenness_centrality, betweenness_centrality_impl, edge_betweenness_centrality};
+    use crate::core::types::{Digraph, Graph, NodeId};
+
+    #[test]
+    fn test_weighted_betweenness_matches_hops_under_unit_costs() {
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<NodeId> = (0..30).map(|_| g.add_node(())).collect();
+        for i in 0..30 {
+            for step in [1, 4, 9] {
+                g.add_edge(n[i], n[(i * 7 + step) % 30], 2.5);
+            }
+        }
+        g.add_edge(n[0], n[7], 2.5);
+        g.add_edge(n[3], n[3], 2.5);
+        g.remove_node(n[11]);
+        let mut u = Graph::<(), f64>::new();
+        let m: Vec<NodeId> = (0..20).map(|_| u.add_node(())).collect();
+        for i in 0..20 {
+            u.add_edge(m[i], m[(i * 3 + 1) % 20], 1.0);
+            u.add_edge(m[i], m[(i + 5) % 20], 1.0);
+        }
+
+        for normalized in [false, true] {
+            let hops = betweenness_centrality(&g, normalized).unwrap();
+            let weighted = betweenness_centrality_impl(&g, normalized, |_| Some(1.0)).unwrap();
+            for (node, score) in &hops {
+                assert!((weighted[node] - score).abs() < 1e-9);
+            }
+            let hops = betweenness_centrality(&u, normalized).unwrap();
+            let weighted = betweenness_centrality_impl(&u, normalized, |w| Some(*w)).unwrap();
+            for (node, score) in &hops {
+                assert!((weighted[node] - score).abs() < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_weighted_betweenness_follows_costs() {
+        // Two equal-cost routes 0 -> 3 through 1 and 2 split the score; the direct edge is
+        // too expensive, and the skipped edge 1 -> 2 does not count.
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<NodeId> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[0], n[2], 2.0);
+        g.add_edge(n[1], n[3], 2.0);
+        g.add_edge(n[2], n[3], 1.0);
+        g.add_edge(n[0], n[3], 10.0);
+        g.add_edge(n[1], n[2], -1.0);
+        let cost = |w: &f64| (*w > 0.0).then_some(*w);
+        let bc = betweenness_centrality_impl(&g, false, cost).unwrap();
+        assert_eq!((bc[&n[1]], bc[&n[2]]), (0.5, 0.5));
+        assert_eq!((bc[&n[0]], bc[&n[3]]), (0.0, 0.0));
+
+        assert!(betweenness_centrality_impl(&g, false, |w| Some(*w)).is_err());
+        assert!(betweenness_centrality_impl(&Graph::<(), f64>::new(), true, |w| Some(*w)).is_err());
+    }
 
     #[test]
     fn test_betweenness_centrality_simple() {
@@ -335,6 +550,29 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_betweenness_ignores_parallel_edges() {
+        let mut simple = Graph::<i32, f64>::new();
+        let mut multi = Graph::<i32, f64>::new();
+        let a: Vec<NodeId> = (0..4).map(|i| simple.add_node(i)).collect();
+        let b: Vec<NodeId> = (0..4).map(|i| multi.add_node(i)).collect();
+        for i in 0..3 {
+            simple.add_edge(a[i], a[i + 1], 1.0);
+            multi.add_edge(b[i], b[i + 1], 1.0);
+        }
+        multi.add_edge(b[1], b[2], 1.0);
+        multi.add_edge(b[2], b[1], 1.0);
+
+        let expected = betweenness_centrality(&simple, false).unwrap();
+        let actual = betweenness_centrality(&multi, false).unwrap();
+        for i in 0..4 {
+            assert!((expected[&a[i]] - actual[&b[i]]).abs() < 1e-12);
+        }
+        let expected = edge_betweenness_centrality(&simple, false).unwrap();
+        let actual = edge_betweenness_centrality(&multi, false).unwrap();
+        assert!((expected[&(a[1], a[2])] - actual[&(b[1], b[2])]).abs() < 1e-12);
+    }
+
     #[test]
     fn test_edge_betweenness_centrality() {
         let mut graph = Graph::<i32, f64>::new();
@@ -10,8 +10,70 @@ use crate::core::paths::dijkstra_path_f64;
 use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeMap};
 use std::fmt::Debug;
 
+/// How closeness centrality scores nodes that cannot reach every other node.
+///
+/// On a connected graph every mode except [`Disconnected::Harmonic`] gives the classic
+/// `(n - 1) / sum_dist`, and `Harmonic` gives the mean reciprocal distance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Disconnected {
+    /// Scale `reachable / sum_dist` by the fraction of the graph that is reachable
+    /// (Wasserman and Faust). Matches networkx's default `wf_improved=True`.
+    #[default]
+    WassermanFaust,
+    /// Normalize within the reachable part only, `reachable / sum_dist`, so a node in a
+    /// small component can outscore one in a large component. Matches networkx's
+    /// `wf_improved=False`.
+    PerComponent,
+    /// Fall back to the normalized harmonic form, `sum(1 / d) / (n - 1)`, where an
+    /// unreachable node contributes `0`.
+    Harmonic,
+    /// Return [`GraphinaError::InvalidGraph`] if any node cannot reach every other node.
+    Error,
+}
+
 /// Compute closeness centrality for all nodes.
+///
+/// Disconnected graphs use the Wasserman-Faust correction; see
+/// [`closeness_centrality_with`] for the other modes.
 pub fn closeness_centrality<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> Result<NodeMap<f64>>
+where
+    A: Debug,
+    Ty: GraphConstructor<A, f64>,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    closeness_centrality_with(graph, Disconnected::WassermanFaust)
+}
+
+/// Compute closeness centrality for all nodes, treating disconnected graphs as `mode` says.
+///
+/// A node that reaches no other node scores `0.0` in every mode that does not error.
+///
+/// # Errors
+///
+/// [`GraphinaError::InvalidGraph`] on an empty graph, or with [`Disconnected::Error`] when
+/// some node cannot reach all the others (on a directed graph, when it is not strongly
+/// connected).
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::centrality::closeness::{Disconnected, closeness_centrality_with};
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let (a, b, c, d, e) = (g.add_node(()), g.add_node(()), g.add_node(()), g.add_node(()), g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(c, d, 1.0);
+/// g.add_edge(d, e, 1.0);
+///
+/// let per_component = closeness_centrality_with(&g, Disconnected::PerComponent).unwrap();
+/// assert_eq!(per_component[&a], 1.0);
+/// assert!(closeness_centrality_with(&g, Disconnected::Error).is_err());
+/// ```
+pub fn closeness_centrality_with<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    mode: Disconnected,
+) -> Result<NodeMap<f64>>
 where
     A: Debug,
     Ty: GraphConstructor<A, f64>,
@@ -26,9 +88,10 @@ where
 
     for (node, _) in graph.nodes() {
         let (dist_map, _) = dijkstra_path_f64(graph, node, None)?;
-        // Sum of shortest path distances to reachable nodes, and how many are
-        // reachable. Closeness is the reciprocal of the mean distance.
+        // Sum of shortest path distances (and of their reciprocals) to reachable
+        // nodes, and how many are reachable.
         let mut sum_dist = 0.0;
+        let mut sum_inverse = 0.0;
         let mut reachable = 0usize;
         // Iterate the distance map directly rather than doing one hash lookup per
         // other node. Summation is order-independent, so iteration order is fine.
@@ -37,16 +100,30 @@ where
                 if let Some(dist_f64) = dist_opt {
                     if *dist_f64 > 0.0 && dist_f64.is_finite() {
                         sum_dist += *dist_f64;
+                        sum_inverse += 1.0 / *dist_f64;
                         reachable += 1;
                     }
                 }
             }
         }
-        // Wasserman-Faust improved closeness: (reachable / sum_dist) scaled by
-        // the fraction of the graph that is reachable, which is well defined on
-        // disconnected graphs and reduces to (n - 1) / sum_dist when connected.
+        if mode == Disconnected::Error && reachable + 1 < n {
+            return Err(GraphinaError::invalid_graph(format!(
+                "Graph is disconnected: node {} reaches {reachable} of {} other nodes",
+                node.index(),
+                n - 1
+            )));
+        }
         let closeness = if sum_dist > 0.0 && n > 1 {
-            (reachable as f64 / sum_dist) * (reachable as f64 / (n as f64 - 1.0))
+            match mode {
+                // Wasserman-Faust improved closeness: (reachable / sum_dist) scaled by
+                // the fraction of the graph that is reachable, which reduces to
+                // (n - 1) / sum_dist when connected.
+                Disconnected::WassermanFaust => {
+                    (reachable as f64 / sum_dist) * (reachable as f64 / (n as f64 - 1.0))
+                }
+                Disconnected::PerComponent | Disconnected::Error => reachable as f64 / sum_dist,
+                Disconnected::Harmonic => sum_inverse / (n as f64 - 1.0),
+            }
         } else {
             0.0
         };
@@ -91,4 +168,39 @@ mod tests {
             cc[&n2]
         );
     }
+
+    // On a triangle plus a disconnected edge, each mode scores the edge's endpoints
+    // differently: each reaches 1 of the 4 other nodes at distance 1.
+    #[test]
+    fn test_closeness_disconnected_modes() {
+        use crate::centrality::closeness::{Disconnected, closeness_centrality_with};
+        use crate::core::types::Graph;
+
+        let mut g = Graph::<i32, f64>::new();
+        let t: Vec<_> = (0..3).map(|i| g.add_node(i)).collect();
+        let a = g.add_node(3);
+        let b = g.add_node(4);
+        g.add_edge(t[0], t[1], 1.0);
+        g.add_edge(t[1], t[2], 1.0);
+        g.add_edge(t[2], t[0], 1.0);
+        g.add_edge(a, b, 1.0);
+
+        let wf = closeness_centrality_with(&g, Disconnected::WassermanFaust).unwrap();
+        assert!((wf[&a] - 0.25).abs() < 1e-9);
+        assert!((wf[&t[0]] - 0.5).abs() < 1e-9);
+        let per = closeness_centrality_with(&g, Disconnected::PerComponent).unwrap();
+        assert!((per[&a] - 1.0).abs() < 1e-9);
+        assert!((per[&t[0]] - 1.0).abs() < 1e-9);
+        let harmonic = closeness_centrality_with(&g, Disconnected::Harmonic).unwrap();
+        assert!((harmonic[&a] - 0.25).abs() < 1e-9);
+        assert!((harmonic[&t[0]] - 0.5).abs() < 1e-9);
+        assert!(closeness_centrality_with(&g, Disconnected::Error).is_err());
+
+        g.add_edge(t[0], a, 1.0);
+        let connected = closeness_centrality_with(&g, Disconnected::Error).unwrap();
+        assert_eq!(
+            connected,
+            closeness_centrality_with(&g, Disconnected::PerComponent).unwrap()
+        );
+    }
 }
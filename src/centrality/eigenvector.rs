@@ -6,7 +6,8 @@
 //! to surface convergence issues and aid observability and error propagation.
 
 use crate::core::error::{GraphinaError, Result};
-use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use crate::core::sparse::{CsrMatrix, Orientation};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeMap};
 
 /// Eigenvector centrality: computes the eigenvector corresponding to the largest eigenvalue
 /// of the adjacency matrix.
@@ -51,35 +52,12 @@ where
         return Ok(centrality);
     }
 
-    // Build node index mapping
-    let node_list: Vec<NodeId> = graph.nodes().map(|(node, _)| node).collect();
-    let mut node_to_idx = std::collections::HashMap::new();
-    for (idx, &node) in node_list.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-    }
-
-    // Store the adjacency as a sparse edge list rather than a dense n x n matrix.
-    // Each entry is (row, col, weight), and the operator product accumulates
-    // `out[row] += weight * x[col]`. This costs O(E) per iteration and O(E)
-    // memory instead of O(n^2). For directed graphs the entry orients so an
-    // incoming edge influences the target (v influences u); for undirected
-    // graphs both orientations are stored to keep the operator symmetric.
+    // Row `u` of the incoming adjacency holds the edges into `u`, so for directed
+    // graphs an incoming edge influences its target; undirected graphs get the
+    // symmetric matrix. Each product costs O(E) and the matrix O(E) memory.
     let directed = graph.is_directed();
-    let mut adj: Vec<(usize, usize, f64)> = Vec::with_capacity(graph.edge_count());
-    for (u, v, w) in graph.edges() {
-        let ui = node_to_idx[&u];
-        let vi = node_to_idx[&v];
-        let weight: f64 = (*w).into();
-
-        if directed {
-            // For directed graphs: v influences u (incoming edges)
-            adj.push((vi, ui, weight));
-        } else {
-            // For undirected graphs the operator is symmetric.
-            adj.push((ui, vi, weight));
-            adj.push((vi, ui, weight));
-        }
-    }
+    let matrix = CsrMatrix::adjacency(graph, Orientation::Incoming);
+    let node_list = matrix.nodes();
 
     // Sparse power iteration. For undirected graphs iterate on the shifted
     // operator (A + I): shifting by the identity moves every eigenvalue up by one
@@ -91,13 +69,14 @@ where
     // shift would make a defective directed operator converge only linearly.
     let shift = if directed { 0.0 } else { 1.0 };
     let mut x = vec![1.0 / (n as f64).sqrt(); n];
+    let mut y = vec![0.0; n];
     let mut converged = false;
 
     for iter in 0..max_iter {
         // y = (A + shift * I) x
-        let mut y: Vec<f64> = x.iter().map(|&xi| shift * xi).collect();
-        for &(row, col, weight) in &adj {
-            y[row] += weight * x[col];
+        matrix.mul_vec(&x, &mut y)?;
+        for (yi, &xi) in y.iter_mut().zip(&x) {
+            *yi += shift * xi;
         }
 
         let norm: f64 = y.iter().map(|v| v * v).sum::<f64>().sqrt();
@@ -106,7 +85,7 @@ where
             // directed structure): fall back to a uniform distribution.
             let mut centrality = NodeMap::default();
             let uniform_value = 1.0 / n as f64;
-            for &node in &node_list {
+            for &node in node_list {
                 centrality.insert(node, uniform_value);
             }
             return Ok(centrality);
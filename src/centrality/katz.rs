@@ -6,6 +6,7 @@
 //! convergence/parameter validation with clear error propagation.
 
 use crate::core::error::{GraphinaError, Result};
+use crate::core::sparse::{CsrMatrix, Orientation};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
 
 /// Katz centrality: computes the relative influence of a node within a network
@@ -42,31 +43,11 @@ where
         return Ok(NodeMap::default());
     }
 
-    // Build proper node index mapping
-    let node_list: Vec<NodeId> = graph.nodes().map(|(node, _)| node).collect();
-    let mut node_to_idx = std::collections::HashMap::new();
-    for (idx, &node) in node_list.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-    }
-
-    // Store the adjacency as a sparse edge list rather than a dense n x n matrix.
-    // The Katz iteration only needs the matrix-vector product `adj * x`, which
-    // costs O(E) over this representation instead of O(n^2) over a dense matrix,
-    // and uses O(E) memory instead of O(n^2). For undirected graphs each edge is
-    // stored once, so the reverse contribution is added explicitly to keep the
-    // operator symmetric; otherwise Katz centrality would not respect the graph's
-    // symmetry.
-    let directed = graph.is_directed();
-    let mut edges: Vec<(usize, usize, f64)> = Vec::with_capacity(graph.edge_count());
-    for (u, v, w) in graph.edges() {
-        let ui = node_to_idx[&u];
-        let vi = node_to_idx[&v];
-        let weight: f64 = (*w).into();
-        edges.push((ui, vi, weight));
-        if !directed && ui != vi {
-            edges.push((vi, ui, weight));
-        }
-    }
+    // The Katz iteration only needs the product `A x`, which the sparse adjacency
+    // computes in O(E) per iteration. On undirected graphs the matrix stores both
+    // orientations of every edge, so the operator respects the graph's symmetry.
+    let matrix = CsrMatrix::adjacency(graph, Orientation::Outgoing);
+    let node_list = matrix.nodes();
 
     // Initial vector
     let mut x = vec![0.0_f64; n];
@@ -76,15 +57,16 @@ where
         vec![1.0; n]
     };
 
+    let mut x_new = vec![0.0_f64; n];
     let mut converged = false;
     for _ in 0..max_iter {
         // x_new = alpha * (adj * x) + beta
-        let mut x_new = beta_vec.clone();
-        for &(ui, vi, weight) in &edges {
-            x_new[ui] += alpha * weight * x[vi];
+        matrix.mul_vec(&x, &mut x_new)?;
+        for (xn, &b) in x_new.iter_mut().zip(&beta_vec) {
+            *xn = alpha * *xn + b;
         }
         let diff_sq: f64 = x_new.iter().zip(&x).map(|(a, b)| (a - b) * (a - b)).sum();
-        x = x_new;
+        std::mem::swap(&mut x, &mut x_new);
         if diff_sq.sqrt() < tolerance {
             converged = true;
             break;
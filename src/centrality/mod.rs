@@ -5,6 +5,8 @@
 //! better observability and error propagation. Selector-style routines that do not produce a
 //! numeric map (for example, community seed pickers) may return plain values instead.
 //!
+//! The main measures are re-exported here, so `graphina::centrality::pagerank` names the
+//! function as well as its module.
 
 pub mod betweenness;
 pub mod closeness;
@@ -16,4 +18,12 @@ pub mod other;
 pub mod pagerank;
 pub mod personalized;
 pub mod personalized_pagerank;
+
+pub use betweenness::{betweenness_centrality, edge_betweenness_centrality};
+pub use closeness::closeness_centrality;
+pub use degree::{degree_centrality, in_degree_centrality, out_degree_centrality};
+pub use eigenvector::eigenvector_centrality;
+pub use harmonic::harmonic_centrality;
+pub use katz::katz_centrality;
+pub use pagerank::{pagerank, pagerank_on};
 pub use personalized_pagerank::personalized_page_rank as personalized_pagerank_vec;
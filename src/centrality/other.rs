@@ -6,7 +6,7 @@
 //! observability and error propagation. Selector-style routines that return node lists (e.g.,
 //! `voterank`) may return plain values.
 
-use crate::core::error::Result;
+use crate::core::error::{GraphinaError, Result};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
 use std::collections::HashMap;
 use std::collections::HashSet;
@@ -30,27 +30,66 @@ where
 {
     let mut centrality = NodeMap::default();
     for (node, _) in graph.nodes() {
-        let mut reached = HashSet::new();
-        let mut current = HashSet::new();
-        current.insert(node);
-        reached.insert(node);
+        centrality.insert(node, reached_within(graph, node, distance) as f64);
+    }
+    Ok(centrality)
+}
+
+/// Local reaching centrality of a single node.
+///
+/// Gives the same value as the entry for `node` in [`local_reaching_centrality`], without
+/// visiting every other node in the graph, which makes it cheap to evaluate for a chosen subset.
+///
+/// # Arguments
+///
+/// * `graph`: the targeted graph.
+/// * `node`: the node to score.
+/// * `distance`: the maximum distance to consider.
+///
+/// # Errors
+///
+/// Returns `NodeNotFound` if `node` is not in the graph.
+pub fn local_reaching_centrality_of<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    node: NodeId,
+    distance: usize,
+) -> Result<f64>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    if !graph.contains_node(node) {
+        return Err(GraphinaError::node_not_found(format!(
+            "Node {:?} is not in the graph",
+            node
+        )));
+    }
+    Ok(reached_within(graph, node, distance) as f64)
+}
+
+/// Number of nodes within `distance` hops of `node`, counting `node` itself.
+fn reached_within<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, node: NodeId, distance: usize) -> usize
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut reached = HashSet::new();
+    let mut current = HashSet::new();
+    current.insert(node);
+    reached.insert(node);
 
-        for _ in 0..distance {
-            let mut next = HashSet::new();
-            for &n in &current {
-                for neighbor in graph.neighbors(n) {
-                    if !reached.contains(&neighbor) {
-                        reached.insert(neighbor);
-                        next.insert(neighbor);
-                    }
+    for _ in 0..distance {
+        let mut next = HashSet::new();
+        for &n in &current {
+            for neighbor in graph.neighbors(n) {
+                if !reached.contains(&neighbor) {
+                    reached.insert(neighbor);
+                    next.insert(neighbor);
                 }
             }
-            current = next;
         }
-
-        centrality.insert(node, reached.len() as f64);
+        current = next;
     }
-    Ok(centrality)
+
+    reached.len()
 }
 
 /// Global reaching centrality: similar to local but considers the entire graph.
@@ -261,4 +300,25 @@ mod tests {
             assert_eq!(voterank(&g, 4), first);
         }
     }
+
+    #[test]
+    fn test_local_reaching_of_matches_full_map() {
+        use crate::centrality::other::{local_reaching_centrality, local_reaching_centrality_of};
+        use crate::core::types::Graph;
+
+        let mut g = Graph::<i32, f64>::new();
+        let nodes: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+        for w in nodes.windows(2) {
+            g.add_edge(w[0], w[1], 1.0);
+        }
+
+        let all = local_reaching_centrality(&g, 2).unwrap();
+        for &n in &nodes {
+            assert_eq!(local_reaching_centrality_of(&g, n, 2).unwrap(), all[&n]);
+        }
+        assert_eq!(all[&nodes[0]], 3.0);
+
+        g.remove_node(nodes[4]);
+        assert!(local_reaching_centrality_of(&g, nodes[4], 1).is_err());
+    }
 }
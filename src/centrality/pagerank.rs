@@ -1,11 +1,14 @@
 //! PageRank algorithms.
 //!
-//! This module provides PageRank centrality measures.
+//! This module provides PageRank centrality measures, over a `BaseGraph` with [`pagerank`] and
+//! over any [`GraphTraversal`], such as a compressed graph, with [`pagerank_on`].
 //!
 //! Convention: functions in this module return `Result<_, crate::core::error::GraphinaError>`
 //! for better observability and error propagation.
 
 use crate::core::error::{GraphinaError, Result};
+use crate::core::sparse::{CsrMatrix, Orientation};
+use crate::core::traits::GraphTraversal;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
 
 /// PageRank: a link analysis algorithm that assigns a numerical weighting to each element
@@ -39,30 +42,11 @@ where
         return Ok(NodeMap::default());
     }
 
-    // Build proper node index mapping to handle non-contiguous indices
-    let node_list: Vec<NodeId> = graph.nodes().map(|(node, _)| node).collect();
-    let mut node_to_idx = std::collections::HashMap::new();
-    for (idx, &node) in node_list.iter().enumerate() {
-        node_to_idx.insert(node, idx);
-    }
-
-    // Build adjacency structure: for each node, store (target_idx, weight)
-    let mut out_degrees = vec![0.0; n];
-    let mut out_edges: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
-
-    let is_directed = graph.is_directed();
-    for (u, v, w) in graph.edges() {
-        let ui = node_to_idx[&u];
-        let vi = node_to_idx[&v];
-        let weight: f64 = (*w).into();
-        out_degrees[ui] += weight;
-        out_edges[ui].push((vi, weight));
-
-        if !is_directed {
-            out_degrees[vi] += weight;
-            out_edges[vi].push((ui, weight));
-        }
-    }
+    // Row `v` of the incoming adjacency holds the edges into `v`, so one product with
+    // the out-weight-scaled ranks distributes every node's rank to its successors.
+    let matrix = CsrMatrix::adjacency(graph, Orientation::Incoming);
+    let node_list = matrix.nodes();
+    let out_degrees = matrix.column_sums();
 
     let mut pr = if let Some(start_map) = nstart {
         let mut p = vec![0.0; n];
@@ -88,6 +72,7 @@ where
     };
 
     let mut pr_new = vec![0.0; n];
+    let mut scaled = vec![0.0; n];
 
     for _ in 0..max_iter {
         // Handle dangling nodes (nodes with no outgoing edges)
@@ -99,19 +84,15 @@ where
         }
         dangling_sum *= damping / n as f64;
 
-        // Initialize with teleportation probability and dangling contribution
-        for pr_new_item in pr_new.iter_mut() {
-            *pr_new_item = (1.0 - damping) / n as f64 + dangling_sum;
+        // Distribute rank from each node to its neighbors, then add the teleportation
+        // probability and dangling contribution.
+        for ((scaled_i, &pr_i), &deg) in scaled.iter_mut().zip(&pr).zip(&out_degrees) {
+            *scaled_i = if deg > 0.0 { damping * pr_i / deg } else { 0.0 };
         }
-
-        // Distribute rank from each node to its neighbors
-        for (i, edges) in out_edges.iter().enumerate() {
-            if out_degrees[i] > 0.0 {
-                let contribution = damping * pr[i] / out_degrees[i];
-                for &(j, weight) in edges {
-                    pr_new[j] += contribution * weight;
-                }
-            }
+        matrix.mul_vec(&scaled, &mut pr_new)?;
+        let base = (1.0 - damping) / n as f64 + dangling_sum;
+        for pr_new_item in pr_new.iter_mut() {
+            *pr_new_item += base;
         }
 
         // Check convergence
@@ -135,6 +116,79 @@ where
     Ok(centrality)
 }
 
+/// PageRank over any [`GraphTraversal`], counting every edge once and ignoring weights.
+///
+/// Follows the same iteration as [`pagerank`] on a graph whose weights are all `1.0`,
+/// including the uniform redistribution of rank from nodes without successors, but reads the
+/// graph only through `node_ids` and `neighbors`. That makes it the PageRank for graphs kept
+/// outside a `BaseGraph`, such as a [`CompressedGraph`](crate::core::compressed::CompressedGraph),
+/// whose successor lists are decoded afresh on every iteration instead of being copied.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::centrality::pagerank_on;
+/// use graphina::core::compressed::CompressedGraph;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, ());
+/// g.add_edge(b, c, ());
+/// g.add_edge(c, a, ());
+/// g.add_edge(a, c, ());
+///
+/// let ranks = pagerank_on(&CompressedGraph::from_graph(&g), 0.85, 100, 1e-9).unwrap();
+/// assert!(ranks[&c] > ranks[&b]);
+/// ```
+pub fn pagerank_on<A, W, G>(
+    graph: &G,
+    damping: f64,
+    max_iter: usize,
+    tolerance: f64,
+) -> Result<NodeMap<f64>>
+where
+    G: GraphTraversal<A, W>,
+{
+    let node_list: Vec<NodeId> = graph.node_ids().collect();
+    let n = node_list.len();
+    if n == 0 {
+        return Ok(NodeMap::default());
+    }
+    let bound = node_list.iter().map(|v| v.index() + 1).max().unwrap_or(0);
+    let mut position = vec![usize::MAX; bound];
+    for (i, v) in node_list.iter().enumerate() {
+        position[v.index()] = i;
+    }
+    let out_degrees: Vec<usize> = node_list
+        .iter()
+        .map(|&v| graph.neighbors(v).count())
+        .collect();
+
+    let mut pr = vec![1.0 / n as f64; n];
+    let mut pr_new = vec![0.0; n];
+    for _ in 0..max_iter {
+        let dangling_sum: f64 = (0..n).filter(|&i| out_degrees[i] == 0).map(|i| pr[i]).sum();
+        let base = (1.0 - damping) / n as f64 + damping * dangling_sum / n as f64;
+        pr_new.fill(base);
+        for (i, &v) in node_list.iter().enumerate() {
+            if out_degrees[i] > 0 {
+                let share = damping * pr[i] / out_degrees[i] as f64;
+                for u in graph.neighbors(v) {
+                    pr_new[position[u.index()]] += share;
+                }
+            }
+        }
+
+        let diff: f64 = pr.iter().zip(&pr_new).map(|(a, b)| (a - b).abs()).sum();
+        std::mem::swap(&mut pr, &mut pr_new);
+        if diff < tolerance {
+            break;
+        }
+    }
+    Ok(node_list.into_iter().zip(pr).collect())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -258,4 +312,35 @@ mod tests {
         let pr_partial = pagerank(&graph, 0.85, 100, 1e-6, Some(&partial_start)).unwrap();
         assert!((pr_partial[&n1] - 0.5).abs() < 1e-3);
     }
+
+    #[test]
+    fn test_pagerank_on_compressed_graph_matches_pagerank() {
+        use crate::core::compressed::CompressedGraph;
+
+        let mut directed: Digraph<(), f64> = Digraph::new();
+        let mut undirected: Graph<(), f64> = Graph::new();
+        let d: Vec<_> = (0..12).map(|_| directed.add_node(())).collect();
+        let u: Vec<_> = (0..12).map(|_| undirected.add_node(())).collect();
+        // Node 11 is dangling in the directed graph, and 3 -> 4 is doubled.
+        for i in 0..11 {
+            for j in [(i * 5 + 1) % 12, (i * 7 + 3) % 12] {
+                directed.add_edge(d[i], d[j], 1.0);
+                undirected.add_edge(u[i], u[j], 1.0);
+            }
+        }
+        directed.add_edge(d[3], d[4], 1.0);
+        directed.remove_node(d[6]);
+
+        let expected = pagerank(&directed, 0.85, 200, 1e-12, None).unwrap();
+        let ranks = pagerank_on(&CompressedGraph::from_graph(&directed), 0.85, 200, 1e-12).unwrap();
+        assert_eq!(ranks.len(), expected.len());
+        for (node, rank) in &expected {
+            assert!((ranks[node] - rank).abs() < 1e-9);
+        }
+        let expected = pagerank(&undirected, 0.85, 200, 1e-12, None).unwrap();
+        let ranks = pagerank_on(&undirected, 0.85, 200, 1e-12).unwrap();
+        for (node, rank) in &expected {
+            assert!((ranks[node] - rank).abs() < 1e-9);
+        }
+    }
 }
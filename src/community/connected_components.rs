@@ -2,54 +2,51 @@
 //!
 //! This module provides connected components for community detection.
 
-use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap, NodeSet};
-use std::collections::VecDeque;
+use crate::core::types::{BaseGraph, Digraph, Directed, GraphConstructor, NodeId, NodeMap};
+use crate::core::union_find::UnionFind;
+use rustc_hash::FxHashMap;
 
-/// Compute connected components of an undirected graph using BFS.
+/// Groups the nodes of `graph` by union-find over its edges, ignoring direction.
 ///
-/// **Time Complexity:** O(n + m)
+/// Components are ordered by their first node in iteration order, and nodes keep that
+/// order within a component.
+fn union_find_components<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<Vec<NodeId>>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut uf = UnionFind::new(graph.node_ids().map(|n| n.index() + 1).max().unwrap_or(0));
+    for (u, v, _) in graph.edges() {
+        uf.union(u.index(), v.index());
+    }
+    let mut slot = vec![usize::MAX; uf.len()];
+    let mut components: Vec<Vec<NodeId>> = Vec::new();
+    for node in graph.node_ids() {
+        let root = uf.find(node.index());
+        if slot[root] == usize::MAX {
+            slot[root] = components.len();
+            components.push(Vec::new());
+        }
+        components[slot[root]].push(node);
+    }
+    components
+}
+
+/// Compute connected components of an undirected graph using union-find.
+///
+/// Edges of a directed graph are followed in both directions, so there this gives the
+/// weakly connected components. Components are ordered by their first node in
+/// iteration order.
+///
+/// **Time Complexity:** O((n + m) α(n))
 ///
 /// # Returns
 /// A vector of components, where each component is a vector of `NodeId`s.
-///
-/// # Correctness Fix
-/// Previous implementation assumed contiguous node indices and had O(n*m) complexity
-/// due to iterating over all edges for each node. This version uses proper neighbor
-/// iteration and handles deleted nodes correctly.
 pub fn connected_components<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<Vec<NodeId>>
 where
     W: Copy,
     Ty: GraphConstructor<A, W>,
 {
-    let mut visited: NodeSet = NodeSet::default();
-    let mut components = Vec::new();
-
-    for (start_node, _) in graph.nodes() {
-        if visited.contains(&start_node) {
-            continue;
-        }
-
-        let mut component = Vec::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back(start_node);
-        visited.insert(start_node);
-
-        while let Some(node) = queue.pop_front() {
-            component.push(node);
-
-            // Use proper neighbor iterator instead of scanning all edges
-            for neighbor in graph.neighbors(node) {
-                if visited.insert(neighbor) {
-                    queue.push_back(neighbor);
-                }
-            }
-        }
-
-        components.push(component);
-    }
-
-    components
+    union_find_components(graph)
 }
 
 /// Compute connected components and return a NodeId -> component ID mapping.
@@ -70,13 +67,13 @@ where
     map
 }
 
-/// Compute the weakly connected components of a graph using BFS.
+/// Compute the weakly connected components of a graph using union-find.
 ///
 /// Edges are followed in both directions, so a directed graph is treated as
 /// undirected for the purpose of connectivity. On an undirected graph this is
 /// equivalent to [`connected_components`].
 ///
-/// **Time Complexity:** O(n + m)
+/// **Time Complexity:** O((n + m) α(n))
 ///
 /// # Returns
 /// A vector of components, where each component is a vector of `NodeId`s.
@@ -85,35 +82,7 @@ where
     W: Copy,
     Ty: GraphConstructor<A, W>,
 {
-    let mut visited: NodeSet = NodeSet::default();
-    let mut components = Vec::new();
-
-    for (start_node, _) in graph.nodes() {
-        if visited.contains(&start_node) {
-            continue;
-        }
-
-        let mut component = Vec::new();
-        let mut queue = VecDeque::new();
-
-        queue.push_back(start_node);
-        visited.insert(start_node);
-
-        while let Some(node) = queue.pop_front() {
-            component.push(node);
-
-            // Follow both outgoing and incoming edges so direction is ignored.
-            for neighbor in graph.neighbors(node).chain(graph.incoming_neighbors(node)) {
-                if visited.insert(neighbor) {
-                    queue.push_back(neighbor);
-                }
-            }
-        }
-
-        components.push(component);
-    }
-
-    components
+    union_find_components(graph)
 }
 
 /// Compute the strongly connected components of a directed graph.
@@ -123,6 +92,9 @@ where
 /// Tarjan's algorithm. On an undirected graph each component is also strongly
 /// connected, so the result matches [`connected_components`].
 ///
+/// Components come in reverse topological order: no edge leads from a component
+/// to one listed after it. [`condensation`] builds the DAG of the components.
+///
 /// **Time Complexity:** O(n + m)
 ///
 /// # Returns
@@ -138,6 +110,192 @@ where
         .collect()
 }
 
+/// Condenses every strongly connected component of a directed graph into a single node.
+///
+/// Returns the condensation and a map from each original node to its node in it. The
+/// condensation is a DAG whose node attributes list the members of each component, in
+/// the order of [`strongly_connected_components`] within a component. Its nodes are
+/// added in topological order, so every edge runs from a lower to a higher index. Each
+/// pair of components joined by at least one edge gets a single edge weighted by the
+/// number of original edges from the first to the second; edges inside a component,
+/// self-loops included, are dropped.
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::connected_components::condensation;
+/// use graphina::core::types::Digraph;
+///
+/// // A 2-cycle feeding a single node.
+/// let mut g = Digraph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, a, 1.0);
+/// g.add_edge(a, c, 1.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let (dag, map) = condensation(&g);
+/// assert_eq!(dag.node_count(), 2);
+/// assert_eq!(map[&a], map[&b]);
+/// let edge = dag.find_edge(map[&a], map[&c]).unwrap();
+/// assert_eq!(dag.edge_weight(edge), Some(&2));
+/// ```
+pub fn condensation<A, W>(
+    graph: &BaseGraph<A, W, Directed>,
+) -> (Digraph<Vec<NodeId>, usize>, NodeMap<NodeId>)
+where
+    W: Copy,
+{
+    let components = strongly_connected_components(graph);
+    let mut dag = Digraph::with_capacity(components.len(), 0);
+    let mut mapping = NodeMap::default();
+    for members in components.into_iter().rev() {
+        let scc = dag.add_node(members);
+        for &node in dag.node_attr(scc).into_iter().flatten() {
+            mapping.insert(node, scc);
+        }
+    }
+
+    let mut counts: FxHashMap<(NodeId, NodeId), usize> = FxHashMap::default();
+    let mut order: Vec<(NodeId, NodeId)> = Vec::new();
+    for (u, v, _) in graph.edges() {
+        let (cu, cv) = (mapping[&u], mapping[&v]);
+        if cu != cv {
+            let count = counts.entry((cu, cv)).or_insert(0);
+            if *count == 0 {
+                order.push((cu, cv));
+            }
+            *count += 1;
+        }
+    }
+    for pair in order {
+        dag.add_edge(pair.0, pair.1, counts[&pair]);
+    }
+    (dag, mapping)
+}
+
+/// Copies the subgraph induced by `members` into a new graph.
+///
+/// Nodes are added in node order, so the copy does not depend on the order of `members`.
+/// Returns the copy and a map from each kept original node to its new ID.
+fn extract_induced<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    mut members: Vec<NodeId>,
+) -> (BaseGraph<A, W, Ty>, NodeMap<NodeId>)
+where
+    A: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W>,
+{
+    members.sort_unstable_by_key(|node| node.index());
+    let mut sub = BaseGraph::with_capacity(members.len(), 0);
+    let mut mapping = NodeMap::default();
+    for node in members {
+        if let Some(attr) = graph.node_attr(node) {
+            mapping.insert(node, sub.add_node(attr.clone()));
+        }
+    }
+    for (u, v, w) in graph.edges() {
+        if let (Some(&nu), Some(&nv)) = (mapping.get(&u), mapping.get(&v)) {
+            sub.add_edge(nu, nv, w.clone());
+        }
+    }
+    (sub, mapping)
+}
+
+/// Extracts the largest connected component as a new graph.
+///
+/// Edge direction is ignored, so on a directed graph this is the largest weakly connected
+/// component. Returns the induced subgraph, with attributes, weights, and parallel edges
+/// copied and nodes in their original order, and a map from each kept node to its ID in
+/// the new graph. Ties go to the component listed first by [`connected_components`]. An
+/// empty graph gives an empty graph and map.
+///
+/// **Time Complexity:** O((n + m) α(n))
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::connected_components::largest_connected_component;
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<&str, f64>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// let c = g.add_node("c");
+/// let d = g.add_node("d");
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let (giant, map) = largest_connected_component(&g);
+/// assert_eq!(giant.node_count(), 3);
+/// assert_eq!(giant.node_attr(map[&c]), Some(&"c"));
+/// assert!(!map.contains_key(&d));
+/// ```
+pub fn largest_connected_component<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> (BaseGraph<A, W, Ty>, NodeMap<NodeId>)
+where
+    A: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W>,
+{
+    let largest = union_find_components(graph)
+        .into_iter()
+        .rev()
+        .max_by_key(Vec::len)
+        .unwrap_or_default();
+    extract_induced(graph, largest)
+}
+
+/// Extracts the largest strongly connected component of a directed graph as a new graph.
+///
+/// Like [`largest_connected_component`], but the component must be strongly connected.
+/// Ties go to the component holding the node with the lowest index.
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::connected_components::largest_strongly_connected_component;
+/// use graphina::core::types::Digraph;
+///
+/// // A 3-cycle with a tail that cannot get back.
+/// let mut g = Digraph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[2], 1.0);
+/// g.add_edge(n[2], n[0], 1.0);
+/// g.add_edge(n[2], n[3], 1.0);
+///
+/// let (core, map) = largest_strongly_connected_component(&g);
+/// assert_eq!((core.node_count(), core.edge_count()), (3, 3));
+/// assert!(!map.contains_key(&n[3]));
+/// ```
+pub fn largest_strongly_connected_component<A, W>(
+    graph: &BaseGraph<A, W, Directed>,
+) -> (Digraph<A, W>, NodeMap<NodeId>)
+where
+    A: Clone,
+    W: Copy,
+{
+    let lowest = |component: &Vec<NodeId>| component.iter().map(|n| n.index()).min();
+    let largest = strongly_connected_components(graph)
+        .into_iter()
+        .max_by(|a, b| {
+            a.len()
+                .cmp(&b.len())
+                .then_with(|| lowest(b).cmp(&lowest(a)))
+        })
+        .unwrap_or_default();
+    extract_induced(graph, largest)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -290,4 +448,82 @@ mod tests {
         assert_eq!(sorted_partition(weakly_connected_components(&g)), cc);
         assert_eq!(sorted_partition(strongly_connected_components(&g)), cc);
     }
+
+    #[test]
+    fn test_largest_components_keep_attributes_and_weights() {
+        let mut g = Graph::<u32, f64>::new();
+        let n: Vec<NodeId> = (0..7).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[2], n[3], 2.0);
+        g.add_edge(n[3], n[4], 3.0);
+        g.add_edge(n[4], n[2], 4.0);
+        g.add_edge(n[4], n[2], 5.0);
+        g.add_edge(n[5], n[6], 6.0);
+        g.remove_node(n[0]);
+
+        let (giant, map) = largest_connected_component(&g);
+        assert_eq!(giant.node_count(), 3);
+        let attrs: Vec<u32> = giant.nodes().map(|(_, a)| *a).collect();
+        assert_eq!(attrs, vec![2, 3, 4]);
+        let mut weights: Vec<f64> = giant.edges().map(|(_, _, w)| *w).collect();
+        weights.sort_by(f64::total_cmp);
+        assert_eq!(weights, vec![2.0, 3.0, 4.0, 5.0]);
+        assert_eq!(giant.node_attr(map[&n[4]]), Some(&4));
+        assert_eq!(map.len(), 3);
+
+        // Two strongly connected pairs of equal size: the one with the lower index wins.
+        let mut d = Digraph::<u32, f64>::new();
+        let m: Vec<NodeId> = (0..5).map(|i| d.add_node(i)).collect();
+        d.add_edge(m[3], m[4], 1.0);
+        d.add_edge(m[4], m[3], 1.0);
+        d.add_edge(m[1], m[2], 1.0);
+        d.add_edge(m[2], m[1], 1.0);
+        d.add_edge(m[0], m[1], 1.0);
+        d.add_edge(m[2], m[3], 1.0);
+        let (core, map) = largest_strongly_connected_component(&d);
+        assert_eq!((core.node_count(), core.edge_count()), (2, 2));
+        assert!(map.contains_key(&m[1]) && map.contains_key(&m[2]));
+        let (weak, _) = largest_connected_component(&d);
+        assert_eq!(weak.node_count(), 5);
+
+        let (empty, map) = largest_connected_component(&Graph::<u32, f64>::new());
+        assert!(empty.is_empty() && map.is_empty());
+    }
+
+    #[test]
+    fn test_condensation_is_a_topologically_ordered_dag() {
+        // Two 3-cycles joined by two edges, plus a tail node with a self-loop.
+        let mut g = Digraph::<i32, f64>::new();
+        let n: Vec<NodeId> = (0..7).map(|i| g.add_node(i)).collect();
+        for (u, v) in [
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 3),
+            (2, 4),
+        ] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        g.add_edge(n[5], n[6], 1.0);
+        g.add_edge(n[6], n[6], 1.0);
+
+        let (dag, map) = condensation(&g);
+        assert_eq!(dag.node_count(), 3);
+        assert_eq!(map.len(), 7);
+        assert_eq!(map[&n[0]], map[&n[2]]);
+        assert_eq!(map[&n[3]], map[&n[5]]);
+        assert_ne!(map[&n[0]], map[&n[3]]);
+        for (u, v, _) in dag.edges() {
+            assert!(u.index() < v.index());
+        }
+        let first = dag.find_edge(map[&n[0]], map[&n[3]]).unwrap();
+        assert_eq!(dag.edge_weight(first), Some(&2));
+        assert_eq!(dag.edge_count(), 2);
+        let mut members = dag.node_attr(map[&n[4]]).unwrap().clone();
+        members.sort();
+        assert_eq!(members, vec![n[3], n[4], n[5]]);
+    }
 }
@@ -10,6 +10,8 @@ use std::collections::{HashMap, HashSet, VecDeque};
 ///
 /// Uses Brandes’ algorithm to compute edge betweenness centrality, then iteratively removes the edge
 /// with the highest betweenness until the graph splits into at least `target_communities`.
+/// Edge direction and weights are ignored: a directed graph is split by its undirected
+/// skeleton into weakly connected pieces.
 ///
 /// **Time Complexity:** Worst-case O(n*m) per iteration (practically often lower).
 ///
@@ -26,6 +28,52 @@ pub fn girvan_newman<A, W, Ty>(
 where
     W: Copy + PartialOrd + Into<f64> + From<u8>,
     Ty: GraphConstructor<A, W>,
+{
+    split(graph, target_communities).map(|(communities, _)| communities)
+}
+
+/// The edges [`girvan_newman`] removes on its way to `target_communities`, in removal
+/// order, as unordered node pairs.
+///
+/// Parallel edges between a pair count as one. Replaying the removals shows how the graph
+/// falls apart, for example as an animation.
+///
+/// # Errors
+/// The same as [`girvan_newman`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::girvan_newman::girvan_newman_removals;
+/// use graphina::core::types::Graph;
+///
+/// // Two triangles joined by a bridge, which carries every shortest path between them.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+///     g.add_edge(n[u], n[v], 1.0);
+/// }
+///
+/// assert_eq!(girvan_newman_removals(&g, 2).unwrap(), vec![(n[2], n[3])]);
+/// ```
+pub fn girvan_newman_removals<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    target_communities: usize,
+) -> Result<Vec<(NodeId, NodeId)>>
+where
+    W: Copy + PartialOrd + Into<f64> + From<u8>,
+    Ty: GraphConstructor<A, W>,
+{
+    split(graph, target_communities).map(|(_, removals)| removals)
+}
+
+/// The communities reached and the node pairs removed to get there.
+type Split = (Vec<Vec<NodeId>>, Vec<(NodeId, NodeId)>);
+
+/// Shared body of [`girvan_newman`] and [`girvan_newman_removals`].
+fn split<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, target_communities: usize) -> Result<Split>
+where
+    Ty: GraphConstructor<A, W>,
 {
     if target_communities == 0 {
         return Err(GraphinaError::invalid_graph(
@@ -58,6 +106,7 @@ where
     }
 
     // Remove edges iteratively until we reach the desired number of components.
+    let mut removals = Vec::new();
     while connected_components_count(&neighbors) < target_communities {
         let edge_btwn = compute_edge_betweenness(n, &neighbors);
         if let Some((&(u, v), _)) = edge_btwn
@@ -67,13 +116,17 @@ where
             neighbors[u].remove(&v);
             neighbors[v].remove(&u);
             active_edges.retain(|&(a, b)| !(a == u && b == v));
+            removals.push((node_list[u], node_list[v]));
         } else {
             return Err(GraphinaError::invalid_graph(
                 "Girvan-Newman: no edges to split further",
             ));
         }
     }
-    Ok(compute_components_from_neighbors(&neighbors, &node_list))
+    Ok((
+        compute_components_from_neighbors(&neighbors, &node_list),
+        removals,
+    ))
 }
 
 /// Helper: Compute connected components from an adjacency list and map back to NodeId.
@@ -1,6 +1,26 @@
 //! Infomap algorithms.
 //!
-//! This module provides Infomap for community detection.
+//! This module provides Infomap for community detection, following Rosvall and Bergstrom's
+//! map equation ("Maps of random walks on complex networks reveal community structure", 2008,
+//! and "Multilevel compression of random walks on networks reveals hierarchical organization
+//! in large integrated systems", 2011).
+//!
+//! The map equation measures how many bits per step it takes to describe a random walk on the
+//! graph when nodes are grouped into modules: each module has its own codebook for the nodes
+//! inside it, and an index codebook names the module the walker enters. A good partition
+//! keeps the walker inside modules for long stretches, so the description is short.
+//!
+//! The flow of the walk is computed once before optimizing:
+//!
+//! * On undirected graphs a node is visited in proportion to its weighted degree and every
+//!   edge carries flow in both directions in proportion to its weight.
+//! * On directed graphs the visit rates are PageRank with teleportation probability
+//!   `teleportation`, and dangling nodes teleport. As in the reference Infomap tool, the
+//!   teleportation steps shape the flow but are not encoded, so only flow along edges enters
+//!   and exits modules.
+//!
+//! Self-loops are ignored, and edge weights must be finite and non-negative. Codelengths are
+//! reported in bits.
 
 use crate::core::error::{GraphinaError, Result};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
@@ -8,6 +28,17 @@ use rand::prelude::*;
 use rand::{SeedableRng, rngs::StdRng};
 use std::collections::HashMap;
 
+/// Teleportation probability used by [`infomap`] on directed graphs.
+const DEFAULT_TELEPORTATION: f64 = 0.15;
+/// Smallest codelength improvement, in bits, that counts as progress.
+const MIN_IMPROVEMENT: f64 = 1e-10;
+/// A sweep of local moves that shortens the codelength by less than this many bits ends the
+/// search on its level; later sweeps only shuffle a few boundary nodes.
+const SWEEP_TOLERANCE: f64 = 1e-4;
+/// Iteration cap and L1 tolerance for the PageRank flow on directed graphs.
+const FLOW_MAX_ITER: usize = 1000;
+const FLOW_TOLERANCE: f64 = 1e-15;
+
 /// Private helper: Create a seeded RNG from an optional seed.
 fn create_rng(seed: Option<u64>) -> StdRng {
     match seed {
@@ -16,20 +47,629 @@ fn create_rng(seed: Option<u64>) -> StdRng {
     }
 }
 
-/// Production-level Infomap (simplified) for community detection.
+fn plogp(p: f64) -> f64 {
+    if p > 0.0 { p * p.log2() } else { 0.0 }
+}
+
+/// A two-level partition found by [`infomap_two_level`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfomapResult {
+    /// Module of each node in internal node order, numbered from zero in order of first
+    /// appearance.
+    pub modules: Vec<usize>,
+    /// Map equation codelength of `modules`, in bits.
+    pub codelength: f64,
+    /// Codelength with every node in a single module, which is the entropy of the visit rates.
+    pub one_level_codelength: f64,
+}
+
+/// A nested partition found by [`infomap_hierarchical`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfomapHierarchy {
+    /// For each node in internal node order, the path of module indices from the top level
+    /// down to the leaf module that holds it. Indices are local to their parent module and
+    /// numbered from zero, so `[1, 0]` is the first submodule of the second top module.
+    pub paths: Vec<Vec<usize>>,
+    /// Hierarchical map equation codelength of the tree, in bits.
+    pub codelength: f64,
+    /// Codelength with every node in a single module, which is the entropy of the visit rates.
+    pub one_level_codelength: f64,
+}
+
+impl InfomapHierarchy {
+    /// Returns the number of module levels, which is the length of the longest path.
+    pub fn depth(&self) -> usize {
+        self.paths.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Returns a flat partition at `level`, where level 0 is the top modules.
+    ///
+    /// Nodes whose leaf module sits above `level` keep that leaf module. Labels are numbered
+    /// from zero in order of first appearance.
+    pub fn modules_at(&self, level: usize) -> Vec<usize> {
+        let mut ids: HashMap<&[usize], usize> = HashMap::new();
+        self.paths
+            .iter()
+            .map(|path| {
+                let prefix = &path[..path.len().min(level + 1)];
+                let next = ids.len();
+                *ids.entry(prefix).or_insert(next)
+            })
+            .collect()
+    }
+}
+
+/// Flow network that the optimizer works on: the nodes of the graph, of a module, or of an
+/// aggregated level.
+#[derive(Debug, Clone)]
+struct Level {
+    /// Visit rate of each node. At the super-module stage this is the module's entry flow.
+    flow: Vec<f64>,
+    /// Total flow leaving and entering each node, including arcs that leave the level.
+    out_flow: Vec<f64>,
+    in_flow: Vec<f64>,
+    /// Arcs between nodes of this level, with their flow.
+    out_arcs: Vec<Vec<(usize, f64)>>,
+    in_arcs: Vec<Vec<(usize, f64)>>,
+}
+
+impl Level {
+    fn len(&self) -> usize {
+        self.flow.len()
+    }
+
+    fn from_arcs(flow: Vec<f64>, arcs: &[(usize, usize, f64)]) -> Self {
+        let n = flow.len();
+        let mut level = Level {
+            flow,
+            out_flow: vec![0.0; n],
+            in_flow: vec![0.0; n],
+            out_arcs: vec![Vec::new(); n],
+            in_arcs: vec![Vec::new(); n],
+        };
+        for &(u, v, f) in arcs {
+            level.out_flow[u] += f;
+            level.in_flow[v] += f;
+            level.out_arcs[u].push((v, f));
+            level.in_arcs[v].push((u, f));
+        }
+        level
+    }
+
+    /// The sub-network induced by `nodes`, keeping each node's total in and out flow.
+    fn restrict(&self, nodes: &[usize]) -> Self {
+        let local: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+        let keep = |arcs: &[(usize, f64)]| -> Vec<(usize, f64)> {
+            arcs.iter()
+                .filter_map(|&(v, f)| local.get(&v).map(|&j| (j, f)))
+                .collect()
+        };
+        Level {
+            flow: nodes.iter().map(|&v| self.flow[v]).collect(),
+            out_flow: nodes.iter().map(|&v| self.out_flow[v]).collect(),
+            in_flow: nodes.iter().map(|&v| self.in_flow[v]).collect(),
+            out_arcs: nodes.iter().map(|&v| keep(&self.out_arcs[v])).collect(),
+            in_arcs: nodes.iter().map(|&v| keep(&self.in_arcs[v])).collect(),
+        }
+    }
+
+    /// Flow, exit flow, and entry flow of each of the `k` modules in `modules`.
+    fn module_stats(&self, modules: &[usize], k: usize) -> (Vec<f64>, Vec<f64>, Vec<f64>) {
+        let mut flow = vec![0.0; k];
+        let mut exit = vec![0.0; k];
+        let mut enter = vec![0.0; k];
+        for u in 0..self.len() {
+            let m = modules[u];
+            flow[m] += self.flow[u];
+            exit[m] += self.out_flow[u];
+            enter[m] += self.in_flow[u];
+            for &(v, f) in &self.out_arcs[u] {
+                if modules[v] == m {
+                    exit[m] -= f;
+                    enter[m] -= f;
+                }
+            }
+        }
+        let clamp = |x: &mut f64| *x = x.max(0.0);
+        exit.iter_mut().for_each(clamp);
+        enter.iter_mut().for_each(clamp);
+        (flow, exit, enter)
+    }
+
+    /// Collapses each module into one node, keeping the arcs between modules.
+    fn aggregate(&self, modules: &[usize], k: usize) -> Self {
+        let (flow, exit, enter) = self.module_stats(modules, k);
+        let mut between: HashMap<(usize, usize), f64> = HashMap::new();
+        for u in 0..self.len() {
+            for &(v, f) in &self.out_arcs[u] {
+                let (mu, mv) = (modules[u], modules[v]);
+                if mu != mv {
+                    *between.entry((mu, mv)).or_insert(0.0) += f;
+                }
+            }
+        }
+        let mut arcs: Vec<(usize, usize, f64)> =
+            between.into_iter().map(|((u, v), f)| (u, v, f)).collect();
+        arcs.sort_unstable_by_key(|&(u, v, _)| (u, v));
+        let mut level = Level::from_arcs(flow, &arcs);
+        level.out_flow = exit;
+        level.in_flow = enter;
+        level
+    }
+}
+
+/// Builds the flow network of `graph`, returning it with the node order it uses.
+fn flow_network<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    teleportation: f64,
+) -> Result<(Vec<NodeId>, Level)>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let n = graph.node_count();
+    if n == 0 {
+        return Err(GraphinaError::invalid_graph("Infomap: empty graph"));
+    }
+    if !(teleportation > 0.0 && teleportation < 1.0) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Infomap: teleportation must be in (0, 1), got {}",
+            teleportation
+        )));
+    }
+    let node_list: Vec<NodeId> = graph.node_ids().collect();
+    let index_of: HashMap<NodeId, usize> =
+        node_list.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let mut edges = Vec::with_capacity(graph.edge_count());
+    for (src, tgt, &w) in graph.edges() {
+        let w: f64 = w.into();
+        if !w.is_finite() || w < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Infomap: edge weights must be finite and non-negative, got {}",
+                w
+            )));
+        }
+        let (u, v) = (index_of[&src], index_of[&tgt]);
+        if u != v && w > 0.0 {
+            edges.push((u, v, w));
+        }
+    }
+
+    let total: f64 = edges.iter().map(|e| e.2).sum();
+    if total <= 0.0 {
+        return Ok((node_list, Level::from_arcs(vec![1.0 / n as f64; n], &[])));
+    }
+    if !graph.is_directed() {
+        let mut flow = vec![0.0; n];
+        let mut arcs = Vec::with_capacity(2 * edges.len());
+        for &(u, v, w) in &edges {
+            let f = w / (2.0 * total);
+            flow[u] += f;
+            flow[v] += f;
+            arcs.push((u, v, f));
+            arcs.push((v, u, f));
+        }
+        return Ok((node_list, Level::from_arcs(flow, &arcs)));
+    }
+
+    let mut out_weight = vec![0.0; n];
+    for &(u, _, w) in &edges {
+        out_weight[u] += w;
+    }
+    let uniform = 1.0 / n as f64;
+    let mut rank = vec![uniform; n];
+    for _ in 0..FLOW_MAX_ITER {
+        let dangling: f64 = (0..n)
+            .filter(|&u| out_weight[u] == 0.0)
+            .map(|u| rank[u])
+            .sum();
+        let base = (teleportation + (1.0 - teleportation) * dangling) * uniform;
+        let mut next = vec![base; n];
+        for &(u, v, w) in &edges {
+            next[v] += (1.0 - teleportation) * rank[u] * w / out_weight[u];
+        }
+        let sum: f64 = next.iter().sum();
+        next.iter_mut().for_each(|x| *x /= sum);
+        let delta: f64 = next.iter().zip(&rank).map(|(a, b)| (a - b).abs()).sum();
+        rank = next;
+        if delta < FLOW_TOLERANCE * n as f64 {
+            break;
+        }
+    }
+    let arcs: Vec<(usize, usize, f64)> = edges
+        .iter()
+        .map(|&(u, v, w)| (u, v, (1.0 - teleportation) * rank[u] * w / out_weight[u]))
+        .collect();
+    Ok((node_list, Level::from_arcs(rank, &arcs)))
+}
+
+/// Flow, exit flow, and entry flow of one module.
+type ModuleState = (f64, f64, f64);
+
+/// Sums over modules of the module terms of the two-level map equation.
+#[derive(Debug, Clone, Copy)]
+struct Sums {
+    enter: f64,
+    plogp_enter: f64,
+    plogp_exit: f64,
+    plogp_exit_flow: f64,
+}
+
+impl Sums {
+    /// The sums after one module changes from `old` to `new`.
+    fn replace(self, old: ModuleState, new: ModuleState) -> Self {
+        Sums {
+            enter: self.enter + new.2 - old.2,
+            plogp_enter: self.plogp_enter + plogp(new.2) - plogp(old.2),
+            plogp_exit: self.plogp_exit + plogp(new.1) - plogp(old.1),
+            plogp_exit_flow: self.plogp_exit_flow + plogp(new.1 + new.0) - plogp(old.1 + old.0),
+        }
+    }
+}
+
+/// Module states and running sums of the two-level map equation, so that the effect of a
+/// single move can be evaluated in constant time. `offset` is the exit flow of the enclosing
+/// module, which shares the index codebook when optimizing inside a module.
+struct ModuleTerms {
+    offset: f64,
+    states: Vec<ModuleState>,
+    sums: Sums,
+}
+
+impl ModuleTerms {
+    fn new(level: &Level, modules: &[usize], k: usize, offset: f64) -> Self {
+        let (flow, exit, enter) = level.module_stats(modules, k);
+        let sums = Sums {
+            enter: enter.iter().sum(),
+            plogp_enter: enter.iter().map(|&x| plogp(x)).sum(),
+            plogp_exit: exit.iter().map(|&x| plogp(x)).sum(),
+            plogp_exit_flow: exit.iter().zip(&flow).map(|(&x, &p)| plogp(x + p)).sum(),
+        };
+        let states = (0..k).map(|m| (flow[m], exit[m], enter[m])).collect();
+        ModuleTerms {
+            offset,
+            states,
+            sums,
+        }
+    }
+
+    /// Codelength without the constant node-visit term.
+    fn codelength(&self) -> f64 {
+        self.cost(self.sums)
+    }
+
+    fn cost(&self, sums: Sums) -> f64 {
+        plogp(self.offset + sums.enter.max(0.0))
+            - plogp(self.offset)
+            - sums.plogp_enter
+            - sums.plogp_exit
+            + sums.plogp_exit_flow
+    }
+
+    /// Replaces the state of module `m` and updates the sums.
+    fn set(&mut self, m: usize, state: ModuleState) {
+        self.sums = self.sums.replace(self.states[m], state);
+        self.states[m] = state;
+    }
+}
+
+/// Sweeps over the nodes of `level` in random order, moving each to the neighboring (or an
+/// empty) module that lowers the codelength most, until a sweep makes no move or `max_iter`
+/// sweeps have run. Returns modules renumbered from zero and their count.
+fn local_moves(
+    level: &Level,
+    offset: f64,
+    max_iter: usize,
+    rng: &mut StdRng,
+) -> (Vec<usize>, usize) {
+    let n = level.len();
+    let mut modules: Vec<usize> = (0..n).collect();
+    let mut size = vec![1usize; n];
+    let mut empty: Vec<usize> = Vec::new();
+    let mut terms = ModuleTerms::new(level, &modules, n, offset);
+    let mut order: Vec<usize> = (0..n).collect();
+    let mut out_to = vec![0.0; n];
+    let mut in_from = vec![0.0; n];
+    let mut touched: Vec<usize> = Vec::new();
+
+    for _ in 0..max_iter {
+        let mut moved = false;
+        let sweep_start = terms.codelength();
+        order.shuffle(rng);
+        for &u in &order {
+            let current = modules[u];
+            for &(v, f) in &level.out_arcs[u] {
+                let m = modules[v];
+                if out_to[m] == 0.0 && in_from[m] == 0.0 {
+                    touched.push(m);
+                }
+                out_to[m] += f;
+            }
+            for &(v, f) in &level.in_arcs[u] {
+                let m = modules[v];
+                if out_to[m] == 0.0 && in_from[m] == 0.0 {
+                    touched.push(m);
+                }
+                in_from[m] += f;
+            }
+            touched.sort_unstable();
+            touched.dedup();
+
+            let (p, out_u, in_u) = (level.flow[u], level.out_flow[u], level.in_flow[u]);
+            let (to_a, from_a) = (out_to[current], in_from[current]);
+            // State of the current module once `u` has left it.
+            let (flow_a, exit_a, enter_a) = terms.states[current];
+            let left = (
+                flow_a - p,
+                (exit_a - (out_u - to_a) + from_a).max(0.0),
+                (enter_a - (in_u - from_a) + to_a).max(0.0),
+            );
+            let without = terms.sums.replace(terms.states[current], left);
+
+            let mut candidates: Vec<usize> =
+                touched.iter().copied().filter(|&m| m != current).collect();
+            if size[current] > 1 {
+                if let Some(&m) = empty.last() {
+                    candidates.push(m);
+                }
+            }
+            let before = terms.codelength();
+            let mut best: Option<(usize, f64, ModuleState)> = None;
+            for &b in &candidates {
+                let (to_b, from_b) = (out_to[b], in_from[b]);
+                let (flow_b, exit_b, enter_b) = terms.states[b];
+                let joined = (
+                    flow_b + p,
+                    (exit_b + (out_u - to_b) - from_b).max(0.0),
+                    (enter_b + (in_u - from_b) - to_b).max(0.0),
+                );
+                let delta = terms.cost(without.replace(terms.states[b], joined)) - before;
+                if delta < -MIN_IMPROVEMENT && best.is_none_or(|(_, d, _)| delta < d) {
+                    best = Some((b, delta, joined));
+                }
+            }
+            if let Some((b, _, joined)) = best {
+                terms.set(current, left);
+                terms.set(b, joined);
+                if empty.last() == Some(&b) {
+                    empty.pop();
+                }
+                size[current] -= 1;
+                size[b] += 1;
+                if size[current] == 0 {
+                    empty.push(current);
+                }
+                modules[u] = b;
+                moved = true;
+            }
+            for &m in &touched {
+                out_to[m] = 0.0;
+                in_from[m] = 0.0;
+            }
+            touched.clear();
+        }
+        let after = terms.codelength();
+        if !moved || sweep_start - after < SWEEP_TOLERANCE {
+            break;
+        }
+    }
+    renumber(&mut modules)
+}
+
+/// Renumbers labels from zero in order of first appearance and returns their count.
+fn renumber(labels: &mut [usize]) -> (Vec<usize>, usize) {
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    for label in labels.iter_mut() {
+        let next = ids.len();
+        *label = *ids.entry(*label).or_insert(next);
+    }
+    (labels.to_vec(), ids.len())
+}
+
+/// Two-level optimization of `level`: local moves, then the same on the network of modules,
+/// repeated until no modules merge. Returns modules of the nodes of `level`, their count, and
+/// the codelength without the constant node-visit term.
+fn optimize(
+    level: &Level,
+    offset: f64,
+    max_iter: usize,
+    rng: &mut StdRng,
+) -> (Vec<usize>, usize, f64) {
+    let mut assignment: Vec<usize> = (0..level.len()).collect();
+    let mut current = level.clone();
+    loop {
+        let (modules, k) = local_moves(&current, offset, max_iter, rng);
+        if k == current.len() {
+            break;
+        }
+        for a in assignment.iter_mut() {
+            *a = modules[*a];
+        }
+        if k == 1 {
+            break;
+        }
+        current = current.aggregate(&modules, k);
+    }
+    let (assignment, k) = renumber(&mut assignment);
+    let cost = ModuleTerms::new(level, &assignment, k, offset).codelength();
+    (assignment, k, cost)
+}
+
+/// Runs [`optimize`] `trials` times and keeps the shortest codelength.
+fn best_of(
+    level: &Level,
+    max_iter: usize,
+    trials: usize,
+    rng: &mut StdRng,
+) -> (Vec<usize>, usize, f64) {
+    let mut best = optimize(level, 0.0, max_iter, rng);
+    for _ in 1..trials {
+        let candidate = optimize(level, 0.0, max_iter, rng);
+        if candidate.2 < best.2 - MIN_IMPROVEMENT {
+            best = candidate;
+        }
+    }
+    best
+}
+
+/// Entropy of the visit rates. Negated, it is the constant node-visit term of every
+/// codelength.
+fn node_entropy(level: &Level) -> f64 {
+    -level.flow.iter().map(|&p| plogp(p)).sum::<f64>()
+}
+
+/// Hierarchical map equation codelength of the module tree given by `paths`.
 ///
-/// Inspired by the map equation framework, each node is initially in its own module.
-/// In randomized order, each node is re-assigned to a neighbor's module if that move increases flow (reduces description length).
+/// Every tree module is identified by a path prefix. A leaf module describes its exit and
+/// the visits to its nodes; any other module describes its exit and the entries into its
+/// children.
+fn tree_codelength(level: &Level, paths: &[Vec<usize>]) -> f64 {
+    let mut exit: HashMap<&[usize], f64> = HashMap::new();
+    let mut enter: HashMap<&[usize], f64> = HashMap::new();
+    let mut flow: HashMap<&[usize], f64> = HashMap::new();
+    let mut children: HashMap<&[usize], Vec<&[usize]>> = HashMap::new();
+    let mut leaves: HashMap<&[usize], f64> = HashMap::new();
+    for (u, path) in paths.iter().enumerate() {
+        for d in 0..=path.len() {
+            *flow.entry(&path[..d]).or_insert(0.0) += level.flow[u];
+            if d > 0 {
+                let siblings = children.entry(&path[..d - 1]).or_default();
+                if !siblings.contains(&&path[..d]) {
+                    siblings.push(&path[..d]);
+                }
+            }
+        }
+        *leaves.entry(path.as_slice()).or_insert(0.0) += plogp(level.flow[u]);
+        for &(v, f) in &level.out_arcs[u] {
+            let other = &paths[v];
+            let common = path.iter().zip(other).take_while(|(a, b)| a == b).count();
+            for d in common + 1..=path.len() {
+                *exit.entry(&path[..d]).or_insert(0.0) += f;
+            }
+            for d in common + 1..=other.len() {
+                *enter.entry(&other[..d]).or_insert(0.0) += f;
+            }
+        }
+    }
+    let get = |map: &HashMap<&[usize], f64>, key: &[usize]| map.get(key).copied().unwrap_or(0.0);
+    let mut total = 0.0;
+    for (&module, &p) in &flow {
+        let q = get(&exit, module);
+        match children.get(module) {
+            Some(kids) => {
+                let entries: Vec<f64> = kids.iter().map(|c| get(&enter, c)).collect();
+                total += plogp(q + entries.iter().sum::<f64>())
+                    - plogp(q)
+                    - entries.iter().map(|&x| plogp(x)).sum::<f64>();
+            }
+            None => total += plogp(q + p) - plogp(q) - get(&leaves, module),
+        }
+    }
+    total
+}
+
+/// Renumbers the module indices in `paths` so that the children of every module are
+/// numbered from zero in order of first appearance.
+fn renumber_paths(paths: &mut [Vec<usize>]) {
+    let mut ids: HashMap<(Vec<usize>, usize), usize> = HashMap::new();
+    let mut counts: HashMap<Vec<usize>, usize> = HashMap::new();
+    for path in paths.iter_mut() {
+        let mut prefix = Vec::with_capacity(path.len());
+        for slot in path.iter_mut() {
+            let key = (prefix.clone(), *slot);
+            let id = match ids.get(&key) {
+                Some(&id) => id,
+                None => {
+                    let count = counts.entry(prefix.clone()).or_insert(0);
+                    let id = *count;
+                    *count += 1;
+                    ids.insert(key, id);
+                    id
+                }
+            };
+            *slot = id;
+            prefix.push(id);
+        }
+    }
+}
+
+/// Tries to split the leaf module holding `nodes` of `level`, whose exit flow is `exit`,
+/// into submodules, recursing into accepted submodules. Appends the submodule indices of
+/// an accepted split to `paths`.
+fn split_module(
+    level: &Level,
+    nodes: &[usize],
+    exit: f64,
+    max_iter: usize,
+    rng: &mut StdRng,
+    paths: &mut [Vec<usize>],
+) {
+    if nodes.len() < 3 {
+        return;
+    }
+    let flow: f64 = nodes.iter().map(|&u| level.flow[u]).sum();
+    let visits: f64 = nodes.iter().map(|&u| plogp(level.flow[u])).sum();
+    let unsplit = plogp(exit + flow) - plogp(exit) - visits;
+    let sub = level.restrict(nodes);
+    let (modules, k, _) = optimize(&sub, exit, max_iter, rng);
+    if k < 2 {
+        return;
+    }
+    let (sub_flow, sub_exit, sub_enter) = sub.module_stats(&modules, k);
+    let mut sub_visits = vec![0.0; k];
+    for (i, &m) in modules.iter().enumerate() {
+        sub_visits[m] += plogp(sub.flow[i]);
+    }
+    let index = plogp(exit + sub_enter.iter().sum::<f64>())
+        - plogp(exit)
+        - sub_enter.iter().map(|&x| plogp(x)).sum::<f64>();
+    let leaves: f64 = (0..k)
+        .map(|m| plogp(sub_exit[m] + sub_flow[m]) - plogp(sub_exit[m]) - sub_visits[m])
+        .sum();
+    if index + leaves >= unsplit - MIN_IMPROVEMENT {
+        return;
+    }
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); k];
+    for (i, &m) in modules.iter().enumerate() {
+        members[m].push(nodes[i]);
+        paths[nodes[i]].push(m);
+    }
+    for (m, group) in members.iter().enumerate() {
+        split_module(level, group, sub_exit[m], max_iter, rng, paths);
+    }
+}
+
+fn check_parameters(max_iter: usize, trials: usize) -> Result<()> {
+    if max_iter == 0 {
+        return Err(GraphinaError::invalid_graph("Infomap: max_iter=0"));
+    }
+    if trials == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "Infomap: at least one trial is required",
+        ));
+    }
+    Ok(())
+}
+
+/// Infomap community detection with the two-level map equation.
+///
+/// Each node starts in its own module. In randomized order, nodes move to the neighboring
+/// module that shortens the description of the random walk most, and once no move helps the
+/// modules are collapsed into single nodes and the search repeats on the coarser network.
+/// On directed graphs the flow uses PageRank with a teleportation probability of 0.15; use
+/// [`infomap_two_level`] to choose it, run several trials, or get the codelength.
 ///
-/// **Time Complexity:** Approximately O(max_iter * (n + m))
+/// **Time Complexity:** Approximately O(max_iter * (n + m)) per aggregation level
 ///
 /// # Parameters
-/// - `max_iter`: Maximum number of iterations.
+/// - `max_iter`: Maximum number of sweeps over the nodes on each level.
 /// - `seed`: Optional seed for RNG used for shuffling nodes.
 ///
 /// # Returns
-/// A vector (length n) of module assignments (usize) for each node.
-/// Returns `GraphinaError::InvalidGraph` on empty graph or invalid parameters.
+/// A vector (length n) of module assignments (usize) for each node, numbered from zero in
+/// order of first appearance.
+/// Returns `GraphinaError::InvalidGraph` on empty graph or `max_iter == 0`, and
+/// `GraphinaError::InvalidArgument` on a negative or non-finite edge weight.
 pub fn infomap<A, W, Ty>(
     graph: &BaseGraph<A, W, Ty>,
     max_iter: usize,
@@ -39,68 +679,290 @@ where
     W: Copy + PartialOrd + Into<f64> + From<u8>,
     Ty: GraphConstructor<A, W>,
 {
-    let n = graph.node_count();
-    if n == 0 {
-        return Err(GraphinaError::invalid_graph("Infomap: empty graph"));
+    infomap_two_level(graph, max_iter, 1, DEFAULT_TELEPORTATION, seed).map(|r| r.modules)
+}
+
+/// Two-level Infomap with control over teleportation and restarts.
+///
+/// Runs the optimization of [`infomap`] `trials` times from different random orders and
+/// keeps the partition with the shortest codelength. `teleportation` only affects directed
+/// graphs.
+///
+/// # Errors
+/// Returns `InvalidGraph` for an empty graph or `max_iter == 0`, and `InvalidArgument` if
+/// `trials == 0`, `teleportation` is not in `(0, 1)`, or an edge weight is negative or not
+/// finite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::infomap::infomap_two_level;
+/// use graphina::core::types::Graph;
+///
+/// // Two triangles joined by a single edge.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+///     g.add_edge(n[u], n[v], 1.0);
+/// }
+///
+/// let result = infomap_two_level(&g, 100, 5, 0.15, Some(1)).unwrap();
+/// assert_eq!(result.modules, vec![0, 0, 0, 1, 1, 1]);
+/// assert!(result.codelength < result.one_level_codelength);
+/// ```
+pub fn infomap_two_level<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    max_iter: usize,
+    trials: usize,
+    teleportation: f64,
+    seed: Option<u64>,
+) -> Result<InfomapResult>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let (_, level) = flow_network(graph, teleportation)?;
+    check_parameters(max_iter, trials)?;
+    let mut rng = create_rng(seed);
+    let (modules, _, cost) = best_of(&level, max_iter, trials, &mut rng);
+    let entropy = node_entropy(&level);
+    Ok(InfomapResult {
+        modules,
+        codelength: cost + entropy,
+        one_level_codelength: entropy,
+    })
+}
+
+/// Hierarchical Infomap with the multilevel map equation.
+///
+/// Starts from the best two-level partition. Modules are then grouped into super-modules
+/// for as long as an extra index level shortens the description, and each leaf module is
+/// recursively split into submodules whenever describing the walk inside it through a
+/// nested index codebook is cheaper. The result is a tree of modules of any depth; on a
+/// graph without nested structure it is the two-level partition.
+///
+/// # Errors
+/// Same as [`infomap_two_level`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::infomap::infomap_hierarchical;
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+///     g.add_edge(n[u], n[v], 1.0);
+/// }
+///
+/// let tree = infomap_hierarchical(&g, 100, 5, 0.15, Some(1)).unwrap();
+/// assert_eq!(tree.modules_at(0), vec![0, 0, 0, 1, 1, 1]);
+/// assert!(tree.codelength <= tree.one_level_codelength);
+/// ```
+pub fn infomap_hierarchical<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    max_iter: usize,
+    trials: usize,
+    teleportation: f64,
+    seed: Option<u64>,
+) -> Result<InfomapHierarchy>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let (_, level) = flow_network(graph, teleportation)?;
+    check_parameters(max_iter, trials)?;
+    let mut rng = create_rng(seed);
+    let entropy = node_entropy(&level);
+    let (modules, k, _) = best_of(&level, max_iter, trials, &mut rng);
+    let mut paths: Vec<Vec<usize>> = modules.iter().map(|&m| vec![m]).collect();
+
+    // Group the top modules into super-modules while that shortens the description. On the
+    // network of modules the "visit rate" of a module is its entry flow, which is what the
+    // codebook of the enclosing super-module has to describe.
+    let mut top_of: Vec<usize> = modules.clone();
+    let mut top = level.aggregate(&modules, k);
+    top.flow = top.in_flow.clone();
+    let mut codelength = tree_codelength(&level, &paths);
+    while top.len() > 2 {
+        let (supers, count, _) = optimize(&top, 0.0, max_iter, &mut rng);
+        if count < 2 || count == top.len() {
+            break;
+        }
+        let candidate: Vec<Vec<usize>> = paths
+            .iter()
+            .zip(&top_of)
+            .map(|(path, &t)| {
+                std::iter::once(supers[t])
+                    .chain(path.iter().copied())
+                    .collect()
+            })
+            .collect();
+        let cost = tree_codelength(&level, &candidate);
+        if cost >= codelength - MIN_IMPROVEMENT {
+            break;
+        }
+        paths = candidate;
+        codelength = cost;
+        top_of.iter_mut().for_each(|t| *t = supers[*t]);
+        top = top.aggregate(&supers, count);
+        top.flow = top.in_flow.clone();
     }
-    if max_iter == 0 {
-        return Err(GraphinaError::invalid_graph("Infomap: max_iter=0"));
+
+    // Split leaf modules into submodules where nesting pays off. Super-modules only add
+    // levels above them, so the leaf modules are still the two-level modules.
+    let (_, exits, _) = level.module_stats(&modules, k);
+    let mut members: Vec<Vec<usize>> = vec![Vec::new(); k];
+    for (u, &m) in modules.iter().enumerate() {
+        members[m].push(u);
     }
-    // Map nodes to contiguous indices and build a weighted adjacency list once
-    // (O(E)). The previous version scanned every edge for every node on every
-    // iteration (O(max_iter * n * E)) and indexed by `NodeId::index()`, which
-    // also broke after a node removal. Mirror the original both-endpoints
-    // accumulation: edge (src, tgt) contributes to src's view of tgt and tgt's
-    // view of src.
-    let node_list: Vec<NodeId> = graph.nodes().map(|(node, _)| node).collect();
-    let mut index_of: HashMap<NodeId, usize> = HashMap::with_capacity(n);
-    for (i, &node) in node_list.iter().enumerate() {
-        index_of.insert(node, i);
-    }
-    let mut adjacency: Vec<Vec<(usize, f64)>> = vec![Vec::new(); n];
-    for (src, tgt, &w) in graph.edges() {
-        let s = index_of[&src];
-        let t = index_of[&tgt];
-        let weight: f64 = w.into();
-        adjacency[s].push((t, weight));
-        adjacency[t].push((s, weight));
+    for (m, group) in members.iter().enumerate() {
+        split_module(&level, group, exits[m], max_iter, &mut rng, &mut paths);
     }
+    renumber_paths(&mut paths);
+    let codelength = tree_codelength(&level, &paths);
+    Ok(InfomapHierarchy {
+        paths,
+        codelength,
+        one_level_codelength: entropy,
+    })
+}
 
-    let mut modules: Vec<usize> = (0..n).collect();
-    let mut rng = create_rng(seed);
-    let mut iter = 0;
+/// Two-level map equation codelength, in bits, of the partition `modules` of `graph`.
+///
+/// `modules` holds one label per node in internal node order. This evaluates a partition
+/// from any source, for example one produced by the reference Infomap tool, under the same
+/// flow model as [`infomap_two_level`].
+///
+/// # Errors
+/// Returns `InvalidGraph` for an empty graph and `InvalidArgument` if `modules` does not
+/// have one entry per node, `teleportation` is not in `(0, 1)`, or an edge weight is
+/// negative or not finite.
+pub fn map_equation<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    modules: &[usize],
+    teleportation: f64,
+) -> Result<f64>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let (_, level) = flow_network(graph, teleportation)?;
+    if modules.len() != level.len() {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Infomap: expected {} module labels, got {}",
+            level.len(),
+            modules.len()
+        )));
+    }
+    let mut labels = modules.to_vec();
+    let (labels, k) = renumber(&mut labels);
+    Ok(ModuleTerms::new(&level, &labels, k, 0.0).codelength() + node_entropy(&level))
+}
 
-    loop {
-        let mut changed = false;
-        let mut nodes: Vec<usize> = (0..n).collect();
-        nodes.shuffle(&mut rng);
-        for &i in &nodes {
-            let mut flow: HashMap<usize, f64> = HashMap::new();
-            let mut total_flow = 0.0;
-            for &(nbr, weight) in &adjacency[i] {
-                let module = modules[nbr];
-                *flow.entry(module).or_insert(0.0) += weight;
-                total_flow += weight;
-            }
-            if total_flow > 0.0 {
-                for val in flow.values_mut() {
-                    *val /= total_flow;
-                }
-            }
-            if let Some((&best_module, _)) = flow
-                .iter()
-                .max_by(|a, b| a.1.partial_cmp(b.1).unwrap_or(std::cmp::Ordering::Equal))
-            {
-                if best_module != modules[i] {
-                    modules[i] = best_module;
-                    changed = true;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    /// `groups` groups of `cliques` cliques of `size` nodes. Cliques in a group form a ring
+    /// with bridges of weight `inner`, and groups form a ring with bridges of weight `outer`.
+    fn nested_cliques(
+        groups: usize,
+        cliques: usize,
+        size: usize,
+        inner: f64,
+        outer: f64,
+    ) -> Graph<(), f64> {
+        let mut g = Graph::new();
+        let nodes: Vec<_> = (0..groups * cliques * size)
+            .map(|_| g.add_node(()))
+            .collect();
+        let at = |grp: usize, c: usize, i: usize| nodes[(grp * cliques + c) * size + i];
+        for grp in 0..groups {
+            for c in 0..cliques {
+                for i in 0..size {
+                    for j in i + 1..size {
+                        g.add_edge(at(grp, c, i), at(grp, c, j), 1.0);
+                    }
                 }
+                g.add_edge(at(grp, c, 0), at(grp, (c + 1) % cliques, 1), inner);
             }
+            g.add_edge(at(grp, 0, 2), at((grp + 1) % groups, 0, 3), outer);
         }
-        iter += 1;
-        if !changed || iter >= max_iter {
-            break;
+        g
+    }
+
+    #[test]
+    fn test_map_equation_matches_closed_form() {
+        // Two triangles joined by one edge: 7 edges, so each arc carries 1/14 of the flow.
+        let mut g: Graph<(), f64> = Graph::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let p = [2.0, 2.0, 3.0, 3.0, 2.0, 2.0].map(|d: f64| d / 14.0);
+        let one_level = -p.iter().map(|&x| plogp(x)).sum::<f64>();
+        let q = 1.0 / 14.0;
+        let two_level = plogp(2.0 * q)
+            - 2.0 * plogp(q)
+            - 2.0 * plogp(q)
+            - p.iter().map(|&x| plogp(x)).sum::<f64>()
+            + 2.0 * plogp(q + 0.5);
+
+        let split = map_equation(&g, &[0, 0, 0, 1, 1, 1], 0.15).unwrap();
+        assert!((split - two_level).abs() < 1e-12);
+        let whole = map_equation(&g, &[0; 6], 0.15).unwrap();
+        assert!((whole - one_level).abs() < 1e-12);
+
+        let result = infomap_two_level(&g, 100, 3, 0.15, Some(9)).unwrap();
+        assert_eq!(result.modules, vec![0, 0, 0, 1, 1, 1]);
+        assert!((result.codelength - two_level).abs() < 1e-12);
+        assert!((result.one_level_codelength - one_level).abs() < 1e-12);
+        assert!(map_equation(&g, &[0; 5], 0.15).is_err());
+    }
+
+    #[test]
+    fn test_directed_flow_uses_teleportation() {
+        // Two directed 3-cycles joined one way: flow drains into the second cycle.
+        let mut g: Digraph<(), f64> = Digraph::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let result = infomap_two_level(&g, 100, 3, 0.15, Some(2)).unwrap();
+        assert_eq!(result.modules, vec![0, 0, 0, 1, 1, 1]);
+        let (_, level) = flow_network(&g, 0.15).unwrap();
+        assert!((level.flow.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert!(level.flow[3] > level.flow[0]);
+        assert!(infomap_two_level(&g, 100, 1, 1.0, None).is_err());
+        assert!(infomap_two_level(&g, 100, 0, 0.15, None).is_err());
+    }
+
+    #[test]
+    fn test_hierarchical_finds_nested_modules() {
+        let g = nested_cliques(3, 4, 5, 0.5, 0.05);
+        let two_level = infomap_two_level(&g, 100, 3, 0.15, Some(4)).unwrap();
+        let tree = infomap_hierarchical(&g, 100, 3, 0.15, Some(4)).unwrap();
+        assert_eq!(tree.depth(), 2);
+        assert!(tree.codelength < two_level.codelength);
+
+        // Three groups on top, each holding its four cliques.
+        let top = tree.modules_at(0);
+        let bottom = tree.modules_at(1);
+        assert_eq!(top.iter().max(), Some(&2));
+        assert_eq!(bottom.iter().max(), Some(&11));
+        for (u, path) in tree.paths.iter().enumerate() {
+            assert_eq!(top[u], u / 20);
+            assert_eq!(bottom[u], u / 5);
+            assert_eq!(path.len(), 2);
         }
+        // The reported codelength is the hierarchical map equation of the returned tree.
+        let (_, level) = flow_network(&g, 0.15).unwrap();
+        let recomputed = tree_codelength(&level, &tree.paths);
+        assert!((recomputed - tree.codelength).abs() < 1e-9);
+        let flat: Vec<Vec<usize>> = two_level.modules.iter().map(|&m| vec![m]).collect();
+        assert!((tree_codelength(&level, &flat) - two_level.codelength).abs() < 1e-9);
     }
-    Ok(modules)
 }
@@ -1,9 +1,11 @@
 //! Label propagation algorithms.
 //!
-//! This module provides label propagation for community detection.
+//! This module provides label propagation for community detection: the classic unweighted
+//! variant, a weighted variant where each neighbor votes with its edge weight, and a seeded
+//! (semi-supervised) variant that spreads a partial ground truth to the rest of the graph.
 
 use crate::core::error::{GraphinaError, Result};
-use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
 use rand::prelude::*;
 use rand::{SeedableRng, rngs::StdRng};
 use std::collections::HashMap as StdHashMap;
@@ -21,6 +23,8 @@ fn create_rng(seed: Option<u64>) -> StdRng {
 /// Each node is initially assigned its own community. In randomized order,
 /// each node updates its label to the most frequent label among its neighbors.
 /// The process stops when no changes occur or when `max_iter` iterations are reached.
+/// On a directed graph edges are followed in both directions, so in- and out-neighbors vote
+/// alike.
 ///
 /// **Time Complexity:** O(max_iter * (n + m))
 ///
@@ -96,6 +100,248 @@ where
     Ok(labels)
 }
 
+/// Per-node `(neighbor index, weight)` lists, indexed like the accompanying node list.
+type WeightedAdjacency = Vec<Vec<(usize, f64)>>;
+
+/// Weighted neighbor lists over contiguous indices, treating every edge as undirected.
+/// Self-loops are skipped so that a node never votes for its own label.
+fn weighted_adjacency<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    algo: &str,
+) -> Result<(Vec<NodeId>, WeightedAdjacency)>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let node_list: Vec<NodeId> = graph.node_ids().collect();
+    let node_to_idx: StdHashMap<NodeId, usize> =
+        node_list.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let mut adjacency = vec![Vec::new(); node_list.len()];
+    for (src, tgt, &w) in graph.edges() {
+        let w: f64 = w.into();
+        if !w.is_finite() || w < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "{}: edge weights must be finite and non-negative, got {}",
+                algo, w
+            )));
+        }
+        let (si, ti) = (node_to_idx[&src], node_to_idx[&tgt]);
+        if si != ti {
+            adjacency[si].push((ti, w));
+            adjacency[ti].push((si, w));
+        }
+    }
+    Ok((node_list, adjacency))
+}
+
+/// Runs asynchronous weighted propagation in place. Nodes with `fixed[i]` never change, and
+/// unlabeled nodes (`None`) only vote once a neighbor has a label. Ties are broken uniformly
+/// at random among the tied labels, keeping the current label when it is one of them.
+fn propagate(
+    adjacency: &[Vec<(usize, f64)>],
+    labels: &mut [Option<usize>],
+    fixed: &[bool],
+    max_iter: usize,
+    rng: &mut StdRng,
+) {
+    let mut order: Vec<usize> = (0..labels.len()).filter(|&i| !fixed[i]).collect();
+    for _ in 0..max_iter {
+        let mut changed = false;
+        order.shuffle(rng);
+        for &i in &order {
+            let mut votes: StdHashMap<usize, f64> = StdHashMap::new();
+            for &(nbr, w) in &adjacency[i] {
+                if let Some(label) = labels[nbr] {
+                    *votes.entry(label).or_insert(0.0) += w;
+                }
+            }
+            let best = votes.values().copied().fold(0.0, f64::max);
+            if best <= 0.0 {
+                continue;
+            }
+            let mut tied: Vec<usize> = votes
+                .into_iter()
+                .filter(|&(_, v)| v == best)
+                .map(|(label, _)| label)
+                .collect();
+            if labels[i].is_some_and(|current| tied.contains(&current)) {
+                continue;
+            }
+            tied.sort_unstable();
+            if let Some(&choice) = tied.choose(rng) {
+                labels[i] = Some(choice);
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+}
+
+/// Label propagation where each neighbor votes with the weight of the connecting edge.
+///
+/// Every node starts in its own community and repeatedly adopts the label with the largest
+/// total edge weight among its neighbors, so a heavy edge outweighs several light ones. Edges
+/// are treated as undirected, self-loops are ignored, and ties are broken at random.
+///
+/// **Time Complexity:** O(max_iter * (n + m))
+///
+/// # Returns
+/// A map from every node to its community label. Labels are numbered from zero in order of
+/// first appearance in node order.
+///
+/// # Errors
+/// Returns `InvalidGraph` for an empty graph or `max_iter == 0`, and `InvalidArgument` if an
+/// edge weight is negative, NaN, or infinite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::label_propagation::weighted_label_propagation;
+/// use graphina::core::types::Graph;
+///
+/// // Two triangles joined by a light edge.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+///     g.add_edge(n[u], n[v], 5.0);
+/// }
+/// g.add_edge(n[2], n[3], 0.1);
+///
+/// let labels = weighted_label_propagation(&g, 100, Some(7)).unwrap();
+/// assert_eq!(labels[&n[0]], labels[&n[2]]);
+/// assert_eq!(labels[&n[3]], labels[&n[5]]);
+/// assert_ne!(labels[&n[0]], labels[&n[5]]);
+/// ```
+pub fn weighted_label_propagation<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    max_iter: usize,
+    seed: Option<u64>,
+) -> Result<NodeMap<usize>>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    if graph.node_count() == 0 {
+        return Err(GraphinaError::invalid_graph(
+            "WeightedLabelPropagation: empty graph",
+        ));
+    }
+    if max_iter == 0 {
+        return Err(GraphinaError::invalid_graph(
+            "WeightedLabelPropagation: max_iter=0",
+        ));
+    }
+    let (node_list, adjacency) = weighted_adjacency(graph, "WeightedLabelPropagation")?;
+    let mut labels: Vec<Option<usize>> = (0..node_list.len()).map(Some).collect();
+    let fixed = vec![false; node_list.len()];
+    propagate(
+        &adjacency,
+        &mut labels,
+        &fixed,
+        max_iter,
+        &mut create_rng(seed),
+    );
+
+    let mut renumber: StdHashMap<usize, usize> = StdHashMap::new();
+    let mut result = NodeMap::default();
+    for (node, label) in node_list.into_iter().zip(labels) {
+        if let Some(label) = label {
+            let next = renumber.len();
+            result.insert(node, *renumber.entry(label).or_insert(next));
+        }
+    }
+    Ok(result)
+}
+
+/// Semi-supervised label propagation from a set of pre-labeled seed nodes.
+///
+/// The nodes in `seeds` keep their given labels throughout. Every other node starts without
+/// a label and repeatedly adopts the label with the largest total edge weight among its
+/// labeled neighbors, so the known labels spread outward until nothing changes or `max_iter`
+/// rounds have run. Edges are treated as undirected, self-loops are ignored, and ties are
+/// broken at random.
+///
+/// **Time Complexity:** O(max_iter * (n + m))
+///
+/// # Returns
+/// A map from each labeled node to its label, using the labels of `seeds`. Nodes that no seed
+/// can reach, such as those in a component without seeds, are left out.
+///
+/// # Errors
+/// Returns `InvalidGraph` for an empty graph or `max_iter == 0`, `InvalidArgument` if `seeds`
+/// is empty or an edge weight is negative, NaN, or infinite, and `NodeNotFound` if a seed is
+/// not in the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::label_propagation::seeded_label_propagation;
+/// use graphina::core::types::{Graph, NodeMap};
+///
+/// // A path 0 - 1 - 2 - 3 - 4 with known labels at both ends.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// for i in 0..4 {
+///     g.add_edge(n[i], n[i + 1], if i == 2 { 0.5 } else { 1.0 });
+/// }
+/// let seeds: NodeMap<usize> = [(n[0], 10), (n[4], 20)].into_iter().collect();
+///
+/// let labels = seeded_label_propagation(&g, &seeds, 100, Some(1)).unwrap();
+/// assert_eq!(labels[&n[1]], 10);
+/// assert_eq!(labels[&n[3]], 20);
+/// // Node 2 is pulled harder by its heavier edge towards node 1.
+/// assert_eq!(labels[&n[2]], 10);
+/// ```
+pub fn seeded_label_propagation<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    seeds: &NodeMap<usize>,
+    max_iter: usize,
+    seed: Option<u64>,
+) -> Result<NodeMap<usize>>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    if graph.node_count() == 0 {
+        return Err(GraphinaError::invalid_graph(
+            "SeededLabelPropagation: empty graph",
+        ));
+    }
+    if max_iter == 0 {
+        return Err(GraphinaError::invalid_graph(
+            "SeededLabelPropagation: max_iter=0",
+        ));
+    }
+    if seeds.is_empty() {
+        return Err(GraphinaError::invalid_argument(
+            "SeededLabelPropagation: at least one seed node is required",
+        ));
+    }
+    if let Some(missing) = seeds.keys().find(|&&v| !graph.contains_node(v)) {
+        return Err(GraphinaError::node_not_found(format!(
+            "SeededLabelPropagation: seed node {} is not in the graph",
+            missing.index()
+        )));
+    }
+    let (node_list, adjacency) = weighted_adjacency(graph, "SeededLabelPropagation")?;
+    let mut labels: Vec<Option<usize>> = node_list.iter().map(|v| seeds.get(v).copied()).collect();
+    let fixed: Vec<bool> = labels.iter().map(Option::is_some).collect();
+    propagate(
+        &adjacency,
+        &mut labels,
+        &fixed,
+        max_iter,
+        &mut create_rng(seed),
+    );
+    Ok(node_list
+        .into_iter()
+        .zip(labels)
+        .filter_map(|(node, label)| label.map(|l| (node, l)))
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -124,4 +370,55 @@ mod tests {
         assert!(!communities.is_empty());
         assert!(communities.len() <= 10);
     }
+
+    #[test]
+    fn test_weighted_votes_override_counts() {
+        use super::weighted_label_propagation;
+        use crate::core::types::Graph;
+
+        // Node 0 has two light neighbors in one group and one heavy neighbor in another.
+        let mut g: Graph<(), f64> = Graph::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        g.add_edge(n[1], n[2], 1.0);
+        g.add_edge(n[3], n[4], 10.0);
+        g.add_edge(n[4], n[5], 10.0);
+        g.add_edge(n[5], n[3], 10.0);
+        g.add_edge(n[0], n[1], 0.2);
+        g.add_edge(n[0], n[2], 0.2);
+        g.add_edge(n[0], n[3], 5.0);
+        for seed in 0..5 {
+            let labels = weighted_label_propagation(&g, 100, Some(seed)).unwrap();
+            assert_eq!(labels[&n[0]], labels[&n[3]]);
+            assert_eq!(labels.len(), 6);
+        }
+
+        g.add_edge(n[1], n[2], -1.0);
+        assert!(weighted_label_propagation(&g, 100, None).is_err());
+    }
+
+    #[test]
+    fn test_seeded_labels_stay_fixed_and_unreachable_nodes_are_omitted() {
+        use super::seeded_label_propagation;
+        use crate::core::types::{Digraph, NodeMap};
+
+        let mut g: Digraph<(), f64> = Digraph::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        // Directed edges still carry labels both ways.
+        g.add_edge(n[1], n[0], 1.0);
+        g.add_edge(n[1], n[2], 3.0);
+        let seeds: NodeMap<usize> = [(n[0], 1), (n[2], 2), (n[3], 3)].into_iter().collect();
+
+        let labels = seeded_label_propagation(&g, &seeds, 50, Some(3)).unwrap();
+        assert_eq!(labels[&n[0]], 1);
+        assert_eq!(labels[&n[2]], 2);
+        assert_eq!(labels[&n[3]], 3);
+        assert_eq!(labels[&n[1]], 2);
+        assert!(!labels.contains_key(&n[4]));
+
+        assert!(seeded_label_propagation(&g, &NodeMap::default(), 50, None).is_err());
+        let removed = g.add_node(());
+        g.remove_node(removed);
+        let bad: NodeMap<usize> = [(removed, 0)].into_iter().collect();
+        assert!(seeded_label_propagation(&g, &bad, 50, None).is_err());
+    }
 }
@@ -1,9 +1,25 @@
+pub mod attributed;
+pub mod community_graph;
 pub mod connected_components;
 pub mod girvan_newman;
 pub mod infomap;
 pub mod label_propagation;
 pub mod louvain;
 pub mod node_maps;
+pub mod partition;
+pub mod quality;
+pub mod sbm;
 pub mod spectral;
 
+pub use connected_components::{
+    connected_components, strongly_connected_components, weakly_connected_components,
+};
+pub use girvan_newman::girvan_newman;
+pub use infomap::infomap;
+pub use label_propagation::label_propagation;
+pub use louvain::louvain;
 pub use node_maps::{infomap_map, label_propagation_map};
+pub use partition::Partition;
+pub use quality::modularity;
+pub use sbm::estimate_block_matrix;
+pub use spectral::spectral_clustering;
@@ -0,0 +1,393 @@
+//! A first-class partition of nodes into communities.
+//!
+//! Detection algorithms return either a list of communities (`Vec<Vec<NodeId>>`) or a label
+//! per node in node order (`Vec<usize>`). [`Partition`] accepts both, answers which community
+//! a node is in without a scan, and compares itself with another partition of the same nodes,
+//! for example a detected partition with a planted one.
+
+use std::collections::BTreeMap;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use rustc_hash::FxHashMap;
+
+/// A partition of a set of nodes into disjoint, non-empty communities.
+///
+/// Communities are numbered from 0 in the order they are stored; empty communities are
+/// dropped on construction, so every index from 0 to `len() - 1` names one.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::Partition;
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+///
+/// // Labels in node order, as label propagation returns them.
+/// let detected = Partition::from_labels(&g, &[7, 7, 2, 2]).unwrap();
+/// assert_eq!(detected.communities(), &[vec![n[2], n[3]], vec![n[0], n[1]]]);
+/// assert_eq!(detected.community_of(n[1]), Some(1));
+///
+/// let truth = Partition::from_communities(vec![vec![n[0], n[1]], vec![n[2], n[3]]]).unwrap();
+/// assert_eq!(detected.adjusted_rand_index(&truth).unwrap(), 1.0);
+/// assert_eq!(detected.variation_of_information(&truth).unwrap(), 0.0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Partition {
+    communities: Vec<Vec<NodeId>>,
+    community_of: NodeMap<usize>,
+}
+
+impl Partition {
+    /// Builds a partition from a list of communities, keeping their order and dropping empty
+    /// ones.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if a node appears more than once.
+    pub fn from_communities(communities: Vec<Vec<NodeId>>) -> Result<Self> {
+        let communities: Vec<Vec<NodeId>> =
+            communities.into_iter().filter(|c| !c.is_empty()).collect();
+        let mut community_of = NodeMap::default();
+        for (c, members) in communities.iter().enumerate() {
+            for &node in members {
+                if community_of.insert(node, c).is_some() {
+                    return Err(GraphinaError::invalid_argument(format!(
+                        "Partition: node {} appears more than once",
+                        node.index()
+                    )));
+                }
+            }
+        }
+        Ok(Partition {
+            communities,
+            community_of,
+        })
+    }
+
+    /// Builds a partition from one label per node of `graph`, in node order.
+    ///
+    /// Labels need not be contiguous. Communities come in ascending label order, with members
+    /// in node order.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if there is not exactly one label per node.
+    pub fn from_labels<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, labels: &[usize]) -> Result<Self>
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        if labels.len() != graph.node_count() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Partition: got {} labels for {} nodes",
+                labels.len(),
+                graph.node_count()
+            )));
+        }
+        Ok(Self::group(graph.node_ids().zip(labels.iter().copied())))
+    }
+
+    /// Builds a partition from a map of nodes to labels, with communities in ascending label
+    /// order and members sorted by ID.
+    pub fn from_node_map(labels: &NodeMap<usize>) -> Self {
+        let mut pairs: Vec<(NodeId, usize)> = labels.iter().map(|(&n, &l)| (n, l)).collect();
+        pairs.sort_unstable();
+        Self::group(pairs.into_iter())
+    }
+
+    fn group(pairs: impl Iterator<Item = (NodeId, usize)>) -> Self {
+        let mut grouped: BTreeMap<usize, Vec<NodeId>> = BTreeMap::new();
+        for (node, label) in pairs {
+            grouped.entry(label).or_default().push(node);
+        }
+        let communities: Vec<Vec<NodeId>> = grouped.into_values().collect();
+        let mut community_of = NodeMap::default();
+        for (c, members) in communities.iter().enumerate() {
+            for &node in members {
+                community_of.insert(node, c);
+            }
+        }
+        Partition {
+            communities,
+            community_of,
+        }
+    }
+
+    /// Returns the communities, in order.
+    pub fn communities(&self) -> &[Vec<NodeId>] {
+        &self.communities
+    }
+
+    /// Returns the communities, consuming the partition.
+    pub fn into_communities(self) -> Vec<Vec<NodeId>> {
+        self.communities
+    }
+
+    /// Returns the members of community `community`, or `None` if there is no such community.
+    pub fn members(&self, community: usize) -> Option<&[NodeId]> {
+        self.communities.get(community).map(Vec::as_slice)
+    }
+
+    /// Returns the community of `node`, or `None` if the partition does not contain it.
+    pub fn community_of(&self, node: NodeId) -> Option<usize> {
+        self.community_of.get(&node).copied()
+    }
+
+    /// Iterates over every node with its community, community by community.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, usize)> + '_ {
+        self.communities
+            .iter()
+            .enumerate()
+            .flat_map(|(c, members)| members.iter().map(move |&node| (node, c)))
+    }
+
+    /// Returns the number of communities.
+    pub fn len(&self) -> usize {
+        self.communities.len()
+    }
+
+    /// Returns `true` if the partition has no communities.
+    pub fn is_empty(&self) -> bool {
+        self.communities.is_empty()
+    }
+
+    /// Returns the number of nodes across all communities.
+    pub fn node_count(&self) -> usize {
+        self.community_of.len()
+    }
+
+    /// Returns the community of every node as a map.
+    pub fn to_node_map(&self) -> NodeMap<usize> {
+        self.community_of.clone()
+    }
+
+    /// Returns the community of every node of `graph`, in node order, the label layout of
+    /// `label_propagation` and `infomap`.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if a node of `graph` is not in the partition.
+    pub fn labels<A, W, Ty>(&self, graph: &BaseGraph<A, W, Ty>) -> Result<Vec<usize>>
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        graph
+            .node_ids()
+            .map(|node| {
+                self.community_of(node).ok_or_else(|| {
+                    GraphinaError::invalid_argument(format!(
+                        "Partition: node {} is not in any community",
+                        node.index()
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    /// Moves every member of community `other` into community `into`.
+    ///
+    /// `other` is removed, so the communities after it move down by one index.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if either index is out of range or they are equal.
+    pub fn merge(&mut self, into: usize, other: usize) -> Result<()> {
+        if into == other || into >= self.len() || other >= self.len() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Partition: cannot merge community {} into {} of {}",
+                other,
+                into,
+                self.len()
+            )));
+        }
+        let moved = self.communities.remove(other);
+        let into = if into > other { into - 1 } else { into };
+        self.communities[into].extend(moved);
+        for (c, members) in self.communities.iter().enumerate().skip(into.min(other)) {
+            for &node in members {
+                self.community_of.insert(node, c);
+            }
+        }
+        Ok(())
+    }
+
+    /// Normalized mutual information between this partition and `other`, in `[0, 1]`.
+    ///
+    /// The mutual information is divided by the arithmetic mean of the two entropies, as in
+    /// scikit-learn's default. Two partitions that each put every node in one community agree
+    /// perfectly and score `1.0`.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if the partitions do not cover the same, non-empty set of
+    /// nodes.
+    pub fn normalized_mutual_information(&self, other: &Partition) -> Result<f64> {
+        let table = self.contingency(other)?;
+        let (h_self, h_other) = (table.entropy(&table.rows), table.entropy(&table.columns));
+        if h_self + h_other == 0.0 {
+            return Ok(1.0);
+        }
+        Ok((2.0 * table.mutual_information() / (h_self + h_other)).clamp(0.0, 1.0))
+    }
+
+    /// Adjusted Rand index between this partition and `other`: `1.0` for identical
+    /// partitions, about `0.0` for unrelated ones, and negative for less agreement than chance.
+    ///
+    /// When the partitions leave no room for chance, as with fewer than two nodes or both
+    /// putting all nodes together, the index is `1.0`.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if the partitions do not cover the same, non-empty set of
+    /// nodes.
+    pub fn adjusted_rand_index(&self, other: &Partition) -> Result<f64> {
+        let table = self.contingency(other)?;
+        let pairs = |count: usize| (count * count.saturating_sub(1) / 2) as f64;
+        let index: f64 = table.cells.values().map(|&c| pairs(c)).sum();
+        let rows: f64 = table.rows.iter().map(|&c| pairs(c)).sum();
+        let columns: f64 = table.columns.iter().map(|&c| pairs(c)).sum();
+        let total = pairs(table.total);
+        let expected = if total == 0.0 {
+            0.0
+        } else {
+            rows * columns / total
+        };
+        let maximum = (rows + columns) / 2.0;
+        if maximum == expected {
+            return Ok(1.0);
+        }
+        Ok((index - expected) / (maximum - expected))
+    }
+
+    /// Variation of information between this partition and `other`, in nats: `0.0` for
+    /// identical partitions, growing as they disagree, and at most `ln(n)` for `n` nodes.
+    ///
+    /// Unlike the other two measures it is a metric, so it obeys the triangle inequality.
+    ///
+    /// # Errors
+    /// Returns `InvalidArgument` if the partitions do not cover the same, non-empty set of
+    /// nodes.
+    pub fn variation_of_information(&self, other: &Partition) -> Result<f64> {
+        let table = self.contingency(other)?;
+        let vi = table.entropy(&table.rows) + table.entropy(&table.columns)
+            - 2.0 * table.mutual_information();
+        Ok(vi.max(0.0))
+    }
+
+    fn contingency(&self, other: &Partition) -> Result<Contingency> {
+        if self.node_count() == 0 || self.node_count() != other.node_count() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Partition: cannot compare partitions of {} and {} nodes",
+                self.node_count(),
+                other.node_count()
+            )));
+        }
+        let mut cells: FxHashMap<(usize, usize), usize> = FxHashMap::default();
+        let mut columns = vec![0; other.len()];
+        for (node, c) in self.iter() {
+            let Some(d) = other.community_of(node) else {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "Partition: node {} is in only one of the partitions",
+                    node.index()
+                )));
+            };
+            *cells.entry((c, d)).or_insert(0) += 1;
+            columns[d] += 1;
+        }
+        Ok(Contingency {
+            cells,
+            rows: self.communities.iter().map(Vec::len).collect(),
+            columns,
+            total: self.node_count(),
+        })
+    }
+}
+
+impl From<Partition> for Vec<Vec<NodeId>> {
+    fn from(partition: Partition) -> Self {
+        partition.communities
+    }
+}
+
+/// Overlap counts between the communities of two partitions of the same nodes.
+struct Contingency {
+    cells: FxHashMap<(usize, usize), usize>,
+    rows: Vec<usize>,
+    columns: Vec<usize>,
+    total: usize,
+}
+
+impl Contingency {
+    fn entropy(&self, sizes: &[usize]) -> f64 {
+        let n = self.total as f64;
+        sizes
+            .iter()
+            .filter(|&&s| s > 0)
+            .map(|&s| {
+                let p = s as f64 / n;
+                -p * p.ln()
+            })
+            .sum()
+    }
+
+    fn mutual_information(&self) -> f64 {
+        let n = self.total as f64;
+        self.cells
+            .iter()
+            .map(|(&(c, d), &count)| {
+                let count = count as f64;
+                count / n * (n * count / (self.rows[c] as f64 * self.columns[d] as f64)).ln()
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+
+    #[test]
+    fn test_partition_conversions_merge_and_comparison() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        let a = Partition::from_labels(&g, &[0, 0, 0, 1, 1, 1]).unwrap();
+        let b = Partition::from_communities(vec![
+            vec![n[0], n[1]],
+            vec![],
+            vec![n[2], n[3]],
+            vec![n[4], n[5]],
+        ])
+        .unwrap();
+        assert_eq!(b.len(), 3);
+        assert_eq!(b.community_of(n[3]), Some(1));
+        assert_eq!(
+            Partition::from_node_map(&b.to_node_map()).communities(),
+            b.communities()
+        );
+        assert_eq!(b.labels(&g).unwrap(), vec![0, 0, 1, 1, 2, 2]);
+        assert_eq!(b.iter().count(), 6);
+
+        // b refines a: I = 2/3 ln 2, H(a) = ln 2, and H(b) = ln 3.
+        let (ln2, ln3) = (2f64.ln(), 3f64.ln());
+        assert!((a.adjusted_rand_index(&b).unwrap() - 0.8 / 3.3).abs() < 1e-12);
+        let nmi = a.normalized_mutual_information(&b).unwrap();
+        assert!((nmi - 4.0 / 3.0 * ln2 / (ln2 + ln3)).abs() < 1e-12);
+        let vi = a.variation_of_information(&b).unwrap();
+        assert!((vi - (ln3 - ln2 / 3.0)).abs() < 1e-12);
+        assert_eq!(a.variation_of_information(&a).unwrap(), 0.0);
+        assert_eq!(a.normalized_mutual_information(&a).unwrap(), 1.0);
+
+        let mut merged = b.clone();
+        merged.merge(2, 0).unwrap();
+        assert_eq!(
+            merged.communities(),
+            &[vec![n[2], n[3]], vec![n[4], n[5], n[0], n[1]]]
+        );
+        assert_eq!(merged.community_of(n[0]), Some(1));
+        assert_eq!(merged.community_of(n[2]), Some(0));
+        assert!(merged.merge(0, 0).is_err());
+
+        assert!(Partition::from_communities(vec![vec![n[0]], vec![n[0]]]).is_err());
+        assert!(Partition::from_labels(&g, &[0, 1]).is_err());
+        let partial = Partition::from_communities(vec![n[..5].to_vec()]).unwrap();
+        assert!(a.adjusted_rand_index(&partial).is_err());
+        assert!(partial.labels(&g).is_err());
+    }
+}
@@ -0,0 +1,365 @@
+//! Partition quality measures.
+//!
+//! This module scores a partition of a graph into communities. [`modularity`] and
+//! [`conductance`] use edge weights; [`coverage`] and [`performance`] count edges. All of
+//! them take the partition as a list of communities, the shape every detection algorithm in
+//! [`community`](super) returns, and check that it covers each node exactly once.
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+
+/// Maps every node to the position of its community in `communities`, checking that the
+/// communities cover the nodes of `graph` exactly once. `algo` prefixes error messages.
+pub(crate) fn community_index<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    communities: &[Vec<NodeId>],
+    algo: &str,
+) -> Result<NodeMap<usize>>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut community_of: NodeMap<usize> = NodeMap::default();
+    for (c, members) in communities.iter().enumerate() {
+        for &node in members {
+            if !graph.contains_node(node) {
+                return Err(GraphinaError::node_not_found(format!(
+                    "{}: node {} is not in the graph",
+                    algo,
+                    node.index()
+                )));
+            }
+            if community_of.insert(node, c).is_some() {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "{}: node {} appears in more than one community",
+                    algo,
+                    node.index()
+                )));
+            }
+        }
+    }
+    if community_of.len() != graph.node_count() {
+        return Err(GraphinaError::invalid_argument(format!(
+            "{}: communities cover {} of {} nodes",
+            algo,
+            community_of.len(),
+            graph.node_count()
+        )));
+    }
+    Ok(community_of)
+}
+
+/// Modularity of a partition of `graph` into `communities`.
+///
+/// For an undirected graph this is Newman's modularity,
+/// `Q = 1/(2m) * sum_ij [A_ij - k_i k_j / (2m)] * [c_i == c_j]`, where `k` is the weighted
+/// degree and `m` the total edge weight. For a directed graph it is the directed modularity
+/// of Leicht and Newman (2008), `Q = 1/m * sum_ij [A_ij - k_i^out k_j^in / m] * [c_i == c_j]`,
+/// which is what [`louvain`](super::louvain::louvain) maximizes on directed graphs. The two
+/// agree when every arc of a directed graph has a reverse arc of the same weight.
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Errors
+/// Returns `NodeNotFound` if a community holds a node that is not in the graph,
+/// `InvalidArgument` if the communities do not cover every node exactly once or an edge
+/// weight is negative or not finite, and `InvalidGraph` if the total edge weight is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::quality::modularity;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[0], 1.0);
+/// g.add_edge(n[2], n[3], 1.0);
+///
+/// let q = modularity(&g, &[vec![n[0], n[1]], vec![n[2], n[3]]]).unwrap();
+/// assert!((q - 4.0 / 9.0).abs() < 1e-12);
+/// ```
+pub fn modularity<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, communities: &[Vec<NodeId>]) -> Result<f64>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let community_of = community_index(graph, communities, "Modularity")?;
+
+    let k = communities.len();
+    let mut internal = 0.0;
+    let mut total = 0.0;
+    let mut tot_out = vec![0.0; k];
+    let mut tot_in = vec![0.0; k];
+    for (u, v, &w) in graph.edges() {
+        let w: f64 = w.into();
+        if !w.is_finite() || w < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Modularity: edge weights must be finite and non-negative, got {}",
+                w
+            )));
+        }
+        let (cu, cv) = (community_of[&u], community_of[&v]);
+        total += w;
+        tot_out[cu] += w;
+        tot_in[cv] += w;
+        if cu == cv {
+            internal += w;
+        }
+    }
+    if total <= 0.0 {
+        return Err(GraphinaError::invalid_graph(
+            "Modularity: total edge weight is zero",
+        ));
+    }
+
+    let expected: f64 = if graph.is_directed() {
+        (0..k).map(|c| tot_out[c] * tot_in[c]).sum::<f64>() / (total * total)
+    } else {
+        // Each undirected edge adds its weight to the degree of both endpoints.
+        (0..k)
+            .map(|c| ((tot_out[c] + tot_in[c]) / (2.0 * total)).powi(2))
+            .sum()
+    };
+    Ok(internal / total - expected)
+}
+
+/// Conductance of each community in `communities`, in the same order.
+///
+/// The conductance of a community `S` is `cut(S) / min(vol(S), vol(V \ S))`, the weight of
+/// the edges leaving `S` over the smaller of the two sides' volumes, where the volume is the
+/// summed weighted degree. Lower is better: `0.0` means no edge leaves the community. A
+/// community gets `0.0` as well when either side has no edges at all, which makes the ratio
+/// undefined. Directed graphs are scored on their undirected view, counting arcs in both
+/// directions.
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Errors
+/// Fails like [`modularity`] on a partition that does not cover the nodes exactly once or an
+/// edge weight that is negative or not finite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::quality::conductance;
+/// use graphina::core::types::Graph;
+///
+/// // Two triangles joined by one edge.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)] {
+///     g.add_edge(n[u], n[v], 1.0);
+/// }
+///
+/// let phi = conductance(&g, &[n[..3].to_vec(), n[3..].to_vec()]).unwrap();
+/// assert_eq!(phi, vec![1.0 / 7.0, 1.0 / 7.0]);
+/// ```
+pub fn conductance<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    communities: &[Vec<NodeId>],
+) -> Result<Vec<f64>>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let community_of = community_index(graph, communities, "Conductance")?;
+    let mut volume = vec![0.0; communities.len()];
+    let mut cut = vec![0.0; communities.len()];
+    let mut total = 0.0;
+    for (u, v, &w) in graph.edges() {
+        let w: f64 = w.into();
+        if !w.is_finite() || w < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Conductance: edge weights must be finite and non-negative, got {}",
+                w
+            )));
+        }
+        let (cu, cv) = (community_of[&u], community_of[&v]);
+        volume[cu] += w;
+        volume[cv] += w;
+        total += 2.0 * w;
+        if cu != cv {
+            cut[cu] += w;
+            cut[cv] += w;
+        }
+    }
+    Ok((0..communities.len())
+        .map(|c| {
+            let smaller = volume[c].min(total - volume[c]);
+            if smaller > 0.0 { cut[c] / smaller } else { 0.0 }
+        })
+        .collect())
+}
+
+/// Coverage of a partition: the fraction of edges that fall inside a community.
+///
+/// Counts edges regardless of weight, self-loops included. `1.0` means no edge runs
+/// between communities, which the trivial one-community partition always reaches, so
+/// coverage is best read next to [`performance`].
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Errors
+/// Returns `NodeNotFound` or `InvalidArgument` like [`modularity`] for a malformed
+/// partition, and `InvalidGraph` if the graph has no edges.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::quality::coverage;
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<(), ()>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], ());
+/// g.add_edge(n[2], n[3], ());
+/// g.add_edge(n[1], n[2], ());
+///
+/// let c = coverage(&g, &[vec![n[0], n[1]], vec![n[2], n[3]]]).unwrap();
+/// assert!((c - 2.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn coverage<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, communities: &[Vec<NodeId>]) -> Result<f64>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let community_of = community_index(graph, communities, "Coverage")?;
+    if graph.edge_count() == 0 {
+        return Err(GraphinaError::invalid_graph("Coverage: graph has no edges"));
+    }
+    let internal = graph
+        .edges()
+        .filter(|(u, v, _)| community_of[u] == community_of[v])
+        .count();
+    Ok(internal as f64 / graph.edge_count() as f64)
+}
+
+/// Performance of a partition: the fraction of node pairs it gets right.
+///
+/// A pair is right when it is adjacent and in the same community, or not adjacent and in
+/// different communities. Pairs are unordered on undirected graphs and ordered on directed
+/// ones, and several edges between a pair count once. Self-loops are ignored.
+///
+/// **Time Complexity:** O(n + m)
+///
+/// # Errors
+/// Returns `NodeNotFound` or `InvalidArgument` like [`modularity`] for a malformed
+/// partition, and `InvalidGraph` if the graph has fewer than two nodes.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::community::quality::performance;
+/// use graphina::core::types::Graph;
+///
+/// // Of the six pairs, only the bridge (1, 2) is scored wrong.
+/// let mut g = Graph::<(), ()>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], ());
+/// g.add_edge(n[2], n[3], ());
+/// g.add_edge(n[1], n[2], ());
+///
+/// let p = performance(&g, &[vec![n[0], n[1]], vec![n[2], n[3]]]).unwrap();
+/// assert!((p - 5.0 / 6.0).abs() < 1e-12);
+/// ```
+pub fn performance<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    communities: &[Vec<NodeId>],
+) -> Result<f64>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let community_of = community_index(graph, communities, "Performance")?;
+    let n = graph.node_count();
+    if n < 2 {
+        return Err(GraphinaError::invalid_graph(
+            "Performance: graph needs at least two nodes",
+        ));
+    }
+    let directed = graph.is_directed();
+    let pairs_of = |size: usize| {
+        let ordered = size * size.saturating_sub(1);
+        if directed { ordered } else { ordered / 2 }
+    };
+    let mut adjacent: std::collections::HashSet<(NodeId, NodeId)> = Default::default();
+    for (u, v, _) in graph.edges().filter(|(u, v, _)| u != v) {
+        let pair = if directed || u < v { (u, v) } else { (v, u) };
+        adjacent.insert(pair);
+    }
+    let internal_edges = adjacent
+        .iter()
+        .filter(|(u, v)| community_of[u] == community_of[v])
+        .count();
+    let external_edges = adjacent.len() - internal_edges;
+    let total = pairs_of(n);
+    let internal_pairs: usize = communities.iter().map(|c| pairs_of(c.len())).sum();
+    let external_non_edges = total - internal_pairs - external_edges;
+    Ok((internal_edges + external_non_edges) as f64 / total as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_modularity_directed_and_undirected() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[2], n[3], 1.0);
+        let halves = [vec![n[0], n[1]], vec![n[2], n[3]]];
+        assert!((modularity(&g, &halves).unwrap() - 0.5).abs() < 1e-12);
+        assert!(modularity(&g, &[n.clone()]).unwrap().abs() < 1e-12);
+
+        // Symmetric arcs give the undirected value.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..4).map(|_| d.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (1, 0), (2, 3), (3, 2)] {
+            d.add_edge(m[u], m[v], 1.0);
+        }
+        let halves = [vec![m[0], m[1]], vec![m[2], m[3]]];
+        assert!((modularity(&d, &halves).unwrap() - 0.5).abs() < 1e-12);
+
+        assert!(modularity(&d, &[vec![m[0], m[1]]]).is_err());
+        assert!(modularity(&d, &[vec![m[0], m[1], m[2], m[3], m[0]]]).is_err());
+    }
+
+    #[test]
+    fn test_partition_quality_scores() {
+        // Two directed triangles joined by one arc, plus a self-loop.
+        let mut d = Digraph::<(), f64>::new();
+        let n: Vec<_> = (0..6).map(|_| d.add_node(())).collect();
+        for &(u, v) in &[
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (2, 3),
+            (0, 0),
+        ] {
+            d.add_edge(n[u], n[v], 1.0);
+        }
+        let halves = [n[..3].to_vec(), n[3..].to_vec()];
+        assert!((coverage(&d, &halves).unwrap() - 7.0 / 8.0).abs() < 1e-12);
+        // 30 ordered pairs: 6 internal arcs and 17 non-adjacent cross pairs are right, while
+        // the 6 missing reverse arcs inside the triangles and the bridge are wrong.
+        assert!((performance(&d, &halves).unwrap() - 23.0 / 30.0).abs() < 1e-12);
+        // The self-loop makes the left side the larger one, with volume 9 against 7.
+        let phi = conductance(&d, &halves).unwrap();
+        assert!((phi[0] - 1.0 / 7.0).abs() < 1e-12);
+        assert_eq!(conductance(&d, &[n.clone()]).unwrap(), vec![0.0]);
+
+        let singletons: Vec<Vec<NodeId>> = n.iter().map(|&v| vec![v]).collect();
+        assert!((coverage(&d, &singletons).unwrap() - 1.0 / 8.0).abs() < 1e-12);
+        // Singletons get every pair right except the 7 arcs between distinct nodes.
+        assert!((performance(&d, &singletons).unwrap() - 23.0 / 30.0).abs() < 1e-12);
+
+        assert!(coverage(&d, &[vec![n[0]]]).is_err());
+        let empty = Graph::<(), f64>::new();
+        assert!(performance(&empty, &[]).is_err());
+        assert!(coverage(&empty, &[]).is_err());
+    }
+}
@@ -25,6 +25,9 @@ fn create_rng(seed: Option<u64>) -> StdRng {
 /// of the nth eigenvector value for the `k`th eigenvector.
 /// If k > the number of nodes in the graph, this routine will panic.
 ///
+/// The Laplacian must be symmetric, so on a directed graph each arc contributes as an
+/// undirected edge and reciprocal arcs add up.
+///
 /// **Time Complexity:** Dominated by the eigen-decomposition (≈ O(n³) worst-case).
 ///
 /// # Parameters
@@ -90,6 +93,7 @@ where
 /// Constructs the unnormalized Laplacian from the weighted adjacency matrix,
 /// computes the smallest `k` eigenvectors via nalgebra’s symmetric eigen-decomposition,
 /// and clusters the rows of the eigenvector matrix using a k-means routine.
+/// Directed graphs are symmetrized as in [`spectral_embeddings`].
 ///
 /// **Time Complexity:** Dominated by the eigen-decomposition (≈ O(n³) worst-case).
 ///
@@ -0,0 +1,356 @@
+/*!
+# Degree Anonymization
+
+Prepares a graph for sharing when node identities must stay hidden. An attacker who knows how
+many contacts someone has can pick that person out of a published graph if no one else has the
+same degree. A graph is **k-degree anonymous** when every degree value is shared by at least
+`k` nodes, so degree alone narrows a target down to no fewer than `k` candidates.
+
+[`k_degree_anonymize`] follows Liu and Terzi (2008): it first finds the anonymous degree
+sequence closest to the current one, then adds edges to reach it, so no existing edge is lost.
+[`perturb_edges`] instead replaces a random share of the edges, which hides individual edges
+rather than degrees. For a shuffle that keeps every degree, see
+[`degree_preserving_rewire`](crate::core::generators::degree_preserving_rewire).
+
+All three modify the graph in place; export the result with any of the `core::io` writers.
+*/
+
+use std::collections::HashSet;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, Graph, GraphConstructor, NodeId};
+use crate::core::validation::has_self_loops;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+/// Returns the largest `k` for which `graph` is k-degree anonymous: the number of nodes
+/// sharing the rarest degree value, or 0 for an empty graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::anonymization::degree_anonymity;
+/// use graphina::core::types::Graph;
+///
+/// // In a path a - b - c, node b is the only one of degree 2.
+/// let mut g = Graph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// assert_eq!(degree_anonymity(&g), 1);
+/// ```
+pub fn degree_anonymity<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> usize
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut degrees: Vec<usize> = graph
+        .node_ids()
+        .map(|v| graph.degree(v).unwrap_or(0))
+        .collect();
+    degrees.sort_unstable();
+    degrees
+        .chunk_by(|a, b| a == b)
+        .map(<[usize]>::len)
+        .min()
+        .unwrap_or(0)
+}
+
+/// Returns the k-degree anonymous sequence closest to `degrees`, in the same order.
+///
+/// Degrees may only rise, and the result minimizes their total increase. Sorted from the
+/// largest, the degrees are split into consecutive groups of `k` to `2k - 1`, and every
+/// degree in a group is raised to the group's largest; a dynamic program picks the cheapest
+/// split in O(n log n + n·k) time.
+///
+/// # Errors
+/// Returns `InvalidArgument` if `k` is zero, or if `degrees` is non-empty but shorter than `k`.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::anonymization::k_degree_anonymous_sequence;
+///
+/// let anonymous = k_degree_anonymous_sequence(&[1, 5, 4, 1, 3, 4], 2).unwrap();
+/// assert_eq!(anonymous, vec![1, 5, 5, 1, 4, 4]);
+/// ```
+pub fn k_degree_anonymous_sequence(degrees: &[usize], k: usize) -> Result<Vec<usize>> {
+    let n = degrees.len();
+    if k == 0 || (n > 0 && n < k) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "k-degree anonymity needs 1 <= k <= {n}, got k = {k}"
+        )));
+    }
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| degrees[b].cmp(&degrees[a]).then(a.cmp(&b)));
+    let sorted: Vec<usize> = order.iter().map(|&i| degrees[i]).collect();
+    let mut prefix = vec![0; n + 1];
+    for (i, &d) in sorted.iter().enumerate() {
+        prefix[i + 1] = prefix[i] + d;
+    }
+    // Cost of raising sorted[start..end] to sorted[start].
+    let cost =
+        |start: usize, end: usize| sorted[start] * (end - start) - (prefix[end] - prefix[start]);
+
+    // best[end] is the cheapest split of sorted[..end]; start[end] where its last group begins.
+    let mut best = vec![usize::MAX; n + 1];
+    let mut start = vec![0; n + 1];
+    best[0] = 0;
+    for end in k..=n {
+        let lowest = end.saturating_sub(2 * k - 1);
+        for s in lowest..=end - k {
+            if best[s] == usize::MAX {
+                continue;
+            }
+            let total = best[s] + cost(s, end);
+            if total < best[end] {
+                best[end] = total;
+                start[end] = s;
+            }
+        }
+    }
+
+    let mut anonymous = vec![0; n];
+    let mut end = n;
+    while end > 0 {
+        let s = start[end];
+        for &i in &order[s..end] {
+            anonymous[i] = sorted[s];
+        }
+        end = s;
+    }
+    Ok(anonymous)
+}
+
+/// Adds edges to `graph` until it is k-degree anonymous, and returns the new edges.
+///
+/// Each round computes the target sequence with [`k_degree_anonymous_sequence`] and joins
+/// nodes that still lack degree, largest shortfall first, never adding a self-loop or a second
+/// edge between the same pair. Not every target sequence can be reached this way; when a node is
+/// left short with no partner, it is joined to the highest-degree node it is not adjacent to and
+/// the target is recomputed. On a simple graph every round adds an edge, so this finishes, since
+/// a complete graph is anonymous, and the result usually needs few edges beyond half the total
+/// shortfall, but it is not guaranteed to be the fewest possible. Every new edge gets a clone of
+/// `weight`, and existing edges are left untouched.
+///
+/// # Errors
+/// Returns `InvalidArgument` if `k` is zero or larger than the number of nodes of a non-empty
+/// graph, or if the graph has a self-loop or parallel edges, which count toward a degree but
+/// leave targets that simple edges cannot reach.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::anonymization::{degree_anonymity, k_degree_anonymize};
+/// use graphina::core::generators::barabasi_albert_graph;
+/// use graphina::core::types::Undirected;
+///
+/// let mut g = barabasi_albert_graph::<Undirected>(200, 2, 7).unwrap();
+/// let before = g.edge_count();
+/// let added = k_degree_anonymize(&mut g, 5, 1.0).unwrap();
+/// assert!(degree_anonymity(&g) >= 5);
+/// assert_eq!(g.edge_count(), before + added.len());
+/// ```
+pub fn k_degree_anonymize<A, W: Clone>(
+    graph: &mut Graph<A, W>,
+    k: usize,
+    weight: W,
+) -> Result<Vec<EdgeId>> {
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    if k == 0 || (!nodes.is_empty() && nodes.len() < k) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "k-degree anonymity needs 1 <= k <= {}, got k = {k}",
+            nodes.len()
+        )));
+    }
+    if has_self_loops(graph) || graph.has_parallel_edges() {
+        return Err(GraphinaError::invalid_argument(
+            "k-degree anonymity needs a simple graph, without self-loops or parallel edges",
+        ));
+    }
+    let mut added = Vec::new();
+    loop {
+        let before = added.len();
+        let degrees: Vec<usize> = nodes
+            .iter()
+            .map(|&v| graph.degree(v).unwrap_or(0))
+            .collect();
+        let target = k_degree_anonymous_sequence(&degrees, k)?;
+        let mut deficit: Vec<usize> = target.iter().zip(&degrees).map(|(t, d)| t - d).collect();
+        if deficit.iter().all(|&r| r == 0) {
+            return Ok(added);
+        }
+
+        let mut queue: Vec<usize> = (0..nodes.len()).filter(|&i| deficit[i] > 0).collect();
+        queue.sort_by(|&a, &b| deficit[b].cmp(&deficit[a]).then(a.cmp(&b)));
+        let mut stranded = None;
+        for &i in &queue {
+            while deficit[i] > 0 {
+                let partner = (0..nodes.len())
+                    .filter(|&j| {
+                        j != i && deficit[j] > 0 && !graph.contains_edge(nodes[i], nodes[j])
+                    })
+                    .max_by(|&a, &b| deficit[a].cmp(&deficit[b]).then(b.cmp(&a)));
+                let Some(j) = partner else {
+                    stranded.get_or_insert(i);
+                    break;
+                };
+                added.push(graph.add_edge(nodes[i], nodes[j], weight.clone()));
+                deficit[i] -= 1;
+                deficit[j] -= 1;
+            }
+        }
+
+        // A node short of its target has degree below n - 1, so it has a non-neighbor.
+        if let Some(i) = stranded.filter(|_| degree_anonymity(graph) < k) {
+            let partner = (0..nodes.len())
+                .filter(|&j| j != i && !graph.contains_edge(nodes[i], nodes[j]))
+                .max_by(|&a, &b| {
+                    let (da, db) = (graph.degree(nodes[a]), graph.degree(nodes[b]));
+                    da.cmp(&db).then(b.cmp(&a))
+                });
+            if let Some(j) = partner {
+                added.push(graph.add_edge(nodes[i], nodes[j], weight.clone()));
+            }
+        }
+        if added.len() == before {
+            return Err(GraphinaError::algorithm_error(
+                "k-degree anonymization stalled: no edge can move the graph toward its target",
+            ));
+        }
+    }
+}
+
+/// Replaces a random `fraction` of the edges of `graph` with random new ones, and returns how
+/// many were replaced.
+///
+/// `round(fraction · E)` edges are removed, then as many node pairs are joined, drawn uniformly
+/// from the pairs that are neither adjacent nor the same node, so the edge count is unchanged.
+/// The new edges take the removed edges' weights in random order. A removed pair may be drawn
+/// again, as in the random add/delete perturbation of Hay et al.
+///
+/// # Errors
+/// Returns `InvalidArgument` if `fraction` is not in `[0, 1]`.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::anonymization::perturb_edges;
+/// use graphina::core::generators::erdos_renyi_graph;
+/// use graphina::core::types::Undirected;
+///
+/// let mut g = erdos_renyi_graph::<Undirected>(100, 0.05, 3).unwrap();
+/// let edges = g.edge_count();
+/// let replaced = perturb_edges(&mut g, 0.1, 42).unwrap();
+/// assert_eq!(replaced, (edges as f64 * 0.1).round() as usize);
+/// assert_eq!(g.edge_count(), edges);
+/// ```
+pub fn perturb_edges<A, W>(graph: &mut Graph<A, W>, fraction: f64, seed: u64) -> Result<usize> {
+    if !(0.0..=1.0).contains(&fraction) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Edge perturbation needs a fraction in [0, 1], got {fraction}"
+        )));
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut edges: Vec<EdgeId> = graph.edge_ids().collect();
+    let count = (edges.len() as f64 * fraction).round() as usize;
+    let (removed, _) = edges.partial_shuffle(&mut rng, count);
+    let mut weights: Vec<W> = removed
+        .iter()
+        .filter_map(|&edge| graph.remove_edge(edge))
+        .collect();
+    weights.shuffle(&mut rng);
+
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let key = |u: NodeId, v: NodeId| (u.index().min(v.index()), u.index().max(v.index()));
+    let adjacent: HashSet<(usize, usize)> = graph
+        .edges()
+        .filter(|(u, v, _)| u != v)
+        .map(|(u, v, _)| key(u, v))
+        .collect();
+    let n = nodes.len();
+    let free = n * n.saturating_sub(1) / 2 - adjacent.len();
+
+    let replaced = weights.len();
+    if free < 4 * replaced {
+        // Dense: list every free pair and draw from the list.
+        let mut pairs: Vec<(NodeId, NodeId)> = (0..n)
+            .flat_map(|i| (i + 1..n).map(move |j| (i, j)))
+            .map(|(i, j)| (nodes[i], nodes[j]))
+            .filter(|&(u, v)| !adjacent.contains(&key(u, v)))
+            .collect();
+        let (chosen, _) = pairs.partial_shuffle(&mut rng, replaced);
+        for (&(u, v), w) in chosen.iter().zip(weights) {
+            graph.add_edge(u, v, w);
+        }
+    } else {
+        // Sparse: most random pairs are free, so draw pairs until one is.
+        let mut taken = adjacent;
+        for w in weights {
+            loop {
+                let (u, v) = (nodes[rng.random_range(0..n)], nodes[rng.random_range(0..n)]);
+                if u != v && taken.insert(key(u, v)) {
+                    graph.add_edge(u, v, w);
+                    break;
+                }
+            }
+        }
+    }
+    Ok(replaced)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generators::barabasi_albert_graph;
+    use crate::core::types::Undirected;
+
+    #[test]
+    fn test_k_degree_anonymize_only_adds_edges() {
+        for (seed, k) in [(1, 2), (2, 3), (3, 5), (4, 10)] {
+            let mut g = barabasi_albert_graph::<Undirected>(120, 2, seed).unwrap();
+            let original: Vec<(NodeId, NodeId)> = g.edges().map(|(u, v, _)| (u, v)).collect();
+            let added = k_degree_anonymize(&mut g, k, 0.5).unwrap();
+            assert!(degree_anonymity(&g) >= k, "seed {seed}, k {k}");
+            assert!(original.iter().all(|&(u, v)| g.contains_edge(u, v)));
+            assert!(!g.has_parallel_edges());
+            assert!(added.iter().all(|&e| g.edge_weight(e) == Some(&0.5)));
+        }
+        assert!(
+            k_degree_anonymize(&mut Graph::<(), f64>::new(), 3, 1.0)
+                .unwrap()
+                .is_empty()
+        );
+        let mut tiny = Graph::<(), f64>::new();
+        tiny.add_node(());
+        assert!(k_degree_anonymize(&mut tiny, 2, 1.0).is_err());
+        // A self-loop or a parallel edge raises a degree past what simple edges can match.
+        let mut looped = Graph::<(), f64>::new();
+        let [a, _] = [(); 2].map(|_| looped.add_node(()));
+        looped.add_edge(a, a, 1.0);
+        assert!(k_degree_anonymize(&mut looped, 2, 1.0).is_err());
+        let mut doubled = Graph::<(), f64>::new();
+        let [a, b, _] = [(); 3].map(|_| doubled.add_node(()));
+        doubled.add_edge(a, b, 1.0);
+        doubled.add_edge(a, b, 1.0);
+        assert!(k_degree_anonymize(&mut doubled, 3, 1.0).is_err());
+        assert_eq!((looped.edge_count(), doubled.edge_count()), (1, 2));
+        assert!(k_degree_anonymous_sequence(&[1, 2], 0).is_err());
+        // Groups of 2: {4, 4} and {3, 1} costs 2, where {4, 4, 3} and {1} is not allowed.
+        assert_eq!(
+            k_degree_anonymous_sequence(&[4, 3, 4, 1], 2).unwrap(),
+            vec![4, 3, 4, 3]
+        );
+
+        let mut g = barabasi_albert_graph::<Undirected>(50, 3, 9).unwrap();
+        let mut before: Vec<f32> = g.edges().map(|(_, _, &w)| w).collect();
+        assert_eq!(perturb_edges(&mut g, 1.0, 5).unwrap(), before.len());
+        let mut after: Vec<f32> = g.edges().map(|(_, _, &w)| w).collect();
+        before.sort_by(f32::total_cmp);
+        after.sort_by(f32::total_cmp);
+        assert_eq!(before, after);
+        assert!(!g.has_parallel_edges());
+        assert!(g.edges().all(|(u, v, _)| u != v));
+        assert!(perturb_edges(&mut g, 1.5, 5).is_err());
+    }
+}
@@ -6,7 +6,7 @@ These builders support fluent APIs, validation, and preset configurations for co
 */
 
 use crate::core::error::{GraphinaError, Result};
-use crate::core::types::{BaseGraph, Directed, GraphConstructor, NodeId, Undirected};
+use crate::core::types::{BaseGraph, Directed, Graph, GraphConstructor, NodeId, Undirected};
 use petgraph::EdgeType;
 use std::marker::PhantomData;
 
@@ -342,6 +342,125 @@ impl TopologyBuilder {
     }
 }
 
+/// How [`from_similarity_matrix`] decides which pairs become edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SparsifyRule {
+    /// Keep every pair whose similarity is at least the threshold.
+    Threshold(f64),
+    /// Keep, for each node, its `k` most similar other nodes. A pair is an edge when either
+    /// node keeps the other, so degrees are at least `k` (given enough nodes) but may exceed it.
+    TopK(usize),
+}
+
+/// Builds an undirected graph from a square, symmetric similarity matrix.
+///
+/// Node `i` stands for row `i` and carries `i` as its attribute. Pairs selected by `rule`
+/// become edges weighted by their similarity; the diagonal is ignored, so the graph has no
+/// self-loops. This is the usual way to turn a correlation matrix into a network. Negative
+/// correlations are compared as they are, so take absolute values first to keep strong
+/// anticorrelations. Ties under [`SparsifyRule::TopK`] go to the lower index.
+///
+/// Entries `(i, j)` and `(j, i)` may differ by rounding noise, and the edge gets their mean.
+///
+/// # Errors
+/// Returns `InvalidArgument` if the matrix is not square, an off-diagonal entry is not
+/// finite, the matrix is not symmetric, the threshold is NaN, or `k` is zero.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::builders::{from_similarity_matrix, SparsifyRule};
+///
+/// let corr = vec![
+///     vec![1.0, 0.9, 0.1],
+///     vec![0.9, 1.0, 0.4],
+///     vec![0.1, 0.4, 1.0],
+/// ];
+/// let strong = from_similarity_matrix(&corr, SparsifyRule::Threshold(0.5)).unwrap();
+/// assert_eq!(strong.edge_count(), 1);
+///
+/// // Every node keeps its best match, so node 2 links to node 1.
+/// let nearest = from_similarity_matrix(&corr, SparsifyRule::TopK(1)).unwrap();
+/// assert_eq!(nearest.edge_count(), 2);
+/// ```
+pub fn from_similarity_matrix(
+    matrix: &[Vec<f64>],
+    rule: SparsifyRule,
+) -> Result<Graph<usize, f64>> {
+    let n = matrix.len();
+    if let Some(i) = matrix.iter().position(|row| row.len() != n) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "from_similarity_matrix: row {} has {} entries, expected {}",
+            i,
+            matrix[i].len(),
+            n
+        )));
+    }
+    let mut sim = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let (a, b) = (matrix[i][j], matrix[j][i]);
+            if !a.is_finite() || !b.is_finite() {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "from_similarity_matrix: entry ({}, {}) is not finite",
+                    i, j
+                )));
+            }
+            if (a - b).abs() > 1e-9 * a.abs().max(b.abs()).max(1.0) {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "from_similarity_matrix: matrix is not symmetric at ({}, {})",
+                    i, j
+                )));
+            }
+            sim[i][j] = (a + b) / 2.0;
+            sim[j][i] = sim[i][j];
+        }
+    }
+
+    let mut keep = vec![vec![false; n]; n];
+    match rule {
+        SparsifyRule::Threshold(t) => {
+            if t.is_nan() {
+                return Err(GraphinaError::invalid_argument(
+                    "from_similarity_matrix: threshold must not be NaN",
+                ));
+            }
+            for i in 0..n {
+                for j in (i + 1)..n {
+                    keep[i][j] = sim[i][j] >= t;
+                }
+            }
+        }
+        SparsifyRule::TopK(k) => {
+            if k == 0 {
+                return Err(GraphinaError::invalid_argument(
+                    "from_similarity_matrix: k must be positive",
+                ));
+            }
+            let mut order: Vec<usize> = Vec::with_capacity(n);
+            for i in 0..n {
+                order.clear();
+                order.extend((0..n).filter(|&j| j != i));
+                order.sort_by(|&a, &b| sim[i][b].total_cmp(&sim[i][a]).then(a.cmp(&b)));
+                for &j in order.iter().take(k) {
+                    keep[i.min(j)][i.max(j)] = true;
+                }
+            }
+        }
+    }
+
+    let mut graph = Graph::with_capacity(n, 0);
+    let nodes: Vec<NodeId> = (0..n).map(|i| graph.add_node(i)).collect();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if keep[i][j] {
+                graph.add_edge(nodes[i], nodes[j], sim[i][j]);
+            }
+        }
+    }
+    Ok(graph)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -480,4 +599,41 @@ mod tests {
 
         assert_eq!(graph.edge_count(), 2);
     }
+
+    #[test]
+    fn test_from_similarity_matrix_rules() {
+        let m = vec![
+            vec![1.0, 0.8, 0.2, -0.9],
+            vec![0.8, 1.0, 0.3, 0.1],
+            vec![0.2, 0.3, 1.0, 0.3],
+            vec![-0.9, 0.1, 0.3, 1.0],
+        ];
+        let g = from_similarity_matrix(&m, SparsifyRule::Threshold(0.3)).unwrap();
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(g.edge_count(), 3);
+        let n: Vec<NodeId> = g.node_ids().collect();
+        assert_eq!(g.edge_weight(g.find_edge(n[0], n[1]).unwrap()), Some(&0.8));
+        assert!(!g.contains_edge(n[0], n[3]));
+
+        // Node 2 ties between nodes 1 and 3 and keeps the lower index.
+        let g = from_similarity_matrix(&m, SparsifyRule::TopK(1)).unwrap();
+        let mut edges: Vec<(usize, usize)> = g
+            .edges()
+            .map(|(u, v, _)| (u.index().min(v.index()), u.index().max(v.index())))
+            .collect();
+        edges.sort();
+        assert_eq!(edges, vec![(0, 1), (1, 2), (2, 3)]);
+
+        assert!(from_similarity_matrix(&m, SparsifyRule::TopK(0)).is_err());
+        assert!(from_similarity_matrix(&m[..3], SparsifyRule::TopK(1)).is_err());
+        let mut skewed = m.clone();
+        skewed[1][2] = 0.5;
+        assert!(from_similarity_matrix(&skewed, SparsifyRule::Threshold(0.0)).is_err());
+        assert_eq!(
+            from_similarity_matrix(&[], SparsifyRule::TopK(3))
+                .unwrap()
+                .node_count(),
+            0
+        );
+    }
 }
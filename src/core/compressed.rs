@@ -0,0 +1,527 @@
+/*!
+# Compressed Graphs
+
+An immutable, compact copy of a graph's structure for graphs too large to keep as a
+`BaseGraph`, in the spirit of the WebGraph framework. Attributes and weights are dropped; what
+is kept is enough for traversals and link analysis such as BFS and PageRank.
+
+Each node's sorted successor list is stored as a byte string:
+
+- **Gap encoding:** consecutive successors are written as differences in variable-length
+  bytes, so the nearby targets typical of real graphs take one byte each.
+- **Reference compression:** a list may instead name one of the few preceding lists, select
+  the successors it shares with it by alternating runs of copied and skipped entries, and
+  gap-encode only the rest. Consecutive nodes of crawled and ordered graphs share most of
+  their successors, which is where most of the saving comes from.
+
+Decoding a list follows at most [`CompressionOptions::max_reference_chain`] references, so
+neighbor iteration stays fast. [`CompressedGraph`] implements [`GraphQuery`] and
+[`GraphTraversal`] with unit attributes and weights, so code written against those traits,
+such as `traversal::bfs_on` and `centrality::pagerank_on`, runs on it unchanged.
+
+```rust
+use graphina::core::compressed::CompressedGraph;
+use graphina::core::types::Digraph;
+
+let mut g = Digraph::<&str, f64>::new();
+let [a, b, c] = ["a", "b", "c"].map(|name| g.add_node(name));
+g.add_edge(a, b, 1.0);
+g.add_edge(a, c, 2.0);
+g.add_edge(b, c, 1.0);
+
+let compressed = CompressedGraph::from_graph(&g);
+assert_eq!(compressed.successors(a).collect::<Vec<_>>(), vec![b, c]);
+assert!(compressed.contains_edge(b, c));
+assert_eq!(compressed.edge_count(), 3);
+```
+*/
+
+use crate::core::traits::{GraphQuery, GraphTraversal};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::graph::NodeIndex;
+
+/// Tuning of the encoding used by [`CompressedGraph::with_options`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressionOptions {
+    /// How many preceding nodes a successor list may copy from. `0` turns reference
+    /// compression off, leaving gap encoding only.
+    pub window: usize,
+    /// Longest chain of references decoding a list may follow. Longer chains compress better
+    /// and decode more slowly.
+    pub max_reference_chain: usize,
+}
+
+impl Default for CompressionOptions {
+    /// A window of 7 and chains of at most 3 references, the WebGraph defaults.
+    fn default() -> Self {
+        CompressionOptions {
+            window: 7,
+            max_reference_chain: 3,
+        }
+    }
+}
+
+/// An immutable graph structure with compressed successor lists.
+///
+/// Node IDs are those of the source graph, and each node's successors come back sorted by
+/// ID, with parallel edges repeated. On an undirected graph an edge is stored with both
+/// endpoints, so [`successors`](Self::successors) lists every neighbor.
+#[derive(Debug, Clone)]
+pub struct CompressedGraph {
+    directed: bool,
+    edge_count: usize,
+    /// Node ID at each position, or `None` when the IDs are exactly `0..n`.
+    ids: Option<Vec<NodeId>>,
+    /// Start of each position's list in `data`, plus the end of the last.
+    offsets: Vec<usize>,
+    data: Vec<u8>,
+}
+
+impl CompressedGraph {
+    /// Compresses `graph` with the default [`CompressionOptions`].
+    pub fn from_graph<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        Self::with_options(graph, CompressionOptions::default())
+    }
+
+    /// Compresses `graph`, trying every reference within `options.window` for each list and
+    /// keeping the shortest encoding.
+    ///
+    /// **Time Complexity:** O(V + E·window)
+    pub fn with_options<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, options: CompressionOptions) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let ids: Vec<NodeId> = graph.node_ids().collect();
+        let n = ids.len();
+        let compact = ids.iter().enumerate().all(|(i, id)| id.index() == i);
+        let bound = ids.last().map_or(0, |id| id.index() + 1);
+        let mut position = vec![u32::MAX; bound];
+        for (i, id) in ids.iter().enumerate() {
+            position[id.index()] = i as u32;
+        }
+
+        let mut lists: Vec<Vec<u32>> = Vec::with_capacity(options.window + 1);
+        let mut chain = vec![0usize; n];
+        let mut offsets = Vec::with_capacity(n + 1);
+        let mut data = Vec::new();
+        let (mut best, mut candidate) = (Vec::new(), Vec::new());
+        for (x, &id) in ids.iter().enumerate() {
+            let mut list: Vec<u32> = graph.neighbors(id).map(|v| position[v.index()]).collect();
+            list.sort_unstable();
+
+            best.clear();
+            encode(&mut best, x, &list, None);
+            let mut best_reference = 0;
+            // `lists` holds the lists of the preceding `window` nodes, nearest last.
+            for (r, previous) in lists.iter().rev().enumerate() {
+                let r = r + 1;
+                if chain[x - r] >= options.max_reference_chain {
+                    continue;
+                }
+                candidate.clear();
+                encode(&mut candidate, x, &list, Some((r, previous)));
+                if candidate.len() < best.len() {
+                    std::mem::swap(&mut best, &mut candidate);
+                    best_reference = r;
+                }
+            }
+            if best_reference > 0 {
+                chain[x] = chain[x - best_reference] + 1;
+            }
+            offsets.push(data.len());
+            data.extend_from_slice(&best);
+
+            if options.window > 0 {
+                if lists.len() == options.window {
+                    lists.remove(0);
+                }
+                lists.push(list);
+            }
+        }
+        offsets.push(data.len());
+        data.shrink_to_fit();
+
+        CompressedGraph {
+            directed: graph.is_directed(),
+            edge_count: graph.edge_count(),
+            ids: (!compact).then_some(ids),
+            offsets,
+            data,
+        }
+    }
+
+    /// Returns whether the source graph was directed.
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    /// Returns the number of edges of the source graph.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Returns the node IDs in ascending order.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        (0..self.node_count()).map(|x| self.id(x))
+    }
+
+    /// Returns whether `node` is in the graph.
+    pub fn contains_node(&self, node: NodeId) -> bool {
+        self.position(node).is_some()
+    }
+
+    /// Returns the successors of `node` in ascending order, or nothing if it is not in the
+    /// graph. The list is decoded once per call.
+    pub fn successors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        let mut list = Vec::new();
+        if let Some(x) = self.position(node) {
+            self.decode(x, &mut list);
+        }
+        list.into_iter().map(|y| self.id(y as usize))
+    }
+
+    /// Returns the number of successors of `node`, read without decoding the list.
+    pub fn out_degree(&self, node: NodeId) -> Option<usize> {
+        let x = self.position(node)?;
+        let mut cursor = self.offsets[x];
+        Some(read_varint(&self.data, &mut cursor) as usize)
+    }
+
+    /// Returns whether an edge leads from `source` to `target`.
+    pub fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        let (Some(x), Some(y)) = (self.position(source), self.position(target)) else {
+            return false;
+        };
+        let mut list = Vec::new();
+        self.decode(x, &mut list);
+        list.binary_search(&(y as u32)).is_ok()
+    }
+
+    /// Returns the number of heap bytes the graph occupies.
+    pub fn heap_size(&self) -> usize {
+        self.data.capacity()
+            + self.offsets.capacity() * std::mem::size_of::<usize>()
+            + self
+                .ids
+                .as_ref()
+                .map_or(0, |ids| ids.capacity() * std::mem::size_of::<NodeId>())
+    }
+
+    /// Returns the average number of bits spent on each stored successor entry, counting only
+    /// the encoded lists.
+    pub fn bits_per_edge(&self) -> f64 {
+        let entries: usize = (0..self.node_count())
+            .map(|x| {
+                let mut cursor = self.offsets[x];
+                read_varint(&self.data, &mut cursor) as usize
+            })
+            .sum();
+        if entries == 0 {
+            0.0
+        } else {
+            (self.data.len() * 8) as f64 / entries as f64
+        }
+    }
+
+    fn id(&self, x: usize) -> NodeId {
+        match &self.ids {
+            Some(ids) => ids[x],
+            None => NodeId::new(NodeIndex::new(x)),
+        }
+    }
+
+    fn position(&self, node: NodeId) -> Option<usize> {
+        match &self.ids {
+            Some(ids) => ids.binary_search(&node).ok(),
+            None => (node.index() < self.node_count()).then_some(node.index()),
+        }
+    }
+
+    /// Decodes the successor positions of position `x` into `out`.
+    fn decode(&self, x: usize, out: &mut Vec<u32>) {
+        out.clear();
+        let mut cursor = self.offsets[x];
+        let degree = read_varint(&self.data, &mut cursor) as usize;
+        if degree == 0 {
+            return;
+        }
+        let reference = read_varint(&self.data, &mut cursor) as usize;
+        let mut copied = Vec::new();
+        if reference > 0 {
+            let mut source = Vec::new();
+            self.decode(x - reference, &mut source);
+            let blocks = read_varint(&self.data, &mut cursor) as usize;
+            let mut at = 0;
+            for block in 0..blocks {
+                let length = read_varint(&self.data, &mut cursor) as usize + usize::from(block > 0);
+                if block % 2 == 0 {
+                    copied.extend_from_slice(&source[at..at + length]);
+                }
+                at += length;
+            }
+        }
+
+        let residuals = degree - copied.len();
+        let mut residual = Vec::with_capacity(residuals);
+        if residuals > 0 {
+            let first = x as i64 + unzigzag(read_varint(&self.data, &mut cursor));
+            residual.push(first as u32);
+            for _ in 1..residuals {
+                let gap = read_varint(&self.data, &mut cursor) as u32;
+                residual.push(residual[residual.len() - 1] + gap);
+            }
+        }
+
+        out.reserve(degree);
+        let (mut i, mut j) = (0, 0);
+        while i < copied.len() && j < residual.len() {
+            if copied[i] <= residual[j] {
+                out.push(copied[i]);
+                i += 1;
+            } else {
+                out.push(residual[j]);
+                j += 1;
+            }
+        }
+        out.extend_from_slice(&copied[i..]);
+        out.extend_from_slice(&residual[j..]);
+    }
+}
+
+/// Appends the encoding of the sorted `list` of position `x`, optionally copying from the
+/// list of position `x - r`.
+fn encode(out: &mut Vec<u8>, x: usize, list: &[u32], reference: Option<(usize, &[u32])>) {
+    write_varint(out, list.len() as u64);
+    if list.is_empty() {
+        return;
+    }
+    let mut residuals = Vec::new();
+    match reference {
+        None => {
+            write_varint(out, 0);
+            residuals.extend_from_slice(list);
+        }
+        Some((r, source)) => {
+            write_varint(out, r as u64);
+            // Match the two sorted lists, pairing equal entries one to one.
+            let mut copied = vec![false; source.len()];
+            let (mut i, mut j) = (0, 0);
+            while i < list.len() {
+                if j < source.len() && source[j] < list[i] {
+                    j += 1;
+                } else if j < source.len() && source[j] == list[i] {
+                    copied[j] = true;
+                    i += 1;
+                    j += 1;
+                } else {
+                    residuals.push(list[i]);
+                    i += 1;
+                }
+            }
+            // Alternating runs of copied and skipped entries, starting with a copy run that
+            // may be empty; a trailing skip run is left out.
+            let mut blocks = Vec::new();
+            let mut at = 0;
+            let mut copying = true;
+            while at < source.len() {
+                let start = at;
+                while at < source.len() && copied[at] == copying {
+                    at += 1;
+                }
+                blocks.push(at - start);
+                copying = !copying;
+            }
+            if blocks.len() % 2 == 0 {
+                blocks.pop();
+            }
+            write_varint(out, blocks.len() as u64);
+            for (k, &length) in blocks.iter().enumerate() {
+                write_varint(out, (length - usize::from(k > 0)) as u64);
+            }
+        }
+    }
+    if let Some((&first, rest)) = residuals.split_first() {
+        write_varint(out, zigzag(first as i64 - x as i64));
+        let mut previous = first;
+        for &y in rest {
+            write_varint(out, u64::from(y - previous));
+            previous = y;
+        }
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(data: &[u8], cursor: &mut usize) -> u64 {
+    let mut value = 0;
+    let mut shift = 0;
+    loop {
+        let byte = data[*cursor];
+        *cursor += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte < 0x80 {
+            return value;
+        }
+        shift += 7;
+    }
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+impl GraphQuery<(), ()> for CompressedGraph {
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node_count() == 0
+    }
+
+    fn node_count(&self) -> usize {
+        CompressedGraph::node_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    fn contains_node(&self, node: NodeId) -> bool {
+        CompressedGraph::contains_node(self, node)
+    }
+
+    fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        CompressedGraph::contains_edge(self, source, target)
+    }
+
+    fn node_attr(&self, node: NodeId) -> Option<&()> {
+        CompressedGraph::contains_node(self, node).then_some(&())
+    }
+
+    fn edge_weight(&self, source: NodeId, target: NodeId) -> Option<&()> {
+        CompressedGraph::contains_edge(self, source, target).then_some(&())
+    }
+}
+
+impl GraphTraversal<(), ()> for CompressedGraph {
+    type NodeIter<'a> = Box<dyn Iterator<Item = NodeId> + 'a>;
+    type NeighborIter<'a> = Box<dyn Iterator<Item = NodeId> + 'a>;
+
+    fn node_ids(&self) -> Self::NodeIter<'_> {
+        Box::new(CompressedGraph::node_ids(self))
+    }
+
+    fn neighbors(&self, node: NodeId) -> Self::NeighborIter<'_> {
+        Box::new(self.successors(node))
+    }
+
+    /// On a directed graph this scans every list to count the incoming edges.
+    fn degree(&self, node: NodeId) -> Option<usize> {
+        if self.directed {
+            Some(self.out_degree(node)? + GraphTraversal::in_degree(self, node)?)
+        } else {
+            self.out_degree(node)
+        }
+    }
+
+    /// On a directed graph this scans every list, since only successors are stored.
+    fn in_degree(&self, node: NodeId) -> Option<usize> {
+        if !self.directed {
+            return self.out_degree(node);
+        }
+        let y = self.position(node)? as u32;
+        let mut list = Vec::new();
+        Some(
+            (0..CompressedGraph::node_count(self))
+                .map(|x| {
+                    self.decode(x, &mut list);
+                    list.iter().filter(|&&z| z == y).count()
+                })
+                .sum(),
+        )
+    }
+
+    fn out_degree(&self, node: NodeId) -> Option<usize> {
+        CompressedGraph::out_degree(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_compressed_graph_matches_source() {
+        // Overlapping neighborhoods, parallel edges, self-loops, and a hole in the IDs.
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut g = Digraph::<(), ()>::new();
+        let nodes: Vec<_> = (0..300).map(|_| g.add_node(())).collect();
+        for i in 0..300 {
+            for _ in 0..8 {
+                let j = (i + rng.random_range(0..20)) % 300;
+                g.add_edge(nodes[i], nodes[j], ());
+            }
+        }
+        g.add_edge(nodes[4], nodes[4], ());
+        g.remove_node(nodes[150]);
+
+        for options in [
+            CompressionOptions::default(),
+            CompressionOptions {
+                window: 0,
+                max_reference_chain: 0,
+            },
+            CompressionOptions {
+                window: 3,
+                max_reference_chain: 1,
+            },
+        ] {
+            let compressed = CompressedGraph::with_options(&g, options);
+            assert_eq!(compressed.node_count(), g.node_count());
+            assert_eq!(compressed.edge_count(), g.edge_count());
+            assert!(!compressed.contains_node(nodes[150]));
+            for u in g.node_ids() {
+                let mut expected: Vec<_> = g.neighbors(u).collect();
+                expected.sort();
+                assert_eq!(compressed.successors(u).collect::<Vec<_>>(), expected);
+                assert_eq!(compressed.out_degree(u), g.out_degree(u));
+            }
+            assert_eq!(
+                GraphTraversal::in_degree(&compressed, nodes[4]),
+                g.in_degree(nodes[4])
+            );
+            assert!(compressed.bits_per_edge() < 16.0);
+        }
+
+        let mut path = Graph::<(), ()>::new();
+        let [a, b, c] = [(); 3].map(|_| path.add_node(()));
+        path.add_edge(a, b, ());
+        path.add_edge(b, c, ());
+        let compressed = CompressedGraph::from_graph(&path);
+        assert_eq!(compressed.successors(b).collect::<Vec<_>>(), vec![a, c]);
+        assert!(compressed.contains_edge(c, b));
+        assert_eq!(GraphTraversal::degree(&compressed, b), Some(2));
+    }
+}
@@ -0,0 +1,276 @@
+/*!
+# Node and Edge Contraction
+
+Quotient-graph operations that merge a set of nodes into one. They are the building block for
+graph coarsening, Karger-style randomized cut algorithms, and community-level summaries.
+
+Contraction never modifies its input. It returns a new graph together with a map from every
+original node to the node that represents it in the result. Edges between two merged nodes
+disappear; edges from the merged set to the rest of the graph are redirected to the new node.
+When redirection leaves several edges between the same pair of nodes, a [`WeightMerge`]
+policy decides whether they are combined or kept side by side.
+*/
+
+use std::collections::HashMap;
+use std::ops::Add;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId, NodeMap, NodeSet};
+use petgraph::EdgeType;
+
+/// How edges that end up between the same pair of nodes after a contraction are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WeightMerge {
+    /// Replace them with one edge carrying the sum of their weights.
+    Sum,
+    /// Replace them with one edge carrying the smallest weight.
+    Min,
+    /// Replace them with one edge carrying the largest weight.
+    Max,
+    /// Keep each edge, so the result may contain parallel edges.
+    KeepParallel,
+}
+
+impl WeightMerge {
+    fn combine<W>(self, acc: W, next: W) -> W
+    where
+        W: Copy + PartialOrd + Add<Output = W>,
+    {
+        match self {
+            WeightMerge::Sum | WeightMerge::KeepParallel => acc + next,
+            WeightMerge::Min => {
+                if next < acc {
+                    next
+                } else {
+                    acc
+                }
+            }
+            WeightMerge::Max => {
+                if next > acc {
+                    next
+                } else {
+                    acc
+                }
+            }
+        }
+    }
+}
+
+/// Merges `nodes` into a single node and returns the contracted graph with a map from every
+/// original node to its node in the result.
+///
+/// `merged_attr` receives the attributes of the merged nodes, in the order they first appear
+/// in `nodes`, and returns the attribute of the new node. Duplicates in `nodes` are ignored,
+/// and a single-node set simply copies the graph. Edges among the merged nodes, including
+/// their self-loops, are dropped. All other parallel edges, whether created by the
+/// contraction or already present, are combined according to `policy`. In an undirected
+/// graph the edges `u - v` and `v - u` count as the same pair.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if `nodes` is empty and `NodeNotFound` if any of them is missing.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::contraction::{contract_nodes, WeightMerge};
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<u32, f64>::new();
+/// let a = g.add_node(1);
+/// let b = g.add_node(2);
+/// let c = g.add_node(4);
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(a, c, 2.0);
+/// g.add_edge(b, c, 3.0);
+///
+/// let merged_attr = |attrs: &[&u32]| attrs.iter().copied().sum();
+/// let (q, map) = contract_nodes(&g, &[a, b], merged_attr, WeightMerge::Sum).unwrap();
+/// assert_eq!(q.node_count(), 2);
+/// assert_eq!(q.node_attr(map[&a]), Some(&3));
+/// let edge = q.find_edge(map[&a], map[&c]).unwrap();
+/// assert_eq!(q.edge_weight(edge), Some(&5.0));
+/// ```
+pub fn contract_nodes<A, W, Ty, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    nodes: &[NodeId],
+    merged_attr: F,
+    policy: WeightMerge,
+) -> Result<(BaseGraph<A, W, Ty>, NodeMap<NodeId>)>
+where
+    A: Clone,
+    W: Copy + PartialOrd + Add<Output = W>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: FnOnce(&[&A]) -> A,
+{
+    if nodes.is_empty() {
+        return Err(GraphinaError::invalid_argument(
+            "Cannot contract an empty node set",
+        ));
+    }
+    let mut members = NodeSet::default();
+    let mut attrs = Vec::with_capacity(nodes.len());
+    for &node in nodes {
+        let attr = graph.node_attr(node).ok_or_else(|| {
+            GraphinaError::node_not_found(format!("Node {} not found in graph", node.index()))
+        })?;
+        if members.insert(node) {
+            attrs.push(attr);
+        }
+    }
+
+    let mut result =
+        BaseGraph::<A, W, Ty>::with_capacity(graph.node_count() - members.len() + 1, 0);
+    let mut mapping = NodeMap::default();
+    let merged = result.add_node(merged_attr(&attrs));
+    for (node, attr) in graph.nodes() {
+        let target = if members.contains(&node) {
+            merged
+        } else {
+            result.add_node(attr.clone())
+        };
+        mapping.insert(node, target);
+    }
+
+    let directed = graph.is_directed();
+    let mut merged_edges: Vec<(NodeId, NodeId, W)> = Vec::new();
+    let mut slot: HashMap<(NodeId, NodeId), usize> = HashMap::new();
+    for (u, v, &w) in graph.edges() {
+        if members.contains(&u) && members.contains(&v) {
+            continue;
+        }
+        let (nu, nv) = (mapping[&u], mapping[&v]);
+        if policy == WeightMerge::KeepParallel {
+            merged_edges.push((nu, nv, w));
+            continue;
+        }
+        let key = if directed || nu <= nv {
+            (nu, nv)
+        } else {
+            (nv, nu)
+        };
+        match slot.get(&key) {
+            Some(&i) => merged_edges[i].2 = policy.combine(merged_edges[i].2, w),
+            None => {
+                slot.insert(key, merged_edges.len());
+                merged_edges.push((nu, nv, w));
+            }
+        }
+    }
+    for (u, v, w) in merged_edges {
+        result.add_edge(u, v, w);
+    }
+
+    Ok((result, mapping))
+}
+
+/// Contracts a single edge, merging its two endpoints.
+///
+/// Equivalent to [`contract_nodes`] on the endpoints of `edge`: the edge itself and any edges
+/// parallel to it are removed. Contracting a self-loop only removes the loops on that node.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if `edge` is not in the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::contraction::{contract_edge, WeightMerge};
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// let ab = g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(a, c, 1.0);
+///
+/// let (q, map) = contract_edge(&g, ab, |_| (), WeightMerge::KeepParallel).unwrap();
+/// assert_eq!(map[&a], map[&b]);
+/// assert_eq!(q.node_count(), 2);
+/// assert_eq!(q.edge_count(), 2);
+/// ```
+pub fn contract_edge<A, W, Ty, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    edge: EdgeId,
+    merged_attr: F,
+    policy: WeightMerge,
+) -> Result<(BaseGraph<A, W, Ty>, NodeMap<NodeId>)>
+where
+    A: Clone,
+    W: Copy + PartialOrd + Add<Output = W>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: FnOnce(&[&A]) -> A,
+{
+    let (u, v) = graph.inner.edge_endpoints(edge.0).ok_or_else(|| {
+        GraphinaError::invalid_argument(format!("Edge {} not found in graph", edge.index()))
+    })?;
+    contract_nodes(
+        graph,
+        &[NodeId::new(u), NodeId::new(v)],
+        merged_attr,
+        policy,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_contract_nodes_policies() {
+        let mut g = Graph::<u32, f64>::new();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let c = g.add_node(3);
+        let d = g.add_node(4);
+        g.add_edge(a, b, 9.0);
+        g.add_edge(a, c, 1.0);
+        g.add_edge(c, b, 4.0);
+        g.add_edge(c, d, 2.0);
+        g.add_edge(b, b, 7.0);
+
+        let first = |attrs: &[&u32]| *attrs[0];
+        let weight_to_c = |policy| {
+            let (q, map) = contract_nodes(&g, &[b, a, b], first, policy).unwrap();
+            assert_eq!(q.node_count(), 3);
+            assert_eq!(q.node_attr(map[&a]), Some(&2));
+            q.edges()
+                .filter(|&(u, v, _)| (u, v) == (map[&a], map[&c]) || (u, v) == (map[&c], map[&a]))
+                .map(|(_, _, &w)| w)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(weight_to_c(WeightMerge::Sum), vec![5.0]);
+        assert_eq!(weight_to_c(WeightMerge::Min), vec![1.0]);
+        assert_eq!(weight_to_c(WeightMerge::Max), vec![4.0]);
+        assert_eq!(weight_to_c(WeightMerge::KeepParallel), vec![1.0, 4.0]);
+
+        assert!(contract_nodes(&g, &[], first, WeightMerge::Sum).is_err());
+        g.remove_node(d);
+        assert!(contract_nodes(&g, &[a, d], first, WeightMerge::Sum).is_err());
+    }
+
+    #[test]
+    fn test_contract_edge_directed_keeps_orientation() {
+        let mut g = Digraph::<(), f64>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let ab = g.add_edge(a, b, 1.0);
+        g.add_edge(b, a, 1.0);
+        g.add_edge(c, a, 2.0);
+        g.add_edge(b, c, 3.0);
+
+        let (q, map) = contract_edge(&g, ab, |_| (), WeightMerge::Sum).unwrap();
+        let m = map[&a];
+        assert_eq!(q.edge_count(), 2);
+        assert!(q.find_edge(map[&c], m).is_some());
+        assert!(q.find_edge(m, map[&c]).is_some());
+
+        g.remove_edge(ab);
+        assert!(contract_edge(&g, ab, |_| (), WeightMerge::Sum).is_err());
+    }
+}
@@ -87,6 +87,15 @@ pub enum GraphinaError {
     #[error("Serialization error: {0}")]
     SerializationError(String),
 
+    /// Serialized data uses a format version this build cannot read
+    #[error("Unsupported format version {found}; this build reads versions up to {supported}")]
+    UnsupportedVersion {
+        /// Version recorded in the data
+        found: u32,
+        /// Newest version this build understands
+        supported: u32,
+    },
+
     /// Invalid argument or parameter
     #[error("Invalid argument: {0}")]
     InvalidArgument(String),
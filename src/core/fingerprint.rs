@@ -0,0 +1,239 @@
+/*!
+# Graph Fingerprints
+
+A stable content hash for checking that two processes, runs, or machines hold the same graph.
+
+The fingerprint depends only on the graph's content: whether it is directed, the multiset of
+node attributes, and the multiset of edges described by their endpoint attributes and weight.
+It does not depend on node or edge IDs or on insertion order, so two loaders that add the same
+data in a different order agree. Attributes and weights are encoded with bincode before
+hashing, so the value is the same on every platform and build, but any change to an encoded
+value, including `0.0` versus `-0.0`, changes it.
+
+Nodes are identified only through their attributes. When several nodes share an attribute,
+for example in a `Graph<(), f64>`, graphs that differ by how edges attach to those nodes can
+collide; give nodes distinguishing attributes when that matters. Attribute types whose
+serialized form depends on iteration order, such as `HashMap`, do not produce stable
+fingerprints.
+*/
+
+use serde::Serialize;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeMap};
+use petgraph::EdgeType;
+
+/// Version tag mixed into every fingerprint, bumped if the scheme ever changes.
+const SCHEME: &[u8] = b"graphina-fingerprint-v1";
+/// Version tag for [`fingerprint_with_ids`], kept apart so the two never collide.
+const SCHEME_WITH_IDS: &[u8] = b"graphina-fingerprint-ids-v1";
+
+/// FNV-1a over `bytes`, followed by the SplitMix64 finalizer for better bit mixing.
+fn digest(bytes: &[u8]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for &b in bytes {
+        h ^= u64::from(b);
+        h = h.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    h ^= h >> 30;
+    h = h.wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    h ^= h >> 27;
+    h = h.wrapping_mul(0x94d0_49bb_1331_11eb);
+    h ^ (h >> 31)
+}
+
+fn digest_value<T: Serialize>(value: &T) -> Result<u64> {
+    let bytes = bincode::serde::encode_to_vec(value, bincode::config::standard())
+        .map_err(GraphinaError::from)?;
+    Ok(digest(&bytes))
+}
+
+fn digest_words(words: &[u64]) -> u64 {
+    let bytes: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+    digest(&bytes)
+}
+
+/// Computes an order-independent content hash of `graph`.
+///
+/// Two graphs get the same fingerprint when they have the same direction and the same
+/// multisets of node attributes and of edges, where an edge is its weight together with the
+/// attributes of its endpoints. In undirected graphs the endpoints are unordered. See the
+/// [module documentation](self) for what the hash can and cannot tell apart.
+///
+/// # Errors
+///
+/// Returns `SerializationError` if an attribute or weight cannot be encoded.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::fingerprint::fingerprint;
+/// use graphina::core::types::Graph;
+///
+/// let mut first = Graph::<&str, f64>::new();
+/// let a = first.add_node("a");
+/// let b = first.add_node("b");
+/// first.add_edge(a, b, 1.5);
+///
+/// // Same content, built in the opposite order
+/// let mut second = Graph::<&str, f64>::new();
+/// let b = second.add_node("b");
+/// let a = second.add_node("a");
+/// second.add_edge(b, a, 1.5);
+///
+/// assert_eq!(fingerprint(&first).unwrap(), fingerprint(&second).unwrap());
+///
+/// second.add_edge(a, a, 0.5);
+/// assert_ne!(fingerprint(&first).unwrap(), fingerprint(&second).unwrap());
+/// ```
+pub fn fingerprint<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<u64>
+where
+    A: Serialize,
+    W: Serialize,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    hash_graph(graph, false)
+}
+
+/// Computes a content hash of `graph` that also depends on its `NodeId`s.
+///
+/// Use this instead of [`fingerprint`] to key data that refers to nodes by ID, such as a
+/// cached `NodeMap`: two graphs only agree when every node has the same ID and attribute in
+/// both and the edges join the same IDs. It is still independent of insertion order and of
+/// `EdgeId`s, and a mutation that changes no content or ID leaves it unchanged.
+///
+/// # Errors
+///
+/// Returns `SerializationError` if an attribute or weight cannot be encoded.
+pub fn fingerprint_with_ids<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<u64>
+where
+    A: Serialize,
+    W: Serialize,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    hash_graph(graph, true)
+}
+
+fn hash_graph<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, with_ids: bool) -> Result<u64>
+where
+    A: Serialize,
+    W: Serialize,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut node_hashes = NodeMap::default();
+    for (node, attr) in graph.nodes() {
+        let mut h = digest_value(attr)?;
+        if with_ids {
+            h = digest_words(&[node.index() as u64, h]);
+        }
+        node_hashes.insert(node, h);
+    }
+    let directed = graph.is_directed();
+    let mut edge_hashes = Vec::with_capacity(graph.edge_count());
+    for (u, v, w) in graph.edges() {
+        let (mut hu, mut hv) = (node_hashes[&u], node_hashes[&v]);
+        if !directed && hu > hv {
+            std::mem::swap(&mut hu, &mut hv);
+        }
+        edge_hashes.push(digest_words(&[hu, hv, digest_value(w)?]));
+    }
+    let mut node_hashes: Vec<u64> = node_hashes.into_values().collect();
+    node_hashes.sort_unstable();
+    edge_hashes.sort_unstable();
+
+    let mut words = Vec::with_capacity(4 + node_hashes.len() + edge_hashes.len());
+    words.push(digest(if with_ids { SCHEME_WITH_IDS } else { SCHEME }));
+    words.push(u64::from(directed));
+    words.push(node_hashes.len() as u64);
+    words.push(edge_hashes.len() as u64);
+    words.extend(node_hashes);
+    words.extend(edge_hashes);
+    Ok(digest_words(&words))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_fingerprint_ignores_order_but_not_content() {
+        let edges = [(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0), (2, 3, 4.0)];
+        let build = |node_order: &[u32], reverse_edges: bool| {
+            let mut g = Graph::<u32, f64>::new();
+            let mut ids = [None; 4];
+            for &i in node_order {
+                ids[i as usize] = Some(g.add_node(i));
+            }
+            let ids: Vec<_> = ids.into_iter().flatten().collect();
+            let mut list = edges.to_vec();
+            if reverse_edges {
+                list.reverse();
+            }
+            for (u, v, w) in list {
+                let (u, v) = if reverse_edges { (v, u) } else { (u, v) };
+                g.add_edge(ids[u], ids[v], w);
+            }
+            g
+        };
+        let base = fingerprint(&build(&[0, 1, 2, 3], false)).unwrap();
+        assert_eq!(fingerprint(&build(&[3, 1, 0, 2], true)).unwrap(), base);
+
+        let mut heavier = build(&[0, 1, 2, 3], false);
+        let e = heavier.edge_ids().next().unwrap();
+        *heavier.edge_weight_mut(e).unwrap() = 1.25;
+        assert_ne!(fingerprint(&heavier).unwrap(), base);
+
+        let mut renamed = build(&[0, 1, 2, 3], false);
+        let n = renamed.node_ids().next().unwrap();
+        renamed.update_node(n, 9);
+        assert_ne!(fingerprint(&renamed).unwrap(), base);
+    }
+
+    #[test]
+    fn test_fingerprint_respects_direction() {
+        let mut d = Digraph::<u32, f64>::new();
+        let a = d.add_node(1);
+        let b = d.add_node(2);
+        d.add_edge(a, b, 1.0);
+        let mut flipped = Digraph::<u32, f64>::new();
+        let a2 = flipped.add_node(1);
+        let b2 = flipped.add_node(2);
+        flipped.add_edge(b2, a2, 1.0);
+        assert_ne!(fingerprint(&d).unwrap(), fingerprint(&flipped).unwrap());
+        // Pinned so that an accidental change to the hashing scheme is caught.
+        assert_eq!(fingerprint(&d).unwrap(), 0x9af0_be4c_419b_4db7);
+
+        let mut u = Graph::<u32, f64>::new();
+        let a3 = u.add_node(1);
+        let b3 = u.add_node(2);
+        u.add_edge(a3, b3, 1.0);
+        assert_ne!(fingerprint(&d).unwrap(), fingerprint(&u).unwrap());
+    }
+
+    #[test]
+    fn test_fingerprint_with_ids_tracks_node_ids() {
+        let build = |first: u32, second: u32| {
+            let mut g = Graph::<u32, f64>::new();
+            let a = g.add_node(first);
+            let b = g.add_node(second);
+            g.add_edge(a, b, 1.0);
+            g
+        };
+        // Same content, but the attributes sit on swapped IDs.
+        let (left, right) = (build(1, 2), build(2, 1));
+        assert_eq!(fingerprint(&left).unwrap(), fingerprint(&right).unwrap());
+        assert_ne!(
+            fingerprint_with_ids(&left).unwrap(),
+            fingerprint_with_ids(&right).unwrap()
+        );
+        assert_eq!(
+            fingerprint_with_ids(&left).unwrap(),
+            fingerprint_with_ids(&build(1, 2)).unwrap()
+        );
+        assert_ne!(
+            fingerprint_with_ids(&left).unwrap(),
+            fingerprint(&left).unwrap()
+        );
+    }
+}
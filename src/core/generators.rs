@@ -2,10 +2,12 @@
 # Graph Generators
 
 Graph generators for classic graphs:
-Erdős–Rényi, complete, bipartite, star, cycle, Watts–Strogatz small-world, and
-Barabási–Albert scale-free graphs. Each generator is generic over the graph type
+Erdős–Rényi, complete, bipartite, star, cycle, Watts–Strogatz small-world,
+Barabási–Albert scale-free, and stochastic block model graphs. Each generator is generic over the graph type
 (directed or undirected) using the `GraphConstructor` trait. Node attributes are fixed
-to `u32` and edge weights to `f32`.
+to `u32` and edge weights to `f32`. `configuration_model` builds an undirected multigraph
+with a given degree sequence, and `degree_preserving_rewire` instead shuffles an existing
+graph of any type while keeping every node's degree.
 
 Most generators use a seeded random number generator for reproducibility. In case of
 invalid parameters (e.g. probability out of [0, 1] or insufficient nodes), functions
@@ -35,8 +37,9 @@ let ws = watts_strogatz_graph::<Undirected>(100, 6, 0.3, 42)
 */
 
 use crate::core::error::GraphinaError;
-use crate::core::types::{BaseGraph, GraphConstructor};
+use crate::core::types::{BaseGraph, Graph, GraphConstructor};
 use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rand::{Rng, SeedableRng};
 
 /// Generates an Erdős–Rényi graph.
@@ -497,6 +500,219 @@ pub fn barabasi_albert_graph<Ty: GraphConstructor<u32, f32>>(
     Ok(graph)
 }
 
+/// Generates a stochastic block model graph.
+///
+/// Nodes are split into consecutive blocks of the given sizes, and each pair of nodes in
+/// blocks `a` and `b` is joined with probability `p_matrix[a][b]`; on a directed graph that
+/// is the probability of the edge from the node in `a` to the node in `b`. There are no
+/// self-loops. Each node's attribute is its block index, so the planted partition can serve
+/// as ground truth for community detection.
+///
+/// # Arguments
+///
+/// * `block_sizes` - The number of nodes in each block (at least one node in total).
+/// * `p_matrix` - A square matrix with one row per block and entries in [0.0, 1.0], symmetric
+///   for undirected graphs.
+/// * `seed` - The seed for the random number generator.
+///
+/// # Type Parameters
+///
+/// * `Ty` - The graph type implementing `GraphConstructor<u32, f32>`.
+///
+/// # Returns
+///
+/// * `Result<BaseGraph<u32, f32, Ty>, GraphinaError>` - The generated graph, or an error if parameters are invalid.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::generators::sbm_graph;
+/// use graphina::core::types::Undirected;
+///
+/// // Two dense blocks of ten nodes with no edges between them.
+/// let p = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+/// let g = sbm_graph::<Undirected>(&[10, 10], &p, 42).unwrap();
+/// assert_eq!(g.edge_count(), 2 * 45);
+/// assert!(g.edges().all(|(u, v, _)| g.node_attr(u) == g.node_attr(v)));
+/// ```
+pub fn sbm_graph<Ty: GraphConstructor<u32, f32>>(
+    block_sizes: &[usize],
+    p_matrix: &[Vec<f64>],
+    seed: u64,
+) -> Result<BaseGraph<u32, f32, Ty>, GraphinaError> {
+    let k = block_sizes.len();
+    if block_sizes.iter().sum::<usize>() == 0 {
+        return Err(GraphinaError::InvalidArgument(
+            "Number of nodes must be greater than zero.".into(),
+        ));
+    }
+    if p_matrix.len() != k || p_matrix.iter().any(|row| row.len() != k) {
+        return Err(GraphinaError::InvalidArgument(format!(
+            "p_matrix must be {k} by {k}, one row and column per block."
+        )));
+    }
+    if p_matrix.iter().flatten().any(|p| !(0.0..=1.0).contains(p)) {
+        return Err(GraphinaError::InvalidArgument(
+            "Probabilities in p_matrix must be in the range [0.0, 1.0].".into(),
+        ));
+    }
+    let directed = <Ty as GraphConstructor<u32, f32>>::is_directed();
+    if !directed && (0..k).any(|a| (0..a).any(|b| p_matrix[a][b] != p_matrix[b][a])) {
+        return Err(GraphinaError::InvalidArgument(
+            "p_matrix must be symmetric for undirected graphs.".into(),
+        ));
+    }
+
+    let mut graph = BaseGraph::<u32, f32, Ty>::new();
+    let mut nodes = Vec::new();
+    for (block, &size) in block_sizes.iter().enumerate() {
+        for _ in 0..size {
+            nodes.push((graph.add_node(block as u32), block));
+        }
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    for (i, &(u, a)) in nodes.iter().enumerate() {
+        let start = if directed { 0 } else { i + 1 };
+        for (j, &(v, b)) in nodes.iter().enumerate().skip(start) {
+            if i != j && rng.random_bool(p_matrix[a][b]) {
+                graph.add_edge(u, v, 1.0);
+            }
+        }
+    }
+    Ok(graph)
+}
+
+/// Generates a configuration model graph with the given degree sequence.
+///
+/// Node `i` gets `degree_sequence[i]` edge ends ("stubs"), and the stubs are shuffled and
+/// joined in pairs, so every node has exactly its degree. The result is a multigraph: it may
+/// contain parallel edges and self-loops, a self-loop using two stubs of the same node. Each
+/// node's attribute is its position in the sequence. The model is defined on undirected
+/// graphs only.
+///
+/// Use it to build a null model with a given degree distribution; to keep a real graph's
+/// simple structure instead, shuffle a copy with [`degree_preserving_rewire`].
+///
+/// # Arguments
+///
+/// * `degree_sequence` - The degree of each node (at least one node, with an even sum).
+/// * `seed` - The seed for the random number generator.
+///
+/// # Returns
+///
+/// * `Result<Graph<u32, f32>, GraphinaError>` - The generated graph, or an error if parameters are invalid.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::generators::configuration_model;
+///
+/// let g = configuration_model(&[3, 2, 2, 1, 0], 42).unwrap();
+/// assert_eq!(g.node_count(), 5);
+/// assert_eq!(g.edge_count(), 4);
+/// ```
+pub fn configuration_model(
+    degree_sequence: &[usize],
+    seed: u64,
+) -> Result<Graph<u32, f32>, GraphinaError> {
+    if degree_sequence.is_empty() {
+        return Err(GraphinaError::InvalidArgument(
+            "Number of nodes must be greater than zero.".into(),
+        ));
+    }
+    let total: usize = degree_sequence.iter().sum();
+    if total % 2 != 0 {
+        return Err(GraphinaError::InvalidArgument(format!(
+            "Degree sequence must have an even sum, but it sums to {total}."
+        )));
+    }
+
+    let mut graph = Graph::<u32, f32>::with_capacity(degree_sequence.len(), total / 2);
+    let mut stubs = Vec::with_capacity(total);
+    for (i, &degree) in degree_sequence.iter().enumerate() {
+        let node = graph.add_node(i as u32);
+        stubs.extend(std::iter::repeat_n(node, degree));
+    }
+    stubs.shuffle(&mut StdRng::seed_from_u64(seed));
+    for pair in stubs.chunks_exact(2) {
+        graph.add_edge(pair[0], pair[1], 1.0);
+    }
+    Ok(graph)
+}
+
+/// Randomizes `graph` in place with up to `n_swaps` double edge swaps and returns the number
+/// performed.
+///
+/// A swap takes edges `(a, b)` and `(c, d)` and replaces them with `(a, d)` and `(c, b)`, each
+/// keeping its weight, so every node keeps its degree (its in- and out-degree on a directed
+/// graph). Swaps that would create a self-loop or an edge that already exists are rejected,
+/// and after `100 * n_swaps` attempts the function gives up, so dense or tiny graphs may
+/// receive fewer swaps than asked.
+///
+/// About ten swaps per edge are enough to forget the original wiring, which makes the result
+/// a sample of the null model that `metrics::significance_test` compares against.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::generators::{degree_preserving_rewire, watts_strogatz_graph};
+/// use graphina::core::types::Undirected;
+///
+/// let original = watts_strogatz_graph::<Undirected>(30, 4, 0.0, 1).unwrap();
+/// let mut shuffled = original.clone();
+/// let n_swaps = 10 * shuffled.edge_count();
+/// let swaps = degree_preserving_rewire(&mut shuffled, n_swaps, 7);
+/// assert!(swaps > 0);
+/// for node in original.node_ids() {
+///     assert_eq!(shuffled.degree(node), original.degree(node));
+/// }
+/// ```
+pub fn degree_preserving_rewire<A, W, Ty>(
+    graph: &mut BaseGraph<A, W, Ty>,
+    n_swaps: usize,
+    seed: u64,
+) -> usize
+where
+    W: Clone,
+    Ty: GraphConstructor<A, W>,
+{
+    let mut edges: Vec<_> = graph
+        .edges_with_ids()
+        .map(|(edge, u, v, _)| (edge, u, v))
+        .collect();
+    if edges.len() < 2 {
+        return 0;
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    let directed = graph.is_directed();
+    let mut swaps = 0;
+    for _ in 0..n_swaps.saturating_mul(100) {
+        if swaps == n_swaps {
+            break;
+        }
+        let i = rng.random_range(0..edges.len());
+        let j = rng.random_range(0..edges.len());
+        let ((first, a, b), (second, mut c, mut d)) = (edges[i], edges[j]);
+        // An undirected edge can be read either way round, which reaches the other swap.
+        if !directed && rng.random::<bool>() {
+            std::mem::swap(&mut c, &mut d);
+        }
+        if i == j || a == c || b == d || a == d || c == b {
+            continue;
+        }
+        if graph.contains_edge(a, d) || graph.contains_edge(c, b) {
+            continue;
+        }
+        let (Some(w1), Some(w2)) = (graph.remove_edge(first), graph.remove_edge(second)) else {
+            continue;
+        };
+        edges[i] = (graph.add_edge(a, d, w1), a, d);
+        edges[j] = (graph.add_edge(c, b, w2), c, b);
+        swaps += 1;
+    }
+    swaps
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -651,6 +867,68 @@ mod tests {
         assert_eq!(graph.edge_count(), expected_edges);
     }
 
+    #[test]
+    fn test_sbm_graph() {
+        let p = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.0, 0.0, 0.0],
+        ];
+        let graph = sbm_graph::<Directed>(&[3, 2, 0], &p, 1).expect("Failed to generate SBM");
+        assert_eq!(graph.node_count(), 5);
+        // Every ordered pair inside block 0, and nothing else since block 2 is empty.
+        assert_eq!(graph.edge_count(), 6);
+        assert_eq!(
+            graph.nodes().map(|(_, &b)| b).collect::<Vec<_>>(),
+            vec![0, 0, 0, 1, 1]
+        );
+
+        assert!(matches!(
+            sbm_graph::<Undirected>(&[2, 2], &[vec![0.5, 0.1], vec![0.2, 0.5]], 1),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            sbm_graph::<Directed>(&[2, 2], &[vec![0.5, 0.1]], 1),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            sbm_graph::<Directed>(&[0], &[vec![0.5]], 1),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_configuration_model() {
+        let degrees = [4, 3, 3, 2, 2, 1, 1, 0];
+        let graph =
+            configuration_model(&degrees, 9).expect("Failed to generate configuration model");
+        assert_eq!(graph.node_count(), 8);
+        assert_eq!(graph.edge_count(), 8);
+        // Count stubs from the edges, so a self-loop counts twice.
+        let mut stubs = vec![0; degrees.len()];
+        for (u, v, _) in graph.edges() {
+            stubs[u.index()] += 1;
+            stubs[v.index()] += 1;
+        }
+        assert_eq!(stubs, degrees);
+        assert_eq!(
+            configuration_model(&degrees, 9)
+                .unwrap()
+                .edges()
+                .collect::<Vec<_>>(),
+            graph.edges().collect::<Vec<_>>()
+        );
+
+        assert!(matches!(
+            configuration_model(&[2, 1], 1),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            configuration_model(&[], 1),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+    }
+
     #[test]
     fn invalid_erdos_params_rejected() {
         assert!(matches!(
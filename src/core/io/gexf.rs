@@ -0,0 +1,1143 @@
+//! GEXF reading and writing.
+//!
+//! GEXF is the XML format of Gephi. A graph to save holds a [`GexfNode`] per node and a
+//! [`GexfEdge`] per edge, whose values refer by title to the attributes declared in a
+//! [`GexfSchema`]; build one from any graph with `map_node_attrs` and `map_edge_weights`.
+//! Dynamic attributes hold several values, each over its own time slice, and are written with
+//! numeric (`double`) time. A graph with [properties](crate::core::properties) is saved and
+//! loaded directly with [`save_gexf_properties`] and [`load_gexf_properties`].
+
+use crate::core::properties::{PropertyTable, Value, ValueType};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Error, ErrorKind, Write};
+
+/// Type of a declared GEXF attribute.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GexfType {
+    /// 32-bit integer.
+    Integer,
+    /// 64-bit integer.
+    Long,
+    /// Single-precision number.
+    Float,
+    /// Double-precision number.
+    Double,
+    /// `true` or `false`.
+    Boolean,
+    /// Free text.
+    String,
+    /// Any other type name, such as Gephi's `liststring`, kept verbatim and not checked.
+    Other(String),
+}
+
+impl GexfType {
+    fn name(&self) -> &str {
+        match self {
+            GexfType::Integer => "integer",
+            GexfType::Long => "long",
+            GexfType::Float => "float",
+            GexfType::Double => "double",
+            GexfType::Boolean => "boolean",
+            GexfType::String => "string",
+            GexfType::Other(name) => name,
+        }
+    }
+
+    fn from_name(name: &str) -> Self {
+        match name {
+            "integer" => GexfType::Integer,
+            "long" => GexfType::Long,
+            "float" => GexfType::Float,
+            "double" => GexfType::Double,
+            "boolean" => GexfType::Boolean,
+            "string" => GexfType::String,
+            other => GexfType::Other(other.to_string()),
+        }
+    }
+
+    fn accepts(&self, value: &str) -> bool {
+        match self {
+            GexfType::Integer => value.trim().parse::<i32>().is_ok(),
+            GexfType::Long => value.trim().parse::<i64>().is_ok(),
+            GexfType::Float | GexfType::Double => value.trim().parse::<f64>().is_ok(),
+            GexfType::Boolean => matches!(value.trim(), "true" | "false"),
+            GexfType::String | GexfType::Other(_) => true,
+        }
+    }
+}
+
+/// Declaration of a node or edge attribute.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GexfAttribute {
+    /// Name of the attribute, used as the key of [`GexfNode::values`] and [`GexfEdge::values`].
+    pub title: String,
+    /// Type every value must parse as.
+    pub kind: GexfType,
+    /// Value Gephi shows for items that do not set the attribute.
+    pub default: Option<String>,
+    /// Whether the attribute changes over time. A static attribute holds one value with no
+    /// time slice; a dynamic one holds any number of values, each with an optional slice.
+    pub dynamic: bool,
+}
+
+impl GexfAttribute {
+    /// Declares a static attribute with no default.
+    pub fn new(title: impl Into<String>, kind: GexfType) -> Self {
+        GexfAttribute {
+            title: title.into(),
+            kind,
+            default: None,
+            dynamic: false,
+        }
+    }
+
+    /// Declares a dynamic attribute with no default.
+    pub fn dynamic(title: impl Into<String>, kind: GexfType) -> Self {
+        GexfAttribute {
+            dynamic: true,
+            ..GexfAttribute::new(title, kind)
+        }
+    }
+}
+
+/// The attributes declared for the nodes and the edges of a GEXF graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GexfSchema {
+    /// Node attributes, in declaration order.
+    pub node_attributes: Vec<GexfAttribute>,
+    /// Edge attributes, in declaration order.
+    pub edge_attributes: Vec<GexfAttribute>,
+}
+
+/// One attribute value, optionally limited to the time slice `start..=end`.
+///
+/// A missing bound leaves the slice open on that side.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GexfValue {
+    /// The value as written in the file.
+    pub value: String,
+    /// Start of the time slice.
+    pub start: Option<f64>,
+    /// End of the time slice.
+    pub end: Option<f64>,
+}
+
+impl GexfValue {
+    /// A value with no time slice.
+    pub fn new(value: impl Into<String>) -> Self {
+        GexfValue {
+            value: value.into(),
+            start: None,
+            end: None,
+        }
+    }
+
+    /// A value that holds from `start` to `end`.
+    pub fn between(value: impl Into<String>, start: f64, end: f64) -> Self {
+        GexfValue {
+            start: Some(start),
+            end: Some(end),
+            ..GexfValue::new(value)
+        }
+    }
+}
+
+/// Attribute values of one node or edge, keyed by attribute title.
+pub type GexfValues = BTreeMap<String, Vec<GexfValue>>;
+
+/// Node attribute of a GEXF graph.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GexfNode {
+    /// Label shown by Gephi.
+    pub label: String,
+    /// Values of the declared node attributes.
+    pub values: GexfValues,
+}
+
+impl GexfNode {
+    /// A node with the given label and no attribute values.
+    pub fn new(label: impl Into<String>) -> Self {
+        GexfNode {
+            label: label.into(),
+            values: GexfValues::new(),
+        }
+    }
+}
+
+/// Edge weight of a GEXF graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GexfEdge {
+    /// Edge weight, `1.0` when a file leaves it out.
+    pub weight: f64,
+    /// Values of the declared edge attributes.
+    pub values: GexfValues,
+}
+
+impl GexfEdge {
+    /// An edge with the given weight and no attribute values.
+    pub fn new(weight: f64) -> Self {
+        GexfEdge {
+            weight,
+            values: GexfValues::new(),
+        }
+    }
+}
+
+impl Default for GexfEdge {
+    fn default() -> Self {
+        GexfEdge::new(1.0)
+    }
+}
+
+/// Writes a graph to a GEXF 1.3 file that Gephi opens directly.
+///
+/// Node ids in the file are node indices, and nodes and edges are written in graph order.
+/// The graph is marked dynamic, with `double` time, when the schema declares a dynamic
+/// attribute.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` that fails with `InvalidInput`, before the file is
+///   created, if two attributes of a class share a title, a value names an undeclared
+///   attribute or does not parse as its declared type, a static attribute holds anything but
+///   one value without a time slice, or a time slice ends before it starts.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::{GexfAttribute, GexfEdge, GexfNode, GexfSchema, GexfType, GexfValue};
+/// use graphina::core::io::save_gexf;
+/// use graphina::core::types::Graph;
+///
+/// let mut g = Graph::<GexfNode, GexfEdge>::new();
+/// let mut alice = GexfNode::new("Alice");
+/// alice.values.insert("team".into(), vec![GexfValue::new("core")]);
+/// alice.values.insert(
+///     "commits".into(),
+///     vec![GexfValue::between("12", 2023.0, 2024.0), GexfValue::between("30", 2024.0, 2025.0)],
+/// );
+/// let a = g.add_node(alice);
+/// let b = g.add_node(GexfNode::new("Bob"));
+/// g.add_edge(a, b, GexfEdge::new(2.0));
+///
+/// let schema = GexfSchema {
+///     node_attributes: vec![
+///         GexfAttribute::new("team", GexfType::String),
+///         GexfAttribute::dynamic("commits", GexfType::Integer),
+///     ],
+///     edge_attributes: vec![],
+/// };
+/// save_gexf("team.gexf", &g, &schema).expect("Failed to save GEXF");
+/// ```
+pub fn save_gexf<Ty>(
+    path: &str,
+    graph: &BaseGraph<GexfNode, GexfEdge, Ty>,
+    schema: &GexfSchema,
+) -> std::io::Result<()>
+where
+    Ty: GraphConstructor<GexfNode, GexfEdge>,
+{
+    let node_ids = attribute_ids(&schema.node_attributes, "node")?;
+    let edge_ids = attribute_ids(&schema.edge_attributes, "edge")?;
+    for (node, attr) in graph.nodes() {
+        check_values(&attr.values, &schema.node_attributes, &node_ids, || {
+            format!("Node {}", node.index())
+        })?;
+    }
+    for (edge, (_, _, weight)) in graph.edges().enumerate() {
+        check_values(&weight.values, &schema.edge_attributes, &edge_ids, || {
+            format!("Edge {}", edge)
+        })?;
+    }
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    write_gexf(&mut writer, graph, schema, &node_ids, &edge_ids)?;
+    writer.flush()
+}
+
+/// Reads a GEXF file, such as one exported by Gephi, into a graph and its schema.
+///
+/// Nodes get new IDs in file order, so a file written by [`save_gexf`] from a graph without
+/// removed nodes comes back with the same IDs. Nested nodes are flattened, edge labels and
+/// per-edge `type` overrides are ignored, as are visualization attributes and spells. An
+/// attribute declared static whose values carry time slices is read as dynamic.
+///
+/// # Returns
+///
+/// * `Result<(BaseGraph<GexfNode, GexfEdge, Ty>, GexfSchema)>` - An `io::Result` that fails
+///   with `InvalidData` if the file is not well-formed, its default edge type does not match
+///   `Ty` (`mutual` counts as undirected), an edge or value refers to an unknown node or
+///   attribute, a value does not parse as its attribute's type, or a time bound or weight is
+///   not a number.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::load_gexf;
+/// use graphina::core::types::Undirected;
+///
+/// let (graph, schema) = load_gexf::<Undirected>("team.gexf").expect("Failed to load GEXF");
+/// ```
+pub fn load_gexf<Ty>(path: &str) -> std::io::Result<(BaseGraph<GexfNode, GexfEdge, Ty>, GexfSchema)>
+where
+    Ty: GraphConstructor<GexfNode, GexfEdge>,
+{
+    parse_gexf(&std::fs::read_to_string(path)?)
+}
+
+/// Writes a graph and its node and edge properties to a GEXF file, declaring one static
+/// attribute per property.
+///
+/// Node labels are the node attributes formatted with `Display`, and edge weights are the
+/// weights converted to `f64`. Integer properties are declared `long`, floats `double`,
+/// booleans `boolean`, and text `string`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::{load_gexf_properties, save_gexf_properties};
+/// use graphina::core::types::{Graph, Undirected};
+///
+/// let mut g = Graph::<&str, f64>::new();
+/// let a = g.add_node("Alice");
+/// g.set_node_prop(a, "age", 31).unwrap();
+/// save_gexf_properties("people.gexf", &g).expect("Failed to save GEXF");
+///
+/// let loaded = load_gexf_properties::<Undirected>("people.gexf").expect("Failed to load GEXF");
+/// ```
+pub fn save_gexf_properties<A, W, Ty>(
+    path: &str,
+    graph: &BaseGraph<A, W, Ty>,
+) -> std::io::Result<()>
+where
+    A: Display,
+    W: Clone + Into<f64>,
+    Ty: GraphConstructor<A, W> + GraphConstructor<GexfNode, GexfEdge>,
+{
+    let declare = |table: &PropertyTable| {
+        table
+            .columns()
+            .map(|(name, column)| {
+                let kind = match column.value_type() {
+                    ValueType::Bool => GexfType::Boolean,
+                    ValueType::Int => GexfType::Long,
+                    ValueType::Float => GexfType::Double,
+                    ValueType::Str => GexfType::String,
+                };
+                GexfAttribute::new(name, kind)
+            })
+            .collect()
+    };
+    let schema = GexfSchema {
+        node_attributes: declare(graph.node_props()),
+        edge_attributes: declare(graph.edge_props()),
+    };
+    let values = |table: &PropertyTable, index: usize| -> GexfValues {
+        table
+            .row(index)
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), vec![GexfValue::new(value.to_string())]))
+            .collect()
+    };
+    let mut gexf =
+        BaseGraph::<GexfNode, GexfEdge, Ty>::with_capacity(graph.node_count(), graph.edge_count());
+    let mut ids = HashMap::with_capacity(graph.node_count());
+    for (node, attr) in graph.nodes() {
+        let mut gexf_node = GexfNode::new(attr.to_string());
+        gexf_node.values = values(graph.node_props(), node.index());
+        ids.insert(node, gexf.add_node(gexf_node));
+    }
+    for (edge, u, v, weight) in graph.edges_with_ids() {
+        let mut gexf_edge = GexfEdge::new(weight.clone().into());
+        gexf_edge.values = values(graph.edge_props(), edge.index());
+        gexf.add_edge(ids[&u], ids[&v], gexf_edge);
+    }
+    save_gexf(path, &gexf, &schema)
+}
+
+/// Reads a GEXF file into a graph of node labels and edge weights, with every static
+/// attribute as a property.
+///
+/// `integer` and `long` attributes become integer properties, `float` and `double` become
+/// floats, `boolean` becomes booleans, and any other type becomes text. A node or edge without
+/// a value gets the attribute's default, if one is declared. Dynamic attributes are skipped,
+/// since a property holds one value; read them with [`load_gexf`].
+///
+/// # Returns
+///
+/// * `Result<BaseGraph<String, f64, Ty>>` - An `io::Result` that fails as [`load_gexf`] does,
+///   or with `InvalidData` if a declared default does not parse as its attribute's type.
+pub fn load_gexf_properties<Ty>(path: &str) -> std::io::Result<BaseGraph<String, f64, Ty>>
+where
+    Ty: GraphConstructor<GexfNode, GexfEdge> + GraphConstructor<String, f64>,
+{
+    let (gexf, schema) = load_gexf::<Ty>(path)?;
+    let mut graph =
+        BaseGraph::<String, f64, Ty>::with_capacity(gexf.node_count(), gexf.edge_count());
+    let mut ids = HashMap::with_capacity(gexf.node_count());
+    for (node, attr) in gexf.nodes() {
+        let id = graph.add_node(attr.label.clone());
+        ids.insert(node, id);
+        for (title, value) in static_values(&attr.values, &schema.node_attributes)? {
+            graph
+                .set_node_prop(id, title, value)
+                .map_err(|e| invalid_data(e.to_string()))?;
+        }
+    }
+    for (u, v, attr) in gexf.edges() {
+        let id = graph.add_edge(ids[&u], ids[&v], attr.weight);
+        for (title, value) in static_values(&attr.values, &schema.edge_attributes)? {
+            graph
+                .set_edge_prop(id, title, value)
+                .map_err(|e| invalid_data(e.to_string()))?;
+        }
+    }
+    Ok(graph)
+}
+
+/// Returns the property values of one node or edge: its value, or else the default, of each
+/// static attribute.
+fn static_values<'a>(
+    values: &'a GexfValues,
+    attributes: &'a [GexfAttribute],
+) -> std::io::Result<Vec<(&'a str, Value)>> {
+    let mut found = Vec::new();
+    for attribute in attributes.iter().filter(|attribute| !attribute.dynamic) {
+        let text = match values.get(&attribute.title).and_then(|list| list.first()) {
+            Some(value) => &value.value,
+            None => match &attribute.default {
+                Some(default) => default,
+                None => continue,
+            },
+        };
+        let bad = || {
+            invalid_data(format!(
+                "Value '{}' of attribute '{}' is not a {}",
+                text,
+                attribute.title,
+                attribute.kind.name()
+            ))
+        };
+        let value = match attribute.kind {
+            GexfType::Integer | GexfType::Long => {
+                Value::Int(text.trim().parse().map_err(|_| bad())?)
+            }
+            GexfType::Float | GexfType::Double => {
+                Value::Float(text.trim().parse().map_err(|_| bad())?)
+            }
+            GexfType::Boolean => Value::Bool(text.trim().parse().map_err(|_| bad())?),
+            GexfType::String | GexfType::Other(_) => Value::Str(text.clone()),
+        };
+        found.push((attribute.title.as_str(), value));
+    }
+    Ok(found)
+}
+
+/// Maps each attribute title to its position, which doubles as its id in the file.
+fn attribute_ids<'a>(
+    attributes: &'a [GexfAttribute],
+    class: &str,
+) -> std::io::Result<HashMap<&'a str, usize>> {
+    let mut ids = HashMap::with_capacity(attributes.len());
+    for (id, attribute) in attributes.iter().enumerate() {
+        if ids.insert(attribute.title.as_str(), id).is_some() {
+            return Err(invalid_input(format!(
+                "More than one {} attribute is titled '{}'",
+                class, attribute.title
+            )));
+        }
+    }
+    Ok(ids)
+}
+
+fn check_values(
+    values: &GexfValues,
+    attributes: &[GexfAttribute],
+    ids: &HashMap<&str, usize>,
+    item: impl Fn() -> String,
+) -> std::io::Result<()> {
+    for (title, list) in values {
+        let Some(&id) = ids.get(title.as_str()) else {
+            return Err(invalid_input(format!(
+                "{} sets undeclared attribute '{}'",
+                item(),
+                title
+            )));
+        };
+        let attribute = &attributes[id];
+        if !attribute.dynamic
+            && (list.len() != 1 || list[0].start.is_some() || list[0].end.is_some())
+        {
+            return Err(invalid_input(format!(
+                "{} must give static attribute '{}' exactly one value without a time slice",
+                item(),
+                title
+            )));
+        }
+        for value in list {
+            if !attribute.kind.accepts(&value.value) {
+                return Err(invalid_input(format!(
+                    "{} gives attribute '{}' the value '{}', which is not a valid {}",
+                    item(),
+                    title,
+                    value.value,
+                    attribute.kind.name()
+                )));
+            }
+            if let (Some(start), Some(end)) = (value.start, value.end) {
+                if start > end {
+                    return Err(invalid_input(format!(
+                        "{} gives attribute '{}' a time slice that ends before it starts",
+                        item(),
+                        title
+                    )));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_gexf<Wr, Ty>(
+    writer: &mut Wr,
+    graph: &BaseGraph<GexfNode, GexfEdge, Ty>,
+    schema: &GexfSchema,
+    node_ids: &HashMap<&str, usize>,
+    edge_ids: &HashMap<&str, usize>,
+) -> std::io::Result<()>
+where
+    Wr: Write,
+    Ty: GraphConstructor<GexfNode, GexfEdge>,
+{
+    let dynamic = schema
+        .node_attributes
+        .iter()
+        .chain(&schema.edge_attributes)
+        .any(|attribute| attribute.dynamic);
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(
+        writer,
+        r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#
+    )?;
+    writeln!(
+        writer,
+        r#"  <graph defaultedgetype="{}" mode="{}"{}>"#,
+        if graph.is_directed() {
+            "directed"
+        } else {
+            "undirected"
+        },
+        if dynamic { "dynamic" } else { "static" },
+        if dynamic {
+            r#" timeformat="double""#
+        } else {
+            ""
+        }
+    )?;
+    write_attributes(writer, "node", &schema.node_attributes)?;
+    write_attributes(writer, "edge", &schema.edge_attributes)?;
+
+    writeln!(writer, "    <nodes>")?;
+    for (node, attr) in graph.nodes() {
+        write!(
+            writer,
+            r#"      <node id="{}" label="{}""#,
+            node.index(),
+            escape(&attr.label)
+        )?;
+        write_values(writer, "node", &attr.values, node_ids)?;
+    }
+    writeln!(writer, "    </nodes>")?;
+    writeln!(writer, "    <edges>")?;
+    for (edge, (source, target, weight)) in graph.edges().enumerate() {
+        write!(
+            writer,
+            r#"      <edge id="{}" source="{}" target="{}" weight="{}""#,
+            edge,
+            source.index(),
+            target.index(),
+            weight.weight
+        )?;
+        write_values(writer, "edge", &weight.values, edge_ids)?;
+    }
+    writeln!(writer, "    </edges>")?;
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</gexf>")
+}
+
+/// Writes the static declarations of a class, then the dynamic ones.
+fn write_attributes<Wr: Write>(
+    writer: &mut Wr,
+    class: &str,
+    attributes: &[GexfAttribute],
+) -> std::io::Result<()> {
+    for (mode, dynamic) in [("static", false), ("dynamic", true)] {
+        if !attributes
+            .iter()
+            .any(|attribute| attribute.dynamic == dynamic)
+        {
+            continue;
+        }
+        writeln!(
+            writer,
+            r#"    <attributes class="{}" mode="{}">"#,
+            class, mode
+        )?;
+        for (id, attribute) in attributes.iter().enumerate() {
+            if attribute.dynamic != dynamic {
+                continue;
+            }
+            write!(
+                writer,
+                r#"      <attribute id="{}" title="{}" type="{}""#,
+                id,
+                escape(&attribute.title),
+                escape(attribute.kind.name())
+            )?;
+            match &attribute.default {
+                Some(default) => writeln!(
+                    writer,
+                    "><default>{}</default></attribute>",
+                    escape(default)
+                )?,
+                None => writeln!(writer, "/>")?,
+            }
+        }
+        writeln!(writer, "    </attributes>")?;
+    }
+    Ok(())
+}
+
+/// Finishes the open tag of a node or edge, with its values if it has any.
+fn write_values<Wr: Write>(
+    writer: &mut Wr,
+    element: &str,
+    values: &GexfValues,
+    ids: &HashMap<&str, usize>,
+) -> std::io::Result<()> {
+    if values.is_empty() {
+        return writeln!(writer, "/>");
+    }
+    writeln!(writer, ">")?;
+    writeln!(writer, "        <attvalues>")?;
+    for (title, list) in values {
+        for value in list {
+            write!(
+                writer,
+                r#"          <attvalue for="{}" value="{}""#,
+                ids[title.as_str()],
+                escape(&value.value)
+            )?;
+            if let Some(start) = value.start {
+                write!(writer, r#" start="{}""#, start)?;
+            }
+            if let Some(end) = value.end {
+                write!(writer, r#" end="{}""#, end)?;
+            }
+            writeln!(writer, "/>")?;
+        }
+    }
+    writeln!(writer, "        </attvalues>")?;
+    writeln!(writer, "      </{}>", element)
+}
+
+/// The node or edge whose element is open.
+enum Item {
+    Node(NodeId),
+    Edge(EdgeId),
+}
+
+fn parse_gexf<Ty>(text: &str) -> std::io::Result<(BaseGraph<GexfNode, GexfEdge, Ty>, GexfSchema)>
+where
+    Ty: GraphConstructor<GexfNode, GexfEdge>,
+{
+    let mut graph = BaseGraph::<GexfNode, GexfEdge, Ty>::new();
+    let mut schema = GexfSchema::default();
+    // Attribute ids of the file, per class, to positions in the schema.
+    let mut node_ids: HashMap<String, usize> = HashMap::new();
+    let mut edge_ids: HashMap<String, usize> = HashMap::new();
+    let mut nodes: HashMap<String, NodeId> = HashMap::new();
+    let mut edge_class = false;
+    let mut dynamic_class = false;
+    let mut items: Vec<Item> = Vec::new();
+    // The attribute whose `<default>` is open, as (is edge class, position).
+    let mut default_of: Option<(bool, usize)> = None;
+    let mut in_default = false;
+
+    let mut tokens = Tokens { rest: text };
+    while let Some(token) = tokens.next_token()? {
+        match token {
+            Token::Open {
+                name,
+                mut attributes,
+                closed,
+            } => match name {
+                "graph" => {
+                    let kind = attributes.get("defaultedgetype").map(String::as_str);
+                    let directed = kind == Some("directed");
+                    if directed != graph.is_directed() {
+                        return Err(invalid_data(format!(
+                            "GEXF graph has default edge type '{}', which does not match a {} graph",
+                            kind.unwrap_or("undirected"),
+                            if graph.is_directed() {
+                                "directed"
+                            } else {
+                                "undirected"
+                            }
+                        )));
+                    }
+                }
+                "attributes" => {
+                    edge_class = attributes.get("class").map(String::as_str) == Some("edge");
+                    dynamic_class = attributes.get("mode").map(String::as_str) == Some("dynamic");
+                }
+                "attribute" => {
+                    let id = required(&mut attributes, "id", "attribute")?;
+                    let title = attributes.remove("title").unwrap_or_else(|| id.clone());
+                    let kind = GexfType::from_name(
+                        attributes.get("type").map_or("string", String::as_str),
+                    );
+                    let mut attribute = GexfAttribute::new(title, kind);
+                    attribute.dynamic = dynamic_class
+                        || attributes.get("mode").map(String::as_str) == Some("dynamic");
+                    let (list, ids) = if edge_class {
+                        (&mut schema.edge_attributes, &mut edge_ids)
+                    } else {
+                        (&mut schema.node_attributes, &mut node_ids)
+                    };
+                    ids.insert(id, list.len());
+                    default_of = Some((edge_class, list.len()));
+                    list.push(attribute);
+                }
+                "default" => in_default = !closed,
+                "node" => {
+                    let id = required(&mut attributes, "id", "node")?;
+                    let label = attributes.remove("label").unwrap_or_else(|| id.clone());
+                    let node = graph.add_node(GexfNode::new(label));
+                    nodes.insert(id, node);
+                    if !closed {
+                        items.push(Item::Node(node));
+                    }
+                }
+                "edge" => {
+                    let mut endpoint = |key: &str| -> std::io::Result<NodeId> {
+                        let id = required(&mut attributes, key, "edge")?;
+                        nodes.get(&id).copied().ok_or_else(|| {
+                            invalid_data(format!("GEXF edge refers to unknown node '{}'", id))
+                        })
+                    };
+                    let (source, target) = (endpoint("source")?, endpoint("target")?);
+                    let weight = match attributes.get("weight") {
+                        Some(weight) => weight.trim().parse::<f64>().map_err(|_| {
+                            invalid_data(format!("GEXF edge weight '{}' is not a number", weight))
+                        })?,
+                        None => 1.0,
+                    };
+                    let edge = graph.add_edge(source, target, GexfEdge::new(weight));
+                    if !closed {
+                        items.push(Item::Edge(edge));
+                    }
+                }
+                "attvalue" => {
+                    let key = attributes
+                        .remove("for")
+                        .or_else(|| attributes.remove("id"))
+                        .ok_or_else(|| invalid_data("GEXF attvalue has no 'for' attribute"))?;
+                    let value = required(&mut attributes, "value", "attvalue")?;
+                    let time = |keys: &[&str]| -> std::io::Result<Option<f64>> {
+                        let Some(raw) = keys.iter().find_map(|key| attributes.get(*key)) else {
+                            return Ok(None);
+                        };
+                        raw.trim().parse::<f64>().map(Some).map_err(|_| {
+                            invalid_data(format!("GEXF time '{}' is not a number", raw))
+                        })
+                    };
+                    let start = time(&["start", "startopen", "timestamp"])?;
+                    let end = time(&["end", "endopen", "timestamp"])?;
+                    let (values, attribute) = match items.last() {
+                        Some(Item::Node(node)) => (
+                            graph.node_attr_mut(*node).map(|node| &mut node.values),
+                            node_ids
+                                .get(&key)
+                                .map(|&id| &mut schema.node_attributes[id]),
+                        ),
+                        Some(Item::Edge(edge)) => (
+                            graph.edge_weight_mut(*edge).map(|edge| &mut edge.values),
+                            edge_ids
+                                .get(&key)
+                                .map(|&id| &mut schema.edge_attributes[id]),
+                        ),
+                        None => {
+                            return Err(invalid_data(
+                                "GEXF attvalue appears outside a node or an edge",
+                            ));
+                        }
+                    };
+                    let (Some(values), Some(attribute)) = (values, attribute) else {
+                        return Err(invalid_data(format!(
+                            "GEXF attvalue refers to unknown attribute '{}'",
+                            key
+                        )));
+                    };
+                    if !attribute.kind.accepts(&value) {
+                        return Err(invalid_data(format!(
+                            "GEXF value '{}' of attribute '{}' is not a valid {}",
+                            value,
+                            attribute.title,
+                            attribute.kind.name()
+                        )));
+                    }
+                    let list = values.entry(attribute.title.clone()).or_default();
+                    list.push(GexfValue { value, start, end });
+                    if list.len() > 1 || start.is_some() || end.is_some() {
+                        attribute.dynamic = true;
+                    }
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name {
+                "node" | "edge" => {
+                    items.pop();
+                }
+                "default" => in_default = false,
+                _ => {}
+            },
+            Token::Text(text) => {
+                if let (true, Some((edge, id))) = (in_default, default_of) {
+                    let list = if edge {
+                        &mut schema.edge_attributes
+                    } else {
+                        &mut schema.node_attributes
+                    };
+                    list[id].default = Some(text);
+                }
+            }
+        }
+    }
+    Ok((graph, schema))
+}
+
+fn required(
+    attributes: &mut HashMap<String, String>,
+    key: &str,
+    element: &str,
+) -> std::io::Result<String> {
+    attributes
+        .remove(key)
+        .ok_or_else(|| invalid_data(format!("GEXF {} has no '{}' attribute", element, key)))
+}
+
+enum Token<'a> {
+    Open {
+        name: &'a str,
+        attributes: HashMap<String, String>,
+        closed: bool,
+    },
+    Close(&'a str),
+    Text(String),
+}
+
+/// A minimal XML tokenizer: enough for GEXF, which has no mixed content or entity
+/// declarations. Comments, processing instructions, and doctypes are skipped.
+struct Tokens<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Tokens<'a> {
+    fn next_token(&mut self) -> std::io::Result<Option<Token<'a>>> {
+        loop {
+            if self.rest.is_empty() {
+                return Ok(None);
+            }
+            if let Some(rest) = self.rest.strip_prefix("<!--") {
+                self.rest = skip_past(rest, "-->")?;
+            } else if let Some(rest) = self.rest.strip_prefix("<![CDATA[") {
+                let end = find(rest, "]]>")?;
+                self.rest = &rest[end + 3..];
+                return Ok(Some(Token::Text(rest[..end].to_string())));
+            } else if let Some(rest) = self.rest.strip_prefix("<?") {
+                self.rest = skip_past(rest, "?>")?;
+            } else if let Some(rest) = self.rest.strip_prefix("<!") {
+                self.rest = skip_past(rest, ">")?;
+            } else if let Some(rest) = self.rest.strip_prefix("</") {
+                let end = find(rest, ">")?;
+                self.rest = &rest[end + 1..];
+                return Ok(Some(Token::Close(rest[..end].trim())));
+            } else if let Some(rest) = self.rest.strip_prefix('<') {
+                return self.open_tag(rest).map(Some);
+            } else {
+                let end = self.rest.find('<').unwrap_or(self.rest.len());
+                let text = self.rest[..end].trim();
+                self.rest = &self.rest[end..];
+                if !text.is_empty() {
+                    return Ok(Some(Token::Text(unescape(text))));
+                }
+            }
+        }
+    }
+
+    fn open_tag(&mut self, rest: &'a str) -> std::io::Result<Token<'a>> {
+        let name_end = rest
+            .find(|c: char| c.is_whitespace() || c == '/' || c == '>')
+            .ok_or_else(|| malformed("unterminated tag"))?;
+        let name = &rest[..name_end];
+        let mut rest = &rest[name_end..];
+        let mut attributes = HashMap::new();
+        loop {
+            rest = rest.trim_start();
+            if let Some(after) = rest.strip_prefix("/>") {
+                self.rest = after;
+                return Ok(Token::Open {
+                    name,
+                    attributes,
+                    closed: true,
+                });
+            }
+            if let Some(after) = rest.strip_prefix('>') {
+                self.rest = after;
+                return Ok(Token::Open {
+                    name,
+                    attributes,
+                    closed: false,
+                });
+            }
+            let eq = rest
+                .find('=')
+                .ok_or_else(|| malformed(&format!("attribute without a value in <{}>", name)))?;
+            let key = rest[..eq].trim();
+            let after = rest[eq + 1..].trim_start();
+            let quote = after
+                .chars()
+                .next()
+                .filter(|&c| c == '"' || c == '\'')
+                .ok_or_else(|| malformed(&format!("unquoted attribute value in <{}>", name)))?;
+            let value_end = after[1..]
+                .find(quote)
+                .ok_or_else(|| malformed(&format!("unterminated attribute value in <{}>", name)))?;
+            attributes.insert(key.to_string(), unescape(&after[1..1 + value_end]));
+            rest = &after[value_end + 2..];
+        }
+    }
+}
+
+fn find(text: &str, pattern: &str) -> std::io::Result<usize> {
+    text.find(pattern)
+        .ok_or_else(|| malformed(&format!("missing '{}'", pattern)))
+}
+
+fn skip_past<'a>(text: &'a str, pattern: &str) -> std::io::Result<&'a str> {
+    Ok(&text[find(text, pattern)? + pattern.len()..])
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Replaces the predefined and numeric character references; any other `&` is kept as is.
+fn unescape(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(amp) = rest.find('&') {
+        unescaped.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').and_then(|semi| {
+            let entity = &rest[1..semi];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity
+                    .strip_prefix("#x")
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse::<u32>))
+                    .and_then(|code| code.ok())
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, semi))
+        });
+        match decoded {
+            Some((c, semi)) => {
+                unescaped.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    unescaped.push_str(rest);
+    unescaped
+}
+
+fn malformed(detail: &str) -> Error {
+    invalid_data(format!("Malformed GEXF: {}", detail))
+}
+
+fn invalid_data(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidData, message.into())
+}
+
+fn invalid_input(message: impl Into<String>) -> Error {
+    Error::new(ErrorKind::InvalidInput, message.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Directed, Graph, Undirected};
+    use std::fs;
+
+    #[test]
+    fn test_gexf_properties_round_trip() {
+        let mut g = Graph::<&str, f64>::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let ab = g.add_edge(a, b, 1.5);
+        g.set_node_prop(a, "age", 31).unwrap();
+        g.set_node_prop(a, "tag", "x & y").unwrap();
+        g.set_node_prop(b, "active", false).unwrap();
+        g.set_edge_prop(ab, "since", 2020.25).unwrap();
+
+        let path = "tmp_graph_properties.gexf";
+        save_gexf_properties(path, &g).expect("save_gexf_properties failed");
+        let loaded = load_gexf_properties::<Undirected>(path).expect("load failed");
+        assert_eq!(loaded.node_attr(a), Some(&"a".to_string()));
+        assert_eq!(loaded.node_props().len(), 3);
+        for (node, name) in [(a, "age"), (a, "tag"), (b, "active"), (b, "age")] {
+            assert_eq!(loaded.node_prop(node, name), g.node_prop(node, name));
+        }
+        assert_eq!(loaded.edge_weight(ab), Some(&1.5));
+        assert_eq!(loaded.edge_prop(ab, "since"), Some(Value::Float(2020.25)));
+
+        // Defaults fill in missing values, and dynamic attributes are left out.
+        fs::write(
+            path,
+            r#"<gexf version="1.3"><graph defaultedgetype="directed" mode="dynamic">
+  <attributes class="node">
+    <attribute id="0" title="team" type="string"><default>none</default></attribute>
+    <attribute id="1" title="load" type="double" mode="dynamic"/>
+  </attributes>
+  <nodes>
+    <node id="a" label="A"><attvalues><attvalue for="0" value="core"/>
+      <attvalue for="1" value="0.5" start="1" end="2"/></attvalues></node>
+    <node id="b" label="B"/>
+  </nodes>
+  <edges/>
+</graph></gexf>"#,
+        )
+        .unwrap();
+        let loaded = load_gexf_properties::<Directed>(path);
+        fs::remove_file(path).expect("Failed to remove temporary file");
+        let loaded = loaded.expect("load failed");
+        let team: Vec<_> = loaded
+            .node_ids()
+            .map(|n| loaded.node_prop(n, "team"))
+            .collect();
+        assert_eq!(team, [Some("core".into()), Some("none".into())]);
+        assert!(loaded.node_props().column("load").is_none());
+    }
+
+    #[test]
+    fn test_gexf_round_trip_and_gephi_input() {
+        let mut g = Digraph::<GexfNode, GexfEdge>::new();
+        let mut a = GexfNode::new("A & \"B\"");
+        a.values
+            .insert("group".into(), vec![GexfValue::new("<core>")]);
+        a.values.insert(
+            "score".into(),
+            vec![
+                GexfValue::between("0.5", 1.0, 2.0),
+                GexfValue {
+                    value: "0.75".into(),
+                    start: Some(2.0),
+                    end: None,
+                },
+            ],
+        );
+        let a = g.add_node(a);
+        let b = g.add_node(GexfNode::new("b"));
+        let mut edge = GexfEdge::new(2.5);
+        edge.values
+            .insert("kind".into(), vec![GexfValue::new("true")]);
+        g.add_edge(a, b, edge);
+        g.add_edge(b, a, GexfEdge::default());
+        let mut schema = GexfSchema {
+            node_attributes: vec![
+                GexfAttribute::new("group", GexfType::String),
+                GexfAttribute::dynamic("score", GexfType::Double),
+            ],
+            edge_attributes: vec![GexfAttribute::new("kind", GexfType::Boolean)],
+        };
+        schema.node_attributes[0].default = Some("none".into());
+
+        let path = "tmp_graph.gexf";
+        save_gexf(path, &g, &schema).expect("save_gexf failed");
+        let loaded = load_gexf::<Directed>(path);
+        fs::remove_file(path).expect("Failed to remove temporary file");
+        let (loaded, loaded_schema) = loaded.expect("load_gexf failed");
+        assert_eq!(loaded_schema, schema);
+        assert_eq!(loaded.node_attr(a), g.node_attr(a));
+        assert_eq!(loaded.node_attr(b), g.node_attr(b));
+        let edges: Vec<_> = loaded.edges().map(|(u, v, w)| (u, v, w.clone())).collect();
+        let expected: Vec<_> = g.edges().map(|(u, v, w)| (u, v, w.clone())).collect();
+        assert_eq!(edges, expected);
+
+        // Undeclared attributes and values of the wrong type are rejected before writing.
+        schema.edge_attributes[0].kind = GexfType::Integer;
+        let err = save_gexf(path, &g, &schema).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidInput);
+        assert!(fs::metadata(path).is_err());
+
+        let gephi = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!-- exported by Gephi -->
+<gexf xmlns="http://gexf.net/1.2" xmlns:viz="http://gexf.net/1.2/viz" version="1.2">
+  <graph mode="static" defaultedgetype="undirected">
+    <attributes class="node">
+      <attribute id="modularity_class" title="Modularity Class" type="integer">
+        <default>0</default>
+      </attribute>
+    </attributes>
+    <nodes>
+      <node id="n1">
+        <attvalues><attvalue for="modularity_class" value="3"></attvalue></attvalues>
+        <viz:color r="10" g="20" b="30"/>
+      </node>
+      <node id="n2" label='Caf&#233;'/>
+    </nodes>
+    <edges>
+      <edge id="0" source="n1" target="n2" label="ignored"/>
+    </edges>
+  </graph>
+</gexf>"#;
+        let (loaded, schema) = parse_gexf::<Undirected>(gephi).unwrap();
+        assert_eq!(schema.node_attributes[0].title, "Modularity Class");
+        assert_eq!(schema.node_attributes[0].default.as_deref(), Some("0"));
+        let labels: Vec<_> = loaded.nodes().map(|(_, n)| n.label.clone()).collect();
+        assert_eq!(labels, vec!["n1", "Café"]);
+        let first = loaded.nodes().next().unwrap().1;
+        assert_eq!(first.values["Modularity Class"], vec![GexfValue::new("3")]);
+        assert_eq!(loaded.edges().next().unwrap().2.weight, 1.0);
+
+        let err = parse_gexf::<Directed>(gephi).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        let unknown = gephi.replace(r#"target="n2""#, r#"target="n9""#);
+        assert!(parse_gexf::<Undirected>(&unknown).is_err());
+    }
+}
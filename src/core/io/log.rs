@@ -0,0 +1,380 @@
+//! Append-only change logs.
+//!
+//! A [`GraphLog`] persists a graph as the sequence of changes that built it, one JSON object
+//! per line, so a long-running process pays only for each change as it happens instead of
+//! rewriting a snapshot. Replaying the log on an empty graph rebuilds the same graph with the
+//! same node and edge IDs, because a graph hands out IDs deterministically for a given sequence
+//! of additions and removals.
+
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Error, ErrorKind, Read, Seek, Write};
+use std::marker::PhantomData;
+
+/// One change to a graph, as stored in a [`GraphLog`].
+///
+/// Nodes and edges are referred to by `NodeId::index()` and `EdgeId::index()` in the graph the
+/// change was made to. Additions record the ID the graph gave out, which replay checks, so a
+/// log that does not match its graph fails instead of silently rebuilding something else.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum GraphEvent<A, W> {
+    /// A node was added with ID `node`.
+    AddNode { node: usize, attr: A },
+    /// Node `node` got a new attribute.
+    UpdateNode { node: usize, attr: A },
+    /// Node `node` was removed, with its edges.
+    RemoveNode { node: usize },
+    /// An edge was added with ID `edge`.
+    AddEdge {
+        edge: usize,
+        source: usize,
+        target: usize,
+        weight: W,
+    },
+    /// Edge `edge` got a new weight.
+    UpdateEdge { edge: usize, weight: W },
+    /// Edge `edge` was removed.
+    RemoveEdge { edge: usize },
+}
+
+impl<A, W> GraphEvent<A, W> {
+    /// Records adding `node`; call it with the ID `add_node` returned.
+    pub fn add_node(node: NodeId, attr: A) -> Self {
+        GraphEvent::AddNode {
+            node: node.index(),
+            attr,
+        }
+    }
+
+    /// Records adding `edge` from `source` to `target`; call it with the ID `add_edge`
+    /// returned.
+    pub fn add_edge(edge: EdgeId, source: NodeId, target: NodeId, weight: W) -> Self {
+        GraphEvent::AddEdge {
+            edge: edge.index(),
+            source: source.index(),
+            target: target.index(),
+            weight,
+        }
+    }
+
+    /// Makes this change to `graph`.
+    ///
+    /// # Errors
+    /// Returns `InvalidData` if the change refers to a node or edge that `graph` does not
+    /// have, or if an addition gets a different ID than the one recorded.
+    pub fn apply<Ty>(self, graph: &mut BaseGraph<A, W, Ty>) -> std::io::Result<()>
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let invalid = |message: String| Error::new(ErrorKind::InvalidData, message);
+        let existing = |graph: &BaseGraph<A, W, Ty>, node: usize| {
+            let id = NodeId::new(NodeIndex::new(node));
+            if graph.contains_node(id) {
+                Ok(id)
+            } else {
+                Err(invalid(format!("Node {node} does not exist")))
+            }
+        };
+        match self {
+            GraphEvent::AddNode { node, attr } => {
+                let id = graph.add_node(attr);
+                if id.index() != node {
+                    return Err(invalid(format!(
+                        "Node {node} was logged, but the graph gave out {}",
+                        id.index()
+                    )));
+                }
+            }
+            GraphEvent::UpdateNode { node, attr } => {
+                let id = existing(graph, node)?;
+                graph.update_node(id, attr);
+            }
+            GraphEvent::RemoveNode { node } => {
+                let id = existing(graph, node)?;
+                graph.remove_node(id);
+            }
+            GraphEvent::AddEdge {
+                edge,
+                source,
+                target,
+                weight,
+            } => {
+                let (source, target) = (existing(graph, source)?, existing(graph, target)?);
+                let id = graph.add_edge(source, target, weight);
+                if id.index() != edge {
+                    return Err(invalid(format!(
+                        "Edge {edge} was logged, but the graph gave out {}",
+                        id.index()
+                    )));
+                }
+            }
+            GraphEvent::UpdateEdge { edge, weight } => {
+                let id = EdgeId::new(EdgeIndex::new(edge));
+                match graph.edge_weight_mut(id) {
+                    Some(slot) => *slot = weight,
+                    None => return Err(invalid(format!("Edge {edge} does not exist"))),
+                }
+            }
+            GraphEvent::RemoveEdge { edge } => {
+                let id = EdgeId::new(EdgeIndex::new(edge));
+                if graph.remove_edge(id).is_none() {
+                    return Err(invalid(format!("Edge {edge} does not exist")));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// An append-only file of [`GraphEvent`]s, one JSON object per line, for graphs with node
+/// attributes `A` and edge weights `W`.
+///
+/// Every [`append`](Self::append) writes and flushes one complete line, so after a crash the
+/// file holds every change appended before it, plus at most one partly written line, which
+/// [`open`](Self::open) cuts off and [`replay`](Self::replay) ignores. Flushing hands the line
+/// to the operating system; call [`sync`](Self::sync) to also wait for the disk.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::{GraphEvent, GraphLog};
+/// use graphina::core::types::Graph;
+///
+/// let mut graph = Graph::<String, f64>::new();
+/// let mut log = GraphLog::create("changes.jsonl").unwrap();
+///
+/// let a = graph.add_node("a".to_string());
+/// log.append(&GraphEvent::add_node(a, "a".to_string())).unwrap();
+/// let b = graph.add_node("b".to_string());
+/// log.append(&GraphEvent::add_node(b, "b".to_string())).unwrap();
+/// let ab = graph.add_edge(a, b, 2.5);
+/// log.append(&GraphEvent::add_edge(ab, a, b, 2.5)).unwrap();
+///
+/// // Later, or after a restart:
+/// let log = GraphLog::<String, f64>::open("changes.jsonl").unwrap();
+/// let recovered: Graph<String, f64> = log.replay().unwrap();
+/// assert_eq!(recovered.edge_weight(ab), Some(&2.5));
+/// ```
+#[derive(Debug)]
+pub struct GraphLog<A, W> {
+    path: String,
+    file: File,
+    events: usize,
+    _events: PhantomData<fn() -> (A, W)>,
+}
+
+impl<A, W> GraphLog<A, W> {
+    /// Creates an empty log at `path`, replacing any file there.
+    pub fn create(path: &str) -> std::io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(GraphLog {
+            path: path.to_string(),
+            file,
+            events: 0,
+            _events: PhantomData,
+        })
+    }
+
+    /// Opens the log at `path` to append to it, creating it if it does not exist.
+    ///
+    /// A partly written last line, left by a crash during [`append`](Self::append), is cut
+    /// off so the next event starts on a line of its own.
+    pub fn open(path: &str) -> std::io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .read(true)
+            .write(true)
+            .open(path)?;
+        let mut contents = Vec::new();
+        file.read_to_end(&mut contents)?;
+        let complete = contents
+            .iter()
+            .rposition(|&byte| byte == b'\n')
+            .map_or(0, |last| last + 1);
+        if complete < contents.len() {
+            file.set_len(complete as u64)?;
+        }
+        file.seek(std::io::SeekFrom::Start(complete as u64))?;
+        let events = contents[..complete]
+            .split(|&byte| byte == b'\n')
+            .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+            .count();
+        Ok(GraphLog {
+            path: path.to_string(),
+            file,
+            events,
+            _events: PhantomData,
+        })
+    }
+
+    /// Appends `event` as one line and flushes it.
+    ///
+    /// The event is not checked against any graph; [`replay`](Self::replay) reports a log
+    /// whose events do not fit together.
+    pub fn append(&mut self, event: &GraphEvent<A, W>) -> std::io::Result<()>
+    where
+        A: Serialize,
+        W: Serialize,
+    {
+        let mut line = serde_json::to_vec(event)?;
+        line.push(b'\n');
+        // One write per line, so a crash cannot interleave two events.
+        self.file.write_all(&line)?;
+        self.file.flush()?;
+        self.events += 1;
+        Ok(())
+    }
+
+    /// Waits until every appended event has reached the disk.
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        self.file.sync_data()
+    }
+
+    /// Returns the number of events in the log, including those appended before it was opened.
+    pub fn len(&self) -> usize {
+        self.events
+    }
+
+    /// Returns `true` if the log holds no events.
+    pub fn is_empty(&self) -> bool {
+        self.events == 0
+    }
+
+    /// Rebuilds the graph by applying every event, in order, to an empty graph.
+    ///
+    /// # Errors
+    /// Fails with `InvalidData`, naming the line, if a complete line is not a valid event or an
+    /// event does not fit the graph built so far. A final line without a newline is ignored as
+    /// an interrupted write.
+    pub fn replay<Ty>(&self) -> std::io::Result<BaseGraph<A, W, Ty>>
+    where
+        A: DeserializeOwned,
+        W: DeserializeOwned,
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut graph = BaseGraph::new();
+        self.replay_into(&mut graph)?;
+        Ok(graph)
+    }
+
+    /// Applies every event, in order, to `graph`, for a log that was started on a copy of
+    /// `graph` rather than on an empty one. Returns the number of events applied.
+    ///
+    /// The events refer to IDs, so `graph` must have the same IDs as the graph the log was
+    /// started on, which holds for a snapshot reloaded from `save_binary` or `save_json` only
+    /// if no node or edge had been removed before it was saved.
+    ///
+    /// # Errors
+    /// As for [`replay`](Self::replay). The events before the failing one stay applied.
+    pub fn replay_into<Ty>(&self, graph: &mut BaseGraph<A, W, Ty>) -> std::io::Result<usize>
+    where
+        A: DeserializeOwned,
+        W: DeserializeOwned,
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut reader = BufReader::new(File::open(&self.path)?);
+        let mut line = String::new();
+        let (mut number, mut applied) = (0, 0);
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 || !line.ends_with('\n') {
+                return Ok(applied);
+            }
+            number += 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let at_line = |error: Error| {
+                Error::new(ErrorKind::InvalidData, format!("Line {number}: {error}"))
+            };
+            let event: GraphEvent<A, W> =
+                serde_json::from_str(&line).map_err(|e| at_line(e.into()))?;
+            event.apply(graph).map_err(at_line)?;
+            applied += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Directed};
+
+    #[test]
+    fn test_graph_log_round_trip_and_recovery() {
+        let path = std::env::temp_dir().join(format!("graphina_log_{}.jsonl", std::process::id()));
+        let path = path.to_str().unwrap();
+        let mut g = Digraph::<String, f64>::new();
+        let mut log = GraphLog::create(path).unwrap();
+        let mut record = |event: GraphEvent<String, f64>| log.append(&event).unwrap();
+
+        let n: Vec<NodeId> = (0..4)
+            .map(|i| {
+                let id = g.add_node(format!("n{i}"));
+                record(GraphEvent::add_node(id, format!("n{i}")));
+                id
+            })
+            .collect();
+        for (i, (u, v)) in [(0, 1), (1, 2), (2, 3)].into_iter().enumerate() {
+            let edge = g.add_edge(n[u], n[v], i as f64);
+            record(GraphEvent::add_edge(edge, n[u], n[v], i as f64));
+        }
+        g.remove_node(n[1]);
+        record(GraphEvent::RemoveNode { node: n[1].index() });
+        // The freed slots are handed out again, and replay must reuse them the same way.
+        let again = g.add_node("again".to_string());
+        record(GraphEvent::add_node(again, "again".to_string()));
+        let back = g.add_edge(n[3], again, 4.0);
+        record(GraphEvent::add_edge(back, n[3], again, 4.0));
+        let last = g.edge_ids().find(|&e| e != back).unwrap();
+        *g.edge_weight_mut(last).unwrap() = 9.0;
+        record(GraphEvent::UpdateEdge {
+            edge: last.index(),
+            weight: 9.0,
+        });
+        g.update_node(n[0], "first".to_string());
+        record(GraphEvent::UpdateNode {
+            node: n[0].index(),
+            attr: "first".to_string(),
+        });
+        g.remove_edge(back);
+        record(GraphEvent::RemoveEdge { edge: back.index() });
+
+        // A torn final write is ignored by replay and cut off by open.
+        OpenOptions::new()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(br#"{"op":"add_no"#)
+            .unwrap();
+        let replayed: Digraph<String, f64> = GraphLog::open(path).unwrap().replay().unwrap();
+        let summary = |g: &Digraph<String, f64>| {
+            let nodes: Vec<_> = g.nodes().map(|(id, a)| (id, a.clone())).collect();
+            let edges: Vec<_> = g
+                .edges_with_ids()
+                .map(|(e, u, v, &w)| (e, u, v, w))
+                .collect();
+            (nodes, edges)
+        };
+        assert_eq!(summary(&replayed), summary(&g));
+
+        let mut reopened = GraphLog::<String, f64>::open(path).unwrap();
+        assert_eq!(reopened.len(), 13);
+        reopened
+            .append(&GraphEvent::RemoveEdge { edge: 40 })
+            .unwrap();
+        let error = reopened.replay::<Directed>().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        assert!(error.to_string().starts_with("Line 14:"), "{error}");
+        std::fs::remove_file(path).unwrap();
+    }
+}
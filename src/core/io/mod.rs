@@ -0,0 +1,1123 @@
+/*!
+# Input/Output (I/O) Routines
+
+Read and write graph files.
+Specifically, it supports:
+
+- **Edge List I/O:**
+  - Reading an edge list from a file into a graph.
+  - Streaming a large edge list in chunks, with optional parallel parsing, progress reports, and a
+    policy for malformed lines.
+  - Writing a graph's edge list to a file.
+
+- **Adjacency List I/O:**
+  - Reading an adjacency list from a file into a graph.
+  - Writing a graph's adjacency list to a file.
+
+- **GEXF I/O:**
+  - Saving and loading graphs in Gephi's GEXF format, with declared node and edge attributes and
+    optional dynamic (time-sliced) values.
+  - Saving and loading node and edge properties as static GEXF attributes.
+
+- **Change Logs:**
+  - Appending each change to a graph to a JSON-lines log as it happens, and replaying the log to
+    rebuild the graph after a restart.
+
+- **Analysis Result I/O:**
+  - Saving and loading per-node scores (`NodeMap<f64>`) as two-column CSV.
+  - Saving and loading community partitions as JSON objects mapping nodes to communities.
+
+Functions use the core graph abstractions defined in `graphina::core::types` and report errors using
+`graphina::core::error::GraphinaError` where appropriate.
+
+Analysis results are keyed by `NodeId::index()`, so they line up with the graph they were computed on
+(or with a copy of it that preserves node indices).
+
+The input files support comments (lines or inline comments beginning with `#` are ignored)
+and allow for optional weight specifications. If a weight is missing, a default of `1.0` is used.
+*/
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use petgraph::EdgeType;
+use petgraph::graph::NodeIndex;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{self, Display};
+use std::fs::File;
+use std::hash::Hash;
+use std::io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Write};
+use std::str::FromStr;
+use std::sync::Arc;
+
+mod gexf;
+mod log;
+
+pub(crate) use gexf::escape as escape_xml;
+pub use gexf::{
+    GexfAttribute, GexfEdge, GexfNode, GexfSchema, GexfType, GexfValue, GexfValues, load_gexf,
+    load_gexf_properties, save_gexf, save_gexf_properties,
+};
+pub use log::{GraphEvent, GraphLog};
+
+/// Reads an edge list from a file and populates the given graph.
+///
+/// Lines containing a `#` are treated as comments. Everything after the first `#` in a line is ignored.
+/// Each non-comment, non-empty line must contain at least two tokens (source and target).
+/// An optional third token represents the weight (default is `1.0`).
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the edge list file.
+/// * `graph` - A mutable reference to the graph to be populated.
+/// * `sep` - A character that separates the values in the edge list.
+///
+/// # Type Parameters
+///
+/// * `W` - The weight type of the graph, which must implement `FromStr` and `Copy`.
+/// * `Ty` - The edge type of the graph, which must implement `GraphConstructor`.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` indicating success or failure. Failure can occur due to I/O errors,
+///   or if any token fails to parse; in such cases, the error message will include details suitable for debugging.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::types::{Graph, NodeId};
+/// use graphina::core::io::read_edge_list;
+///
+/// let mut graph = Graph::<i32, f32>::new();
+/// // Assume "edges.txt" exists and follows the correct format.
+/// read_edge_list("edges.txt", &mut graph, ',').expect("Failed to read edge list");
+/// ```
+pub fn read_edge_list<W, Ty>(
+    path: &str,
+    graph: &mut BaseGraph<i32, W, Ty>,
+    sep: char,
+) -> std::io::Result<()>
+where
+    W: Copy + std::str::FromStr,
+    <W as std::str::FromStr>::Err: std::fmt::Display + std::fmt::Debug,
+    Ty: GraphConstructor<i32, W>,
+{
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut node_map = HashMap::new();
+    for line in reader.lines() {
+        let mut line = line?;
+        // Remove comments: if '#' is present, only take text before it.
+        if let Some(idx) = line.find('#') {
+            line.truncate(idx);
+        }
+        // Skip if the line is empty after removing comments.
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line.trim().split(sep).map(|s| s.trim()).collect();
+        if tokens.len() < 2 {
+            continue;
+        }
+        let src_val: i32 = tokens[0].parse().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing source value '{}': {}", tokens[0], e),
+            )
+        })?;
+        let tgt_val: i32 = tokens[1].parse().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing target value '{}': {}", tokens[1], e),
+            )
+        })?;
+        let weight: W = if tokens.len() >= 3 {
+            tokens[2].parse().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error parsing weight '{}': {}", tokens[2], e),
+                )
+            })?
+        } else {
+            "1.0".parse::<W>().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error parsing default weight '1.0': {}", e),
+                )
+            })?
+        };
+        let src_node = *node_map
+            .entry(src_val)
+            .or_insert_with(|| graph.add_node(src_val));
+        let tgt_node = *node_map
+            .entry(tgt_val)
+            .or_insert_with(|| graph.add_node(tgt_val));
+        graph.add_edge(src_node, tgt_node, weight);
+    }
+    Ok(())
+}
+
+/// How [`read_edge_list_with`] treats a line it cannot parse.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum MalformedLines {
+    /// Stop with an `InvalidData` error naming the line.
+    #[default]
+    Fail,
+    /// Skip the line and count it in [`EdgeListReport::skipped`].
+    Skip,
+    /// Skip the line, count it, and record why in [`EdgeListReport::errors`].
+    Collect,
+}
+
+/// How far a streaming read has got, reported after each chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EdgeListProgress {
+    /// Bytes read so far.
+    pub bytes: u64,
+    /// Lines read so far.
+    pub lines: usize,
+    /// Edges added so far.
+    pub edges: usize,
+}
+
+/// A callback that receives [`EdgeListProgress`] reports.
+pub type ProgressFn = Arc<dyn Fn(&EdgeListProgress) + Send + Sync>;
+
+/// Format and behavior of [`read_edge_list_with`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::io::{EdgeListOptions, MalformedLines};
+///
+/// let options = EdgeListOptions::new()
+///     .with_separator(',')
+///     .with_malformed(MalformedLines::Collect)
+///     .with_threads(4)
+///     .with_progress(|p| eprintln!("{} lines", p.lines));
+/// assert_eq!(options.separator, Some(','));
+/// ```
+#[derive(Clone)]
+pub struct EdgeListOptions {
+    /// The character between tokens, or `None` to split on runs of whitespace.
+    pub separator: Option<char>,
+    /// Text that starts a comment, running to the end of the line. Empty disables comments.
+    pub comment_prefix: String,
+    /// What to do with lines that do not parse.
+    pub malformed: MalformedLines,
+    /// Expected number of distinct nodes, reserved up front.
+    pub node_capacity: usize,
+    /// Expected number of edges, reserved up front.
+    pub edge_capacity: usize,
+    /// Number of lines read and parsed at a time.
+    pub chunk_lines: usize,
+    /// Number of threads that parse each chunk. Nodes and edges are still added in file order.
+    pub threads: usize,
+    /// Called after each chunk.
+    pub progress: Option<ProgressFn>,
+}
+
+impl fmt::Debug for EdgeListOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EdgeListOptions")
+            .field("separator", &self.separator)
+            .field("comment_prefix", &self.comment_prefix)
+            .field("malformed", &self.malformed)
+            .field("node_capacity", &self.node_capacity)
+            .field("edge_capacity", &self.edge_capacity)
+            .field("chunk_lines", &self.chunk_lines)
+            .field("threads", &self.threads)
+            .field("progress", &self.progress.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        Self {
+            separator: None,
+            comment_prefix: "#".to_string(),
+            malformed: MalformedLines::Fail,
+            node_capacity: 0,
+            edge_capacity: 0,
+            chunk_lines: 65_536,
+            threads: 1,
+            progress: None,
+        }
+    }
+}
+
+impl EdgeListOptions {
+    /// Creates the default options: whitespace-separated tokens, `#` comments, failing on
+    /// malformed lines, and parsing on one thread.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the character between tokens.
+    pub fn with_separator(mut self, separator: char) -> Self {
+        self.separator = Some(separator);
+        self
+    }
+
+    /// Sets the text that starts a comment; an empty string disables comments.
+    pub fn with_comment_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.comment_prefix = prefix.into();
+        self
+    }
+
+    /// Sets what happens to lines that do not parse.
+    pub fn with_malformed(mut self, malformed: MalformedLines) -> Self {
+        self.malformed = malformed;
+        self
+    }
+
+    /// Sets the expected numbers of distinct nodes and of edges.
+    pub fn with_capacity(mut self, nodes: usize, edges: usize) -> Self {
+        self.node_capacity = nodes;
+        self.edge_capacity = edges;
+        self
+    }
+
+    /// Sets the number of lines per chunk; values below one are treated as one.
+    pub fn with_chunk_lines(mut self, lines: usize) -> Self {
+        self.chunk_lines = lines;
+        self
+    }
+
+    /// Sets the number of parsing threads; values below one are treated as one.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Sets the callback that receives a progress report after each chunk.
+    pub fn with_progress(
+        mut self,
+        progress: impl Fn(&EdgeListProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.progress = Some(Arc::new(progress));
+        self
+    }
+}
+
+/// A line that [`read_edge_list_with`] skipped under [`MalformedLines::Collect`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineError {
+    /// The line number, starting from 1.
+    pub line: usize,
+    /// Why the line did not parse.
+    pub message: String,
+}
+
+/// What a streaming read did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EdgeListReport {
+    /// Lines read, including blank, comment, and skipped lines.
+    pub lines: usize,
+    /// Edges added.
+    pub edges: usize,
+    /// Nodes added.
+    pub nodes: usize,
+    /// Malformed lines that were skipped.
+    pub skipped: usize,
+    /// The skipped lines and their errors, under [`MalformedLines::Collect`].
+    pub errors: Vec<LineError>,
+}
+
+enum ParsedLine<A, W> {
+    Blank,
+    Edge(A, A, W),
+    Malformed(String),
+}
+
+fn parse_token<T>(token: &str, what: &str) -> std::result::Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse()
+        .map_err(|e| format!("cannot parse {what} '{token}': {e}"))
+}
+
+fn parse_edge_line<A, W>(line: &str, separator: Option<char>, comment: &str) -> ParsedLine<A, W>
+where
+    A: FromStr,
+    A::Err: Display,
+    W: FromStr,
+    W::Err: Display,
+{
+    let content = match line.find(comment) {
+        Some(idx) if !comment.is_empty() => &line[..idx],
+        _ => line,
+    };
+    let content = content.trim();
+    if content.is_empty() {
+        return ParsedLine::Blank;
+    }
+    let tokens: Vec<&str> = match separator {
+        Some(sep) => content.split(sep).map(str::trim).collect(),
+        None => content.split_whitespace().collect(),
+    };
+    if tokens.len() < 2 {
+        return ParsedLine::Malformed("expected a source and a target".to_string());
+    }
+    let parsed = parse_token(tokens[0], "source").and_then(|source| {
+        let target = parse_token(tokens[1], "target")?;
+        let weight = parse_token(tokens.get(2).copied().unwrap_or("1"), "weight")?;
+        Ok(ParsedLine::Edge(source, target, weight))
+    });
+    parsed.unwrap_or_else(ParsedLine::Malformed)
+}
+
+fn parse_chunk<A, W>(
+    lines: &[String],
+    options: &EdgeListOptions,
+) -> std::io::Result<Vec<ParsedLine<A, W>>>
+where
+    A: FromStr + Send,
+    A::Err: Display,
+    W: FromStr + Send,
+    W::Err: Display,
+{
+    let (separator, comment) = (options.separator, options.comment_prefix.as_str());
+    let parse_all = |part: &[String]| -> Vec<ParsedLine<A, W>> {
+        part.iter()
+            .map(|line| parse_edge_line(line, separator, comment))
+            .collect()
+    };
+    let threads = options.threads.max(1);
+    if threads == 1 || lines.len() < 2 * threads {
+        return Ok(parse_all(lines));
+    }
+    let per_thread = lines.len().div_ceil(threads);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = lines
+            .chunks(per_thread)
+            .map(|part| scope.spawn(move || parse_all(part)))
+            .collect();
+        let mut parsed = Vec::with_capacity(lines.len());
+        for handle in handles {
+            let part = handle
+                .join()
+                .map_err(|_| Error::other("edge list parsing thread panicked"))?;
+            parsed.extend(part);
+        }
+        Ok(parsed)
+    })
+}
+
+/// Streams an edge list from a file into the given graph.
+///
+/// A streaming, configurable counterpart to [`read_edge_list`]. The file is read
+/// [`chunk_lines`](EdgeListOptions::chunk_lines) lines at a time, so memory use beyond the graph
+/// itself stays bounded however large the file is. Each line holds a source, a target, and
+/// optionally a weight, which defaults to 1; further tokens are ignored. Node labels can be any
+/// type that parses from a token, and each distinct label becomes one new node, in order of first
+/// appearance, whatever the number of threads.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or is not UTF-8, or, under
+/// [`MalformedLines::Fail`], if a line does not parse. Edges read before the error stay in
+/// `graph`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::{EdgeListOptions, MalformedLines, read_edge_list_with};
+/// use graphina::core::types::Graph;
+///
+/// let mut graph = Graph::<u64, f64>::new();
+/// let options = EdgeListOptions::new()
+///     .with_malformed(MalformedLines::Skip)
+///     .with_threads(8)
+///     .with_progress(|p| eprintln!("{} MB", p.bytes >> 20));
+/// let report = read_edge_list_with("edges.tsv", &mut graph, &options).unwrap();
+/// println!("{} edges, {} lines skipped", report.edges, report.skipped);
+/// ```
+pub fn read_edge_list_with<A, W, Ty>(
+    path: &str,
+    graph: &mut BaseGraph<A, W, Ty>,
+    options: &EdgeListOptions,
+) -> std::io::Result<EdgeListReport>
+where
+    A: FromStr + Eq + Hash + Clone + Send,
+    A::Err: Display,
+    W: FromStr + Send,
+    W::Err: Display,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    read_edge_list_from(BufReader::new(File::open(path)?), graph, options)
+}
+
+/// Streams an edge list from any buffered reader into the given graph.
+///
+/// Behaves as [`read_edge_list_with`], for input that is not a file, such as standard input or a
+/// decompressing reader.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::io::{EdgeListOptions, MalformedLines, read_edge_list_from};
+/// use graphina::core::types::Digraph;
+///
+/// let input = "# source target weight\n1 2 0.5\n2 3\nbad line here\n";
+/// let mut graph = Digraph::<u32, f64>::new();
+/// let options = EdgeListOptions::new().with_malformed(MalformedLines::Collect);
+/// let report = read_edge_list_from(input.as_bytes(), &mut graph, &options).unwrap();
+/// assert_eq!((report.nodes, report.edges), (3, 2));
+/// assert_eq!(report.errors[0].line, 4);
+/// ```
+pub fn read_edge_list_from<R, A, W, Ty>(
+    mut reader: R,
+    graph: &mut BaseGraph<A, W, Ty>,
+    options: &EdgeListOptions,
+) -> std::io::Result<EdgeListReport>
+where
+    R: BufRead,
+    A: FromStr + Eq + Hash + Clone + Send,
+    A::Err: Display,
+    W: FromStr + Send,
+    W::Err: Display,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    graph.reserve_nodes(options.node_capacity);
+    graph.reserve_edges(options.edge_capacity);
+    let mut ids: HashMap<A, NodeId> = HashMap::with_capacity(options.node_capacity);
+    let mut report = EdgeListReport::default();
+    let mut bytes = 0u64;
+    let chunk_lines = options.chunk_lines.max(1);
+    let mut chunk: Vec<String> = Vec::with_capacity(chunk_lines);
+    let mut finished = false;
+    while !finished {
+        chunk.clear();
+        while chunk.len() < chunk_lines {
+            let mut line = String::new();
+            let read = reader.read_line(&mut line)?;
+            if read == 0 {
+                finished = true;
+                break;
+            }
+            bytes += read as u64;
+            chunk.push(line);
+        }
+        for (offset, parsed) in parse_chunk::<A, W>(&chunk, options)?
+            .into_iter()
+            .enumerate()
+        {
+            let line = report.lines + offset + 1;
+            match parsed {
+                ParsedLine::Blank => {}
+                ParsedLine::Edge(source, target, weight) => {
+                    let u = *ids
+                        .entry(source)
+                        .or_insert_with_key(|label| graph.add_node(label.clone()));
+                    let v = *ids
+                        .entry(target)
+                        .or_insert_with_key(|label| graph.add_node(label.clone()));
+                    graph.add_edge(u, v, weight);
+                    report.edges += 1;
+                }
+                ParsedLine::Malformed(message) => match options.malformed {
+                    MalformedLines::Fail => {
+                        return Err(Error::new(
+                            ErrorKind::InvalidData,
+                            format!("line {line}: {message}"),
+                        ));
+                    }
+                    MalformedLines::Skip => report.skipped += 1,
+                    MalformedLines::Collect => {
+                        report.skipped += 1;
+                        report.errors.push(LineError { line, message });
+                    }
+                },
+            }
+        }
+        report.lines += chunk.len();
+        report.nodes = ids.len();
+        if let Some(progress) = &options.progress {
+            progress(&EdgeListProgress {
+                bytes,
+                lines: report.lines,
+                edges: report.edges,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// Writes the edge list of a graph to a file.
+///
+/// Each line in the output file will contain the source attribute, target attribute, and weight,
+/// separated by the provided separator.
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the output file.
+/// * `graph` - A reference to the graph to be written.
+/// * `sep` - A character that separates the values in the output file.
+///
+/// # Type Parameters
+///
+/// * `Ty` - The edge type of the graph, which must implement `GraphConstructor`.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` indicating success or failure. Failure occurs if a node attribute is missing
+///   or if writing to the file fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::types::{Graph, NodeId};
+/// use graphina::core::io::write_edge_list;
+///
+/// let mut graph = Graph::<i32, f32>::new();
+/// let n1 = graph.add_node(1);
+/// let n2 = graph.add_node(2);
+/// graph.add_edge(n1, n2, 3.5);
+/// write_edge_list("output_edges.txt", &graph, ',').expect("Failed to write edge list");
+/// ```
+pub fn write_edge_list<Ty>(
+    path: &str,
+    graph: &BaseGraph<i32, f32, Ty>,
+    sep: char,
+) -> std::io::Result<()>
+where
+    Ty: GraphConstructor<i32, f32>,
+{
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    for (src, tgt, weight) in graph.edges() {
+        let src_attr = graph.node_attr(src).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing node attribute for source node: {:?}", src),
+            )
+        })?;
+        let tgt_attr = graph.node_attr(tgt).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing node attribute for target node: {:?}", tgt),
+            )
+        })?;
+        writeln!(writer, "{}{}{}{}{}", src_attr, sep, tgt_attr, sep, weight)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads an adjacency list from a file and populates the given graph.
+///
+/// Each non-empty line is expected to be in the following format:
+///
+/// ```text
+/// <node><sep><neighbor1><sep><weight1><sep><neighbor2><sep><weight2>...
+/// ```
+///
+/// Lines containing a `#` are treated as comments. Everything after the first `#` in a line is ignored.
+/// If a weight is missing for a neighbor, a default weight of `1.0` is used.
+///
+/// # Arguments
+///
+/// * `path` - A string slice holding the path to the adjacency list file.
+/// * `graph` - A mutable reference to the graph to be populated.
+/// * `sep` - A character that separates the tokens in the file.
+///
+/// # Type Parameters
+///
+/// * `Ty` - The edge type of the graph, which must implement `GraphConstructor`.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` indicating success or failure. Parsing errors are returned if any token
+///   fails to convert to the expected type.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::types::{Graph, NodeId};
+/// use graphina::core::io::read_adjacency_list;
+///
+/// let mut graph = Graph::<i32, f32>::new();
+/// // Assume "adj_list.txt" exists and follows the correct format.
+/// read_adjacency_list("adj_list.txt", &mut graph, ' ').expect("Failed to read adjacency list");
+/// ```
+pub fn read_adjacency_list<Ty>(
+    path: &str,
+    graph: &mut BaseGraph<i32, f32, Ty>,
+    sep: char,
+) -> std::io::Result<()>
+where
+    Ty: GraphConstructor<i32, f32>,
+{
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut node_map = HashMap::new();
+    for line in reader.lines() {
+        let mut line = line?;
+        // Remove comments: if '#' is present, only take text before it.
+        if let Some(idx) = line.find('#') {
+            line.truncate(idx);
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        let tokens: Vec<&str> = line.trim().split(sep).map(|s| s.trim()).collect();
+        if tokens.is_empty() {
+            continue;
+        }
+        // The first token is the source node attribute.
+        let src_val: i32 = tokens[0].parse().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing source value '{}': {}", tokens[0], e),
+            )
+        })?;
+        let src_node = *node_map
+            .entry(src_val)
+            .or_insert_with(|| graph.add_node(src_val));
+        // Process subsequent tokens in pairs: neighbor and weight.
+        let mut i = 1;
+        while i < tokens.len() {
+            let neighbor_val: i32 = tokens[i].parse().map_err(|e| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    format!("Error parsing neighbor value '{}': {}", tokens[i], e),
+                )
+            })?;
+            let weight: f32 = if i + 1 < tokens.len() {
+                tokens[i + 1].parse().map_err(|e| {
+                    Error::new(
+                        ErrorKind::InvalidData,
+                        format!("Error parsing weight '{}': {}", tokens[i + 1], e),
+                    )
+                })?
+            } else {
+                1.0
+            };
+            let neighbor_node = *node_map
+                .entry(neighbor_val)
+                .or_insert_with(|| graph.add_node(neighbor_val));
+            graph.add_edge(src_node, neighbor_node, weight);
+            i += 2;
+        }
+    }
+    Ok(())
+}
+
+/// Writes the adjacency list of a graph to a file.
+///
+/// Each line in the output file will be in the following format:
+///
+/// ```text
+/// <node><sep><neighbor1>:<weight1><sep><neighbor2>:<weight2>...
+/// ```
+///
+/// # Arguments
+///
+/// * `path` - A string slice holding the path to the output file.
+/// * `graph` - A reference to the graph to be written.
+/// * `sep` - A character that separates the tokens in the output file.
+///
+/// # Type Parameters
+///
+/// * `Ty` - The edge type of the graph, which must implement `GraphConstructor`.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` indicating success or failure. An error is returned if any node attribute
+///   is missing or if writing to the file fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::types::{Graph, NodeId};
+/// use graphina::core::io::write_adjacency_list;
+///
+/// let mut graph = Graph::<i32, f32>::new();
+/// let n1 = graph.add_node(1);
+/// let n2 = graph.add_node(2);
+/// graph.add_edge(n1, n2, 2.5);
+/// write_adjacency_list("output_adj.txt", &graph, ' ').expect("Failed to write adjacency list");
+/// ```
+pub fn write_adjacency_list<Ty>(
+    path: &str,
+    graph: &BaseGraph<i32, f32, Ty>,
+    sep: char,
+) -> std::io::Result<()>
+where
+    Ty: GraphConstructor<i32, f32>,
+{
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    let mut adj_map: HashMap<i32, Vec<(i32, f32)>> = HashMap::new();
+    for (src, tgt, weight) in graph.edges() {
+        let src_attr = graph.node_attr(src).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing node attribute for source node: {:?}", src),
+            )
+        })?;
+        let tgt_attr = graph.node_attr(tgt).ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Missing node attribute for target node: {:?}", tgt),
+            )
+        })?;
+        adj_map
+            .entry(*src_attr)
+            .or_default()
+            .push((*tgt_attr, *weight));
+    }
+    // Write each node and its neighbors.
+    for (_, attr) in graph.nodes() {
+        write!(writer, "{}", attr)?;
+        if let Some(neighbors) = adj_map.get(attr) {
+            for (nbr, weight) in neighbors {
+                // Write neighbor and weight separated by a colon.
+                write!(writer, "{}{}:{}", sep, nbr, weight)?;
+            }
+        }
+        writeln!(writer)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes a per-node score map to a CSV file.
+///
+/// The file starts with a `node,value` header, followed by one `index,value` line per node in
+/// ascending index order. Values are written with full round-trip precision, and non-finite
+/// values are written as `NaN`, `inf`, or `-inf`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::save_nodemap_csv;
+/// use graphina::core::types::{Graph, NodeMap};
+///
+/// let mut graph = Graph::<i32, f64>::new();
+/// let n1 = graph.add_node(1);
+/// let scores: NodeMap<f64> = [(n1, 0.25)].into_iter().collect();
+/// save_nodemap_csv("scores.csv", &scores).expect("Failed to save scores");
+/// ```
+pub fn save_nodemap_csv(path: &str, values: &NodeMap<f64>) -> std::io::Result<()> {
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    let mut entries: Vec<(usize, f64)> = values.iter().map(|(n, v)| (n.index(), *v)).collect();
+    entries.sort_by_key(|(idx, _)| *idx);
+    writeln!(writer, "node,value")?;
+    for (idx, value) in entries {
+        writeln!(writer, "{},{}", idx, value)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a per-node score map written by [`save_nodemap_csv`].
+///
+/// Lines are `index,value` pairs. A leading `node,value` header, blank lines, and `#` comments are
+/// skipped. The returned `NodeId`s are rebuilt from the stored indices and are not checked against
+/// any graph.
+///
+/// # Returns
+///
+/// * `Result<NodeMap<f64>>` - An `io::Result` that fails with `InvalidData` if a line does not have
+///   exactly two fields, a field fails to parse, or a node appears more than once.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::load_nodemap_csv;
+///
+/// let scores = load_nodemap_csv("scores.csv").expect("Failed to load scores");
+/// ```
+pub fn load_nodemap_csv(path: &str) -> std::io::Result<NodeMap<f64>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let mut values = NodeMap::default();
+    for (line_no, line) in reader.lines().enumerate() {
+        let mut line = line?;
+        if let Some(idx) = line.find('#') {
+            line.truncate(idx);
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() || (line_no == 0 && trimmed == "node,value") {
+            continue;
+        }
+        let tokens: Vec<&str> = trimmed.split(',').map(|s| s.trim()).collect();
+        if tokens.len() != 2 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!(
+                    "Line {}: expected 'node,value', got '{}'",
+                    line_no + 1,
+                    trimmed
+                ),
+            ));
+        }
+        let node: usize = tokens[0].parse().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing node index '{}': {}", tokens[0], e),
+            )
+        })?;
+        let value: f64 = tokens[1].parse().map_err(|e| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("Error parsing value '{}': {}", tokens[1], e),
+            )
+        })?;
+        if values
+            .insert(NodeId::new(NodeIndex::new(node)), value)
+            .is_some()
+        {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("Node {} appears more than once", node),
+            ));
+        }
+    }
+    Ok(values)
+}
+
+/// Writes a community partition to a JSON file.
+///
+/// The file holds a single object that maps each node index (as a string key) to the position of
+/// its community in `communities`, for example `{"0": 0, "1": 0, "2": 1}`. This is the same
+/// node-to-label layout that most graph tools accept.
+///
+/// # Returns
+///
+/// * `Result<()>` - An `io::Result` that fails with `InvalidInput` if a node belongs to more than
+///   one community, or with the underlying error if writing fails.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::save_partition_json;
+/// use graphina::core::types::Graph;
+///
+/// let mut graph = Graph::<i32, f64>::new();
+/// let n1 = graph.add_node(1);
+/// let n2 = graph.add_node(2);
+/// save_partition_json("partition.json", &[vec![n1], vec![n2]]).expect("Failed to save partition");
+/// ```
+pub fn save_partition_json(path: &str, communities: &[Vec<NodeId>]) -> std::io::Result<()> {
+    let mut labels: BTreeMap<usize, usize> = BTreeMap::new();
+    for (community, members) in communities.iter().enumerate() {
+        for node in members {
+            if labels.insert(node.index(), community).is_some() {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Node {} belongs to more than one community", node.index()),
+                ));
+            }
+        }
+    }
+    let file = File::create(path)?;
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, &labels)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads a community partition written by [`save_partition_json`].
+///
+/// Community labels need not be contiguous; communities are returned in ascending label order,
+/// with members sorted by `NodeId`.
+///
+/// # Returns
+///
+/// * `Result<Vec<Vec<NodeId>>>` - An `io::Result` that fails with `InvalidData` if the file is not
+///   a JSON object of node indices to non-negative integer labels.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use graphina::core::io::load_partition_json;
+///
+/// let communities = load_partition_json("partition.json").expect("Failed to load partition");
+/// ```
+pub fn load_partition_json(path: &str) -> std::io::Result<Vec<Vec<NodeId>>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+    let labels: BTreeMap<usize, usize> = serde_json::from_reader(reader)?;
+    let mut grouped: BTreeMap<usize, Vec<NodeId>> = BTreeMap::new();
+    for (node, community) in labels {
+        grouped
+            .entry(community)
+            .or_default()
+            .push(NodeId::new(NodeIndex::new(node)));
+    }
+    Ok(grouped.into_values().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+    use std::fs;
+    use std::io::Read;
+    #[test]
+    fn test_read_edge_list() {
+        let tmp_path = "tmp_edge_list.txt";
+        let edge_list = "\
+# This is a comment line and should be ignored
+1,2,1.5
+2,3,2.0
+3,1,3.0  # Comment after data should be ignored
+";
+        fs::write(tmp_path, edge_list).expect("Unable to write temporary file");
+        let mut graph = Graph::<i32, f32>::new();
+        read_edge_list(tmp_path, &mut graph, ',').expect("read_edge_list failed");
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+    #[test]
+    fn test_write_edge_list() {
+        let mut graph = Graph::<i32, f32>::new();
+        let n1 = graph.add_node(1);
+        let n2 = graph.add_node(2);
+        let n3 = graph.add_node(3);
+        graph.add_edge(n1, n2, 1.5);
+        graph.add_edge(n2, n3, 2.0);
+        graph.add_edge(n3, n1, 3.0);
+        let tmp_path = "tmp_edge_list_out.txt";
+        write_edge_list(tmp_path, &graph, ',').expect("write_edge_list failed");
+        let mut content = String::new();
+        fs::File::open(tmp_path)
+            .expect("Failed to open output file")
+            .read_to_string(&mut content)
+            .expect("Failed to read output file");
+        assert!(content.contains("1,2,1.5") || content.contains("2,1,1.5"));
+        assert!(content.contains("2,3,2") || content.contains("3,2,2"));
+        assert!(content.contains("3,1,3") || content.contains("1,3,3"));
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+    #[test]
+    fn test_read_adjacency_list() {
+        let tmp_path = "tmp_adj_list.txt";
+        let adj_list = "\
+# Adjacency list with comments
+1,2,1.5,3,2.5
+2,3,2.0
+3
+";
+        fs::write(tmp_path, adj_list).expect("Unable to write temporary file");
+        let mut graph = Graph::<i32, f32>::new();
+        read_adjacency_list(tmp_path, &mut graph, ',').expect("read_adjacency_list failed");
+        assert_eq!(graph.node_count(), 3);
+        assert_eq!(graph.edge_count(), 3);
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+    #[test]
+    fn test_write_adjacency_list() {
+        let mut graph = Graph::<i32, f32>::new();
+        let n1 = graph.add_node(1);
+        let n2 = graph.add_node(2);
+        let n3 = graph.add_node(3);
+        graph.add_edge(n1, n2, 1.5);
+        graph.add_edge(n1, n3, 2.5);
+        graph.add_edge(n2, n3, 2.0);
+        let tmp_path = "tmp_adj_list_out.txt";
+        write_adjacency_list(tmp_path, &graph, ',').expect("write_adjacency_list failed");
+        let mut content = String::new();
+        fs::File::open(tmp_path)
+            .expect("Failed to open output file")
+            .read_to_string(&mut content)
+            .expect("Failed to read output file");
+        assert!(!content.is_empty());
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+    #[test]
+    fn test_nodemap_csv_round_trip() {
+        let mut graph = Graph::<i32, f32>::new();
+        let n1 = graph.add_node(1);
+        let n2 = graph.add_node(2);
+        let n3 = graph.add_node(3);
+        let scores: NodeMap<f64> = [(n3, f64::NAN), (n1, 0.1 + 0.2), (n2, -4.0)]
+            .into_iter()
+            .collect();
+        let tmp_path = "tmp_nodemap.csv";
+        save_nodemap_csv(tmp_path, &scores).expect("save_nodemap_csv failed");
+        let content = fs::read_to_string(tmp_path).expect("Failed to read output file");
+        assert!(content.starts_with("node,value\n0,"));
+        let loaded = load_nodemap_csv(tmp_path).expect("load_nodemap_csv failed");
+        assert_eq!(loaded.len(), 3);
+        assert_eq!(loaded[&n1], 0.1 + 0.2);
+        assert_eq!(loaded[&n2], -4.0);
+        assert!(loaded[&n3].is_nan());
+
+        fs::write(tmp_path, "0,1.0\n0,2.0\n").expect("Unable to write temporary file");
+        assert!(load_nodemap_csv(tmp_path).is_err());
+        fs::write(tmp_path, "0,abc\n").expect("Unable to write temporary file");
+        assert!(load_nodemap_csv(tmp_path).is_err());
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+    #[test]
+    fn test_partition_json_round_trip() {
+        let mut graph = Graph::<i32, f32>::new();
+        let nodes: Vec<NodeId> = (0..4).map(|i| graph.add_node(i)).collect();
+        let communities = vec![vec![nodes[2], nodes[0]], vec![nodes[1], nodes[3]]];
+        let tmp_path = "tmp_partition.json";
+        save_partition_json(tmp_path, &communities).expect("save_partition_json failed");
+        let loaded = load_partition_json(tmp_path).expect("load_partition_json failed");
+        assert_eq!(
+            loaded,
+            vec![vec![nodes[0], nodes[2]], vec![nodes[1], nodes[3]]]
+        );
+
+        let overlapping = vec![vec![nodes[0]], vec![nodes[0]]];
+        assert!(save_partition_json(tmp_path, &overlapping).is_err());
+        fs::write(tmp_path, r#"{"0": "a"}"#).expect("Unable to write temporary file");
+        assert!(load_partition_json(tmp_path).is_err());
+        fs::remove_file(tmp_path).expect("Failed to remove temporary file");
+    }
+
+    #[test]
+    fn test_read_edge_list_from_chunks_and_threads() {
+        use crate::core::types::Digraph;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let mut input = String::from("# header\n");
+        for i in 0..1000 {
+            input.push_str(&format!("{},{},{}\n", i % 97, (i * 7) % 101, i));
+        }
+        input.push_str("5,x,1\n\n1\n");
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let seen = Arc::clone(&calls);
+        let options = EdgeListOptions::new()
+            .with_separator(',')
+            .with_chunk_lines(64)
+            .with_threads(4)
+            .with_malformed(MalformedLines::Collect)
+            .with_progress(move |_| {
+                seen.fetch_add(1, Ordering::Relaxed);
+            });
+        let mut threaded = Digraph::<i64, u32>::new();
+        let report = read_edge_list_from(input.as_bytes(), &mut threaded, &options).unwrap();
+        assert_eq!(report.lines, 1004);
+        assert_eq!(report.edges, 1000);
+        assert_eq!(report.skipped, 2);
+        assert_eq!(report.errors[0].line, 1002);
+        assert!(report.errors[1].message.contains("source and a target"));
+        assert_eq!(calls.load(Ordering::Relaxed), 16);
+
+        let mut sequential = Digraph::<i64, u32>::new();
+        let options = options.with_threads(1).with_chunk_lines(100_000);
+        read_edge_list_from(input.as_bytes(), &mut sequential, &options).unwrap();
+        let threaded_edges: Vec<_> = threaded.edges().map(|(u, v, &w)| (u, v, w)).collect();
+        let sequential_edges: Vec<_> = sequential.edges().map(|(u, v, &w)| (u, v, w)).collect();
+        assert_eq!(threaded_edges, sequential_edges);
+        assert_eq!(report.nodes, threaded.node_count());
+
+        let strict = EdgeListOptions::new().with_separator(',');
+        let err = read_edge_list_from(input.as_bytes(), &mut Digraph::<i64, u32>::new(), &strict)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+        assert!(err.to_string().starts_with("line 1002:"));
+    }
+}
@@ -0,0 +1,183 @@
+/*!
+# Type-Level Requirements
+
+Some preconditions can be carried by the types of a graph instead of being checked when an
+algorithm runs. Functions bounded by [`Undirectedness`] do not compile for a `Digraph`, and
+functions bounded by [`NonNegativeWeights`] do not compile for weights that could be
+negative, so the matching `GraphinaError` cannot happen.
+
+`f64` weights can be negative or NaN, so they do not qualify on their own. Wrapping them in
+[`NonNegativeF64`] moves the check to the point where each weight is created:
+
+```rust
+use graphina::core::markers::NonNegativeF64;
+use graphina::core::paths::dijkstra_nonnegative;
+use graphina::core::types::Graph;
+
+let mut g = Graph::<&str, f64>::new();
+let a = g.add_node("a");
+let b = g.add_node("b");
+g.add_edge(a, b, 2.5);
+
+// A negative or NaN weight is rejected once, here, rather than inside every search.
+let checked = g.try_map_edge_weights(|_, &w| NonNegativeF64::new(w)).unwrap();
+let distances = dijkstra_nonnegative(&checked, a).unwrap();
+assert_eq!(distances[&b].map(f64::from), Some(2.5));
+```
+*/
+
+use std::cmp::Ordering;
+use std::fmt::{self, Debug, Display};
+use std::ops::Add;
+
+use petgraph::EdgeType;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::Undirected;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for crate::core::types::Undirected {}
+}
+
+/// Edge types without a direction, which is only [`Undirected`].
+///
+/// The trait is sealed, so no other edge type can claim it.
+///
+/// # Example
+///
+/// ```rust,compile_fail
+/// use graphina::core::markers::Undirectedness;
+/// use graphina::core::types::{BaseGraph, Digraph, GraphConstructor};
+///
+/// fn undirected_only<Ty>(_: &BaseGraph<(), f64, Ty>)
+/// where
+///     Ty: GraphConstructor<(), f64> + Undirectedness,
+/// {
+/// }
+///
+/// // `Directed` does not implement `Undirectedness`, so this is a type error.
+/// undirected_only(&Digraph::<(), f64>::new());
+/// ```
+pub trait Undirectedness: EdgeType + sealed::Sealed {}
+
+impl Undirectedness for Undirected {}
+
+/// Weight types with no negative values.
+///
+/// Implemented for the unsigned integers and [`NonNegativeF64`]. Implementing it for another
+/// type promises that no value is less than [`ZERO`](Self::ZERO); algorithms bounded by this
+/// trait skip their runtime weight checks and rely on that promise. As with the unsigned
+/// integers, overflowing sums are the caller's concern.
+pub trait NonNegativeWeights: Copy + Ord + Add<Output = Self> + Debug {
+    /// The weight of an empty path.
+    const ZERO: Self;
+}
+
+macro_rules! unsigned_weights {
+    ($($t:ty),*) => {
+        $(impl NonNegativeWeights for $t {
+            const ZERO: Self = 0;
+        })*
+    };
+}
+
+unsigned_weights!(u8, u16, u32, u64, u128, usize);
+
+/// An `f64` that is neither negative nor NaN, and so totally ordered.
+///
+/// Positive infinity is allowed and stands for an edge that can never be used profitably.
+/// Negative zero is stored as zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct NonNegativeF64(f64);
+
+impl NonNegativeF64 {
+    /// Wraps `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` if `value` is negative or NaN.
+    pub fn new(value: f64) -> Result<Self> {
+        if value.is_nan() || value < 0.0 {
+            return Err(GraphinaError::invalid_argument(format!(
+                "expected a nonnegative weight, found {value}"
+            )));
+        }
+        // Adding zero turns -0.0 into 0.0, so equal values compare equal below.
+        Ok(Self(value + 0.0))
+    }
+
+    /// The wrapped value.
+    pub fn get(self) -> f64 {
+        self.0
+    }
+}
+
+impl Eq for NonNegativeF64 {}
+
+impl PartialOrd for NonNegativeF64 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for NonNegativeF64 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl Add for NonNegativeF64 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        // Two values that are neither negative nor NaN cannot sum to either.
+        Self(self.0 + other.0)
+    }
+}
+
+impl Display for NonNegativeF64 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+impl TryFrom<f64> for NonNegativeF64 {
+    type Error = GraphinaError;
+
+    fn try_from(value: f64) -> Result<Self> {
+        Self::new(value)
+    }
+}
+
+impl From<NonNegativeF64> for f64 {
+    fn from(value: NonNegativeF64) -> f64 {
+        value.0
+    }
+}
+
+impl NonNegativeWeights for NonNegativeF64 {
+    const ZERO: Self = Self(0.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nonnegative_f64_rejects_negative_and_nan() {
+        assert!(NonNegativeF64::new(-1e-12).is_err());
+        assert!(NonNegativeF64::new(f64::NAN).is_err());
+        assert!(NonNegativeF64::try_from(f64::NEG_INFINITY).is_err());
+
+        let zero = NonNegativeF64::new(-0.0).unwrap();
+        assert_eq!(zero, NonNegativeF64::ZERO);
+        assert_eq!(zero.cmp(&NonNegativeF64::ZERO), Ordering::Equal);
+        let inf = NonNegativeF64::new(f64::INFINITY).unwrap();
+        let two = NonNegativeF64::new(2.0).unwrap();
+        assert!(two < inf);
+        assert_eq!(f64::from(two + two), 4.0);
+        assert_eq!(two.to_string(), "2");
+    }
+}
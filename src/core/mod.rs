@@ -1,9 +1,28 @@
+pub mod aggregation;
+pub mod anonymization;
 pub mod builders;
+pub mod compressed;
+pub mod contraction;
 pub mod error;
+#[cfg(feature = "ndarray")]
+pub mod features;
+pub mod fingerprint;
 pub mod generators;
 pub mod io;
+pub mod markers;
 pub mod paths;
+pub mod percolation;
+pub mod products;
+pub mod properties;
+pub mod sampling;
 pub mod serialization;
+pub mod sparse;
+pub mod temporal;
 pub mod traits;
+pub mod transform;
 pub mod types;
+pub mod union_find;
 pub mod validation;
+pub mod vectors;
+pub mod views;
+pub mod weights;
@@ -6,15 +6,27 @@ It supports single‑source and all‑pairs computations via (classical) algorit
 
 - **Dijkstra’s Algorithm:**
   Computes single‑source shortest paths for graphs with nonnegative weights.
+  A multi-source variant assigns every node to its nearest source, and `dijkstra_nonnegative`
+  takes weights that are nonnegative by type, so it has no weight check to fail.
+  `dijkstra_to_targets` stops once the first or every requested target is settled, and
+  `shortest_path_tree` and `routing_table` keep the paths as a tree or as first hops.
 
 - **Bellman–Ford Algorithm:**
   Computes single‑source shortest paths even with negative weights and detects negative cycles.
 
 - **A\* (A-Star) Algorithm:**
   Finds a shortest path from a source to a target using an admissible heuristic.
+  `a_star_impl` takes `f64` costs and estimates from closures, for weights without a total order.
+
+- **Simple Path Enumeration:**
+  Lists every path between two nodes that visits no node twice, optionally bounded in length.
+
+- **Yen's k Shortest Paths:**
+  Ranks the `k` cheapest loopless paths between two nodes.
 
 - **Floyd–Warshall Algorithm:**
-  Computes all‑pairs shortest paths using dynamic programming.
+  Computes all‑pairs shortest paths using dynamic programming, as nested maps or as a dense
+  distance matrix with a node index.
 
 - **Johnson’s Algorithm:**
   Computes all‑pairs shortest paths for sparse graphs (even with negative edge weights) by re-weighting the graph and then running Dijkstra’s algorithm from each node.
@@ -28,14 +40,16 @@ It supports single‑source and all‑pairs computations via (classical) algorit
 Preconditions for each algorithm are enforced at runtime using `graphina::core::error::GraphinaError`.
 For example, algorithms that require nonnegative edge weights will return a `Result` containing a
 `GraphinaError::InvalidArgument` if a negative weight is encountered. Users should handle these
-`Result` types accordingly.
+`Result` types accordingly. Where a precondition fits in a type, `core::markers` offers traits
+that move it to compile time.
 
 */
 
 use crate::core::error::{GraphinaError, Result};
-use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeId, NodeMap};
+use crate::core::markers::NonNegativeWeights;
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, GraphinaGraph, NodeId, NodeMap};
 use std::cmp::Reverse;
-use std::collections::{BinaryHeap, VecDeque};
+use std::collections::{BTreeMap, BinaryHeap, HashSet, VecDeque};
 use std::fmt::Debug;
 use std::ops::{Add, Sub};
 
@@ -211,30 +225,344 @@ where
     NodeId: Ord,
     BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
 {
-    // Dense, index-keyed buffers (see `dijkstra`). A cutoff or impassable-edge
-    // search may touch few nodes, but the return contract is a complete map (one
-    // entry per node, `None` when unreachable), so we fill from the dense buffers
-    // at the end rather than building two full maps up front.
+    let (dist, trace) = dijkstra_search(
+        graph,
+        source,
+        |_| false,
+        cutoff,
+        |_, _, w| eval_cost(w),
+        |_| 0.0,
+    )?;
+    // A cutoff or impassable-edge search may touch few nodes, but the return contract is a
+    // complete map (one entry per node, `None` when unreachable), so we fill from the dense
+    // buffers at the end rather than building two full maps up front.
+    Ok((
+        dense_to_nodemap(graph, &dist),
+        dense_to_nodemap(graph, &trace),
+    ))
+}
+
+/// When [`dijkstra_to_targets`] may stop searching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetStop {
+    /// Stop at the nearest target, returning only that one.
+    First,
+    /// Stop once every target is settled, or nothing else is reachable.
+    #[default]
+    All,
+}
+
+/// Cost and path for each target reached by [`dijkstra_to_targets`].
+pub type TargetPaths = NodeMap<(f64, Vec<NodeId>)>;
+
+/// Runs Dijkstra's algorithm from `source` only until the requested targets are settled.
+///
+/// A full single-source run settles every reachable node; when only a few destinations
+/// matter, stopping early saves most of that work. `eval_cost` works as in
+/// [`dijkstra_path_impl`], returning `None` for an edge that cannot be used.
+///
+/// # Returns
+///
+/// A map with one `(cost, path)` entry per settled target, where `path` runs from `source`
+/// to the target inclusive. Unreachable targets are absent, and with [`TargetStop::First`]
+/// the map holds only the nearest target. A target equal to `source` costs 0.
+///
+/// # Errors
+///
+/// - `NodeNotFound` if `source` or a target is not in the graph.
+/// - `InvalidArgument` if `eval_cost` returns a negative or NaN cost.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::{TargetStop, dijkstra_to_targets};
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let ids: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// for pair in ids.windows(2) {
+///     g.add_edge(pair[0], pair[1], 1.0);
+/// }
+///
+/// let all = dijkstra_to_targets(&g, ids[0], &[ids[3], ids[1]], TargetStop::All, |w| Some(*w)).unwrap();
+/// assert_eq!(all[&ids[3]], (3.0, ids[..4].to_vec()));
+/// assert_eq!(all.len(), 2);
+///
+/// let first = dijkstra_to_targets(&g, ids[0], &[ids[3], ids[1]], TargetStop::First, |w| Some(*w)).unwrap();
+/// assert_eq!(first.len(), 1);
+/// assert_eq!(first[&ids[1]].0, 1.0);
+/// ```
+pub fn dijkstra_to_targets<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    targets: &[NodeId],
+    stop: TargetStop,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<TargetPaths>
+where
+    W: Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    if let Some(missing) = std::iter::once(&source)
+        .chain(targets)
+        .find(|n| !graph.contains_node(**n))
+    {
+        return Err(GraphinaError::node_not_found(format!(
+            "Node {:?} is not in the graph",
+            missing
+        )));
+    }
+    let mut result = TargetPaths::default();
+    if targets.is_empty() {
+        return Ok(result);
+    }
+    let mut pending = vec![false; index_bound(graph)];
+    let mut remaining = 0;
+    for t in targets {
+        if !std::mem::replace(&mut pending[t.index()], true) {
+            remaining += 1;
+        }
+    }
+    // Only settled targets have final distances, so remember which ones the search reached
+    // instead of reading every target's tentative entry afterwards.
+    let mut settled = Vec::new();
+    let (dist, trace) = dijkstra_search(
+        graph,
+        source,
+        |u| {
+            if !std::mem::replace(&mut pending[u.index()], false) {
+                return false;
+            }
+            settled.push(u);
+            remaining -= 1;
+            stop == TargetStop::First || remaining == 0
+        },
+        None,
+        |_, _, w| eval_cost(w),
+        |_| 0.0,
+    )?;
+    for t in settled {
+        if let Some((path, costs)) = trace_path(source, t, &dist, &trace) {
+            result.insert(t, (costs.last().copied().unwrap_or(0.0), path));
+        }
+    }
+    Ok(result)
+}
+
+/// Builds the tree of shortest paths from `source`.
+///
+/// The tree is a copy of `graph` that keeps the nodes reachable from `source`, with their
+/// node and edge IDs, attributes, and properties, and for each of them other than `source`
+/// only the edge its shortest path arrives by. Where parallel edges join a node to its
+/// predecessor, the cheapest one is kept. Every path from `source` in the tree is therefore a
+/// shortest path in `graph`; on a directed graph the edges point away from `source`.
+///
+/// # Errors
+///
+/// - `NodeNotFound` if `source` is not in the graph.
+/// - `InvalidArgument` if a reachable weight is negative or NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::shortest_path_tree;
+/// use graphina::core::types::Graph;
+///
+/// // A triangle whose long side is slower than the other two together.
+/// let mut g = Graph::<&str, f64>::new();
+/// let [a, b, c] = ["a", "b", "c"].map(|name| g.add_node(name));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// let ac = g.add_edge(a, c, 3.0);
+///
+/// let tree = shortest_path_tree(&g, a).unwrap();
+/// assert_eq!(tree.node_count(), 3);
+/// assert_eq!(tree.edge_count(), 2);
+/// assert!(tree.edge_weight(ac).is_none());
+/// ```
+pub fn shortest_path_tree<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+) -> Result<BaseGraph<A, W, Ty>>
+where
+    A: Clone,
+    W: Copy + Into<f64> + Debug,
+    Ty: GraphConstructor<A, W> + Clone,
+{
+    let (dist, trace) = tree_search(graph, source)?;
+    // The cheapest edge from each reached node's predecessor to it.
+    let mut arrival: Vec<Option<(EdgeId, f64)>> = vec![None; dist.len()];
+    for (e, u, v, &w) in graph.edges_with_ids() {
+        let w: f64 = w.into();
+        let into = if trace[v.index()] == Some(u) {
+            v
+        } else if !graph.is_directed() && trace[u.index()] == Some(v) {
+            u
+        } else {
+            continue;
+        };
+        let best = &mut arrival[into.index()];
+        if best.is_none_or(|(_, cost)| w < cost) {
+            *best = Some((e, w));
+        }
+    }
+    let mut tree = graph.clone();
+    tree.retain_nodes(|v, _| dist[v.index()].is_some());
+    let kept: HashSet<EdgeId> = arrival.into_iter().flatten().map(|(e, _)| e).collect();
+    let removed: Vec<EdgeId> = tree.edge_ids().filter(|e| !kept.contains(e)).collect();
+    for e in removed {
+        tree.remove_edge(e);
+    }
+    Ok(tree)
+}
+
+/// Maps each node reachable from `source` to the first hop of its shortest path.
+///
+/// This is the routing table a node would hold in a network simulation: to reach a
+/// destination, forward to its entry, which is always a neighbor of `source`. Nodes that
+/// cannot be reached are absent, and so is `source` itself. Shortest paths follow
+/// [`shortest_path_tree`], so the entries agree with the tree.
+///
+/// # Errors
+///
+/// - `NodeNotFound` if `source` is not in the graph.
+/// - `InvalidArgument` if a reachable weight is negative or NaN.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::routing_table;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(a, d, 1.0);
+///
+/// let table = routing_table(&g, a).unwrap();
+/// assert_eq!(table[&c], b);
+/// assert_eq!(table[&d], d);
+/// assert!(!table.contains_key(&a));
+/// ```
+pub fn routing_table<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+) -> Result<NodeMap<NodeId>>
+where
+    W: Copy + Into<f64> + Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    let (_, trace) = tree_search(graph, source)?;
+    let mut hops: Vec<Option<NodeId>> = vec![None; trace.len()];
+    let mut chain = Vec::new();
+    let mut table = NodeMap::default();
+    for v in graph.node_ids() {
+        // Climb to the first node whose hop is known or whose predecessor is `source`, then
+        // hand its hop down the nodes passed on the way.
+        let mut node = v;
+        let hop = loop {
+            if let Some(hop) = hops[node.index()] {
+                break Some(hop);
+            }
+            match trace[node.index()] {
+                Some(pred) if pred == source => break Some(node),
+                Some(pred) => {
+                    chain.push(node);
+                    node = pred;
+                }
+                None => break None,
+            }
+        };
+        let Some(hop) = hop else {
+            chain.clear();
+            continue;
+        };
+        hops[node.index()] = Some(hop);
+        for n in chain.drain(..) {
+            hops[n.index()] = Some(hop);
+        }
+        table.insert(v, hop);
+    }
+    Ok(table)
+}
+
+/// Full single-source search for [`shortest_path_tree`] and [`routing_table`].
+fn tree_search<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, source: NodeId) -> Result<DenseSearch>
+where
+    W: Copy + Into<f64> + Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    if !graph.contains_node(source) {
+        return Err(GraphinaError::node_not_found(format!(
+            "Node {:?} is not in the graph",
+            source
+        )));
+    }
+    dijkstra_search(
+        graph,
+        source,
+        |_| false,
+        None,
+        |_, _, &w| Some(w.into()),
+        |_| 0.0,
+    )
+}
+
+/// Distances and predecessors indexed by `NodeId::index()`.
+type DenseSearch = (Vec<Option<f64>>, Vec<Option<NodeId>>);
+
+/// Dense distances and predecessors for [`dijkstra_path_impl`], [`dijkstra_to_targets`],
+/// [`k_shortest_paths`], and [`a_star_impl`].
+///
+/// `eval_cost` sees each edge as `(from, to, weight)`, so callers can block individual
+/// edges or nodes. `stop` is called on each node as it is settled, and the search ends as
+/// soon as it returns true. `heuristic` turns the search into A*: nodes are settled in order
+/// of distance plus estimate, and a node is searched again if a shorter path to it turns up
+/// later, so an admissible but inconsistent estimate still finds shortest paths. Pass
+/// `|_| 0.0` for plain Dijkstra.
+fn dijkstra_search<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    mut stop: impl FnMut(NodeId) -> bool,
+    cutoff: Option<f64>,
+    eval_cost: impl Fn(NodeId, NodeId, &W) -> Option<f64>,
+    heuristic: impl Fn(NodeId) -> f64,
+) -> Result<DenseSearch>
+where
+    W: Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    // Dense, index-keyed buffers (see `dijkstra`).
     let bound = index_bound(graph);
     let mut dist: Vec<Option<f64>> = vec![None; bound];
     let mut trace: Vec<Option<NodeId>> = vec![None; bound];
     let mut heap = BinaryHeap::new();
-
+    let priority = |node: NodeId, d: NotNan<f64>| {
+        let h = heuristic(node);
+        NotNan::new(*d + h).map_err(|_| {
+            GraphinaError::invalid_argument(format!(
+                "A* requires not NaN estimates, but found estimate: {:?}, node: {:?}",
+                h, node
+            ))
+        })
+    };
+
+    let zero = NotNan::from(0u8);
     dist[source.index()] = Some(0.0);
+    heap.push(Reverse((priority(source, zero)?, zero, source)));
 
-    heap.push(Reverse((
-        NotNan::new(0.0).unwrap_or_else(|_| NotNan::new(1.0).unwrap_or(NotNan::from(1))),
-        source,
-    )));
-
-    while let Some(Reverse((d, u))) = heap.pop() {
+    while let Some(Reverse((_, d, u))) = heap.pop() {
         if let Some(current) = dist[u.index()] {
             if *d > current {
                 continue;
             }
         }
+        if stop(u) {
+            break;
+        }
         for (v, edge) in graph.outgoing_edges(u) {
-            let Some(w) = eval_cost(edge) else {
+            let Some(w) = eval_cost(u, v, edge) else {
                 continue;
             };
             if w.is_sign_negative() {
@@ -259,14 +587,11 @@ where
             if dist[vi].is_none() || Some(*next) < dist[vi] {
                 dist[vi] = Some(*next);
                 trace[vi] = Some(u);
-                heap.push(Reverse((next, v)));
+                heap.push(Reverse((priority(v, next)?, next, v)));
             }
         }
     }
-    Ok((
-        dense_to_nodemap(graph, &dist),
-        dense_to_nodemap(graph, &trace),
-    ))
+    Ok((dist, trace))
 }
 
 /// Full implementation of Dijkstra's algorithm for finding shortest paths in a graph
@@ -346,14 +671,81 @@ where
     W: Copy + PartialOrd + Add<Output = W> + Sub<Output = W> + From<u8> + Ord + Debug,
     Ty: GraphConstructor<A, W>,
     NodeId: Ord,
+{
+    let dist = dijkstra_dense(graph, source, W::from(0u8), |w| {
+        if w < W::from(0u8) {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Dijkstra requires nonnegative weights, but found weight: {:?}",
+                w
+            )));
+        }
+        Ok(())
+    })?;
+    Ok(dense_to_nodemap(graph, &dist))
+}
+
+/// Computes single-source shortest paths for weights that cannot be negative by type.
+///
+/// This is [`dijkstra`] with the nonnegativity check moved into the bound on `W`, either an
+/// unsigned integer or `f64` wrapped in [`NonNegativeF64`](crate::core::markers::NonNegativeF64).
+/// Each value is `Some(cost)` for a node reachable from `source` and `None` otherwise.
+///
+/// # Errors
+///
+/// Returns `NodeNotFound` if `source` is not in the graph, which is the only failure left.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::dijkstra_nonnegative;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), u32>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 4);
+/// g.add_edge(a, c, 1);
+/// g.add_edge(c, b, 2);
+///
+/// let distances = dijkstra_nonnegative(&g, a).unwrap();
+/// assert_eq!(distances[&b], Some(3));
+/// assert_eq!(dijkstra_nonnegative(&g, b).unwrap()[&a], None);
+/// ```
+pub fn dijkstra_nonnegative<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+) -> Result<NodeMap<Option<W>>>
+where
+    W: NonNegativeWeights,
+    Ty: GraphConstructor<A, W>,
+{
+    if !graph.contains_node(source) {
+        return Err(GraphinaError::node_not_found(
+            "Dijkstra source is not in the graph",
+        ));
+    }
+    let dist = dijkstra_dense(graph, source, W::ZERO, |_| Ok(()))?;
+    Ok(dense_to_nodemap(graph, &dist))
+}
+
+/// Dense distances from `source` for [`dijkstra`] and [`dijkstra_nonnegative`], calling
+/// `check` on every edge weight the search reads.
+fn dijkstra_dense<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    zero: W,
+    check: impl Fn(W) -> Result<()>,
+) -> Result<Vec<Option<W>>>
+where
+    W: Copy + Ord + Add<Output = W>,
+    Ty: GraphConstructor<A, W>,
 {
     // Dense, index-keyed distance buffer: `vec[id.index()]` is hash-free in the
     // inner loop. Converted to the `NodeMap` return type once at the end.
     let mut dist: Vec<Option<W>> = vec![None; index_bound(graph)];
     let mut heap = BinaryHeap::new();
 
-    dist[source.index()] = Some(W::from(0u8));
-    heap.push(Reverse((W::from(0u8), source)));
+    dist[source.index()] = Some(zero);
+    heap.push(Reverse((zero, source)));
 
     while let Some(Reverse((d, u))) = heap.pop() {
         if let Some(current) = dist[u.index()] {
@@ -362,21 +754,118 @@ where
             }
         }
         for (v, w) in outgoing_edges(graph, u) {
-            if w < W::from(0u8) {
+            check(w)?;
+            let next = d + w;
+            let vi = v.index();
+            if dist[vi].is_none() || Some(next) < dist[vi] {
+                dist[vi] = Some(next);
+                heap.push(Reverse((next, v)));
+            }
+        }
+    }
+    Ok(dist)
+}
+
+/// Computes shortest paths from a set of sources at once, for `f64` weights.
+///
+/// Every source starts at distance 0, so each node receives its distance to the
+/// nearest source together with that source. This is the primitive behind
+/// nearest-facility assignment and Voronoi-style partitions of a graph.
+///
+/// # Returns
+///
+/// A pair of NodeMaps with one entry per node:
+/// - the distance to the nearest source (`None` if unreachable or beyond `cutoff`), and
+/// - the nearest source itself (`None` if unreachable).
+///
+/// # Errors
+///
+/// - `NodeNotFound` if a source is not in the graph.
+/// - `InvalidArgument` if a negative or NaN weight is found.
+///
+/// # Complexity
+///
+/// - Time: O(E log V)
+/// - Space: O(V)
+///
+/// # Example
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::core::paths::multi_source_dijkstra;
+///
+/// let mut graph = Graph::new();
+/// let ids = (0..5).map(|i| graph.add_node(i)).collect::<Vec<_>>();
+/// for pair in ids.windows(2) {
+///     graph.add_edge(pair[0], pair[1], 1.0);
+/// }
+///
+/// let (dist, nearest) = multi_source_dijkstra(&graph, &[ids[0], ids[4]], None).unwrap();
+/// assert_eq!(dist[&ids[1]], Some(1.0));
+/// assert_eq!(nearest[&ids[1]], Some(ids[0]));
+/// assert_eq!(nearest[&ids[3]], Some(ids[4]));
+/// ```
+pub fn multi_source_dijkstra<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    sources: &[NodeId],
+    cutoff: Option<f64>,
+) -> Result<PathFindResult>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let bound = index_bound(graph);
+    let mut dist: Vec<Option<f64>> = vec![None; bound];
+    let mut nearest: Vec<Option<NodeId>> = vec![None; bound];
+    let mut heap = BinaryHeap::new();
+
+    for &s in sources {
+        if !graph.contains_node(s) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Source node {:?} is not in the graph",
+                s
+            )));
+        }
+        if dist[s.index()].is_none() {
+            dist[s.index()] = Some(0.0);
+            nearest[s.index()] = Some(s);
+            heap.push(Reverse((NotNan::from(0u8), s)));
+        }
+    }
+
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if let Some(current) = dist[u.index()] {
+            if *d > current {
+                continue;
+            }
+        }
+        let origin = nearest[u.index()];
+        for (v, w) in outgoing_edges(graph, u) {
+            let Ok(w) = NotNan::new(w) else {
+                return Err(GraphinaError::invalid_argument(
+                    "Dijkstra requires not NaN weights",
+                ));
+            };
+            if w.is_sign_negative() {
                 return Err(GraphinaError::invalid_argument(format!(
                     "Dijkstra requires nonnegative weights, but found weight: {:?}",
                     w
                 )));
             }
             let next = d + w;
+            if cutoff.is_some_and(|c| *next > c) {
+                continue;
+            }
             let vi = v.index();
-            if dist[vi].is_none() || Some(next) < dist[vi] {
-                dist[vi] = Some(next);
+            if dist[vi].is_none_or(|cur| *next < cur) {
+                dist[vi] = Some(*next);
+                nearest[vi] = origin;
                 heap.push(Reverse((next, v)));
             }
         }
     }
-    Ok(dense_to_nodemap(graph, &dist))
+    Ok((
+        dense_to_nodemap(graph, &dist),
+        dense_to_nodemap(graph, &nearest),
+    ))
 }
 
 /// ============================
@@ -531,75 +1020,538 @@ where
     }
 }
 
+/// Finds a shortest path from `source` to `target` with A*, using `f64` costs from
+/// `eval_cost`.
+///
+/// Unlike [`a_star`], the weight type needs no total order, so `f64`-weighted graphs work
+/// without wrapping every weight. `eval_cost` works as in [`dijkstra_path_impl`], returning
+/// `None` for an edge that cannot be used, and `heuristic` estimates the remaining cost from
+/// a node to `target`. An admissible estimate, one that never exceeds the true cost, gives a
+/// shortest path; `|_| 0.0` makes the search plain Dijkstra.
+///
+/// # Returns
+///
+/// `Some((cost, path))`, where `path` runs from `source` to `target` inclusive, or `None` if
+/// `target` cannot be reached.
+///
+/// # Errors
+///
+/// - `NodeNotFound` if `source` or `target` is not in the graph.
+/// - `InvalidArgument` if `eval_cost` returns a negative or NaN cost, or `heuristic` a NaN
+///   estimate.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::a_star_impl;
+/// use graphina::core::types::Graph;
+///
+/// // Points on a line, with a detour through the third one.
+/// let mut g = Graph::<f64, f64>::new();
+/// let ids: Vec<_> = [0.0, 1.0, 2.0, 3.0].map(|x| g.add_node(x)).to_vec();
+/// g.add_edge(ids[0], ids[1], 1.0);
+/// g.add_edge(ids[1], ids[3], 2.0);
+/// g.add_edge(ids[0], ids[2], 2.0);
+/// g.add_edge(ids[2], ids[3], 3.0);
+///
+/// let goal = *g.node_attr(ids[3]).unwrap();
+/// let heuristic = |n| (goal - g.node_attr(n).unwrap()).abs();
+/// let (cost, path) = a_star_impl(&g, ids[0], ids[3], heuristic, |w| Some(*w))
+///     .unwrap()
+///     .unwrap();
+/// assert_eq!(cost, 3.0);
+/// assert_eq!(path, vec![ids[0], ids[1], ids[3]]);
+/// ```
+pub fn a_star_impl<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    target: NodeId,
+    heuristic: impl Fn(NodeId) -> f64,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<(f64, Vec<NodeId>)>>
+where
+    W: Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    for node in [source, target] {
+        if !graph.contains_node(node) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Node {:?} is not in the graph",
+                node
+            )));
+        }
+    }
+    let (dist, trace) = dijkstra_search(
+        graph,
+        source,
+        |u| u == target,
+        None,
+        |_, _, w| eval_cost(w),
+        heuristic,
+    )?;
+    Ok(trace_path(source, target, &dist, &trace)
+        .map(|(path, costs)| (costs.last().copied().unwrap_or(0.0), path)))
+}
+
+/// ============================
+/// Simple Path Enumeration
+/// ============================
+///
+/// Enumerates every simple path (one that repeats no node) from `source` to `target`.
+///
+/// Paths follow outgoing edges on a directed graph and either direction on an undirected one.
+/// `max_length`, when given, bounds the number of edges in a path. Parallel edges do not
+/// produce duplicate paths, since a path is reported as its node sequence. A node is not
+/// considered to have a path to itself, so `source == target` yields no paths.
+///
+/// # Errors
+///
+/// Returns `NodeNotFound` if `source` or `target` is not in the graph.
+///
+/// # Complexity
+///
+/// The number of simple paths can grow exponentially with graph size, so bound `max_length`
+/// on anything but small or sparse graphs.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::all_simple_paths;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// let c = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(a, c, 1.0);
+///
+/// let mut paths = all_simple_paths(&g, a, c, None).unwrap();
+/// paths.sort();
+/// assert_eq!(paths, vec![vec![a, b, c], vec![a, c]]);
+/// assert_eq!(all_simple_paths(&g, a, c, Some(1)).unwrap(), vec![vec![a, c]]);
+/// ```
+pub fn all_simple_paths<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    target: NodeId,
+    max_length: Option<usize>,
+) -> Result<Vec<Vec<NodeId>>>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    for node in [source, target] {
+        if !graph.contains_node(node) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Node {:?} is not in the graph",
+                node
+            )));
+        }
+    }
+    let mut paths = Vec::new();
+    if source == target || max_length == Some(0) {
+        return Ok(paths);
+    }
+
+    let mut on_path = vec![false; index_bound(graph)];
+    let mut path = vec![source];
+    on_path[source.index()] = true;
+    // One frame per node on the current path, holding the neighbors still to try.
+    let mut frames: Vec<Vec<NodeId>> = vec![unique_neighbors(graph, source)];
+    while let Some(frame) = frames.last_mut() {
+        let Some(next) = frame.pop() else {
+            frames.pop();
+            if let Some(done) = path.pop() {
+                on_path[done.index()] = false;
+            }
+            continue;
+        };
+        if on_path[next.index()] {
+            continue;
+        }
+        if next == target {
+            let mut found = path.clone();
+            found.push(next);
+            paths.push(found);
+            continue;
+        }
+        // `path` holds `path.len() - 1` edges; stepping to `next` adds one more, and from
+        // there at least one further edge is needed to reach the target.
+        if max_length.is_some_and(|limit| path.len() + 1 > limit) {
+            continue;
+        }
+        on_path[next.index()] = true;
+        path.push(next);
+        frames.push(unique_neighbors(graph, next));
+    }
+    Ok(paths)
+}
+
+/// Neighbors of `u` with parallel edges collapsed, in reverse adjacency order so that popping
+/// from the back visits them in adjacency order.
+fn unique_neighbors<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, u: NodeId) -> Vec<NodeId>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut seen = Vec::new();
+    for v in graph.neighbors(u) {
+        if !seen.contains(&v) {
+            seen.push(v);
+        }
+    }
+    seen.reverse();
+    seen
+}
+
+/// ============================
+/// Yen's k Shortest Paths
+/// ============================
+///
+/// Finds the `k` cheapest loopless paths from `source` to `target`, ranked by total cost.
+///
+/// This is Yen's algorithm: each new path branches off one already found at a "spur" node,
+/// and the rest of it is the shortest path from there, found by the same Dijkstra search as
+/// [`dijkstra_path_impl`], that avoids the earlier nodes of the branch and every edge already
+/// taken out of the spur node by a found path with the same prefix.
+///
+/// `eval_cost` works as in [`dijkstra_path_impl`]: it returns the nonnegative cost of an
+/// edge, or `None` to make it impassable. A path is a node sequence, so parallel edges do not
+/// produce duplicate paths; the cheapest of them is used. Paths of equal cost are ordered by
+/// their node sequences, so the result is deterministic. Fewer than `k` paths are returned
+/// if fewer exist, none if `target` is unreachable, and only `[source]` at cost `0.0` when
+/// `source == target`.
+///
+/// # Errors
+///
+/// Returns `NodeNotFound` if `source` or `target` is not in the graph, `InvalidArgument` if
+/// `k == 0` or if a cost is negative or NaN.
+///
+/// # Complexity
+///
+/// O(k · n · (m + n log n)) time: up to `n` Dijkstra searches per path found.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::k_shortest_paths;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[3], 1.0);
+/// g.add_edge(n[0], n[2], 1.0);
+/// g.add_edge(n[2], n[3], 2.0);
+/// g.add_edge(n[0], n[3], 5.0);
+///
+/// let paths = k_shortest_paths(&g, n[0], n[3], 5, |w| Some(*w)).unwrap();
+/// assert_eq!(
+///     paths,
+///     vec![
+///         (2.0, vec![n[0], n[1], n[3]]),
+///         (3.0, vec![n[0], n[2], n[3]]),
+///         (5.0, vec![n[0], n[3]]),
+///     ]
+/// );
+/// ```
+pub fn k_shortest_paths<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    target: NodeId,
+    k: usize,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Vec<(f64, Vec<NodeId>)>>
+where
+    W: Debug,
+    Ty: GraphConstructor<A, W>,
+{
+    for node in [source, target] {
+        if !graph.contains_node(node) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Node {:?} is not in the graph",
+                node
+            )));
+        }
+    }
+    if k == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "k_shortest_paths requires k > 0",
+        ));
+    }
+    if source == target {
+        return Ok(vec![(0.0, vec![source])]);
+    }
+
+    let bound = index_bound(graph);
+    // Each found path is kept with the cost of every prefix, so a branch can reuse the
+    // cost of its root instead of summing edges again.
+    let mut found: Vec<(Vec<NodeId>, Vec<f64>)> = Vec::new();
+    let (dist, trace) = dijkstra_search(
+        graph,
+        source,
+        |u| u == target,
+        None,
+        |_, _, w| eval_cost(w),
+        |_| 0.0,
+    )?;
+    match trace_path(source, target, &dist, &trace) {
+        Some(first) => found.push(first),
+        None => return Ok(Vec::new()),
+    }
+
+    // Candidates keyed by (cost, path): the smallest key is the next path to accept, and
+    // equal paths reached from different spurs collapse into one entry.
+    let mut candidates: BTreeMap<(NotNan<f64>, Vec<NodeId>), Vec<f64>> = BTreeMap::new();
+    let mut blocked_nodes = vec![false; bound];
+    while found.len() < k {
+        let Some((last, last_costs)) = found.last() else {
+            break;
+        };
+        for spur in 0..last.len() - 1 {
+            let root = &last[..=spur];
+            let blocked_edges: Vec<(NodeId, NodeId)> = found
+                .iter()
+                .filter(|(path, _)| path.len() > spur + 1 && &path[..=spur] == root)
+                .map(|(path, _)| (path[spur], path[spur + 1]))
+                .collect();
+            for node in &root[..spur] {
+                blocked_nodes[node.index()] = true;
+            }
+            let search = dijkstra_search(
+                graph,
+                last[spur],
+                |u| u == target,
+                None,
+                |u, v, w| {
+                    if blocked_nodes[v.index()] || blocked_edges.contains(&(u, v)) {
+                        None
+                    } else {
+                        eval_cost(w)
+                    }
+                },
+                |_| 0.0,
+            );
+            for node in &root[..spur] {
+                blocked_nodes[node.index()] = false;
+            }
+            let (dist, trace) = search?;
+            let Some((tail, tail_costs)) = trace_path(last[spur], target, &dist, &trace) else {
+                continue;
+            };
+            let root_cost = last_costs[spur];
+            let mut path = root.to_vec();
+            path.extend_from_slice(&tail[1..]);
+            let mut costs = last_costs[..=spur].to_vec();
+            costs.extend(tail_costs[1..].iter().map(|c| root_cost + c));
+            let total = costs.last().copied().unwrap_or(0.0);
+            if let Ok(total) = NotNan::new(total) {
+                if !found.iter().any(|(p, _)| *p == path) {
+                    candidates.entry((total, path)).or_insert(costs);
+                }
+            }
+        }
+        match candidates.pop_first() {
+            Some(((_, path), costs)) => found.push((path, costs)),
+            None => break,
+        }
+    }
+
+    Ok(found
+        .into_iter()
+        .map(|(path, costs)| (costs.last().copied().unwrap_or(0.0), path))
+        .collect())
+}
+
+/// Follows `trace` back from `target` to `source`, returning the path and the cost of each
+/// of its prefixes, or `None` if `target` was not reached.
+fn trace_path(
+    source: NodeId,
+    target: NodeId,
+    dist: &[Option<f64>],
+    trace: &[Option<NodeId>],
+) -> Option<(Vec<NodeId>, Vec<f64>)> {
+    dist[target.index()]?;
+    let mut path = vec![target];
+    let mut node = target;
+    while node != source {
+        node = trace[node.index()]?;
+        path.push(node);
+    }
+    path.reverse();
+    let costs = path
+        .iter()
+        .map(|v| dist[v.index()].unwrap_or(0.0))
+        .collect();
+    Some((path, costs))
+}
+
+/// All-pairs distances in a dense matrix, from [`floyd_warshall_matrix`].
+///
+/// Row and column `i` belong to `nodes[i]`, in graph order. Node IDs need not be
+/// contiguous, so look positions up with [`position`](Self::position) rather than
+/// indexing by `NodeId::index()`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistanceMatrix<W> {
+    /// The nodes, in the order of the rows and columns.
+    pub nodes: Vec<NodeId>,
+    /// `distances[i][j]` is the cost of a shortest path from `nodes[i]` to `nodes[j]`, or
+    /// `None` if there is none. The diagonal is zero.
+    pub distances: Vec<Vec<Option<W>>>,
+    /// Position of each node, keyed by `NodeId::index()`.
+    positions: Vec<Option<usize>>,
+}
+
+impl<W: Copy> DistanceMatrix<W> {
+    fn new(nodes: Vec<NodeId>, distances: Vec<Vec<Option<W>>>) -> Self {
+        let bound = nodes.iter().map(|n| n.index() + 1).max().unwrap_or(0);
+        let mut positions = vec![None; bound];
+        for (i, node) in nodes.iter().enumerate() {
+            positions[node.index()] = Some(i);
+        }
+        DistanceMatrix {
+            nodes,
+            distances,
+            positions,
+        }
+    }
+
+    /// Returns the row and column of `node`, if it was in the graph.
+    pub fn position(&self, node: NodeId) -> Option<usize> {
+        self.positions.get(node.index()).copied().flatten()
+    }
+
+    /// Returns the distance from `source` to `target`, or `None` if either is unknown or
+    /// `target` is unreachable.
+    pub fn distance(&self, source: NodeId, target: NodeId) -> Option<W> {
+        self.distances[self.position(source)?][self.position(target)?]
+    }
+
+    /// Returns the distances from `source`, in the order of [`nodes`](Self::nodes).
+    pub fn row(&self, source: NodeId) -> Option<&[Option<W>]> {
+        Some(&self.distances[self.position(source)?])
+    }
+
+    /// Converts the matrix to nested maps, as [`floyd_warshall`] returns.
+    pub fn to_nodemap(&self) -> NodeMap<NodeMap<Option<W>>> {
+        self.nodes
+            .iter()
+            .zip(&self.distances)
+            .map(|(&u, row)| {
+                (
+                    u,
+                    self.nodes
+                        .iter()
+                        .copied()
+                        .zip(row.iter().copied())
+                        .collect(),
+                )
+            })
+            .collect()
+    }
+}
+
 /// ============================
 /// Floyd–Warshall Algorithm
 /// ============================
 ///
-/// Computes all‑pairs shortest paths using dynamic programming.
-/// Returns `Some(map)` where `map[u][v]` is:
-///     - `Some(cost)` if a path from node `u` to `v` exists, or
-///     - `None` if `v` is unreachable from `u`.
-/// Returns `None` if a negative cycle is detected.
+/// Computes all‑pairs shortest paths using dynamic programming, into a dense
+/// [`DistanceMatrix`]. Returns `None` if a negative cycle is detected.
+///
+/// The matrix holds one `Option<W>` per pair and nothing else, so it is the form to
+/// use on large graphs; [`floyd_warshall`] builds V^2 hash map entries on top of it.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::paths::floyd_warshall_matrix;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<(), i32>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 4);
+/// g.add_edge(b, c, -1);
+/// g.add_edge(a, c, 5);
+///
+/// let matrix = floyd_warshall_matrix(&g).unwrap();
+/// assert_eq!(matrix.distance(a, c), Some(3));
+/// assert_eq!(matrix.distance(c, a), None);
+/// ```
 ///
 /// # Complexity
 ///
 /// - **Time:** O(V^3)
 /// - **Space:** O(V^2)
-pub fn floyd_warshall<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Option<NodeMap<NodeMap<Option<W>>>>
+pub fn floyd_warshall_matrix<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Option<DistanceMatrix<W>>
 where
     W: Copy + PartialOrd + Add<Output = W> + From<u8>,
     Ty: GraphConstructor<A, W>,
 {
+    let zero = W::from(0u8);
     let n = graph.node_count();
-    let nodes: Vec<NodeId> = graph.node_ids().collect();
-    // Map each NodeId to its position in `nodes`. NodeId indices are not guaranteed
-    // to be contiguous (a StableGraph never recycles indices), so positions, not
-    // raw indices, address the matrix.
-    let pos: NodeMap<usize> = nodes.iter().enumerate().map(|(i, u)| (*u, i)).collect();
-
-    let mut dist = vec![vec![None; n]; n];
-    for (i, row) in dist.iter_mut().enumerate().take(n) {
-        row[i] = Some(W::from(0u8));
+    // NodeId indices are not guaranteed to be contiguous, so positions, not raw
+    // indices, address the matrix.
+    let mut matrix = DistanceMatrix::new(graph.node_ids().collect(), vec![vec![None; n]; n]);
+    let dist = &mut matrix.distances;
+    for (i, row) in dist.iter_mut().enumerate() {
+        row[i] = Some(zero);
     }
     // Populate via outgoing_edges so undirected edges (stored once) are recorded in
     // both directions, matching dijkstra and bellman_ford.
-    for (i, &u) in nodes.iter().enumerate() {
+    for (i, &u) in matrix.nodes.iter().enumerate() {
         for (v, w) in outgoing_edges(graph, u) {
-            let Some(&j) = pos.get(&v) else { continue };
+            let Some(j) = matrix.positions[v.index()] else {
+                continue;
+            };
             match dist[i][j] {
-                Some(current) if w < current => dist[i][j] = Some(w),
-                None => dist[i][j] = Some(w),
-                _ => {}
+                Some(current) if w >= current => {}
+                _ => dist[i][j] = Some(w),
             }
         }
     }
     for k in 0..n {
-        for i in 0..n {
-            for j in 0..n {
-                if let (Some(dik), Some(dkj)) = (dist[i][k], dist[k][j]) {
-                    let candidate = dik + dkj;
-                    match dist[i][j] {
-                        Some(dij) if candidate < dij => dist[i][j] = Some(candidate),
-                        None => dist[i][j] = Some(candidate),
-                        _ => {}
-                    }
+        // Row k does not change while k is the pivot (dist[k][k] is zero unless a
+        // negative cycle was found), so a copy lets every other row update in place.
+        let through = dist[k].clone();
+        for row in dist.iter_mut() {
+            let Some(dik) = row[k] else { continue };
+            for (dij, dkj) in row.iter_mut().zip(&through) {
+                let Some(dkj) = *dkj else { continue };
+                let candidate = dik + dkj;
+                match *dij {
+                    Some(current) if candidate >= current => {}
+                    _ => *dij = Some(candidate),
                 }
             }
         }
-    }
-    for (i, row) in dist.iter_mut().enumerate().take(n) {
-        row[i] = Some(W::from(0u8));
-    }
-    // Convert to NodeMap form
-    let mut outer: NodeMap<NodeMap<Option<W>>> = NodeMap::default();
-    for (i, u) in nodes.iter().enumerate() {
-        let mut inner: NodeMap<Option<W>> = NodeMap::default();
-        for (j, v) in nodes.iter().enumerate() {
-            inner.insert(*v, dist[i][j]);
+        // A negative cycle shows up as a negative diagonal entry. Stopping at once
+        // keeps the distances from spiraling towards overflow.
+        if (0..n).any(|i| dist[i][i].is_some_and(|d| d < zero)) {
+            return None;
         }
-        outer.insert(*u, inner);
     }
-    Some(outer)
+    Some(matrix)
+}
+
+/// Computes all‑pairs shortest paths using dynamic programming.
+/// Returns `Some(map)` where `map[u][v]` is:
+///     - `Some(cost)` if a path from node `u` to `v` exists, or
+///     - `None` if `v` is unreachable from `u`.
+/// Returns `None` if a negative cycle is detected.
+///
+/// This wraps [`floyd_warshall_matrix`], whose dense output avoids the V^2 hash map
+/// entries built here; prefer it for graphs beyond a few thousand nodes.
+///
+/// # Complexity
+///
+/// - **Time:** O(V^3)
+/// - **Space:** O(V^2)
+pub fn floyd_warshall<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Option<NodeMap<NodeMap<Option<W>>>>
+where
+    W: Copy + PartialOrd + Add<Output = W> + From<u8>,
+    Ty: GraphConstructor<A, W>,
+{
+    floyd_warshall_matrix(graph).map(|matrix| matrix.to_nodemap())
 }
 
 /// ============================
@@ -803,6 +1755,98 @@ where
 #[cfg(test)]
 mod tests {
 
+    #[test]
+    fn test_k_shortest_paths_matches_ranked_simple_paths() {
+        use crate::core::paths::{all_simple_paths, k_shortest_paths};
+        use crate::core::types::{Digraph, Graph, NodeId};
+
+        // Cheapest edge cost between consecutive nodes, summed along a path.
+        fn path_cost(g: &Digraph<(), f64>, path: &[NodeId]) -> f64 {
+            path.windows(2)
+                .map(|pair| {
+                    g.edges_between(pair[0], pair[1])
+                        .map(|(_, w)| *w)
+                        .fold(f64::INFINITY, f64::min)
+                })
+                .sum()
+        }
+
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<NodeId> = (0..7).map(|_| g.add_node(())).collect();
+        for i in 0..7 {
+            for j in 0..7 {
+                if i != j && (i * 5 + j * 3) % 4 != 0 {
+                    g.add_edge(n[i], n[j], ((i * 7 + j * 11) % 5 + 1) as f64);
+                }
+            }
+        }
+        g.add_edge(n[0], n[6], 0.5);
+        g.add_edge(n[0], n[6], 9.0);
+
+        let mut expected: Vec<(f64, Vec<NodeId>)> = all_simple_paths(&g, n[0], n[6], None)
+            .unwrap()
+            .into_iter()
+            .map(|p| (path_cost(&g, &p), p))
+            .collect();
+        expected.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        let ranked = k_shortest_paths(&g, n[0], n[6], 25, |w| Some(*w)).unwrap();
+        assert_eq!(ranked.len(), 25);
+        for (got, want) in ranked.iter().zip(&expected) {
+            assert!((got.0 - want.0).abs() < 1e-9);
+            assert!((path_cost(&g, &got.1) - got.0).abs() < 1e-9);
+        }
+        let all = k_shortest_paths(&g, n[0], n[6], usize::MAX, |w| Some(*w)).unwrap();
+        assert_eq!(all.len(), expected.len());
+
+        // Undirected, with an impassable edge.
+        let mut u = Graph::<(), f64>::new();
+        let m: Vec<NodeId> = (0..4).map(|_| u.add_node(())).collect();
+        u.add_edge(m[0], m[1], 1.0);
+        u.add_edge(m[1], m[3], 1.0);
+        u.add_edge(m[2], m[0], 1.0);
+        u.add_edge(m[3], m[2], -1.0);
+        let paths = k_shortest_paths(&u, m[3], m[0], 3, |w| (*w > 0.0).then_some(*w)).unwrap();
+        assert_eq!(paths, vec![(2.0, vec![m[3], m[1], m[0]])]);
+        assert!(k_shortest_paths(&u, m[3], m[0], 3, |w| Some(*w)).is_err());
+        assert!(k_shortest_paths(&u, m[0], m[1], 0, |w| Some(*w)).is_err());
+        assert_eq!(
+            k_shortest_paths(&u, m[2], m[2], 3, |w| Some(*w)).unwrap(),
+            vec![(0.0, vec![m[2]])]
+        );
+    }
+
+    #[test]
+    fn test_all_simple_paths_undirected_with_limit() {
+        use crate::core::paths::all_simple_paths;
+        use crate::core::types::Graph;
+
+        // Square a-b-c-d-a with a chord b-d (doubled to check parallel edges).
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        for (u, v) in [(0, 1), (1, 2), (2, 3), (3, 0), (1, 3), (1, 3)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let mut all = all_simple_paths(&g, n[0], n[2], None).unwrap();
+        all.sort();
+        assert_eq!(
+            all,
+            vec![
+                vec![n[0], n[1], n[2]],
+                vec![n[0], n[1], n[3], n[2]],
+                vec![n[0], n[3], n[1], n[2]],
+                vec![n[0], n[3], n[2]],
+            ]
+        );
+        assert_eq!(all_simple_paths(&g, n[0], n[2], Some(2)).unwrap().len(), 2);
+        assert!(all_simple_paths(&g, n[0], n[0], None).unwrap().is_empty());
+
+        g.remove_node(n[3]);
+        assert!(all_simple_paths(&g, n[0], n[3], None).is_err());
+        assert_eq!(
+            all_simple_paths(&g, n[0], n[2], None).unwrap(),
+            vec![vec![n[0], n[1], n[2]]]
+        );
+    }
     #[test]
     fn test_dijkstra_negative_weights() {
         use crate::core::paths::dijkstra_path_f64;
@@ -1092,4 +2136,190 @@ mod tests {
         assert_eq!(matrix[&n0][&n2], Some(OrderedFloat(3.0)));
         assert_eq!(matrix[&n1][&n3], Some(OrderedFloat(5.0)));
     }
+
+    #[test]
+    fn test_floyd_warshall_matrix_matches_maps_and_finds_negative_cycles() {
+        use crate::core::types::Digraph;
+        let mut g = Digraph::<(), i64>::new();
+        let n: Vec<NodeId> = (0..5).map(|_| g.add_node(())).collect();
+        for (u, v, w) in [
+            (0, 1, 4),
+            (1, 2, -2),
+            (0, 2, 3),
+            (2, 4, 1),
+            (4, 0, 7),
+            (3, 4, 1),
+        ] {
+            g.add_edge(n[u], n[v], w);
+        }
+        // Positions must not be confused with IDs once a node is gone.
+        g.remove_node(n[3]);
+        let matrix = floyd_warshall_matrix(&g).unwrap();
+        assert_eq!(matrix.nodes.len(), 4);
+        assert_eq!(matrix.position(n[3]), None);
+        assert_eq!(matrix.position(n[4]), Some(3));
+        assert_eq!(matrix.distance(n[0], n[4]), Some(3));
+        assert_eq!(matrix.distance(n[4], n[1]), Some(11));
+        assert_eq!(
+            matrix.row(n[2]).unwrap(),
+            [Some(8), Some(12), Some(0), Some(1)]
+        );
+        assert_eq!(floyd_warshall(&g).unwrap(), matrix.to_nodemap());
+
+        g.add_edge(n[2], n[1], 1);
+        assert!(floyd_warshall_matrix(&g).is_none());
+        assert!(floyd_warshall(&g).is_none());
+        let mut looped = Digraph::<(), i64>::new();
+        let a = looped.add_node(());
+        looped.add_edge(a, a, -1);
+        assert!(floyd_warshall_matrix(&looped).is_none());
+    }
+
+    #[test]
+    fn test_multi_source_dijkstra_directed() {
+        // 0 -> 1 -> 2 <- 3: node 2 is nearer to source 3, and node 0 is reachable
+        // from neither source because edges are followed forward only.
+        use crate::core::paths::multi_source_dijkstra;
+        use crate::core::types::Digraph;
+        let mut g = Digraph::<i32, f64>::new();
+        let n: Vec<_> = (0..4).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 5.0);
+        g.add_edge(n[3], n[2], 2.0);
+        let (dist, nearest) = multi_source_dijkstra(&g, &[n[1], n[3]], None).unwrap();
+        assert_eq!(dist[&n[2]], Some(2.0));
+        assert_eq!(nearest[&n[2]], Some(n[3]));
+        assert_eq!(dist[&n[0]], None);
+        assert_eq!(nearest[&n[0]], None);
+
+        let (capped, _) = multi_source_dijkstra(&g, &[n[0]], Some(3.0)).unwrap();
+        assert_eq!(capped[&n[2]], None);
+
+        g.remove_node(n[3]);
+        assert!(multi_source_dijkstra(&g, &[n[3]], None).is_err());
+    }
+
+    #[test]
+    fn test_dijkstra_to_targets_stops_early() {
+        use crate::core::paths::{TargetStop, dijkstra_to_targets};
+        use crate::core::types::Digraph;
+        // 0 -> 1 -> 2 -> 3, with a blocked shortcut 0 -> 3 and an isolated node 4.
+        let mut g = Digraph::<(), (f64, bool)>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], (1.0, true));
+        g.add_edge(n[1], n[2], (1.0, true));
+        g.add_edge(n[2], n[3], (1.0, true));
+        g.add_edge(n[0], n[3], (0.5, false));
+        let open = |&(w, ok): &(f64, bool)| ok.then_some(w);
+
+        let found =
+            dijkstra_to_targets(&g, n[0], &[n[3], n[4], n[0]], TargetStop::All, open).unwrap();
+        assert_eq!(found.len(), 2);
+        assert_eq!(found[&n[0]], (0.0, vec![n[0]]));
+        assert_eq!(found[&n[3]], (3.0, vec![n[0], n[1], n[2], n[3]]));
+
+        // The search stops at node 1, before node 2 is settled, so only node 1 is reported.
+        let nearest =
+            dijkstra_to_targets(&g, n[0], &[n[2], n[1], n[1]], TargetStop::First, open).unwrap();
+        assert_eq!(nearest.keys().collect::<Vec<_>>(), vec![&n[1]]);
+        assert!(
+            dijkstra_to_targets(&g, n[0], &[], TargetStop::All, open)
+                .unwrap()
+                .is_empty()
+        );
+
+        g.remove_node(n[4]);
+        assert!(dijkstra_to_targets(&g, n[0], &[n[4]], TargetStop::All, open).is_err());
+    }
+
+    #[test]
+    fn test_a_star_impl_with_f64_costs() {
+        use crate::core::paths::{a_star_impl, dijkstra_path_f64};
+        use crate::core::types::Digraph;
+        // 0 -> 1 -> 3 costs 3, 0 -> 2 -> 3 costs 4, and 4 is unreachable.
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[3], 2.0);
+        g.add_edge(n[0], n[2], 1.0);
+        g.add_edge(n[2], n[3], 3.0);
+        let cost = |w: &f64| Some(*w);
+
+        let (dist, _) = dijkstra_path_f64(&g, n[0], None).unwrap();
+        for &t in &n {
+            let found = a_star_impl(&g, n[0], t, |_| 0.0, cost).unwrap();
+            assert_eq!(found.map(|(c, _)| c), dist[&t]);
+        }
+
+        // An exact estimate for node 1 delays it behind node 2, but the path still goes
+        // through it.
+        let h = |v: NodeId| if v == n[1] { 2.0 } else { 0.0 };
+        let found = a_star_impl(&g, n[0], n[3], h, cost).unwrap();
+        assert_eq!(found, Some((3.0, vec![n[0], n[1], n[3]])));
+
+        let blocked = |w: &f64| (*w != 2.0).then_some(*w);
+        let found = a_star_impl(&g, n[0], n[3], |_| 0.0, blocked).unwrap();
+        assert_eq!(found, Some((4.0, vec![n[0], n[2], n[3]])));
+        assert_eq!(
+            a_star_impl(&g, n[0], n[0], |_| 0.0, cost).unwrap(),
+            Some((0.0, vec![n[0]]))
+        );
+
+        assert!(a_star_impl(&g, n[0], n[3], |_| f64::NAN, cost).is_err());
+        assert!(a_star_impl(&g, n[0], n[3], |_| 0.0, |w| Some(-w)).is_err());
+        assert!(a_star_impl(&g, n[0], n[3], |_| 0.0, |_| Some(f64::NAN)).is_err());
+        g.remove_node(n[4]);
+        assert!(a_star_impl(&g, n[0], n[4], |_| 0.0, cost).is_err());
+    }
+
+    #[test]
+    fn test_shortest_path_tree_and_routing_table() {
+        use crate::core::generators::erdos_renyi_graph;
+        use crate::core::paths::{dijkstra_path_f64, routing_table, shortest_path_tree};
+        use crate::core::types::{Digraph, Undirected};
+
+        // Varied weights, so shortest paths are mostly unique.
+        let g = erdos_renyi_graph::<Undirected>(30, 0.15, 5)
+            .unwrap()
+            .map_edge_weights(|e, _| 1.0 + (e.index() * 7 % 11) as f64);
+        let source = g.node_ids().next().unwrap();
+        let tree = shortest_path_tree(&g, source).unwrap();
+        let table = routing_table(&g, source).unwrap();
+        let (dist, _) = dijkstra_path_f64(&g, source, None).unwrap();
+        let (tree_dist, tree_trace) = dijkstra_path_f64(&tree, source, None).unwrap();
+        let reached = dist.values().filter(|d| d.is_some()).count();
+        assert_eq!(tree.node_count(), reached);
+        assert_eq!(tree.edge_count(), reached - 1);
+        for v in tree.node_ids() {
+            assert_eq!(tree_dist[&v], dist[&v]);
+            if v == source {
+                continue;
+            }
+            let mut hop = v;
+            while tree_trace[&hop] != Some(source) {
+                hop = tree_trace[&hop].unwrap();
+            }
+            assert_eq!(table[&v], hop);
+        }
+        assert_eq!(table.len(), reached - 1);
+
+        // Directed, with a cheaper parallel edge and a node only reachable against direction.
+        let mut d = Digraph::<(), f64>::new();
+        let [a, b, c, x] = [(); 4].map(|_| d.add_node(()));
+        d.add_edge(a, b, 2.0);
+        let cheap = d.add_edge(a, b, 1.0);
+        let bc = d.add_edge(b, c, 1.0);
+        d.add_edge(x, a, 1.0);
+        let tree = shortest_path_tree(&d, a).unwrap();
+        assert_eq!(tree.edge_ids().collect::<Vec<_>>(), vec![cheap, bc]);
+        assert!(!tree.contains_node(x));
+        let table = routing_table(&d, a).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!((table[&b], table[&c]), (b, b));
+
+        d.add_edge(c, a, -1.0);
+        assert!(routing_table(&d, a).is_err());
+        d.remove_node(x);
+        assert!(shortest_path_tree(&d, x).is_err());
+    }
 }
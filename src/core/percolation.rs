@@ -0,0 +1,343 @@
+/*!
+# Percolation
+
+How the largest connected component shrinks as nodes (site percolation) or edges (bond
+percolation) are removed, for resilience studies of infrastructure networks and for the
+epidemic threshold of contact networks. A random removal order models failures, and a
+highest-degree-first order models a targeted attack.
+
+[`percolation_curve`] follows Newman and Ziff (2000): rather than searching the graph again
+after every removal, it adds the elements back in reverse order with a union-find, so the
+whole curve costs little more than one pass over the edges. Read the curve from the end to get
+the occupation (adding) direction.
+
+Components are weak on directed graphs: edge direction is ignored.
+*/
+
+use std::collections::HashSet;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+use crate::core::union_find::UnionFind;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+
+/// The order in which [`percolation_curve`] removes nodes or edges.
+///
+/// Elements not listed stay in the graph throughout, so a partial order removes only a
+/// subset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PercolationOrder {
+    /// Site percolation: remove these nodes, with their edges, in order.
+    Sites(Vec<NodeId>),
+    /// Bond percolation: remove these edges in order, keeping every node.
+    Bonds(Vec<EdgeId>),
+}
+
+impl PercolationOrder {
+    /// Every node, in an order shuffled by `seed`.
+    pub fn random_sites<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, seed: u64) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut nodes: Vec<NodeId> = graph.node_ids().collect();
+        nodes.shuffle(&mut StdRng::seed_from_u64(seed));
+        PercolationOrder::Sites(nodes)
+    }
+
+    /// Every edge, in an order shuffled by `seed`.
+    pub fn random_bonds<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, seed: u64) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut edges: Vec<EdgeId> = graph.edge_ids().collect();
+        edges.shuffle(&mut StdRng::seed_from_u64(seed));
+        PercolationOrder::Bonds(edges)
+    }
+
+    /// Every node, highest degree first, with ties in node order. Degrees are those of the
+    /// intact graph.
+    pub fn highest_degree_first<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut nodes: Vec<NodeId> = graph.node_ids().collect();
+        nodes.sort_by_key(|&v| std::cmp::Reverse(graph.degree(v).unwrap_or(0)));
+        PercolationOrder::Sites(nodes)
+    }
+
+    /// Returns the number of elements to remove.
+    pub fn len(&self) -> usize {
+        match self {
+            PercolationOrder::Sites(nodes) => nodes.len(),
+            PercolationOrder::Bonds(edges) => edges.len(),
+        }
+    }
+
+    /// Returns `true` if nothing is removed.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// One point of a percolation curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PercolationPoint {
+    /// Number of elements of the order removed so far.
+    pub removed: usize,
+    /// Share of the graph's nodes (site) or edges (bond) still present.
+    pub occupied: f64,
+    /// Size of the largest component as a share of the graph's nodes, removed ones included.
+    pub giant: f64,
+}
+
+/// Computes the size of the giant component as the elements of `order` are removed, one
+/// point every `step` removals.
+///
+/// The curve starts at the intact graph (`removed == 0`) and always ends once every listed
+/// element is gone, even when `step` does not divide their number. Fractions are of the
+/// graph's node or edge count, and `0.0` for an empty graph.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if `step` is zero, or if the order lists an element twice or
+/// one that is not in the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::percolation::{PercolationOrder, percolation_curve};
+/// use graphina::core::types::Graph;
+///
+/// // A star: removing the hub shatters it, removing a leaf barely matters.
+/// let mut g = Graph::<(), f64>::new();
+/// let hub = g.add_node(());
+/// for _ in 0..4 {
+///     let leaf = g.add_node(());
+///     g.add_edge(hub, leaf, 1.0);
+/// }
+///
+/// let attack = percolation_curve(&g, &PercolationOrder::highest_degree_first(&g), 1).unwrap();
+/// assert_eq!(attack[0].giant, 1.0);
+/// assert_eq!(attack[1].giant, 0.2);
+/// ```
+pub fn percolation_curve<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    order: &PercolationOrder,
+    step: usize,
+) -> Result<Vec<PercolationPoint>>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    if step == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "Percolation step must be positive",
+        ));
+    }
+    let bound = graph.node_ids().map(|v| v.index() + 1).max().unwrap_or(0);
+    let mut sizes = Sizes::new(bound);
+    let total = match order {
+        PercolationOrder::Sites(order) => {
+            check_distinct(order.iter().map(|&v| (v.index(), graph.contains_node(v))))?;
+            let removed: HashSet<NodeId> = order.iter().copied().collect();
+            let mut present = vec![false; bound];
+            let mut restore = |v: NodeId, sizes: &mut Sizes| {
+                present[v.index()] = true;
+                sizes.add(v.index());
+                for u in graph.inner.neighbors_undirected(v.0) {
+                    if present[u.index()] {
+                        sizes.union(u.index(), v.index());
+                    }
+                }
+            };
+            for v in graph.node_ids().filter(|v| !removed.contains(v)) {
+                restore(v, &mut sizes);
+            }
+            sizes.record();
+            for &v in order.iter().rev() {
+                restore(v, &mut sizes);
+                sizes.record();
+            }
+            graph.node_count()
+        }
+        PercolationOrder::Bonds(order) => {
+            check_distinct(
+                order
+                    .iter()
+                    .map(|&e| (e.index(), graph.edge_weight(e).is_some())),
+            )?;
+            let removed: HashSet<EdgeId> = order.iter().copied().collect();
+            for v in graph.node_ids() {
+                sizes.add(v.index());
+            }
+            for (e, u, v, _) in graph.edges_with_ids() {
+                if !removed.contains(&e) {
+                    sizes.union(u.index(), v.index());
+                }
+            }
+            sizes.record();
+            for &e in order.iter().rev() {
+                if let Some((u, v)) = graph.inner.edge_endpoints(e.0) {
+                    sizes.union(u.index(), v.index());
+                }
+                sizes.record();
+            }
+            graph.edge_count()
+        }
+    };
+    // The history runs from everything removed back to the intact graph.
+    let mut giant = sizes.history;
+    giant.reverse();
+    let nodes = graph.node_count();
+    let fraction = |count: usize, of: usize| {
+        if of == 0 {
+            0.0
+        } else {
+            count as f64 / of as f64
+        }
+    };
+    let point = |removed: usize| PercolationPoint {
+        removed,
+        occupied: fraction(total - removed, total),
+        giant: fraction(giant[removed], nodes),
+    };
+    let mut points: Vec<PercolationPoint> = (0..=order.len()).step_by(step).map(point).collect();
+    if order.len() % step != 0 {
+        points.push(point(order.len()));
+    }
+    Ok(points)
+}
+
+/// Fails if an `(index, exists)` pair names a missing element or repeats an index.
+fn check_distinct(elements: impl Iterator<Item = (usize, bool)>) -> Result<()> {
+    let mut seen = HashSet::new();
+    for (index, exists) in elements {
+        if !exists {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Percolation order lists {index}, which is not in the graph"
+            )));
+        }
+        if !seen.insert(index) {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Percolation order lists {index} more than once"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Union-find over node indices that tracks the size of the largest component.
+struct Sizes {
+    sets: UnionFind,
+    size: Vec<usize>,
+    largest: usize,
+    /// The largest component size at every recorded state.
+    history: Vec<usize>,
+}
+
+impl Sizes {
+    fn new(bound: usize) -> Self {
+        Sizes {
+            sets: UnionFind::new(bound),
+            size: vec![0; bound],
+            largest: 0,
+            history: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, v: usize) {
+        self.size[v] = 1;
+        self.largest = self.largest.max(1);
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.sets.find(a), self.sets.find(b));
+        if self.sets.union(ra, rb) {
+            let root = self.sets.find(ra);
+            self.size[root] = self.size[ra] + self.size[rb];
+            self.largest = self.largest.max(self.size[root]);
+        }
+    }
+
+    fn record(&mut self) {
+        self.history.push(self.largest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generators::erdos_renyi_graph;
+    use crate::core::types::{Directed, Undirected};
+
+    /// Largest weak component of `graph`, found by searching from scratch.
+    fn largest<A: Clone, W: Clone, Ty: GraphConstructor<A, W>>(
+        graph: &BaseGraph<A, W, Ty>,
+    ) -> usize {
+        let mut sets = UnionFind::new(graph.node_ids().map(|v| v.index() + 1).max().unwrap_or(0));
+        for (u, v, _) in graph.edges() {
+            sets.union(u.index(), v.index());
+        }
+        let mut counts = std::collections::HashMap::new();
+        for v in graph.node_ids() {
+            *counts.entry(sets.find(v.index())).or_insert(0) += 1;
+        }
+        counts.into_values().max().unwrap_or(0)
+    }
+
+    #[test]
+    fn test_percolation_curve_matches_removal_one_by_one() {
+        let g = erdos_renyi_graph::<Directed>(40, 0.06, 7).unwrap();
+        let n = g.node_count() as f64;
+        let sites = PercolationOrder::random_sites(&g, 3);
+        let bonds = PercolationOrder::random_bonds(&g, 3);
+        for order in [&sites, &bonds] {
+            let curve = percolation_curve(&g, order, 3).unwrap();
+            assert_eq!(curve.len(), order.len().div_ceil(3) + 1);
+            assert_eq!(curve.last().unwrap().removed, order.len());
+            let mut h = g.clone();
+            let mut removed = 0;
+            for point in curve {
+                while removed < point.removed {
+                    match order {
+                        PercolationOrder::Sites(nodes) => {
+                            h.remove_node(nodes[removed]);
+                        }
+                        PercolationOrder::Bonds(edges) => {
+                            h.remove_edge(edges[removed]);
+                        }
+                    }
+                    removed += 1;
+                }
+                assert_eq!(
+                    point.giant,
+                    largest(&h) as f64 / n,
+                    "{order:?} at {removed}"
+                );
+            }
+        }
+
+        // A partial order leaves the other nodes in place.
+        let g = erdos_renyi_graph::<Undirected>(30, 0.2, 1).unwrap();
+        let hubs = match PercolationOrder::highest_degree_first(&g) {
+            PercolationOrder::Sites(nodes) => nodes[..5].to_vec(),
+            PercolationOrder::Bonds(_) => unreachable!(),
+        };
+        let curve = percolation_curve(&g, &PercolationOrder::Sites(hubs.clone()), 10).unwrap();
+        assert_eq!(curve.len(), 2);
+        assert!((curve[1].occupied - 25.0 / 30.0).abs() < 1e-12);
+        let mut h = g.clone();
+        hubs.iter().for_each(|&v| {
+            h.remove_node(v);
+        });
+        assert_eq!(curve[1].giant, largest(&h) as f64 / 30.0);
+
+        assert!(percolation_curve(&g, &sites, 0).is_err());
+        let twice = PercolationOrder::Sites(vec![hubs[0], hubs[0]]);
+        assert!(percolation_curve(&g, &twice, 1).is_err());
+        let empty = BaseGraph::<u32, f32, Undirected>::new();
+        let curve = percolation_curve(&empty, &PercolationOrder::Sites(vec![]), 1).unwrap();
+        assert_eq!(curve[0].giant, 0.0);
+    }
+}
@@ -0,0 +1,338 @@
+/*!
+# Graph Products
+
+Constructors that combine two graphs `G` and `H` into one whose nodes are the pairs `(u, v)`
+with `u` in `G` and `v` in `H`. The products differ only in which pairs are adjacent:
+
+- **Cartesian** `G □ H`: one coordinate stays fixed while the other follows an edge. Grids
+  are products of paths, and hypercubes are repeated products of a single edge.
+- **Tensor** `G × H`: both coordinates follow an edge at the same time.
+- **Strong** `G ⊠ H`: the union of the Cartesian and tensor products, so king's-move grids
+  are strong products of paths.
+- **Lexicographic** `G[H]`: every node of `G` is replaced by a copy of `H`, and copies whose
+  `G` nodes are adjacent are joined completely.
+
+The factors must share a direction type and a weight type. Each product node carries a clone
+of both attributes, and the returned map gives the product node for every pair of factor
+nodes. In directed products every edge keeps the orientation of the factor edges it comes
+from.
+*/
+
+use std::collections::HashMap;
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::EdgeType;
+
+/// Product nodes, keyed by the pair of factor nodes they stand for.
+pub type ProductMap = HashMap<(NodeId, NodeId), NodeId>;
+
+fn product_nodes<A, B, W, Ty>(
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+    edges: usize,
+) -> (BaseGraph<(A, B), W, Ty>, ProductMap)
+where
+    A: Clone,
+    B: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+{
+    let mut product =
+        BaseGraph::<(A, B), W, Ty>::with_capacity(g.node_count() * h.node_count(), edges);
+    let mut pairs = HashMap::with_capacity(g.node_count() * h.node_count());
+    for (u, a) in g.nodes() {
+        for (v, b) in h.nodes() {
+            pairs.insert((u, v), product.add_node((a.clone(), b.clone())));
+        }
+    }
+    (product, pairs)
+}
+
+/// Adds the edges of the Cartesian product: `G` edges copied for each node of `H`, and `H`
+/// edges for each node of `G`.
+fn add_cartesian_edges<A, B, W, Ty>(
+    product: &mut BaseGraph<(A, B), W, Ty>,
+    pairs: &ProductMap,
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+) where
+    W: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+{
+    for (u, u2, w) in g.edges() {
+        for v in h.node_ids() {
+            product.add_edge(pairs[&(u, v)], pairs[&(u2, v)], w.clone());
+        }
+    }
+    for (v, v2, w) in h.edges() {
+        for u in g.node_ids() {
+            product.add_edge(pairs[&(u, v)], pairs[&(u, v2)], w.clone());
+        }
+    }
+}
+
+/// Adds the edges of the tensor product. An undirected pair of non-loop edges yields two
+/// product edges, one for each way of matching their endpoints.
+fn add_tensor_edges<A, B, W, Ty, F>(
+    product: &mut BaseGraph<(A, B), W, Ty>,
+    pairs: &ProductMap,
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+    combine: F,
+) where
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+    F: Fn(&W, &W) -> W,
+{
+    let directed = g.is_directed();
+    for (u, u2, wg) in g.edges() {
+        for (v, v2, wh) in h.edges() {
+            product.add_edge(pairs[&(u, v)], pairs[&(u2, v2)], combine(wg, wh));
+            if !directed && u != u2 && v != v2 {
+                product.add_edge(pairs[&(u, v2)], pairs[&(u2, v)], combine(wg, wh));
+            }
+        }
+    }
+}
+
+/// Builds the Cartesian product `G □ H`.
+///
+/// `(u, v)` and `(u', v')` are adjacent when `u = u'` and `v - v'` is an edge of `H`, or
+/// `v = v'` and `u - u'` is an edge of `G`. Each product edge keeps the weight of the factor
+/// edge it copies.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::products::cartesian_product;
+/// use graphina::core::types::Graph;
+///
+/// // The product of two 3-node paths is a 3x3 grid.
+/// let mut path = Graph::<(), f64>::new();
+/// let p: Vec<_> = (0..3).map(|_| path.add_node(())).collect();
+/// path.add_edge(p[0], p[1], 1.0);
+/// path.add_edge(p[1], p[2], 1.0);
+///
+/// let (grid, pairs) = cartesian_product(&path, &path);
+/// assert_eq!(grid.node_count(), 9);
+/// assert_eq!(grid.edge_count(), 12);
+/// assert_eq!(grid.degree(pairs[&(p[1], p[1])]), Some(4));
+/// ```
+pub fn cartesian_product<A, B, W, Ty>(
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+) -> (BaseGraph<(A, B), W, Ty>, ProductMap)
+where
+    A: Clone,
+    B: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+{
+    let edges = g.edge_count() * h.node_count() + h.edge_count() * g.node_count();
+    let (mut product, pairs) = product_nodes(g, h, edges);
+    add_cartesian_edges(&mut product, &pairs, g, h);
+    (product, pairs)
+}
+
+/// Builds the tensor (categorical, Kronecker) product `G × H`.
+///
+/// `(u, v)` and `(u', v')` are adjacent when `u - u'` is an edge of `G` and `v - v'` is an
+/// edge of `H`. `combine` receives the two factor weights and returns the weight of the
+/// product edge.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::products::tensor_product;
+/// use graphina::core::types::Graph;
+///
+/// // The tensor product of two single edges is a pair of disjoint edges.
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 2.0);
+///
+/// let (t, pairs) = tensor_product(&g, &g, |x, y| x * y);
+/// assert_eq!(t.edge_count(), 2);
+/// assert!(t.contains_edge(pairs[&(a, a)], pairs[&(b, b)]));
+/// assert!(t.contains_edge(pairs[&(a, b)], pairs[&(b, a)]));
+/// assert!(t.edges().all(|(_, _, &w)| w == 4.0));
+/// ```
+pub fn tensor_product<A, B, W, Ty, F>(
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+    combine: F,
+) -> (BaseGraph<(A, B), W, Ty>, ProductMap)
+where
+    A: Clone,
+    B: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+    F: Fn(&W, &W) -> W,
+{
+    let (mut product, pairs) = product_nodes(g, h, 2 * g.edge_count() * h.edge_count());
+    add_tensor_edges(&mut product, &pairs, g, h, combine);
+    (product, pairs)
+}
+
+/// Builds the strong product `G ⊠ H`, the union of [`cartesian_product`] and
+/// [`tensor_product`].
+///
+/// Cartesian edges keep their factor weight and tensor edges take `combine` of both factor
+/// weights.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::products::strong_product;
+/// use graphina::core::types::Graph;
+///
+/// // Two single edges give the complete graph on four nodes.
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+///
+/// let (s, _) = strong_product(&g, &g, |x, y| x.max(*y));
+/// assert_eq!(s.node_count(), 4);
+/// assert_eq!(s.edge_count(), 6);
+/// ```
+pub fn strong_product<A, B, W, Ty, F>(
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+    combine: F,
+) -> (BaseGraph<(A, B), W, Ty>, ProductMap)
+where
+    A: Clone,
+    B: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+    F: Fn(&W, &W) -> W,
+{
+    let edges = g.edge_count() * h.node_count()
+        + h.edge_count() * g.node_count()
+        + 2 * g.edge_count() * h.edge_count();
+    let (mut product, pairs) = product_nodes(g, h, edges);
+    add_cartesian_edges(&mut product, &pairs, g, h);
+    add_tensor_edges(&mut product, &pairs, g, h, combine);
+    (product, pairs)
+}
+
+/// Builds the lexicographic product (composition) `G[H]`.
+///
+/// `(u, v)` and `(u', v')` are adjacent when `u - u'` is an edge of `G`, whatever `v` and `v'`
+/// are, or when `u = u'` and `v - v'` is an edge of `H`. Edges of the first kind carry the
+/// `G` weight and edges of the second kind the `H` weight. Unlike the other products this one
+/// is not symmetric: `G[H]` and `H[G]` usually differ.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::products::lexicographic_product;
+/// use graphina::core::types::Graph;
+///
+/// // Replacing both ends of an edge with two isolated nodes gives the 4-cycle K(2, 2).
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+/// let mut h = Graph::<(), f64>::new();
+/// h.add_node(());
+/// h.add_node(());
+///
+/// let (l, _) = lexicographic_product(&g, &h);
+/// assert_eq!(l.node_count(), 4);
+/// assert_eq!(l.edge_count(), 4);
+/// assert!(l.node_ids().all(|v| l.degree(v) == Some(2)));
+/// ```
+pub fn lexicographic_product<A, B, W, Ty>(
+    g: &BaseGraph<A, W, Ty>,
+    h: &BaseGraph<B, W, Ty>,
+) -> (BaseGraph<(A, B), W, Ty>, ProductMap)
+where
+    A: Clone,
+    B: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<B, W> + GraphConstructor<(A, B), W> + EdgeType,
+{
+    let h_nodes: Vec<NodeId> = h.node_ids().collect();
+    let edges = g.edge_count() * h_nodes.len() * h_nodes.len() + h.edge_count() * g.node_count();
+    let (mut product, pairs) = product_nodes(g, h, edges);
+    let directed = g.is_directed();
+    for (u, u2, w) in g.edges() {
+        for (i, &v) in h_nodes.iter().enumerate() {
+            // An undirected loop u - u joins each pair of copies once, not once per direction.
+            let start = if !directed && u == u2 { i } else { 0 };
+            for &v2 in &h_nodes[start..] {
+                product.add_edge(pairs[&(u, v)], pairs[&(u2, v2)], w.clone());
+            }
+        }
+    }
+    for (v, v2, w) in h.edges() {
+        for u in g.node_ids() {
+            product.add_edge(pairs[&(u, v)], pairs[&(u, v2)], w.clone());
+        }
+    }
+    (product, pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    fn path(n: usize) -> (Graph<usize, f64>, Vec<NodeId>) {
+        let mut g = Graph::new();
+        let nodes: Vec<_> = (0..n).map(|i| g.add_node(i)).collect();
+        for pair in nodes.windows(2) {
+            g.add_edge(pair[0], pair[1], 1.0);
+        }
+        (g, nodes)
+    }
+
+    #[test]
+    fn test_undirected_product_sizes() {
+        // P3 has 3 nodes and 2 edges, P4 has 4 nodes and 3 edges.
+        let (p3, a) = path(3);
+        let (p4, b) = path(4);
+        let sizes =
+            |(g, _): (Graph<(usize, usize), f64>, ProductMap)| (g.node_count(), g.edge_count());
+        assert_eq!(sizes(cartesian_product(&p3, &p4)), (12, 3 * 3 + 4 * 2));
+        assert_eq!(
+            sizes(tensor_product(&p3, &p4, |x, y| x + y)),
+            (12, 2 * 2 * 3)
+        );
+        assert_eq!(sizes(strong_product(&p3, &p4, |x, y| x + y)), (12, 17 + 12));
+        assert_eq!(sizes(lexicographic_product(&p3, &p4)), (12, 2 * 16 + 3 * 3));
+        assert_eq!(sizes(lexicographic_product(&p4, &p3)), (12, 3 * 9 + 4 * 2));
+
+        let (grid, pairs) = cartesian_product(&p3, &p4);
+        assert_eq!(grid.node_attr(pairs[&(a[2], b[1])]), Some(&(2, 1)));
+        assert!(grid.contains_edge(pairs[&(a[0], b[1])], pairs[&(a[0], b[2])]));
+        assert!(!grid.contains_edge(pairs[&(a[0], b[1])], pairs[&(a[1], b[2])]));
+        let (king, pairs) = strong_product(&p3, &p4, |x, y| x + y);
+        let diagonal = king
+            .find_edge(pairs[&(a[0], b[1])], pairs[&(a[1], b[2])])
+            .unwrap();
+        assert_eq!(king.edge_weight(diagonal), Some(&2.0));
+    }
+
+    #[test]
+    fn test_directed_products_keep_orientation() {
+        let mut g = Digraph::<(), f64>::new();
+        let x = g.add_node(());
+        let y = g.add_node(());
+        g.add_edge(x, y, 1.0);
+
+        let (c, pairs) = cartesian_product(&g, &g);
+        assert_eq!(c.edge_count(), 4);
+        assert!(c.contains_edge(pairs[&(x, x)], pairs[&(y, x)]));
+        assert!(!c.contains_edge(pairs[&(y, x)], pairs[&(x, x)]));
+
+        let (t, pairs) = tensor_product(&g, &g, |a, b| a + b);
+        assert_eq!(t.edge_count(), 1);
+        assert!(t.contains_edge(pairs[&(x, x)], pairs[&(y, y)]));
+
+        let (l, pairs) = lexicographic_product(&g, &g);
+        assert_eq!(l.edge_count(), 4 + 2);
+        assert!(l.contains_edge(pairs[&(x, y)], pairs[&(y, x)]));
+        assert!(!l.contains_edge(pairs[&(y, x)], pairs[&(x, y)]));
+    }
+}
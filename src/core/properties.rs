@@ -0,0 +1,545 @@
+/*!
+# Node and Edge Properties
+
+A property layer that sits next to a graph's `A` and `W` payloads, for datasets whose nodes and
+edges carry many named, differently typed fields, as in NetworkX's attribute dictionaries.
+
+```rust
+use graphina::core::properties::Value;
+use graphina::core::types::Graph;
+
+let mut g = Graph::<(), f64>::new();
+let a = g.add_node(());
+g.set_node_prop(a, "name", Value::Str("alice".into())).unwrap();
+g.set_node_prop(a, "age", 42).unwrap();
+assert_eq!(g.node_prop(a, "age"), Some(Value::Int(42)));
+// A property keeps the type of its first value.
+assert!(g.set_node_prop(a, "age", "unknown").is_err());
+```
+
+Each property is stored as a typed [`Column`] indexed by `NodeId::index()` (or
+`EdgeId::index()`), so a scan over one property reads a plain slice. Removing a node or an edge
+clears its values, so a slot reused by a later addition starts without properties.
+
+`save_json` and `save_binary` keep the properties, `save_graphml` writes each one as a typed
+key, and [`save_gexf_properties`](crate::core::io::save_gexf_properties) and
+[`load_gexf_properties`](crate::core::io::load_gexf_properties) map them to GEXF attributes.
+*/
+
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+
+/// One property value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Value {
+    /// A boolean.
+    Bool(bool),
+    /// A 64-bit integer.
+    Int(i64),
+    /// A 64-bit floating-point number.
+    Float(f64),
+    /// Text.
+    Str(String),
+}
+
+/// The type of a [`Value`] and of the [`Column`] that stores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValueType {
+    /// [`Value::Bool`].
+    Bool,
+    /// [`Value::Int`].
+    Int,
+    /// [`Value::Float`].
+    Float,
+    /// [`Value::Str`].
+    Str,
+}
+
+impl Value {
+    /// Returns the type of this value.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Value::Bool(_) => ValueType::Bool,
+            Value::Int(_) => ValueType::Int,
+            Value::Float(_) => ValueType::Float,
+            Value::Str(_) => ValueType::Str,
+        }
+    }
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Int(value) => write!(f, "{value}"),
+            Value::Float(value) => write!(f, "{value}"),
+            Value::Str(value) => f.write_str(value),
+        }
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<i32> for Value {
+    fn from(value: i32) -> Self {
+        Value::Int(value.into())
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<String> for Value {
+    fn from(value: String) -> Self {
+        Value::Str(value)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(value: &str) -> Self {
+        Value::Str(value.to_string())
+    }
+}
+
+/// The values of one property, indexed by node or edge index.
+///
+/// A column may be shorter than the graph's index bound; indices past its end have no value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Column {
+    /// Values of a [`ValueType::Bool`] property.
+    Bool(Vec<Option<bool>>),
+    /// Values of a [`ValueType::Int`] property.
+    Int(Vec<Option<i64>>),
+    /// Values of a [`ValueType::Float`] property.
+    Float(Vec<Option<f64>>),
+    /// Values of a [`ValueType::Str`] property.
+    Str(Vec<Option<String>>),
+}
+
+/// Runs `$body` with `$values` bound to the column's vector, whatever its type.
+macro_rules! each_column {
+    ($column:expr, $values:ident => $body:expr) => {
+        match $column {
+            Column::Bool($values) => $body,
+            Column::Int($values) => $body,
+            Column::Float($values) => $body,
+            Column::Str($values) => $body,
+        }
+    };
+}
+
+fn put<T>(values: &mut Vec<Option<T>>, index: usize, value: T) -> Option<T> {
+    if values.len() <= index {
+        values.resize_with(index + 1, || None);
+    }
+    values[index].replace(value)
+}
+
+impl Column {
+    fn empty(kind: ValueType) -> Self {
+        match kind {
+            ValueType::Bool => Column::Bool(Vec::new()),
+            ValueType::Int => Column::Int(Vec::new()),
+            ValueType::Float => Column::Float(Vec::new()),
+            ValueType::Str => Column::Str(Vec::new()),
+        }
+    }
+
+    /// Returns the type of the values in this column.
+    pub fn value_type(&self) -> ValueType {
+        match self {
+            Column::Bool(_) => ValueType::Bool,
+            Column::Int(_) => ValueType::Int,
+            Column::Float(_) => ValueType::Float,
+            Column::Str(_) => ValueType::Str,
+        }
+    }
+
+    /// Returns the value at `index`, if one is set.
+    pub fn get(&self, index: usize) -> Option<Value> {
+        match self {
+            Column::Bool(values) => values.get(index)?.map(Value::Bool),
+            Column::Int(values) => values.get(index)?.map(Value::Int),
+            Column::Float(values) => values.get(index)?.map(Value::Float),
+            Column::Str(values) => values.get(index)?.clone().map(Value::Str),
+        }
+    }
+
+    /// Returns the number of set values.
+    pub fn count(&self) -> usize {
+        each_column!(self, values => values.iter().filter(|v| v.is_some()).count())
+    }
+
+    /// Returns an iterator over the indices and values that are set, in index order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Value)> + '_ {
+        let len = each_column!(self, values => values.len());
+        (0..len).filter_map(|index| self.get(index).map(|value| (index, value)))
+    }
+
+    /// Returns the values if this is a [`ValueType::Bool`] column.
+    pub fn as_bools(&self) -> Option<&[Option<bool>]> {
+        match self {
+            Column::Bool(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the values if this is a [`ValueType::Int`] column.
+    pub fn as_ints(&self) -> Option<&[Option<i64>]> {
+        match self {
+            Column::Int(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the values if this is a [`ValueType::Float`] column.
+    pub fn as_floats(&self) -> Option<&[Option<f64>]> {
+        match self {
+            Column::Float(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the values if this is a [`ValueType::Str`] column.
+    pub fn as_strs(&self) -> Option<&[Option<String>]> {
+        match self {
+            Column::Str(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Stores `value` at `index` and returns the value it replaced, or `None` with the
+    /// column unchanged if `value` has another type.
+    fn set(&mut self, index: usize, value: Value) -> Option<Option<Value>> {
+        match (self, value) {
+            (Column::Bool(values), Value::Bool(v)) => Some(put(values, index, v).map(Value::Bool)),
+            (Column::Int(values), Value::Int(v)) => Some(put(values, index, v).map(Value::Int)),
+            (Column::Float(values), Value::Float(v)) => {
+                Some(put(values, index, v).map(Value::Float))
+            }
+            (Column::Str(values), Value::Str(v)) => Some(put(values, index, v).map(Value::Str)),
+            _ => None,
+        }
+    }
+
+    fn take(&mut self, index: usize) -> Option<Value> {
+        let value = self.get(index)?;
+        each_column!(self, values => values[index] = None);
+        Some(value)
+    }
+
+    /// Returns the column whose `i`-th value is this column's value at `order[i]`.
+    fn reordered(&self, order: &[usize]) -> Self {
+        each_column!(self, values => {
+            let mut picked: Vec<_> = order
+                .iter()
+                .map(|&index| values.get(index).cloned().flatten())
+                .collect();
+            while picked.last().is_some_and(Option::is_none) {
+                picked.pop();
+            }
+            picked.into()
+        })
+    }
+
+    fn truncate(&mut self, len: usize) {
+        each_column!(self, values => values.truncate(len));
+    }
+}
+
+impl From<Vec<Option<bool>>> for Column {
+    fn from(values: Vec<Option<bool>>) -> Self {
+        Column::Bool(values)
+    }
+}
+
+impl From<Vec<Option<i64>>> for Column {
+    fn from(values: Vec<Option<i64>>) -> Self {
+        Column::Int(values)
+    }
+}
+
+impl From<Vec<Option<f64>>> for Column {
+    fn from(values: Vec<Option<f64>>) -> Self {
+        Column::Float(values)
+    }
+}
+
+impl From<Vec<Option<String>>> for Column {
+    fn from(values: Vec<Option<String>>) -> Self {
+        Column::Str(values)
+    }
+}
+
+/// The properties of a graph's nodes, or of its edges: one [`Column`] per property name.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PropertyTable {
+    columns: BTreeMap<String, Column>,
+}
+
+impl PropertyTable {
+    /// Returns the column of property `name`.
+    pub fn column(&self, name: &str) -> Option<&Column> {
+        self.columns.get(name)
+    }
+
+    /// Returns an iterator over the property names and their columns, sorted by name.
+    pub fn columns(&self) -> impl Iterator<Item = (&str, &Column)> {
+        self.columns
+            .iter()
+            .map(|(name, column)| (name.as_str(), column))
+    }
+
+    /// Returns the number of properties.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Returns `true` if no property has been declared.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Returns the value of property `name` at `index`.
+    pub fn get(&self, index: usize, name: &str) -> Option<Value> {
+        self.columns.get(name)?.get(index)
+    }
+
+    /// Returns every property set at `index`, sorted by name.
+    pub fn row(&self, index: usize) -> Vec<(&str, Value)> {
+        self.columns()
+            .filter_map(|(name, column)| Some((name, column.get(index)?)))
+            .collect()
+    }
+
+    pub(crate) fn set(&mut self, index: usize, name: &str, value: Value) -> Result<Option<Value>> {
+        let kind = value.value_type();
+        let column = self
+            .columns
+            .entry(name.to_string())
+            .or_insert_with(|| Column::empty(kind));
+        let found = column.value_type();
+        column.set(index, value).ok_or_else(|| {
+            GraphinaError::invalid_argument(format!(
+                "Property '{name}' holds {found:?} values, not {kind:?}"
+            ))
+        })
+    }
+
+    pub(crate) fn remove(&mut self, index: usize, name: &str) -> Option<Value> {
+        self.columns.get_mut(name)?.take(index)
+    }
+
+    pub(crate) fn drop_column(&mut self, name: &str) -> Option<Column> {
+        self.columns.remove(name)
+    }
+
+    /// Clears every value at `index`.
+    pub(crate) fn clear_index(&mut self, index: usize) {
+        for column in self.columns.values_mut() {
+            column.take(index);
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.columns.clear();
+    }
+
+    /// Returns the table whose `i`-th row is this table's row `order[i]`, for a graph whose
+    /// elements are renumbered.
+    pub(crate) fn reordered(&self, order: &[usize]) -> Self {
+        PropertyTable {
+            columns: self
+                .columns
+                .iter()
+                .map(|(name, column)| (name.clone(), column.reordered(order)))
+                .collect(),
+        }
+    }
+
+    /// Drops the values at indices `len` and above.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        for column in self.columns.values_mut() {
+            column.truncate(len);
+        }
+    }
+}
+
+/// Property access on nodes and edges.
+impl<A, W, Ty> BaseGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    /// Sets property `name` of `node` and returns the value it replaced.
+    ///
+    /// # Errors
+    /// Returns `NodeNotFound` if `node` is not in the graph, and `InvalidArgument` if the
+    /// property already holds values of another type.
+    pub fn set_node_prop(
+        &mut self,
+        node: NodeId,
+        name: &str,
+        value: impl Into<Value>,
+    ) -> Result<Option<Value>> {
+        if !self.contains_node(node) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Cannot set property '{name}' of missing node {}",
+                node.index()
+            )));
+        }
+        self.node_props.set(node.index(), name, value.into())
+    }
+
+    /// Returns property `name` of `node`, if it is set.
+    pub fn node_prop(&self, node: NodeId, name: &str) -> Option<Value> {
+        self.node_props.get(node.index(), name)
+    }
+
+    /// Removes property `name` from `node` and returns its value.
+    pub fn remove_node_prop(&mut self, node: NodeId, name: &str) -> Option<Value> {
+        self.node_props.remove(node.index(), name)
+    }
+
+    /// Removes property `name` from every node, returning its column, so the name can be
+    /// reused with another type.
+    pub fn drop_node_prop(&mut self, name: &str) -> Option<Column> {
+        self.node_props.drop_column(name)
+    }
+
+    /// Returns the node properties, for reading whole columns.
+    pub fn node_props(&self) -> &PropertyTable {
+        &self.node_props
+    }
+
+    /// Sets property `name` of `edge` and returns the value it replaced.
+    ///
+    /// # Errors
+    /// Returns `EdgeNotFound` if `edge` is not in the graph, and `InvalidArgument` if the
+    /// property already holds values of another type.
+    pub fn set_edge_prop(
+        &mut self,
+        edge: EdgeId,
+        name: &str,
+        value: impl Into<Value>,
+    ) -> Result<Option<Value>> {
+        if self.edge_weight(edge).is_none() {
+            return Err(GraphinaError::edge_not_found(format!(
+                "Cannot set property '{name}' of missing edge {}",
+                edge.index()
+            )));
+        }
+        self.edge_props.set(edge.index(), name, value.into())
+    }
+
+    /// Returns property `name` of `edge`, if it is set.
+    pub fn edge_prop(&self, edge: EdgeId, name: &str) -> Option<Value> {
+        self.edge_props.get(edge.index(), name)
+    }
+
+    /// Removes property `name` from `edge` and returns its value.
+    pub fn remove_edge_prop(&mut self, edge: EdgeId, name: &str) -> Option<Value> {
+        self.edge_props.remove(edge.index(), name)
+    }
+
+    /// Removes property `name` from every edge, returning its column, so the name can be
+    /// reused with another type.
+    pub fn drop_edge_prop(&mut self, name: &str) -> Option<Column> {
+        self.edge_props.drop_column(name)
+    }
+
+    /// Returns the edge properties, for reading whole columns.
+    pub fn edge_props(&self) -> &PropertyTable {
+        &self.edge_props
+    }
+
+    /// Returns the properties renumbered to match a copy of this graph that adds the nodes,
+    /// then the edges, in ID order, as `compact`, `map_node_attrs`, and serialization do.
+    pub(crate) fn compacted_props(&self) -> (PropertyTable, PropertyTable) {
+        let nodes: Vec<usize> = self.node_ids().map(|id| id.index()).collect();
+        let edges: Vec<usize> = self.edge_ids().map(|id| id.index()).collect();
+        (
+            self.node_props.reordered(&nodes),
+            self.edge_props.reordered(&edges),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_props_follow_removal_and_compaction() {
+        let mut g = Digraph::<u32, f64>::new();
+        let n: Vec<NodeId> = (0..4).map(|i| g.add_node(i)).collect();
+        let e01 = g.add_edge(n[0], n[1], 1.0);
+        let e12 = g.add_edge(n[1], n[2], 2.0);
+        let e23 = g.add_edge(n[2], n[3], 3.0);
+        for (i, &node) in n.iter().enumerate() {
+            g.set_node_prop(node, "rank", i as i64).unwrap();
+        }
+        g.set_node_prop(n[3], "name", "last").unwrap();
+        for edge in [e01, e12, e23] {
+            g.set_edge_prop(edge, "kept", true).unwrap();
+        }
+
+        assert_eq!(
+            g.set_node_prop(n[0], "rank", 7).unwrap(),
+            Some(Value::Int(0))
+        );
+        assert!(matches!(
+            g.set_node_prop(n[0], "rank", 1.5),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        assert_eq!(g.node_props().column("rank").unwrap().count(), 4);
+
+        // Removing node 1 clears its values and those of its two edges.
+        g.remove_node(n[1]);
+        assert_eq!(g.node_prop(n[1], "rank"), None);
+        assert_eq!(g.edge_props().column("kept").unwrap().count(), 1);
+        let again = g.add_node(9);
+        assert_eq!(again, n[1]);
+        assert_eq!(g.node_prop(again, "rank"), None);
+        assert!(g.set_node_prop(NodeId::new(99.into()), "rank", 1).is_err());
+
+        let remap = g.compact();
+        assert_eq!(g.node_prop(remap[&n[3]], "name"), Some("last".into()));
+        assert_eq!(g.node_prop(remap[&n[2]], "rank"), Some(Value::Int(2)));
+        let edge = g.find_edge(remap[&n[2]], remap[&n[3]]).unwrap();
+        assert_eq!(g.edge_prop(edge, "kept"), Some(Value::Bool(true)));
+
+        let mapped = g.map_node_attrs(|_, &a| a * 2);
+        assert_eq!(mapped.node_props(), g.node_props());
+        assert_eq!(
+            g.drop_node_prop("rank").unwrap().value_type(),
+            ValueType::Int
+        );
+        g.set_node_prop(remap[&n[0]], "rank", "first").unwrap();
+
+        let mut u = Graph::<(), f64>::new();
+        let a = u.add_node(());
+        u.set_node_prop(a, "x", 1.0).unwrap();
+        u.clear();
+        assert!(u.node_props().is_empty());
+    }
+}
@@ -0,0 +1,239 @@
+/*!
+# Weighted Sampling
+
+Walker's alias method draws from a fixed discrete distribution in constant time after a linear
+setup. [`AliasTable`] holds one distribution, and [`WeightedNeighborSampler`] holds one per node,
+built from the weights of its outgoing edges, so that random walks such as those of node2vec or
+Monte Carlo diffusion simulations pay the setup once and then take each step in constant time.
+
+Draws take any [`rand::Rng`], so a seeded `StdRng` makes them reproducible.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::EdgeType;
+use petgraph::visit::EdgeRef;
+use rand::Rng;
+
+/// A discrete distribution over `0..n` that can be sampled in constant time.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::sampling::AliasTable;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let table = AliasTable::new(&[1.0, 0.0, 3.0]).unwrap();
+/// let mut rng = StdRng::seed_from_u64(7);
+/// let draws: Vec<usize> = (0..1000).map(|_| table.sample(&mut rng)).collect();
+/// assert!(!draws.contains(&1));
+/// assert!(draws.iter().filter(|&&i| i == 2).count() > 600);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct AliasTable {
+    /// Probability of keeping column `i` rather than taking its alias.
+    keep: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    /// Builds the table for outcomes with the given relative weights.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` if `weights` is empty, contains a negative or non-finite
+    /// value, or sums to zero.
+    pub fn new(weights: &[f64]) -> Result<Self> {
+        if let Some(w) = weights.iter().find(|w| !w.is_finite() || **w < 0.0) {
+            return Err(GraphinaError::invalid_argument(format!(
+                "sampling weights must be finite and nonnegative, found {w}"
+            )));
+        }
+        let total: f64 = weights.iter().sum();
+        if !(total > 0.0 && total.is_finite()) {
+            return Err(GraphinaError::invalid_argument(
+                "sampling weights must have a positive, finite sum",
+            ));
+        }
+        let n = weights.len();
+        let mut keep: Vec<f64> = weights.iter().map(|w| w * n as f64 / total).collect();
+        let mut alias: Vec<usize> = (0..n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..n).partition(|&i| keep[i] < 1.0);
+        // Vose's pairing: each underfull column is topped up from one overfull column.
+        while let (Some(&s), Some(&l)) = (small.last(), large.last()) {
+            small.pop();
+            alias[s] = l;
+            keep[l] -= 1.0 - keep[s];
+            if keep[l] < 1.0 {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // Whatever is left is full up to rounding error.
+        for i in small.into_iter().chain(large) {
+            keep[i] = 1.0;
+        }
+        Ok(Self { keep, alias })
+    }
+
+    /// Returns the number of outcomes.
+    pub fn len(&self) -> usize {
+        self.keep.len()
+    }
+
+    /// Returns true if there are no outcomes, which [`AliasTable::new`] never produces.
+    pub fn is_empty(&self) -> bool {
+        self.keep.is_empty()
+    }
+
+    /// Draws an outcome index in `0..len()`.
+    pub fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let column = rng.random_range(0..self.keep.len());
+        if rng.random::<f64>() < self.keep[column] {
+            column
+        } else {
+            self.alias[column]
+        }
+    }
+}
+
+/// Per-node alias tables for drawing a neighbor with probability proportional to edge weight.
+///
+/// Directed graphs sample along outgoing edges. Parallel edges each count with their own
+/// weight, and edges of weight zero are never drawn.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::sampling::WeightedNeighborSampler;
+/// use graphina::core::types::Graph;
+/// use rand::SeedableRng;
+/// use rand::rngs::StdRng;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let (a, b, c) = (g.add_node(()), g.add_node(()), g.add_node(()));
+/// g.add_edge(a, b, 9.0);
+/// g.add_edge(a, c, 1.0);
+///
+/// let sampler = WeightedNeighborSampler::new(&g).unwrap();
+/// let mut rng = StdRng::seed_from_u64(1);
+/// let to_b = (0..1000).filter(|_| sampler.sample(a, &mut rng) == Some(b)).count();
+/// assert!(to_b > 850);
+///
+/// let walk = sampler.walk(c, 5, &mut rng);
+/// assert_eq!(walk.len(), 6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct WeightedNeighborSampler {
+    /// Indexed by `NodeId::index()`; `None` for removed slots and nodes with nothing to draw.
+    tables: Vec<Option<(Vec<NodeId>, AliasTable)>>,
+}
+
+impl WeightedNeighborSampler {
+    /// Builds a table for every node from the weights of its outgoing edges.
+    ///
+    /// Nodes without edges, or whose edges all weigh zero, get no table and are dead ends.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` if an edge weight is negative or not finite.
+    pub fn new<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<Self>
+    where
+        W: Copy + Into<f64>,
+        Ty: GraphConstructor<A, W> + EdgeType,
+    {
+        let bound = graph.node_ids().map(|n| n.index() + 1).max().unwrap_or(0);
+        let mut tables = vec![None; bound];
+        for node in graph.node_ids() {
+            let (targets, weights): (Vec<NodeId>, Vec<f64>) = graph
+                .inner
+                .edges(node.0)
+                .map(|e| (NodeId::new(e.target()), (*e.weight()).into()))
+                .filter(|&(_, w): &(NodeId, f64)| w != 0.0)
+                .unzip();
+            if weights.is_empty() {
+                continue;
+            }
+            tables[node.index()] = Some((targets, AliasTable::new(&weights)?));
+        }
+        Ok(Self { tables })
+    }
+
+    /// Returns the neighbors `node` can step to, in the order of its alias table.
+    pub fn neighbors(&self, node: NodeId) -> &[NodeId] {
+        match self.tables.get(node.index()) {
+            Some(Some((targets, _))) => targets,
+            _ => &[],
+        }
+    }
+
+    /// Draws a neighbor of `node`, or returns `None` if it has none with positive weight.
+    pub fn sample<R: Rng + ?Sized>(&self, node: NodeId, rng: &mut R) -> Option<NodeId> {
+        let (targets, table) = self.tables.get(node.index())?.as_ref()?;
+        targets.get(table.sample(rng)).copied()
+    }
+
+    /// Takes up to `steps` weighted random steps from `start` and returns the visited nodes,
+    /// starting with `start`.
+    ///
+    /// The walk ends early at a node it cannot leave.
+    pub fn walk<R: Rng + ?Sized>(&self, start: NodeId, steps: usize, rng: &mut R) -> Vec<NodeId> {
+        let mut walk = Vec::with_capacity(steps + 1);
+        walk.push(start);
+        let mut current = start;
+        for _ in 0..steps {
+            match self.sample(current, rng) {
+                Some(next) => {
+                    walk.push(next);
+                    current = next;
+                }
+                None => break,
+            }
+        }
+        walk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_alias_sampling_matches_weights() {
+        let weights = [0.5, 2.0, 0.0, 1.5, 4.0];
+        let table = AliasTable::new(&weights).unwrap();
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut counts = [0usize; 5];
+        let draws = 200_000;
+        for _ in 0..draws {
+            counts[table.sample(&mut rng)] += 1;
+        }
+        for (count, w) in counts.iter().zip(weights) {
+            let expected = w / 8.0;
+            assert!((*count as f64 / draws as f64 - expected).abs() < 0.01);
+        }
+        assert!(AliasTable::new(&[]).is_err());
+        assert!(AliasTable::new(&[0.0, 0.0]).is_err());
+        assert!(AliasTable::new(&[1.0, -1.0]).is_err());
+
+        let mut g = Digraph::<(), f64>::new();
+        let (a, b, c) = (g.add_node(()), g.add_node(()), g.add_node(()));
+        g.add_edge(a, b, 1.0);
+        g.add_edge(b, c, 0.0);
+        g.add_edge(a, c, 0.0);
+        let sampler = WeightedNeighborSampler::new(&g).unwrap();
+        assert_eq!(sampler.neighbors(a), &[b]);
+        assert_eq!(sampler.sample(b, &mut rng), None);
+        assert_eq!(sampler.walk(a, 10, &mut rng), vec![a, b]);
+
+        let mut u = Graph::<(), u32>::new();
+        let (x, y) = (u.add_node(()), u.add_node(()));
+        u.add_edge(x, y, 2);
+        let sampler = WeightedNeighborSampler::new(&u).unwrap();
+        assert_eq!(sampler.sample(y, &mut rng), Some(x));
+    }
+}
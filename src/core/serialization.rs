@@ -5,7 +5,25 @@ Graph serialization and deserialization.
 - JSON (human-readable, debugging)
 - Binary (fast, compact)
 - GraphML (interoperability with other tools)
+- D3 node-link JSON (for web visualizations)
+- Mermaid and PlantUML diagrams (for Markdown documents and wikis)
 - Edge list (simple text format)
+
+## Binary Format Versions
+
+Binary files start with the 8-byte [`BINARY_MAGIC`] and a little-endian `u32` format version,
+followed by the bincode-encoded [`SerializableGraph`]. Loading dispatches on the version, so
+a file written by an older release keeps loading after the layout changes, and a file from a
+newer release fails with `UnsupportedVersion` instead of decoding into garbage.
+
+| Version | Written by | Layout |
+|---------|------------|--------|
+| 0 | releases before the header existed | bincode `SerializableGraph`, no header |
+| 1 | releases before node and edge properties | header, then bincode `SerializableGraph` without the property tables |
+| 2 | current | header, then bincode `SerializableGraph` |
+
+A headerless file is recognized because bincode writes the `directed` flag first, as byte 0
+or 1, which can never be the first byte of the magic.
 */
 
 use std::fs::File;
@@ -16,9 +34,97 @@ use bincode;
 use serde::{Deserialize, Serialize};
 
 use crate::core::error::GraphinaError;
+use crate::core::io::escape_xml;
+use crate::core::properties::{PropertyTable, ValueType};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
 use petgraph::EdgeType;
 
+/// Leading bytes of every binary graph file.
+pub const BINARY_MAGIC: [u8; 8] = *b"GRAPHINA";
+
+/// Binary format version written by [`BaseGraph::save_binary`].
+pub const BINARY_FORMAT_VERSION: u32 = 2;
+
+/// Returns the binary format version of `bytes` and the payload that follows the header.
+/// Data without the magic is the headerless version 0.
+fn split_binary_header(bytes: &[u8]) -> Result<(u32, &[u8]), GraphinaError> {
+    let Some(rest) = bytes.strip_prefix(&BINARY_MAGIC) else {
+        return Ok((0, bytes));
+    };
+    let (version, payload) = rest.split_first_chunk::<4>().ok_or_else(|| {
+        GraphinaError::SerializationError("Binary graph header is truncated".into())
+    })?;
+    Ok((u32::from_le_bytes(*version), payload))
+}
+
+/// Decodes a binary graph file, migrating older layouts to the current one.
+fn decode_binary<A, W>(bytes: &[u8]) -> Result<SerializableGraph<A, W>, GraphinaError>
+where
+    A: for<'de> Deserialize<'de>,
+    W: for<'de> Deserialize<'de>,
+{
+    let (version, payload) = split_binary_header(bytes)?;
+    match version {
+        // Version 1 only added the header; neither has the property tables of version 2.
+        0 | 1 => {
+            type Layout<A, W> = (bool, Vec<A>, Vec<(usize, usize, W)>);
+            let ((directed, nodes, edges), _): (Layout<A, W>, usize) =
+                bincode::serde::decode_from_slice(payload, bincode::config::standard())
+                    .map_err(GraphinaError::from)?;
+            Ok(SerializableGraph {
+                directed,
+                nodes,
+                edges,
+                node_properties: PropertyTable::default(),
+                edge_properties: PropertyTable::default(),
+            })
+        }
+        2 => {
+            let (graph, _): (SerializableGraph<A, W>, usize) =
+                bincode::serde::decode_from_slice(payload, bincode::config::standard())
+                    .map_err(GraphinaError::from)?;
+            Ok(graph)
+        }
+        found => Err(GraphinaError::UnsupportedVersion {
+            found,
+            supported: BINARY_FORMAT_VERSION,
+        }),
+    }
+}
+
+/// Returns the binary format version of the graph file at `path` without decoding it.
+///
+/// # Errors
+///
+/// Returns `IoError` if the file cannot be read and `SerializationError` if its header is
+/// truncated.
+pub fn binary_format_version<P: AsRef<Path>>(path: P) -> Result<u32, GraphinaError> {
+    let bytes = std::fs::read(path).map_err(GraphinaError::from)?;
+    split_binary_header(&bytes).map(|(version, _)| version)
+}
+
+/// Writes the properties set at `index` as GraphML `data` elements, numbering the keys of
+/// `table` from `first`.
+fn write_graphml_props(
+    writer: &mut impl Write,
+    table: &PropertyTable,
+    index: usize,
+    first: usize,
+) -> Result<(), GraphinaError> {
+    for (key, (_, column)) in table.columns().enumerate() {
+        if let Some(value) = column.get(index) {
+            writeln!(
+                writer,
+                "      <data key=\"d{}\">{}</data>",
+                first + key,
+                escape_xml(&value.to_string())
+            )
+            .map_err(GraphinaError::from)?;
+        }
+    }
+    Ok(())
+}
+
 /// Serializable representation of a graph for JSON/binary formats.
 ///
 /// This intermediate format allows serialization of graphs with any node/edge attributes
@@ -31,6 +137,12 @@ pub struct SerializableGraph<A, W> {
     pub nodes: Vec<A>,
     /// Edges as (source_index, target_index, weight) tuples
     pub edges: Vec<(usize, usize, W)>,
+    /// Node properties indexed by node position; missing in files from before properties
+    #[serde(default)]
+    pub node_properties: PropertyTable,
+    /// Edge properties indexed by edge position; missing in files from before properties
+    #[serde(default)]
+    pub edge_properties: PropertyTable,
 }
 
 impl<A, W, Ty> BaseGraph<A, W, Ty>
@@ -77,10 +189,14 @@ where
             })
             .collect();
 
+        // Positions follow ID order, as for a compacted graph.
+        let (node_properties, edge_properties) = self.compacted_props();
         SerializableGraph {
             directed: self.is_directed(),
             nodes: node_attrs,
             edges,
+            node_properties,
+            edge_properties,
         }
     }
 
@@ -96,6 +212,8 @@ where
     ///     directed: false,
     ///     nodes: vec![1, 2, 3],
     ///     edges: vec![(0, 1, 1.0), (1, 2, 2.0)],
+    ///     node_properties: Default::default(),
+    ///     edge_properties: Default::default(),
     /// };
     ///
     /// let graph = Graph::<i32, f64>::from_serializable(&serializable);
@@ -117,6 +235,7 @@ where
             graph.add_edge(node_ids[*src_idx], node_ids[*tgt_idx], weight.clone());
         }
 
+        graph.set_serialized_props(data);
         graph
     }
 
@@ -139,9 +258,19 @@ where
         for (src_idx, tgt_idx, weight) in &data.edges {
             graph.add_edge(node_ids[*src_idx], node_ids[*tgt_idx], weight.clone());
         }
+        graph.set_serialized_props(data);
         Ok(graph)
     }
 
+    /// Takes the properties of a graph that was just built from `data`, whose positions are
+    /// the new IDs. Values past the last node or edge are dropped.
+    fn set_serialized_props(&mut self, data: &SerializableGraph<A, W>) {
+        self.node_props = data.node_properties.clone();
+        self.node_props.truncate(data.nodes.len());
+        self.edge_props = data.edge_properties.clone();
+        self.edge_props.truncate(data.edges.len());
+    }
+
     /// Saves the graph to a JSON file.
     ///
     /// # Example
@@ -205,7 +334,8 @@ where
 
     /// Saves the graph to a binary file (using bincode).
     ///
-    /// Binary format is much faster and more compact than JSON.
+    /// Binary format is much faster and more compact than JSON. The file starts with a
+    /// versioned header; see the [module documentation](self) for the format history.
     ///
     /// # Example
     ///
@@ -227,13 +357,23 @@ where
         let encoded = bincode::serde::encode_to_vec(&serializable, bincode::config::standard())
             .map_err(GraphinaError::from)?;
 
-        std::io::Write::write_all(&mut writer, &encoded).map_err(GraphinaError::from)?;
+        writer
+            .write_all(&BINARY_MAGIC)
+            .map_err(GraphinaError::from)?;
+        writer
+            .write_all(&BINARY_FORMAT_VERSION.to_le_bytes())
+            .map_err(GraphinaError::from)?;
+        writer.write_all(&encoded).map_err(GraphinaError::from)?;
+        writer.flush().map_err(GraphinaError::from)?;
 
         Ok(())
     }
 
     /// Loads a graph from a binary file.
     ///
+    /// Files from every earlier format version load; see the [module documentation](self).
+    /// A file written by a newer release returns `UnsupportedVersion`.
+    ///
     /// # Example
     ///
     /// ```rust,no_run
@@ -253,9 +393,7 @@ where
         let mut buffer = Vec::new();
         std::io::Read::read_to_end(&mut reader, &mut buffer).map_err(GraphinaError::from)?;
 
-        let (serializable, _): (SerializableGraph<A, W>, usize) =
-            bincode::serde::decode_from_slice(&buffer, bincode::config::standard())
-                .map_err(GraphinaError::from)?;
+        let serializable: SerializableGraph<A, W> = decode_binary(&buffer)?;
 
         Ok(Self::from_serializable(&serializable))
     }
@@ -272,9 +410,7 @@ where
         let mut buffer = Vec::new();
         std::io::Read::read_to_end(&mut reader, &mut buffer).map_err(GraphinaError::from)?;
 
-        let (serializable, _): (SerializableGraph<A, W>, usize) =
-            bincode::serde::decode_from_slice(&buffer, bincode::config::standard())
-                .map_err(GraphinaError::from)?;
+        let serializable: SerializableGraph<A, W> = decode_binary(&buffer)?;
 
         Self::try_from_serializable(&serializable)
     }
@@ -339,6 +475,31 @@ where
             "  <key id=\"d1\" for=\"edge\" attr.name=\"weight\" attr.type=\"double\"/>"
         )
         .map_err(GraphinaError::from)?;
+        // Properties get the keys after d1: node properties first, then edge properties.
+        let node_keys = self.node_props.len();
+        let tables = [
+            ("node", &self.node_props, 2),
+            ("edge", &self.edge_props, 2 + node_keys),
+        ];
+        for (class, table, first) in tables {
+            for (i, (name, column)) in table.columns().enumerate() {
+                let kind = match column.value_type() {
+                    ValueType::Bool => "boolean",
+                    ValueType::Int => "long",
+                    ValueType::Float => "double",
+                    ValueType::Str => "string",
+                };
+                writeln!(
+                    writer,
+                    "  <key id=\"d{}\" for=\"{}\" attr.name=\"{}\" attr.type=\"{}\"/>",
+                    first + i,
+                    class,
+                    escape_xml(name),
+                    kind
+                )
+                .map_err(GraphinaError::from)?;
+            }
+        }
 
         // Start graph
         let graph_type = if self.is_directed() {
@@ -356,11 +517,12 @@ where
                 .map_err(GraphinaError::from)?;
             writeln!(writer, "      <data key=\"d0\">{}</data>", attr)
                 .map_err(GraphinaError::from)?;
+            write_graphml_props(&mut writer, &self.node_props, node_id.index(), 2)?;
             writeln!(writer, "    </node>").map_err(GraphinaError::from)?;
         }
 
         // Write edges
-        for (edge_count, (src, tgt, weight)) in self.edges().enumerate() {
+        for (edge_count, (edge_id, src, tgt, weight)) in self.edges_with_ids().enumerate() {
             writeln!(
                 writer,
                 "    <edge id=\"e{}\" source=\"n{}\" target=\"n{}\">",
@@ -371,6 +533,12 @@ where
             .map_err(GraphinaError::from)?;
             writeln!(writer, "      <data key=\"d1\">{}</data>", weight)
                 .map_err(GraphinaError::from)?;
+            write_graphml_props(
+                &mut writer,
+                &self.edge_props,
+                edge_id.index(),
+                2 + node_keys,
+            )?;
             writeln!(writer, "    </edge>").map_err(GraphinaError::from)?;
         }
 
@@ -380,6 +548,171 @@ where
 
         Ok(())
     }
+
+    /// Converts the graph to the node-link JSON that D3 force layouts read.
+    ///
+    /// The result has the shape `{"directed": bool, "nodes": [...], "links": [...]}`. Each
+    /// node has an `"id"` equal to its node index. Attributes that serialize to a JSON object
+    /// have their fields merged into the node, with `"id"` taking precedence; any other
+    /// attribute is stored under `"attr"`, and `()` attributes are omitted. Each link has
+    /// `"source"` and `"target"` node indices and a `"weight"`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    ///
+    /// let mut g = Graph::<&str, f64>::new();
+    /// let a = g.add_node("a");
+    /// let b = g.add_node("b");
+    /// g.add_edge(a, b, 2.0);
+    ///
+    /// let json = g.to_d3_json().unwrap();
+    /// assert_eq!(json["nodes"][1]["attr"], "b");
+    /// assert_eq!(json["links"][0]["weight"], 2.0);
+    /// ```
+    pub fn to_d3_json(&self) -> Result<serde_json::Value, GraphinaError> {
+        use serde_json::{Map, Value, json};
+
+        let mut nodes = Vec::with_capacity(self.node_count());
+        for (id, attr) in self.nodes() {
+            let mut node = match serde_json::to_value(attr).map_err(GraphinaError::from)? {
+                Value::Object(fields) => fields,
+                Value::Null => Map::new(),
+                other => Map::from_iter([("attr".to_string(), other)]),
+            };
+            node.insert("id".to_string(), json!(id.index()));
+            nodes.push(Value::Object(node));
+        }
+        let mut links = Vec::with_capacity(self.edge_count());
+        for (src, tgt, weight) in self.edges() {
+            links.push(json!({
+                "source": src.index(),
+                "target": tgt.index(),
+                "weight": serde_json::to_value(weight).map_err(GraphinaError::from)?,
+            }));
+        }
+        Ok(json!({
+            "directed": self.is_directed(),
+            "nodes": nodes,
+            "links": links,
+        }))
+    }
+
+    /// Saves the graph as D3 node-link JSON; see [`to_d3_json`](Self::to_d3_json).
+    pub fn save_d3_json<P: AsRef<Path>>(&self, path: P) -> Result<(), GraphinaError> {
+        let value = self.to_d3_json()?;
+        let file = File::create(path).map_err(GraphinaError::from)?;
+        serde_json::to_writer(BufWriter::new(file), &value).map_err(GraphinaError::from)?;
+        Ok(())
+    }
+
+    /// Converts the graph to a Mermaid flowchart, for pasting into Markdown that renders
+    /// Mermaid blocks, such as GitHub and GitLab pages.
+    ///
+    /// Nodes are named `n<index>` and labeled with their attribute; edges are arrows on
+    /// directed graphs and plain lines otherwise, labeled with their weight when `weights`
+    /// is `true`. Double quotes in labels become `#quot;` and line breaks become `<br>`.
+    /// Meant for small graphs: past a few dozen nodes the diagrams get hard to read.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Digraph;
+    ///
+    /// let mut g = Digraph::<&str, f64>::new();
+    /// let a = g.add_node("parse");
+    /// let b = g.add_node("check");
+    /// g.add_edge(a, b, 2.5);
+    ///
+    /// assert_eq!(
+    ///     g.to_mermaid(true),
+    ///     "flowchart LR\n    n0[\"parse\"]\n    n1[\"check\"]\n    n0 -->|\"2.5\"| n1\n"
+    /// );
+    /// ```
+    pub fn to_mermaid(&self, weights: bool) -> String
+    where
+        A: std::fmt::Display,
+        W: std::fmt::Display,
+    {
+        let escape = |text: String| text.replace('"', "#quot;").replace('\n', "<br>");
+        let arrow = if self.is_directed() { "-->" } else { "---" };
+        let mut out = String::from("flowchart LR\n");
+        for (id, attr) in self.nodes() {
+            out.push_str(&format!(
+                "    n{}[\"{}\"]\n",
+                id.index(),
+                escape(attr.to_string())
+            ));
+        }
+        for (src, tgt, weight) in self.edges() {
+            let label = if weights {
+                format!("|\"{}\"|", escape(weight.to_string()))
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                "    n{} {arrow}{label} n{}\n",
+                src.index(),
+                tgt.index()
+            ));
+        }
+        out
+    }
+
+    /// Converts the graph to a PlantUML diagram, for wikis and documentation tools that
+    /// render PlantUML.
+    ///
+    /// Nodes become `rectangle`s named `n<index>` and labeled with their attribute; edges
+    /// are `-->` on directed graphs and `--` otherwise, with the weight after a colon when
+    /// `weights` is `true`. PlantUML has no escape for double quotes, so they become single
+    /// quotes, and line breaks become `\n`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    ///
+    /// let mut g = Graph::<&str, f64>::new();
+    /// let a = g.add_node("left");
+    /// let b = g.add_node("right");
+    /// g.add_edge(a, b, 1.0);
+    ///
+    /// let uml = g.to_plantuml(false);
+    /// assert!(uml.starts_with("@startuml\n"));
+    /// assert!(uml.contains("rectangle \"left\" as n0\n"));
+    /// assert!(uml.contains("n0 -- n1\n"));
+    /// ```
+    pub fn to_plantuml(&self, weights: bool) -> String
+    where
+        A: std::fmt::Display,
+        W: std::fmt::Display,
+    {
+        let escape = |text: String| text.replace('"', "'").replace('\n', "\\n");
+        let arrow = if self.is_directed() { "-->" } else { "--" };
+        let mut out = String::from("@startuml\nleft to right direction\n");
+        for (id, attr) in self.nodes() {
+            out.push_str(&format!(
+                "rectangle \"{}\" as n{}\n",
+                escape(attr.to_string()),
+                id.index()
+            ));
+        }
+        for (src, tgt, weight) in self.edges() {
+            let label = if weights {
+                format!(" : {}", escape(weight.to_string()))
+            } else {
+                String::new()
+            };
+            out.push_str(&format!(
+                "n{} {arrow} n{}{label}\n",
+                src.index(),
+                tgt.index()
+            ));
+        }
+        out.push_str("@enduml\n");
+        out
+    }
 }
 
 #[cfg(test)]
@@ -401,6 +734,7 @@ mod tests {
         assert!(!json_str.contains("Infinity"));
     }
     use super::*;
+    use crate::core::properties::Value;
     use crate::core::types::{Digraph, Graph};
     use std::fs;
 
@@ -425,6 +759,8 @@ mod tests {
             directed: false,
             nodes: vec![10, 20, 30],
             edges: vec![(0, 1, 1.0), (1, 2, 2.0), (2, 0, 3.0)],
+            node_properties: PropertyTable::default(),
+            edge_properties: PropertyTable::default(),
         };
 
         let graph = Graph::<i32, f64>::from_serializable(&serializable);
@@ -472,6 +808,105 @@ mod tests {
         fs::remove_file(path).ok();
     }
 
+    #[test]
+    fn test_binary_format_versions() {
+        let mut g = Graph::<i32, f64>::new();
+        let n1 = g.add_node(1);
+        let n2 = g.add_node(2);
+        g.add_edge(n1, n2, 2.5);
+
+        let path = "test_graph_versions.bin";
+        g.save_binary(path).expect("Failed to save binary");
+        let bytes = fs::read(path).expect("Failed to read file");
+        assert_eq!(&bytes[..8], &BINARY_MAGIC);
+        assert_eq!(binary_format_version(path).unwrap(), BINARY_FORMAT_VERSION);
+
+        // A headerless file from before versioning still loads, as does a version 1 file,
+        // both laid out without property tables.
+        let legacy = bincode::serde::encode_to_vec(
+            (false, vec![1, 2], vec![(0usize, 1usize, 2.5)]),
+            bincode::config::standard(),
+        )
+        .unwrap();
+        fs::write(path, &legacy).unwrap();
+        assert_eq!(binary_format_version(path).unwrap(), 0);
+        let loaded = Graph::<i32, f64>::load_binary_strict(path).expect("Legacy load failed");
+        assert_eq!(loaded.edge_count(), 1);
+        let mut version_1 = BINARY_MAGIC.to_vec();
+        version_1.extend_from_slice(&1u32.to_le_bytes());
+        version_1.extend_from_slice(&legacy);
+        fs::write(path, &version_1).unwrap();
+        let loaded = Graph::<i32, f64>::load_binary(path).expect("Version 1 load failed");
+        assert_eq!(loaded.edge_count(), 1);
+        assert!(loaded.node_props().is_empty());
+
+        // A newer version is rejected with a clear error, as is a cut-off header.
+        let mut future = BINARY_MAGIC.to_vec();
+        future.extend_from_slice(&99u32.to_le_bytes());
+        future.extend_from_slice(&legacy);
+        fs::write(path, &future).unwrap();
+        match Graph::<i32, f64>::load_binary(path) {
+            Err(GraphinaError::UnsupportedVersion { found, supported }) => {
+                assert_eq!((found, supported), (99, BINARY_FORMAT_VERSION));
+            }
+            other => panic!("expected UnsupportedVersion, got {:?}", other.map(|_| ())),
+        }
+        fs::write(path, &BINARY_MAGIC[..]).unwrap();
+        assert!(matches!(
+            Graph::<i32, f64>::load_binary(path),
+            Err(GraphinaError::SerializationError(_))
+        ));
+
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn test_properties_survive_json_binary_and_graphml() {
+        let mut g = Graph::<i32, f64>::new();
+        let n: Vec<NodeId> = (0..3).map(|i| g.add_node(i)).collect();
+        let e = g.add_edge(n[1], n[2], 1.0);
+        g.set_node_prop(n[0], "name", "a & b").unwrap();
+        g.set_node_prop(n[2], "name", "c").unwrap();
+        g.set_node_prop(n[2], "age", 40).unwrap();
+        g.set_edge_prop(e, "since", 2019.5).unwrap();
+        // Positions skip the removed node, so properties must move with them.
+        g.remove_node(n[0]);
+
+        let json = "test_graph_properties.json";
+        let bin = "test_graph_properties.bin";
+        g.save_json(json).unwrap();
+        g.save_binary(bin).unwrap();
+        for loaded in [
+            Graph::<i32, f64>::load_json(json).unwrap(),
+            Graph::<i32, f64>::load_binary(bin).unwrap(),
+        ] {
+            let c = loaded.nodes().find(|(_, a)| **a == 2).unwrap().0;
+            assert_eq!(loaded.node_prop(c, "name"), Some(Value::Str("c".into())));
+            assert_eq!(loaded.node_prop(c, "age"), Some(Value::Int(40)));
+            let edge = loaded.edge_ids().next().unwrap();
+            assert_eq!(loaded.edge_prop(edge, "since"), Some(Value::Float(2019.5)));
+            assert_eq!(loaded.node_props().column("name").unwrap().count(), 1);
+        }
+
+        let graphml = "test_graph_properties.graphml";
+        g.set_node_prop(n[1], "name", "x<y").unwrap();
+        g.save_graphml(graphml).unwrap();
+        let content = fs::read_to_string(graphml).unwrap();
+        assert!(
+            content.contains("<key id=\"d2\" for=\"node\" attr.name=\"age\" attr.type=\"long\"/>")
+        );
+        assert!(
+            content
+                .contains("<key id=\"d4\" for=\"edge\" attr.name=\"since\" attr.type=\"double\"/>")
+        );
+        assert!(content.contains("<data key=\"d3\">x&lt;y</data>"));
+        assert!(content.contains("<data key=\"d4\">2019.5</data>"));
+
+        for path in [json, bin, graphml] {
+            fs::remove_file(path).ok();
+        }
+    }
+
     #[test]
     fn test_graphml_export() {
         let mut g = Graph::<i32, f64>::new();
@@ -523,6 +958,8 @@ mod tests {
             directed: true,
             nodes: vec![1, 2],
             edges: vec![(0, 1, 1.0)],
+            node_properties: PropertyTable::default(),
+            edge_properties: PropertyTable::default(),
         };
         // Undirected Graph should error in strict mode
         type UGraph = crate::core::types::Graph<i32, f64>;
@@ -536,4 +973,59 @@ mod tests {
         assert_eq!(g.node_count(), 2);
         assert_eq!(g.edge_count(), 1);
     }
+
+    #[test]
+    fn test_d3_json_flattens_object_attributes() {
+        #[derive(Clone, Serialize)]
+        struct Person {
+            name: &'static str,
+            id: u32,
+        }
+        let mut g = crate::core::types::Digraph::<Person, f64>::new();
+        let a = g.add_node(Person { name: "a", id: 7 });
+        let b = g.add_node(Person { name: "b", id: 8 });
+        g.add_edge(b, a, 0.5);
+
+        let json = g.to_d3_json().unwrap();
+        assert_eq!(json["directed"], true);
+        assert_eq!(json["nodes"][0]["name"], "a");
+        assert_eq!(json["nodes"][0]["id"], 0);
+        assert_eq!(json["links"][0]["source"], 1);
+        assert_eq!(json["links"][0]["target"], 0);
+        assert_eq!(json["links"][0]["weight"], 0.5);
+
+        let mut plain = Graph::<(), f64>::new();
+        plain.add_node(());
+        assert_eq!(
+            plain.to_d3_json().unwrap()["nodes"][0],
+            serde_json::json!({"id": 0})
+        );
+    }
+
+    #[test]
+    fn test_diagram_exports_escape_labels() {
+        let mut g = Graph::<String, f64>::new();
+        let a = g.add_node("say \"hi\"".to_string());
+        let b = g.add_node("two\nlines".to_string());
+        g.add_edge(a, b, 0.5);
+
+        let mermaid = g.to_mermaid(true);
+        assert!(mermaid.contains("n0[\"say #quot;hi#quot;\"]"));
+        assert!(mermaid.contains("n1[\"two<br>lines\"]"));
+        assert!(mermaid.contains("n0 ---|\"0.5\"| n1"));
+        assert!(g.to_mermaid(false).ends_with("n0 --- n1\n"));
+
+        let uml = g.to_plantuml(true);
+        assert!(uml.contains("rectangle \"say 'hi'\" as n0"));
+        assert!(uml.contains("rectangle \"two\\nlines\" as n1"));
+        assert!(uml.contains("n0 -- n1 : 0.5\n"));
+        assert!(uml.ends_with("@enduml\n"));
+
+        let mut d = Digraph::<i32, i32>::new();
+        let x = d.add_node(1);
+        let y = d.add_node(2);
+        d.add_edge(x, y, 7);
+        assert!(d.to_mermaid(false).contains("n0 --> n1\n"));
+        assert!(d.to_plantuml(true).contains("n0 --> n1 : 7\n"));
+    }
 }
@@ -0,0 +1,898 @@
+/*!
+# Temporal Graphs
+
+A temporal graph is a graph whose edges are only usable during a time interval. Each edge
+(a *contact*) carries a start time and an end time: a walk may enter the edge at its start
+and leaves it at its end. A time-respecting path (a *journey*) uses contacts in order, so each
+contact must start no earlier than the previous one ended.
+
+Questions such as "when is the earliest I can reach `v`?" have no static equivalent: a path
+that exists in the aggregated graph can be impossible in time. This module provides the
+`TemporalGraph` type and the following journey algorithms:
+
+- **Earliest arrival:** the first time each node can be reached from a source.
+- **Latest departure:** the last time each node can leave and still reach a target in time.
+- **Fastest journeys:** the shortest duration of any journey from a source to each node.
+- **Temporal reachability:** the sets of nodes reachable from, or able to reach, a node
+  within a time window.
+- **Journeys:** an earliest-arrival or fewest-hops journey to a single target, as the nodes it
+  passes through.
+
+[`TemporalGraph::graph_at`] and [`TemporalGraph::window`] cut the graph down to one instant or
+one time window, the first as an ordinary static graph for the rest of the library.
+
+Times are `f64` values and must be finite, with `start <= end`. Edge weights are stored with
+each contact but are not used by the journey algorithms, which optimize time only.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::paths::PathFindResult;
+use crate::core::types::{BaseGraph, Directed, EdgeId, GraphConstructor, NodeId, NodeMap, NodeSet};
+use ordered_float::OrderedFloat;
+use petgraph::Direction;
+use petgraph::EdgeType;
+use petgraph::visit::EdgeRef;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// An edge of a temporal graph: a weight together with the interval in which it is active.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemporalEdge<W> {
+    /// The edge weight.
+    pub weight: W,
+    /// The time at which the edge becomes usable (the departure time of a traversal).
+    pub start: f64,
+    /// The time at which the edge stops being usable (the arrival time of a traversal).
+    pub end: f64,
+}
+
+impl<W> TemporalEdge<W> {
+    /// Returns the length of the active interval.
+    pub fn duration(&self) -> f64 {
+        self.end - self.start
+    }
+
+    /// Returns true if the edge is active at time `t` (inclusive at both ends).
+    pub fn is_active_at(&self, t: f64) -> bool {
+        self.start <= t && t <= self.end
+    }
+}
+
+/// A graph whose edges carry time intervals.
+///
+/// Nodes are static; edges are [`TemporalEdge`]s, and any number of them may connect the
+/// same pair of nodes at different times. The `Ty` parameter selects directed (the default)
+/// or undirected contacts, as for [`BaseGraph`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::temporal::{TemporalGraph, earliest_arrival};
+///
+/// let mut tg = TemporalGraph::<&str, f64>::new();
+/// let a = tg.add_node("a");
+/// let b = tg.add_node("b");
+/// let c = tg.add_node("c");
+/// tg.add_edge(a, b, 1.0, 1.0, 2.0).unwrap();
+/// tg.add_edge(b, c, 1.0, 0.5, 1.0).unwrap(); // over before `b` is reached
+/// tg.add_edge(b, c, 1.0, 3.0, 4.0).unwrap();
+///
+/// let (arrival, _) = earliest_arrival(&tg, a, 0.0).unwrap();
+/// assert_eq!(arrival[&c], Some(4.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TemporalGraph<A, W, Ty = Directed>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    pub(crate) graph: BaseGraph<A, TemporalEdge<W>, Ty>,
+}
+
+impl<A, W, Ty> Default for TemporalGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, W, Ty> TemporalGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    /// Creates a new, empty temporal graph.
+    pub fn new() -> Self {
+        Self {
+            graph: BaseGraph::new(),
+        }
+    }
+
+    /// Returns true if contacts are directed.
+    pub fn is_directed(&self) -> bool {
+        <Ty as GraphConstructor<A, TemporalEdge<W>>>::is_directed()
+    }
+
+    /// Returns true if the graph has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    /// Returns the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Returns the number of temporal edges.
+    pub fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    /// Returns true if the node exists.
+    pub fn contains_node(&self, node: NodeId) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    /// Adds a node with the given attribute.
+    pub fn add_node(&mut self, attr: A) -> NodeId {
+        self.graph.add_node(attr)
+    }
+
+    /// Adds an edge from `source` to `target` that is active from `start` to `end`.
+    ///
+    /// # Errors
+    ///
+    /// * `NodeNotFound` if either endpoint is missing.
+    /// * `InvalidArgument` if a time is not finite or `end < start`.
+    pub fn add_edge(
+        &mut self,
+        source: NodeId,
+        target: NodeId,
+        weight: W,
+        start: f64,
+        end: f64,
+    ) -> Result<EdgeId> {
+        if !self.contains_node(source) || !self.contains_node(target) {
+            return Err(GraphinaError::node_not_found(format!(
+                "Cannot add temporal edge {:?} -> {:?}: node not found",
+                source, target
+            )));
+        }
+        if !start.is_finite() || !end.is_finite() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Temporal edge times must be finite, got [{}, {}]",
+                start, end
+            )));
+        }
+        if end < start {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Temporal edge must not end before it starts, got [{}, {}]",
+                start, end
+            )));
+        }
+        Ok(self
+            .graph
+            .add_edge(source, target, TemporalEdge { weight, start, end }))
+    }
+
+    /// Returns the attribute of a node.
+    pub fn node_attr(&self, node: NodeId) -> Option<&A> {
+        self.graph.node_attr(node)
+    }
+
+    /// Returns an iterator over all nodes and their attributes.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &A)> + '_ {
+        self.graph.nodes()
+    }
+
+    /// Returns an iterator over all node IDs.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.graph.node_ids()
+    }
+
+    /// Returns an iterator over all temporal edges as `(source, target, edge)`.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &TemporalEdge<W>)> + '_ {
+        self.graph.edges()
+    }
+
+    /// Returns the earliest start and latest end over all edges, or `None` without edges.
+    pub fn time_span(&self) -> Option<(f64, f64)> {
+        self.edges().fold(None, |span, (_, _, e)| match span {
+            None => Some((e.start, e.end)),
+            Some((lo, hi)) => Some((lo.min(e.start), hi.max(e.end))),
+        })
+    }
+
+    /// Returns the static graph underlying this temporal graph.
+    pub fn as_base_graph(&self) -> &BaseGraph<A, TemporalEdge<W>, Ty> {
+        &self.graph
+    }
+
+    /// Returns the snapshot at time `t`: a static graph with every node and the edges active
+    /// at `t`, keeping their weights.
+    ///
+    /// Temporal graphs never remove nodes, so each node keeps its `NodeId` in the snapshot.
+    ///
+    /// ```rust
+    /// use graphina::core::temporal::TemporalGraph;
+    ///
+    /// let mut tg = TemporalGraph::<&str, f64>::new();
+    /// let a = tg.add_node("a");
+    /// let b = tg.add_node("b");
+    /// tg.add_edge(a, b, 1.0, 0.0, 2.0).unwrap();
+    /// tg.add_edge(b, a, 5.0, 3.0, 4.0).unwrap();
+    ///
+    /// let snapshot = tg.graph_at(1.0);
+    /// assert_eq!(snapshot.edge_count(), 1);
+    /// assert!(snapshot.contains_edge(a, b));
+    /// ```
+    pub fn graph_at(&self, t: f64) -> BaseGraph<A, W, Ty>
+    where
+        A: Clone,
+        W: Clone,
+        Ty: GraphConstructor<A, W>,
+    {
+        let mut snapshot = BaseGraph::new();
+        for (_, attr) in self.nodes() {
+            snapshot.add_node(attr.clone());
+        }
+        for (u, v, edge) in self.edges().filter(|(_, _, e)| e.is_active_at(t)) {
+            snapshot.add_edge(u, v, edge.weight.clone());
+        }
+        snapshot
+    }
+
+    /// Returns the temporal graph restricted to the window `[start, end]`: every node, and
+    /// the edges whose whole interval lies inside the window.
+    ///
+    /// Contacts that only overlap the window are dropped, so the journeys of the result are
+    /// exactly the journeys that depart no earlier than `start` and arrive no later than
+    /// `end`, matching [`temporal_reachable_from`]. Node IDs are kept.
+    ///
+    /// # Errors
+    ///
+    /// `InvalidArgument` if the window is not finite or `end < start`.
+    pub fn window(&self, start: f64, end: f64) -> Result<Self>
+    where
+        A: Clone,
+        W: Clone,
+    {
+        validate_window(start, end)?;
+        let mut windowed = Self::new();
+        for (_, attr) in self.nodes() {
+            windowed.add_node(attr.clone());
+        }
+        for (u, v, edge) in self.edges() {
+            if start <= edge.start && edge.end <= end {
+                windowed.graph.add_edge(u, v, edge.clone());
+            }
+        }
+        Ok(windowed)
+    }
+
+    /// Returns the contacts that can be taken from `node`, as `(neighbor, edge)`.
+    ///
+    /// For undirected graphs this includes every incident edge.
+    fn contacts_from(&self, node: NodeId) -> impl Iterator<Item = (NodeId, &TemporalEdge<W>)> {
+        self.graph
+            .inner
+            .edges_directed(node.0, Direction::Outgoing)
+            .map(|e| (NodeId::new(e.target()), e.weight()))
+    }
+
+    /// Returns the contacts that can lead into `node`, as `(neighbor, edge)`.
+    ///
+    /// For undirected graphs this includes every incident edge.
+    fn contacts_into(&self, node: NodeId) -> impl Iterator<Item = (NodeId, &TemporalEdge<W>)> {
+        self.graph
+            .inner
+            .edges_directed(node.0, Direction::Incoming)
+            .map(|e| (NodeId::new(e.source()), e.weight()))
+    }
+
+    fn index_bound(&self) -> usize {
+        self.node_ids()
+            .map(|n| n.index())
+            .max()
+            .map_or(0, |m| m + 1)
+    }
+
+    fn dense_to_nodemap<T: Copy>(&self, dense: &[T]) -> NodeMap<T> {
+        self.node_ids().map(|u| (u, dense[u.index()])).collect()
+    }
+
+    fn require_node(&self, node: NodeId) -> Result<()> {
+        if self.contains_node(node) {
+            Ok(())
+        } else {
+            Err(GraphinaError::node_not_found(format!(
+                "Node {:?} is not in the temporal graph",
+                node
+            )))
+        }
+    }
+}
+
+fn require_finite(t: f64, name: &str) -> Result<()> {
+    if t.is_finite() {
+        Ok(())
+    } else {
+        Err(GraphinaError::invalid_argument(format!(
+            "{} must be finite, got {}",
+            name, t
+        )))
+    }
+}
+
+// ============================
+// Time-Respecting Paths
+// ============================
+
+/// Dense earliest-arrival search: the label-setting analogue of Dijkstra's algorithm where
+/// the label of a node is the time it is reached. Contacts that end after `arrive_by` are
+/// ignored.
+fn earliest_arrival_dense<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+    depart_after: f64,
+    arrive_by: Option<f64>,
+) -> (Vec<Option<f64>>, Vec<Option<NodeId>>)
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    let bound = graph.index_bound();
+    let mut arrival: Vec<Option<f64>> = vec![None; bound];
+    let mut pred: Vec<Option<NodeId>> = vec![None; bound];
+    let mut heap = BinaryHeap::new();
+
+    arrival[source.index()] = Some(depart_after);
+    heap.push(Reverse((OrderedFloat(depart_after), source)));
+
+    while let Some(Reverse((OrderedFloat(t), u))) = heap.pop() {
+        if arrival[u.index()].is_some_and(|best| t > best) {
+            continue;
+        }
+        for (v, edge) in graph.contacts_from(u) {
+            if edge.start < t || arrive_by.is_some_and(|limit| edge.end > limit) {
+                continue;
+            }
+            let vi = v.index();
+            if arrival[vi].is_none_or(|best| edge.end < best) {
+                arrival[vi] = Some(edge.end);
+                pred[vi] = Some(u);
+                heap.push(Reverse((OrderedFloat(edge.end), v)));
+            }
+        }
+    }
+    (arrival, pred)
+}
+
+/// Dense latest-departure search, the time-reversed mirror of [`earliest_arrival_dense`].
+/// Contacts that start before `depart_after` are ignored.
+fn latest_departure_dense<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    target: NodeId,
+    arrive_by: f64,
+    depart_after: Option<f64>,
+) -> (Vec<Option<f64>>, Vec<Option<NodeId>>)
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    let bound = graph.index_bound();
+    let mut departure: Vec<Option<f64>> = vec![None; bound];
+    let mut succ: Vec<Option<NodeId>> = vec![None; bound];
+    let mut heap = BinaryHeap::new();
+
+    departure[target.index()] = Some(arrive_by);
+    heap.push((OrderedFloat(arrive_by), target));
+
+    while let Some((OrderedFloat(t), u)) = heap.pop() {
+        if departure[u.index()].is_some_and(|best| t < best) {
+            continue;
+        }
+        for (x, edge) in graph.contacts_into(u) {
+            if edge.end > t || depart_after.is_some_and(|limit| edge.start < limit) {
+                continue;
+            }
+            let xi = x.index();
+            if departure[xi].is_none_or(|best| edge.start > best) {
+                departure[xi] = Some(edge.start);
+                succ[xi] = Some(u);
+                heap.push((OrderedFloat(edge.start), x));
+            }
+        }
+    }
+    (departure, succ)
+}
+
+/// Computes the earliest time each node can be reached from `source` when leaving no
+/// earlier than `depart_after`.
+///
+/// # Returns
+///
+/// A pair of NodeMaps with one entry per node:
+/// - the earliest arrival time (`None` if unreachable; `depart_after` for the source), and
+/// - the predecessor on an earliest-arrival journey (`None` for the source or unreachable).
+///
+/// # Errors
+///
+/// `NodeNotFound` if `source` is missing, or `InvalidArgument` if `depart_after` is not finite.
+///
+/// # Complexity
+///
+/// O(E log V) time and O(V) space.
+pub fn earliest_arrival<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+    depart_after: f64,
+) -> Result<PathFindResult>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(source)?;
+    require_finite(depart_after, "depart_after")?;
+    let (arrival, pred) = earliest_arrival_dense(graph, source, depart_after, None);
+    Ok((
+        graph.dense_to_nodemap(&arrival),
+        graph.dense_to_nodemap(&pred),
+    ))
+}
+
+/// Computes the latest time each node can leave and still reach `target` by `arrive_by`.
+///
+/// # Returns
+///
+/// A pair of NodeMaps with one entry per node:
+/// - the latest departure time (`None` if the target cannot be reached; `arrive_by` for the
+///   target itself), and
+/// - the next node on a latest-departure journey (`None` for the target or unreachable).
+///
+/// # Errors
+///
+/// `NodeNotFound` if `target` is missing, or `InvalidArgument` if `arrive_by` is not finite.
+///
+/// # Complexity
+///
+/// O(E log V) time and O(V) space.
+pub fn latest_departure<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    target: NodeId,
+    arrive_by: f64,
+) -> Result<PathFindResult>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(target)?;
+    require_finite(arrive_by, "arrive_by")?;
+    let (departure, succ) = latest_departure_dense(graph, target, arrive_by, None);
+    Ok((
+        graph.dense_to_nodemap(&departure),
+        graph.dense_to_nodemap(&succ),
+    ))
+}
+
+/// Computes the minimum duration of any journey from `source` to each node.
+///
+/// The duration of a journey is its arrival time minus the start of its first contact, so
+/// waiting at the source before departing is free. The optimum always departs on one of the
+/// source's contacts, so the function runs an earliest-arrival search from each distinct
+/// departure time of the source and keeps the best duration per node.
+///
+/// # Returns
+///
+/// A NodeMap with the fastest duration per node (`Some(0.0)` for the source, `None` if
+/// unreachable).
+///
+/// # Errors
+///
+/// `NodeNotFound` if `source` is missing.
+///
+/// # Complexity
+///
+/// O(k * E log V) time, where k is the number of distinct start times of edges leaving
+/// `source`.
+pub fn fastest_journeys<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+) -> Result<NodeMap<Option<f64>>>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(source)?;
+    let mut departures: Vec<f64> = graph.contacts_from(source).map(|(_, e)| e.start).collect();
+    departures.sort_by(f64::total_cmp);
+    departures.dedup();
+
+    let mut best: Vec<Option<f64>> = vec![None; graph.index_bound()];
+    for t in departures {
+        let (arrival, _) = earliest_arrival_dense(graph, source, t, None);
+        for (slot, arr) in best.iter_mut().zip(arrival) {
+            if let Some(arr) = arr {
+                let duration = arr - t;
+                if slot.is_none_or(|d| duration < d) {
+                    *slot = Some(duration);
+                }
+            }
+        }
+    }
+    best[source.index()] = Some(0.0);
+    Ok(graph.dense_to_nodemap(&best))
+}
+
+/// Returns the nodes reachable from `source` by a journey that departs no earlier than
+/// `start` and arrives no later than `end`. The source itself is included.
+///
+/// # Errors
+///
+/// `NodeNotFound` if `source` is missing, or `InvalidArgument` if the window is not finite
+/// or `end < start`.
+pub fn temporal_reachable_from<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+    start: f64,
+    end: f64,
+) -> Result<NodeSet>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(source)?;
+    validate_window(start, end)?;
+    let (arrival, _) = earliest_arrival_dense(graph, source, start, Some(end));
+    Ok(graph
+        .node_ids()
+        .filter(|u| arrival[u.index()].is_some())
+        .collect())
+}
+
+/// Returns the nodes that can reach `target` by a journey that departs no earlier than
+/// `start` and arrives no later than `end`. The target itself is included.
+///
+/// # Errors
+///
+/// `NodeNotFound` if `target` is missing, or `InvalidArgument` if the window is not finite
+/// or `end < start`.
+pub fn temporal_reachable_to<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    target: NodeId,
+    start: f64,
+    end: f64,
+) -> Result<NodeSet>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(target)?;
+    validate_window(start, end)?;
+    let (departure, _) = latest_departure_dense(graph, target, end, Some(start));
+    Ok(graph
+        .node_ids()
+        .filter(|u| departure[u.index()].is_some())
+        .collect())
+}
+
+/// A time-respecting path to a single target.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Journey {
+    /// The nodes passed through, from the source to the target.
+    pub path: Vec<NodeId>,
+    /// The time the target is reached.
+    pub arrival: f64,
+}
+
+impl Journey {
+    /// Returns the number of contacts taken.
+    pub fn hops(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+}
+
+/// Finds a journey from `source` to `target` that arrives as early as possible, leaving no
+/// earlier than `depart_after`.
+///
+/// Returns `None` if `target` cannot be reached in time. The journey to the source itself is
+/// the one-node path arriving at `depart_after`.
+///
+/// # Errors
+///
+/// `NodeNotFound` if either node is missing, or `InvalidArgument` if `depart_after` is not
+/// finite.
+///
+/// # Complexity
+///
+/// O(E log V) time and O(V) space.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::temporal::{TemporalGraph, earliest_arrival_journey};
+///
+/// let mut tg = TemporalGraph::<&str, ()>::new();
+/// let a = tg.add_node("a");
+/// let b = tg.add_node("b");
+/// let c = tg.add_node("c");
+/// tg.add_edge(a, b, (), 1.0, 2.0).unwrap();
+/// tg.add_edge(b, c, (), 2.0, 3.0).unwrap();
+/// tg.add_edge(a, c, (), 4.0, 5.0).unwrap();
+///
+/// let journey = earliest_arrival_journey(&tg, a, c, 0.0).unwrap().unwrap();
+/// assert_eq!(journey.path, vec![a, b, c]);
+/// assert_eq!(journey.arrival, 3.0);
+/// ```
+pub fn earliest_arrival_journey<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+    target: NodeId,
+    depart_after: f64,
+) -> Result<Option<Journey>>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(source)?;
+    graph.require_node(target)?;
+    require_finite(depart_after, "depart_after")?;
+    let (arrival, pred) = earliest_arrival_dense(graph, source, depart_after, None);
+    let Some(at) = arrival[target.index()] else {
+        return Ok(None);
+    };
+    let mut path = vec![target];
+    let mut current = target;
+    while let Some(previous) = pred[current.index()] {
+        path.push(previous);
+        current = previous;
+    }
+    path.reverse();
+    Ok(Some(Journey { path, arrival: at }))
+}
+
+/// Finds a journey from `source` to `target` with as few contacts as possible, leaving no
+/// earlier than `depart_after`, and the earliest arrival among those.
+///
+/// This is the time-respecting analogue of breadth-first search. It proceeds in rounds: after
+/// round `k`, each node holds the earliest time it can be reached with at most `k` contacts,
+/// and the search stops at the first round that reaches `target`. Returns `None` if `target`
+/// cannot be reached in time.
+///
+/// # Errors
+///
+/// `NodeNotFound` if either node is missing, or `InvalidArgument` if `depart_after` is not
+/// finite.
+///
+/// # Complexity
+///
+/// O(h * (V + E)) time and O(h * V) space, where h is the number of hops of the result, at
+/// most V - 1.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::temporal::{TemporalGraph, fewest_hops_journey};
+///
+/// let mut tg = TemporalGraph::<&str, ()>::new();
+/// let a = tg.add_node("a");
+/// let b = tg.add_node("b");
+/// let c = tg.add_node("c");
+/// tg.add_edge(a, b, (), 1.0, 2.0).unwrap();
+/// tg.add_edge(b, c, (), 2.0, 3.0).unwrap();
+/// tg.add_edge(a, c, (), 4.0, 5.0).unwrap();
+///
+/// // The direct contact arrives later but takes one hop.
+/// let journey = fewest_hops_journey(&tg, a, c, 0.0).unwrap().unwrap();
+/// assert_eq!(journey.path, vec![a, c]);
+/// assert_eq!(journey.hops(), 1);
+/// ```
+pub fn fewest_hops_journey<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    source: NodeId,
+    target: NodeId,
+    depart_after: f64,
+) -> Result<Option<Journey>>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    graph.require_node(source)?;
+    graph.require_node(target)?;
+    require_finite(depart_after, "depart_after")?;
+    let bound = graph.index_bound();
+    // `rounds[k][v]` is the earliest arrival at `v` with at most `k` contacts, and
+    // `improved[k][v]` the node it came from if round `k` lowered it.
+    let mut first = vec![None; bound];
+    first[source.index()] = Some(depart_after);
+    let mut rounds: Vec<Vec<Option<f64>>> = vec![first];
+    let mut improved: Vec<Vec<Option<NodeId>>> = vec![vec![None; bound]];
+    while rounds.len() <= graph.node_count() {
+        let last = &rounds[rounds.len() - 1];
+        if last[target.index()].is_some() {
+            break;
+        }
+        let mut next = last.clone();
+        let mut from = vec![None; bound];
+        for u in graph.node_ids() {
+            let Some(t) = last[u.index()] else {
+                continue;
+            };
+            for (v, edge) in graph.contacts_from(u) {
+                let vi = v.index();
+                if edge.start >= t && next[vi].is_none_or(|best| edge.end < best) {
+                    next[vi] = Some(edge.end);
+                    from[vi] = Some(u);
+                }
+            }
+        }
+        if from.iter().all(Option::is_none) {
+            return Ok(None);
+        }
+        rounds.push(next);
+        improved.push(from);
+    }
+    let mut round = rounds.len() - 1;
+    let Some(at) = rounds[round][target.index()] else {
+        return Ok(None);
+    };
+    let mut path = vec![target];
+    let mut current = target;
+    while current != source || round > 0 {
+        // Go back to the round that set the current node's arrival time.
+        let time = rounds[round][current.index()];
+        while round > 0 && rounds[round - 1][current.index()] == time {
+            round -= 1;
+        }
+        let Some(previous) = improved[round][current.index()] else {
+            break;
+        };
+        path.push(previous);
+        current = previous;
+        round -= 1;
+    }
+    path.reverse();
+    Ok(Some(Journey { path, arrival: at }))
+}
+
+fn validate_window(start: f64, end: f64) -> Result<()> {
+    require_finite(start, "Window start")?;
+    require_finite(end, "Window end")?;
+    if end < start {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Window must not end before it starts, got [{}, {}]",
+            start, end
+        )));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Undirected;
+
+    /// a -> b at [1, 2], b -> c at [0, 1] (too early) and [3, 5], a -> c at [6, 7], c -> d at [5, 6].
+    fn sample() -> (TemporalGraph<&'static str, ()>, [NodeId; 4]) {
+        let mut tg = TemporalGraph::new();
+        let a = tg.add_node("a");
+        let b = tg.add_node("b");
+        let c = tg.add_node("c");
+        let d = tg.add_node("d");
+        tg.add_edge(a, b, (), 1.0, 2.0).unwrap();
+        tg.add_edge(b, c, (), 0.0, 1.0).unwrap();
+        tg.add_edge(b, c, (), 3.0, 5.0).unwrap();
+        tg.add_edge(a, c, (), 6.0, 7.0).unwrap();
+        tg.add_edge(c, d, (), 5.0, 6.0).unwrap();
+        (tg, [a, b, c, d])
+    }
+
+    #[test]
+    fn test_add_edge_validation() {
+        let mut tg = TemporalGraph::<i32, f64>::new();
+        let a = tg.add_node(0);
+        let b = tg.add_node(1);
+        assert!(tg.add_edge(a, b, 1.0, 2.0, 1.0).is_err());
+        assert!(tg.add_edge(a, b, 1.0, f64::NAN, 1.0).is_err());
+        assert!(tg.add_edge(a, b, 1.0, 1.0, 1.0).is_ok());
+        assert_eq!(tg.edge_count(), 1);
+        assert_eq!(tg.time_span(), Some((1.0, 1.0)));
+    }
+
+    #[test]
+    fn test_earliest_arrival_respects_time() {
+        let (tg, [a, b, c, d]) = sample();
+        let (arrival, pred) = earliest_arrival(&tg, a, 0.0).unwrap();
+        assert_eq!(arrival[&a], Some(0.0));
+        assert_eq!(arrival[&b], Some(2.0));
+        assert_eq!(arrival[&c], Some(5.0));
+        assert_eq!(arrival[&d], Some(6.0));
+        assert_eq!(pred[&c], Some(b));
+        assert_eq!(pred[&d], Some(c));
+
+        // Leaving after 1 misses a -> b, so c is only reached directly at 7, too late for d.
+        let (late, _) = earliest_arrival(&tg, a, 1.5).unwrap();
+        assert_eq!(late[&b], None);
+        assert_eq!(late[&c], Some(7.0));
+        assert_eq!(late[&d], None);
+    }
+
+    #[test]
+    fn test_latest_departure() {
+        let (tg, [a, b, c, d]) = sample();
+        let (departure, succ) = latest_departure(&tg, d, 6.0).unwrap();
+        assert_eq!(departure[&d], Some(6.0));
+        assert_eq!(departure[&c], Some(5.0));
+        assert_eq!(departure[&b], Some(3.0));
+        assert_eq!(departure[&a], Some(1.0));
+        assert_eq!(succ[&a], Some(b));
+    }
+
+    #[test]
+    fn test_fastest_journeys() {
+        let (tg, [a, b, c, d]) = sample();
+        let fastest = fastest_journeys(&tg, a).unwrap();
+        assert_eq!(fastest[&a], Some(0.0));
+        assert_eq!(fastest[&b], Some(1.0));
+        // a -> c directly takes 1, beating the earlier but slower route via b.
+        assert_eq!(fastest[&c], Some(1.0));
+        assert_eq!(fastest[&d], Some(5.0));
+    }
+
+    #[test]
+    fn test_temporal_reachability_windows() {
+        let (tg, [a, b, c, d]) = sample();
+        let from_a = temporal_reachable_from(&tg, a, 0.0, 5.0).unwrap();
+        assert_eq!(from_a, [a, b, c].into_iter().collect());
+        let to_d = temporal_reachable_to(&tg, d, 0.0, 10.0).unwrap();
+        assert_eq!(to_d, [a, b, c, d].into_iter().collect());
+        let to_d_late = temporal_reachable_to(&tg, d, 2.0, 10.0).unwrap();
+        assert_eq!(to_d_late, [b, c, d].into_iter().collect());
+        assert!(temporal_reachable_from(&tg, a, 5.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_snapshots_and_windows() {
+        let (tg, [a, b, c, d]) = sample();
+        let at_5 = tg.graph_at(5.0);
+        assert_eq!(at_5.node_count(), 4);
+        // b -> c ends at 5 and c -> d starts at 5, both active at the boundary.
+        assert_eq!(at_5.edge_count(), 2);
+        assert!(at_5.contains_edge(b, c) && at_5.contains_edge(c, d));
+
+        let early = tg.window(0.0, 5.0).unwrap();
+        assert_eq!(early.edge_count(), 3);
+        assert_eq!(early.node_count(), 4);
+        let (arrival, _) = earliest_arrival(&early, a, 0.0).unwrap();
+        assert_eq!(arrival[&c], Some(5.0));
+        assert_eq!(arrival[&d], None);
+        assert!(tg.window(1.0, 0.0).is_err());
+    }
+
+    #[test]
+    fn test_journeys_to_a_target() {
+        let (tg, [a, b, c, d]) = sample();
+        let earliest = earliest_arrival_journey(&tg, a, d, 0.0).unwrap().unwrap();
+        assert_eq!(earliest.path, vec![a, b, c, d]);
+        assert_eq!(earliest.arrival, 6.0);
+        // The direct a -> c contact saves a hop but lands after c -> d has ended.
+        let fewest = fewest_hops_journey(&tg, a, d, 0.0).unwrap().unwrap();
+        assert_eq!(fewest, earliest);
+        let to_c = fewest_hops_journey(&tg, a, c, 0.0).unwrap().unwrap();
+        assert_eq!((to_c.path, to_c.arrival), (vec![a, c], 7.0));
+
+        let itself = fewest_hops_journey(&tg, b, b, 3.5).unwrap().unwrap();
+        assert_eq!((itself.hops(), itself.arrival), (0, 3.5));
+        assert_eq!(earliest_arrival_journey(&tg, d, a, 0.0).unwrap(), None);
+        assert_eq!(fewest_hops_journey(&tg, d, a, 0.0).unwrap(), None);
+        assert_eq!(fewest_hops_journey(&tg, a, d, 1.5).unwrap(), None);
+    }
+
+    #[test]
+    fn test_undirected_contacts_work_both_ways() {
+        let mut tg = TemporalGraph::<i32, (), Undirected>::new();
+        let a = tg.add_node(0);
+        let b = tg.add_node(1);
+        let c = tg.add_node(2);
+        tg.add_edge(b, a, (), 1.0, 1.0).unwrap();
+        tg.add_edge(c, b, (), 2.0, 2.0).unwrap();
+        let (arrival, _) = earliest_arrival(&tg, a, 0.0).unwrap();
+        assert_eq!(arrival[&c], Some(2.0));
+        let (departure, _) = latest_departure(&tg, c, 5.0).unwrap();
+        assert_eq!(departure[&a], Some(1.0));
+        // Reversed in time, c cannot reach a as the b-c contact happens after a-b.
+        let (from_c, _) = earliest_arrival(&tg, c, 0.0).unwrap();
+        assert_eq!(from_c[&a], None);
+    }
+}
@@ -15,7 +15,8 @@ the operations that make sense for their graph type.
 */
 
 use crate::core::error::Result;
-use crate::core::types::{EdgeId, NodeId};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+use petgraph::EdgeType;
 
 /// Core read-only graph operations.
 ///
@@ -192,6 +193,84 @@ where
         Self: Sized;
 }
 
+// `BaseGraph` implements the query and traversal traits by delegating to its inherent methods,
+// so code written against the traits accepts a graph as well as the views in `core::views`.
+impl<A, W, Ty> GraphQuery<A, W> for BaseGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    fn is_directed(&self) -> bool {
+        BaseGraph::is_directed(self)
+    }
+
+    fn is_empty(&self) -> bool {
+        BaseGraph::is_empty(self)
+    }
+
+    fn node_count(&self) -> usize {
+        BaseGraph::node_count(self)
+    }
+
+    fn edge_count(&self) -> usize {
+        BaseGraph::edge_count(self)
+    }
+
+    fn contains_node(&self, node: NodeId) -> bool {
+        BaseGraph::contains_node(self, node)
+    }
+
+    fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        BaseGraph::contains_edge(self, source, target)
+    }
+
+    fn node_attr(&self, node: NodeId) -> Option<&A> {
+        BaseGraph::node_attr(self, node)
+    }
+
+    fn edge_weight(&self, source: NodeId, target: NodeId) -> Option<&W> {
+        self.find_edge(source, target)
+            .and_then(|e| BaseGraph::edge_weight(self, e))
+    }
+}
+
+impl<A, W, Ty> GraphTraversal<A, W> for BaseGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    type NodeIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+    type NeighborIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+
+    fn node_ids(&self) -> Self::NodeIter<'_> {
+        Box::new(BaseGraph::node_ids(self))
+    }
+
+    fn neighbors(&self, node: NodeId) -> Self::NeighborIter<'_> {
+        Box::new(BaseGraph::neighbors(self, node))
+    }
+
+    fn degree(&self, node: NodeId) -> Option<usize> {
+        BaseGraph::degree(self, node)
+    }
+
+    fn in_degree(&self, node: NodeId) -> Option<usize> {
+        BaseGraph::in_degree(self, node)
+    }
+
+    fn out_degree(&self, node: NodeId) -> Option<usize> {
+        BaseGraph::out_degree(self, node)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
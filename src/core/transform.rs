@@ -0,0 +1,221 @@
+/*!
+# Structural Transforms
+
+Operations that derive a new graph from the shape of an existing one. The line graph turns
+every edge into a node, which lets node algorithms such as community detection work on edges.
+Subdivision replaces every edge with a path, which is handy for layouts that bend long edges
+and for reductions that need every edge to have a midpoint.
+
+Like contraction, these never modify their input and return a map back to the original graph.
+*/
+
+use std::collections::HashSet;
+
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId, NodeMap};
+use petgraph::EdgeType;
+
+/// Builds the line graph, in which every edge of `graph` becomes a node.
+///
+/// Each node of the result carries the weight of the edge it stands for, and the returned map
+/// gives that edge's ID. In an undirected graph two nodes are linked when their edges share an
+/// endpoint; edges that share both endpoints are linked once. In a directed graph `(u, v)`
+/// links to `(v, w)`, so a self-loop links to itself. The weight of every line-graph edge is
+/// the original node the two edges meet at.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::transform::line_graph;
+/// use graphina::core::types::Graph;
+///
+/// // A path a - b - c - d has three edges, and its line graph is a path over them.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// let bc = g.add_edge(n[1], n[2], 2.0);
+/// g.add_edge(n[2], n[3], 3.0);
+///
+/// let (lg, edges) = line_graph(&g);
+/// assert_eq!(lg.node_count(), 3);
+/// assert_eq!(lg.edge_count(), 2);
+/// let middle = lg.node_ids().find(|v| edges[v] == bc).unwrap();
+/// assert_eq!(lg.node_attr(middle), Some(&2.0));
+/// assert_eq!(lg.degree(middle), Some(2));
+/// ```
+pub fn line_graph<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> (BaseGraph<W, NodeId, Ty>, NodeMap<EdgeId>)
+where
+    W: Clone,
+    Ty: GraphConstructor<A, W> + GraphConstructor<W, NodeId> + EdgeType,
+{
+    let mut result = BaseGraph::<W, NodeId, Ty>::with_capacity(graph.edge_count(), 0);
+    let mut origin = NodeMap::default();
+    let mut heads: NodeMap<Vec<NodeId>> = NodeMap::default();
+    let mut tails: NodeMap<Vec<NodeId>> = NodeMap::default();
+    for (edge, u, v, w) in graph.edges_with_ids() {
+        let node = result.add_node(w.clone());
+        origin.insert(node, edge);
+        tails.entry(u).or_default().push(node);
+        if u != v || graph.is_directed() {
+            heads.entry(v).or_default().push(node);
+        }
+    }
+
+    if graph.is_directed() {
+        for (shared, incoming) in &heads {
+            if let Some(outgoing) = tails.get(shared) {
+                for &a in incoming {
+                    for &b in outgoing {
+                        result.add_edge(a, b, *shared);
+                    }
+                }
+            }
+        }
+    } else {
+        let mut linked = HashSet::new();
+        for shared in graph.node_ids() {
+            let incident: Vec<NodeId> = tails
+                .get(&shared)
+                .into_iter()
+                .chain(heads.get(&shared))
+                .flatten()
+                .copied()
+                .collect();
+            for (i, &a) in incident.iter().enumerate() {
+                for &b in &incident[i + 1..] {
+                    if linked.insert((a.min(b), a.max(b))) {
+                        result.add_edge(a, b, shared);
+                    }
+                }
+            }
+        }
+    }
+
+    (result, origin)
+}
+
+/// Replaces every edge with a path through `k` new nodes.
+///
+/// `new_attr` receives the ID of the edge being split and the position of the new node along
+/// it, from `0` next to the source to `k - 1` next to the target. Each of the `k + 1` pieces
+/// keeps the weight of the original edge, and directed edges keep their orientation. With
+/// `k == 0` the result is a copy of `graph`. The returned map sends each original node to its
+/// copy.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::transform::subdivide_edges;
+/// use graphina::core::types::Digraph;
+///
+/// let mut g = Digraph::<&str, f64>::new();
+/// let a = g.add_node("a");
+/// let b = g.add_node("b");
+/// g.add_edge(a, b, 1.0);
+///
+/// let (s, map) = subdivide_edges(&g, 2, |_, _| "mid");
+/// assert_eq!(s.node_count(), 4);
+/// assert_eq!(s.edge_count(), 3);
+/// assert_eq!(s.out_degree(map[&a]), Some(1));
+/// assert_eq!(s.in_degree(map[&b]), Some(1));
+/// ```
+pub fn subdivide_edges<A, W, Ty, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    k: usize,
+    mut new_attr: F,
+) -> (BaseGraph<A, W, Ty>, NodeMap<NodeId>)
+where
+    A: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: FnMut(EdgeId, usize) -> A,
+{
+    let mut result = BaseGraph::<A, W, Ty>::with_capacity(
+        graph.node_count() + k * graph.edge_count(),
+        (k + 1) * graph.edge_count(),
+    );
+    let mut mapping = NodeMap::default();
+    for (node, attr) in graph.nodes() {
+        mapping.insert(node, result.add_node(attr.clone()));
+    }
+    for (edge, u, v, w) in graph.edges_with_ids() {
+        let mut prev = mapping[&u];
+        for step in 0..k {
+            let mid = result.add_node(new_attr(edge, step));
+            result.add_edge(prev, mid, w.clone());
+            prev = mid;
+        }
+        result.add_edge(prev, mapping[&v], w.clone());
+    }
+    (result, mapping)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_line_graph_undirected_and_directed() {
+        // A triangle plus a parallel edge and a self-loop.
+        let mut g = Graph::<(), f64>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        g.add_edge(a, b, 1.0);
+        g.add_edge(b, c, 1.0);
+        g.add_edge(c, a, 1.0);
+        g.add_edge(a, b, 2.0);
+        g.add_edge(c, c, 3.0);
+        let (lg, origin) = line_graph(&g);
+        assert_eq!(lg.node_count(), 5);
+        assert_eq!(origin.len(), 5);
+        // The four non-loop edges pairwise share an endpoint, and two of them meet the loop at c
+        assert_eq!(lg.edge_count(), 8);
+        assert!(lg.edges().all(|(x, y, _)| x != y));
+
+        let mut d = Digraph::<(), f64>::new();
+        let a = d.add_node(());
+        let b = d.add_node(());
+        let c = d.add_node(());
+        let ab = d.add_edge(a, b, 1.0);
+        let bc = d.add_edge(b, c, 1.0);
+        let cc = d.add_edge(c, c, 1.0);
+        let (lg, origin) = line_graph(&d);
+        let node_of = |e| lg.node_ids().find(|v| origin[v] == e).unwrap();
+        let mut links: Vec<_> = lg
+            .edges()
+            .map(|(x, y, &at)| (origin[&x], origin[&y], at))
+            .collect();
+        links.sort_by_key(|&(x, y, _)| (x.index(), y.index()));
+        assert_eq!(links, vec![(ab, bc, b), (bc, cc, c), (cc, cc, c)]);
+        assert_eq!(lg.out_degree(node_of(ab)), Some(1));
+    }
+
+    #[test]
+    fn test_subdivide_edges() {
+        let mut g = Graph::<u32, f64>::new();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        let c = g.add_node(3);
+        let ab = g.add_edge(a, b, 4.0);
+        g.add_edge(b, c, 5.0);
+
+        let (s, map) = subdivide_edges(&g, 3, |e, i| 100 * e.index() as u32 + i as u32 + 10);
+        assert_eq!(s.node_count(), 9);
+        assert_eq!(s.edge_count(), 8);
+        assert_eq!(s.node_attr(map[&b]), Some(&2));
+        assert_eq!(s.degree(map[&b]), Some(2));
+        assert_eq!(s.degree(map[&a]), Some(1));
+        let first_mid = s.neighbors(map[&a]).next().unwrap();
+        assert_eq!(
+            s.node_attr(first_mid),
+            Some(&(100 * ab.index() as u32 + 10))
+        );
+        assert!(s.edges().all(|(_, _, &w)| w == 4.0 || w == 5.0));
+
+        let (copy, _) = subdivide_edges(&g, 0, |_, _| 0);
+        assert_eq!((copy.node_count(), copy.edge_count()), (3, 2));
+    }
+}
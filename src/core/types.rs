@@ -20,13 +20,16 @@ g.try_update_node(n1, 30).expect("Node update should succeed");
 */
 // Import the new unified error type
 use crate::core::error::{GraphinaError, Result};
+use crate::core::properties::PropertyTable;
 use petgraph::EdgeType;
 use petgraph::graph::{EdgeIndex, NodeIndex};
 use petgraph::prelude::EdgeRef;
 use petgraph::stable_graph::StableGraph as PetGraph;
 use petgraph::visit::{IntoEdgeReferences, IntoNodeReferences};
 use sprs::{CsMat, TriMat};
+use std::borrow::Borrow;
 use std::collections::HashMap;
+use std::hash::Hash;
 use std::ops::{Index, IndexMut};
 /// Marker type for directed graphs.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -117,6 +120,17 @@ pub type DigraphMarker = Directed;
 pub type Graph<A, W> = BaseGraph<A, W, Undirected>;
 /// Marker alias for undirected graphs.
 pub type GraphMarker = Undirected;
+/// Type alias for an undirected graph that is expected to hold parallel edges.
+///
+/// `BaseGraph` always stores every edge added with [`BaseGraph::add_edge`] under its own
+/// [`EdgeId`], so a `MultiGraph` is the same type as a [`Graph`]; the alias states the
+/// intent. Use [`BaseGraph::edges_between`] to reach every parallel edge, and
+/// [`BaseGraph::add_edge_if_absent`] to keep a graph simple.
+pub type MultiGraph<A, W> = BaseGraph<A, W, Undirected>;
+/// Type alias for a directed graph that is expected to hold parallel edges.
+///
+/// See [`MultiGraph`]; edges `u -> v` and `v -> u` are never parallel to each other here.
+pub type MultiDigraph<A, W> = BaseGraph<A, W, Directed>;
 /// type alias for HashMap mapping NodeId to T
 ///
 /// Uses `FxBuildHasher` rather than the default SipHash. `NodeId` keys are small
@@ -136,9 +150,14 @@ pub type NodeSet = std::collections::HashSet<NodeId, rustc_hash::FxBuildHasher>;
 /// - `A`: Node attribute type.
 /// - `W`: Edge weight type.
 /// - `Ty`: Graph type (directed/undirected) implementing `GraphConstructor` and `EdgeType`.
+///
+/// Nodes and edges can also carry named properties of mixed types; see
+/// [`core::properties`](crate::core::properties).
 #[derive(Debug, Clone)]
 pub struct BaseGraph<A, W, Ty: GraphConstructor<A, W> + EdgeType> {
     pub(crate) inner: PetGraph<A, W, Ty>,
+    pub(crate) node_props: PropertyTable,
+    pub(crate) edge_props: PropertyTable,
 }
 impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Default for BaseGraph<A, W, Ty> {
     fn default() -> Self {
@@ -150,6 +169,8 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
     pub fn new() -> Self {
         Self {
             inner: Ty::new_graph(),
+            node_props: PropertyTable::default(),
+            edge_props: PropertyTable::default(),
         }
     }
     /// Creates a new graph with pre-allocated capacity for nodes and edges.
@@ -158,6 +179,8 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
     pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         Self {
             inner: PetGraph::<A, W, Ty>::with_capacity(nodes, edges),
+            node_props: PropertyTable::default(),
+            edge_props: PropertyTable::default(),
         }
     }
     /// Returns a builder for constructing a graph with a fluent API.
@@ -322,23 +345,43 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
         edge_ids
     }
     /// Removes a node from the graph, returning its attribute if it existed.
+    ///
+    /// The properties of the node and of its edges are cleared.
     pub fn remove_node(&mut self, node: NodeId) -> Option<A> {
-        self.inner.remove_node(node.0)
+        if !self.edge_props.is_empty() {
+            let incident: Vec<usize> = self
+                .inner
+                .edges_directed(node.0, petgraph::Direction::Outgoing)
+                .chain(
+                    self.inner
+                        .edges_directed(node.0, petgraph::Direction::Incoming),
+                )
+                .map(|e| e.id().index())
+                .collect();
+            for edge in incident {
+                self.edge_props.clear_index(edge);
+            }
+        }
+        let attr = self.inner.remove_node(node.0)?;
+        self.node_props.clear_index(node.index());
+        Some(attr)
     }
     /// Attempts to remove a node from the graph.
     pub fn try_remove_node(&mut self, node: NodeId) -> Result<A> {
-        self.inner
-            .remove_node(node.0)
+        self.remove_node(node)
             .ok_or_else(|| GraphinaError::node_not_found("Node not found during removal"))
     }
     /// Removes an edge from the graph, returning its weight if it existed.
+    ///
+    /// The properties of the edge are cleared.
     pub fn remove_edge(&mut self, edge: EdgeId) -> Option<W> {
-        self.inner.remove_edge(edge.0)
+        let weight = self.inner.remove_edge(edge.0)?;
+        self.edge_props.clear_index(edge.index());
+        Some(weight)
     }
     /// Attempts to remove an edge from the graph.
     pub fn try_remove_edge(&mut self, edge: EdgeId) -> Result<W> {
-        self.inner
-            .remove_edge(edge.0)
+        self.remove_edge(edge)
             .ok_or_else(|| GraphinaError::edge_not_found("Edge not found during removal"))
     }
     /// Returns the number of nodes in the graph.
@@ -349,6 +392,90 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
     pub fn edge_count(&self) -> usize {
         self.inner.edge_count()
     }
+    /// Reserves room for at least `additional` more nodes.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.inner.reserve_nodes(additional);
+    }
+    /// Reserves room for at least `additional` more edges.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.inner.reserve_edges(additional);
+    }
+    /// Returns the allocated node and edge capacity.
+    ///
+    /// Removed nodes and edges leave vacant slots that still count against the capacity;
+    /// see [`vacant_slots`](Self::vacant_slots).
+    pub fn capacity(&self) -> (usize, usize) {
+        self.inner.capacity()
+    }
+    /// Returns how many node and edge slots are held by removed elements.
+    ///
+    /// Removal keeps the slot so that other IDs stay valid. New elements reuse vacant slots,
+    /// but a graph that shrank for good keeps paying for them until [`compact`](Self::compact).
+    pub fn vacant_slots(&self) -> (usize, usize) {
+        use petgraph::visit::{EdgeIndexable, NodeIndexable};
+        (
+            self.inner.node_bound() - self.inner.node_count(),
+            EdgeIndexable::edge_bound(&self.inner) - self.inner.edge_count(),
+        )
+    }
+    /// Releases spare capacity beyond the last used slot.
+    ///
+    /// Vacant slots in the middle are kept, since freeing them would change IDs. Call
+    /// [`compact`](Self::compact) first to drop them as well.
+    pub fn shrink_to_fit(&mut self) {
+        self.inner.shrink_to_fit();
+    }
+    /// Rebuilds the graph without vacant slots and returns the new ID of every node.
+    ///
+    /// Nodes and edges keep their relative order, so the graph is otherwise unchanged, but
+    /// every `NodeId` and `EdgeId` held elsewhere is invalidated: translate node IDs through
+    /// the returned map. The new storage is sized to the remaining elements, so no
+    /// [`shrink_to_fit`](Self::shrink_to_fit) call is needed afterwards.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    ///
+    /// let mut g = Graph::<u32, f64>::new();
+    /// let ids: Vec<_> = (0..100).map(|i| g.add_node(i)).collect();
+    /// for &id in &ids[..90] {
+    ///     g.remove_node(id);
+    /// }
+    /// assert_eq!(g.vacant_slots().0, 90);
+    ///
+    /// let remap = g.compact();
+    /// assert_eq!(g.vacant_slots(), (0, 0));
+    /// assert_eq!(g.node_attr(remap[&ids[95]]), Some(&95));
+    /// ```
+    pub fn compact(&mut self) -> NodeMap<NodeId> {
+        (self.node_props, self.edge_props) = self.compacted_props();
+        let fresh =
+            PetGraph::<A, W, Ty>::with_capacity(self.inner.node_count(), self.inner.edge_count());
+        let mut old = std::mem::replace(&mut self.inner, fresh);
+        let edges: Vec<(EdgeIndex, NodeIndex, NodeIndex)> = old
+            .edge_references()
+            .map(|e| (e.id(), e.source(), e.target()))
+            .collect();
+        let nodes: Vec<NodeIndex> = old.node_indices().collect();
+        let mut weights = Vec::with_capacity(edges.len());
+        for (e, u, v) in edges {
+            if let Some(w) = old.remove_edge(e) {
+                weights.push((u, v, w));
+            }
+        }
+        let mut remap = NodeMap::default();
+        for n in nodes {
+            if let Some(attr) = old.remove_node(n) {
+                remap.insert(NodeId(n), NodeId(self.inner.add_node(attr)));
+            }
+        }
+        for (u, v, w) in weights {
+            self.inner
+                .add_edge(remap[&NodeId(u)].0, remap[&NodeId(v)].0, w);
+        }
+        remap
+    }
     /// Returns an iterator over the neighbors of a node.
     pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
         self.inner.neighbors(node.0).map(NodeId::new)
@@ -428,6 +555,9 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
         self.to_nodemap(|_, _| Default::default())
     }
     /// Finds and returns the first edge from `source` to `target`.
+    ///
+    /// With parallel edges, which one is returned is unspecified; use
+    /// [`edges_between`](Self::edges_between) to get all of them.
     pub fn find_edge(&self, source: NodeId, target: NodeId) -> Option<EdgeId> {
         // Delegate to petgraph's adjacency-based lookup, which walks only the
         // source node's incident edges (O(degree)) rather than scanning every
@@ -436,9 +566,11 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
         // only, which preserves the previous semantics.
         self.inner.find_edge(source.0, target.0).map(EdgeId::new)
     }
-    /// Clears all nodes and edges from the graph.
+    /// Clears all nodes and edges from the graph, along with their properties.
     pub fn clear(&mut self) {
         self.inner.clear();
+        self.node_props.clear();
+        self.edge_props.clear();
     }
     /// Returns an iterator over all node IDs.
     pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + '_ {
@@ -492,6 +624,99 @@ impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> BaseGraph<A, W, Ty> {
             )
         })
     }
+    /// Returns every edge from `source` to `target`, in either orientation on undirected
+    /// graphs, as `(edge_id, weight)` pairs in insertion order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::MultiGraph;
+    ///
+    /// let mut g = MultiGraph::<&str, f64>::new();
+    /// let a = g.add_node("a");
+    /// let b = g.add_node("b");
+    /// let first = g.add_edge(a, b, 1.0);
+    /// let second = g.add_edge(b, a, 2.5);
+    ///
+    /// let between: Vec<_> = g.edges_between(a, b).collect();
+    /// assert_eq!(between, vec![(first, &1.0), (second, &2.5)]);
+    /// assert_eq!(g.edge_multiplicity(a, b), 2);
+    /// ```
+    pub fn edges_between(
+        &self,
+        source: NodeId,
+        target: NodeId,
+    ) -> impl Iterator<Item = (EdgeId, &W)> + '_ {
+        let mut edges: Vec<(EdgeId, &W)> = self
+            .inner
+            .edges_connecting(source.0, target.0)
+            .map(|e| (EdgeId::new(e.id()), e.weight()))
+            .collect();
+        let directed = <Ty as GraphConstructor<A, W>>::is_directed();
+        if !directed && source != target {
+            // petgraph only yields the edges stored with `source` first here.
+            edges.extend(
+                self.inner
+                    .edges_connecting(target.0, source.0)
+                    .map(|e| (EdgeId::new(e.id()), e.weight())),
+            );
+        }
+        edges.sort_by_key(|&(id, _)| id.index());
+        edges.dedup_by_key(|&mut (id, _)| id.index());
+        edges.into_iter()
+    }
+    /// Returns the number of edges from `source` to `target`, counting parallel edges.
+    pub fn edge_multiplicity(&self, source: NodeId, target: NodeId) -> usize {
+        self.edges_between(source, target).count()
+    }
+    /// Returns true if some pair of nodes is joined by more than one edge.
+    ///
+    /// On directed graphs, `u -> v` and `v -> u` are distinct and do not count.
+    pub fn has_parallel_edges(&self) -> bool {
+        let directed = <Ty as GraphConstructor<A, W>>::is_directed();
+        let mut seen = std::collections::HashSet::with_capacity(self.edge_count());
+        self.edges().any(|(u, v, _)| {
+            let key = if directed || u <= v { (u, v) } else { (v, u) };
+            !seen.insert(key)
+        })
+    }
+    /// Merges every group of parallel edges into one edge and returns how many edges
+    /// were removed.
+    ///
+    /// The first edge of each group (by [`EdgeId`]) is kept, and `combine` folds the weight
+    /// of each removed edge into it, for example `|kept, other| *kept += other` to add up
+    /// capacities. Node ids stay valid, and so do the ids of the kept edges.
+    pub fn merge_parallel_edges<F>(&mut self, mut combine: F) -> usize
+    where
+        F: FnMut(&mut W, W),
+    {
+        let directed = <Ty as GraphConstructor<A, W>>::is_directed();
+        let mut kept: HashMap<(NodeId, NodeId), EdgeId> = HashMap::new();
+        let mut merges: Vec<(EdgeId, EdgeId)> = Vec::new();
+        let mut ids: Vec<(EdgeId, NodeId, NodeId)> = self
+            .edges_with_ids()
+            .map(|(id, u, v, _)| (id, u, v))
+            .collect();
+        ids.sort_by_key(|&(id, _, _)| id.index());
+        for (id, u, v) in ids {
+            let key = if directed || u <= v { (u, v) } else { (v, u) };
+            match kept.get(&key) {
+                Some(&first) => merges.push((first, id)),
+                None => {
+                    kept.insert(key, id);
+                }
+            }
+        }
+        let removed = merges.len();
+        for (first, extra) in merges {
+            if let Some(weight) = self.remove_edge(extra) {
+                if let Some(target) = self.inner.edge_weight_mut(first.0) {
+                    combine(target, weight);
+                }
+            }
+        }
+        removed
+    }
     /// Adds an edge if it doesn't already exist. Returns (edge_id, inserted?).
     pub fn add_edge_if_absent(
         &mut self,
@@ -617,8 +842,11 @@ where
         A: Clone,
         Ty: GraphConstructor<A, U>,
     {
+        let (node_props, edge_props) = self.compacted_props();
         let mut new_graph = BaseGraph::<A, U, Ty> {
             inner: <Ty as GraphConstructor<A, U>>::new_graph(),
+            node_props,
+            edge_props,
         };
         let mut mapping: HashMap<NodeId, NodeId> = HashMap::new();
         for (node, attr) in self.nodes() {
@@ -634,6 +862,46 @@ where
         new_graph
     }
 }
+/// Construction from edges between named nodes.
+impl<S, W, Ty> BaseGraph<S, W, Ty>
+where
+    S: Eq + Hash + Clone,
+    Ty: GraphConstructor<S, W> + EdgeType,
+{
+    /// Builds a graph from `(source, target, weight)` triples that name their endpoints.
+    ///
+    /// Each distinct name becomes one node, in order of first appearance, with the name as its
+    /// attribute. Every triple becomes an edge, so repeated pairs give parallel edges. The
+    /// returned [`NameIndex`] maps names to node IDs and back.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    ///
+    /// let (g, index) = Graph::from_named_edges([("alice", "bob", 1.0), ("bob", "carol", 2.0)]);
+    /// assert_eq!(g.node_count(), 3);
+    /// let bob = index.id("bob").unwrap();
+    /// assert_eq!(index.name(bob), Some(&"bob"));
+    /// assert_eq!(g.degree(bob), Some(2));
+    /// ```
+    pub fn from_named_edges<I>(edges: I) -> (Self, NameIndex<S>)
+    where
+        I: IntoIterator<Item = (S, S, W)>,
+    {
+        let mut graph = Self::new();
+        let mut index = NameIndex {
+            ids: HashMap::new(),
+            names: Vec::new(),
+        };
+        for (source, target, weight) in edges {
+            let u = index.intern(&mut graph, source);
+            let v = index.intern(&mut graph, target);
+            graph.add_edge(u, v, weight);
+        }
+        (graph, index)
+    }
+}
 /// Mapping utilities for transforming node attributes and edge weights.
 impl<A, W, Ty> BaseGraph<A, W, Ty>
 where
@@ -646,8 +914,11 @@ where
         Ty: GraphConstructor<B, W>,
     {
         // Use mapping HashMap to handle non-contiguous NodeIndex values.
+        let (node_props, edge_props) = self.compacted_props();
         let mut new_graph = BaseGraph::<B, W, Ty> {
             inner: <Ty as GraphConstructor<B, W>>::new_graph(),
+            node_props,
+            edge_props,
         };
         let mut id_map: HashMap<NodeId, NodeId> = HashMap::new();
         for (nid, a) in self.nodes() {
@@ -668,8 +939,11 @@ where
         A: Clone,
         Ty: GraphConstructor<A, U>,
     {
+        let (node_props, edge_props) = self.compacted_props();
         let mut new_graph = BaseGraph::<A, U, Ty> {
             inner: <Ty as GraphConstructor<A, U>>::new_graph(),
+            node_props,
+            edge_props,
         };
         let mut id_map: HashMap<NodeId, NodeId> = HashMap::new();
         for (nid, a) in self.nodes() {
@@ -683,6 +957,36 @@ where
         }
         new_graph
     }
+    /// Maps edge weights with a fallible function, stopping at the first error.
+    ///
+    /// This is how to move checked weights into the type, for example wrapping `f64`
+    /// weights in `core::markers::NonNegativeF64`.
+    pub fn try_map_edge_weights<U, E>(
+        &self,
+        mut f: impl FnMut(EdgeId, &W) -> std::result::Result<U, E>,
+    ) -> std::result::Result<BaseGraph<A, U, Ty>, E>
+    where
+        A: Clone,
+        Ty: GraphConstructor<A, U>,
+    {
+        let (node_props, edge_props) = self.compacted_props();
+        let mut new_graph = BaseGraph::<A, U, Ty> {
+            inner: <Ty as GraphConstructor<A, U>>::new_graph(),
+            node_props,
+            edge_props,
+        };
+        let mut id_map: HashMap<NodeId, NodeId> = HashMap::new();
+        for (nid, a) in self.nodes() {
+            let new_id = new_graph.add_node(a.clone());
+            id_map.insert(nid, new_id);
+        }
+        for (eid, u, v, w) in self.edges_with_ids() {
+            if let (Some(&nu), Some(&nv)) = (id_map.get(&u), id_map.get(&v)) {
+                new_graph.add_edge(nu, nv, f(eid, w)?);
+            }
+        }
+        Ok(new_graph)
+    }
 }
 /// Indexing support for node attributes using NodeId.
 impl<A, W, Ty> Index<NodeId> for BaseGraph<A, W, Ty>
@@ -858,6 +1162,58 @@ impl<T> IntoIterator for OrderedNodeMap<T> {
         self.0.into_iter()
     }
 }
+/// A two-way map between node names and node IDs, returned by
+/// [`BaseGraph::from_named_edges`].
+///
+/// The index is a snapshot of the graph as built. Nodes added or removed afterwards are not
+/// reflected in it.
+#[derive(Debug, Clone)]
+pub struct NameIndex<S> {
+    ids: HashMap<S, NodeId>,
+    names: Vec<S>,
+}
+impl<S: Eq + Hash + Clone> NameIndex<S> {
+    fn intern<W, Ty>(&mut self, graph: &mut BaseGraph<S, W, Ty>, name: S) -> NodeId
+    where
+        Ty: GraphConstructor<S, W> + EdgeType,
+    {
+        if let Some(&node) = self.ids.get(&name) {
+            return node;
+        }
+        let node = graph.add_node(name.clone());
+        self.ids.insert(name.clone(), node);
+        self.names.push(name);
+        node
+    }
+    /// Returns the ID of the node with the given name.
+    pub fn id<Q>(&self, name: &Q) -> Option<NodeId>
+    where
+        S: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(name).copied()
+    }
+    /// Returns the name of the given node.
+    pub fn name(&self, node: NodeId) -> Option<&S> {
+        // Nodes are interned into a fresh graph, so the i-th name has index i.
+        self.names.get(node.index())
+    }
+    /// Returns the number of names.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+    /// Returns true if no names were interned.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+    /// Returns an iterator over `(node, name)` pairs in ID order.
+    pub fn iter(&self) -> impl Iterator<Item = (NodeId, &S)> {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (NodeId::new(NodeIndex::new(i)), name))
+    }
+}
 #[cfg(test)]
 mod tests {
 
@@ -1039,4 +1395,94 @@ mod tests {
         assert_eq!(mapped.edge_count(), 0);
         assert_eq!(mapped.node_count(), 2);
     }
+
+    #[test]
+    fn test_parallel_edges_are_distinguishable_and_mergeable() {
+        let mut g = MultiGraph::<(), f64>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        let c = g.add_node(());
+        let e1 = g.add_edge(a, b, 1.0);
+        let e2 = g.add_edge(b, a, 2.0);
+        let e3 = g.add_edge(a, b, 4.0);
+        g.add_edge(b, c, 1.0);
+        g.add_edge(c, c, 1.0);
+        g.add_edge(c, c, 1.0);
+
+        let ids: Vec<EdgeId> = g.edges_between(b, a).map(|(id, _)| id).collect();
+        assert_eq!(ids, vec![e1, e2, e3]);
+        assert_eq!(g.edge_multiplicity(c, c), 2);
+        assert_eq!(g.edge_multiplicity(a, c), 0);
+        assert!(g.has_parallel_edges());
+
+        assert_eq!(g.merge_parallel_edges(|kept, w| *kept += w), 3);
+        assert!(!g.has_parallel_edges());
+        assert_eq!(g.edge_weight(e1), Some(&7.0));
+        assert_eq!(g.edge_count(), 3);
+
+        let mut d = MultiDigraph::<(), f64>::new();
+        let x = d.add_node(());
+        let y = d.add_node(());
+        d.add_edge(x, y, 1.0);
+        d.add_edge(y, x, 1.0);
+        assert!(!d.has_parallel_edges());
+        assert_eq!(d.edge_multiplicity(x, y), 1);
+        d.add_edge(x, y, 3.0);
+        assert_eq!(d.merge_parallel_edges(|kept, w| *kept = kept.min(w)), 1);
+        assert_eq!(
+            d.edges_between(x, y).map(|(_, w)| *w).collect::<Vec<_>>(),
+            vec![1.0]
+        );
+    }
+
+    #[test]
+    fn test_capacity_reserve_and_compact() {
+        use crate::core::types::Digraph;
+        let mut g = Digraph::<i32, f64>::new();
+        g.reserve_nodes(64);
+        g.reserve_edges(32);
+        let (nodes_cap, edges_cap) = g.capacity();
+        assert!(nodes_cap >= 64 && edges_cap >= 32);
+
+        let ids: Vec<_> = (0..10).map(|i| g.add_node(i)).collect();
+        for w in ids.windows(2) {
+            g.add_edge(w[0], w[1], w[0].index() as f64);
+        }
+        for &id in &ids[2..8] {
+            g.remove_node(id);
+        }
+        // Removing six path nodes also removes the seven edges touching them.
+        assert_eq!(g.vacant_slots(), (6, 7));
+
+        let remap = g.compact();
+        assert_eq!(g.vacant_slots(), (0, 0));
+        assert_eq!(g.node_count(), 4);
+        assert_eq!(remap.len(), 4);
+        let (a, b) = (remap[&ids[0]], remap[&ids[1]]);
+        assert_eq!(g.node_attr(a), Some(&0));
+        assert!(g.contains_edge(a, b));
+        assert!(g.contains_edge(remap[&ids[8]], remap[&ids[9]]));
+        g.shrink_to_fit();
+        assert!(g.capacity().0 >= g.node_count());
+    }
+
+    #[test]
+    fn test_from_named_edges_interns_names() {
+        use crate::core::types::Digraph;
+        let rows = vec![
+            ("a".to_string(), "b".to_string(), 1.0),
+            ("b".to_string(), "a".to_string(), 2.0),
+            ("c".to_string(), "c".to_string(), 3.0),
+        ];
+        let (g, index) = Digraph::from_named_edges(rows);
+        assert_eq!(g.node_count(), 3);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(index.len(), 3);
+        let (a, b) = (index.id("a").unwrap(), index.id("b").unwrap());
+        assert_eq!(g.node_attr(a).map(String::as_str), Some("a"));
+        assert!(g.contains_edge(a, b) && g.contains_edge(b, a));
+        assert_eq!(index.id("missing"), None);
+        let names: Vec<&str> = index.iter().map(|(_, name)| name.as_str()).collect();
+        assert_eq!(names, ["a", "b", "c"]);
+    }
 }
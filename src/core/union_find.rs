@@ -0,0 +1,241 @@
+/*!
+# Union–Find
+
+Disjoint-set structures over the index space `0..n`, shared by connected components,
+Kruskal's and Borůvka's minimum spanning trees, and the spanning-tree heuristics.
+
+[`UnionFind`] is the sequential structure, with path halving and union by rank.
+[`ConcurrentUnionFind`] accepts unions from many threads at once through atomic parent
+pointers; it links the larger root under the smaller, so the representative of a set is
+always its smallest element.
+
+Graph algorithms index these structures by `NodeId::index()`, which can have gaps after
+node removals, so size them by the graph's index bound rather than its node count.
+*/
+
+use std::cmp::Ordering as RankOrdering;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// A disjoint-set structure with path halving and union by rank.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::union_find::UnionFind;
+///
+/// let mut uf = UnionFind::new(4);
+/// assert!(uf.union(0, 1));
+/// assert!(!uf.union(1, 0));
+/// assert!(uf.connected(0, 1));
+/// assert!(!uf.connected(0, 2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    /// Creates `n` singleton sets `{0}, {1}, ..., {n - 1}`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Whether the structure has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative of the set containing `i`.
+    ///
+    /// # Panics
+    /// Panics if `i >= len()`.
+    pub fn find(&mut self, mut i: usize) -> usize {
+        while self.parent[i] != i {
+            self.parent[i] = self.parent[self.parent[i]];
+            i = self.parent[i];
+        }
+        i
+    }
+
+    /// Merges the sets containing `a` and `b`, returning `false` if they were already joined.
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra == rb {
+            return false;
+        }
+        match self.rank[ra].cmp(&self.rank[rb]) {
+            RankOrdering::Less => self.parent[ra] = rb,
+            RankOrdering::Greater => self.parent[rb] = ra,
+            RankOrdering::Equal => {
+                self.parent[rb] = ra;
+                self.rank[ra] = self.rank[ra].saturating_add(1);
+            }
+        }
+        true
+    }
+
+    /// Whether `a` and `b` are in the same set.
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+/// A disjoint-set structure that can be shared across threads.
+///
+/// All operations take `&self`. Unions are lock-free: a root is linked with a single
+/// compare-and-swap, retried if another thread changed it first. Each set is represented
+/// by its smallest element, so the final partition and representatives do not depend on
+/// the order in which concurrent unions happen.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::union_find::ConcurrentUnionFind;
+///
+/// let uf = ConcurrentUnionFind::new(5);
+/// std::thread::scope(|s| {
+///     s.spawn(|| uf.union(3, 4));
+///     s.spawn(|| uf.union(4, 1));
+/// });
+/// assert_eq!(uf.find(3), 1);
+/// assert!(!uf.connected(0, 4));
+/// ```
+#[derive(Debug)]
+pub struct ConcurrentUnionFind {
+    parent: Vec<AtomicUsize>,
+}
+
+impl ConcurrentUnionFind {
+    /// Creates `n` singleton sets `{0}, {1}, ..., {n - 1}`.
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).map(AtomicUsize::new).collect(),
+        }
+    }
+
+    /// Number of elements.
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    /// Whether the structure has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative (smallest element) of the set containing `i`.
+    ///
+    /// While unions are still running on other threads, the answer reflects some point during
+    /// the call; once they finish it is exact.
+    ///
+    /// # Panics
+    /// Panics if `i >= len()`.
+    pub fn find(&self, mut i: usize) -> usize {
+        loop {
+            let p = self.parent[i].load(Ordering::Acquire);
+            if p == i {
+                return i;
+            }
+            let gp = self.parent[p].load(Ordering::Acquire);
+            if gp != p {
+                // Path halving; losing the race only means less compression.
+                let _ = self.parent[i].compare_exchange_weak(
+                    p,
+                    gp,
+                    Ordering::AcqRel,
+                    Ordering::Relaxed,
+                );
+            }
+            i = gp;
+        }
+    }
+
+    /// Merges the sets containing `a` and `b`, returning `false` if they were already joined.
+    pub fn union(&self, a: usize, b: usize) -> bool {
+        let (mut a, mut b) = (a, b);
+        loop {
+            let (ra, rb) = (self.find(a), self.find(b));
+            if ra == rb {
+                return false;
+            }
+            let (low, high) = if ra < rb { (ra, rb) } else { (rb, ra) };
+            if self.parent[high]
+                .compare_exchange(high, low, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+            {
+                return true;
+            }
+            // `high` was linked elsewhere in the meantime; retry from the new roots.
+            a = ra;
+            b = rb;
+        }
+    }
+
+    /// Whether `a` and `b` are in the same set, once concurrent unions have finished.
+    pub fn connected(&self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find_merges_and_tracks_sets() {
+        let mut uf = UnionFind::new(6);
+        assert_eq!(uf.len(), 6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+        assert!(uf.connected(0, 3));
+        assert!(!uf.connected(0, 4));
+        let roots: std::collections::HashSet<usize> = (0..6).map(|i| uf.find(i)).collect();
+        assert_eq!(roots.len(), 3);
+        assert!(UnionFind::new(0).is_empty());
+    }
+
+    #[test]
+    fn test_concurrent_union_find_matches_sequential() {
+        let n = 10_000;
+        let pairs: Vec<(usize, usize)> = (0..n)
+            .map(|i| (i, (i * 7_919 + 13) % n))
+            .filter(|&(i, _)| i % 5 != 0)
+            .collect();
+        let shared = ConcurrentUnionFind::new(n);
+        std::thread::scope(|s| {
+            for chunk in pairs.chunks(pairs.len() / 8 + 1) {
+                let shared = &shared;
+                s.spawn(move || {
+                    for &(a, b) in chunk {
+                        shared.union(a, b);
+                    }
+                });
+            }
+        });
+        let mut seq = UnionFind::new(n);
+        for &(a, b) in &pairs {
+            seq.union(a, b);
+        }
+        for i in 0..n {
+            let root = shared.find(i);
+            assert!(root <= i);
+            assert_eq!(shared.find(root), root);
+            assert!(seq.connected(i, root));
+        }
+        let seq_sets: std::collections::HashSet<usize> = (0..n).map(|i| seq.find(i)).collect();
+        let shared_sets: std::collections::HashSet<usize> =
+            (0..n).map(|i| shared.find(i)).collect();
+        assert_eq!(seq_sets.len(), shared_sets.len());
+    }
+}
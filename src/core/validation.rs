@@ -63,6 +63,29 @@ where
     graph.edges().any(|(_, _, w)| (*w).into() < 0.0)
 }
 
+/// Returns true if any edge weight is NaN.
+///
+/// NaN compares false against everything, so shortest-path and sorting-based algorithms
+/// silently produce wrong orderings on it instead of failing.
+pub fn has_nan_weights<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> bool
+where
+    W: Copy + Into<f64>,
+{
+    graph.edges().any(|(_, _, w)| (*w).into().is_nan())
+}
+
+/// Returns true if any edge weight is positive or negative infinity.
+pub fn has_infinite_weights<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> bool
+where
+    W: Copy + Into<f64>,
+{
+    graph.edges().any(|(_, _, w)| (*w).into().is_infinite())
+}
+
 /// Returns true if the graph contains any self-loops (edges from a node to itself).
 pub fn has_self_loops<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
     graph: &BaseGraph<A, W, Ty>,
@@ -259,6 +282,26 @@ where
     }
 }
 
+/// Validates that every edge weight is finite.
+///
+/// Returns `Ok(())` if no weight is NaN or infinite, otherwise returns an error.
+pub fn require_finite_weights<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+    algo_name: &str,
+) -> Result<()>
+where
+    W: Copy + Into<f64>,
+{
+    if has_nan_weights(graph) || has_infinite_weights(graph) {
+        Err(GraphinaError::invalid_argument(format!(
+            "{} requires finite edge weights",
+            algo_name
+        )))
+    } else {
+        Ok(())
+    }
+}
+
 /// Validates that the graph has no self-loops.
 ///
 /// Returns `Ok(())` if there are no self-loops, otherwise returns an error.
@@ -419,6 +462,95 @@ pub fn validate_is_dag<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
     }
 }
 
+/// What [`sanitize_weights`] does with an edge whose weight is not finite.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum WeightRepair {
+    /// Remove the edge.
+    DropEdge,
+    /// Clamp every weight, finite or not, into `[min, max]`. Infinities go to the nearer bound;
+    /// NaN has no position in the range, so those edges are removed.
+    Clamp { min: f64, max: f64 },
+    /// Set the weight to the given value.
+    Replace(f64),
+}
+
+/// Repairs NaN and infinite edge weights in place and returns the number of edges changed or
+/// removed.
+///
+/// Run this right after loading data from an external source, before handing the graph to
+/// algorithms that assume ordered, finite weights. Under `Clamp`, finite weights outside the
+/// range also count as changed.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if the clamp bounds are not finite or `min > max`, or if the
+/// replacement value is itself not finite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::core::validation::{has_nan_weights, sanitize_weights, WeightRepair};
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, f64::NAN);
+/// g.add_edge(b, a, 2.0);
+///
+/// assert_eq!(sanitize_weights(&mut g, WeightRepair::DropEdge).unwrap(), 1);
+/// assert!(!has_nan_weights(&g));
+/// assert_eq!(g.edge_count(), 1);
+/// ```
+pub fn sanitize_weights<A, Ty: GraphConstructor<A, f64> + EdgeType>(
+    graph: &mut BaseGraph<A, f64, Ty>,
+    policy: WeightRepair,
+) -> Result<usize> {
+    match policy {
+        WeightRepair::Clamp { min, max } if !(min.is_finite() && max.is_finite() && min <= max) => {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Clamp bounds must be finite with min <= max, got [{}, {}]",
+                min, max
+            )));
+        }
+        WeightRepair::Replace(value) if !value.is_finite() => {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Replacement weight must be finite, got {}",
+                value
+            )));
+        }
+        _ => {}
+    }
+
+    let mut changed = 0;
+    let mut doomed = Vec::new();
+    for edge in graph.edge_ids().collect::<Vec<_>>() {
+        let Some(w) = graph.edge_weight_mut(edge) else {
+            continue;
+        };
+        let repaired = match policy {
+            WeightRepair::DropEdge if !w.is_finite() => None,
+            WeightRepair::DropEdge => Some(*w),
+            WeightRepair::Clamp { .. } if w.is_nan() => None,
+            WeightRepair::Clamp { min, max } => Some(w.clamp(min, max)),
+            WeightRepair::Replace(value) if !w.is_finite() => Some(value),
+            WeightRepair::Replace(_) => Some(*w),
+        };
+        match repaired {
+            None => doomed.push(edge),
+            Some(new) if new != *w => {
+                *w = new;
+                changed += 1;
+            }
+            Some(_) => {}
+        }
+    }
+    for edge in &doomed {
+        graph.remove_edge(*edge);
+    }
+    Ok(changed + doomed.len())
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -483,6 +615,51 @@ mod tests {
         assert!(has_negative_weights(&g));
     }
 
+    #[test]
+    fn test_non_finite_weights_are_detected_and_repaired() {
+        let build = || {
+            let mut g = Graph::<i32, f64>::new();
+            let n1 = g.add_node(1);
+            let n2 = g.add_node(2);
+            let n3 = g.add_node(3);
+            g.add_edge(n1, n2, f64::NAN);
+            g.add_edge(n2, n3, f64::INFINITY);
+            g.add_edge(n3, n1, 7.0);
+            g
+        };
+        let g = build();
+        assert!(has_nan_weights(&g));
+        assert!(has_infinite_weights(&g));
+        assert!(require_finite_weights(&g, "Test").is_err());
+
+        let mut dropped = build();
+        assert_eq!(
+            sanitize_weights(&mut dropped, WeightRepair::DropEdge).unwrap(),
+            2
+        );
+        assert_eq!(dropped.edge_count(), 1);
+        assert!(require_finite_weights(&dropped, "Test").is_ok());
+
+        let mut clamped = build();
+        let policy = WeightRepair::Clamp { min: 0.0, max: 5.0 };
+        assert_eq!(sanitize_weights(&mut clamped, policy).unwrap(), 3);
+        let mut weights: Vec<f64> = clamped.edges().map(|(_, _, &w)| w).collect();
+        weights.sort_by(f64::total_cmp);
+        assert_eq!(weights, vec![5.0, 5.0]);
+
+        let mut replaced = build();
+        assert_eq!(
+            sanitize_weights(&mut replaced, WeightRepair::Replace(1.0)).unwrap(),
+            2
+        );
+        assert_eq!(replaced.edge_count(), 3);
+        assert!(!has_nan_weights(&replaced) && !has_infinite_weights(&replaced));
+
+        let bad = WeightRepair::Clamp { min: 2.0, max: 1.0 };
+        assert!(sanitize_weights(&mut replaced, bad).is_err());
+        assert!(sanitize_weights(&mut replaced, WeightRepair::Replace(f64::NAN)).is_err());
+    }
+
     #[test]
     fn test_is_dag() {
         let mut g = Digraph::<i32, f64>::new();
@@ -0,0 +1,482 @@
+/*!
+# Graphs From Vectors
+
+Nearest-neighbor graphs built from points such as node embeddings, so that the output of an
+embedding or a feature pipeline can be analyzed with the graph algorithms of this crate.
+
+[`knn_graph`] compares every pair of points. With the `hnsw` feature,
+[`approximate_knn_graph`] finds the neighbors through a hierarchical navigable small world
+(HNSW) index instead, which scales to far larger point sets at the cost of occasionally
+missing a true neighbor.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{Graph, NodeId};
+
+/// Distance between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DistanceMetric {
+    /// Straight-line distance, `sqrt(sum (x_i - y_i)^2)`.
+    Euclidean,
+    /// Taxicab distance, `sum |x_i - y_i|`.
+    Manhattan,
+    /// `1 - cos(x, y)`, in `[0, 2]`. Zero vectors are rejected.
+    Cosine,
+}
+
+impl DistanceMetric {
+    /// Distance between `x` and `y`, which must have the same length.
+    pub fn distance(self, x: &[f64], y: &[f64]) -> f64 {
+        match self {
+            DistanceMetric::Euclidean => x
+                .iter()
+                .zip(y)
+                .map(|(a, b)| (a - b) * (a - b))
+                .sum::<f64>()
+                .sqrt(),
+            DistanceMetric::Manhattan => x.iter().zip(y).map(|(a, b)| (a - b).abs()).sum(),
+            DistanceMetric::Cosine => {
+                let (mut dot, mut xx, mut yy) = (0.0, 0.0, 0.0);
+                for (a, b) in x.iter().zip(y) {
+                    dot += a * b;
+                    xx += a * a;
+                    yy += b * b;
+                }
+                (1.0 - dot / (xx.sqrt() * yy.sqrt())).max(0.0)
+            }
+        }
+    }
+}
+
+/// Checks that `points` share one dimension, have finite coordinates, and, for the cosine
+/// distance, are nonzero.
+fn validate_points(points: &[Vec<f64>], k: usize, metric: DistanceMetric) -> Result<()> {
+    if k == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "KnnGraph: k must be at least 1",
+        ));
+    }
+    let Some(first) = points.first() else {
+        return Ok(());
+    };
+    for (i, p) in points.iter().enumerate() {
+        if p.len() != first.len() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "KnnGraph: point {} has dimension {}, expected {}",
+                i,
+                p.len(),
+                first.len()
+            )));
+        }
+        if p.iter().any(|c| !c.is_finite()) {
+            return Err(GraphinaError::invalid_argument(format!(
+                "KnnGraph: point {} has a non-finite coordinate",
+                i
+            )));
+        }
+        if metric == DistanceMetric::Cosine && p.iter().all(|&c| c == 0.0) {
+            return Err(GraphinaError::invalid_argument(format!(
+                "KnnGraph: point {} is the zero vector, which has no cosine distance",
+                i
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Builds the undirected graph with one node per point and an edge between two points when
+/// either is among the other's `neighbors`. Node `i` has attribute `i`, and edges are
+/// weighted by distance.
+fn union_graph(
+    points: &[Vec<f64>],
+    metric: DistanceMetric,
+    neighbors: &[Vec<usize>],
+) -> Graph<usize, f64> {
+    let mut graph = Graph::with_capacity(points.len(), neighbors.iter().map(Vec::len).sum());
+    let ids: Vec<NodeId> = (0..points.len()).map(|i| graph.add_node(i)).collect();
+    let mut seen = rustc_hash::FxHashSet::default();
+    for (i, list) in neighbors.iter().enumerate() {
+        for &j in list {
+            let key = (i.min(j), i.max(j));
+            if seen.insert(key) {
+                let d = metric.distance(&points[i], &points[j]);
+                graph.add_edge(ids[key.0], ids[key.1], d);
+            }
+        }
+    }
+    graph
+}
+
+/// Builds the k-nearest-neighbor graph of `points` by comparing every pair.
+///
+/// The result is undirected: nodes `i` and `j`, whose attributes are the point indices, are
+/// joined when `j` is among the `k` points closest to `i` or the other way around, and the
+/// edge is weighted by their distance. Ties are broken by the lower point index, so the
+/// graph is deterministic. When `k` is at least the number of other points, the graph is
+/// complete.
+///
+/// **Time Complexity:** O(n² d) for n points of dimension d
+///
+/// # Errors
+/// Returns `InvalidArgument` if `k` is zero, the points differ in dimension, a coordinate
+/// is not finite, or, for [`DistanceMetric::Cosine`], a point is the zero vector.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::vectors::{knn_graph, DistanceMetric};
+///
+/// let points = vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![10.0, 0.0], vec![10.0, 1.5]];
+/// let graph = knn_graph(&points, 1, DistanceMetric::Euclidean).unwrap();
+///
+/// // Two pairs of close points, with no edge between the pairs.
+/// assert_eq!(graph.edge_count(), 2);
+/// ```
+pub fn knn_graph(
+    points: &[Vec<f64>],
+    k: usize,
+    metric: DistanceMetric,
+) -> Result<Graph<usize, f64>> {
+    validate_points(points, k, metric)?;
+    let n = points.len();
+    let mut neighbors = Vec::with_capacity(n);
+    let mut row: Vec<(f64, usize)> = Vec::with_capacity(n);
+    for i in 0..n {
+        row.clear();
+        row.extend(
+            (0..n)
+                .filter(|&j| j != i)
+                .map(|j| (metric.distance(&points[i], &points[j]), j)),
+        );
+        let take = k.min(row.len());
+        let by_distance =
+            |a: &(f64, usize), b: &(f64, usize)| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1));
+        if take < row.len() {
+            row.select_nth_unstable_by(take, by_distance);
+            row.truncate(take);
+        }
+        neighbors.push(row.iter().map(|&(_, j)| j).collect());
+    }
+    Ok(union_graph(points, metric, &neighbors))
+}
+
+/// Builds an approximate k-nearest-neighbor graph of `points` with an HNSW index.
+///
+/// The result has the same shape as [`knn_graph`], but each point's neighbors come from a
+/// search of the index, so a few true neighbors may be replaced by slightly farther points.
+/// `ef` is the size of the candidate list kept during construction and search; larger
+/// values raise recall and cost time, and values below `k` are raised to `k`. Each point
+/// keeps up to 16 links per layer. The index is built in point order with layers drawn from
+/// `seed`, so a fixed seed gives the same graph on every run.
+///
+/// Requires the `hnsw` feature.
+///
+/// **Time Complexity:** about O(n log n · ef · d)
+///
+/// # Errors
+/// Returns `InvalidArgument` under the same conditions as [`knn_graph`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::vectors::{approximate_knn_graph, knn_graph, DistanceMetric};
+///
+/// let points: Vec<Vec<f64>> = (0..200).map(|i| vec![(i as f64).sin(), (i as f64).cos()]).collect();
+/// let exact = knn_graph(&points, 5, DistanceMetric::Euclidean).unwrap();
+/// let approx = approximate_knn_graph(&points, 5, DistanceMetric::Euclidean, 64, Some(7)).unwrap();
+/// assert_eq!(approx.node_count(), exact.node_count());
+/// ```
+#[cfg(feature = "hnsw")]
+pub fn approximate_knn_graph(
+    points: &[Vec<f64>],
+    k: usize,
+    metric: DistanceMetric,
+    ef: usize,
+    seed: Option<u64>,
+) -> Result<Graph<usize, f64>> {
+    validate_points(points, k, metric)?;
+    let index = hnsw::Hnsw::build(points, metric, ef.max(k), seed);
+    let mut visited = hnsw::Visited::new(points.len());
+    let neighbors: Vec<Vec<usize>> = (0..points.len())
+        .map(|i| index.nearest(i, k, ef.max(k), &mut visited))
+        .collect();
+    Ok(union_graph(points, metric, &neighbors))
+}
+
+#[cfg(feature = "hnsw")]
+mod hnsw {
+    //! A compact HNSW index (Malkov and Yashunin, 2018) over a borrowed point set.
+
+    use super::DistanceMetric;
+    use ordered_float::OrderedFloat;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+    use std::cmp::Reverse;
+    use std::collections::BinaryHeap;
+
+    /// Links per node on the upper layers; layer 0 keeps twice as many.
+    const MAX_LINKS: usize = 16;
+
+    type Scored = (OrderedFloat<f64>, usize);
+
+    /// Visited marks reused across searches; a node is visited when its mark equals the
+    /// current epoch, so starting a search costs one increment instead of a clear.
+    pub(super) struct Visited {
+        marks: Vec<u32>,
+        epoch: u32,
+    }
+
+    impl Visited {
+        pub(super) fn new(n: usize) -> Self {
+            Visited {
+                marks: vec![0; n],
+                epoch: 0,
+            }
+        }
+
+        fn start(&mut self) {
+            self.epoch = self.epoch.wrapping_add(1);
+            if self.epoch == 0 {
+                self.marks.fill(0);
+                self.epoch = 1;
+            }
+        }
+
+        /// Marks `node`, returning whether it was unmarked.
+        fn insert(&mut self, node: usize) -> bool {
+            let fresh = self.marks[node] != self.epoch;
+            self.marks[node] = self.epoch;
+            fresh
+        }
+    }
+
+    pub(super) struct Hnsw<'a> {
+        points: &'a [Vec<f64>],
+        metric: DistanceMetric,
+        /// `links[node][layer]` lists the neighbors of `node` on `layer`.
+        links: Vec<Vec<Vec<usize>>>,
+        entry: usize,
+    }
+
+    impl<'a> Hnsw<'a> {
+        pub(super) fn build(
+            points: &'a [Vec<f64>],
+            metric: DistanceMetric,
+            ef_construction: usize,
+            seed: Option<u64>,
+        ) -> Self {
+            let mut rng = match seed {
+                Some(s) => StdRng::seed_from_u64(s),
+                None => StdRng::seed_from_u64(rand::random::<u64>()),
+            };
+            let level_scale = 1.0 / (MAX_LINKS as f64).ln();
+            let mut index = Hnsw {
+                points,
+                metric,
+                links: Vec::with_capacity(points.len()),
+                entry: 0,
+            };
+            let mut visited = Visited::new(points.len());
+            for node in 0..points.len() {
+                let draw: f64 = rng.random::<f64>();
+                let level = (-(1.0 - draw).ln() * level_scale).floor() as usize;
+                index.insert(node, level, ef_construction, &mut visited);
+            }
+            index
+        }
+
+        fn distance(&self, a: usize, b: usize) -> f64 {
+            self.metric.distance(&self.points[a], &self.points[b])
+        }
+
+        fn top_level(&self) -> usize {
+            self.links.get(self.entry).map_or(0, |l| l.len() - 1)
+        }
+
+        fn insert(&mut self, node: usize, level: usize, ef: usize, visited: &mut Visited) {
+            self.links.push(vec![Vec::new(); level + 1]);
+            if node == 0 {
+                return;
+            }
+            let top = self.top_level();
+            let mut entry = self.entry;
+            for layer in (level + 1..=top).rev() {
+                entry = self.greedy(node, entry, layer);
+            }
+            let mut entries = vec![entry];
+            for layer in (0..=level.min(top)).rev() {
+                let found = self.search_layer(node, &entries, ef, layer, visited);
+                let cap = if layer == 0 { 2 * MAX_LINKS } else { MAX_LINKS };
+                let chosen: Vec<usize> = found.iter().take(MAX_LINKS).map(|&(_, n)| n).collect();
+                for &other in &chosen {
+                    self.links[other][layer].push(node);
+                    if self.links[other][layer].len() > cap {
+                        self.prune(other, layer, cap);
+                    }
+                }
+                self.links[node][layer] = chosen;
+                entries = found.into_iter().map(|(_, n)| n).collect();
+            }
+            if level > top {
+                self.entry = node;
+            }
+        }
+
+        /// Keeps the `cap` closest links of `node` on `layer`.
+        fn prune(&mut self, node: usize, layer: usize, cap: usize) {
+            let mut scored: Vec<Scored> = self.links[node][layer]
+                .iter()
+                .map(|&n| (OrderedFloat(self.distance(node, n)), n))
+                .collect();
+            scored.sort_unstable();
+            scored.truncate(cap);
+            self.links[node][layer] = scored.into_iter().map(|(_, n)| n).collect();
+        }
+
+        /// Walks `layer` from `entry` towards `query` while a neighbor is closer.
+        fn greedy(&self, query: usize, mut entry: usize, layer: usize) -> usize {
+            let mut best = self.distance(query, entry);
+            loop {
+                let mut moved = false;
+                for &n in &self.links[entry][layer] {
+                    let d = self.distance(query, n);
+                    if d < best {
+                        best = d;
+                        entry = n;
+                        moved = true;
+                    }
+                }
+                if !moved {
+                    return entry;
+                }
+            }
+        }
+
+        /// The `ef` nodes closest to `query` found on `layer`, nearest first.
+        fn search_layer(
+            &self,
+            query: usize,
+            entries: &[usize],
+            ef: usize,
+            layer: usize,
+            visited: &mut Visited,
+        ) -> Vec<Scored> {
+            visited.start();
+            let mut candidates: BinaryHeap<Reverse<Scored>> = BinaryHeap::new();
+            let mut best: BinaryHeap<Scored> = BinaryHeap::new();
+            for &e in entries {
+                if visited.insert(e) {
+                    let d = OrderedFloat(self.distance(query, e));
+                    candidates.push(Reverse((d, e)));
+                    best.push((d, e));
+                }
+            }
+            while best.len() > ef {
+                best.pop();
+            }
+            while let Some(Reverse((d, node))) = candidates.pop() {
+                if best.len() >= ef && best.peek().is_some_and(|&(worst, _)| d > worst) {
+                    break;
+                }
+                for &n in self.links[node].get(layer).into_iter().flatten() {
+                    if !visited.insert(n) {
+                        continue;
+                    }
+                    let dn = OrderedFloat(self.distance(query, n));
+                    if best.len() < ef || best.peek().is_some_and(|&(worst, _)| dn < worst) {
+                        candidates.push(Reverse((dn, n)));
+                        best.push((dn, n));
+                        if best.len() > ef {
+                            best.pop();
+                        }
+                    }
+                }
+            }
+            let mut found = best.into_vec();
+            found.sort_unstable();
+            found
+        }
+
+        /// Up to `k` approximate nearest neighbors of the indexed point `query`.
+        pub(super) fn nearest(
+            &self,
+            query: usize,
+            k: usize,
+            ef: usize,
+            visited: &mut Visited,
+        ) -> Vec<usize> {
+            let mut entry = self.entry;
+            for layer in (1..=self.top_level()).rev() {
+                entry = self.greedy(query, entry, layer);
+            }
+            self.search_layer(query, &[entry], ef + 1, 0, visited)
+                .into_iter()
+                .map(|(_, n)| n)
+                .filter(|&n| n != query)
+                .take(k)
+                .collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring(n: usize) -> Vec<Vec<f64>> {
+        (0..n)
+            .map(|i| {
+                let t = i as f64 * std::f64::consts::TAU / n as f64;
+                vec![t.cos(), t.sin()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_knn_graph_on_a_ring() {
+        // On a ring, each point's two nearest neighbors are the adjacent points.
+        let graph = knn_graph(&ring(12), 2, DistanceMetric::Euclidean).unwrap();
+        assert_eq!(graph.node_count(), 12);
+        assert_eq!(graph.edge_count(), 12);
+        assert!(graph.nodes().all(|(id, _)| graph.degree(id) == Some(2)));
+
+        let complete = knn_graph(&ring(5), 10, DistanceMetric::Manhattan).unwrap();
+        assert_eq!(complete.edge_count(), 10);
+        assert_eq!(
+            knn_graph(&[], 3, DistanceMetric::Cosine)
+                .unwrap()
+                .node_count(),
+            0
+        );
+
+        assert!(knn_graph(&ring(3), 0, DistanceMetric::Euclidean).is_err());
+        assert!(knn_graph(&[vec![1.0], vec![1.0, 2.0]], 1, DistanceMetric::Euclidean).is_err());
+        assert!(knn_graph(&[vec![0.0, 0.0], vec![1.0, 2.0]], 1, DistanceMetric::Cosine).is_err());
+    }
+
+    #[cfg(feature = "hnsw")]
+    #[test]
+    fn test_approximate_knn_graph_recall() {
+        let points: Vec<Vec<f64>> = (0..500)
+            .map(|i| {
+                let x = i as f64;
+                vec![
+                    (x * 0.37).sin() * 3.0,
+                    (x * 0.11).cos() * 2.0,
+                    (x * 0.07).sin(),
+                ]
+            })
+            .collect();
+        let exact = knn_graph(&points, 8, DistanceMetric::Euclidean).unwrap();
+        let approx =
+            approximate_knn_graph(&points, 8, DistanceMetric::Euclidean, 64, Some(1)).unwrap();
+        let hits = exact
+            .edges()
+            .filter(|&(u, v, _)| approx.contains_edge(u, v))
+            .count();
+        assert!(hits as f64 / exact.edge_count() as f64 > 0.95);
+        let again =
+            approximate_knn_graph(&points, 8, DistanceMetric::Euclidean, 64, Some(1)).unwrap();
+        assert_eq!(again.edge_count(), approx.edge_count());
+    }
+}
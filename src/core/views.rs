@@ -0,0 +1,398 @@
+/*!
+# Graph Views
+
+Read-only wrappers that present a borrowed graph differently without copying it.
+
+- [`Reversed`] flips every edge of a directed graph, so a search that follows outgoing edges
+  in the view follows incoming edges in the graph.
+- [`AsUndirected`] ignores edge directions, which is the structure behind weak connectivity.
+
+On an undirected graph both views look exactly like the graph itself. They implement
+[`GraphQuery`] and [`GraphTraversal`], as does [`BaseGraph`], so functions written against
+those traits, such as `traversal::bfs_on`, accept a graph or a view alike.
+
+```rust
+use graphina::core::types::Digraph;
+
+let mut g = Digraph::<(), ()>::new();
+let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+g.add_edge(a, b, ());
+g.add_edge(c, b, ());
+
+// The predecessors of b, without building a reversed copy.
+let mut parents: Vec<_> = g.reversed().neighbors(b).collect();
+parents.sort();
+assert_eq!(parents, vec![a, c]);
+// Ignoring directions, b is joined to both.
+assert_eq!(g.as_undirected().degree(b), Some(2));
+```
+*/
+
+use crate::core::traits::{GraphQuery, GraphTraversal};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::Direction;
+use petgraph::EdgeType;
+use petgraph::visit::EdgeRef;
+
+/// A directed graph with every edge reversed.
+///
+/// Created by [`BaseGraph::reversed`]. Node ids and attributes are shared with the graph.
+#[derive(Debug)]
+pub struct Reversed<'a, A, W, Ty: GraphConstructor<A, W>> {
+    graph: &'a BaseGraph<A, W, Ty>,
+}
+
+// Derived `Clone` and `Copy` would require them of `A`, `W`, and `Ty`.
+impl<A, W, Ty: GraphConstructor<A, W>> Clone for Reversed<'_, A, W, Ty> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, W, Ty: GraphConstructor<A, W>> Copy for Reversed<'_, A, W, Ty> {}
+
+impl<'a, A, W, Ty> Reversed<'a, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    /// Wraps `graph`.
+    pub fn new(graph: &'a BaseGraph<A, W, Ty>) -> Self {
+        Self { graph }
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &'a BaseGraph<A, W, Ty> {
+        self.graph
+    }
+
+    /// Returns an iterator over all node IDs.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + 'a {
+        self.graph.node_ids()
+    }
+
+    /// Returns an iterator over the nodes that have an edge to `node` in the underlying graph.
+    pub fn neighbors(&self, node: NodeId) -> Box<dyn Iterator<Item = NodeId> + 'a> {
+        self.graph.incoming_neighbors(node)
+    }
+
+    /// Returns the reversed edges leaving `node` as `(target, weight)`.
+    pub fn outgoing_edges(&self, node: NodeId) -> Box<dyn Iterator<Item = (NodeId, &'a W)> + 'a> {
+        let graph = self.graph;
+        if !graph.is_directed() {
+            return Box::new(graph.outgoing_edges(node));
+        }
+        Box::new(
+            graph
+                .inner
+                .edges_directed(node.0, Direction::Incoming)
+                .map(|e| (NodeId::new(e.source()), e.weight())),
+        )
+    }
+
+    /// Returns an iterator over all edges as `(source, target, weight)`, reversed.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &'a W)> + 'a {
+        let directed = self.graph.is_directed();
+        self.graph
+            .edges()
+            .map(move |(u, v, w)| if directed { (v, u, w) } else { (u, v, w) })
+    }
+
+    /// Returns true if the view has an edge from `source` to `target`.
+    pub fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        self.graph.contains_edge(target, source)
+    }
+
+    /// Returns the number of reversed edges leaving `node`, or `None` if it does not exist.
+    pub fn out_degree(&self, node: NodeId) -> Option<usize> {
+        self.graph.in_degree(node)
+    }
+
+    /// Returns the number of reversed edges entering `node`, or `None` if it does not exist.
+    pub fn in_degree(&self, node: NodeId) -> Option<usize> {
+        self.graph.out_degree(node)
+    }
+}
+
+/// A graph with edge directions ignored.
+///
+/// Created by [`BaseGraph::as_undirected`]. Each edge of the graph appears once, so a pair of
+/// opposite directed edges looks like two parallel undirected edges. Self-loops count once.
+#[derive(Debug)]
+pub struct AsUndirected<'a, A, W, Ty: GraphConstructor<A, W>> {
+    graph: &'a BaseGraph<A, W, Ty>,
+}
+
+impl<A, W, Ty: GraphConstructor<A, W>> Clone for AsUndirected<'_, A, W, Ty> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, W, Ty: GraphConstructor<A, W>> Copy for AsUndirected<'_, A, W, Ty> {}
+
+impl<'a, A, W, Ty> AsUndirected<'a, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    /// Wraps `graph`.
+    pub fn new(graph: &'a BaseGraph<A, W, Ty>) -> Self {
+        Self { graph }
+    }
+
+    /// Returns the underlying graph.
+    pub fn graph(&self) -> &'a BaseGraph<A, W, Ty> {
+        self.graph
+    }
+
+    /// Returns an iterator over all node IDs.
+    pub fn node_ids(&self) -> impl Iterator<Item = NodeId> + 'a {
+        self.graph.node_ids()
+    }
+
+    /// Returns an iterator over the nodes joined to `node` by an edge in either direction.
+    pub fn neighbors(&self, node: NodeId) -> impl Iterator<Item = NodeId> + 'a {
+        self.outgoing_edges(node).map(|(v, _)| v)
+    }
+
+    /// Returns the edges incident to `node` as `(other endpoint, weight)`.
+    pub fn outgoing_edges(&self, node: NodeId) -> Box<dyn Iterator<Item = (NodeId, &'a W)> + 'a> {
+        let graph = self.graph;
+        if !graph.is_directed() {
+            return Box::new(graph.outgoing_edges(node));
+        }
+        let incoming = graph
+            .inner
+            .edges_directed(node.0, Direction::Incoming)
+            // A self-loop is already listed among the outgoing edges.
+            .filter(move |e| e.source() != node.0)
+            .map(|e| (NodeId::new(e.source()), e.weight()));
+        Box::new(graph.outgoing_edges(node).chain(incoming))
+    }
+
+    /// Returns an iterator over all edges and their weights, each once.
+    pub fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId, &'a W)> + 'a {
+        self.graph.edges()
+    }
+
+    /// Returns true if there is an edge between `source` and `target` in either direction.
+    pub fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        self.graph.contains_edge(source, target) || self.graph.contains_edge(target, source)
+    }
+
+    /// Returns the number of edges incident to `node`, or `None` if it does not exist.
+    pub fn degree(&self, node: NodeId) -> Option<usize> {
+        self.graph
+            .contains_node(node)
+            .then(|| self.outgoing_edges(node).count())
+    }
+}
+
+impl<A, W, Ty> BaseGraph<A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    /// Returns a view of this graph with every edge reversed.
+    pub fn reversed(&self) -> Reversed<'_, A, W, Ty> {
+        Reversed::new(self)
+    }
+
+    /// Returns a view of this graph with edge directions ignored.
+    pub fn as_undirected(&self) -> AsUndirected<'_, A, W, Ty> {
+        AsUndirected::new(self)
+    }
+}
+
+impl<A, W, Ty> GraphQuery<A, W> for Reversed<'_, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    fn is_directed(&self) -> bool {
+        self.graph.is_directed()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn contains_node(&self, node: NodeId) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        Reversed::contains_edge(self, source, target)
+    }
+
+    fn node_attr(&self, node: NodeId) -> Option<&A> {
+        self.graph.node_attr(node)
+    }
+
+    fn edge_weight(&self, source: NodeId, target: NodeId) -> Option<&W> {
+        GraphQuery::edge_weight(self.graph, target, source)
+    }
+}
+
+impl<A, W, Ty> GraphTraversal<A, W> for Reversed<'_, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    type NodeIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+    type NeighborIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+
+    fn node_ids(&self) -> Self::NodeIter<'_> {
+        Box::new(self.graph.node_ids())
+    }
+
+    fn neighbors(&self, node: NodeId) -> Self::NeighborIter<'_> {
+        Reversed::neighbors(self, node)
+    }
+
+    fn degree(&self, node: NodeId) -> Option<usize> {
+        self.graph.degree(node)
+    }
+
+    fn in_degree(&self, node: NodeId) -> Option<usize> {
+        Reversed::in_degree(self, node)
+    }
+
+    fn out_degree(&self, node: NodeId) -> Option<usize> {
+        Reversed::out_degree(self, node)
+    }
+}
+
+impl<A, W, Ty> GraphQuery<A, W> for AsUndirected<'_, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    fn is_directed(&self) -> bool {
+        false
+    }
+
+    fn is_empty(&self) -> bool {
+        self.graph.is_empty()
+    }
+
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    fn edge_count(&self) -> usize {
+        self.graph.edge_count()
+    }
+
+    fn contains_node(&self, node: NodeId) -> bool {
+        self.graph.contains_node(node)
+    }
+
+    fn contains_edge(&self, source: NodeId, target: NodeId) -> bool {
+        AsUndirected::contains_edge(self, source, target)
+    }
+
+    fn node_attr(&self, node: NodeId) -> Option<&A> {
+        self.graph.node_attr(node)
+    }
+
+    fn edge_weight(&self, source: NodeId, target: NodeId) -> Option<&W> {
+        GraphQuery::edge_weight(self.graph, source, target)
+            .or_else(|| GraphQuery::edge_weight(self.graph, target, source))
+    }
+}
+
+impl<A, W, Ty> GraphTraversal<A, W> for AsUndirected<'_, A, W, Ty>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    type NodeIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+    type NeighborIter<'a>
+        = Box<dyn Iterator<Item = NodeId> + 'a>
+    where
+        Self: 'a,
+        A: 'a,
+        W: 'a;
+
+    fn node_ids(&self) -> Self::NodeIter<'_> {
+        Box::new(self.graph.node_ids())
+    }
+
+    fn neighbors(&self, node: NodeId) -> Self::NeighborIter<'_> {
+        Box::new(AsUndirected::neighbors(self, node))
+    }
+
+    fn degree(&self, node: NodeId) -> Option<usize> {
+        AsUndirected::degree(self, node)
+    }
+
+    fn in_degree(&self, node: NodeId) -> Option<usize> {
+        AsUndirected::degree(self, node)
+    }
+
+    fn out_degree(&self, node: NodeId) -> Option<usize> {
+        AsUndirected::degree(self, node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_views_match_materialized_graphs() {
+        // a -> b, b -> a, b -> c, c -> c
+        let mut g = Digraph::<(), u32>::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+        g.add_edge(a, b, 1);
+        g.add_edge(b, a, 2);
+        g.add_edge(b, c, 3);
+        g.add_edge(c, c, 4);
+
+        let rev = g.reversed();
+        let mut into_b: Vec<_> = rev.outgoing_edges(b).map(|(v, w)| (v, *w)).collect();
+        into_b.sort();
+        assert_eq!(into_b, vec![(a, 1)]);
+        assert!(rev.contains_edge(c, b) && !rev.contains_edge(b, c));
+        assert_eq!(GraphQuery::edge_weight(&rev, c, b), Some(&3));
+        assert_eq!((rev.out_degree(c), rev.in_degree(c)), (Some(2), Some(1)));
+        assert!(rev.edges().any(|e| e == (c, b, &3)));
+
+        let und = g.as_undirected();
+        let mut around_b: Vec<_> = und.neighbors(b).collect();
+        around_b.sort();
+        assert_eq!(around_b, vec![a, a, c]);
+        assert_eq!(und.neighbors(c).collect::<Vec<_>>(), vec![c, b]);
+        assert_eq!(und.degree(c), Some(2));
+        assert_eq!(GraphQuery::edge_weight(&und, c, b), Some(&3));
+        assert!(!GraphQuery::is_directed(&und));
+        assert_eq!(GraphQuery::edge_count(&und), 4);
+
+        // On an undirected graph both views are the graph itself.
+        let mut u = Graph::<(), u32>::new();
+        let [x, y] = [(); 2].map(|_| u.add_node(()));
+        u.add_edge(x, y, 5);
+        assert_eq!(u.reversed().neighbors(x).collect::<Vec<_>>(), vec![y]);
+        assert_eq!(u.as_undirected().degree(y), u.degree(y));
+        assert_eq!(u.reversed().edges().next(), u.edges().next());
+    }
+}
@@ -0,0 +1,251 @@
+/*!
+# Edge Weight Statistics
+
+Summaries of the edge weight distribution and quantile-based trimming. Similarity graphs built
+from noisy data tend to carry a long tail of near-zero weights and a few extreme ones; looking
+at the distribution and cutting both tails is a common step before community detection.
+
+Weights that are NaN or infinite have no place in a distribution, so these functions reject
+them; repair them first with `core::validation::sanitize_weights`.
+*/
+
+use std::ops::RangeInclusive;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor};
+use petgraph::EdgeType;
+
+/// Summary of a graph's edge weights, from [`weight_statistics`].
+///
+/// Keeps the sorted weights, so quantiles and histograms of any resolution can be read off
+/// without another pass over the graph.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeightStatistics {
+    /// Number of edges.
+    pub count: usize,
+    /// Smallest weight.
+    pub min: f64,
+    /// Largest weight.
+    pub max: f64,
+    /// Arithmetic mean.
+    pub mean: f64,
+    /// Population standard deviation.
+    pub std_dev: f64,
+    sorted: Vec<f64>,
+}
+
+/// Equal-width bin counts over `[min, max]`, from [`WeightStatistics::histogram`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    /// Bin boundaries, one more than there are bins. Bin `i` covers `edges[i]..edges[i + 1]`,
+    /// and the last bin also includes its upper boundary.
+    pub edges: Vec<f64>,
+    /// Number of weights in each bin.
+    pub counts: Vec<usize>,
+}
+
+impl WeightStatistics {
+    /// Returns the `q`-quantile, interpolating linearly between the two nearest weights.
+    ///
+    /// `q` is clamped to `[0, 1]`, so `quantile(0.0)` is `min` and `quantile(1.0)` is `max`.
+    pub fn quantile(&self, q: f64) -> f64 {
+        let position = q.clamp(0.0, 1.0) * (self.count - 1) as f64;
+        let below = position.floor() as usize;
+        let above = position.ceil() as usize;
+        let fraction = position - below as f64;
+        self.sorted[below] + (self.sorted[above] - self.sorted[below]) * fraction
+    }
+
+    /// Returns the median weight.
+    pub fn median(&self) -> f64 {
+        self.quantile(0.5)
+    }
+
+    /// Counts the weights in `bins` equal-width bins spanning `[min, max]`.
+    ///
+    /// When every weight is equal, they all land in the first bin. Zero bins gives an empty
+    /// histogram.
+    pub fn histogram(&self, bins: usize) -> Histogram {
+        if bins == 0 {
+            return Histogram {
+                edges: Vec::new(),
+                counts: Vec::new(),
+            };
+        }
+        let width = (self.max - self.min) / bins as f64;
+        let edges = (0..=bins)
+            .map(|i| {
+                if i == bins {
+                    self.max
+                } else {
+                    self.min + width * i as f64
+                }
+            })
+            .collect();
+        let mut counts = vec![0; bins];
+        for &w in &self.sorted {
+            let bin = if width > 0.0 {
+                (((w - self.min) / width) as usize).min(bins - 1)
+            } else {
+                0
+            };
+            counts[bin] += 1;
+        }
+        Histogram { edges, counts }
+    }
+}
+
+/// Computes the distribution of edge weights in `graph`.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if the graph has no edges or a weight is NaN or infinite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::core::weights::weight_statistics;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// for (i, w) in [1.0, 2.0, 3.0, 10.0].into_iter().enumerate() {
+///     g.add_edge(n[i], n[i + 1], w);
+/// }
+///
+/// let stats = weight_statistics(&g).unwrap();
+/// assert_eq!((stats.min, stats.max, stats.mean), (1.0, 10.0, 4.0));
+/// assert_eq!(stats.median(), 2.5);
+/// assert_eq!(stats.histogram(3).counts, vec![3, 0, 1]);
+/// ```
+pub fn weight_statistics<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<WeightStatistics>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut sorted: Vec<f64> = graph.edges().map(|(_, _, w)| (*w).into()).collect();
+    if sorted.is_empty() {
+        return Err(GraphinaError::invalid_argument(
+            "Weight statistics need at least one edge",
+        ));
+    }
+    if let Some(bad) = sorted.iter().find(|w| !w.is_finite()) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Weight statistics need finite weights, found {}",
+            bad
+        )));
+    }
+    sorted.sort_by(f64::total_cmp);
+    let count = sorted.len();
+    let mean = sorted.iter().sum::<f64>() / count as f64;
+    let variance = sorted.iter().map(|w| (w - mean).powi(2)).sum::<f64>() / count as f64;
+    Ok(WeightStatistics {
+        count,
+        min: sorted[0],
+        max: sorted[count - 1],
+        mean,
+        std_dev: variance.sqrt(),
+        sorted,
+    })
+}
+
+/// Removes every edge whose weight falls outside the given range of weight quantiles, and
+/// returns the number removed.
+///
+/// `0.05..=0.95` drops the lightest and heaviest five percent of edges; `0.1..=1.0` trims only
+/// the light tail. Bounds are computed as by [`WeightStatistics::quantile`] and are inclusive,
+/// so edges tied with a bound stay. Nodes are never removed.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if the range is not within `[0, 1]` or is empty, or on the same
+/// conditions as [`weight_statistics`], in which case the graph is left unchanged.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::core::weights::trim_edges_by_weight;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..11).map(|_| g.add_node(())).collect();
+/// for i in 0..10 {
+///     g.add_edge(n[i], n[i + 1], i as f64);
+/// }
+///
+/// // Keep weights between the 10th and 80th percentiles, 0.9 and 7.2.
+/// assert_eq!(trim_edges_by_weight(&mut g, 0.1..=0.8).unwrap(), 3);
+/// assert_eq!(g.edge_count(), 7);
+/// ```
+pub fn trim_edges_by_weight<A, W, Ty>(
+    graph: &mut BaseGraph<A, W, Ty>,
+    quantile_range: RangeInclusive<f64>,
+) -> Result<usize>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let (lo, hi) = quantile_range.into_inner();
+    if !(0.0..=1.0).contains(&lo) || !(0.0..=1.0).contains(&hi) || lo > hi {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Quantile range must lie within [0, 1] with start <= end, got {}..={}",
+            lo, hi
+        )));
+    }
+    let stats = weight_statistics(graph)?;
+    let (min, max) = (stats.quantile(lo), stats.quantile(hi));
+    let doomed: Vec<_> = graph
+        .edges_with_ids()
+        .filter(|&(_, _, _, w)| {
+            let w: f64 = (*w).into();
+            w < min || w > max
+        })
+        .map(|(edge, _, _, _)| edge)
+        .collect();
+    for &edge in &doomed {
+        graph.remove_edge(edge);
+    }
+    Ok(doomed.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_weight_statistics_and_trimming() {
+        let mut g = Digraph::<(), f32>::new();
+        let [a, b] = [(); 2].map(|_| g.add_node(()));
+        for w in [4.0, 4.0, 4.0, 2.0, 6.0] {
+            g.add_edge(a, b, w);
+        }
+        let stats = weight_statistics(&g).unwrap();
+        assert_eq!(stats.count, 5);
+        assert_eq!(stats.mean, 4.0);
+        assert!((stats.std_dev - 1.6f64.sqrt()).abs() < 1e-12);
+        assert_eq!(stats.quantile(0.25), 4.0);
+        assert_eq!(stats.quantile(-1.0), 2.0);
+        let histogram = stats.histogram(4);
+        assert_eq!(histogram.edges, vec![2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(histogram.counts, vec![1, 0, 3, 1]);
+        assert!(stats.histogram(0).counts.is_empty());
+
+        // Ties with the bounds survive, and an invalid range changes nothing.
+        assert!(trim_edges_by_weight(&mut g, 0.9..=0.1).is_err());
+        assert!(trim_edges_by_weight(&mut g, 0.0..=1.5).is_err());
+        assert_eq!(trim_edges_by_weight(&mut g, 0.25..=0.75).unwrap(), 2);
+        assert_eq!(g.edge_count(), 3);
+        assert_eq!(
+            weight_statistics(&g).unwrap().histogram(2).counts,
+            vec![3, 0]
+        );
+
+        let mut empty = Graph::<(), f64>::new();
+        assert!(weight_statistics(&empty).is_err());
+        let [x, y] = [(); 2].map(|_| empty.add_node(()));
+        empty.add_edge(x, y, f64::NAN);
+        assert!(trim_edges_by_weight(&mut empty, 0.0..=1.0).is_err());
+        assert_eq!(empty.edge_count(), 1);
+    }
+}
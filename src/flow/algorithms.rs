@@ -0,0 +1,498 @@
+/*!
+# Maximum Flow and Minimum Cut
+
+Maximum s-t flow where every edge weight is a capacity, and the minimum s-t cut it
+certifies. It provides the following algorithms:
+
+- **Edmonds–Karp:**
+  Ford–Fulkerson with breadth-first augmenting paths, so each augmentation uses a shortest
+  path. Runs in `O(V E²)`.
+
+- **Dinic's Algorithm:**
+  Builds a level graph with one breadth-first search per phase and saturates it with a
+  blocking flow. Runs in `O(V² E)`, and much faster than that on unit-capacity and sparse
+  graphs.
+
+An undirected edge can carry flow either way, up to its capacity. Self-loops never carry
+flow, and parallel edges add up.
+
+## Error Handling
+
+A missing source or sink is a `NodeNotFound` error. A source equal to the sink, or a
+capacity that is negative, infinite, or NaN, is an `InvalidArgument` error.
+*/
+
+use std::collections::VecDeque;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, EdgeMap, GraphConstructor, NodeId};
+
+/// Residual capacities at or below this are treated as saturated.
+const EPSILON: f64 = 1e-12;
+
+/// A maximum flow.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MaxFlow {
+    /// Total flow from the source to the sink.
+    pub value: f64,
+    /// Flow on every edge, in the edge's stored direction. On undirected graphs a negative
+    /// value is flow from the edge's target to its source.
+    pub flows: EdgeMap<f64>,
+}
+
+/// Result type for [`min_cut`]: the flow value, the cut edges as `(source side, sink side)`
+/// pairs, and the `(source side, sink side)` node partition.
+pub type MinCutResult = (f64, Vec<(NodeId, NodeId)>, (Vec<NodeId>, Vec<NodeId>));
+
+/// Residual network over dense node indices. Arc `2i` follows edge `i` and arc `2i + 1` is
+/// its reverse, so `arc ^ 1` is always the partner arc.
+struct Residual {
+    edges: Vec<EdgeId>,
+    head: Vec<usize>,
+    residual: Vec<f64>,
+    arcs: Vec<Vec<usize>>,
+    source: usize,
+    sink: usize,
+    directed: bool,
+}
+
+impl Residual {
+    fn new<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, source: NodeId, sink: NodeId) -> Result<Self>
+    where
+        W: Copy + Into<f64>,
+        Ty: GraphConstructor<A, W>,
+    {
+        for (role, node) in [("source", source), ("sink", sink)] {
+            if !graph.contains_node(node) {
+                return Err(GraphinaError::node_not_found(format!(
+                    "Max flow: {role} node {node:?} is not in the graph"
+                )));
+            }
+        }
+        if source == sink {
+            return Err(GraphinaError::invalid_argument(
+                "Max flow: source and sink must differ",
+            ));
+        }
+
+        let bound = graph.node_ids().map(|v| v.index() + 1).max().unwrap_or(0);
+        let directed = graph.is_directed();
+        let mut edges = Vec::with_capacity(graph.edge_count());
+        let mut head = Vec::with_capacity(2 * graph.edge_count());
+        let mut residual = Vec::with_capacity(2 * graph.edge_count());
+        let mut arcs = vec![Vec::new(); bound];
+        for (edge, u, v, w) in graph.edges_with_ids() {
+            let capacity: f64 = (*w).into();
+            if !capacity.is_finite() || capacity < 0.0 {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "Max flow: capacity {capacity} of edge {edge:?} must be finite and nonnegative"
+                )));
+            }
+            edges.push(edge);
+            if u == v {
+                head.extend([u.index(), u.index()]);
+                residual.extend([0.0, 0.0]);
+                continue;
+            }
+            let arc = head.len();
+            head.extend([v.index(), u.index()]);
+            residual.extend([capacity, if directed { 0.0 } else { capacity }]);
+            arcs[u.index()].push(arc);
+            arcs[v.index()].push(arc + 1);
+        }
+        Ok(Self {
+            edges,
+            head,
+            residual,
+            arcs,
+            source: source.index(),
+            sink: sink.index(),
+            directed,
+        })
+    }
+
+    /// Breadth-first distances from the source over unsaturated arcs, with the arc used to
+    /// reach each node. Stops early once the sink is reached if `stop_at_sink` is set.
+    fn bfs(&self, stop_at_sink: bool) -> (Vec<usize>, Vec<usize>) {
+        let n = self.arcs.len();
+        let mut level = vec![usize::MAX; n];
+        let mut via = vec![usize::MAX; n];
+        level[self.source] = 0;
+        let mut queue = VecDeque::from([self.source]);
+        while let Some(u) = queue.pop_front() {
+            for &arc in &self.arcs[u] {
+                let v = self.head[arc];
+                if self.residual[arc] > EPSILON && level[v] == usize::MAX {
+                    level[v] = level[u] + 1;
+                    via[v] = arc;
+                    if stop_at_sink && v == self.sink {
+                        return (level, via);
+                    }
+                    queue.push_back(v);
+                }
+            }
+        }
+        (level, via)
+    }
+
+    fn push(&mut self, arc: usize, amount: f64) {
+        self.residual[arc] -= amount;
+        self.residual[arc ^ 1] += amount;
+    }
+
+    /// Edmonds–Karp: augment along shortest paths until the sink is unreachable.
+    fn edmonds_karp(&mut self) -> f64 {
+        let mut total = 0.0;
+        loop {
+            let (level, via) = self.bfs(true);
+            if level[self.sink] == usize::MAX {
+                return total;
+            }
+            let mut bottleneck = f64::INFINITY;
+            let mut v = self.sink;
+            while v != self.source {
+                bottleneck = bottleneck.min(self.residual[via[v]]);
+                v = self.head[via[v] ^ 1];
+            }
+            let mut v = self.sink;
+            while v != self.source {
+                self.push(via[v], bottleneck);
+                v = self.head[via[v] ^ 1];
+            }
+            total += bottleneck;
+        }
+    }
+
+    /// Dinic: one level graph per phase, saturated by repeated depth-first searches that
+    /// skip arcs already found to be dead ends.
+    fn dinic(&mut self) -> f64 {
+        let n = self.arcs.len();
+        let mut total = 0.0;
+        loop {
+            let (level, _) = self.bfs(false);
+            if level[self.sink] == usize::MAX {
+                return total;
+            }
+            let mut next_arc = vec![0; n];
+            let mut path: Vec<usize> = Vec::new();
+            let mut u = self.source;
+            loop {
+                if u == self.sink {
+                    let bottleneck = path
+                        .iter()
+                        .map(|&arc| self.residual[arc])
+                        .fold(f64::INFINITY, f64::min);
+                    for &arc in &path {
+                        self.push(arc, bottleneck);
+                    }
+                    total += bottleneck;
+                    // Retreat to the tail of the first saturated arc.
+                    let keep = path
+                        .iter()
+                        .position(|&arc| self.residual[arc] <= EPSILON)
+                        .unwrap_or(0);
+                    path.truncate(keep);
+                    u = path.last().map_or(self.source, |&arc| self.head[arc]);
+                    continue;
+                }
+                let advance = self.arcs[u][next_arc[u]..].iter().position(|&arc| {
+                    self.residual[arc] > EPSILON && level[self.head[arc]] == level[u] + 1
+                });
+                match advance {
+                    Some(offset) => {
+                        next_arc[u] += offset;
+                        let arc = self.arcs[u][next_arc[u]];
+                        path.push(arc);
+                        u = self.head[arc];
+                    }
+                    None => {
+                        next_arc[u] = self.arcs[u].len();
+                        match path.pop() {
+                            Some(arc) => {
+                                u = self.head[arc ^ 1];
+                                next_arc[u] += 1;
+                            }
+                            None => break,
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn into_max_flow(self, value: f64) -> MaxFlow {
+        let flows = self
+            .edges
+            .iter()
+            .enumerate()
+            .map(|(i, &edge)| {
+                // An undirected arc pair starts at `(c, c)` and pushing `x` forward leaves
+                // `(c - x, c + x)`.
+                let flow = if self.directed {
+                    self.residual[2 * i + 1]
+                } else {
+                    (self.residual[2 * i + 1] - self.residual[2 * i]) / 2.0
+                };
+                (edge, flow)
+            })
+            .collect();
+        MaxFlow { value, flows }
+    }
+}
+
+/// Maximum s-t flow by the Edmonds–Karp algorithm.
+///
+/// Edge weights are capacities. See the [module documentation](self) for the errors.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::flow::edmonds_karp;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let s = g.add_node(());
+/// let a = g.add_node(());
+/// let t = g.add_node(());
+/// g.add_edge(s, a, 3.0);
+/// g.add_edge(a, t, 2.0);
+/// g.add_edge(s, t, 1.0);
+///
+/// let flow = edmonds_karp(&g, s, t).unwrap();
+/// assert_eq!(flow.value, 3.0);
+/// ```
+pub fn edmonds_karp<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    sink: NodeId,
+) -> Result<MaxFlow>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let mut residual = Residual::new(graph, source, sink)?;
+    let value = residual.edmonds_karp();
+    Ok(residual.into_max_flow(value))
+}
+
+/// Maximum s-t flow by Dinic's algorithm.
+///
+/// Gives the same flow value as [`edmonds_karp`], usually faster; the per-edge flows may
+/// differ when the maximum flow is not unique.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::flow::dinic;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let s = g.add_node(());
+/// let a = g.add_node(());
+/// let t = g.add_node(());
+/// g.add_edge(s, a, 2.0);
+/// g.add_edge(t, a, 5.0);
+///
+/// let flow = dinic(&g, s, t).unwrap();
+/// assert_eq!(flow.value, 2.0);
+/// ```
+pub fn dinic<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, source: NodeId, sink: NodeId) -> Result<MaxFlow>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let mut residual = Residual::new(graph, source, sink)?;
+    let value = residual.dinic();
+    Ok(residual.into_max_flow(value))
+}
+
+/// Minimum s-t cut, from a maximum flow computed with [`dinic`].
+///
+/// The source side holds the nodes still reachable from the source in the residual network,
+/// which is the cut closest to the source. The cut edges are the edges from the source side
+/// to the sink side; their capacities add up to the flow value. Both node lists are in node
+/// order.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::flow::min_cut;
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let s = g.add_node(());
+/// let a = g.add_node(());
+/// let t = g.add_node(());
+/// g.add_edge(s, a, 3.0);
+/// g.add_edge(a, t, 2.0);
+///
+/// let (value, cut, (source_side, sink_side)) = min_cut(&g, s, t).unwrap();
+/// assert_eq!(value, 2.0);
+/// assert_eq!(cut, vec![(a, t)]);
+/// assert_eq!((source_side, sink_side), (vec![s, a], vec![t]));
+/// ```
+pub fn min_cut<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    source: NodeId,
+    sink: NodeId,
+) -> Result<MinCutResult>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W>,
+{
+    let mut residual = Residual::new(graph, source, sink)?;
+    let value = residual.dinic();
+    let (level, _) = residual.bfs(false);
+    let on_source_side = |v: NodeId| level[v.index()] != usize::MAX;
+
+    let (source_side, sink_side): (Vec<NodeId>, Vec<NodeId>) =
+        graph.node_ids().partition(|&v| on_source_side(v));
+    let directed = graph.is_directed();
+    let cut = graph
+        .edges()
+        .filter_map(|(u, v, _)| match (on_source_side(u), on_source_side(v)) {
+            (true, false) => Some((u, v)),
+            (false, true) if !directed => Some((v, u)),
+            _ => None,
+        })
+        .collect();
+    Ok((value, cut, (source_side, sink_side)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    /// The classic CLRS flow network, with maximum flow 23.
+    fn clrs() -> (Digraph<(), f64>, Vec<NodeId>) {
+        let mut g = Digraph::new();
+        let n: Vec<NodeId> = (0..6).map(|_| g.add_node(())).collect();
+        for (u, v, c) in [
+            (0, 1, 16.0),
+            (0, 2, 13.0),
+            (2, 1, 4.0),
+            (1, 3, 12.0),
+            (3, 2, 9.0),
+            (2, 4, 14.0),
+            (4, 3, 7.0),
+            (3, 5, 20.0),
+            (4, 5, 4.0),
+        ] {
+            g.add_edge(n[u], n[v], c);
+        }
+        (g, n)
+    }
+
+    /// Checks capacity limits and conservation at every node but the terminals.
+    fn assert_feasible<Ty: GraphConstructor<(), f64>>(
+        g: &BaseGraph<(), f64, Ty>,
+        flow: &MaxFlow,
+        s: NodeId,
+        t: NodeId,
+    ) {
+        let mut excess: std::collections::HashMap<NodeId, f64> = Default::default();
+        for (edge, u, v, c) in g.edges_with_ids() {
+            let f = flow.flows[&edge];
+            assert!(f.abs() <= *c + 1e-9);
+            assert!(!g.is_directed() || f >= -1e-9);
+            *excess.entry(u).or_default() -= f;
+            *excess.entry(v).or_default() += f;
+        }
+        for (v, e) in excess {
+            let expected = if v == s {
+                -flow.value
+            } else if v == t {
+                flow.value
+            } else {
+                0.0
+            };
+            assert!((e - expected).abs() < 1e-9, "{v:?}: {e}");
+        }
+    }
+
+    #[test]
+    fn test_max_flow_and_min_cut_agree() {
+        let (g, n) = clrs();
+        for flow in [
+            edmonds_karp(&g, n[0], n[5]).unwrap(),
+            dinic(&g, n[0], n[5]).unwrap(),
+        ] {
+            assert_eq!(flow.value, 23.0);
+            assert_feasible(&g, &flow, n[0], n[5]);
+        }
+        let (value, cut, (source_side, sink_side)) = min_cut(&g, n[0], n[5]).unwrap();
+        assert_eq!(value, 23.0);
+        let capacity: f64 = cut
+            .iter()
+            .map(|&(u, v)| *g.edge_weight(g.find_edge(u, v).unwrap()).unwrap())
+            .sum();
+        assert_eq!(capacity, 23.0);
+        assert_eq!(source_side, vec![n[0], n[1], n[2], n[4]]);
+        assert_eq!(sink_side, vec![n[3], n[5]]);
+
+        // Undirected edges carry flow both ways; parallel edges add up and self-loops are ignored.
+        let mut u = Graph::<(), f64>::new();
+        let m: Vec<NodeId> = (0..4).map(|_| u.add_node(())).collect();
+        u.add_edge(m[1], m[0], 2.0);
+        u.add_edge(m[0], m[1], 1.0);
+        u.add_edge(m[1], m[1], 9.0);
+        u.add_edge(m[3], m[1], 5.0);
+        u.add_edge(m[0], m[2], 1.0);
+        u.add_edge(m[2], m[3], 4.0);
+        for flow in [
+            edmonds_karp(&u, m[0], m[3]).unwrap(),
+            dinic(&u, m[0], m[3]).unwrap(),
+        ] {
+            assert_eq!(flow.value, 4.0);
+            assert_feasible(&u, &flow, m[0], m[3]);
+        }
+        let (value, cut, _) = min_cut(&u, m[0], m[3]).unwrap();
+        assert_eq!(value, 4.0);
+        assert_eq!(cut, vec![(m[0], m[1]), (m[0], m[1]), (m[0], m[2])]);
+
+        // Unreachable sink, bad terminals, and bad capacities.
+        let mut d = Digraph::<(), f64>::new();
+        let a = d.add_node(());
+        let b = d.add_node(());
+        d.add_edge(b, a, 1.0);
+        assert_eq!(dinic(&d, a, b).unwrap().value, 0.0);
+        assert!(matches!(
+            dinic(&d, a, a),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+        d.remove_node(b);
+        assert!(matches!(
+            min_cut(&d, a, b),
+            Err(GraphinaError::NodeNotFound(_))
+        ));
+        let c = d.add_node(());
+        d.add_edge(a, c, -1.0);
+        assert!(matches!(
+            edmonds_karp(&d, a, c),
+            Err(GraphinaError::InvalidArgument(_))
+        ));
+    }
+
+    #[test]
+    fn test_dinic_matches_edmonds_karp_on_layered_networks() {
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<NodeId> = (0..60).map(|_| g.add_node(())).collect();
+        for i in 0..59 {
+            for step in [1, 3, 7, 13] {
+                let j = i + step;
+                if j < 60 {
+                    g.add_edge(n[i], n[j], ((i * 31 + step * 17) % 11 + 1) as f64);
+                }
+            }
+            g.add_edge(n[i + 1], n[i], ((i * 7) % 5) as f64);
+        }
+        let ek = edmonds_karp(&g, n[0], n[59]).unwrap();
+        let di = dinic(&g, n[0], n[59]).unwrap();
+        assert!((ek.value - di.value).abs() < 1e-9);
+        assert_feasible(&g, &di, n[0], n[59]);
+        let (value, _, (source_side, _)) = min_cut(&g, n[0], n[59]).unwrap();
+        assert!((value - di.value).abs() < 1e-9);
+        assert!(source_side.contains(&n[0]) && !source_side.contains(&n[59]));
+    }
+}
@@ -0,0 +1,9 @@
+//! Network flow algorithms module.
+//!
+//! Maximum s-t flow (Edmonds–Karp and Dinic) and minimum s-t cuts, with edge weights as
+//! capacities. All algorithms depend only on the core module for basic graph operations.
+
+pub mod algorithms;
+
+// Re-export all public items
+pub use algorithms::{MaxFlow, MinCutResult, dinic, edmonds_karp, min_cut};
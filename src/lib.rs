@@ -6,16 +6,21 @@ A graph data science library that provides common graph types, algorithms, and d
 
 ## Module Overview
 
-* `core` – Always enabled: basic graph types, builders, IO, serialization, paths, validation.
+* `core` – Always enabled: basic graph types, node and edge properties, builders, IO, serialization, fingerprints, paths, validation, type-level requirements, contraction, percolation, line graphs and subdivision, graph products, temporal graphs.
 * `centrality` *(feature: centrality)* – Node/edge importance measures (Result-based APIs).
 * `community` *(feature: community)* – Community detection and clustering (Result-based APIs).
 * `links` *(feature: links)* – Link prediction algorithms.
 * `metrics` *(feature: metrics)* – Graph and node metrics (diameter, radius, clustering, etc.).
+* `flow` *(feature: flow)* – Maximum flow and minimum cut algorithms.
 * `mst` *(feature: mst)* – Minimum spanning tree algorithms.
 * `traversal` *(feature: traversal)* – BFS/DFS and related traversal strategies.
 * `approximation` *(feature: approximation)* – Heuristics for NP-hard problems.
 * `parallel` *(feature: parallel)* – Parallel implementations for selected algorithms.
-* `subgraphs` *(feature: subgraphs)* – Induced subgraph and ego network utilities.
+* `subgraphs` *(feature: subgraphs)* – Induced subgraph and ego network utilities, and rule-based rewriting.
+* `visualization` *(feature: visualization)* – Node layouts and SVG/HTML rendering.
+* `ml` *(feature: ml)* – linfa-backed clustering and classification over node features.
+* `prelude` – Always enabled: the most used types, traits, and algorithms for `use graphina::prelude::*`.
+* `registry` *(feature: registry)* – Runs enabled algorithms by name with uniform parameters and results, or in the background with feature `async`.
 
 ## API Conventions
 
@@ -35,18 +40,29 @@ pub mod centrality;
 pub mod community;
 /// Core graph types and utilities.
 pub mod core;
+/// Maximum flow and minimum cut algorithms.
+#[cfg(feature = "flow")]
+pub mod flow;
 /// Link prediction algorithms.
 #[cfg(feature = "links")]
 pub mod links;
 /// Graph metrics and metrics-based algorithms.
 #[cfg(feature = "metrics")]
 pub mod metrics;
+/// Clustering and classification over node features.
+#[cfg(feature = "ml")]
+pub mod ml;
 /// Minimum spanning tree algorithms.
 #[cfg(feature = "mst")]
 pub mod mst;
 /// Parallel implementations of algorithms.
 #[cfg(feature = "parallel")]
 pub mod parallel;
+/// The most used types, traits, and algorithms.
+pub mod prelude;
+/// Name-based dispatch over the enabled algorithms.
+#[cfg(feature = "registry")]
+pub mod registry;
 /// Logging configuration.
 #[cfg(feature = "logging")]
 mod settings;
@@ -56,3 +72,6 @@ pub mod subgraphs;
 /// Graph traversal algorithms.
 #[cfg(feature = "traversal")]
 pub mod traversal;
+/// Node layouts and graph rendering.
+#[cfg(feature = "visualization")]
+pub mod visualization;
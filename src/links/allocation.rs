@@ -2,27 +2,8 @@
 //!
 //! Resource allocation-based link prediction algorithms.
 
+use super::neighborhood::{LinkScore, NeighborIndex};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
-use std::collections::HashSet;
-
-/// Neighbor set with `rustc_hash`'s fast hasher; `NodeId` keys do not need
-/// SipHash's DoS resistance, and the default hasher dominated the per-pair cost.
-type NodeSet = HashSet<NodeId, rustc_hash::FxBuildHasher>;
-
-/// Helper: If no ebunch is provided, generate all unordered pairs of nodes.
-fn default_ebunch<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<(NodeId, NodeId)>
-where
-    Ty: crate::core::types::GraphConstructor<A, W>,
-{
-    let nodes: Vec<NodeId> = graph.nodes().map(|(u, _)| u).collect();
-    let mut ebunch = Vec::new();
-    for i in 0..nodes.len() {
-        for j in (i + 1)..nodes.len() {
-            ebunch.push((nodes[i], nodes[j]));
-        }
-    }
-    ebunch
-}
 
 /// Resource Allocation Index (RA)
 /// For each pair (u, v), RA = sum_{w in N(u) ∩ N(v)} (1 / degree(w))
@@ -33,25 +14,7 @@ pub fn resource_allocation_index<A, Ty>(
 where
     Ty: GraphConstructor<A, f64>,
 {
-    let pairs = match ebunch {
-        Some(p) => p.to_vec(),
-        None => default_ebunch(graph),
-    };
-    let mut results = Vec::with_capacity(pairs.len());
-    for (u, v) in pairs {
-        let neighbors_u: NodeSet = graph.neighbors(u).collect();
-        let neighbors_v: NodeSet = graph.neighbors(v).collect();
-        // Sum over the common neighbors directly, without collecting them first.
-        let score: f64 = neighbors_u
-            .intersection(&neighbors_v)
-            .map(|w| {
-                let deg = graph.neighbors(*w).count();
-                if deg > 0 { 1.0 / deg as f64 } else { 0.0 }
-            })
-            .sum();
-        results.push(((u, v), score));
-    }
-    results
+    NeighborIndex::new(graph).score_pairs(ebunch, LinkScore::ResourceAllocation)
 }
 
 /// RA Index Soundarajan–Hopcroft
@@ -67,31 +30,19 @@ where
     F: Fn(NodeId) -> C,
     C: Eq,
 {
-    let pairs = match ebunch {
-        Some(p) => p.to_vec(),
-        None => default_ebunch(graph),
-    };
-    let mut results = Vec::new();
-    for (u, v) in pairs {
-        let set_u: Vec<NodeId> = graph.neighbors(u).collect();
-        let set_v: HashSet<NodeId> = graph.neighbors(v).collect();
-        let common: Vec<NodeId> = set_u.into_iter().filter(|w| set_v.contains(w)).collect();
-        let score: f64 = common
-            .into_iter()
-            .filter_map(|w| {
-                if community(u) == community(w) && community(v) == community(w) {
-                    let deg = graph.neighbors(w).count();
-                    if deg > 0 {
-                        Some(1.0 / (deg as f64))
-                    } else {
-                        None
-                    }
-                } else {
-                    None
-                }
-            })
-            .sum();
-        results.push(((u, v), score));
-    }
-    results
+    let index = NeighborIndex::new(graph);
+    index.map_pairs(ebunch, |u, v| {
+        let cu = community(u);
+        if cu != community(v) {
+            return 0.0;
+        }
+        let mut score = 0.0;
+        index.for_each_common(u, v, |w| {
+            let deg = index.degree(w);
+            if deg > 0 && community(w) == cu {
+                score += 1.0 / deg as f64;
+            }
+        });
+        score
+    })
 }
@@ -2,23 +2,8 @@
 //!
 //! Cluster-based link prediction algorithms.
 
+use super::neighborhood::NeighborIndex;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
-use std::collections::HashSet;
-
-/// Helper: If no ebunch is provided, generate all unordered pairs of nodes.
-fn default_ebunch<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<(NodeId, NodeId)>
-where
-    Ty: crate::core::types::GraphConstructor<A, W>,
-{
-    let nodes: Vec<NodeId> = graph.nodes().map(|(u, _)| u).collect();
-    let mut ebunch = Vec::new();
-    for i in 0..nodes.len() {
-        for j in (i + 1)..nodes.len() {
-            ebunch.push((nodes[i], nodes[j]));
-        }
-    }
-    ebunch
-}
 
 /// Within–Inter Cluster Ratio
 /// For each pair (u, v), computes the ratio:
@@ -35,22 +20,18 @@ where
     F: Fn(NodeId) -> C,
     C: Eq,
 {
-    let pairs = match ebunch {
-        Some(p) => p.to_vec(),
-        None => default_ebunch(graph),
-    };
-    let mut results = Vec::new();
-    for (u, v) in pairs {
-        let set_u: Vec<NodeId> = graph.neighbors(u).collect();
-        let set_v: HashSet<NodeId> = graph.neighbors(v).collect();
-        let common: Vec<NodeId> = set_u.into_iter().filter(|w| set_v.contains(w)).collect();
-        let within = common
-            .iter()
-            .filter(|&&w| community(u) == community(w) && community(v) == community(w))
-            .count() as f64;
-        let inter = (common.len() as f64) - within;
-        let score = (within + delta) / (inter + delta);
-        results.push(((u, v), score));
-    }
-    results
+    let index = NeighborIndex::new(graph);
+    index.map_pairs(ebunch, |u, v| {
+        let cu = community(u);
+        let same = cu == community(v);
+        let (mut within, mut total) = (0usize, 0usize);
+        index.for_each_common(u, v, |w| {
+            total += 1;
+            if same && community(w) == cu {
+                within += 1;
+            }
+        });
+        let inter = (total - within) as f64;
+        (within as f64 + delta) / (inter + delta)
+    })
 }
@@ -0,0 +1,261 @@
+//! Direction-aware link prediction algorithms.
+//!
+//! The scorers in the other `links` modules read neighbors without regard to direction.
+//! On a follower graph that mixes up "both follow the same account" with "both are followed
+//! by the same account", which predict different things. The functions here take a
+//! [`NeighborDirection`] that picks which neighbors count as common.
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use std::collections::HashSet;
+
+type NodeSet = HashSet<NodeId, rustc_hash::FxBuildHasher>;
+
+/// Which neighbors of the two nodes are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NeighborDirection {
+    /// Common successors: nodes that both `u` and `v` point to.
+    Out,
+    /// Common predecessors: nodes that point to both `u` and `v`.
+    In,
+    /// Nodes adjacent to both `u` and `v` in either direction.
+    Both,
+    /// Intermediaries on a two-step path `u -> w -> v`. Pairs are ordered.
+    Through,
+}
+
+impl NeighborDirection {
+    /// Whether swapping the two nodes of a pair can change its score.
+    fn is_ordered(self) -> bool {
+        self == NeighborDirection::Through
+    }
+}
+
+fn successors<A, W, Ty: GraphConstructor<A, W>>(g: &BaseGraph<A, W, Ty>, n: NodeId) -> NodeSet {
+    g.outgoing_neighbors(n).collect()
+}
+
+fn predecessors<A, W, Ty: GraphConstructor<A, W>>(g: &BaseGraph<A, W, Ty>, n: NodeId) -> NodeSet {
+    g.incoming_neighbors(n).collect()
+}
+
+fn either<A, W, Ty: GraphConstructor<A, W>>(g: &BaseGraph<A, W, Ty>, n: NodeId) -> NodeSet {
+    g.outgoing_neighbors(n)
+        .chain(g.incoming_neighbors(n))
+        .collect()
+}
+
+/// The common neighbors of `(u, v)` under `direction`, without `u` and `v` themselves.
+fn common<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    u: NodeId,
+    v: NodeId,
+    direction: NeighborDirection,
+) -> Vec<NodeId>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let (set_u, set_v) = match direction {
+        NeighborDirection::Out => (successors(graph, u), successors(graph, v)),
+        NeighborDirection::In => (predecessors(graph, u), predecessors(graph, v)),
+        NeighborDirection::Both => (either(graph, u), either(graph, v)),
+        NeighborDirection::Through => (successors(graph, u), predecessors(graph, v)),
+    };
+    set_u
+        .intersection(&set_v)
+        .copied()
+        .filter(|&w| w != u && w != v)
+        .collect()
+}
+
+/// Degree of a common neighbor `w` counted towards the pair: for common successors, how
+/// many nodes point to `w`; for common predecessors, how many nodes `w` points to; and the
+/// number of distinct neighbors otherwise.
+fn popularity<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    w: NodeId,
+    direction: NeighborDirection,
+) -> usize
+where
+    Ty: GraphConstructor<A, W>,
+{
+    match direction {
+        NeighborDirection::Out => predecessors(graph, w).len(),
+        NeighborDirection::In => successors(graph, w).len(),
+        NeighborDirection::Both | NeighborDirection::Through => either(graph, w).len(),
+    }
+}
+
+/// Helper: If no ebunch is provided, generate all pairs of nodes, ordered when the
+/// direction makes the score asymmetric and unordered otherwise.
+fn default_ebunch<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    direction: NeighborDirection,
+) -> Vec<(NodeId, NodeId)>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let mut ebunch = Vec::new();
+    for i in 0..nodes.len() {
+        for j in 0..nodes.len() {
+            if i < j || (i > j && direction.is_ordered()) {
+                ebunch.push((nodes[i], nodes[j]));
+            }
+        }
+    }
+    ebunch
+}
+
+/// Directed Common Neighbors
+/// For each pair (u, v), counts the common neighbors selected by `direction`.
+///
+/// On an undirected graph every direction gives the plain common-neighbor count. Without
+/// an `ebunch`, all unordered pairs are scored, or all ordered pairs for
+/// [`NeighborDirection::Through`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::links::directed::{directed_common_neighbors, NeighborDirection};
+///
+/// // a and b both follow c; d follows a and b.
+/// let mut g = Digraph::<(), f64>::new();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.add_edge(a, c, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(d, a, 1.0);
+/// g.add_edge(d, b, 1.0);
+///
+/// let pair = [(a, b)];
+/// assert_eq!(directed_common_neighbors(&g, Some(&pair), NeighborDirection::Out)[0].1, 1.0);
+/// assert_eq!(directed_common_neighbors(&g, Some(&pair), NeighborDirection::Both)[0].1, 2.0);
+/// ```
+pub fn directed_common_neighbors<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    ebunch: Option<&[(NodeId, NodeId)]>,
+    direction: NeighborDirection,
+) -> Vec<((NodeId, NodeId), f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let pairs = match ebunch {
+        Some(p) => p.to_vec(),
+        None => default_ebunch(graph, direction),
+    };
+    pairs
+        .into_iter()
+        .map(|(u, v)| ((u, v), common(graph, u, v, direction).len() as f64))
+        .collect()
+}
+
+/// Directed Adamic–Adar Index
+/// For each pair (u, v), AA = sum over the common neighbors w selected by `direction` of
+/// 1 / log(popularity(w)).
+///
+/// The popularity of a common successor is its in-degree, since an account that everyone
+/// follows says little about two followers; likewise, a common predecessor is weighed by
+/// its out-degree. [`NeighborDirection::Both`] and [`NeighborDirection::Through`] use the
+/// number of distinct neighbors. Neighbors with popularity `<= 1` are skipped. On an
+/// undirected graph every direction gives the plain Adamic–Adar index.
+pub fn directed_adamic_adar_index<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    ebunch: Option<&[(NodeId, NodeId)]>,
+    direction: NeighborDirection,
+) -> Vec<((NodeId, NodeId), f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let pairs = match ebunch {
+        Some(p) => p.to_vec(),
+        None => default_ebunch(graph, direction),
+    };
+    let mut results = Vec::with_capacity(pairs.len());
+    for (u, v) in pairs {
+        let score: f64 = common(graph, u, v, direction)
+            .into_iter()
+            .filter_map(|w| {
+                let deg = popularity(graph, w, direction);
+                if deg > 1 {
+                    Some(1.0 / (deg as f64).ln())
+                } else {
+                    None
+                }
+            })
+            .sum();
+        results.push(((u, v), score));
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+    use crate::links::similarity::adamic_adar_index;
+
+    #[test]
+    fn test_directed_variants_separate_followers_and_followees() {
+        // 0 and 1 follow 2 and 3; 4 follows 0 and 1; 0 follows 5, which follows 1.
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<_> = (0..7).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[
+            (0, 2),
+            (1, 2),
+            (0, 3),
+            (1, 3),
+            (4, 0),
+            (4, 1),
+            (0, 5),
+            (5, 1),
+            (6, 2),
+        ] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let pair = [(n[0], n[1])];
+        let cn = |d| directed_common_neighbors(&g, Some(&pair), d)[0].1;
+        assert_eq!(cn(NeighborDirection::Out), 2.0);
+        assert_eq!(cn(NeighborDirection::In), 1.0);
+        assert_eq!(cn(NeighborDirection::Both), 4.0);
+        assert_eq!(cn(NeighborDirection::Through), 1.0);
+        let reversed = [(n[1], n[0])];
+        assert_eq!(
+            directed_common_neighbors(&g, Some(&reversed), NeighborDirection::Through)[0].1,
+            0.0
+        );
+
+        // Node 2 has three followers and node 3 two.
+        let aa = directed_adamic_adar_index(&g, Some(&pair), NeighborDirection::Out)[0].1;
+        assert!((aa - (1.0 / 3f64.ln() + 1.0 / 2f64.ln())).abs() < 1e-12);
+
+        assert_eq!(
+            directed_common_neighbors(&g, None, NeighborDirection::In).len(),
+            21
+        );
+        assert_eq!(
+            directed_adamic_adar_index(&g, None, NeighborDirection::Through).len(),
+            42
+        );
+    }
+
+    #[test]
+    fn test_directed_variants_match_undirected_scores() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (0, 2), (1, 2), (2, 3), (1, 3), (3, 4)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let expected = adamic_adar_index(&g, None);
+        for direction in [
+            NeighborDirection::Out,
+            NeighborDirection::In,
+            NeighborDirection::Both,
+        ] {
+            let scores = directed_adamic_adar_index(&g, None, direction);
+            for (a, b) in scores.iter().zip(&expected) {
+                assert_eq!(a.0, b.0);
+                assert!((a.1 - b.1).abs() < 1e-12);
+            }
+        }
+    }
+}
@@ -1,6 +1,10 @@
 pub mod allocation;
 pub mod attachment;
+pub mod attributed;
 pub mod centrality;
 pub mod cluster;
+pub mod directed;
+pub mod neighborhood;
 pub mod similarity;
 pub mod soundarajan_hopcroft;
+pub mod triadic;
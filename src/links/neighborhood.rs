@@ -0,0 +1,369 @@
+//! Shared neighbor index for neighborhood-based link prediction.
+//!
+//! Every neighborhood-based scorer compares the neighbor sets of the two nodes of a pair.
+//! [`NeighborIndex`] builds those sets once, as sorted arrays of dense node positions, so
+//! that each pair costs one merge of two slices instead of two hash-set constructions.
+//! The scorers in this module's siblings build an index per call; build one yourself and
+//! call [`NeighborIndex::score_pairs`] to score several batches or metrics against the same
+//! graph without rebuilding it.
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+
+/// Marks a `NodeId` slot with no node behind it.
+const ABSENT: u32 = u32::MAX;
+
+/// Below this length ratio, merging two sorted lists beats binary-searching the longer one.
+const GALLOP_RATIO: usize = 16;
+
+/// Neighborhood-based similarity scores available in [`NeighborIndex::score_pairs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LinkScore {
+    /// `|N(u) ∩ N(v)|`.
+    CommonNeighbors,
+    /// `|N(u) ∩ N(v)| / |N(u) ∪ N(v)|`, or `0.0` when the union is empty.
+    Jaccard,
+    /// Sum of `1 / ln(degree(w))` over common neighbors `w` of degree above 1.
+    AdamicAdar,
+    /// Sum of `1 / degree(w)` over common neighbors `w`.
+    ResourceAllocation,
+    /// `degree(u) * degree(v)`.
+    PreferentialAttachment,
+}
+
+/// Sorted neighbor lists of every node of a graph, in a compressed sparse row layout.
+///
+/// Neighbors are those returned by [`BaseGraph::neighbors`], counted once each; degrees
+/// count every edge, so parallel edges raise a node's degree exactly as in the per-pair
+/// scorers. Nodes that are not in the graph have no neighbors and degree 0.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::links::neighborhood::{LinkScore, NeighborIndex};
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.add_edge(a, c, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(b, d, 1.0);
+///
+/// let index = NeighborIndex::new(&g);
+/// let pairs = [(a, b), (a, d)];
+/// let jaccard = index.score_pairs(Some(&pairs), LinkScore::Jaccard);
+/// assert_eq!(jaccard[0].1, 0.5);
+/// assert_eq!(index.common_neighbors(a, d), 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct NeighborIndex {
+    /// Dense position of each `NodeId::index()`, or [`ABSENT`].
+    slot: Vec<u32>,
+    /// `offsets[p]..offsets[p + 1]` delimits the neighbors of position `p` in `targets`.
+    offsets: Vec<usize>,
+    /// Neighbor positions, sorted and deduplicated per node.
+    targets: Vec<u32>,
+    /// Degree of each position, counting parallel edges.
+    degree: Vec<usize>,
+    /// `NodeId` of each position.
+    ids: Vec<NodeId>,
+    /// Whether neighbor lists are symmetric, so that `w` lists `u` whenever `u` lists `w`.
+    symmetric: bool,
+}
+
+impl NeighborIndex {
+    /// Builds the index in O(V + E log d) time, where d is the maximum degree.
+    pub fn new<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, W>,
+    {
+        let ids: Vec<NodeId> = graph.node_ids().collect();
+        let bound = ids.iter().map(|id| id.index() + 1).max().unwrap_or(0);
+        let mut slot = vec![ABSENT; bound];
+        for (p, id) in ids.iter().enumerate() {
+            slot[id.index()] = p as u32;
+        }
+        let mut offsets = Vec::with_capacity(ids.len() + 1);
+        let mut targets = Vec::new();
+        let mut degree = Vec::with_capacity(ids.len());
+        offsets.push(0);
+        for &id in &ids {
+            let start = targets.len();
+            targets.extend(graph.neighbors(id).map(|n| slot[n.index()]));
+            degree.push(targets.len() - start);
+            targets[start..].sort_unstable();
+            let mut end = start;
+            for i in start..targets.len() {
+                if i == start || targets[i] != targets[end - 1] {
+                    targets[end] = targets[i];
+                    end += 1;
+                }
+            }
+            targets.truncate(end);
+            offsets.push(end);
+        }
+        NeighborIndex {
+            slot,
+            offsets,
+            targets,
+            degree,
+            ids,
+            symmetric: !graph.is_directed(),
+        }
+    }
+
+    fn position(&self, node: NodeId) -> Option<usize> {
+        match self.slot.get(node.index()) {
+            Some(&p) if p != ABSENT => Some(p as usize),
+            _ => None,
+        }
+    }
+
+    fn list(&self, node: NodeId) -> &[u32] {
+        match self.position(node) {
+            Some(p) => &self.targets[self.offsets[p]..self.offsets[p + 1]],
+            None => &[],
+        }
+    }
+
+    /// Number of edges at `node`, counting parallel edges; 0 if it is not in the graph.
+    pub fn degree(&self, node: NodeId) -> usize {
+        self.position(node).map_or(0, |p| self.degree[p])
+    }
+
+    /// Calls `f` with the position of every common neighbor of `u` and `v`, in increasing
+    /// order.
+    fn for_each_common_position(&self, u: NodeId, v: NodeId, mut f: impl FnMut(usize)) {
+        let (mut a, mut b) = (self.list(u), self.list(v));
+        if a.len() > b.len() {
+            std::mem::swap(&mut a, &mut b);
+        }
+        if a.is_empty() {
+            return;
+        }
+        if a.len() * GALLOP_RATIO < b.len() {
+            let mut rest = b;
+            for &x in a {
+                match rest.binary_search(&x) {
+                    Ok(i) => {
+                        f(x as usize);
+                        rest = &rest[i + 1..];
+                    }
+                    Err(i) => rest = &rest[i..],
+                }
+            }
+            return;
+        }
+        let (mut i, mut j) = (0, 0);
+        while i < a.len() && j < b.len() {
+            match a[i].cmp(&b[j]) {
+                std::cmp::Ordering::Less => i += 1,
+                std::cmp::Ordering::Greater => j += 1,
+                std::cmp::Ordering::Equal => {
+                    f(a[i] as usize);
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    }
+
+    /// Calls `f` with every common neighbor of `u` and `v`.
+    pub(crate) fn for_each_common(&self, u: NodeId, v: NodeId, mut f: impl FnMut(NodeId)) {
+        self.for_each_common_position(u, v, |p| f(self.ids[p]));
+    }
+
+    /// Number of distinct common neighbors of `u` and `v`.
+    pub fn common_neighbors(&self, u: NodeId, v: NodeId) -> usize {
+        let mut count = 0;
+        self.for_each_common_position(u, v, |_| count += 1);
+        count
+    }
+
+    /// Scores one pair.
+    pub fn score(&self, u: NodeId, v: NodeId, metric: LinkScore) -> f64 {
+        match metric {
+            LinkScore::CommonNeighbors => self.common_neighbors(u, v) as f64,
+            LinkScore::Jaccard => {
+                let common = self.common_neighbors(u, v);
+                // |A ∪ B| = |A| + |B| - |A ∩ B|.
+                let union = self.list(u).len() + self.list(v).len() - common;
+                if union > 0 {
+                    common as f64 / union as f64
+                } else {
+                    0.0
+                }
+            }
+            LinkScore::AdamicAdar => {
+                let mut total = 0.0;
+                self.for_each_common_position(u, v, |p| {
+                    let deg = self.degree[p];
+                    if deg > 1 {
+                        total += 1.0 / (deg as f64).ln();
+                    }
+                });
+                total
+            }
+            LinkScore::ResourceAllocation => {
+                let mut total = 0.0;
+                self.for_each_common_position(u, v, |p| {
+                    let deg = self.degree[p];
+                    if deg > 0 {
+                        total += 1.0 / deg as f64;
+                    }
+                });
+                total
+            }
+            LinkScore::PreferentialAttachment => (self.degree(u) * self.degree(v)) as f64,
+        }
+    }
+
+    /// Scores every pair in `ebunch`, or every unordered pair of nodes when it is `None`, in
+    /// the same shape as the other `links` scorers.
+    pub fn score_pairs(
+        &self,
+        ebunch: Option<&[(NodeId, NodeId)]>,
+        metric: LinkScore,
+    ) -> Vec<((NodeId, NodeId), f64)> {
+        if ebunch.is_none() && self.symmetric && metric != LinkScore::PreferentialAttachment {
+            return self.score_all_two_hop(metric);
+        }
+        self.map_pairs(ebunch, |u, v| self.score(u, v, metric))
+    }
+
+    /// Scores all unordered pairs by walking two-hop paths from each node, so the work is
+    /// proportional to the number of paths `u - w - v` rather than to the number of pairs
+    /// times the degree. Most pairs of a sparse graph share no neighbor and only cost the
+    /// write of a zero. Requires symmetric neighbor lists. Common neighbors are visited in
+    /// increasing position, as in the merge, so the sums come out bit-for-bit the same.
+    fn score_all_two_hop(&self, metric: LinkScore) -> Vec<((NodeId, NodeId), f64)> {
+        let n = self.ids.len();
+        let mut results = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+        let mut count = vec![0usize; n];
+        let mut sum = vec![0.0f64; n];
+        let mut touched: Vec<usize> = Vec::new();
+        for i in 0..n {
+            let own = &self.targets[self.offsets[i]..self.offsets[i + 1]];
+            for &w in own {
+                let w = w as usize;
+                let term = match metric {
+                    LinkScore::AdamicAdar if self.degree[w] > 1 => {
+                        1.0 / (self.degree[w] as f64).ln()
+                    }
+                    LinkScore::ResourceAllocation if self.degree[w] > 0 => {
+                        1.0 / self.degree[w] as f64
+                    }
+                    _ => 0.0,
+                };
+                for &x in &self.targets[self.offsets[w]..self.offsets[w + 1]] {
+                    let x = x as usize;
+                    if x > i {
+                        if count[x] == 0 {
+                            touched.push(x);
+                        }
+                        count[x] += 1;
+                        sum[x] += term;
+                    }
+                }
+            }
+            let u = self.ids[i];
+            for j in i + 1..n {
+                let score = match metric {
+                    LinkScore::CommonNeighbors => count[j] as f64,
+                    LinkScore::Jaccard => {
+                        let union = own.len() + (self.offsets[j + 1] - self.offsets[j]) - count[j];
+                        if union > 0 {
+                            count[j] as f64 / union as f64
+                        } else {
+                            0.0
+                        }
+                    }
+                    _ => sum[j],
+                };
+                results.push(((u, self.ids[j]), score));
+            }
+            for x in touched.drain(..) {
+                count[x] = 0;
+                sum[x] = 0.0;
+            }
+        }
+        results
+    }
+
+    /// Applies `score` to every pair in `ebunch`, or to every unordered pair of nodes.
+    pub(crate) fn map_pairs(
+        &self,
+        ebunch: Option<&[(NodeId, NodeId)]>,
+        mut score: impl FnMut(NodeId, NodeId) -> f64,
+    ) -> Vec<((NodeId, NodeId), f64)> {
+        match ebunch {
+            Some(pairs) => pairs.iter().map(|&(u, v)| ((u, v), score(u, v))).collect(),
+            None => {
+                let n = self.ids.len();
+                let mut results = Vec::with_capacity(n * n.saturating_sub(1) / 2);
+                for (i, &u) in self.ids.iter().enumerate() {
+                    for &v in &self.ids[i + 1..] {
+                        results.push(((u, v), score(u, v)));
+                    }
+                }
+                results
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_all_pairs_match_explicit_pairs() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[
+            (0, 1),
+            (0, 2),
+            (1, 2),
+            (1, 2),
+            (2, 3),
+            (3, 4),
+            (4, 4),
+            (1, 4),
+        ] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        g.remove_node(n[5]);
+        let index = NeighborIndex::new(&g);
+        let pairs: Vec<(NodeId, NodeId)> = index
+            .score_pairs(None, LinkScore::CommonNeighbors)
+            .into_iter()
+            .map(|(pair, _)| pair)
+            .collect();
+        assert_eq!(pairs.len(), 10);
+        for metric in [
+            LinkScore::CommonNeighbors,
+            LinkScore::Jaccard,
+            LinkScore::AdamicAdar,
+            LinkScore::ResourceAllocation,
+        ] {
+            assert_eq!(
+                index.score_pairs(None, metric),
+                index.score_pairs(Some(&pairs), metric)
+            );
+        }
+        // Node 2 has degree 4 because of the parallel edge, but is one neighbor of 0.
+        assert_eq!(index.degree(n[2]), 4);
+        assert_eq!(index.score(n[0], n[1], LinkScore::Jaccard), 0.25);
+        assert_eq!(index.degree(n[5]), 0);
+
+        // Directed graphs compare successors.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..3).map(|_| d.add_node(())).collect();
+        d.add_edge(m[0], m[2], 1.0);
+        d.add_edge(m[1], m[2], 1.0);
+        let index = NeighborIndex::new(&d);
+        let all = index.score_pairs(None, LinkScore::CommonNeighbors);
+        assert_eq!(all[0], ((m[0], m[1]), 1.0));
+        assert_eq!(all[2], ((m[1], m[2]), 0.0));
+    }
+}
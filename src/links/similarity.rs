@@ -2,6 +2,7 @@
 //!
 //! Similarity-based link prediction algorithms.
 
+use super::neighborhood::{LinkScore, NeighborIndex};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
 use std::collections::HashSet;
 
@@ -9,21 +10,6 @@ use std::collections::HashSet;
 /// SipHash's DoS resistance, and the default hasher dominated the per-pair cost.
 type NodeSet = HashSet<NodeId, rustc_hash::FxBuildHasher>;
 
-/// Helper: If no ebunch is provided, generate all unordered pairs of nodes.
-fn default_ebunch<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<(NodeId, NodeId)>
-where
-    Ty: crate::core::types::GraphConstructor<A, W>,
-{
-    let nodes: Vec<NodeId> = graph.nodes().map(|(u, _)| u).collect();
-    let mut ebunch = Vec::new();
-    for i in 0..nodes.len() {
-        for j in (i + 1)..nodes.len() {
-            ebunch.push((nodes[i], nodes[j]));
-        }
-    }
-    ebunch
-}
-
 /// Jaccard Coefficient
 /// For each pair (u, v), Jaccard = |N(u) ∩ N(v)| / |N(u) ∪ N(v)|
 pub fn jaccard_coefficient<A, Ty>(
@@ -33,25 +19,7 @@ pub fn jaccard_coefficient<A, Ty>(
 where
     Ty: GraphConstructor<A, f64>,
 {
-    let pairs = match ebunch {
-        Some(p) => p.to_vec(),
-        None => default_ebunch(graph),
-    };
-    let mut results = Vec::with_capacity(pairs.len());
-    for (u, v) in pairs {
-        let set_u: NodeSet = graph.neighbors(u).collect();
-        let set_v: NodeSet = graph.neighbors(v).collect();
-        let intersection = set_u.intersection(&set_v).count();
-        // |A ∪ B| = |A| + |B| - |A ∩ B|, so the union size needs no second pass.
-        let union = set_u.len() + set_v.len() - intersection;
-        let score = if union > 0 {
-            intersection as f64 / union as f64
-        } else {
-            0.0
-        };
-        results.push(((u, v), score));
-    }
-    results
+    NeighborIndex::new(graph).score_pairs(ebunch, LinkScore::Jaccard)
 }
 
 /// Adamic–Adar Index
@@ -63,33 +31,14 @@ pub fn adamic_adar_index<A, Ty>(
 where
     Ty: GraphConstructor<A, f64>,
 {
-    let pairs = match ebunch {
-        Some(p) => p.to_vec(),
-        None => default_ebunch(graph),
-    };
-    let mut results = Vec::with_capacity(pairs.len());
-    for (u, v) in pairs {
-        let set_u: NodeSet = graph.neighbors(u).collect();
-        let set_v: NodeSet = graph.neighbors(v).collect();
-        // Sum over the common neighbors directly, without collecting them first.
-        let score: f64 = set_u
-            .intersection(&set_v)
-            .filter_map(|w| {
-                let deg = graph.neighbors(*w).count();
-                if deg > 1 {
-                    Some(1.0 / (deg as f64).ln())
-                } else {
-                    None
-                }
-            })
-            .sum();
-        results.push(((u, v), score));
-    }
-    results
+    NeighborIndex::new(graph).score_pairs(ebunch, LinkScore::AdamicAdar)
 }
 
 /// Common Neighbors
 /// For a pair (u, v), returns the number of common neighbors.
+///
+/// This scores a single pair without building a [`NeighborIndex`]; use the index to count
+/// common neighbors for many pairs.
 pub fn common_neighbors<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, u: NodeId, v: NodeId) -> usize
 where
     Ty: GraphConstructor<A, W>,
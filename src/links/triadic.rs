@@ -0,0 +1,250 @@
+//! Triadic closure link prediction.
+//!
+//! Triadic closure is the tendency of two people with a friend in common to become friends
+//! themselves. This module scores each open triad `u - w - v` by the chance that `w`
+//! introduces `u` and `v`, and combines the triads of a pair into a closure probability.
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+type Ties = HashMap<NodeId, f64, rustc_hash::FxBuildHasher>;
+
+/// Undirected tie strengths, summing parallel and reciprocal edges. Self-loops and edges
+/// with non-positive or non-finite weights carry no tie.
+fn tie_strengths<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> NodeMap<Ties>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let mut ties: NodeMap<Ties> = graph.node_ids().map(|n| (n, Ties::default())).collect();
+    for (u, v, &w) in graph.edges() {
+        if u == v || !w.is_finite() || w <= 0.0 {
+            continue;
+        }
+        *ties.entry(u).or_default().entry(v).or_insert(0.0) += w;
+        *ties.entry(v).or_default().entry(u).or_insert(0.0) += w;
+    }
+    ties
+}
+
+/// Probability that `w`, introducing one pair of its contacts with each contact drawn in
+/// proportion to tie strength and without replacement, introduces the contacts at strengths
+/// `a` and `b`. It is `1 / C(k, 2)` on an unweighted node of degree `k`.
+fn introduction_probability(a: f64, b: f64, strength: f64) -> f64 {
+    let p = a * b / strength * (1.0 / (strength - a) + 1.0 / (strength - b));
+    p.clamp(0.0, 1.0)
+}
+
+fn total_strength(ties: &Ties) -> f64 {
+    ties.values().sum()
+}
+
+/// Triadic closure probability.
+///
+/// For each pair (u, v), every common neighbor `w` introduces `u` and `v` with the
+/// probability of drawing them as a pair from its contacts in proportion to tie strength,
+/// and the pair closes if any of them does: `P = 1 - prod_w (1 - p_w(u, v))`. A common
+/// neighbor with only `u` and `v` as contacts closes the pair for certain, while a
+/// well-connected hub contributes little. Scores lie in `[0, 1]`, and pairs without common
+/// neighbors score `0.0`.
+///
+/// Edges with non-positive or non-finite weights and self-loops are ignored, and parallel
+/// and reciprocal edges add up into one tie.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::links::triadic::triadic_closure;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let [a, b, hub] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, hub, 1.0);
+/// g.add_edge(b, hub, 1.0);
+///
+/// // The hub knows nobody else, so it is certain to introduce a and b.
+/// let scores = triadic_closure(&g, Some(&[(a, b)]));
+/// assert_eq!(scores[0].1, 1.0);
+/// ```
+pub fn triadic_closure<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    ebunch: Option<&[(NodeId, NodeId)]>,
+) -> Vec<((NodeId, NodeId), f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let ties = tie_strengths(graph);
+    let strength: NodeMap<f64> = ties.iter().map(|(&n, t)| (n, total_strength(t))).collect();
+    let empty = Ties::default();
+    let score = |u: NodeId, v: NodeId| -> f64 {
+        if u == v {
+            return 0.0;
+        }
+        let tu = ties.get(&u).unwrap_or(&empty);
+        let tv = ties.get(&v).unwrap_or(&empty);
+        let (small, large) = if tu.len() <= tv.len() {
+            (tu, tv)
+        } else {
+            (tv, tu)
+        };
+        let mut open = 1.0;
+        for (w, &a) in small {
+            if let Some(&b) = large.get(w) {
+                open *= 1.0 - introduction_probability(a, b, strength[w]);
+            }
+        }
+        1.0 - open
+    };
+    match ebunch {
+        Some(pairs) => pairs.iter().map(|&(u, v)| ((u, v), score(u, v))).collect(),
+        None => {
+            let nodes: Vec<NodeId> = graph.node_ids().collect();
+            let mut results = Vec::new();
+            for i in 0..nodes.len() {
+                for &v in &nodes[i + 1..] {
+                    results.push(((nodes[i], v), score(nodes[i], v)));
+                }
+            }
+            results
+        }
+    }
+}
+
+/// Closure probabilities of `node` with every non-adjacent node two hops away, best first.
+fn candidates(ties: &NodeMap<Ties>, strength: &NodeMap<f64>, node: NodeId) -> Vec<(NodeId, f64)> {
+    let Some(own) = ties.get(&node) else {
+        return Vec::new();
+    };
+    let mut open: HashMap<NodeId, f64, rustc_hash::FxBuildHasher> = HashMap::default();
+    for (w, &a) in own {
+        for (&v, &b) in &ties[w] {
+            if v != node && !own.contains_key(&v) {
+                *open.entry(v).or_insert(1.0) *= 1.0 - introduction_probability(a, b, strength[w]);
+            }
+        }
+    }
+    let mut ranked: Vec<(NodeId, f64)> = open.into_iter().map(|(v, p)| (v, 1.0 - p)).collect();
+    ranked.sort_unstable_by(|x, y| match y.1.total_cmp(&x.1) {
+        Ordering::Equal => x.0.index().cmp(&y.0.index()),
+        other => other,
+    });
+    ranked
+}
+
+/// The `k` best new neighbors for `node` by [`triadic_closure`] probability.
+///
+/// Candidates are the nodes two hops away that are not already adjacent to `node`. They are
+/// returned from most to least likely, with ties broken by node index, so the output is
+/// deterministic. A node that is not in the graph, or that has no open triads, gets an
+/// empty list.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::links::triadic::triadic_top_k;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let [me, friend, fof, busy, stranger] = [(); 5].map(|_| g.add_node(()));
+/// g.add_edge(me, friend, 1.0);
+/// g.add_edge(friend, fof, 1.0);
+/// g.add_edge(me, busy, 1.0);
+/// g.add_edge(busy, stranger, 1.0);
+/// g.add_edge(busy, fof, 1.0);
+///
+/// let picks = triadic_top_k(&g, me, 1);
+/// assert_eq!(picks[0].0, fof);
+/// ```
+pub fn triadic_top_k<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    node: NodeId,
+    k: usize,
+) -> Vec<(NodeId, f64)>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let ties = tie_strengths(graph);
+    let strength: NodeMap<f64> = ties.iter().map(|(&n, t)| (n, total_strength(t))).collect();
+    let mut ranked = candidates(&ties, &strength, node);
+    ranked.truncate(k);
+    ranked
+}
+
+/// [`triadic_top_k`] for every node, sharing the tie strengths across nodes.
+///
+/// Nodes without open triads map to an empty list.
+pub fn triadic_top_k_all<A, Ty>(
+    graph: &BaseGraph<A, f64, Ty>,
+    k: usize,
+) -> NodeMap<Vec<(NodeId, f64)>>
+where
+    Ty: GraphConstructor<A, f64>,
+{
+    let ties = tie_strengths(graph);
+    let strength: NodeMap<f64> = ties.iter().map(|(&n, t)| (n, total_strength(t))).collect();
+    graph
+        .node_ids()
+        .map(|node| {
+            let mut ranked = candidates(&ties, &strength, node);
+            ranked.truncate(k);
+            (node, ranked)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_triadic_closure_weights_and_hubs() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        // Hub 2 links 0, 1, 3, and 4; 5 is isolated.
+        for &leaf in &[0, 1, 3, 4] {
+            g.add_edge(n[2], n[leaf], 1.0);
+        }
+        let scores = triadic_closure(&g, Some(&[(n[0], n[1]), (n[0], n[5]), (n[0], n[0])]));
+        // An unweighted hub of degree 4 introduces any pair with probability 1/6.
+        assert!((scores[0].1 - 1.0 / 6.0).abs() < 1e-12);
+        assert_eq!(scores[1].1, 0.0);
+        assert_eq!(scores[2].1, 0.0);
+        assert_eq!(triadic_closure(&g, None).len(), 15);
+
+        // A second, private common neighbor closes the pair for certain.
+        g.add_edge(n[0], n[5], 1.0);
+        g.add_edge(n[1], n[5], 1.0);
+        assert_eq!(triadic_closure(&g, Some(&[(n[0], n[1])]))[0].1, 1.0);
+
+        // Stronger ties to the hub make their pair more likely to close.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..4).map(|_| d.add_node(())).collect();
+        d.add_edge(m[0], m[3], 3.0);
+        d.add_edge(m[3], m[1], 3.0);
+        d.add_edge(m[3], m[2], 1.0);
+        let s = triadic_closure(&d, Some(&[(m[0], m[1]), (m[0], m[2])]));
+        assert!(s[0].1 > s[1].1);
+    }
+
+    #[test]
+    fn test_triadic_top_k_ranks_open_triads() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[0], n[2], 1.0);
+        g.add_edge(n[1], n[3], 1.0);
+        g.add_edge(n[2], n[3], 1.0);
+        g.add_edge(n[2], n[4], 1.0);
+
+        let top = triadic_top_k(&g, n[0], 5);
+        let order: Vec<NodeId> = top.iter().map(|&(v, _)| v).collect();
+        assert_eq!(order, vec![n[3], n[4]]);
+        assert_eq!(triadic_top_k(&g, n[0], 1).len(), 1);
+
+        let all = triadic_top_k_all(&g, 2);
+        assert_eq!(all[&n[0]], top);
+        let from_3: Vec<NodeId> = all[&n[3]].iter().map(|&(v, _)| v).collect();
+        assert_eq!(from_3, vec![n[0], n[4]]);
+    }
+}
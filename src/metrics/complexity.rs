@@ -0,0 +1,200 @@
+/*!
+# Entropy and Complexity
+
+Single-number summaries of how heterogeneous a graph's structure is, for comparing the members
+of a network ensemble: two graphs with the same size and density can still differ sharply in
+how their degrees and connections are spread.
+
+All entropies use the natural logarithm, so they are measured in nats.
+*/
+
+use std::collections::HashMap;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor};
+use petgraph::EdgeType;
+
+use super::spectrum::laplacian_spectrum;
+
+/// Shannon entropy `-Σ p ln p` of a distribution given by non-negative counts, `0.0` when empty.
+fn shannon<I: IntoIterator<Item = f64>>(counts: I) -> f64 {
+    let counts: Vec<f64> = counts.into_iter().filter(|&c| c > 0.0).collect();
+    let total: f64 = counts.iter().sum();
+    if total <= 0.0 {
+        return 0.0;
+    }
+    counts
+        .iter()
+        .map(|&c| {
+            let p = c / total;
+            -p * p.ln()
+        })
+        .sum()
+}
+
+/// Computes the entropy of the degree distribution, `-Σ P(k) ln P(k)` over the fraction
+/// `P(k)` of nodes with degree `k`.
+///
+/// It is `0.0` for a regular graph and grows as the degrees spread out, up to `ln V` if every
+/// node had a different degree. Directed graphs use the total degree (in plus out). An
+/// empty graph gives `0.0`.
+///
+/// # Time Complexity
+/// O(V + E)
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::degree_entropy;
+///
+/// // A star on four nodes has one node of degree 3 and three of degree 1.
+/// let mut g = Graph::<(), f64>::new();
+/// let hub = g.add_node(());
+/// for _ in 0..3 {
+///     let leaf = g.add_node(());
+///     g.add_edge(hub, leaf, 1.0);
+/// }
+///
+/// let expected = -(0.25_f64 * 0.25_f64.ln() + 0.75 * 0.75_f64.ln());
+/// assert!((degree_entropy(&g) - expected).abs() < 1e-12);
+/// ```
+pub fn degree_entropy<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> f64
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut histogram: HashMap<usize, usize> = HashMap::new();
+    for node in graph.node_ids() {
+        *histogram
+            .entry(graph.degree(node).unwrap_or(0))
+            .or_insert(0) += 1;
+    }
+    shannon(histogram.into_values().map(|count| count as f64))
+}
+
+/// Computes the von Neumann entropy of an undirected graph, `-Σ λ ln λ` over the eigenvalues
+/// `λ` of the density matrix `L / tr(L)`, where `L` is the weighted Laplacian.
+///
+/// This spectral entropy treats the scaled Laplacian as a quantum state. It is `ln(V - 1)`
+/// for the complete graph, the largest value among graphs on `V` nodes, and lower for graphs
+/// whose Laplacian spectrum is concentrated on a few eigenvalues, such as stars. Parallel edges
+/// add up and self-loops are ignored, as in [`laplacian_spectrum`].
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` for a directed or empty graph, or one whose edges have zero total
+/// weight, since its density matrix is undefined.
+///
+/// # Time Complexity
+/// O(V³) for the dense eigendecomposition
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::von_neumann_entropy;
+///
+/// // The Laplacian of K4 has eigenvalues 0, 4, 4, 4.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// for i in 0..4 {
+///     for j in i + 1..4 {
+///         g.add_edge(n[i], n[j], 1.0);
+///     }
+/// }
+///
+/// assert!((von_neumann_entropy(&g).unwrap() - 3.0_f64.ln()).abs() < 1e-9);
+/// ```
+pub fn von_neumann_entropy<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<f64>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let spectrum = laplacian_spectrum(graph, None)?;
+    // Round-off can leave tiny negative eigenvalues; they carry no weight.
+    let spectrum: Vec<f64> = spectrum.into_iter().map(|l| l.max(0.0)).collect();
+    let trace: f64 = spectrum.iter().sum();
+    if trace <= 0.0 {
+        return Err(GraphinaError::invalid_graph(
+            "Von Neumann entropy needs edges of positive total weight",
+        ));
+    }
+    Ok(shannon(spectrum))
+}
+
+/// Computes the offdiagonal complexity of Claussen (2007).
+///
+/// Count the edges `c(k, l)` between nodes of degree `k` and degree `l >= k`, sum each
+/// diagonal of that matrix into `a(n) = Σ_k c(k, k + n)`, and return the entropy of the
+/// normalized `a`. Regular graphs score `0.0`, since every edge joins nodes of equal degree;
+/// graphs whose edges span many different degree gaps, such as hierarchical and scale-free
+/// networks, score higher. Edge weights and directions are ignored, directed graphs use the
+/// total degree, and a graph without edges gives `0.0`.
+///
+/// # Time Complexity
+/// O(V + E)
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::offdiagonal_complexity;
+///
+/// // Every edge of a cycle joins two nodes of degree 2.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// for i in 0..5 {
+///     g.add_edge(n[i], n[(i + 1) % 5], 1.0);
+/// }
+///
+/// assert_eq!(offdiagonal_complexity(&g), 0.0);
+/// ```
+pub fn offdiagonal_complexity<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> f64
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut diagonals: HashMap<usize, usize> = HashMap::new();
+    for (u, v, _) in graph.edges() {
+        let ku = graph.degree(u).unwrap_or(0);
+        let kv = graph.degree(v).unwrap_or(0);
+        *diagonals.entry(ku.abs_diff(kv)).or_insert(0) += 1;
+    }
+    shannon(diagonals.into_values().map(|count| count as f64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_entropy_and_complexity_measures() {
+        // A path 0-1-2-3 has degrees 1, 2, 2, 1 and edges with degree differences 1, 0, 1.
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        for i in 0..3 {
+            g.add_edge(n[i], n[i + 1], 1.0);
+        }
+        assert!((degree_entropy(&g) - 2.0_f64.ln()).abs() < 1e-12);
+        let third = 1.0_f64 / 3.0;
+        let split = -(third * third.ln() + (1.0 - third) * (1.0 - third).ln());
+        assert!((offdiagonal_complexity(&g) - split).abs() < 1e-12);
+
+        // The path's Laplacian eigenvalues are 2 - 2cos(kπ/4) for k = 0..3, with trace 6.
+        let expected: f64 = (1..4)
+            .map(|k| {
+                let p = (2.0 - 2.0 * (k as f64 * std::f64::consts::PI / 4.0).cos()) / 6.0;
+                -p * p.ln()
+            })
+            .sum();
+        assert!((von_neumann_entropy(&g).unwrap() - expected).abs() < 1e-9);
+
+        let mut edgeless = Graph::<(), f64>::new();
+        edgeless.add_node(());
+        assert_eq!(degree_entropy(&edgeless), 0.0);
+        assert_eq!(offdiagonal_complexity(&edgeless), 0.0);
+        assert!(von_neumann_entropy(&edgeless).is_err());
+        assert_eq!(degree_entropy(&Graph::<(), f64>::new()), 0.0);
+        assert!(von_neumann_entropy(&Digraph::<(), f64>::new()).is_err());
+    }
+}
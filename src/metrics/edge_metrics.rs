@@ -0,0 +1,201 @@
+/*!
+# Edge-level Metrics
+
+Tie-strength measures from social network analysis, one value per edge.
+
+All functions ignore edge direction and weights, and skip self-loops when collecting
+neighbors. Parallel edges between the same endpoints get the same value.
+*/
+
+use crate::core::types::{BaseGraph, EdgeMap, GraphConstructor, NodeId};
+use petgraph::EdgeType;
+use std::collections::{HashMap, HashSet};
+
+/// Undirected neighbor sets of every node, without self-loops.
+fn undirected_neighbors<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> HashMap<NodeId, HashSet<NodeId>> {
+    let mut adj: HashMap<NodeId, HashSet<NodeId>> =
+        graph.node_ids().map(|n| (n, HashSet::new())).collect();
+    for (u, v, _) in graph.edges() {
+        if u != v {
+            adj.entry(u).or_default().insert(v);
+            adj.entry(v).or_default().insert(u);
+        }
+    }
+    adj
+}
+
+/// Scores every edge from the common neighbors of its endpoints, computing each endpoint pair
+/// once so that parallel edges share the work.
+fn per_edge<A, W, Ty, F>(graph: &BaseGraph<A, W, Ty>, mut score: F) -> EdgeMap<f64>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: FnMut(&HashMap<NodeId, HashSet<NodeId>>, NodeId, NodeId, &[NodeId]) -> f64,
+{
+    let adj = undirected_neighbors(graph);
+    let mut cache: HashMap<(NodeId, NodeId), f64> = HashMap::new();
+    let mut result = EdgeMap::default();
+    for (edge, u, v, _) in graph.edges_with_ids() {
+        let value = *cache.entry((u, v)).or_insert_with(|| {
+            if u == v {
+                return 0.0;
+            }
+            let common: Vec<NodeId> = adj[&u].intersection(&adj[&v]).copied().collect();
+            score(&adj, u, v, &common)
+        });
+        result.insert(edge, value);
+    }
+    result
+}
+
+/// Embeddedness of every edge: the number of neighbors its endpoints share.
+///
+/// A self-loop has embeddedness 0.
+///
+/// # Time Complexity
+/// O(m * d) where d is the maximum degree
+pub fn embeddedness<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> EdgeMap<f64> {
+    per_edge(graph, |_, _, _, common| common.len() as f64)
+}
+
+/// Neighborhood overlap of every edge `(u, v)`: the shared neighbors divided by the nodes
+/// adjacent to `u` or `v`, not counting `u` and `v` themselves.
+///
+/// Values lie in `[0, 1]`. An edge whose endpoints have no other neighbors, such as an
+/// isolated edge or a self-loop, has overlap 0.
+///
+/// # Time Complexity
+/// O(m * d) where d is the maximum degree
+pub fn neighborhood_overlap<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> EdgeMap<f64> {
+    per_edge(graph, |adj, u, v, common| {
+        // Each endpoint is in the other's neighbor set.
+        let union = adj[&u].len() + adj[&v].len() - common.len() - 2;
+        if union == 0 {
+            0.0
+        } else {
+            common.len() as f64 / union as f64
+        }
+    })
+}
+
+/// Dispersion of every edge `(u, v)`, following Backstrom and Kleinberg (2014).
+///
+/// Dispersion counts the pairs of shared neighbors `s` and `t` that are not adjacent and
+/// have no common neighbor among `u`'s neighbors other than `u` and `v`. High dispersion
+/// means the shared friends come from otherwise separate parts of `u`'s network, a strong
+/// signal for romantic and family ties. The measure is not symmetric: `u` is the source of
+/// the edge, or the first endpoint passed to `add_edge` for an undirected graph. With `normalized`, the count is divided by the
+/// embeddedness of the edge; edges with no shared neighbors have dispersion 0 either way.
+///
+/// # Time Complexity
+/// O(m * d³) where d is the maximum degree
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::edge_metrics::dispersion;
+///
+/// // u and v share the friends a and b, who otherwise have nothing in common.
+/// let mut g = Graph::<(), ()>::new();
+/// let [u, v, a, b] = [(); 4].map(|_| g.add_node(()));
+/// let tie = g.add_edge(u, v, ());
+/// for friend in [a, b] {
+///     g.add_edge(u, friend, ());
+///     g.add_edge(v, friend, ());
+/// }
+///
+/// assert_eq!(dispersion(&g, false)[&tie], 1.0);
+/// assert_eq!(dispersion(&g, true)[&tie], 0.5);
+/// ```
+pub fn dispersion<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+    normalized: bool,
+) -> EdgeMap<f64> {
+    per_edge(graph, |adj, u, v, common| {
+        let ego = &adj[&u];
+        let mut total = 0usize;
+        for (i, &s) in common.iter().enumerate() {
+            let s_nbrs = &adj[&s];
+            for &t in &common[i + 1..] {
+                if s_nbrs.contains(&t) {
+                    continue;
+                }
+                let t_nbrs = &adj[&t];
+                let linked = s_nbrs
+                    .iter()
+                    .any(|w| *w != u && *w != v && ego.contains(w) && t_nbrs.contains(w));
+                if !linked {
+                    total += 1;
+                }
+            }
+        }
+        if normalized && !common.is_empty() {
+            total as f64 / common.len() as f64
+        } else {
+            total as f64
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_embeddedness_and_overlap() {
+        // Triangle 0-1-2 with a pendant 3 on node 0.
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        let e01 = g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 1.0);
+        g.add_edge(n[2], n[0], 1.0);
+        let e03 = g.add_edge(n[0], n[3], 1.0);
+
+        let emb = embeddedness(&g);
+        assert_eq!(emb[&e01], 1.0);
+        assert_eq!(emb[&e03], 0.0);
+        let overlap = neighborhood_overlap(&g);
+        // N(0) ∪ N(1) without 0 and 1 is {2, 3}, and only 2 is shared.
+        assert_eq!(overlap[&e01], 0.5);
+        assert_eq!(overlap[&e03], 0.0);
+
+        // Direction is ignored.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..3).map(|_| d.add_node(())).collect();
+        let a = d.add_edge(m[0], m[1], 1.0);
+        d.add_edge(m[2], m[0], 1.0);
+        d.add_edge(m[2], m[1], 1.0);
+        assert_eq!(embeddedness(&d)[&a], 1.0);
+    }
+
+    #[test]
+    fn test_dispersion_ignores_connected_friends() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+        let (u, v) = (n[0], n[1]);
+        let tie = g.add_edge(u, v, 1.0);
+        for &f in &n[2..] {
+            g.add_edge(u, f, 1.0);
+            g.add_edge(v, f, 1.0);
+        }
+        // All three shared friends are separate: three dispersed pairs.
+        assert_eq!(dispersion(&g, false)[&tie], 3.0);
+        // Linking two of them removes that pair.
+        g.add_edge(n[2], n[3], 1.0);
+        assert_eq!(dispersion(&g, false)[&tie], 2.0);
+        // A shared contact of the last two inside u's network removes theirs.
+        let w = g.add_node(());
+        g.add_edge(u, w, 1.0);
+        g.add_edge(n[3], w, 1.0);
+        g.add_edge(n[4], w, 1.0);
+        assert_eq!(dispersion(&g, false)[&tie], 1.0);
+        assert!((dispersion(&g, true)[&tie] - 1.0 / 3.0).abs() < 1e-12);
+    }
+}
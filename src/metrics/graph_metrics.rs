@@ -5,8 +5,11 @@ Graph-level metrics and statistics.
 */
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Debug;
 
-use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use crate::core::error::Result;
+use crate::core::paths::dijkstra_path_impl;
+use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeId};
 use petgraph::EdgeType;
 
 /// Orders a pair of node indices as `(low, high)`, the canonical key form for the
@@ -302,6 +305,176 @@ pub fn assortativity<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
     numerator / denominator
 }
 
+/// Which nodes the weighted distance metrics measure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DistanceScope {
+    /// The whole graph; a graph that is not connected has no finite value, so the metric is
+    /// `None`.
+    #[default]
+    Whole,
+    /// The largest connected component (strongly connected on directed graphs), so a
+    /// disconnected graph still gets a value. Ties go to the component holding the node with
+    /// the lowest index.
+    LargestComponent,
+}
+
+/// Per-source eccentricity and total distance over the nodes in `scope`.
+///
+/// Returns `None` for an empty graph, or for [`DistanceScope::Whole`] when some node cannot
+/// reach another. Shortest paths between members of a strongly connected component never
+/// leave it, so the largest component is measured by searching the whole graph from its
+/// members and ignoring distances to other nodes.
+fn weighted_distance_profile<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    scope: DistanceScope,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<Vec<(f64, f64)>>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    if graph.is_empty() {
+        return Ok(None);
+    }
+    let members: Vec<NodeId> = match scope {
+        DistanceScope::Whole => graph.node_ids().collect(),
+        DistanceScope::LargestComponent => {
+            let lowest = |c: &Vec<NodeId>| c.iter().map(|n| n.index()).min();
+            petgraph::algo::tarjan_scc(graph.as_petgraph())
+                .into_iter()
+                .map(|c| c.into_iter().map(NodeId::new).collect::<Vec<NodeId>>())
+                .max_by(|a, b| {
+                    a.len()
+                        .cmp(&b.len())
+                        .then_with(|| lowest(b).cmp(&lowest(a)))
+                })
+                .unwrap_or_default()
+        }
+    };
+    let mut profile = Vec::with_capacity(members.len());
+    for &source in &members {
+        let (distances, _) = dijkstra_path_impl(graph, source, None, &eval_cost)?;
+        let (mut eccentricity, mut total) = (0.0_f64, 0.0);
+        for target in &members {
+            match distances.get(target).copied().flatten() {
+                Some(d) => {
+                    eccentricity = eccentricity.max(d);
+                    total += d;
+                }
+                None => return Ok(None),
+            }
+        }
+        profile.push((eccentricity, total));
+    }
+    Ok(Some(profile))
+}
+
+/// Computes the weighted diameter: the largest shortest-path distance between two nodes.
+///
+/// Edge costs come from `eval_cost` as in [`dijkstra_path_impl`]: return the cost of an edge,
+/// or `None` to leave it out. With [`DistanceScope::Whole`] a graph that is not connected
+/// (strongly connected if directed) gives `None`; with [`DistanceScope::LargestComponent`]
+/// only its largest component is measured. An empty graph gives `None`.
+///
+/// # Errors
+/// Returns an error if a cost is negative, as [`dijkstra_path_impl`] does.
+///
+/// # Time Complexity
+/// O(V * (E + V) log V) - Runs Dijkstra from each node
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::{DistanceScope, weighted_diameter};
+///
+/// let mut g = Graph::<i32, f64>::new();
+/// let n1 = g.add_node(1);
+/// let n2 = g.add_node(2);
+/// let n3 = g.add_node(3);
+/// let n4 = g.add_node(4);
+/// g.add_edge(n1, n2, 1.5);
+/// g.add_edge(n2, n3, 2.0);
+///
+/// assert_eq!(weighted_diameter(&g, DistanceScope::Whole, |w| Some(*w)).unwrap(), None);
+/// let largest = weighted_diameter(&g, DistanceScope::LargestComponent, |w| Some(*w));
+/// assert_eq!(largest.unwrap(), Some(3.5));
+/// ```
+pub fn weighted_diameter<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    scope: DistanceScope,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<f64>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    Ok(weighted_distance_profile(graph, scope, eval_cost)?
+        .map(|profile| profile.iter().map(|&(e, _)| e).fold(0.0, f64::max)))
+}
+
+/// Computes the weighted radius: the smallest weighted eccentricity of any node.
+///
+/// Takes the same arguments, and treats disconnected graphs the same way, as
+/// [`weighted_diameter`].
+///
+/// # Time Complexity
+/// O(V * (E + V) log V)
+pub fn weighted_radius<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    scope: DistanceScope,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<f64>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    Ok(
+        weighted_distance_profile(graph, scope, eval_cost)?.map(|profile| {
+            profile
+                .iter()
+                .map(|&(e, _)| e)
+                .fold(f64::INFINITY, f64::min)
+        }),
+    )
+}
+
+/// Computes the weighted average path length: the mean shortest-path distance over all
+/// ordered pairs of distinct nodes.
+///
+/// Takes the same arguments, and treats disconnected graphs the same way, as
+/// [`weighted_diameter`]. A single node gives `Some(0.0)`.
+///
+/// # Time Complexity
+/// O(V * (E + V) log V)
+pub fn weighted_average_path_length<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    scope: DistanceScope,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<f64>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    Ok(
+        weighted_distance_profile(graph, scope, eval_cost)?.map(|profile| {
+            let n = profile.len();
+            if n < 2 {
+                return 0.0;
+            }
+            profile.iter().map(|&(_, total)| total).sum::<f64>() / (n * (n - 1)) as f64
+        }),
+    )
+}
+
 /// Helper function: Computes BFS distances from a start node.
 fn bfs_distances<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
     graph: &BaseGraph<A, W, Ty>,
@@ -477,6 +650,68 @@ mod tests {
         assert!((avg - 1.333).abs() < 0.01);
     }
 
+    #[test]
+    fn test_weighted_distance_metrics() {
+        use super::*;
+        use crate::core::types::{Digraph, Graph};
+
+        // A weighted path 0 -1.0- 1 -2.0- 2 plus a separate heavy pair.
+        let mut g = Graph::<i32, f64>::new();
+        let n: Vec<NodeId> = (0..5).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 2.0);
+        g.add_edge(n[3], n[4], 10.0);
+        let cost = |w: &f64| Some(*w);
+
+        assert_eq!(
+            weighted_diameter(&g, DistanceScope::Whole, cost).unwrap(),
+            None
+        );
+        let scope = DistanceScope::LargestComponent;
+        assert_eq!(weighted_diameter(&g, scope, cost).unwrap(), Some(3.0));
+        assert_eq!(weighted_radius(&g, scope, cost).unwrap(), Some(2.0));
+        // Ordered pairs: 2 * (1 + 3 + 2) / 6.
+        let apl = weighted_average_path_length(&g, scope, cost)
+            .unwrap()
+            .unwrap();
+        assert!((apl - 2.0).abs() < 1e-12);
+
+        // Unit costs reproduce the hop-based metrics once the graph is connected.
+        g.add_edge(n[2], n[3], 1.0);
+        let unit = |_: &f64| Some(1.0);
+        let whole = DistanceScope::Whole;
+        assert_eq!(
+            weighted_diameter(&g, whole, unit).unwrap(),
+            diameter(&g).map(|d| d as f64)
+        );
+        assert_eq!(
+            weighted_radius(&g, whole, unit).unwrap(),
+            radius(&g).map(|r| r as f64)
+        );
+        assert_eq!(
+            weighted_average_path_length(&g, whole, unit).unwrap(),
+            average_path_length(&g)
+        );
+        assert!(weighted_diameter(&g, whole, |w| Some(-*w)).is_err());
+
+        // On a digraph the largest component is strongly connected: the 3-cycle, not the tail.
+        let mut d = Digraph::<i32, f64>::new();
+        let m: Vec<NodeId> = (0..4).map(|i| d.add_node(i)).collect();
+        d.add_edge(m[0], m[1], 1.0);
+        d.add_edge(m[1], m[2], 1.0);
+        d.add_edge(m[2], m[0], 1.0);
+        d.add_edge(m[2], m[3], 1.0);
+        assert_eq!(
+            weighted_diameter(&d, DistanceScope::Whole, cost).unwrap(),
+            None
+        );
+        assert_eq!(weighted_diameter(&d, scope, cost).unwrap(), Some(2.0));
+        assert_eq!(
+            weighted_radius(&Graph::<i32, f64>::new(), scope, cost).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_assortativity() {
         let mut g = Graph::<i32, f64>::new();
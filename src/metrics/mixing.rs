@@ -0,0 +1,242 @@
+/*!
+# Mixing Patterns
+
+How the edges of a graph are spread between kinds of nodes. [`weighted_assortativity`] asks
+whether heavily connected nodes link to each other, using edge weights where
+[`assortativity`](super::assortativity) counts edges. [`mixing_matrix`] groups nodes into
+categories and sums the edge weight between every pair of categories, which shows which groups
+favor or avoid each other rather than only how strong homophily is overall.
+*/
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use petgraph::EdgeType;
+
+/// Computes the weighted degree assortativity of the graph.
+///
+/// Each node's value is its strength, the total weight of its edges, and each edge counts in
+/// proportion to its weight; the result is the weighted Pearson correlation of the strengths at
+/// either end of an edge, counting both orderings of every edge as [`assortativity`] does. With
+/// all weights equal it is the same coefficient as [`assortativity`]. Weights should be
+/// non-negative.
+///
+/// Returns a value between -1 and 1, or `0.0` when it is undefined: no edges, no total weight,
+/// or every edge joining nodes of equal strength.
+///
+/// [`assortativity`]: super::assortativity
+///
+/// # Time Complexity
+/// O(V + E)
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::weighted_assortativity;
+///
+/// // A heavy pair and a light pair: strong nodes link to strong nodes.
+/// let mut g = Graph::<(), f64>::new();
+/// let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 5.0);
+/// g.add_edge(c, d, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// assert!(weighted_assortativity(&g) > 0.0);
+/// ```
+pub fn weighted_assortativity<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> f64
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut strength: NodeMap<f64> = NodeMap::default();
+    for (u, v, &w) in graph.edges() {
+        let w = w.into();
+        *strength.entry(u).or_insert(0.0) += w;
+        *strength.entry(v).or_insert(0.0) += w;
+    }
+
+    let (mut total, mut sum, mut sum_sq, mut sum_prod) = (0.0, 0.0, 0.0, 0.0);
+    for (u, v, &w) in graph.edges() {
+        let w: f64 = w.into();
+        let (j, k) = (strength[&u], strength[&v]);
+        // Both orderings, so both ends share one mean and variance.
+        total += 2.0 * w;
+        sum += w * (j + k);
+        sum_sq += w * (j * j + k * k);
+        sum_prod += 2.0 * w * j * k;
+    }
+    if total <= 0.0 {
+        return 0.0;
+    }
+    let mean = sum / total;
+    let variance = sum_sq / total - mean * mean;
+    if variance <= 0.0 {
+        return 0.0;
+    }
+    ((sum_prod / total - mean * mean) / variance).clamp(-1.0, 1.0)
+}
+
+/// Edge weight between every pair of node categories, from [`mixing_matrix`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixingMatrix<C> {
+    /// The categories, in the order their first node appears in the graph. Row and column `i`
+    /// belong to `categories[i]`.
+    pub categories: Vec<C>,
+    /// `weights[i][j]` is the total weight of edges from category `i` to category `j`.
+    pub weights: Vec<Vec<f64>>,
+}
+
+impl<C: Eq> MixingMatrix<C> {
+    /// Returns the row and column of `category`, if any node has it.
+    pub fn index_of(&self, category: &C) -> Option<usize> {
+        self.categories.iter().position(|c| c == category)
+    }
+
+    /// Returns the weight between two categories, or `0.0` if either does not occur.
+    pub fn weight(&self, from: &C, to: &C) -> f64 {
+        match (self.index_of(from), self.index_of(to)) {
+            (Some(i), Some(j)) => self.weights[i][j],
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the matrix divided by its total, so the entries sum to 1; all zeros when the
+    /// total is zero.
+    pub fn normalized(&self) -> Vec<Vec<f64>> {
+        let total: f64 = self.weights.iter().flatten().sum();
+        self.weights
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&w| if total == 0.0 { 0.0 } else { w / total })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Newman's assortativity coefficient over the categories: `1.0` when every edge stays
+    /// within a category, `0.0` for mixing no different from chance, and negative when edges
+    /// prefer other categories.
+    ///
+    /// Returns `0.0` when it is undefined, as with no edges or a single category.
+    pub fn assortativity(&self) -> f64 {
+        let e = self.normalized();
+        let trace: f64 = (0..e.len()).map(|i| e[i][i]).sum();
+        let chance: f64 = (0..e.len())
+            .map(|i| {
+                let row: f64 = e[i].iter().sum();
+                let column: f64 = e.iter().map(|r| r[i]).sum();
+                row * column
+            })
+            .sum();
+        if chance >= 1.0 {
+            return 0.0;
+        }
+        (trace - chance) / (1.0 - chance)
+    }
+}
+
+/// Sums edge weights between the categories that `category_fn` assigns to nodes.
+///
+/// On a directed graph rows are source categories and columns target categories. On an
+/// undirected graph every edge counts in both directions, so the matrix is symmetric, an edge
+/// within one category adds twice its weight to the diagonal, and the entries add up to twice
+/// the total weight; [`MixingMatrix::normalized`] then gives the fraction of edge ends in each
+/// cell, the form Newman's coefficient is defined on.
+///
+/// # Time Complexity
+/// O(V + E + K²) for `K` categories.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::mixing_matrix;
+///
+/// let mut g = Graph::<&str, f64>::new();
+/// let [a, b, c] = ["red", "red", "blue"].map(|color| g.add_node(color));
+/// g.add_edge(a, b, 2.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let mixing = mixing_matrix(&g, |n| *g.node_attr(n).unwrap());
+/// assert_eq!(mixing.categories, vec!["red", "blue"]);
+/// assert_eq!(mixing.weights, vec![vec![4.0, 1.0], vec![1.0, 0.0]]);
+/// assert_eq!(mixing.weight(&"blue", &"red"), 1.0);
+/// ```
+pub fn mixing_matrix<A, W, Ty, F, C>(graph: &BaseGraph<A, W, Ty>, category_fn: F) -> MixingMatrix<C>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: Fn(NodeId) -> C,
+    C: Eq + Hash + Clone,
+{
+    let mut categories = Vec::new();
+    let mut index: HashMap<C, usize> = HashMap::new();
+    let mut of: NodeMap<usize> = NodeMap::default();
+    for node in graph.node_ids() {
+        let category = category_fn(node);
+        let i = *index.entry(category.clone()).or_insert_with(|| {
+            categories.push(category);
+            categories.len() - 1
+        });
+        of.insert(node, i);
+    }
+
+    let k = categories.len();
+    let mut weights = vec![vec![0.0; k]; k];
+    for (u, v, &w) in graph.edges() {
+        let (i, j, w) = (of[&u], of[&v], w.into());
+        weights[i][j] += w;
+        if !graph.is_directed() {
+            weights[j][i] += w;
+        }
+    }
+    MixingMatrix {
+        categories,
+        weights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+    use crate::metrics::assortativity;
+
+    #[test]
+    fn test_weighted_assortativity_and_mixing_matrix() {
+        let mut g = Graph::<u8, f64>::new();
+        let n: Vec<_> = [0, 0, 0, 1, 1, 2].map(|c| g.add_node(c)).to_vec();
+        for (u, v) in [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 5), (0, 5)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        // Unit weights reduce it to the unweighted coefficient.
+        assert!((weighted_assortativity(&g) - assortativity(&g)).abs() < 1e-12);
+        assert_eq!(weighted_assortativity(&Graph::<(), f64>::new()), 0.0);
+
+        let mixing = mixing_matrix(&g, |v| *g.node_attr(v).unwrap());
+        assert_eq!(mixing.categories, vec![0, 1, 2]);
+        assert_eq!(
+            mixing.weights,
+            vec![
+                vec![6.0, 1.0, 1.0],
+                vec![1.0, 2.0, 1.0],
+                vec![1.0, 1.0, 0.0]
+            ]
+        );
+        let total: f64 = mixing.normalized().iter().flatten().sum();
+        assert!((total - 1.0).abs() < 1e-12);
+        // Trace 8/14 against chance (64 + 16 + 4) / 196.
+        let (trace, chance) = (8.0 / 14.0, 84.0 / 196.0);
+        assert!((mixing.assortativity() - (trace - chance) / (1.0 - chance)).abs() < 1e-12);
+        assert_eq!(mixing.weight(&0, &7), 0.0);
+
+        let mut d = Digraph::<u8, f32>::new();
+        let (x, y) = (d.add_node(0), d.add_node(1));
+        d.add_edge(x, y, 2.5);
+        let directed = mixing_matrix(&d, |v| *d.node_attr(v).unwrap());
+        assert_eq!(directed.weights, vec![vec![0.0, 2.5], vec![0.0, 0.0]]);
+        assert_eq!(directed.weight(&1, &0), 0.0);
+    }
+}
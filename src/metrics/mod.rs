@@ -3,12 +3,31 @@
 //! Graph and node metrics for network analysis.
 //! All metrics depend only on the core module for basic graph operations.
 
+pub mod complexity;
+pub mod edge_metrics;
 pub mod graph_metrics;
+pub mod mixing;
 pub mod node_metrics;
+pub mod significance;
+pub mod spatial;
+pub mod spectrum;
+pub mod symmetry;
+pub mod temporal;
+pub mod vitality;
 
 // Re-export all public functions
+pub use complexity::{degree_entropy, offdiagonal_complexity, von_neumann_entropy};
+pub use edge_metrics::{dispersion, embeddedness, neighborhood_overlap};
 pub use graph_metrics::{
-    assortativity, average_clustering_coefficient, average_path_length, diameter, radius,
-    transitivity,
+    DistanceScope, assortativity, average_clustering_coefficient, average_path_length, diameter,
+    radius, transitivity, weighted_average_path_length, weighted_diameter, weighted_radius,
 };
+pub use mixing::{MixingMatrix, mixing_matrix, weighted_assortativity};
 pub use node_metrics::{clustering_coefficient, triangles};
+pub use significance::{SignificanceResult, significance_test};
+pub use spatial::{detour_index, node_route_factors, route_factor};
+pub use spectrum::{adjacency_spectrum, fiedler_vector, laplacian_spectrum};
+pub use symmetry::{
+    approximate_vertex_orbits, intersection_array, is_distance_regular, is_regular,
+};
+pub use vitality::{ComponentImpact, closeness_vitality, component_impact};
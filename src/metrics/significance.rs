@@ -0,0 +1,161 @@
+/*!
+Significance of a metric against a degree-preserving null model
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::generators::degree_preserving_rewire;
+use crate::core::types::{BaseGraph, GraphConstructor};
+use petgraph::EdgeType;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// How an observed metric compares with its values on randomized graphs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignificanceResult {
+    /// The metric on the original graph.
+    pub observed: f64,
+    /// Mean of the metric over the randomized graphs.
+    pub null_mean: f64,
+    /// Population standard deviation of the metric over the randomized graphs.
+    pub null_std: f64,
+    /// `(observed - null_mean) / null_std`, or `None` when every randomized value is equal.
+    pub z_score: Option<f64>,
+    /// Two-sided empirical p-value: the share of randomized values at least as far from
+    /// `null_mean` as `observed`, counting the observation itself so it is never zero.
+    pub p_value: f64,
+    /// The metric on each randomized graph, in the order they were drawn.
+    pub null_values: Vec<f64>,
+}
+
+/// Tests whether `metric_fn` on `graph` differs from what its degree sequence alone explains.
+///
+/// Each of the `n_random` null graphs is a copy of `graph` randomized with
+/// [`degree_preserving_rewire`], ten swaps per edge, so every node keeps its degree while the
+/// rest of the structure is shuffled. The copies are drawn from seeds derived from `seed`, so
+/// a run is reproducible.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if `n_random` is zero or the metric returns NaN on any graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::generators::watts_strogatz_graph;
+/// use graphina::core::types::Undirected;
+/// use graphina::metrics::{significance_test, transitivity};
+///
+/// // A ring lattice is far more clustered than a random graph with the same degrees.
+/// let lattice = watts_strogatz_graph::<Undirected>(40, 6, 0.0, 1).unwrap();
+/// let result = significance_test(&lattice, |g| transitivity(g), 20, 42).unwrap();
+/// assert!(result.observed > result.null_mean);
+/// assert!(result.z_score.unwrap() > 3.0);
+/// assert!(result.p_value < 0.1);
+/// ```
+pub fn significance_test<A, W, Ty, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    mut metric_fn: F,
+    n_random: usize,
+    seed: u64,
+) -> Result<SignificanceResult>
+where
+    A: Clone,
+    W: Clone,
+    Ty: GraphConstructor<A, W> + EdgeType + Clone,
+    F: FnMut(&BaseGraph<A, W, Ty>) -> f64,
+{
+    if n_random == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "Significance testing needs at least one randomized graph",
+        ));
+    }
+    let observed = metric_fn(graph);
+    let swaps = graph.edge_count().saturating_mul(10);
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut null_values = Vec::with_capacity(n_random);
+    for _ in 0..n_random {
+        let mut null = graph.clone();
+        degree_preserving_rewire(&mut null, swaps, rng.random());
+        null_values.push(metric_fn(&null));
+    }
+    if observed.is_nan() || null_values.iter().any(|v| v.is_nan()) {
+        return Err(GraphinaError::invalid_argument(
+            "The metric returned NaN, so it cannot be compared with the null model",
+        ));
+    }
+
+    let n = n_random as f64;
+    let null_mean = null_values.iter().sum::<f64>() / n;
+    let null_std = (null_values
+        .iter()
+        .map(|v| (v - null_mean).powi(2))
+        .sum::<f64>()
+        / n)
+        .sqrt();
+    let z_score = (null_std > 0.0).then(|| (observed - null_mean) / null_std);
+    let distance = (observed - null_mean).abs();
+    let extreme = null_values
+        .iter()
+        .filter(|v| (*v - null_mean).abs() >= distance)
+        .count();
+    Ok(SignificanceResult {
+        observed,
+        null_mean,
+        null_std,
+        z_score,
+        p_value: (extreme + 1) as f64 / (n + 1.0),
+        null_values,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Digraph;
+
+    #[test]
+    fn test_significance_test_preserves_degrees_and_is_reproducible() {
+        let mut g = Digraph::<(), f64>::new();
+        let n: Vec<_> = (0..8).map(|_| g.add_node(())).collect();
+        for i in 0..8 {
+            g.add_edge(n[i], n[(i + 1) % 8], i as f64);
+            g.add_edge(n[i], n[(i + 3) % 8], 1.0);
+        }
+        let degrees = |g: &Digraph<(), f64>| -> Vec<(usize, usize)> {
+            g.node_ids()
+                .map(|v| (g.in_degree(v).unwrap_or(0), g.out_degree(v).unwrap_or(0)))
+                .collect()
+        };
+        let before = degrees(&g);
+        let total: f64 = g.edges().map(|(_, _, w)| w).sum();
+
+        // A metric fixed by the degree sequence has no spread and no z-score.
+        let fixed = significance_test(
+            &g,
+            |h| {
+                assert_eq!(degrees(h), before);
+                h.edges().map(|(_, _, w)| w).sum::<f64>()
+            },
+            10,
+            3,
+        )
+        .unwrap();
+        assert_eq!(fixed.null_mean, total);
+        assert_eq!(fixed.z_score, None);
+        assert_eq!(fixed.p_value, 1.0);
+
+        let count = |h: &Digraph<(), f64>| {
+            h.edges()
+                .filter(|(u, v, _)| v.index() == (u.index() + 1) % 8)
+                .count() as f64
+        };
+        let a = significance_test(&g, count, 10, 9).unwrap();
+        let b = significance_test(&g, count, 10, 9).unwrap();
+        assert_eq!(a, b);
+        assert_eq!(a.observed, 8.0);
+        assert!(a.null_mean < 8.0);
+
+        assert!(significance_test(&g, count, 0, 9).is_err());
+        assert!(significance_test(&g, |_| f64::NAN, 2, 9).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+/*!
+# Spatial Metrics
+
+Navigation metrics for graphs embedded in the plane, such as road, rail, or pipe networks whose
+nodes carry coordinates. They compare the shortest-path distance between two nodes with the
+straight-line distance between them:
+
+- the *route factor* (or circuity) of a pair is `network / euclidean`, at least `1.0` when edge
+  costs are at least as long as the segments they join, and
+- the *detour index* is its reciprocal, `euclidean / network`, in `(0, 1]` under the same
+  condition, where `1.0` means every trip runs in a straight line.
+
+Coordinates come from a `position` callback on the node attribute, so any attribute type works.
+Pairs that cannot reach each other, and pairs at zero network or straight-line distance, are
+left out.
+*/
+
+use std::fmt::Debug;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::paths::dijkstra_path_impl;
+use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeId, NodeMap};
+use petgraph::EdgeType;
+
+/// The `(network, euclidean)` distances from each node to every other measurable node.
+type DistancePairs = Vec<(NodeId, Vec<(f64, f64)>)>;
+
+fn distance_pairs<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    position: impl Fn(&A) -> Option<(f64, f64)>,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<DistancePairs>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    let mut coords = NodeMap::default();
+    for (node, attr) in graph.nodes() {
+        let (x, y) = position(attr).ok_or_else(|| {
+            GraphinaError::invalid_argument(format!("Node {} has no position", node.index()))
+        })?;
+        if !x.is_finite() || !y.is_finite() {
+            return Err(GraphinaError::invalid_argument(format!(
+                "Node {} has a non-finite position ({x}, {y})",
+                node.index()
+            )));
+        }
+        coords.insert(node, (x, y));
+    }
+    let mut pairs = Vec::with_capacity(graph.node_count());
+    for source in graph.node_ids() {
+        let (distances, _) = dijkstra_path_impl(graph, source, None, &eval_cost)?;
+        let (sx, sy) = coords[&source];
+        let row = distances
+            .into_iter()
+            .filter(|&(target, _)| target != source)
+            .filter_map(|(target, network)| {
+                let (tx, ty) = coords[&target];
+                let euclidean = (tx - sx).hypot(ty - sy);
+                network
+                    .filter(|&d| d > 0.0 && euclidean > 0.0)
+                    .map(|d| (d, euclidean))
+            })
+            .collect();
+        pairs.push((source, row));
+    }
+    Ok(pairs)
+}
+
+/// Mean of `ratio` over every measured pair, `None` when there is none.
+fn mean_ratio(pairs: &DistancePairs, ratio: impl Fn(f64, f64) -> f64) -> Option<f64> {
+    let (sum, count) = pairs
+        .iter()
+        .flat_map(|(_, row)| row.iter())
+        .fold((0.0, 0usize), |(sum, count), &(network, euclidean)| {
+            (sum + ratio(network, euclidean), count + 1)
+        });
+    (count > 0).then(|| sum / count as f64)
+}
+
+/// Computes the mean route factor, `network / euclidean`, over all ordered pairs of distinct
+/// nodes that can reach each other.
+///
+/// `position` returns a node's planar coordinates, and edge costs come from `eval_cost` as in
+/// [`dijkstra_path_impl`], `None` leaving an edge out. Pairs at zero network or straight-line
+/// distance are skipped. Gives `None` when no pair is left, for example on a graph without
+/// edges.
+///
+/// # Errors
+///
+/// `InvalidArgument` if a node has no position or a non-finite one, and the errors of
+/// [`dijkstra_path_impl`], such as a negative cost.
+///
+/// # Time Complexity
+/// O(V * (E + V) log V) - Runs Dijkstra from each node
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::{detour_index, route_factor};
+///
+/// // Two sides of a unit square: the corners (0, 0) and (1, 1) are 2 apart by road but
+/// // only √2 apart in a straight line.
+/// let mut g = Graph::<(f64, f64), f64>::new();
+/// let a = g.add_node((0.0, 0.0));
+/// let b = g.add_node((1.0, 0.0));
+/// let c = g.add_node((1.0, 1.0));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let rf = route_factor(&g, |p| Some(*p), |w| Some(*w)).unwrap().unwrap();
+/// assert!((rf - (2.0 + 2.0_f64.sqrt()) / 3.0).abs() < 1e-12);
+/// let di = detour_index(&g, |p| Some(*p), |w| Some(*w)).unwrap().unwrap();
+/// assert!((di - (2.0 + 1.0 / 2.0_f64.sqrt()) / 3.0).abs() < 1e-12);
+/// ```
+pub fn route_factor<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    position: impl Fn(&A) -> Option<(f64, f64)>,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<f64>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    let pairs = distance_pairs(graph, position, eval_cost)?;
+    Ok(mean_ratio(&pairs, |network, euclidean| network / euclidean))
+}
+
+/// Computes the detour index, the mean of `euclidean / network` over the same pairs as
+/// [`route_factor`].
+///
+/// Averaging the reciprocal rather than inverting the mean route factor keeps a few very
+/// indirect pairs from dominating the score. Takes the same arguments, and fails in the same
+/// cases, as [`route_factor`].
+///
+/// # Time Complexity
+/// O(V * (E + V) log V)
+pub fn detour_index<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    position: impl Fn(&A) -> Option<(f64, f64)>,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Option<f64>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    let pairs = distance_pairs(graph, position, eval_cost)?;
+    Ok(mean_ratio(&pairs, |network, euclidean| euclidean / network))
+}
+
+/// Computes each node's mean route factor to the nodes it can reach, `None` for a node with
+/// no measurable pair.
+///
+/// Nodes whose trips out are the most roundabout score highest, which points at the poorly
+/// connected parts of a network. Takes the same arguments, and fails in the same cases, as
+/// [`route_factor`].
+///
+/// # Time Complexity
+/// O(V * (E + V) log V)
+pub fn node_route_factors<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    position: impl Fn(&A) -> Option<(f64, f64)>,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<NodeMap<Option<f64>>>
+where
+    A: Debug,
+    W: Debug,
+    Ty: GraphConstructor<A, W> + EdgeType,
+    BaseGraph<A, W, Ty>: GraphinaGraph<A, W>,
+{
+    let pairs = distance_pairs(graph, position, eval_cost)?;
+    Ok(pairs
+        .into_iter()
+        .map(|(node, row)| {
+            let mean = (!row.is_empty())
+                .then(|| row.iter().map(|&(n, e)| n / e).sum::<f64>() / row.len() as f64);
+            (node, mean)
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_route_factor_and_detour_index() {
+        // A straight line of three nodes has no detour at all; a directed edge back from the
+        // end is 3 long for a straight-line distance of 2.
+        let mut g = Digraph::<(f64, f64), f64>::new();
+        let n: Vec<_> = (0..3).map(|i| g.add_node((i as f64, 0.0))).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 1.0);
+        let at = |p: &(f64, f64)| Some(*p);
+        assert_eq!(route_factor(&g, at, |w| Some(*w)).unwrap(), Some(1.0));
+        assert_eq!(detour_index(&g, at, |w| Some(*w)).unwrap(), Some(1.0));
+
+        g.add_edge(n[2], n[0], 3.0);
+        let per_node = node_route_factors(&g, at, |w| Some(*w)).unwrap();
+        assert_eq!(per_node[&n[0]], Some(1.0));
+        // From n[2]: to n[0] costs 3 over 2, to n[1] costs 4 over 1.
+        assert_eq!(per_node[&n[2]], Some((1.5 + 4.0) / 2.0));
+
+        // Dropping every edge leaves nothing to measure.
+        assert_eq!(route_factor(&g, at, |_| None).unwrap(), None);
+
+        let mut missing = Graph::<Option<(f64, f64)>, f64>::new();
+        let a = missing.add_node(Some((0.0, 0.0)));
+        let b = missing.add_node(None);
+        missing.add_edge(a, b, 1.0);
+        assert!(route_factor(&missing, |p| *p, |w| Some(*w)).is_err());
+        let empty = Graph::<(f64, f64), f64>::new();
+        assert_eq!(route_factor(&empty, at, |w| Some(*w)).unwrap(), None);
+    }
+}
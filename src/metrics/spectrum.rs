@@ -0,0 +1,302 @@
+/*!
+# Graph Spectra
+
+Eigenvalues of the adjacency and Laplacian matrices of an undirected graph, and the Fiedler
+vector. The spectrum summarizes structure that is hard to see locally: the number of zero
+Laplacian eigenvalues counts connected components, the second smallest one (the algebraic
+connectivity) measures how hard the graph is to cut, and the largest adjacency eigenvalue
+bounds the average and maximum degree.
+
+The matrices are built densely and decomposed with nalgebra's symmetric eigensolver, so these
+functions suit graphs with up to a few thousand nodes.
+*/
+
+use nalgebra::DMatrix;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use crate::core::validation::is_connected;
+use petgraph::EdgeType;
+
+/// Builds the dense adjacency or Laplacian matrix in node order. Parallel edges add up. A
+/// self-loop sits on the adjacency diagonal but cancels out of the Laplacian.
+fn dense_matrix<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    laplacian: bool,
+) -> Result<(Vec<NodeId>, DMatrix<f64>)>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    if graph.is_directed() {
+        return Err(GraphinaError::invalid_graph(
+            "Spectra are only defined here for undirected graphs",
+        ));
+    }
+    if graph.is_empty() {
+        return Err(GraphinaError::invalid_graph("Graph is empty"));
+    }
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let position: NodeMap<usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let n = nodes.len();
+    let mut m = DMatrix::<f64>::zeros(n, n);
+    for (u, v, &w) in graph.edges() {
+        let (i, j) = (position[&u], position[&v]);
+        let w: f64 = w.into();
+        if i == j {
+            if !laplacian {
+                m[(i, i)] += w;
+            }
+        } else if laplacian {
+            m[(i, j)] -= w;
+            m[(j, i)] -= w;
+            m[(i, i)] += w;
+            m[(j, j)] += w;
+        } else {
+            m[(i, j)] += w;
+            m[(j, i)] += w;
+        }
+    }
+    Ok((nodes, m))
+}
+
+fn check_k(k: Option<usize>, n: usize) -> Result<usize> {
+    match k {
+        None => Ok(n),
+        Some(k) if k == 0 || k > n => Err(GraphinaError::invalid_argument(format!(
+            "k must be between 1 and the node count {}, got {}",
+            n, k
+        ))),
+        Some(k) => Ok(k),
+    }
+}
+
+/// Returns the `k` smallest eigenvalues of the Laplacian `L = D - A`, in ascending order, or
+/// all of them when `k` is `None`.
+///
+/// Edge weights are used as entries of `A`, so parallel edges add up; self-loops do not
+/// affect the Laplacian. The smallest eigenvalue is always zero, and it repeats once per
+/// connected component.
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` for a directed or empty graph, and `InvalidArgument` if `k` is zero
+/// or larger than the node count.
+///
+/// # Time Complexity
+/// O(V³) for the dense eigendecomposition
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::laplacian_spectrum;
+///
+/// // The Laplacian of the triangle K3 has eigenvalues 0, 3, 3.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..3).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[2], 1.0);
+/// g.add_edge(n[2], n[0], 1.0);
+///
+/// let spectrum = laplacian_spectrum(&g, None).unwrap();
+/// assert!(spectrum[0].abs() < 1e-9);
+/// assert!((spectrum[2] - 3.0).abs() < 1e-9);
+/// ```
+pub fn laplacian_spectrum<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    k: Option<usize>,
+) -> Result<Vec<f64>>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let (nodes, lap) = dense_matrix(graph, true)?;
+    let k = check_k(k, nodes.len())?;
+    let mut values: Vec<f64> = lap.symmetric_eigenvalues().iter().copied().collect();
+    values.sort_by(f64::total_cmp);
+    values.truncate(k);
+    Ok(values)
+}
+
+/// Returns the `k` largest eigenvalues of the weighted adjacency matrix, in descending order,
+/// or all of them when `k` is `None`.
+///
+/// Parallel edges add up and a self-loop contributes its weight to the diagonal.
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` for a directed or empty graph, and `InvalidArgument` if `k` is zero
+/// or larger than the node count.
+///
+/// # Time Complexity
+/// O(V³) for the dense eigendecomposition
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::adjacency_spectrum;
+///
+/// // A single edge has adjacency eigenvalues 1 and -1.
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+///
+/// let top = adjacency_spectrum(&g, Some(1)).unwrap();
+/// assert!((top[0] - 1.0).abs() < 1e-9);
+/// ```
+pub fn adjacency_spectrum<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    k: Option<usize>,
+) -> Result<Vec<f64>>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let (nodes, adj) = dense_matrix(graph, false)?;
+    let k = check_k(k, nodes.len())?;
+    let mut values: Vec<f64> = adj.symmetric_eigenvalues().iter().copied().collect();
+    values.sort_by(|a, b| b.total_cmp(a));
+    values.truncate(k);
+    Ok(values)
+}
+
+/// Returns the algebraic connectivity and the Fiedler vector of a connected graph.
+///
+/// The algebraic connectivity is the second smallest Laplacian eigenvalue and the Fiedler
+/// vector is a unit eigenvector for it. Splitting the nodes by the sign of their entry gives a
+/// good balanced cut. An eigenvector is only defined up to sign, so the vector is oriented to
+/// make the entry of largest magnitude positive; if that eigenvalue repeats, as in highly
+/// symmetric graphs, any vector of its eigenspace is an equally valid answer.
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` if the graph is directed, has fewer than two nodes, or is not
+/// connected, since the Fiedler vector of a disconnected graph is not unique.
+///
+/// # Time Complexity
+/// O(V³) for the dense eigendecomposition
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::fiedler_vector;
+///
+/// // In a path the Fiedler vector changes sign in the middle.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// for i in 0..3 {
+///     g.add_edge(n[i], n[i + 1], 1.0);
+/// }
+///
+/// let (connectivity, vector) = fiedler_vector(&g).unwrap();
+/// assert!(connectivity > 0.0);
+/// assert!(vector[&n[0]] * vector[&n[3]] < 0.0);
+/// assert!(vector[&n[0]] * vector[&n[1]] > 0.0);
+/// ```
+pub fn fiedler_vector<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Result<(f64, NodeMap<f64>)>
+where
+    W: Copy + Into<f64>,
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let (nodes, lap) = dense_matrix(graph, true)?;
+    if nodes.len() < 2 {
+        return Err(GraphinaError::invalid_graph(
+            "Fiedler vector needs at least two nodes",
+        ));
+    }
+    if !is_connected(graph) {
+        return Err(GraphinaError::invalid_graph(
+            "Fiedler vector is not unique for a disconnected graph",
+        ));
+    }
+    let eig = lap.symmetric_eigen();
+    let mut order: Vec<usize> = (0..nodes.len()).collect();
+    order.sort_by(|&a, &b| eig.eigenvalues[a].total_cmp(&eig.eigenvalues[b]));
+    let second = order[1];
+    let column = eig.eigenvectors.column(second);
+    let pivot = column
+        .iter()
+        .copied()
+        .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+        .unwrap_or(1.0);
+    let sign = if pivot < 0.0 { -1.0 } else { 1.0 };
+    let vector = nodes
+        .iter()
+        .zip(column.iter())
+        .map(|(&v, &x)| (v, sign * x))
+        .collect();
+    Ok((eig.eigenvalues[second], vector))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    fn close(a: &[f64], b: &[f64]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| (x - y).abs() < 1e-9)
+    }
+
+    #[test]
+    fn test_spectra_of_star_and_components() {
+        // The star K(1, 3) has Laplacian spectrum 0, 1, 1, 4 and adjacency spectrum
+        // sqrt(3), 0, 0, -sqrt(3).
+        let mut star = Graph::<(), f64>::new();
+        let hub = star.add_node(());
+        for _ in 0..3 {
+            let leaf = star.add_node(());
+            star.add_edge(hub, leaf, 1.0);
+        }
+        let s3 = 3f64.sqrt();
+        assert!(close(
+            &laplacian_spectrum(&star, None).unwrap(),
+            &[0.0, 1.0, 1.0, 4.0]
+        ));
+        assert!(close(
+            &adjacency_spectrum(&star, None).unwrap(),
+            &[s3, 0.0, 0.0, -s3]
+        ));
+        assert!(close(
+            &laplacian_spectrum(&star, Some(2)).unwrap(),
+            &[0.0, 1.0]
+        ));
+
+        // One zero Laplacian eigenvalue per component; a self-loop changes nothing.
+        let extra = star.add_node(());
+        star.add_edge(extra, extra, 5.0);
+        let spectrum = laplacian_spectrum(&star, None).unwrap();
+        assert!(close(&spectrum[..2], &[0.0, 0.0]));
+        assert!(fiedler_vector(&star).is_err());
+
+        assert!(laplacian_spectrum(&star, Some(0)).is_err());
+        assert!(adjacency_spectrum(&star, Some(6)).is_err());
+        assert!(laplacian_spectrum(&Graph::<(), f64>::new(), None).is_err());
+        let mut d = Digraph::<(), f64>::new();
+        d.add_node(());
+        assert!(adjacency_spectrum(&d, None).is_err());
+    }
+
+    #[test]
+    fn test_fiedler_vector_splits_weighted_barbell() {
+        // Two heavy triangles joined by a light edge split along that edge.
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)] {
+            g.add_edge(n[u], n[v], 10.0);
+        }
+        g.add_edge(n[2], n[3], 0.5);
+
+        let (connectivity, vector) = fiedler_vector(&g).unwrap();
+        let lowest = laplacian_spectrum(&g, Some(2)).unwrap();
+        assert!((connectivity - lowest[1]).abs() < 1e-9);
+        let side = |i: usize| vector[&n[i]] > 0.0;
+        assert!((0..3).all(|i| side(i) == side(0)));
+        assert!((3..6).all(|i| side(i) != side(0)));
+        let norm: f64 = vector.values().map(|x| x * x).sum();
+        assert!((norm - 1.0).abs() < 1e-9);
+    }
+}
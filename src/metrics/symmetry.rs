@@ -0,0 +1,327 @@
+/*!
+# Regularity and Symmetry
+
+Checks for degree and distance regularity, and a color-refinement estimate of which nodes are
+structurally interchangeable.
+
+In a distance-regular graph every node sees the same number of nodes at each distance, so all
+nodes have the same eccentricity and a single BFS already gives the diameter. More generally,
+nodes in the same automorphism orbit have identical distance profiles, and
+[`approximate_vertex_orbits`] groups nodes so that every orbit lies within one group.
+*/
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::EdgeType;
+
+/// Returns true if every node has the same degree.
+///
+/// In a directed graph every node must have the same in-degree and the same out-degree. An
+/// empty graph is not considered regular.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::is_regular;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// for i in 0..4 {
+///     g.add_edge(n[i], n[(i + 1) % 4], 1.0);
+/// }
+/// assert!(is_regular(&g));
+/// g.add_edge(n[0], n[2], 1.0);
+/// assert!(!is_regular(&g));
+/// ```
+pub fn is_regular<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> bool {
+    let mut degrees = graph
+        .node_ids()
+        .map(|v| (graph.in_degree(v), graph.out_degree(v), graph.degree(v)));
+    match degrees.next() {
+        Some(first) => degrees.all(|d| d == first),
+        None => false,
+    }
+}
+
+/// Computes the intersection array `(b, c)` of a distance-regular graph.
+///
+/// A connected graph of diameter `d` is distance-regular when, for any two nodes `u` and `v`
+/// at distance `i`, the number `b[i]` of neighbors of `v` at distance `i + 1` from `u` and the
+/// number `c[i - 1]` of neighbors at distance `i - 1` depend only on `i`. The result lists
+/// `b[0..d]` and `c[1..=d]`, matching the usual notation `{b0, ..., b(d-1); c1, ..., cd}`.
+///
+/// Returns `None` if the graph is not distance-regular, including when it is empty,
+/// disconnected, directed, or has self-loops or parallel edges.
+///
+/// # Time Complexity
+/// O(V * (V + E)) - Runs BFS from each node
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::intersection_array;
+///
+/// // The 6-cycle has intersection array {2, 1, 1; 1, 1, 2}.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// for i in 0..6 {
+///     g.add_edge(n[i], n[(i + 1) % 6], 1.0);
+/// }
+/// assert_eq!(intersection_array(&g), Some((vec![2, 1, 1], vec![1, 1, 2])));
+/// ```
+pub fn intersection_array<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> Option<(Vec<usize>, Vec<usize>)> {
+    if graph.is_directed() || graph.is_empty() {
+        return None;
+    }
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let position: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let mut adjacency = vec![Vec::new(); nodes.len()];
+    let mut seen = HashSet::new();
+    for (u, v, _) in graph.edges() {
+        let (i, j) = (position[&u], position[&v]);
+        if i == j || !seen.insert((i.min(j), i.max(j))) {
+            return None;
+        }
+        adjacency[i].push(j);
+        adjacency[j].push(i);
+    }
+
+    // Per distance i: (neighbors one step farther, neighbors one step closer).
+    let mut counts: Vec<(usize, usize)> = Vec::new();
+    let mut diameter = None;
+    for source in 0..nodes.len() {
+        let mut dist = vec![usize::MAX; nodes.len()];
+        dist[source] = 0;
+        let mut queue = VecDeque::from([source]);
+        let mut order = Vec::with_capacity(nodes.len());
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for &w in &adjacency[v] {
+                if dist[w] == usize::MAX {
+                    dist[w] = dist[v] + 1;
+                    queue.push_back(w);
+                }
+            }
+        }
+        if order.len() != nodes.len() {
+            return None;
+        }
+        let ecc = dist[order[order.len() - 1]];
+        if *diameter.get_or_insert(ecc) != ecc {
+            return None;
+        }
+        for &v in &order {
+            let i = dist[v];
+            let farther = adjacency[v].iter().filter(|&&w| dist[w] == i + 1).count();
+            let closer = adjacency[v].iter().filter(|&&w| dist[w] + 1 == i).count();
+            match counts.get(i) {
+                Some(&expected) if expected != (farther, closer) => return None,
+                Some(_) => {}
+                None => counts.push((farther, closer)),
+            }
+        }
+    }
+
+    let d = counts.len() - 1;
+    let b = counts[..d].iter().map(|&(farther, _)| farther).collect();
+    let c = counts[1..].iter().map(|&(_, closer)| closer).collect();
+    Some((b, c))
+}
+
+/// Returns true if the graph is distance-regular.
+///
+/// See [`intersection_array`] for the definition and the graphs that are rejected. Every
+/// distance-regular graph is regular, and all of its nodes have the same eccentricity.
+///
+/// # Time Complexity
+/// O(V * (V + E))
+pub fn is_distance_regular<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> bool {
+    intersection_array(graph).is_some()
+}
+
+/// Groups nodes that color refinement (1-dimensional Weisfeiler-Leman) cannot tell apart.
+///
+/// Every node starts with its degree as its color, or its `(in, out)` degree pair in a
+/// directed graph. Each round recolors a node by its old color together with the multiset of
+/// its neighbors' colors, until the number of colors stops growing.
+///
+/// Nodes in the same automorphism orbit always end up in the same group, so the groups are a
+/// coarsening of the true orbits: a group of size one is a node fixed by every automorphism,
+/// but two nodes sharing a group are not guaranteed to be interchangeable. For example, every
+/// node of a regular graph lands in one group. Groups are ordered by their final color, which
+/// depends only on the graph's structure, and nodes within a group follow node order.
+///
+/// # Time Complexity
+/// O(V * (V + E) * log V) in the worst case; most graphs stabilize after a few rounds.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::approximate_vertex_orbits;
+///
+/// // A 5-node path folds into ends, next-to-ends, and the middle.
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// for i in 0..4 {
+///     g.add_edge(n[i], n[i + 1], 1.0);
+/// }
+/// let orbits = approximate_vertex_orbits(&g);
+/// assert_eq!(orbits.len(), 3);
+/// assert!(orbits.contains(&vec![n[0], n[4]]));
+/// assert!(orbits.contains(&vec![n[2]]));
+/// ```
+pub fn approximate_vertex_orbits<A, W, Ty: GraphConstructor<A, W> + EdgeType>(
+    graph: &BaseGraph<A, W, Ty>,
+) -> Vec<Vec<NodeId>> {
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let position: HashMap<NodeId, usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let directed = graph.is_directed();
+    let out_adj: Vec<Vec<usize>> = nodes
+        .iter()
+        .map(|&v| graph.outgoing_neighbors(v).map(|w| position[&w]).collect())
+        .collect();
+    let in_adj: Vec<Vec<usize>> = if directed {
+        nodes
+            .iter()
+            .map(|&v| graph.incoming_neighbors(v).map(|w| position[&w]).collect())
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    // Signatures are compared as plain integer vectors and renumbered in sorted order, so the
+    // final colors do not depend on how the nodes happen to be numbered.
+    let renumber = |signatures: Vec<Vec<usize>>| -> (Vec<usize>, usize) {
+        let mut distinct = signatures.clone();
+        distinct.sort();
+        distinct.dedup();
+        let ids: HashMap<&Vec<usize>, usize> =
+            distinct.iter().enumerate().map(|(i, s)| (s, i)).collect();
+        (signatures.iter().map(|s| ids[s]).collect(), distinct.len())
+    };
+
+    let initial = (0..nodes.len())
+        .map(|i| {
+            let out = out_adj[i].len();
+            if directed {
+                vec![in_adj[i].len(), out]
+            } else {
+                vec![out]
+            }
+        })
+        .collect();
+    let (mut colors, mut classes) = renumber(initial);
+    loop {
+        let signatures = (0..nodes.len())
+            .map(|i| {
+                let mut out: Vec<usize> = out_adj[i].iter().map(|&j| colors[j]).collect();
+                out.sort_unstable();
+                let mut signature = vec![colors[i], out.len()];
+                signature.extend(out);
+                if directed {
+                    let mut inc: Vec<usize> = in_adj[i].iter().map(|&j| colors[j]).collect();
+                    inc.sort_unstable();
+                    signature.extend(inc);
+                }
+                signature
+            })
+            .collect();
+        let (refined, refined_classes) = renumber(signatures);
+        if refined_classes == classes {
+            break;
+        }
+        colors = refined;
+        classes = refined_classes;
+    }
+
+    let mut groups = vec![Vec::new(); classes];
+    for (i, &v) in nodes.iter().enumerate() {
+        groups[colors[i]].push(v);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::products::cartesian_product;
+    use crate::core::types::{Digraph, Graph};
+
+    fn petersen() -> Graph<(), f64> {
+        let mut g = Graph::new();
+        let n: Vec<_> = (0..10).map(|_| g.add_node(())).collect();
+        for i in 0..5 {
+            g.add_edge(n[i], n[(i + 1) % 5], 1.0);
+            g.add_edge(n[i], n[i + 5], 1.0);
+            g.add_edge(n[i + 5], n[(i + 2) % 5 + 5], 1.0);
+        }
+        g
+    }
+
+    #[test]
+    fn test_distance_regularity() {
+        let p = petersen();
+        assert!(is_regular(&p));
+        assert_eq!(intersection_array(&p), Some((vec![3, 2], vec![1, 1])));
+
+        // The triangular prism is 3-regular but not distance-regular.
+        let mut triangle = Graph::<(), f64>::new();
+        let t: Vec<_> = (0..3).map(|_| triangle.add_node(())).collect();
+        for i in 0..3 {
+            triangle.add_edge(t[i], t[(i + 1) % 3], 1.0);
+        }
+        let mut edge = Graph::<(), f64>::new();
+        let a = edge.add_node(());
+        let b = edge.add_node(());
+        edge.add_edge(a, b, 1.0);
+        let (prism, _) = cartesian_product(&triangle, &edge);
+        assert!(is_regular(&prism));
+        assert!(!is_distance_regular(&prism));
+
+        let mut single = Graph::<(), f64>::new();
+        single.add_node(());
+        assert_eq!(intersection_array(&single), Some((vec![], vec![])));
+        assert!(!is_regular(&Graph::<(), f64>::new()));
+        assert!(!is_distance_regular(&Graph::<(), f64>::new()));
+
+        let mut disconnected = edge.clone();
+        let c = disconnected.add_node(());
+        let d = disconnected.add_node(());
+        disconnected.add_edge(c, d, 1.0);
+        assert!(is_regular(&disconnected));
+        assert!(!is_distance_regular(&disconnected));
+    }
+
+    #[test]
+    fn test_approximate_vertex_orbits() {
+        assert_eq!(approximate_vertex_orbits(&petersen()).len(), 1);
+
+        // A star folds its leaves together.
+        let mut star = Graph::<(), f64>::new();
+        let hub = star.add_node(());
+        let leaves: Vec<_> = (0..4).map(|_| star.add_node(())).collect();
+        for &l in &leaves {
+            star.add_edge(hub, l, 1.0);
+        }
+        assert_eq!(approximate_vertex_orbits(&star), vec![leaves, vec![hub]]);
+
+        // Direction breaks the symmetry of a path.
+        let mut d = Digraph::<(), f64>::new();
+        let n: Vec<_> = (0..3).map(|_| d.add_node(())).collect();
+        d.add_edge(n[0], n[1], 1.0);
+        d.add_edge(n[1], n[2], 1.0);
+        assert_eq!(approximate_vertex_orbits(&d).len(), 3);
+        assert!(!is_regular(&d));
+        assert!(approximate_vertex_orbits(&Digraph::<(), f64>::new()).is_empty());
+    }
+}
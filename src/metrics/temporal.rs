@@ -0,0 +1,388 @@
+/*!
+# Temporal Metrics
+
+Sliding-window activity metrics and burst detection for temporal graphs.
+
+Each temporal edge counts as an event at its start time for both endpoints. The window
+functions slice the time span of the graph into windows of a fixed length that advance by a
+fixed step, and report one value per node and window. Burst detection follows Kleinberg's
+two-parameter automaton model ("Bursty and Hierarchical Structure in Streams", 2002): event
+gaps are explained by a hierarchy of states with increasing event rates, and a burst is a
+maximal run of time spent in an elevated state.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::temporal::{TemporalEdge, TemporalGraph};
+use crate::core::types::{GraphConstructor, NodeId, NodeMap};
+use petgraph::EdgeType;
+use std::collections::HashSet;
+
+/// Upper bound on the number of automaton states used by [`kleinberg_bursts`].
+const MAX_BURST_STATES: usize = 64;
+
+/// A period of elevated activity found by [`kleinberg_bursts`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Burst {
+    /// Burst intensity: the automaton state, starting at 1 for the first elevated rate.
+    pub level: usize,
+    /// Time of the first event in the burst.
+    pub start: f64,
+    /// Time of the last event in the burst.
+    pub end: f64,
+}
+
+/// Returns the start times of the sliding windows over `[span_start, span_end]`.
+fn window_starts(span: Option<(f64, f64)>, window: f64, step: f64) -> Result<Vec<f64>> {
+    if !(window.is_finite() && window > 0.0) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Window length must be positive and finite, got {}",
+            window
+        )));
+    }
+    if !(step.is_finite() && step > 0.0) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Window step must be positive and finite, got {}",
+            step
+        )));
+    }
+    let Some((lo, hi)) = span else {
+        return Ok(Vec::new());
+    };
+    let count = ((hi - lo) / step).floor() as usize + 1;
+    Ok((0..count).map(|k| lo + k as f64 * step).collect())
+}
+
+/// Shared driver for the window series: `value` receives the `(neighbor, time)` events of
+/// one node inside one window and returns the value to record.
+fn window_series<A, W, Ty, F>(
+    graph: &TemporalGraph<A, W, Ty>,
+    window: f64,
+    step: f64,
+    mut value: F,
+) -> Result<(Vec<f64>, NodeMap<Vec<usize>>)>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+    F: FnMut(&[(NodeId, f64)]) -> usize,
+{
+    let starts = window_starts(graph.time_span(), window, step)?;
+
+    // Per-node events `(other endpoint, time)`, sorted by time.
+    let mut events: NodeMap<Vec<(NodeId, f64)>> =
+        graph.node_ids().map(|u| (u, Vec::new())).collect();
+    for (u, v, edge) in graph.edges() {
+        if let Some(list) = events.get_mut(&u) {
+            list.push((v, edge.start));
+        }
+        if u != v {
+            if let Some(list) = events.get_mut(&v) {
+                list.push((u, edge.start));
+            }
+        }
+    }
+
+    let mut series = NodeMap::default();
+    for (node, mut list) in events {
+        list.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let values = starts
+            .iter()
+            .map(|&s| {
+                let lo = list.partition_point(|e| e.1 < s);
+                let hi = list.partition_point(|e| e.1 < s + window);
+                value(&list[lo..hi])
+            })
+            .collect();
+        series.insert(node, values);
+    }
+    Ok((starts, series))
+}
+
+/// Counts, for every node, the temporal edges incident to it that start in each window.
+///
+/// Windows are half-open intervals `[s, s + window)`, where `s` runs from the earliest start
+/// time of the graph in increments of `step` up to its latest end time. With `step < window`
+/// the windows overlap.
+///
+/// # Returns
+///
+/// The window start times, and per node a vector with one event count per window.
+///
+/// # Errors
+///
+/// `InvalidArgument` if `window` or `step` is not positive and finite.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::temporal::TemporalGraph;
+/// use graphina::metrics::temporal::activity_series;
+///
+/// let mut tg = TemporalGraph::<i32, ()>::new();
+/// let a = tg.add_node(0);
+/// let b = tg.add_node(1);
+/// for t in [0.0, 1.0, 5.0] {
+///     tg.add_edge(a, b, (), t, t).unwrap();
+/// }
+/// let (starts, series) = activity_series(&tg, 2.0, 2.0).unwrap();
+/// assert_eq!(starts, vec![0.0, 2.0, 4.0]);
+/// assert_eq!(series[&a], vec![2, 0, 1]);
+/// ```
+pub fn activity_series<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    window: f64,
+    step: f64,
+) -> Result<(Vec<f64>, NodeMap<Vec<usize>>)>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    window_series(graph, window, step, |events| events.len())
+}
+
+/// Counts, for every node, the distinct neighbors it is in contact with in each window.
+///
+/// This is the degree of the node in the static graph aggregated over the window, taken
+/// regardless of edge direction. Windows are defined as in [`activity_series`].
+///
+/// # Errors
+///
+/// `InvalidArgument` if `window` or `step` is not positive and finite.
+pub fn windowed_degree_series<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    window: f64,
+    step: f64,
+) -> Result<(Vec<f64>, NodeMap<Vec<usize>>)>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    let mut seen = HashSet::new();
+    window_series(graph, window, step, |events| {
+        seen.clear();
+        seen.extend(events.iter().map(|e| e.0));
+        seen.len()
+    })
+}
+
+/// Detects bursts in a sequence of event times with Kleinberg's automaton model.
+///
+/// The state `i` of the automaton emits gaps between events from an exponential distribution
+/// with rate `s^i / g`, where `g` is the mean gap. Moving up by one state costs
+/// `gamma * ln(n)` for `n` events and moving down is free. The optimal state sequence is found
+/// with the Viterbi algorithm; every maximal run at state `level` or above is reported as one
+/// burst of that level, so bursts of higher levels nest inside lower ones.
+///
+/// # Arguments
+///
+/// * `times` - Event times, in any order.
+/// * `s` - Rate ratio between consecutive states; must be greater than 1 (2 is typical).
+/// * `gamma` - Cost of moving up a state; larger values report fewer bursts (1 is typical).
+///
+/// # Returns
+///
+/// The bursts ordered by start time and then by level. Fewer than two events, or events that
+/// all share one timestamp, yield no bursts.
+///
+/// # Errors
+///
+/// `InvalidArgument` if a time is not finite, `s <= 1`, or `gamma` is negative.
+pub fn kleinberg_bursts(times: &[f64], s: f64, gamma: f64) -> Result<Vec<Burst>> {
+    if times.iter().any(|t| !t.is_finite()) {
+        return Err(GraphinaError::invalid_argument(
+            "Event times must be finite.",
+        ));
+    }
+    if !(s.is_finite() && s > 1.0) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "The rate ratio s must be greater than 1, got {}",
+            s
+        )));
+    }
+    if !(gamma.is_finite() && gamma >= 0.0) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "The transition cost gamma must be nonnegative, got {}",
+            gamma
+        )));
+    }
+
+    let mut sorted = times.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let n = sorted.len();
+    if n < 2 {
+        return Ok(Vec::new());
+    }
+    let gaps: Vec<f64> = sorted.windows(2).map(|w| w[1] - w[0]).collect();
+    let total = sorted[n - 1] - sorted[0];
+    let Some(min_gap) = gaps
+        .iter()
+        .copied()
+        .filter(|&g| g > 0.0)
+        .min_by(f64::total_cmp)
+    else {
+        return Ok(Vec::new());
+    };
+
+    // The number of states needed for the fastest rate to fit the smallest gap, following
+    // Kleinberg's bound k = 1 + log_s(T) + log_s(1 / min_gap).
+    let k = (1.0 + (total / min_gap).ln() / s.ln())
+        .ceil()
+        .clamp(2.0, MAX_BURST_STATES as f64) as usize;
+    let mean_gap = total / gaps.len() as f64;
+    let rates: Vec<f64> = (0..k).map(|i| s.powi(i as i32) / mean_gap).collect();
+    let up_cost = gamma * (n as f64).ln();
+
+    // Viterbi over the gaps. cost[j] is the cheapest way to explain the gaps so far ending in
+    // state j; back[g][j] records the predecessor state.
+    let emission = |rate: f64, gap: f64| rate * gap - rate.ln();
+    let mut cost: Vec<f64> = (0..k)
+        .map(|j| j as f64 * up_cost + emission(rates[j], gaps[0]))
+        .collect();
+    let mut back: Vec<Vec<usize>> = Vec::with_capacity(gaps.len());
+    back.push((0..k).collect());
+    for &gap in &gaps[1..] {
+        let mut next = vec![f64::INFINITY; k];
+        let mut from = vec![0; k];
+        for j in 0..k {
+            for (i, &c) in cost.iter().enumerate() {
+                let step = if j > i { (j - i) as f64 * up_cost } else { 0.0 };
+                if c + step < next[j] {
+                    next[j] = c + step;
+                    from[j] = i;
+                }
+            }
+            next[j] += emission(rates[j], gap);
+        }
+        cost = next;
+        back.push(from);
+    }
+
+    let mut state = (0..k)
+        .min_by(|&a, &b| cost[a].total_cmp(&cost[b]))
+        .unwrap_or(0);
+    let mut states = vec![0; gaps.len()];
+    for g in (0..gaps.len()).rev() {
+        states[g] = state;
+        state = back[g][state];
+    }
+
+    // Gap g spans events g and g + 1; collect the maximal runs at or above each level.
+    let mut bursts = Vec::new();
+    let top = states.iter().copied().max().unwrap_or(0);
+    for level in 1..=top {
+        let mut g = 0;
+        while g < states.len() {
+            if states[g] < level {
+                g += 1;
+                continue;
+            }
+            let run_start = g;
+            while g < states.len() && states[g] >= level {
+                g += 1;
+            }
+            bursts.push(Burst {
+                level,
+                start: sorted[run_start],
+                end: sorted[g],
+            });
+        }
+    }
+    bursts.sort_by(|a, b| a.start.total_cmp(&b.start).then(a.level.cmp(&b.level)));
+    Ok(bursts)
+}
+
+/// Runs [`kleinberg_bursts`] on the event times of every node, where the events of a node are
+/// the start times of its incident temporal edges.
+///
+/// # Errors
+///
+/// `InvalidArgument` if `s <= 1` or `gamma` is negative.
+pub fn node_bursts<A, W, Ty>(
+    graph: &TemporalGraph<A, W, Ty>,
+    s: f64,
+    gamma: f64,
+) -> Result<NodeMap<Vec<Burst>>>
+where
+    Ty: GraphConstructor<A, TemporalEdge<W>> + EdgeType,
+{
+    let mut times: NodeMap<Vec<f64>> = graph.node_ids().map(|u| (u, Vec::new())).collect();
+    for (u, v, edge) in graph.edges() {
+        if let Some(list) = times.get_mut(&u) {
+            list.push(edge.start);
+        }
+        if u != v {
+            if let Some(list) = times.get_mut(&v) {
+                list.push(edge.start);
+            }
+        }
+    }
+    times
+        .into_iter()
+        .map(|(u, t)| kleinberg_bursts(&t, s, gamma).map(|b| (u, b)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degree_series_counts_distinct_neighbors() {
+        let mut tg = TemporalGraph::<i32, ()>::new();
+        let a = tg.add_node(0);
+        let b = tg.add_node(1);
+        let c = tg.add_node(2);
+        tg.add_edge(a, b, (), 0.0, 0.5).unwrap();
+        tg.add_edge(b, a, (), 1.0, 1.5).unwrap();
+        tg.add_edge(a, c, (), 1.5, 2.0).unwrap();
+        tg.add_edge(b, c, (), 3.0, 3.5).unwrap();
+
+        let (starts, degree) = windowed_degree_series(&tg, 2.0, 1.0).unwrap();
+        assert_eq!(starts, vec![0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(degree[&a], vec![2, 2, 0, 0]);
+        assert_eq!(degree[&b], vec![1, 1, 1, 1]);
+
+        let (_, activity) = activity_series(&tg, 2.0, 1.0).unwrap();
+        assert_eq!(activity[&a], vec![3, 2, 0, 0]);
+
+        assert!(activity_series(&tg, 0.0, 1.0).is_err());
+        assert!(activity_series(&tg, 1.0, -1.0).is_err());
+    }
+
+    #[test]
+    fn test_kleinberg_finds_dense_cluster() {
+        // Sparse events every 10 units with a dense cluster of events 0.1 apart around t = 50.
+        let mut times: Vec<f64> = (0..10).map(|i| i as f64 * 10.0).collect();
+        times.extend((0..20).map(|i| 50.0 + i as f64 * 0.1));
+        let bursts = kleinberg_bursts(&times, 2.0, 1.0).unwrap();
+        assert!(!bursts.is_empty());
+        let first = bursts.iter().find(|b| b.level == 1).unwrap();
+        assert!(first.start >= 49.0 && first.start <= 50.0);
+        assert!(first.end >= 51.8 && first.end <= 60.0);
+    }
+
+    #[test]
+    fn test_kleinberg_regular_events_have_no_bursts() {
+        let times: Vec<f64> = (0..50).map(|i| i as f64).collect();
+        assert!(kleinberg_bursts(&times, 2.0, 1.0).unwrap().is_empty());
+        assert!(kleinberg_bursts(&[1.0], 2.0, 1.0).unwrap().is_empty());
+        assert!(kleinberg_bursts(&times, 1.0, 1.0).is_err());
+    }
+
+    #[test]
+    fn test_node_bursts_covers_every_node() {
+        let mut tg = TemporalGraph::<i32, ()>::new();
+        let a = tg.add_node(0);
+        let b = tg.add_node(1);
+        let idle = tg.add_node(2);
+        for i in 0..10 {
+            tg.add_edge(a, b, (), i as f64 * 10.0, i as f64 * 10.0)
+                .unwrap();
+        }
+        for i in 0..20 {
+            let t = 50.0 + i as f64 * 0.1;
+            tg.add_edge(a, b, (), t, t).unwrap();
+        }
+        let bursts = node_bursts(&tg, 2.0, 1.0).unwrap();
+        assert_eq!(bursts.len(), 3);
+        assert!(!bursts[&a].is_empty());
+        assert_eq!(bursts[&a], bursts[&b]);
+        assert!(bursts[&idle].is_empty());
+    }
+}
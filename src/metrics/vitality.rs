@@ -0,0 +1,513 @@
+/*!
+# Node Vitality
+
+Scores for how much the graph suffers when a single node is removed, for ranking the
+critical nodes of an infrastructure network.
+
+[`closeness_vitality`] measures the change in the total distance between all pairs of nodes
+(the Wiener index). Recomputing every distance for every removed node would cost a full
+all-pairs run per node, so it keeps, for each source, the dominator tree of the source's
+shortest-path DAG: removing a node that dominates nothing leaves every other distance from
+that source unchanged, and only the remaining (source, node) pairs are searched again.
+
+[`component_impact`] measures the change in the component structure instead. One DFS finds
+every cut vertex and the sizes of the pieces it leaves, so it runs in O(V + E) for all nodes
+together.
+*/
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use ordered_float::NotNan;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeMap};
+use petgraph::EdgeType;
+
+/// Marks an unset slot in the dense, index-keyed vectors below.
+const NONE: usize = usize::MAX;
+
+/// Outgoing `(target, cost)` lists indexed by `NodeId::index()`, validating each cost once.
+fn cost_adjacency<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    bound: usize,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<Vec<Vec<(usize, f64)>>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut adjacency = vec![Vec::new(); bound];
+    for u in graph.node_ids() {
+        for (v, w) in graph.outgoing_edges(u) {
+            let Some(cost) = eval_cost(w) else { continue };
+            if cost.is_nan() || cost < 0.0 {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "Closeness vitality needs non-negative edge costs, got {cost}"
+                )));
+            }
+            if u != v {
+                adjacency[u.index()].push((v.index(), cost));
+            }
+        }
+    }
+    Ok(adjacency)
+}
+
+/// One Dijkstra run over `adjacency`, optionally with node `skip` removed.
+struct Search {
+    /// Distance from the source, `INFINITY` when unreached.
+    dist: Vec<f64>,
+    /// Reached nodes in the order they were settled.
+    order: Vec<usize>,
+    /// Shortest-path predecessors, filled only when requested.
+    preds: Vec<Vec<usize>>,
+}
+
+fn search(adjacency: &[Vec<(usize, f64)>], source: usize, skip: usize, record: bool) -> Search {
+    let bound = adjacency.len();
+    let mut dist = vec![f64::INFINITY; bound];
+    let mut settled = vec![false; bound];
+    let mut preds = if record {
+        vec![Vec::new(); bound]
+    } else {
+        Vec::new()
+    };
+    let mut order = Vec::new();
+    let mut heap: BinaryHeap<Reverse<(NotNan<f64>, usize)>> = BinaryHeap::new();
+    dist[source] = 0.0;
+    heap.push(Reverse((NotNan::default(), source)));
+    while let Some(Reverse((d, u))) = heap.pop() {
+        if settled[u] || d.into_inner() > dist[u] {
+            continue;
+        }
+        settled[u] = true;
+        order.push(u);
+        for &(v, cost) in &adjacency[u] {
+            if v == skip || settled[v] {
+                continue;
+            }
+            let next = dist[u] + cost;
+            if next < dist[v] {
+                dist[v] = next;
+                if record {
+                    preds[v].clear();
+                    preds[v].push(u);
+                }
+                if let Ok(key) = NotNan::new(next) {
+                    heap.push(Reverse((key, v)));
+                }
+            } else if record && next == dist[v] {
+                preds[v].push(u);
+            }
+        }
+    }
+    Search { dist, order, preds }
+}
+
+/// Marks the nodes that dominate some other node in the shortest-path DAG of `found`.
+///
+/// Nodes are visited in settling order, so every predecessor's immediate dominator is known
+/// before its successors are reached, and a node's immediate dominator is the lowest common
+/// ancestor of its predecessors. A zero-cost edge into an already settled node is not
+/// recorded as a predecessor; that can only mark more nodes, which costs an extra search but
+/// never a wrong distance.
+fn dominators(found: &Search) -> Vec<bool> {
+    let bound = found.dist.len();
+    let mut idom = vec![NONE; bound];
+    let mut depth = vec![0usize; bound];
+    let mut dominates = vec![false; bound];
+    let Some(&source) = found.order.first() else {
+        return dominates;
+    };
+    idom[source] = source;
+    for &t in &found.order[1..] {
+        let mut preds = found.preds[t].iter().copied();
+        let Some(mut common) = preds.next() else {
+            continue;
+        };
+        for mut p in preds {
+            while p != common {
+                if depth[p] >= depth[common] {
+                    p = idom[p];
+                } else {
+                    common = idom[common];
+                }
+            }
+        }
+        idom[t] = common;
+        depth[t] = depth[common] + 1;
+        dominates[common] = true;
+    }
+    dominates
+}
+
+/// Computes the closeness vitality of every node: the Wiener index of the graph (the sum of
+/// shortest-path distances over all pairs of nodes) minus the Wiener index once the node is
+/// removed.
+///
+/// Pairs are unordered on undirected graphs and ordered on directed ones, matching
+/// networkx's `closeness_vitality`. A node whose removal disconnects the remaining nodes
+/// scores `f64::NEG_INFINITY`. A node that lies on no other shortest path still scores the
+/// distances of the pairs it takes part in. Edge costs come from `eval_cost`, which returns
+/// `None` to leave an edge out; self-loops are ignored. An empty graph gives an empty map.
+///
+/// # Errors
+///
+/// [`GraphinaError::InvalidGraph`] if the graph is not connected (strongly connected if
+/// directed), because its Wiener index is infinite, and
+/// [`GraphinaError::InvalidArgument`] on a negative or NaN cost.
+///
+/// # Time Complexity
+///
+/// O(V * (E + V) log V) for the initial distances, plus one Dijkstra run for every pair of a
+/// source and a node that dominates another node in the source's shortest-path DAG.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::closeness_vitality;
+///
+/// // Removing any corner of a triangle leaves a single edge, so the total distance drops
+/// // from 3 to 1.
+/// let mut g = Graph::<(), f64>::new();
+/// let (a, b, c) = (g.add_node(()), g.add_node(()), g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+/// g.add_edge(c, a, 1.0);
+///
+/// let vitality = closeness_vitality(&g, |w| Some(*w)).unwrap();
+/// assert_eq!(vitality[&a], 2.0);
+/// ```
+pub fn closeness_vitality<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    eval_cost: impl Fn(&W) -> Option<f64>,
+) -> Result<NodeMap<f64>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let nodes: Vec<usize> = graph.node_ids().map(|n| n.index()).collect();
+    let bound = nodes.iter().max().map_or(0, |&m| m + 1);
+    let adjacency = cost_adjacency(graph, bound, eval_cost)?;
+
+    let mut wiener = 0.0;
+    // Wiener index of the graph without each node, summed over the remaining sources.
+    let mut after = vec![0.0; bound];
+    for &source in &nodes {
+        let found = search(&adjacency, source, NONE, true);
+        if found.order.len() < nodes.len() {
+            return Err(GraphinaError::invalid_graph(
+                "Closeness vitality is undefined on a disconnected graph",
+            ));
+        }
+        let row: f64 = found.order.iter().map(|&t| found.dist[t]).sum();
+        wiener += row;
+        let dominates = dominators(&found);
+        for &removed in &nodes {
+            if removed == source || after[removed] == f64::INFINITY {
+                continue;
+            }
+            if !dominates[removed] {
+                after[removed] += row - found.dist[removed];
+                continue;
+            }
+            let rerun = search(&adjacency, source, removed, false);
+            after[removed] = if rerun.order.len() + 1 < nodes.len() {
+                f64::INFINITY
+            } else {
+                after[removed] + rerun.order.iter().map(|&t| rerun.dist[t]).sum::<f64>()
+            };
+        }
+    }
+
+    let pairs = if graph.is_directed() { 1.0 } else { 2.0 };
+    Ok(graph
+        .node_ids()
+        .map(|node| {
+            let rest = after[node.index()];
+            let vitality = if rest == f64::INFINITY {
+                f64::NEG_INFINITY
+            } else {
+                (wiener - rest) / pairs
+            };
+            (node, vitality)
+        })
+        .collect())
+}
+
+/// How removing one node changes the connected components of a graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentImpact {
+    /// Number of components after the removal minus the number before: `-1` for an isolated
+    /// node, `0` for a node whose removal separates nothing, and `k - 1` for a cut vertex
+    /// that splits its component into `k` pieces.
+    pub component_change: isize,
+    /// Size of the largest component left after the removal, anywhere in the graph.
+    pub largest_component: usize,
+    /// Pairs of the other nodes that were connected before the removal and are not after
+    /// it, the pairwise-connectivity loss.
+    pub disconnected_pairs: usize,
+}
+
+/// Computes the [`ComponentImpact`] of removing each node.
+///
+/// Components are weakly connected ones on directed graphs: edge directions are ignored.
+/// Never fails; an empty graph gives an empty map.
+///
+/// # Time Complexity
+///
+/// O(V + E): one iterative DFS finds the cut vertices and the pieces each one leaves.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::metrics::component_impact;
+///
+/// // The middle of a path splits it into two single nodes.
+/// let mut g = Graph::<(), f64>::new();
+/// let (a, b, c) = (g.add_node(()), g.add_node(()), g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let impact = component_impact(&g);
+/// assert_eq!(impact[&b].component_change, 1);
+/// assert_eq!(impact[&b].largest_component, 1);
+/// assert_eq!(impact[&b].disconnected_pairs, 1);
+/// assert_eq!(impact[&a].component_change, 0);
+/// ```
+pub fn component_impact<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> NodeMap<ComponentImpact>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let nodes: Vec<usize> = graph.node_ids().map(|n| n.index()).collect();
+    let bound = nodes.iter().max().map_or(0, |&m| m + 1);
+    let mut neighbors = vec![Vec::new(); bound];
+    for (u, v, _) in graph.edges() {
+        if u != v {
+            neighbors[u.index()].push(v.index());
+            neighbors[v.index()].push(u.index());
+        }
+    }
+
+    let mut disc = vec![NONE; bound];
+    let mut low = vec![0usize; bound];
+    let mut size = vec![1usize; bound];
+    let mut component = vec![0usize; bound];
+    // Per node: the number, total size, largest size, and sum of pairs of the subtrees its
+    // removal cuts off from the rest of its component.
+    let mut cut = vec![(0usize, 0usize, 0usize, 0usize); bound];
+    let mut component_sizes = Vec::new();
+    let mut clock = 0;
+    for &root in &nodes {
+        if disc[root] != NONE {
+            continue;
+        }
+        let id = component_sizes.len();
+        disc[root] = clock;
+        low[root] = clock;
+        clock += 1;
+        component[root] = id;
+        let mut stack = vec![(root, NONE, 0usize)];
+        while let Some(frame) = stack.last_mut() {
+            let (v, parent, next) = *frame;
+            if let Some(&w) = neighbors[v].get(next) {
+                frame.2 += 1;
+                if disc[w] == NONE {
+                    disc[w] = clock;
+                    low[w] = clock;
+                    clock += 1;
+                    component[w] = id;
+                    stack.push((w, v, 0));
+                } else {
+                    low[v] = low[v].min(disc[w]);
+                }
+                continue;
+            }
+            stack.pop();
+            if parent != NONE {
+                low[parent] = low[parent].min(low[v]);
+                size[parent] += size[v];
+                if low[v] >= disc[parent] {
+                    let entry = &mut cut[parent];
+                    entry.0 += 1;
+                    entry.1 += size[v];
+                    entry.2 = entry.2.max(size[v]);
+                    entry.3 += pairs(size[v]);
+                }
+            }
+        }
+        component_sizes.push(size[root]);
+    }
+
+    // The two largest components, so each node can look up the largest one it is not in.
+    let mut largest = (0usize, NONE);
+    let mut second = 0usize;
+    for (id, &s) in component_sizes.iter().enumerate() {
+        if s > largest.0 {
+            second = largest.0;
+            largest = (s, id);
+        } else if s > second {
+            second = s;
+        }
+    }
+
+    graph
+        .node_ids()
+        .map(|node| {
+            let v = node.index();
+            let total = component_sizes[component[v]];
+            let (mut pieces, separated, mut biggest, mut kept_pairs) = cut[v];
+            let rest = total - 1 - separated;
+            if rest > 0 {
+                pieces += 1;
+                biggest = biggest.max(rest);
+                kept_pairs += pairs(rest);
+            }
+            let elsewhere = if largest.1 == component[v] {
+                second
+            } else {
+                largest.0
+            };
+            let impact = ComponentImpact {
+                component_change: pieces as isize - 1,
+                largest_component: biggest.max(elsewhere),
+                disconnected_pairs: pairs(total - 1) - kept_pairs,
+            };
+            (node, impact)
+        })
+        .collect()
+}
+
+/// Number of unordered pairs among `n` items.
+fn pairs(n: usize) -> usize {
+    n * n.saturating_sub(1) / 2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    /// Closeness vitality by brute force: the Wiener index with and without each node.
+    fn brute_force<Ty>(graph: &BaseGraph<(), f64, Ty>) -> NodeMap<f64>
+    where
+        Ty: GraphConstructor<(), f64> + EdgeType,
+    {
+        let wiener = |skip: usize| -> f64 {
+            let bound = graph.node_ids().map(|n| n.index() + 1).max().unwrap_or(0);
+            let adjacency = cost_adjacency(graph, bound, |w| Some(*w)).unwrap();
+            let mut total = 0.0;
+            for source in graph.node_ids().map(|n| n.index()) {
+                if source == skip {
+                    continue;
+                }
+                let found = search(&adjacency, source, skip, false);
+                let expected = graph.node_count() - usize::from(skip != NONE);
+                if found.order.len() < expected {
+                    return f64::INFINITY;
+                }
+                total += found.order.iter().map(|&t| found.dist[t]).sum::<f64>();
+            }
+            total
+        };
+        let scale = if graph.is_directed() { 1.0 } else { 2.0 };
+        let before = wiener(NONE);
+        graph
+            .node_ids()
+            .map(|n| (n, (before - wiener(n.index())) / scale))
+            .collect()
+    }
+
+    fn assert_close(actual: &NodeMap<f64>, expected: &NodeMap<f64>) {
+        for (node, &want) in expected {
+            let got = actual[node];
+            let same = got == want || (got - want).abs() < 1e-9;
+            assert!(same, "node {}: {got} != {want}", node.index());
+        }
+    }
+
+    #[test]
+    fn test_closeness_vitality_matches_brute_force() {
+        // Two weighted squares sharing node 3, plus a chord with a tie at node 5 and a
+        // pendant node at 7: cut vertices, ties, and leaves all appear.
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..8).map(|_| g.add_node(())).collect();
+        for &(u, v, w) in &[
+            (0, 1, 1.0),
+            (1, 2, 2.0),
+            (2, 3, 1.0),
+            (3, 0, 2.0),
+            (3, 4, 1.0),
+            (4, 5, 1.0),
+            (5, 6, 1.0),
+            (6, 3, 1.0),
+            (4, 6, 2.0),
+            (6, 7, 3.0),
+        ] {
+            g.add_edge(n[u], n[v], w);
+        }
+        let vitality = closeness_vitality(&g, |w| Some(*w)).unwrap();
+        let expected = brute_force(&g);
+        assert_close(&vitality, &expected);
+        assert_eq!(vitality[&n[3]], f64::NEG_INFINITY);
+        assert_eq!(vitality[&n[6]], f64::NEG_INFINITY);
+
+        // A bidirected 4-cycle with asymmetric costs and a shortcut: every removal leaves a
+        // strongly connected path, so all scores are finite.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..4).map(|_| d.add_node(())).collect();
+        for i in 0..4 {
+            d.add_edge(m[i], m[(i + 1) % 4], 1.0);
+            d.add_edge(m[(i + 1) % 4], m[i], 3.0);
+        }
+        d.add_edge(m[0], m[2], 1.5);
+        let vitality = closeness_vitality(&d, |w| Some(*w)).unwrap();
+        let expected = brute_force(&d);
+        assert_close(&vitality, &expected);
+        assert!(vitality.values().all(|v| v.is_finite()));
+
+        g.add_node(());
+        assert!(closeness_vitality(&g, |w| Some(*w)).is_err());
+        assert!(closeness_vitality(&d, |w| Some(-*w)).is_err());
+    }
+
+    #[test]
+    fn test_component_impact() {
+        // A triangle 0-1-2 with a path 2-3-4 hanging off it, an isolated node 5, and a
+        // separate edge 6-7.
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..8).map(|_| g.add_node(())).collect();
+        for &(u, v) in &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (6, 7)] {
+            g.add_edge(n[u], n[v], 1.0);
+        }
+        let impact = component_impact(&g);
+        let get = |i: usize| impact[&n[i]];
+        assert_eq!(
+            get(2),
+            ComponentImpact {
+                component_change: 1,
+                largest_component: 2,
+                // {0, 1} and {3, 4} lose their 2 * 2 cross pairs.
+                disconnected_pairs: 4,
+            }
+        );
+        assert_eq!(get(3).component_change, 1);
+        assert_eq!(get(3).largest_component, 3);
+        assert_eq!(get(3).disconnected_pairs, 3);
+        assert_eq!(get(0).component_change, 0);
+        assert_eq!(get(0).largest_component, 4);
+        assert_eq!(get(0).disconnected_pairs, 0);
+        assert_eq!(get(5).component_change, -1);
+        assert_eq!(get(5).largest_component, 5);
+        assert_eq!(get(6).component_change, 0);
+
+        // Directions are ignored: the middle of a directed path is still a cut vertex.
+        let mut d = Digraph::<(), f64>::new();
+        let m: Vec<_> = (0..3).map(|_| d.add_node(())).collect();
+        d.add_edge(m[0], m[1], 1.0);
+        d.add_edge(m[2], m[1], 1.0);
+        assert_eq!(component_impact(&d)[&m[1]].component_change, 1);
+        assert!(component_impact(&Graph::<(), f64>::new()).is_empty());
+    }
+}
@@ -0,0 +1,253 @@
+/*!
+# Machine Learning on Node Features
+
+Thin wrappers around [linfa](https://github.com/rust-ml/linfa) that run clustering and
+classification over per-node features, such as centrality scores, metrics, or embeddings,
+and hand results back as `NodeMap`s keyed by the graph's own node ids.
+
+Features are assembled with `core::features::node_feature_matrix`, so every map passed in
+becomes one column and every node one row. Missing values are rejected rather than imputed;
+fill them before calling these functions if the graph has nodes without a score.
+
+Requires the `ml` feature (which implies `ndarray`).
+
+Independent of other extensions; depends only on core.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::features::node_feature_matrix;
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use linfa::DatasetBase;
+use linfa::traits::{Fit, Predict};
+use linfa_clustering::KMeans;
+use linfa_logistic::MultiLogisticRegression;
+use ndarray::{Array1, Array2, Axis};
+use petgraph::EdgeType;
+use rand_xoshiro::Xoshiro256Plus;
+use rand_xoshiro::rand_core::SeedableRng;
+
+/// Seed used by [`kmeans_nodes`] when the caller does not supply one.
+const DEFAULT_SEED: u64 = 42;
+
+/// Builds the feature matrix and rejects empty or non-finite input.
+fn finite_feature_matrix<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    features: &[&NodeMap<f64>],
+) -> Result<(Vec<NodeId>, Array2<f64>)>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    if graph.is_empty() {
+        return Err(GraphinaError::invalid_graph("Graph has no nodes"));
+    }
+    if features.is_empty() {
+        return Err(GraphinaError::invalid_argument(
+            "At least one feature map is required",
+        ));
+    }
+    let (rows, x) = node_feature_matrix(graph, features);
+    if let Some((i, _)) = x
+        .axis_iter(Axis(0))
+        .enumerate()
+        .find(|(_, row)| row.iter().any(|v| !v.is_finite()))
+    {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Node {:?} has a missing or non-finite feature value",
+            rows[i]
+        )));
+    }
+    Ok((rows, x))
+}
+
+/// Clusters nodes by their features with k-means and returns each node's cluster index.
+///
+/// Cluster indices lie in `0..k`. The run is deterministic for a given `seed`; `None`
+/// uses a fixed default seed so repeated calls agree.
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` for an empty graph, `InvalidArgument` when `k` is zero or larger
+/// than the number of nodes, when no feature maps are given, or when a node lacks a finite
+/// value in some map, and `AlgorithmError` if linfa fails to fit.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::{Graph, NodeMap};
+/// use graphina::ml::kmeans_nodes;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let nodes: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// let score: NodeMap<f64> = nodes
+///     .iter()
+///     .zip([0.0, 0.1, 5.0, 5.1])
+///     .map(|(&n, v)| (n, v))
+///     .collect();
+///
+/// let clusters = kmeans_nodes(&g, &[&score], 2, Some(7)).unwrap();
+/// assert_eq!(clusters[&nodes[0]], clusters[&nodes[1]]);
+/// assert_ne!(clusters[&nodes[0]], clusters[&nodes[2]]);
+/// ```
+pub fn kmeans_nodes<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    features: &[&NodeMap<f64>],
+    k: usize,
+    seed: Option<u64>,
+) -> Result<NodeMap<usize>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let (rows, x) = finite_feature_matrix(graph, features)?;
+    if k == 0 || k > rows.len() {
+        return Err(GraphinaError::invalid_argument(format!(
+            "k must be between 1 and the number of nodes ({}), got {}",
+            rows.len(),
+            k
+        )));
+    }
+
+    let rng = Xoshiro256Plus::seed_from_u64(seed.unwrap_or(DEFAULT_SEED));
+    let dataset = DatasetBase::from(x);
+    let model = KMeans::params_with_rng(k, rng)
+        .fit(&dataset)
+        .map_err(|e| GraphinaError::algorithm_error(format!("k-means failed: {e}")))?;
+    let assignment: Array1<usize> = model.predict(dataset.records());
+
+    Ok(rows.into_iter().zip(assignment).collect())
+}
+
+/// Trains a multinomial logistic regression on labeled nodes and predicts a class for
+/// every node in the graph.
+///
+/// `labels` supplies the training set: nodes present in the map are used for fitting, and
+/// the returned map covers all nodes, including the labeled ones. Class labels can be any
+/// `usize` values, but at least two distinct classes are required. `max_iterations` bounds the
+/// optimizer; the features are not rescaled, so normalize them first when their ranges
+/// differ widely.
+///
+/// # Errors
+///
+/// Returns `InvalidGraph` for an empty graph, `NodeNotFound` when a labeled node is not in
+/// the graph, `InvalidArgument` for missing or non-finite features, fewer than two classes,
+/// or a zero iteration budget, and `AlgorithmError` if the solver fails.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::{Graph, NodeMap};
+/// use graphina::ml::logistic_regression_nodes;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let nodes: Vec<_> = (0..6).map(|_| g.add_node(())).collect();
+/// let score: NodeMap<f64> = nodes
+///     .iter()
+///     .zip([-2.0, -1.5, -1.0, 1.0, 1.5, 2.0])
+///     .map(|(&n, v)| (n, v))
+///     .collect();
+/// let labels: NodeMap<usize> = [(nodes[0], 0), (nodes[1], 0), (nodes[4], 1), (nodes[5], 1)]
+///     .into_iter()
+///     .collect();
+///
+/// let predicted = logistic_regression_nodes(&g, &[&score], &labels, 100).unwrap();
+/// assert_eq!(predicted[&nodes[2]], 0);
+/// assert_eq!(predicted[&nodes[3]], 1);
+/// ```
+pub fn logistic_regression_nodes<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    features: &[&NodeMap<f64>],
+    labels: &NodeMap<usize>,
+    max_iterations: u64,
+) -> Result<NodeMap<usize>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    if max_iterations == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "max_iterations must be positive",
+        ));
+    }
+    let (rows, x) = finite_feature_matrix(graph, features)?;
+    if let Some(node) = labels.keys().find(|n| !graph.contains_node(**n)) {
+        return Err(GraphinaError::node_not_found(format!(
+            "Labeled node {node:?} is not in the graph"
+        )));
+    }
+
+    let train_rows: Vec<usize> = (0..rows.len())
+        .filter(|&i| labels.contains_key(&rows[i]))
+        .collect();
+    let targets: Array1<usize> = train_rows.iter().map(|&i| labels[&rows[i]]).collect();
+    let mut classes: Vec<usize> = targets.to_vec();
+    classes.sort_unstable();
+    classes.dedup();
+    if classes.len() < 2 {
+        return Err(GraphinaError::invalid_argument(
+            "Labels must cover at least two distinct classes",
+        ));
+    }
+
+    let train = DatasetBase::new(x.select(Axis(0), &train_rows), targets);
+    let model = MultiLogisticRegression::default()
+        .max_iterations(max_iterations)
+        .fit(&train)
+        .map_err(|e| GraphinaError::algorithm_error(format!("Logistic regression failed: {e}")))?;
+    let predicted: Array1<usize> = model.predict(&x);
+
+    Ok(rows.into_iter().zip(predicted).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    fn two_blobs() -> (Graph<(), f64>, Vec<NodeId>, NodeMap<f64>, NodeMap<f64>) {
+        let mut g = Graph::<(), f64>::new();
+        let nodes: Vec<NodeId> = (0..6).map(|_| g.add_node(())).collect();
+        let xs = [0.0, 0.2, 0.1, 9.0, 9.1, 8.9];
+        let ys = [0.0, 0.1, 0.3, 9.0, 8.8, 9.2];
+        let fx = nodes.iter().copied().zip(xs).collect();
+        let fy = nodes.iter().copied().zip(ys).collect();
+        (g, nodes, fx, fy)
+    }
+
+    #[test]
+    fn test_kmeans_separates_blobs_deterministically() {
+        let (g, nodes, fx, fy) = two_blobs();
+        let first = kmeans_nodes(&g, &[&fx, &fy], 2, Some(3)).unwrap();
+        let second = kmeans_nodes(&g, &[&fx, &fy], 2, Some(3)).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 6);
+        assert!(nodes[..3].iter().all(|n| first[n] == first[&nodes[0]]));
+        assert!(nodes[3..].iter().all(|n| first[n] == first[&nodes[3]]));
+        assert_ne!(first[&nodes[0]], first[&nodes[3]]);
+    }
+
+    #[test]
+    fn test_kmeans_rejects_bad_input() {
+        let (g, nodes, mut fx, _) = two_blobs();
+        assert!(kmeans_nodes(&g, &[&fx], 0, None).is_err());
+        assert!(kmeans_nodes(&g, &[&fx], 7, None).is_err());
+        assert!(kmeans_nodes(&g, &[], 2, None).is_err());
+        fx.remove(&nodes[1]);
+        assert!(kmeans_nodes(&g, &[&fx], 2, None).is_err());
+        let empty = Digraph::<(), ()>::new();
+        assert!(kmeans_nodes(&empty, &[&fx], 1, None).is_err());
+    }
+
+    #[test]
+    fn test_logistic_regression_labels_unlabeled_nodes() {
+        let (g, nodes, fx, fy) = two_blobs();
+        let labels: NodeMap<usize> = [(nodes[0], 4), (nodes[1], 4), (nodes[3], 9), (nodes[4], 9)]
+            .into_iter()
+            .collect();
+        let predicted = logistic_regression_nodes(&g, &[&fx, &fy], &labels, 200).unwrap();
+        assert_eq!(predicted.len(), 6);
+        assert_eq!(predicted[&nodes[2]], 4);
+        assert_eq!(predicted[&nodes[5]], 9);
+
+        let one_class: NodeMap<usize> = [(nodes[0], 1), (nodes[1], 1)].into_iter().collect();
+        assert!(logistic_regression_nodes(&g, &[&fx], &one_class, 50).is_err());
+        assert!(logistic_regression_nodes(&g, &[&fx], &labels, 0).is_err());
+    }
+}
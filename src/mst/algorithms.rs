@@ -27,8 +27,8 @@ If other required conditions are violated, the algorithm may also signal an erro
 
 use crate::core::error::{GraphinaError, Result};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use crate::core::union_find::UnionFind;
 use rayon::prelude::*;
-use std::cmp::Ordering;
 use std::convert::From;
 use std::ops::{Add, AddAssign, Sub};
 
@@ -55,47 +55,6 @@ where
         .map_or(0, |m| m + 1)
 }
 
-/// A simple union–find (disjoint-set) data structure.
-struct UnionFind {
-    parent: Vec<usize>,
-    rank: Vec<usize>,
-}
-
-impl UnionFind {
-    /// Creates a new union–find structure for `n` elements.
-    fn new(n: usize) -> Self {
-        Self {
-            parent: (0..n).collect(),
-            rank: vec![0; n],
-        }
-    }
-
-    /// Finds the representative of the set that contains `i`, using path compression.
-    fn find(&mut self, i: usize) -> usize {
-        if self.parent[i] != i {
-            self.parent[i] = self.find(self.parent[i]);
-        }
-        self.parent[i]
-    }
-
-    /// Unions the sets containing `i` and `j` using union by rank.
-    fn union(&mut self, i: usize, j: usize) {
-        let i = self.find(i);
-        let j = self.find(j);
-        if i == j {
-            return;
-        }
-        match self.rank[i].cmp(&self.rank[j]) {
-            Ordering::Less => self.parent[i] = j,
-            Ordering::Greater => self.parent[j] = i,
-            Ordering::Equal => {
-                self.parent[j] = i;
-                self.rank[i] += 1;
-            }
-        }
-    }
-}
-
 /// Represents an edge in the MST.
 #[derive(Debug, Clone, Copy)]
 pub struct MstEdge<W> {
@@ -1,17 +1,135 @@
 /*!
 Parallel breadth-first search algorithms
+
+Each search is direction-optimizing (Beamer, Asanović, and Patterson, 2012). While the
+frontier is small, it runs top-down: the frontier nodes claim their unvisited neighbors.
+Once the frontier's edges outnumber a fraction of the edges still unexplored, which on
+power-law graphs happens within a few levels of reaching a hub, it switches to bottom-up:
+every unvisited node looks for any parent in the frontier and stops at the first one.
+It switches back when the frontier shrinks again. Both steps run in parallel over a
+compressed sparse row snapshot of the graph, with the visited set kept as an atomic bitmap.
 */
 
 use rayon::prelude::*;
-use std::collections::{HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use super::csr::Csr;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
 use petgraph::EdgeType;
 
+/// Switch to bottom-up once the frontier has more than `1 / ALPHA` of the unexplored edges.
+const ALPHA: usize = 14;
+/// Switch back to top-down once the frontier has fewer than `1 / BETA` of the nodes.
+const BETA: usize = 24;
+/// Smallest batch of frontier nodes or bitmap words handed to one task.
+const MIN_TASK: usize = 64;
+
+/// A fixed-size bitmap whose bits can be set concurrently.
+struct AtomicBitmap {
+    words: Vec<AtomicU64>,
+}
+
+impl AtomicBitmap {
+    fn new(n: usize) -> Self {
+        Self {
+            words: (0..n.div_ceil(64)).map(|_| AtomicU64::new(0)).collect(),
+        }
+    }
+
+    fn contains(&self, i: usize) -> bool {
+        self.words[i / 64].load(Ordering::Relaxed) & (1 << (i % 64)) != 0
+    }
+
+    /// Sets bit `i`, returning `true` if this call is the one that set it.
+    fn insert(&self, i: usize) -> bool {
+        let mask = 1 << (i % 64);
+        self.words[i / 64].fetch_or(mask, Ordering::Relaxed) & mask == 0
+    }
+}
+
+/// Top-down step: frontier nodes claim their unvisited successors.
+fn top_down(csr: &Csr, frontier: &[usize], visited: &AtomicBitmap) -> Vec<usize> {
+    frontier
+        .par_iter()
+        .with_min_len(MIN_TASK)
+        .flat_map_iter(|&u| {
+            csr.successors(u)
+                .iter()
+                .copied()
+                .filter(|&w| visited.insert(w))
+        })
+        .collect()
+}
+
+/// Bottom-up step: unvisited nodes look for a predecessor in the frontier.
+fn bottom_up(csr: &Csr, frontier: &[usize], visited: &AtomicBitmap) -> Vec<usize> {
+    let n = csr.len();
+    let mut in_frontier = vec![0u64; n.div_ceil(64)];
+    for &v in frontier {
+        in_frontier[v / 64] |= 1 << (v % 64);
+    }
+    let next: Vec<usize> = (0..in_frontier.len())
+        .into_par_iter()
+        .with_min_len(MIN_TASK)
+        .flat_map_iter(|word| {
+            let in_frontier = &in_frontier;
+            (word * 64..((word + 1) * 64).min(n)).filter(move |&v| {
+                !visited.contains(v)
+                    && csr
+                        .predecessors(v)
+                        .iter()
+                        .any(|&u| in_frontier[u / 64] & (1 << (u % 64)) != 0)
+            })
+        })
+        .collect();
+    for &v in &next {
+        visited.insert(v);
+    }
+    next
+}
+
+/// Direction-optimizing BFS from `source`, returning positions level by level.
+fn search(csr: &Csr, source: usize) -> Vec<usize> {
+    let n = csr.len();
+    let visited = AtomicBitmap::new(n);
+    visited.insert(source);
+    let mut order = vec![source];
+    let mut frontier = vec![source];
+    let mut frontier_edges = csr.successors(source).len();
+    let mut unexplored_edges = csr.edge_count() - frontier_edges;
+    let mut use_bottom_up = false;
+
+    while !frontier.is_empty() {
+        use_bottom_up = if use_bottom_up {
+            frontier.len() >= n / BETA
+        } else {
+            frontier_edges > unexplored_edges / ALPHA
+        };
+        let mut next = if use_bottom_up {
+            bottom_up(csr, &frontier, &visited)
+        } else {
+            top_down(csr, &frontier, &visited)
+        };
+        next.par_sort_unstable();
+        frontier_edges = next.iter().map(|&v| csr.successors(v).len()).sum();
+        unexplored_edges = unexplored_edges.saturating_sub(frontier_edges);
+        order.extend_from_slice(&next);
+        frontier = next;
+    }
+    order
+}
+
 /// Parallel breadth-first search from multiple starting nodes.
 ///
 /// Processes multiple BFS searches in parallel, useful for computing shortest paths
-/// from multiple sources simultaneously.
+/// from multiple sources simultaneously. Each search is itself parallel and
+/// direction-optimizing (see the module documentation), and the graph is snapshotted
+/// once and shared by all searches.
+///
+/// Returns, for each start in input order, the nodes reachable from it following edge
+/// direction. They are listed level by level, starting with the start itself, and in
+/// node order within a level, so the result does not depend on the thread count. A
+/// start that is not in the graph gets an empty list.
 ///
 /// # Example
 ///
@@ -29,6 +147,7 @@ use petgraph::EdgeType;
 /// let starts = vec![n1, n2];
 /// let results = bfs_parallel(&g, &starts);
 /// assert_eq!(results.len(), 2);
+/// assert_eq!(results[1][0], n2);
 /// ```
 pub fn bfs_parallel<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, starts: &[NodeId]) -> Vec<Vec<NodeId>>
 where
@@ -36,27 +155,15 @@ where
     W: Sync,
     Ty: GraphConstructor<A, W> + EdgeType + Sync,
 {
+    let csr = Csr::new(graph);
     starts
         .par_iter()
-        .map(|&start| {
-            let mut visited = Vec::new();
-            let mut queue = VecDeque::new();
-            let mut seen = HashSet::new();
-
-            queue.push_back(start);
-            seen.insert(start);
-
-            while let Some(node) = queue.pop_front() {
-                visited.push(node);
-
-                for neighbor in graph.neighbors(node) {
-                    if seen.insert(neighbor) {
-                        queue.push_back(neighbor);
-                    }
-                }
-            }
-
-            visited
+        .map(|&start| match csr.position(start) {
+            Some(source) => search(&csr, source)
+                .into_iter()
+                .map(|v| csr.node(v))
+                .collect(),
+            None => Vec::new(),
         })
         .collect()
 }
@@ -64,7 +171,8 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::Graph;
+    use crate::core::types::{Digraph, Graph};
+    use std::collections::{HashMap, VecDeque};
 
     #[test]
     fn test_bfs_parallel() {
@@ -80,4 +188,51 @@ mod tests {
         assert_eq!(results[0].len(), 3);
         assert_eq!(results[1].len(), 3);
     }
+
+    /// Hop distances by a plain queue-based BFS.
+    fn reference_levels(g: &Digraph<(), f64>, start: NodeId) -> HashMap<NodeId, usize> {
+        let mut dist = HashMap::from([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+        while let Some(u) = queue.pop_front() {
+            for w in g.neighbors(u) {
+                if !dist.contains_key(&w) {
+                    dist.insert(w, dist[&u] + 1);
+                    queue.push_back(w);
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_bfs_parallel_switches_direction_on_hubs() {
+        // Hubs pointing at thousands of nodes force bottom-up steps; the leaves then point
+        // at a long chain, which brings the search back to top-down.
+        let mut g = Digraph::<(), f64>::new();
+        let nodes: Vec<NodeId> = (0..6000).map(|_| g.add_node(())).collect();
+        for hub in 1..4 {
+            g.add_edge(nodes[0], nodes[hub], 1.0);
+            for leaf in (4..5000).filter(|leaf| leaf % 3 == hub % 3) {
+                g.add_edge(nodes[hub], nodes[leaf], 1.0);
+                g.add_edge(nodes[leaf], nodes[(leaf * 7) % 5000], 1.0);
+            }
+        }
+        for i in 4999..5999 {
+            g.add_edge(nodes[i], nodes[i + 1], 1.0);
+        }
+        g.remove_node(nodes[2500]);
+
+        let results = bfs_parallel(&g, &[nodes[0], nodes[5500], nodes[2500]]);
+        for (start, order) in [nodes[0], nodes[5500]].iter().zip(&results) {
+            let levels = reference_levels(&g, *start);
+            assert_eq!(order.len(), levels.len());
+            assert_eq!(order[0], *start);
+            for pair in order.windows(2) {
+                let (a, b) = (levels[&pair[0]], levels[&pair[1]]);
+                assert!(a < b || (a == b && pair[0].index() < pair[1].index()));
+            }
+        }
+        assert_eq!(results[1].len(), 500);
+        assert!(results[2].is_empty());
+    }
 }
@@ -2,15 +2,21 @@
 Parallel clustering coefficient computation
 */
 
-use rayon::prelude::*;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 
+use super::config::ParallelConfig;
+use super::csr::Csr;
+use super::triangles::linked_successor_pairs;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
 use petgraph::EdgeType;
 
 /// Parallel computation of clustering coefficients for all nodes.
 ///
-/// Computes local clustering coefficient for each node in parallel.
+/// Computes local clustering coefficient for each node in parallel, over a
+/// compressed sparse row snapshot with one scratch buffer per worker thread.
+/// Self-loops and parallel edges are ignored. On a directed graph a node's
+/// neighbors are its successors, and two of them count as linked when an edge
+/// joins them in either direction.
 ///
 /// # Example
 ///
@@ -37,43 +43,69 @@ where
     W: Sync,
     Ty: GraphConstructor<A, W> + EdgeType + Sync,
 {
-    let nodes: Vec<NodeId> = graph.node_ids().collect();
-
-    // Precompute every node's neighbor set once (O(V + E)) and share it read-only
-    // across the rayon tasks, so the inner adjacency test is an O(1) hash lookup
-    // instead of an O(degree) `contains_edge` scan.
-    let adjacency: HashMap<NodeId, HashSet<NodeId>> = nodes
-        .iter()
-        .map(|&node| (node, graph.neighbors(node).collect()))
-        .collect();
-
-    nodes
-        .par_iter()
-        .map(|&node| {
-            let neighbors: Vec<NodeId> = graph.neighbors(node).collect();
-            let k = neighbors.len();
-
+    let csr = Csr::new(graph);
+    let links = linked_successor_pairs(&csr, !graph.is_directed());
+    links
+        .into_iter()
+        .enumerate()
+        .map(|(v, linked)| {
+            let k = csr.successors(v).len();
             let coefficient = if k < 2 {
                 0.0
             } else {
-                let mut triangles = 0;
-                for i in 0..neighbors.len() {
-                    let si = &adjacency[&neighbors[i]];
-                    for other in &neighbors[i + 1..] {
-                        if si.contains(other) {
-                            triangles += 1;
-                        }
-                    }
-                }
-                let possible_edges = k * (k - 1) / 2;
-                triangles as f64 / possible_edges as f64
+                linked as f64 / (k * (k - 1) / 2) as f64
             };
-
-            (node, coefficient)
+            (csr.node(v), coefficient)
         })
         .collect()
 }
 
+/// Parallel average clustering coefficient.
+///
+/// The mean of [`clustering_coefficients_parallel`] over all nodes, `0.0` for an
+/// empty graph. Per-node coefficients never depend on scheduling, but their mean
+/// is a floating-point sum; pass [`ParallelConfig::deterministic`] for a
+/// bit-stable result.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::parallel::{average_clustering_parallel, ParallelConfig};
+///
+/// let mut g = Graph::<i32, f64>::new();
+/// let n1 = g.add_node(1);
+/// let n2 = g.add_node(2);
+/// let n3 = g.add_node(3);
+/// let n4 = g.add_node(4);
+/// g.add_edge(n1, n2, 1.0);
+/// g.add_edge(n2, n3, 1.0);
+/// g.add_edge(n3, n1, 1.0);
+/// g.add_edge(n3, n4, 1.0);
+///
+/// let avg = average_clustering_parallel(&g, &ParallelConfig::deterministic());
+/// assert!((avg - (1.0 + 1.0 + 1.0 / 3.0) / 4.0).abs() < 1e-12);
+/// ```
+pub fn average_clustering_parallel<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    config: &ParallelConfig,
+) -> f64
+where
+    A: Sync,
+    W: Sync,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync,
+{
+    let coefficients = clustering_coefficients_parallel(graph);
+    if coefficients.is_empty() {
+        return 0.0;
+    }
+    let values: Vec<f64> = graph
+        .node_ids()
+        .map(|node| coefficients.get(&node).copied().unwrap_or(0.0))
+        .collect();
+    config.sum(&values) / values.len() as f64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
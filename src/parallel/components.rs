@@ -1,17 +1,23 @@
 /*!
-Parallel connected components detection
+Parallel connected components detection, and running an algorithm on each component in parallel
 */
 
-use std::collections::{HashMap, HashSet, VecDeque};
+use rayon::prelude::*;
+use std::collections::HashMap;
 
-use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use crate::core::error::Result;
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+use crate::core::union_find::ConcurrentUnionFind;
 use petgraph::EdgeType;
 
 /// Parallel connected components detection.
 ///
-/// Finds all connected components in parallel by processing multiple starting points.
+/// Merges the endpoints of all edges in parallel through a shared
+/// [`ConcurrentUnionFind`], ignoring edge direction.
 ///
-/// Returns a mapping from node to component ID.
+/// Returns a mapping from node to component ID. IDs are numbered from zero in
+/// the order components are first reached in node iteration order, as in the
+/// sequential `connected_components`, so they do not depend on the thread count.
 ///
 /// # Example
 ///
@@ -47,32 +53,22 @@ where
     W: Sync + Send,
     Ty: GraphConstructor<A, W> + EdgeType + Sync + Send,
 {
-    let nodes: Vec<NodeId> = graph.node_ids().collect();
-    let mut component_map: HashMap<NodeId, usize> = HashMap::with_capacity(nodes.len());
-    let mut visited: HashSet<NodeId> = HashSet::new();
-    let mut current_id: usize = 0;
-
-    for node in nodes {
-        if visited.contains(&node) {
-            continue;
-        }
-
-        let mut queue = VecDeque::new();
-        queue.push_back(node);
-        visited.insert(node);
-
-        while let Some(current) = queue.pop_front() {
-            component_map.insert(current, current_id);
-            for neighbor in graph.neighbors(current) {
-                if visited.insert(neighbor) {
-                    queue.push_back(neighbor);
-                }
-            }
-        }
+    let uf = ConcurrentUnionFind::new(graph.node_ids().map(|n| n.index() + 1).max().unwrap_or(0));
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .map(|(u, v, _)| (u.index(), v.index()))
+        .collect();
+    edges.par_iter().for_each(|&(u, v)| {
+        uf.union(u, v);
+    });
 
-        current_id += 1;
+    let mut id_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut component_map: HashMap<NodeId, usize> = HashMap::with_capacity(graph.node_count());
+    for node in graph.node_ids() {
+        let next_id = id_of_root.len();
+        let id = *id_of_root.entry(uf.find(node.index())).or_insert(next_id);
+        component_map.insert(node, id);
     }
-
     component_map
 }
 
@@ -96,10 +92,167 @@ where
         .collect()
 }
 
+/// One connected component of a graph, copied into a graph of its own.
+#[derive(Debug, Clone)]
+pub struct Component<A, W, Ty: GraphConstructor<A, W> + EdgeType> {
+    /// The component's nodes and edges. Its node IDs are numbered from zero.
+    pub graph: BaseGraph<A, W, Ty>,
+    /// `original[i]` is the ID, in the graph that was split, of the component's node `i`.
+    pub original: Vec<NodeId>,
+}
+
+impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Component<A, W, Ty> {
+    /// Translates a node ID of the component graph to the ID in the graph that was split.
+    pub fn original_id(&self, node: NodeId) -> Option<NodeId> {
+        self.original.get(node.index()).copied()
+    }
+}
+
+/// Splits a graph into its connected components, ignoring edge direction.
+///
+/// Components come in the order of their IDs from [`connected_components_parallel`], and the
+/// nodes of each keep their relative order. Edges keep their direction and weight.
+pub fn split_components_parallel<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Vec<Component<A, W, Ty>>
+where
+    A: Clone + Sync + Send,
+    W: Clone + Sync + Send,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync + Send,
+{
+    let component_of = connected_components_parallel(graph);
+    let count = component_of.values().max().map_or(0, |&c| c + 1);
+
+    let mut members: Vec<Vec<NodeId>> = vec![Vec::new(); count];
+    let mut local: HashMap<NodeId, usize> = HashMap::with_capacity(graph.node_count());
+    for node in graph.node_ids() {
+        if let Some(&c) = component_of.get(&node) {
+            local.insert(node, members[c].len());
+            members[c].push(node);
+        }
+    }
+    let mut edges: Vec<Vec<(usize, usize, &W)>> = vec![Vec::new(); count];
+    for (u, v, weight) in graph.edges() {
+        if let (Some(&c), Some(&lu), Some(&lv)) =
+            (component_of.get(&u), local.get(&u), local.get(&v))
+        {
+            edges[c].push((lu, lv, weight));
+        }
+    }
+
+    members
+        .into_par_iter()
+        .zip(edges.into_par_iter())
+        .map(|(original, edges)| {
+            let mut sub = BaseGraph::<A, W, Ty>::with_capacity(original.len(), edges.len());
+            let ids: Vec<NodeId> = original
+                .iter()
+                .filter_map(|&node| graph.node_attr(node))
+                .map(|attr| sub.add_node(attr.clone()))
+                .collect();
+            for (u, v, weight) in edges {
+                sub.add_edge(ids[u], ids[v], weight.clone());
+            }
+            Component {
+                graph: sub,
+                original,
+            }
+        })
+        .collect()
+}
+
+/// Runs `f` on every connected component in parallel and returns the results in component
+/// order.
+///
+/// This suits algorithms with one result per component, such as a diameter or a spanning
+/// tree. Each [`Component`] translates its own node IDs back with
+/// [`Component::original_id`]. All components are copied out of `graph` before `f` runs, so
+/// memory use briefly doubles.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::parallel::map_components_parallel;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[2], 1.0);
+/// g.add_edge(n[3], n[4], 1.0);
+///
+/// let sizes = map_components_parallel(&g, |c| c.graph.node_count());
+/// assert_eq!(sizes, vec![3, 2]);
+/// ```
+pub fn map_components_parallel<A, W, Ty, R, F>(graph: &BaseGraph<A, W, Ty>, f: F) -> Vec<R>
+where
+    A: Clone + Sync + Send,
+    W: Clone + Sync + Send,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync + Send,
+    R: Send,
+    F: Fn(&Component<A, W, Ty>) -> R + Sync,
+{
+    split_components_parallel(graph)
+        .par_iter()
+        .map(&f)
+        .collect()
+}
+
+/// Runs a per-node algorithm on every connected component in parallel and merges the results.
+///
+/// `f` sees each component as a graph of its own, so any function from a graph to a
+/// `Result<NodeMap<T>>` can be passed directly. The merged map is keyed by the node IDs of
+/// `graph`. If `f` fails on any component, the error of the first such component is returned.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::{Graph, NodeMap};
+/// use graphina::parallel::for_each_component_parallel;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..5).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[1], n[2], 1.0);
+/// g.add_edge(n[3], n[4], 1.0);
+///
+/// // The size of the component each node belongs to.
+/// let sizes = for_each_component_parallel(&g, |c| {
+///     Ok(c.node_ids().map(|node| (node, c.node_count())).collect::<NodeMap<_>>())
+/// })
+/// .unwrap();
+/// assert_eq!(sizes[&n[2]], 3);
+/// assert_eq!(sizes[&n[3]], 2);
+/// ```
+pub fn for_each_component_parallel<A, W, Ty, T, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    f: F,
+) -> Result<NodeMap<T>>
+where
+    A: Clone + Sync + Send,
+    W: Clone + Sync + Send,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync + Send,
+    T: Send,
+    F: Fn(&BaseGraph<A, W, Ty>) -> Result<NodeMap<T>> + Sync,
+{
+    let results = map_components_parallel(graph, |component| {
+        f(&component.graph).map(|values| {
+            values
+                .into_iter()
+                .filter_map(|(node, value)| component.original_id(node).map(|id| (id, value)))
+                .collect::<Vec<_>>()
+        })
+    });
+    let mut merged = NodeMap::with_capacity_and_hasher(graph.node_count(), Default::default());
+    for result in results {
+        merged.extend(result?);
+    }
+    Ok(merged)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::core::types::Graph;
+    use std::collections::HashSet;
 
     #[test]
     fn test_connected_components_parallel() {
@@ -139,4 +292,63 @@ mod tests {
         assert!(list.iter().any(|c| c.contains(&n1) && c.contains(&n2)));
         assert!(list.iter().any(|c| c.contains(&n3) && c.contains(&n4)));
     }
+
+    #[test]
+    fn test_connected_components_parallel_ids_follow_node_order() {
+        let mut g = Graph::<usize, f64>::new();
+        let nodes: Vec<NodeId> = (0..2000).map(|i| g.add_node(i)).collect();
+        // Node i joins i + 10 within each block of 100, giving 20 blocks of 10 chains.
+        for block in 0..20 {
+            for j in 0..90 {
+                let i = block * 100 + j;
+                g.add_edge(nodes[i + 10], nodes[i], 1.0);
+            }
+        }
+        g.remove_node(nodes[5]);
+        let map = connected_components_parallel(&g);
+        assert_eq!(map.len(), 1999);
+        let distinct: HashSet<usize> = map.values().copied().collect();
+        // Chain 5 of the first block survives the removal of its head, node 5.
+        assert_eq!(distinct.len(), 200);
+        assert_eq!(map[&nodes[0]], 0);
+        assert_eq!(map[&nodes[9]], 8);
+        assert_eq!(map[&nodes[15]], 9);
+        assert_eq!(map[&nodes[1909]], 199);
+    }
+
+    #[test]
+    fn test_for_each_component_parallel_maps_ids_back() {
+        use crate::core::error::GraphinaError;
+        use crate::core::types::Digraph;
+        let mut g = Digraph::<usize, f64>::new();
+        let nodes: Vec<NodeId> = (0..6).map(|i| g.add_node(i)).collect();
+        g.remove_node(nodes[0]);
+        g.add_edge(nodes[2], nodes[1], 1.0);
+        g.add_edge(nodes[3], nodes[4], 1.0);
+        g.add_edge(nodes[5], nodes[4], 1.0);
+
+        let parts = split_components_parallel(&g);
+        let originals: Vec<Vec<NodeId>> = parts.iter().map(|c| c.original.clone()).collect();
+        assert_eq!(originals, vec![nodes[1..3].to_vec(), nodes[3..6].to_vec()]);
+        assert_eq!(parts[1].graph.edge_count(), 2);
+
+        // Each node's out-degree within its component, keyed by the original IDs.
+        let out_degree = for_each_component_parallel(&g, |c| {
+            Ok(c.nodes()
+                .map(|(node, _)| (node, c.neighbors(node).count()))
+                .collect())
+        })
+        .unwrap();
+        assert_eq!(out_degree.len(), 5);
+        assert_eq!(out_degree[&nodes[2]], 1);
+        assert_eq!(out_degree[&nodes[4]], 0);
+
+        let failed = for_each_component_parallel::<_, _, _, (), _>(&g, |c| {
+            Err(GraphinaError::invalid_argument(c.node_count().to_string()))
+        });
+        assert_eq!(
+            failed.unwrap_err().to_string(),
+            GraphinaError::invalid_argument("2").to_string()
+        );
+    }
 }
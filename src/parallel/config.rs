@@ -0,0 +1,130 @@
+/*!
+Configuration for parallel algorithms.
+
+Floating-point addition is not associative, and Rayon splits a parallel sum wherever work
+stealing happens to divide it, so the same sum can round differently from run to run.
+[`Reduction::Deterministic`] fixes the split instead: the values are cut into chunks of a set
+size, each chunk is summed pairwise, and the chunk totals are combined pairwise in order.
+The result then depends only on the input and the chunk size, not on the thread count or
+scheduling.
+*/
+
+use rayon::prelude::*;
+
+/// Below this many values, pairwise summation falls back to a plain loop.
+const PAIRWISE_BASE: usize = 32;
+
+/// How parallel algorithms combine floating-point partial results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Reduction {
+    /// Let Rayon split the work as it sees fit. Fastest, but the last bits of a sum can
+    /// vary between runs.
+    #[default]
+    Unordered,
+    /// Sum in fixed chunks with pairwise summation, giving bit-identical results on every
+    /// run and for any number of threads.
+    Deterministic,
+}
+
+/// Options shared by the parallel algorithms that accept a configuration.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::parallel::ParallelConfig;
+///
+/// let config = ParallelConfig::deterministic().with_chunk_size(4096);
+/// let values: Vec<f64> = (0..10_000).map(|i| 1.0 / (i as f64 + 1.0)).collect();
+/// assert_eq!(config.sum(&values), config.sum(&values));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParallelConfig {
+    /// How floating-point sums are reduced.
+    pub reduction: Reduction,
+    /// Number of values per chunk for [`Reduction::Deterministic`]. Changing it may change
+    /// the rounding of results, so keep it fixed when comparing runs.
+    pub chunk_size: usize,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self {
+            reduction: Reduction::Unordered,
+            chunk_size: 1024,
+        }
+    }
+}
+
+impl ParallelConfig {
+    /// Creates the default configuration, with unordered reductions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a configuration with deterministic reductions.
+    pub fn deterministic() -> Self {
+        Self::default().with_reduction(Reduction::Deterministic)
+    }
+
+    /// Sets the reduction mode.
+    pub fn with_reduction(mut self, reduction: Reduction) -> Self {
+        self.reduction = reduction;
+        self
+    }
+
+    /// Sets the chunk size for deterministic reductions; zero is treated as one.
+    pub fn with_chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Sums `values` in parallel according to the reduction mode.
+    pub fn sum(&self, values: &[f64]) -> f64 {
+        match self.reduction {
+            Reduction::Unordered => values.par_iter().sum(),
+            Reduction::Deterministic => {
+                let partials: Vec<f64> = values
+                    .par_chunks(self.chunk_size.max(1))
+                    .map(pairwise_sum)
+                    .collect();
+                pairwise_sum(&partials)
+            }
+        }
+    }
+}
+
+/// Pairwise (cascade) summation, whose rounding error grows with `log n` rather than `n`.
+fn pairwise_sum(values: &[f64]) -> f64 {
+    if values.len() <= PAIRWISE_BASE {
+        return values.iter().sum();
+    }
+    let (left, right) = values.split_at(values.len() / 2);
+    pairwise_sum(left) + pairwise_sum(right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deterministic_sum_ignores_thread_count() {
+        let values: Vec<f64> = (0..100_000)
+            .map(|i| ((i * 7919) % 1000) as f64 * 1e-3 + 1e8 * ((i % 3) as f64 - 1.0))
+            .collect();
+        let config = ParallelConfig::deterministic().with_chunk_size(257);
+        let sums: Vec<u64> = [1, 3, 8]
+            .iter()
+            .map(|&threads| {
+                rayon::ThreadPoolBuilder::new()
+                    .num_threads(threads)
+                    .build()
+                    .unwrap()
+                    .install(|| config.sum(&values))
+                    .to_bits()
+            })
+            .collect();
+        assert!(sums.windows(2).all(|w| w[0] == w[1]));
+        assert_eq!(ParallelConfig::new().with_chunk_size(0).chunk_size, 1);
+        assert_eq!(config.sum(&[]), 0.0);
+    }
+}
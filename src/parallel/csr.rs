@@ -0,0 +1,171 @@
+/*!
+Compressed sparse row snapshots shared by the parallel algorithms.
+
+A snapshot maps the graph's nodes to dense positions `0..n` in node order and stores each
+node's neighbors as a sorted slice of positions, without duplicates or self-loops. The
+neighbor lists are read-only, so any number of Rayon tasks can scan them at once.
+*/
+
+use std::sync::{Mutex, MutexGuard};
+
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+use petgraph::EdgeType;
+
+/// Adjacency in compressed sparse row form, over dense positions in node order.
+pub(crate) struct Csr {
+    nodes: Vec<NodeId>,
+    position: Vec<usize>,
+    out_offsets: Vec<usize>,
+    out_targets: Vec<usize>,
+    /// Incoming adjacency for directed snapshots; symmetric ones reuse the outgoing lists.
+    incoming: Option<(Vec<usize>, Vec<usize>)>,
+}
+
+impl Csr {
+    /// Snapshots `graph` following edge direction.
+    pub(crate) fn new<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, W> + EdgeType,
+    {
+        let (nodes, position) = positions(graph);
+        let (out_offsets, out_targets) = adjacency(&nodes, &position, |n| graph.neighbors(n));
+        let incoming = graph
+            .is_directed()
+            .then(|| adjacency(&nodes, &position, |n| graph.incoming_neighbors(n)));
+        Self {
+            nodes,
+            position,
+            out_offsets,
+            out_targets,
+            incoming,
+        }
+    }
+
+    /// Snapshots `graph` with edge direction ignored, so every list is symmetric.
+    pub(crate) fn undirected<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, W> + EdgeType,
+    {
+        let (nodes, position) = positions(graph);
+        let (out_offsets, out_targets) = adjacency(&nodes, &position, |n| {
+            graph.neighbors(n).chain(graph.incoming_neighbors(n))
+        });
+        Self {
+            nodes,
+            position,
+            out_offsets,
+            out_targets,
+            incoming: None,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Total length of the outgoing lists.
+    pub(crate) fn edge_count(&self) -> usize {
+        self.out_targets.len()
+    }
+
+    /// The node at position `v`.
+    pub(crate) fn node(&self, v: usize) -> NodeId {
+        self.nodes[v]
+    }
+
+    /// The nodes of the snapshot, in position order.
+    pub(crate) fn nodes(&self) -> &[NodeId] {
+        &self.nodes
+    }
+
+    pub(crate) fn position(&self, node: NodeId) -> Option<usize> {
+        self.position
+            .get(node.index())
+            .copied()
+            .filter(|&p| p != usize::MAX)
+    }
+
+    /// Distinct successors of `v`, sorted by position.
+    pub(crate) fn successors(&self, v: usize) -> &[usize] {
+        &self.out_targets[self.out_offsets[v]..self.out_offsets[v + 1]]
+    }
+
+    /// Distinct predecessors of `v`, sorted by position.
+    pub(crate) fn predecessors(&self, v: usize) -> &[usize] {
+        match &self.incoming {
+            Some((offsets, targets)) => &targets[offsets[v]..offsets[v + 1]],
+            None => self.successors(v),
+        }
+    }
+}
+
+fn positions<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> (Vec<NodeId>, Vec<usize>)
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let bound = nodes.iter().map(|n| n.index() + 1).max().unwrap_or(0);
+    let mut position = vec![usize::MAX; bound];
+    for (i, node) in nodes.iter().enumerate() {
+        position[node.index()] = i;
+    }
+    (nodes, position)
+}
+
+/// Offsets and targets of the adjacency given by `neighbors`, sorted, deduplicated, and
+/// without self-loops.
+fn adjacency<F, I>(nodes: &[NodeId], position: &[usize], neighbors: F) -> (Vec<usize>, Vec<usize>)
+where
+    F: Fn(NodeId) -> I,
+    I: Iterator<Item = NodeId>,
+{
+    let mut offsets = Vec::with_capacity(nodes.len() + 1);
+    let mut targets = Vec::new();
+    offsets.push(0);
+    for (v, &node) in nodes.iter().enumerate() {
+        let start = targets.len();
+        targets.extend(
+            neighbors(node)
+                .map(|w| position[w.index()])
+                .filter(|&w| w != v),
+        );
+        targets[start..].sort_unstable();
+        let mut kept = start;
+        for i in start..targets.len() {
+            if kept == start || targets[kept - 1] != targets[i] {
+                targets[kept] = targets[i];
+                kept += 1;
+            }
+        }
+        targets.truncate(kept);
+        offsets.push(targets.len());
+    }
+    (offsets, targets)
+}
+
+/// One scratch buffer per Rayon worker thread.
+///
+/// Per-node work that needs an `O(n)` marker array borrows the buffer of the thread it runs
+/// on, so the arrays are allocated once per thread rather than once per task. Each worker
+/// only ever locks its own slot, so the locks are uncontended.
+pub(crate) struct PerThread<T> {
+    slots: Vec<Mutex<T>>,
+}
+
+impl<T> PerThread<T> {
+    pub(crate) fn new(init: impl Fn() -> T) -> Self {
+        Self {
+            slots: (0..rayon::current_num_threads().max(1))
+                .map(|_| Mutex::new(init()))
+                .collect(),
+        }
+    }
+
+    /// The buffer of the calling worker thread.
+    pub(crate) fn get(&self) -> MutexGuard<'_, T> {
+        let slot = rayon::current_thread_index().unwrap_or(0) % self.slots.len();
+        self.slots[slot]
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
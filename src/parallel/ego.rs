@@ -0,0 +1,184 @@
+/*!
+Parallel ego-network statistics for every node
+*/
+
+use rayon::prelude::*;
+
+use super::csr::{Csr, PerThread};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeMap};
+use petgraph::EdgeType;
+
+/// Structure of one node's ego network, excluding the ego itself.
+///
+/// The alters are the nodes within the search radius. For a directed graph, pairs and ties
+/// are ordered, so `a -> b` and `b -> a` are two ties.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EgoMetrics {
+    /// Number of alters.
+    pub size: usize,
+    /// Number of alter pairs joined by an edge, ignoring parallel edges.
+    pub ties: usize,
+    /// Number of alter pairs, `size * (size - 1)`, halved for undirected graphs.
+    pub pairs: usize,
+    /// `ties / pairs`, or `0.0` with fewer than two alters.
+    pub density: f64,
+    /// Number of alter pairs with no edge between them, which only the ego can bridge.
+    pub brokerage: usize,
+}
+
+/// Computes [`EgoMetrics`] for every node's ego network of the given `radius` in parallel.
+///
+/// The result is what `SubgraphOps::ego_graph(node, radius)` followed by counting would give,
+/// without building a subgraph per node: each worker thread reuses one marker buffer over a
+/// shared compressed sparse row snapshot. Alters are found along outgoing edges, as in
+/// `ego_graph`. Self-loops and parallel edges are ignored, and a radius of 0 leaves every
+/// node without alters.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::parallel::ego_metrics_all_parallel;
+///
+/// // A star with one extra edge between two leaves.
+/// let mut g = Graph::<(), ()>::new();
+/// let [hub, a, b, c] = [(); 4].map(|_| g.add_node(()));
+/// g.add_edge(hub, a, ());
+/// g.add_edge(hub, b, ());
+/// g.add_edge(hub, c, ());
+/// g.add_edge(a, b, ());
+///
+/// let metrics = ego_metrics_all_parallel(&g, 1);
+/// assert_eq!(metrics[&hub].size, 3);
+/// assert_eq!(metrics[&hub].ties, 1);
+/// assert_eq!(metrics[&hub].brokerage, 2);
+/// assert!((metrics[&hub].density - 1.0 / 3.0).abs() < 1e-12);
+/// ```
+pub fn ego_metrics_all_parallel<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    radius: usize,
+) -> NodeMap<EgoMetrics>
+where
+    A: Sync,
+    W: Sync,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync,
+{
+    let csr = Csr::new(graph);
+    let n = csr.len();
+    let directed = graph.is_directed();
+    // Per thread: the ego that last marked each position, and the alters of the current ego.
+    let scratch = PerThread::new(|| (Vec::<usize>::new(), Vec::<usize>::new()));
+    let metrics: Vec<EgoMetrics> = (0..n)
+        .into_par_iter()
+        .with_min_len(64)
+        .map(|ego| {
+            let mut guard = scratch.get();
+            let (mark, alters) = &mut *guard;
+            if mark.len() < n {
+                mark.resize(n, usize::MAX);
+            }
+            alters.clear();
+            mark[ego] = ego;
+
+            // Breadth-first by levels; `alters[frontier..]` is the level just found.
+            let mut frontier = 0;
+            for depth in 0..radius {
+                let level_end = alters.len();
+                if depth == 0 {
+                    reach(&csr, ego, ego, mark, alters);
+                } else {
+                    for i in frontier..level_end {
+                        reach(&csr, alters[i], ego, mark, alters);
+                    }
+                }
+                if alters.len() == level_end {
+                    break;
+                }
+                frontier = level_end;
+            }
+
+            let size = alters.len();
+            let ties = alters
+                .iter()
+                .map(|&u| {
+                    csr.successors(u)
+                        .iter()
+                        .filter(|&&w| w != ego && mark[w] == ego && (directed || u < w))
+                        .count()
+                })
+                .sum();
+            let pairs = if directed {
+                size * size.saturating_sub(1)
+            } else {
+                size * size.saturating_sub(1) / 2
+            };
+            EgoMetrics {
+                size,
+                ties,
+                pairs,
+                density: if pairs == 0 {
+                    0.0
+                } else {
+                    ties as f64 / pairs as f64
+                },
+                brokerage: pairs - ties,
+            }
+        })
+        .collect();
+    csr.nodes().iter().copied().zip(metrics).collect()
+}
+
+/// Marks the unmarked successors of `u` as alters of `ego`.
+fn reach(csr: &Csr, u: usize, ego: usize, mark: &mut [usize], alters: &mut Vec<usize>) {
+    for &w in csr.successors(u) {
+        if mark[w] != ego {
+            mark[w] = ego;
+            alters.push(w);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Digraph;
+
+    #[test]
+    fn test_ego_metrics_follow_radius_and_direction() {
+        // a -> b -> c -> d, plus b -> a and a self-loop on c.
+        let mut g = Digraph::<(), ()>::new();
+        let [a, b, c, d] = [(); 4].map(|_| g.add_node(()));
+        g.add_edge(a, b, ());
+        g.add_edge(b, a, ());
+        g.add_edge(b, c, ());
+        g.add_edge(c, d, ());
+        g.add_edge(c, c, ());
+
+        let one = ego_metrics_all_parallel(&g, 1);
+        // b's alters are a and c, with no tie between them in either direction.
+        assert_eq!(
+            one[&b],
+            EgoMetrics {
+                size: 2,
+                ties: 0,
+                pairs: 2,
+                density: 0.0,
+                brokerage: 2,
+            }
+        );
+        assert_eq!(one[&d].size, 0);
+
+        // Within two hops of a: b, and then c, joined by b -> c only.
+        let two = ego_metrics_all_parallel(&g, 2);
+        assert_eq!((two[&a].size, two[&a].ties, two[&a].pairs), (2, 1, 2));
+        assert_eq!(two[&a].density, 0.5);
+
+        let three = ego_metrics_all_parallel(&g, 3);
+        assert_eq!((three[&a].size, three[&a].ties), (3, 2));
+        assert!(
+            ego_metrics_all_parallel(&g, 0)
+                .values()
+                .all(|m| m.size == 0)
+        );
+    }
+}
@@ -9,21 +9,35 @@ All parallel functions have the `_parallel` suffix to distinguish them from sequ
 Independent of other extensions; depends only on core.
 */
 
+pub mod aggregation;
 pub mod bfs;
 pub mod closeness;
 pub mod clustering;
 pub mod components;
+pub mod config;
+pub mod cores;
+mod csr;
 pub mod degrees;
+pub mod ego;
 pub mod pagerank;
 pub mod paths;
+pub mod percolation;
 pub mod triangles;
 
 // Re-export main functions for convenience
+pub use aggregation::aggregate_neighbors_parallel;
 pub use bfs::bfs_parallel;
 pub use closeness::closeness_centrality_parallel;
-pub use clustering::clustering_coefficients_parallel;
-pub use components::connected_components_parallel;
+pub use clustering::{average_clustering_parallel, clustering_coefficients_parallel};
+pub use components::{
+    Component, connected_components_parallel, for_each_component_parallel, map_components_parallel,
+    split_components_parallel,
+};
+pub use config::{ParallelConfig, Reduction};
+pub use cores::core_number_parallel;
 pub use degrees::degrees_parallel;
-pub use pagerank::pagerank_parallel;
+pub use ego::{EgoMetrics, ego_metrics_all_parallel};
+pub use pagerank::{pagerank_parallel, pagerank_parallel_with_config};
 pub use paths::{all_pairs_shortest_path_length_parallel, shortest_paths_parallel};
+pub use percolation::percolation_curves_parallel;
 pub use triangles::triangles_parallel;
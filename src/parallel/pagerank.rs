@@ -5,6 +5,7 @@ Parallel PageRank computation
 use rayon::prelude::*;
 use std::collections::HashMap;
 
+use super::config::ParallelConfig;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
 use petgraph::EdgeType;
 
@@ -43,6 +44,52 @@ pub fn pagerank_parallel<A, W, Ty>(
     tolerance: f64,
     nstart: Option<&HashMap<NodeId, f64>>,
 ) -> HashMap<NodeId, f64>
+where
+    A: Sync,
+    W: Sync,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync,
+{
+    pagerank_parallel_with_config(
+        graph,
+        damping,
+        max_iterations,
+        tolerance,
+        nstart,
+        &ParallelConfig::default(),
+    )
+}
+
+/// Parallel PageRank with explicit [`ParallelConfig`].
+///
+/// Takes the same parameters as [`pagerank_parallel`]. With
+/// [`Reduction::Deterministic`](super::config::Reduction::Deterministic), the
+/// per-iteration sums are reduced in a fixed order, so repeated runs return
+/// bit-identical ranks regardless of the thread count.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::parallel::{pagerank_parallel_with_config, ParallelConfig};
+///
+/// let mut g = Digraph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+///
+/// let config = ParallelConfig::deterministic();
+/// let first = pagerank_parallel_with_config(&g, 0.85, 100, 1e-9, None, &config);
+/// let second = pagerank_parallel_with_config(&g, 0.85, 100, 1e-9, None, &config);
+/// assert_eq!(first[&b].to_bits(), second[&b].to_bits());
+/// ```
+pub fn pagerank_parallel_with_config<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    damping: f64,
+    max_iterations: usize,
+    tolerance: f64,
+    nstart: Option<&HashMap<NodeId, f64>>,
+    config: &ParallelConfig,
+) -> HashMap<NodeId, f64>
 where
     A: Sync,
     W: Sync,
@@ -107,13 +154,14 @@ where
         let prev = ranks.clone();
 
         // Compute sum of ranks of dangling nodes (out-degree == 0) for redistribution
-        let dangling_sum: f64 = nodes
+        let dangling: Vec<f64> = nodes
             .par_iter()
             .map(|&node| {
                 let out_deg = graph.out_degree(node).unwrap_or(0);
                 if out_deg == 0 { prev[&node] } else { 0.0 }
             })
-            .sum();
+            .collect();
+        let dangling_sum = config.sum(&dangling);
 
         let base = (1.0 - damping) / n as f64 + damping * dangling_sum / n as f64;
 
@@ -155,7 +203,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::core::types::Graph;
+    use crate::core::types::{Digraph, Graph};
 
     #[test]
     fn test_pagerank_parallel() {
@@ -180,4 +228,28 @@ mod tests {
         assert!((ranks[&n2] - avg).abs() < 0.1);
         assert!((ranks[&n3] - avg).abs() < 0.1);
     }
+
+    #[test]
+    fn test_pagerank_parallel_deterministic_across_thread_counts() {
+        // Many dangling nodes make the dangling-mass sum span several chunks.
+        let mut g = Digraph::<(), f64>::new();
+        let nodes: Vec<_> = (0..3000).map(|_| g.add_node(())).collect();
+        for i in 0..nodes.len() {
+            if i % 3 != 0 {
+                g.add_edge(nodes[i], nodes[(i * 7 + 1) % nodes.len()], 1.0);
+            }
+        }
+        let config = ParallelConfig::deterministic().with_chunk_size(64);
+        let run = |threads: usize| -> Vec<u64> {
+            let ranks = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap()
+                .install(|| pagerank_parallel_with_config(&g, 0.85, 50, 0.0, None, &config));
+            nodes.iter().map(|n| ranks[n].to_bits()).collect()
+        };
+        let single = run(1);
+        assert_eq!(run(4), single);
+        assert_eq!(run(7), single);
+    }
 }
@@ -0,0 +1,80 @@
+/*!
+Parallel percolation curves over many removal orders
+*/
+
+use rayon::prelude::*;
+
+use crate::core::error::Result;
+use crate::core::percolation::{PercolationOrder, PercolationPoint, percolation_curve};
+use crate::core::types::{BaseGraph, GraphConstructor};
+use petgraph::EdgeType;
+
+/// Computes [`percolation_curve`] for every order in parallel, returning the curves in the
+/// order of `orders`.
+///
+/// A single curve is a sequential pass, so the parallelism is across orders. Random
+/// percolation averages many shuffled orders, which makes this the usual entry point.
+///
+/// # Errors
+///
+/// Returns the error of the first order that fails, as [`percolation_curve`] would.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::generators::erdos_renyi_graph;
+/// use graphina::core::percolation::PercolationOrder;
+/// use graphina::core::types::Undirected;
+/// use graphina::parallel::percolation_curves_parallel;
+///
+/// let g = erdos_renyi_graph::<Undirected>(200, 0.02, 1).unwrap();
+/// let orders: Vec<_> = (0..32).map(|seed| PercolationOrder::random_sites(&g, seed)).collect();
+/// let curves = percolation_curves_parallel(&g, &orders, 10).unwrap();
+///
+/// // Every curve has the same points, so average them point by point.
+/// let mean: Vec<f64> = (0..curves[0].len())
+///     .map(|i| curves.iter().map(|c| c[i].giant).sum::<f64>() / curves.len() as f64)
+///     .collect();
+/// assert_eq!(*mean.last().unwrap(), 0.0);
+/// ```
+pub fn percolation_curves_parallel<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    orders: &[PercolationOrder],
+    step: usize,
+) -> Result<Vec<Vec<PercolationPoint>>>
+where
+    A: Sync,
+    W: Sync,
+    Ty: GraphConstructor<A, W> + EdgeType + Sync,
+{
+    orders
+        .par_iter()
+        .map(|order| percolation_curve(graph, order, step))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::generators::barabasi_albert_graph;
+    use crate::core::types::Undirected;
+
+    #[test]
+    fn test_percolation_curves_parallel_matches_sequential() {
+        let g = barabasi_albert_graph::<Undirected>(300, 2, 5).unwrap();
+        let mut orders: Vec<PercolationOrder> = (0..8)
+            .map(|seed| PercolationOrder::random_bonds(&g, seed))
+            .collect();
+        orders.push(PercolationOrder::highest_degree_first(&g));
+        let curves = percolation_curves_parallel(&g, &orders, 7).unwrap();
+        for (order, curve) in orders.iter().zip(&curves) {
+            assert_eq!(curve, &percolation_curve(&g, order, 7).unwrap());
+        }
+        // Removing the hubs first breaks a scale-free graph far sooner than random failures.
+        let attack = &curves[8][5];
+        let failure = PercolationOrder::random_sites(&g, 0);
+        let failure = percolation_curve(&g, &failure, 7).unwrap()[5];
+        assert!(attack.giant < failure.giant, "{attack:?} vs {failure:?}");
+        assert!(percolation_curves_parallel(&g, &orders, 0).is_err());
+    }
+}
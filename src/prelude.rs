@@ -0,0 +1,45 @@
+/*!
+The most used types, traits, and algorithms, for a single glob import.
+
+```rust
+use graphina::prelude::*;
+
+let mut g: Graph<&str, u32> = Graph::new();
+let a = g.add_node("a");
+let b = g.add_node("b");
+g.add_edge(a, b, 3);
+
+let distances: NodeMap<Option<u32>> = dijkstra(&g, a).unwrap();
+assert_eq!(distances[&b], Some(3));
+```
+
+Algorithms are included only when their feature is enabled. The prelude leaves out
+`core::error::Result`, which would shadow the standard `Result` in the importing module.
+Paths through the prelude and the module roots, such as `graphina::centrality::pagerank`,
+are meant to stay put across releases, while the submodules behind them may be reorganized.
+*/
+
+pub use crate::core::error::GraphinaError;
+pub use crate::core::paths::{a_star, bellman_ford, dijkstra, dijkstra_path_f64, floyd_warshall};
+pub use crate::core::types::{
+    BaseGraph, Digraph, Directed, EdgeId, EdgeMap, Graph, GraphConstructor, GraphinaGraph, NodeId,
+    NodeMap, NodeSet, Undirected,
+};
+
+#[cfg(feature = "centrality")]
+pub use crate::centrality::{
+    betweenness_centrality, closeness_centrality, degree_centrality, eigenvector_centrality,
+    pagerank,
+};
+#[cfg(feature = "community")]
+pub use crate::community::{connected_components, label_propagation, louvain, modularity};
+#[cfg(feature = "metrics")]
+pub use crate::metrics::{average_clustering_coefficient, diameter, transitivity};
+#[cfg(feature = "mst")]
+pub use crate::mst::{kruskal_mst, prim_mst};
+#[cfg(feature = "subgraphs")]
+pub use crate::subgraphs::SubgraphOps;
+#[cfg(feature = "traversal")]
+pub use crate::traversal::{bfs, dfs};
+#[cfg(feature = "visualization")]
+pub use crate::visualization::{LayoutAlgorithm, compute_layout, to_svg};
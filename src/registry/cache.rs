@@ -0,0 +1,279 @@
+/*!
+# On-Disk Result Cache
+
+Memoizes registry results in a directory so that expensive analyses, such as betweenness on
+a large graph, are computed once and reused across runs. Each entry is keyed by the algorithm
+name, its parameters, and the graph's
+[`fingerprint_with_ids`](crate::core::fingerprint::fingerprint_with_ids). Mutating the graph
+changes its fingerprint, so a stale entry is never returned; it is simply never looked up
+again, and [`ResultCache::clear`] removes it.
+
+```rust,no_run
+use graphina::core::types::Graph;
+use graphina::registry::{Params, Registry, ResultCache};
+
+let graph = Graph::<u32, f64>::new();
+let cache = ResultCache::new("target/graphina-cache").unwrap();
+let registry = Registry::with_defaults();
+let scores = registry
+    .run_cached(&cache, "betweenness", &graph, &Params::new())
+    .unwrap();
+```
+
+Entries are bincode files. A file that cannot be read or decoded, for example one written by
+an incompatible version, counts as a miss and is overwritten.
+*/
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::{AnalysisResult, Params};
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{NodeId, NodeMap};
+use petgraph::graph::NodeIndex;
+
+/// File extension of cache entries; [`ResultCache::clear`] only removes these.
+const EXTENSION: &str = "gcache";
+/// Bumped whenever the entry layout changes, so older entries read as misses.
+const FORMAT: u32 = 1;
+
+/// A directory of memoized [`AnalysisResult`]s.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Record {
+    format: u32,
+    algorithm: String,
+    params: String,
+    graph: u64,
+    result: Stored,
+}
+
+/// `AnalysisResult` with nodes stored as `NodeId::index()`, since `NodeId` is not
+/// serializable. The id-aware graph key guarantees the indices still name the same nodes.
+#[derive(Serialize, Deserialize)]
+enum Stored {
+    NodeScores(Vec<(usize, f64)>),
+    NodeLabels(Vec<(usize, usize)>),
+    Communities(Vec<Vec<usize>>),
+    Scalar(f64),
+}
+
+fn node(index: usize) -> NodeId {
+    NodeId::new(NodeIndex::new(index))
+}
+
+impl From<&AnalysisResult> for Stored {
+    fn from(result: &AnalysisResult) -> Self {
+        match result {
+            AnalysisResult::NodeScores(m) => {
+                Stored::NodeScores(m.iter().map(|(n, &x)| (n.index(), x)).collect())
+            }
+            AnalysisResult::NodeLabels(m) => {
+                Stored::NodeLabels(m.iter().map(|(n, &x)| (n.index(), x)).collect())
+            }
+            AnalysisResult::Communities(groups) => Stored::Communities(
+                groups
+                    .iter()
+                    .map(|g| g.iter().map(NodeId::index).collect())
+                    .collect(),
+            ),
+            AnalysisResult::Scalar(x) => Stored::Scalar(*x),
+        }
+    }
+}
+
+impl From<Stored> for AnalysisResult {
+    fn from(stored: Stored) -> Self {
+        match stored {
+            Stored::NodeScores(v) => AnalysisResult::NodeScores(
+                v.into_iter()
+                    .map(|(i, x)| (node(i), x))
+                    .collect::<NodeMap<_>>(),
+            ),
+            Stored::NodeLabels(v) => AnalysisResult::NodeLabels(
+                v.into_iter()
+                    .map(|(i, x)| (node(i), x))
+                    .collect::<NodeMap<_>>(),
+            ),
+            Stored::Communities(groups) => AnalysisResult::Communities(
+                groups
+                    .into_iter()
+                    .map(|g| g.into_iter().map(node).collect())
+                    .collect(),
+            ),
+            Stored::Scalar(x) => AnalysisResult::Scalar(x),
+        }
+    }
+}
+
+/// FNV-1a, used only to shorten the parameter string into a file name.
+fn short_hash(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ u64::from(b)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+impl ResultCache {
+    /// Opens the cache in `dir`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the directory cannot be created.
+    pub fn new(dir: impl AsRef<Path>) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        fs::create_dir_all(&dir).map_err(GraphinaError::from)?;
+        Ok(ResultCache { dir })
+    }
+
+    /// Returns the cache directory.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, algorithm: &str, params: &str, graph: u64) -> PathBuf {
+        let name: String = algorithm
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect();
+        self.dir.join(format!(
+            "{}-{:016x}-{:016x}.{}",
+            name,
+            graph,
+            short_hash(params),
+            EXTENSION
+        ))
+    }
+
+    /// Returns the stored result for `algorithm` with `params` on the graph whose
+    /// [`fingerprint_with_ids`](crate::core::fingerprint::fingerprint_with_ids) is `graph`, or
+    /// `None` on a miss.
+    pub fn get(&self, algorithm: &str, params: &Params, graph: u64) -> Option<AnalysisResult> {
+        let params = params.cache_key();
+        let bytes = fs::read(self.path(algorithm, &params, graph)).ok()?;
+        let (record, _): (Record, usize) =
+            bincode::serde::decode_from_slice(&bytes, bincode::config::standard()).ok()?;
+        let matches = record.format == FORMAT
+            && record.algorithm == algorithm
+            && record.params == params
+            && record.graph == graph;
+        matches.then(|| record.result.into())
+    }
+
+    /// Stores `result` for `algorithm` with `params` on the graph keyed by `graph`.
+    ///
+    /// The entry is written to a temporary file and renamed into place, so a concurrent
+    /// reader never sees a partial entry.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SerializationError` or `IoError` if the entry cannot be written.
+    pub fn put(
+        &self,
+        algorithm: &str,
+        params: &Params,
+        graph: u64,
+        result: &AnalysisResult,
+    ) -> Result<()> {
+        let params = params.cache_key();
+        let path = self.path(algorithm, &params, graph);
+        let record = Record {
+            format: FORMAT,
+            algorithm: algorithm.to_string(),
+            params,
+            graph,
+            result: result.into(),
+        };
+        let bytes = bincode::serde::encode_to_vec(&record, bincode::config::standard())
+            .map_err(GraphinaError::from)?;
+        let tmp = path.with_extension(format!("{}.{}.tmp", EXTENSION, std::process::id()));
+        fs::write(&tmp, bytes).map_err(GraphinaError::from)?;
+        fs::rename(&tmp, &path).map_err(GraphinaError::from)
+    }
+
+    /// Deletes every cache entry in the directory and returns how many were removed. Other
+    /// files are left alone.
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the directory cannot be listed or an entry cannot be removed.
+    pub fn clear(&self) -> Result<usize> {
+        let mut removed = 0;
+        for entry in fs::read_dir(&self.dir).map_err(GraphinaError::from)? {
+            let path = entry.map_err(GraphinaError::from)?.path();
+            if path.extension().is_some_and(|ext| ext == EXTENSION) {
+                fs::remove_file(&path).map_err(GraphinaError::from)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::fingerprint::fingerprint_with_ids;
+    use crate::core::types::Graph;
+    use crate::registry::Registry;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    fn counting_registry() -> Registry<u32, crate::core::types::Undirected> {
+        let mut registry = Registry::new();
+        registry.register("degrees", "Degree per node", &["scale"], |g, p| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            let scale = p.float("scale", 1.0)?;
+            Ok(AnalysisResult::NodeScores(
+                g.node_ids()
+                    .map(|n| (n, g.degree(n).unwrap_or(0) as f64 * scale))
+                    .collect(),
+            ))
+        });
+        registry
+    }
+
+    #[test]
+    fn test_result_cache_hits_misses_and_invalidates() {
+        let dir = std::env::temp_dir().join(format!("graphina-cache-test-{}", std::process::id()));
+        let cache = ResultCache::new(&dir).unwrap();
+        let registry = counting_registry();
+        let mut g = Graph::<u32, f64>::new();
+        let a = g.add_node(1);
+        let b = g.add_node(2);
+        g.add_edge(a, b, 1.0);
+
+        let params = Params::new();
+        let first = registry.run_cached(&cache, "degrees", &g, &params).unwrap();
+        let second = registry.run_cached(&cache, "degrees", &g, &params).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(CALLS.load(Ordering::SeqCst), 1);
+
+        // Different parameters and a mutated graph are both misses.
+        let scaled = Params::new().with("scale", 2.0);
+        registry.run_cached(&cache, "degrees", &g, &scaled).unwrap();
+        let c = g.add_node(3);
+        g.add_edge(b, c, 1.0);
+        let after = registry.run_cached(&cache, "degrees", &g, &params).unwrap();
+        assert_eq!(CALLS.load(Ordering::SeqCst), 3);
+        assert_eq!(after.as_node_scores().map(|m| m[&b]), Some(2.0));
+
+        let key = fingerprint_with_ids(&g).unwrap();
+        assert_eq!(cache.get("degrees", &params, key), Some(after));
+        assert_eq!(cache.clear().unwrap(), 3);
+        assert_eq!(cache.get("degrees", &params, key), None);
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,713 @@
+/*!
+# Algorithm Registry
+
+Name-based dispatch over Graphina algorithms for applications that pick what to run at
+runtime: pipelines read from a config file, job queues, or a scripting layer. Every entry
+takes a graph and a [`Params`] bag and returns an [`AnalysisResult`], so one call site can run
+any registered algorithm without a `match` over algorithm names.
+
+```rust
+use graphina::core::types::Graph;
+use graphina::registry::{AnalysisResult, Params, run};
+
+let mut g = Graph::<(), f64>::new();
+let a = g.add_node(());
+let b = g.add_node(());
+g.add_edge(a, b, 1.0);
+
+let params = Params::new().with("damping", 0.9);
+let scores = run("pagerank", &g, &params).unwrap();
+assert!(matches!(scores, AnalysisResult::NodeScores(_)));
+```
+
+[`Registry::with_defaults`] only contains algorithms whose feature is enabled, so enable
+`centrality`, `community`, or `metrics` together with `registry` for the built-in entries.
+Applications add their own algorithms with [`Registry::register`], and [`Pipeline`] chains
+registry calls with cleaning, filtering, and export steps. [`ResultCache`] keeps results on
+disk between runs. With the `async` feature, [`tasks`] runs registry calls and arbitrary
+closures on a background thread pool and returns awaitable handles.
+
+Unlike the extensions, this module sits above them and may call into any enabled one.
+Extensions must never import it.
+*/
+
+pub mod cache;
+pub mod pipeline;
+#[cfg(feature = "async")]
+pub mod tasks;
+
+pub use cache::ResultCache;
+pub use pipeline::{Pipeline, PipelineOutput};
+#[cfg(feature = "async")]
+pub use tasks::{Progress, TaskHandle, spawn, spawn_run};
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use serde::Serialize;
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::fingerprint::fingerprint_with_ids;
+use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeId, NodeMap};
+use petgraph::EdgeType;
+
+/// A single parameter value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParamValue {
+    /// A floating-point number.
+    Float(f64),
+    /// An integer.
+    Int(i64),
+    /// A flag.
+    Bool(bool),
+    /// A string.
+    Str(String),
+}
+
+impl From<f64> for ParamValue {
+    fn from(value: f64) -> Self {
+        ParamValue::Float(value)
+    }
+}
+
+impl From<i64> for ParamValue {
+    fn from(value: i64) -> Self {
+        ParamValue::Int(value)
+    }
+}
+
+impl From<i32> for ParamValue {
+    fn from(value: i32) -> Self {
+        ParamValue::Int(i64::from(value))
+    }
+}
+
+impl From<bool> for ParamValue {
+    fn from(value: bool) -> Self {
+        ParamValue::Bool(value)
+    }
+}
+
+impl From<&str> for ParamValue {
+    fn from(value: &str) -> Self {
+        ParamValue::Str(value.to_string())
+    }
+}
+
+impl From<String> for ParamValue {
+    fn from(value: String) -> Self {
+        ParamValue::Str(value)
+    }
+}
+
+/// Named parameters for a registry call.
+///
+/// The typed getters return the default when a key is missing and `InvalidArgument` when it
+/// holds a value of the wrong type. An `Int` is accepted wherever a float is expected.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Params {
+    values: HashMap<String, ParamValue>,
+}
+
+impl Params {
+    /// Creates an empty parameter set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets `key` and returns the parameter set, for chaining.
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<ParamValue>) -> Self {
+        self.set(key, value);
+        self
+    }
+
+    /// Sets `key`, replacing any previous value.
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<ParamValue>) {
+        self.values.insert(key.into(), value.into());
+    }
+
+    /// Returns the raw value of `key`, if present.
+    pub fn get(&self, key: &str) -> Option<&ParamValue> {
+        self.values.get(key)
+    }
+
+    /// Iterates over the parameter names.
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        self.values.keys().map(String::as_str)
+    }
+
+    /// Returns `key` as a float, or `default` if it is missing.
+    pub fn float(&self, key: &str, default: f64) -> Result<f64> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(ParamValue::Float(x)) => Ok(*x),
+            Some(ParamValue::Int(i)) => Ok(*i as f64),
+            Some(other) => Err(wrong_type(key, "a number", other)),
+        }
+    }
+
+    /// Returns `key` as a non-negative integer, or `default` if it is missing.
+    pub fn usize(&self, key: &str, default: usize) -> Result<usize> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(ParamValue::Int(i)) => usize::try_from(*i).map_err(|_| {
+                GraphinaError::invalid_argument(format!(
+                    "Parameter '{}' must be non-negative, got {}",
+                    key, i
+                ))
+            }),
+            Some(other) => Err(wrong_type(key, "an integer", other)),
+        }
+    }
+
+    /// Returns `key` as a flag, or `default` if it is missing.
+    pub fn bool(&self, key: &str, default: bool) -> Result<bool> {
+        match self.get(key) {
+            None => Ok(default),
+            Some(ParamValue::Bool(b)) => Ok(*b),
+            Some(other) => Err(wrong_type(key, "a boolean", other)),
+        }
+    }
+
+    /// Returns `key` as an optional seed, `None` if it is missing.
+    pub fn seed(&self, key: &str) -> Result<Option<u64>> {
+        match self.get(key) {
+            None => Ok(None),
+            Some(ParamValue::Int(i)) => u64::try_from(*i).map(Some).map_err(|_| {
+                GraphinaError::invalid_argument(format!(
+                    "Parameter '{}' must be non-negative, got {}",
+                    key, i
+                ))
+            }),
+            Some(other) => Err(wrong_type(key, "an integer", other)),
+        }
+    }
+
+    /// A canonical form of the parameters for cache lookups, independent of insertion order.
+    fn cache_key(&self) -> String {
+        let mut keys: Vec<&str> = self.keys().collect();
+        keys.sort_unstable();
+        keys.iter()
+            .map(|k| format!("{}={:?}", k, self.get(k)))
+            .collect::<Vec<_>>()
+            .join(";")
+    }
+}
+
+fn wrong_type(key: &str, expected: &str, got: &ParamValue) -> GraphinaError {
+    GraphinaError::invalid_argument(format!(
+        "Parameter '{}' must be {}, got {:?}",
+        key, expected, got
+    ))
+}
+
+/// The output of a registry call.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnalysisResult {
+    /// One score per node, as returned by centrality measures.
+    NodeScores(NodeMap<f64>),
+    /// One label per node, such as a community or component id.
+    NodeLabels(NodeMap<usize>),
+    /// A partition of the nodes into groups.
+    Communities(Vec<Vec<NodeId>>),
+    /// A single number describing the whole graph.
+    Scalar(f64),
+}
+
+impl AnalysisResult {
+    /// Returns the node scores, if this is a `NodeScores` result.
+    pub fn as_node_scores(&self) -> Option<&NodeMap<f64>> {
+        match self {
+            AnalysisResult::NodeScores(scores) => Some(scores),
+            _ => None,
+        }
+    }
+
+    /// Returns the node labels, if this is a `NodeLabels` result.
+    pub fn as_node_labels(&self) -> Option<&NodeMap<usize>> {
+        match self {
+            AnalysisResult::NodeLabels(labels) => Some(labels),
+            _ => None,
+        }
+    }
+
+    /// Returns the groups, if this is a `Communities` result.
+    pub fn as_communities(&self) -> Option<&[Vec<NodeId>]> {
+        match self {
+            AnalysisResult::Communities(groups) => Some(groups),
+            _ => None,
+        }
+    }
+
+    /// Returns the value, if this is a `Scalar` result.
+    pub fn as_scalar(&self) -> Option<f64> {
+        match self {
+            AnalysisResult::Scalar(x) => Some(*x),
+            _ => None,
+        }
+    }
+}
+
+/// The function type stored for each registered algorithm.
+pub type Runner<A, Ty> = fn(&BaseGraph<A, f64, Ty>, &Params) -> Result<AnalysisResult>;
+
+/// A registered algorithm: its runner, a one-line description, and the parameters it accepts.
+pub struct Entry<A, Ty: GraphConstructor<A, f64>> {
+    /// Short human-readable description.
+    pub description: &'static str,
+    /// Names of the parameters the runner reads. Any other key is rejected.
+    pub params: &'static [&'static str],
+    /// The type-erased algorithm.
+    pub run: Runner<A, Ty>,
+}
+
+impl<A, Ty: GraphConstructor<A, f64>> Clone for Entry<A, Ty> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A, Ty: GraphConstructor<A, f64>> Copy for Entry<A, Ty> {}
+
+impl<A, Ty: GraphConstructor<A, f64>> Debug for Entry<A, Ty> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Entry")
+            .field("description", &self.description)
+            .field("params", &self.params)
+            .finish()
+    }
+}
+
+/// A table of algorithms over `f64`-weighted graphs with node attributes `A` and direction
+/// `Ty`, looked up by name.
+#[derive(Debug)]
+pub struct Registry<A, Ty: GraphConstructor<A, f64>> {
+    entries: HashMap<String, Entry<A, Ty>>,
+}
+
+impl<A, Ty: GraphConstructor<A, f64>> Default for Registry<A, Ty> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, Ty: GraphConstructor<A, f64>> Registry<A, Ty> {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Registry {
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Adds or replaces the algorithm called `name`.
+    pub fn register(
+        &mut self,
+        name: impl Into<String>,
+        description: &'static str,
+        params: &'static [&'static str],
+        run: Runner<A, Ty>,
+    ) {
+        self.entries.insert(
+            name.into(),
+            Entry {
+                description,
+                params,
+                run,
+            },
+        );
+    }
+
+    /// Returns the entry called `name`, if any.
+    pub fn get(&self, name: &str) -> Option<&Entry<A, Ty>> {
+        self.entries.get(name)
+    }
+
+    /// Returns the registered names in sorted order.
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.entries.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Runs the algorithm called `name` on `graph`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` if no algorithm has that name or if `params` holds a key
+    /// the algorithm does not accept, and propagates any error from the algorithm itself.
+    pub fn run(
+        &self,
+        name: &str,
+        graph: &BaseGraph<A, f64, Ty>,
+        params: &Params,
+    ) -> Result<AnalysisResult> {
+        let entry = self.checked_entry(name, params)?;
+        (entry.run)(graph, params)
+    }
+
+    /// Looks up `name` and checks `params` against the parameters it accepts.
+    fn checked_entry(&self, name: &str, params: &Params) -> Result<Entry<A, Ty>> {
+        let entry = self.entries.get(name).ok_or_else(|| {
+            GraphinaError::invalid_argument(format!(
+                "Unknown algorithm '{}'; registered: {}",
+                name,
+                self.names().join(", ")
+            ))
+        })?;
+        let mut unknown: Vec<&str> = params
+            .keys()
+            .filter(|key| !entry.params.contains(key))
+            .collect();
+        if !unknown.is_empty() {
+            unknown.sort_unstable();
+            return Err(GraphinaError::invalid_argument(format!(
+                "Algorithm '{}' does not accept parameter(s) {}; accepted: {}",
+                name,
+                unknown.join(", "),
+                entry.params.join(", ")
+            )));
+        }
+        Ok(*entry)
+    }
+}
+
+impl<A, Ty> Registry<A, Ty>
+where
+    A: Serialize,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+{
+    /// Runs `name` like [`Registry::run`], but returns the result stored in `cache` for the
+    /// same algorithm, parameters, and graph if there is one, and stores a freshly computed
+    /// result otherwise.
+    ///
+    /// The graph is identified by [`fingerprint_with_ids`], so any mutation that changes its
+    /// content or node IDs misses the cache. Entries are keyed by algorithm name, so clear
+    /// the cache after registering a different runner under an existing name.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Registry::run`], plus `SerializationError` or `IoError` if the graph cannot be
+    /// fingerprinted or the entry cannot be written.
+    pub fn run_cached(
+        &self,
+        cache: &ResultCache,
+        name: &str,
+        graph: &BaseGraph<A, f64, Ty>,
+        params: &Params,
+    ) -> Result<AnalysisResult> {
+        let key = fingerprint_with_ids(graph)?;
+        if let Some(hit) = cache.get(name, params, key) {
+            return Ok(hit);
+        }
+        let result = self.run(name, graph, params)?;
+        cache.put(name, params, key, &result)?;
+        Ok(result)
+    }
+}
+
+impl<A, Ty> Registry<A, Ty>
+where
+    A: Debug,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    /// Creates a registry with the built-in algorithms of every enabled feature.
+    ///
+    /// | Name | Feature | Parameters | Result |
+    /// |------|---------|------------|--------|
+    /// | `degree` | centrality | | `NodeScores` |
+    /// | `pagerank` | centrality | `damping` (0.85), `max_iter` (100), `tolerance` (1e-6) | `NodeScores` |
+    /// | `eigenvector` | centrality | `max_iter` (100), `tolerance` (1e-6) | `NodeScores` |
+    /// | `katz` | centrality | `alpha` (0.1), `max_iter` (1000), `tolerance` (1e-6) | `NodeScores` |
+    /// | `betweenness` | centrality | `normalized` (true) | `NodeScores` |
+    /// | `closeness` | centrality | | `NodeScores` |
+    /// | `harmonic` | centrality | | `NodeScores` |
+    /// | `louvain` | community | `seed` | `Communities` |
+    /// | `label_propagation` | community | `max_iter` (100), `seed` | `NodeLabels` |
+    /// | `weighted_label_propagation` | community | `max_iter` (100), `seed` | `NodeLabels` |
+    /// | `connected_components` | community | | `Communities` |
+    /// | `transitivity` | metrics | | `Scalar` |
+    /// | `average_clustering` | metrics | | `Scalar` |
+    /// | `diameter` | metrics | | `Scalar` |
+    /// | `radius` | metrics | | `Scalar` |
+    /// | `average_path_length` | metrics | | `Scalar` |
+    pub fn with_defaults() -> Self {
+        // Stays empty, and so unmutated, when no algorithm feature is enabled.
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+        #[cfg(feature = "centrality")]
+        builtins::centrality(&mut registry);
+        #[cfg(feature = "community")]
+        builtins::community(&mut registry);
+        #[cfg(feature = "metrics")]
+        builtins::metrics(&mut registry);
+        registry
+    }
+}
+
+/// Runs the built-in algorithm called `name` on `graph`.
+///
+/// This builds [`Registry::with_defaults`] on every call; keep a `Registry` around instead
+/// when running many algorithms or registering custom ones.
+///
+/// # Errors
+///
+/// See [`Registry::run`].
+pub fn run<A, Ty>(
+    name: &str,
+    graph: &BaseGraph<A, f64, Ty>,
+    params: &Params,
+) -> Result<AnalysisResult>
+where
+    A: Debug,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    Registry::with_defaults().run(name, graph, params)
+}
+
+#[cfg(any(feature = "centrality", feature = "community", feature = "metrics"))]
+mod builtins {
+    use super::*;
+
+    #[cfg(feature = "centrality")]
+    pub(super) fn centrality<A, Ty>(registry: &mut Registry<A, Ty>)
+    where
+        A: Debug,
+        Ty: GraphConstructor<A, f64> + EdgeType,
+        BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+    {
+        use crate::centrality::betweenness::betweenness_centrality;
+        use crate::centrality::closeness::closeness_centrality;
+        use crate::centrality::degree::degree_centrality;
+        use crate::centrality::eigenvector::eigenvector_centrality;
+        use crate::centrality::harmonic::harmonic_centrality;
+        use crate::centrality::katz::katz_centrality;
+        use crate::centrality::pagerank::pagerank;
+
+        registry.register("degree", "Degree centrality", &[], |g, _| {
+            degree_centrality(g).map(AnalysisResult::NodeScores)
+        });
+        registry.register(
+            "pagerank",
+            "PageRank by power iteration",
+            &["damping", "max_iter", "tolerance"],
+            |g, p| {
+                pagerank(
+                    g,
+                    p.float("damping", 0.85)?,
+                    p.usize("max_iter", 100)?,
+                    p.float("tolerance", 1e-6)?,
+                    None,
+                )
+                .map(AnalysisResult::NodeScores)
+            },
+        );
+        registry.register(
+            "eigenvector",
+            "Eigenvector centrality",
+            &["max_iter", "tolerance"],
+            |g, p| {
+                eigenvector_centrality(g, p.usize("max_iter", 100)?, p.float("tolerance", 1e-6)?)
+                    .map(AnalysisResult::NodeScores)
+            },
+        );
+        registry.register(
+            "katz",
+            "Katz centrality with a uniform base score",
+            &["alpha", "max_iter", "tolerance"],
+            |g, p| {
+                katz_centrality(
+                    g,
+                    p.float("alpha", 0.1)?,
+                    None,
+                    p.usize("max_iter", 1000)?,
+                    p.float("tolerance", 1e-6)?,
+                )
+                .map(AnalysisResult::NodeScores)
+            },
+        );
+        registry.register(
+            "betweenness",
+            "Betweenness centrality",
+            &["normalized"],
+            |g, p| {
+                betweenness_centrality(g, p.bool("normalized", true)?)
+                    .map(AnalysisResult::NodeScores)
+            },
+        );
+        registry.register("closeness", "Closeness centrality", &[], |g, _| {
+            closeness_centrality(g).map(AnalysisResult::NodeScores)
+        });
+        registry.register("harmonic", "Harmonic centrality", &[], |g, _| {
+            harmonic_centrality(g).map(AnalysisResult::NodeScores)
+        });
+    }
+
+    #[cfg(feature = "community")]
+    pub(super) fn community<A, Ty>(registry: &mut Registry<A, Ty>)
+    where
+        Ty: GraphConstructor<A, f64> + EdgeType,
+    {
+        use crate::community::connected_components::connected_components;
+        use crate::community::label_propagation::weighted_label_propagation;
+        use crate::community::louvain::louvain;
+        use crate::community::node_maps::label_propagation_map;
+
+        registry.register(
+            "louvain",
+            "Louvain modularity optimization",
+            &["seed"],
+            |g, p| louvain(g, p.seed("seed")?).map(AnalysisResult::Communities),
+        );
+        registry.register(
+            "label_propagation",
+            "Label propagation community detection",
+            &["max_iter", "seed"],
+            |g, p| {
+                label_propagation_map(g, p.usize("max_iter", 100)?, p.seed("seed")?)
+                    .map(AnalysisResult::NodeLabels)
+            },
+        );
+        registry.register(
+            "weighted_label_propagation",
+            "Label propagation with edge-weighted votes",
+            &["max_iter", "seed"],
+            |g, p| {
+                weighted_label_propagation(g, p.usize("max_iter", 100)?, p.seed("seed")?)
+                    .map(AnalysisResult::NodeLabels)
+            },
+        );
+        registry.register(
+            "connected_components",
+            "Connected components, weakly connected for directed graphs",
+            &[],
+            |g, _| Ok(AnalysisResult::Communities(connected_components(g))),
+        );
+    }
+
+    #[cfg(feature = "metrics")]
+    pub(super) fn metrics<A, Ty>(registry: &mut Registry<A, Ty>)
+    where
+        Ty: GraphConstructor<A, f64> + EdgeType,
+    {
+        use crate::metrics::{
+            average_clustering_coefficient, average_path_length, diameter, radius, transitivity,
+        };
+
+        registry.register(
+            "transitivity",
+            "Global clustering coefficient",
+            &[],
+            |g, _| Ok(AnalysisResult::Scalar(transitivity(g))),
+        );
+        registry.register(
+            "average_clustering",
+            "Mean local clustering coefficient",
+            &[],
+            |g, _| Ok(AnalysisResult::Scalar(average_clustering_coefficient(g))),
+        );
+        registry.register("diameter", "Longest shortest path in hops", &[], |g, _| {
+            diameter(g)
+                .map(|d| AnalysisResult::Scalar(d as f64))
+                .ok_or_else(|| {
+                    GraphinaError::invalid_graph("Diameter needs a non-empty, connected graph")
+                })
+        });
+        registry.register("radius", "Smallest eccentricity in hops", &[], |g, _| {
+            radius(g)
+                .map(|r| AnalysisResult::Scalar(r as f64))
+                .ok_or_else(|| {
+                    GraphinaError::invalid_graph("Radius needs a non-empty, connected graph")
+                })
+        });
+        registry.register(
+            "average_path_length",
+            "Mean shortest path length in hops",
+            &[],
+            |g, _| {
+                average_path_length(g)
+                    .map(AnalysisResult::Scalar)
+                    .ok_or_else(|| {
+                        GraphinaError::invalid_graph(
+                            "Average path length needs a non-empty, connected graph",
+                        )
+                    })
+            },
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+
+    fn triangle_with_tail() -> Graph<(), f64> {
+        let mut g = Graph::new();
+        let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 1.0);
+        g.add_edge(n[2], n[0], 1.0);
+        g.add_edge(n[2], n[3], 1.0);
+        g
+    }
+
+    #[test]
+    fn test_params_typed_getters() {
+        let p = Params::new()
+            .with("x", 2)
+            .with("flag", true)
+            .with("name", "abc");
+        assert_eq!(p.float("x", 0.0).unwrap(), 2.0);
+        assert_eq!(p.usize("x", 0).unwrap(), 2);
+        assert_eq!(p.float("missing", 1.5).unwrap(), 1.5);
+        assert!(p.bool("flag", false).unwrap());
+        assert_eq!(p.seed("missing").unwrap(), None);
+        assert!(p.usize("flag", 0).is_err());
+        assert!(p.float("name", 0.0).is_err());
+        assert!(Params::new().with("n", -1).usize("n", 0).is_err());
+    }
+
+    #[test]
+    fn test_custom_registration_and_errors() {
+        let mut registry = Registry::new();
+        registry.register("order", "Node count", &["scale"], |g, p| {
+            Ok(AnalysisResult::Scalar(
+                g.node_count() as f64 * p.float("scale", 1.0)?,
+            ))
+        });
+        let g = triangle_with_tail();
+        let result = registry
+            .run("order", &g, &Params::new().with("scale", 0.5))
+            .unwrap();
+        assert_eq!(result.as_scalar(), Some(2.0));
+        assert_eq!(registry.names(), vec!["order"]);
+
+        let err = registry.run("missing", &g, &Params::new()).unwrap_err();
+        assert!(err.to_string().contains("order"));
+        assert!(
+            registry
+                .run("order", &g, &Params::new().with("sclae", 2.0))
+                .is_err()
+        );
+    }
+
+    #[cfg(all(feature = "centrality", feature = "community", feature = "metrics"))]
+    #[test]
+    fn test_defaults_dispatch_to_algorithms() {
+        let g = triangle_with_tail();
+        let registry = Registry::with_defaults();
+        assert!(registry.names().contains(&"pagerank"));
+
+        let direct = crate::centrality::pagerank::pagerank(&g, 0.85, 100, 1e-6, None).unwrap();
+        let via = registry.run("pagerank", &g, &Params::new()).unwrap();
+        assert_eq!(via.as_node_scores(), Some(&direct));
+
+        let groups = run("connected_components", &g, &Params::new()).unwrap();
+        assert_eq!(groups.as_communities().map(<[_]>::len), Some(1));
+        let d = run("diameter", &g, &Params::new()).unwrap();
+        assert_eq!(d.as_scalar(), Some(2.0));
+        assert!(run("pagerank", &g, &Params::new().with("damping", "high")).is_err());
+    }
+}
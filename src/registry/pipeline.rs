@@ -0,0 +1,575 @@
+/*!
+# Analysis Pipelines
+
+A [`Pipeline`] is a reusable, ordered list of steps run against one graph: cleaning,
+narrowing to a subgraph, analyses looked up in a [`Registry`], and exports. Building the
+pipeline does no work; [`Pipeline::run`] executes the steps in order and returns the final
+graph together with every named result.
+
+```rust
+use graphina::core::types::Graph;
+use graphina::core::validation::WeightRepair;
+use graphina::registry::pipeline::Pipeline;
+
+let mut g = Graph::<(), f64>::new();
+let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+g.add_edge(n[0], n[1], 1.0);
+g.add_edge(n[1], n[2], f64::NAN);
+g.add_node(()); // isolated
+
+let output = Pipeline::new()
+    .clean(WeightRepair::Replace(1.0))
+    .largest_component()
+    .analyze("diameter")
+    .analyze("radius")
+    .run(g)
+    .unwrap();
+assert_eq!(output.graph.node_count(), 3);
+assert_eq!(output.get("diameter").and_then(|r| r.as_scalar()), Some(2.0));
+```
+
+Results are cached for the duration of a run. Two analyses with the same algorithm and
+parameters run once, and the hop-distance measures `diameter`, `radius`, and
+`average_path_length` are answered by the pipeline itself from a single set of BFS trees
+shared between them. Any step that changes the graph clears the cache. To keep results
+across runs as well, use [`Pipeline::run_cached`] with a [`ResultCache`].
+*/
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use serde::Serialize;
+
+use super::{AnalysisResult, Params, Registry, ResultCache};
+use crate::core::error::{GraphinaError, Result};
+use crate::core::fingerprint::fingerprint_with_ids;
+use crate::core::io::{save_nodemap_csv, save_partition_json};
+use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph, NodeId, NodeMap};
+use crate::core::validation::{WeightRepair, sanitize_weights};
+use petgraph::EdgeType;
+
+type Transform<A, Ty> = Box<dyn Fn(BaseGraph<A, f64, Ty>) -> Result<BaseGraph<A, f64, Ty>>>;
+type GraphKey<A, Ty> = fn(&BaseGraph<A, f64, Ty>) -> Result<u64>;
+type Export<A, Ty> =
+    Box<dyn Fn(&BaseGraph<A, f64, Ty>, &HashMap<String, AnalysisResult>) -> Result<()>>;
+
+enum Step<A, Ty: GraphConstructor<A, f64>> {
+    Transform(Transform<A, Ty>),
+    Analyze {
+        key: String,
+        algorithm: String,
+        params: Params,
+    },
+    Export(Export<A, Ty>),
+}
+
+/// An ordered list of graph analysis steps.
+pub struct Pipeline<A, Ty: GraphConstructor<A, f64>> {
+    registry: Registry<A, Ty>,
+    steps: Vec<Step<A, Ty>>,
+}
+
+/// The final graph and the results of a [`Pipeline::run`], keyed by step name.
+#[derive(Debug, Clone)]
+pub struct PipelineOutput<A, Ty: GraphConstructor<A, f64>> {
+    /// The graph after every transforming step.
+    pub graph: BaseGraph<A, f64, Ty>,
+    /// Analysis results, keyed by the name given to each analysis step.
+    pub results: HashMap<String, AnalysisResult>,
+}
+
+impl<A, Ty: GraphConstructor<A, f64>> PipelineOutput<A, Ty> {
+    /// Returns the result stored under `key`, if any.
+    pub fn get(&self, key: &str) -> Option<&AnalysisResult> {
+        self.results.get(key)
+    }
+}
+
+impl<A, Ty> Default for Pipeline<A, Ty>
+where
+    A: Debug,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, Ty> Pipeline<A, Ty>
+where
+    A: Debug,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    /// Creates an empty pipeline whose analyses come from [`Registry::with_defaults`].
+    pub fn new() -> Self {
+        Self::with_registry(Registry::with_defaults())
+    }
+}
+
+impl<A, Ty> Pipeline<A, Ty>
+where
+    Ty: GraphConstructor<A, f64> + EdgeType,
+{
+    /// Creates an empty pipeline that looks analyses up in `registry`.
+    pub fn with_registry(registry: Registry<A, Ty>) -> Self {
+        Pipeline {
+            registry,
+            steps: Vec::new(),
+        }
+    }
+
+    /// Repairs NaN and infinite weights with [`sanitize_weights`].
+    pub fn clean(self, policy: WeightRepair) -> Self {
+        self.transform(move |mut graph| {
+            sanitize_weights(&mut graph, policy)?;
+            Ok(graph)
+        })
+    }
+
+    /// Keeps only the nodes for which `keep` returns `true`, with the edges among them.
+    ///
+    /// Removal leaves the remaining `NodeId`s unchanged, so results stay comparable with the
+    /// input graph.
+    pub fn filter_nodes<F>(self, keep: F) -> Self
+    where
+        F: Fn(NodeId, &A) -> bool + 'static,
+    {
+        self.transform(move |mut graph| {
+            graph.retain_nodes(|node, attr| keep(node, attr));
+            Ok(graph)
+        })
+    }
+
+    /// Keeps only the largest connected component, weakly connected for directed graphs.
+    /// Ties go to the component found first in node order.
+    pub fn largest_component(self) -> Self {
+        self.transform(|mut graph| {
+            let keep = largest_component_nodes(&graph);
+            graph.retain_nodes(|node, _| keep.contains_key(&node));
+            Ok(graph)
+        })
+    }
+
+    /// Adds a custom step that replaces the graph.
+    pub fn transform<F>(mut self, step: F) -> Self
+    where
+        F: Fn(BaseGraph<A, f64, Ty>) -> Result<BaseGraph<A, f64, Ty>> + 'static,
+    {
+        self.steps.push(Step::Transform(Box::new(step)));
+        self
+    }
+
+    /// Runs `algorithm` with default parameters and stores the result under its own name.
+    pub fn analyze(self, algorithm: &str) -> Self {
+        self.analyze_with(algorithm, algorithm, Params::new())
+    }
+
+    /// Runs `algorithm` with `params` and stores the result under `key`.
+    pub fn analyze_with(mut self, key: &str, algorithm: &str, params: Params) -> Self {
+        self.steps.push(Step::Analyze {
+            key: key.to_string(),
+            algorithm: algorithm.to_string(),
+            params,
+        });
+        self
+    }
+
+    /// Adds a custom step that reads the current graph and the results so far, for example to
+    /// write them somewhere.
+    pub fn export<F>(mut self, step: F) -> Self
+    where
+        F: Fn(&BaseGraph<A, f64, Ty>, &HashMap<String, AnalysisResult>) -> Result<()> + 'static,
+    {
+        self.steps.push(Step::Export(Box::new(step)));
+        self
+    }
+
+    /// Writes the `NodeScores` result stored under `key` as CSV with
+    /// [`save_nodemap_csv`].
+    pub fn export_scores_csv(self, key: &str, path: &str) -> Self {
+        let (key, path) = (key.to_string(), path.to_string());
+        self.export(move |_, results| {
+            let scores = match results.get(&key) {
+                Some(AnalysisResult::NodeScores(scores)) => scores,
+                _ => return Err(missing_result(&key, "NodeScores")),
+            };
+            save_nodemap_csv(&path, scores).map_err(GraphinaError::from)
+        })
+    }
+
+    /// Writes the `Communities` result stored under `key` as JSON with
+    /// [`save_partition_json`].
+    pub fn export_partition_json(self, key: &str, path: &str) -> Self {
+        let (key, path) = (key.to_string(), path.to_string());
+        self.export(move |_, results| {
+            let groups = match results.get(&key) {
+                Some(AnalysisResult::Communities(groups)) => groups,
+                _ => return Err(missing_result(&key, "Communities")),
+            };
+            save_partition_json(&path, groups).map_err(GraphinaError::from)
+        })
+    }
+
+    /// Returns the number of steps.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Runs every step in order on `graph`.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failing step and returns its error. Analyses fail as described in
+    /// [`Registry::run`]; the shared distance measures return `InvalidGraph` for an empty or
+    /// disconnected graph, and exports return `InvalidArgument` if the result they write is
+    /// missing or of the wrong kind.
+    pub fn run(&self, graph: BaseGraph<A, f64, Ty>) -> Result<PipelineOutput<A, Ty>> {
+        self.execute(graph, None)
+    }
+
+    fn execute(
+        &self,
+        graph: BaseGraph<A, f64, Ty>,
+        disk: Option<(&ResultCache, GraphKey<A, Ty>)>,
+    ) -> Result<PipelineOutput<A, Ty>> {
+        let mut graph = graph;
+        let mut results = HashMap::new();
+        let mut cache: HashMap<(String, String), AnalysisResult> = HashMap::new();
+        let mut hops: Option<HopTable> = None;
+        let mut graph_key: Option<u64> = None;
+        for step in &self.steps {
+            match step {
+                Step::Transform(apply) => {
+                    graph = apply(graph)?;
+                    cache.clear();
+                    hops = None;
+                    graph_key = None;
+                }
+                Step::Analyze {
+                    key,
+                    algorithm,
+                    params,
+                } => {
+                    let cache_key = (algorithm.clone(), params.cache_key());
+                    let result = match cache.get(&cache_key) {
+                        Some(result) => result.clone(),
+                        None => {
+                            let stored = match disk {
+                                Some((store, key_of)) => {
+                                    let k = match graph_key {
+                                        Some(k) => k,
+                                        None => *graph_key.insert(key_of(&graph)?),
+                                    };
+                                    match store.get(algorithm, params, k) {
+                                        Some(hit) => hit,
+                                        None => {
+                                            let result =
+                                                self.compute(&graph, algorithm, params, &mut hops)?;
+                                            store.put(algorithm, params, k, &result)?;
+                                            result
+                                        }
+                                    }
+                                }
+                                None => self.compute(&graph, algorithm, params, &mut hops)?,
+                            };
+                            cache.insert(cache_key, stored.clone());
+                            stored
+                        }
+                    };
+                    results.insert(key.clone(), result);
+                }
+                Step::Export(apply) => apply(&graph, &results)?,
+            }
+        }
+        Ok(PipelineOutput { graph, results })
+    }
+
+    fn compute(
+        &self,
+        graph: &BaseGraph<A, f64, Ty>,
+        algorithm: &str,
+        params: &Params,
+        hops: &mut Option<HopTable>,
+    ) -> Result<AnalysisResult> {
+        match shared_distance(algorithm, params) {
+            Some(measure) => hops
+                .get_or_insert_with(|| HopTable::new(graph))
+                .measure(measure),
+            None => self.registry.run(algorithm, graph, params),
+        }
+    }
+}
+
+impl<A, Ty> Pipeline<A, Ty>
+where
+    A: Serialize,
+    Ty: GraphConstructor<A, f64> + EdgeType,
+{
+    /// Runs the pipeline like [`Pipeline::run`], reusing and storing analysis results in
+    /// `cache`.
+    ///
+    /// Each analysis is looked up under the current graph's
+    /// [`fingerprint_with_ids`], so results computed before a transforming step are never
+    /// reused after it unless the step left the graph unchanged.
+    ///
+    /// # Errors
+    ///
+    /// As for [`Pipeline::run`], plus `SerializationError` or `IoError` if the graph cannot be
+    /// fingerprinted or an entry cannot be written.
+    pub fn run_cached(
+        &self,
+        graph: BaseGraph<A, f64, Ty>,
+        cache: &ResultCache,
+    ) -> Result<PipelineOutput<A, Ty>> {
+        self.execute(graph, Some((cache, fingerprint_with_ids::<A, f64, Ty>)))
+    }
+}
+
+impl<Ty> Pipeline<i32, Ty>
+where
+    Ty: GraphConstructor<i32, f64> + EdgeType,
+{
+    /// Loads an edge list with [`read_edge_list`](crate::core::io::read_edge_list) and runs
+    /// the pipeline on it.
+    pub fn run_edge_list(&self, path: &str, sep: char) -> Result<PipelineOutput<i32, Ty>> {
+        let mut graph = BaseGraph::<i32, f64, Ty>::new();
+        crate::core::io::read_edge_list(path, &mut graph, sep)?;
+        self.run(graph)
+    }
+}
+
+fn missing_result(key: &str, kind: &str) -> GraphinaError {
+    GraphinaError::invalid_argument(format!("Export needs a {} result under '{}'", kind, key))
+}
+
+#[derive(Clone, Copy)]
+enum Distance {
+    Diameter,
+    Radius,
+    AveragePathLength,
+}
+
+fn shared_distance(algorithm: &str, params: &Params) -> Option<Distance> {
+    if params.keys().next().is_some() {
+        return None;
+    }
+    match algorithm {
+        "diameter" => Some(Distance::Diameter),
+        "radius" => Some(Distance::Radius),
+        "average_path_length" => Some(Distance::AveragePathLength),
+        _ => None,
+    }
+}
+
+/// Hop distances from every node, following edge direction. `None` when some node cannot
+/// reach another or the graph is empty, which makes all three measures undefined.
+struct HopTable {
+    eccentricities: Option<Vec<usize>>,
+    total: usize,
+    pairs: usize,
+}
+
+impl HopTable {
+    fn new<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> Self
+    where
+        Ty: GraphConstructor<A, f64> + EdgeType,
+    {
+        let n = graph.node_count();
+        let mut table = HopTable {
+            eccentricities: Some(Vec::with_capacity(n)),
+            total: 0,
+            pairs: 0,
+        };
+        if n == 0 {
+            table.eccentricities = None;
+            return table;
+        }
+        for source in graph.node_ids() {
+            let mut dist: NodeMap<usize> = NodeMap::default();
+            dist.insert(source, 0);
+            let mut queue = std::collections::VecDeque::from([source]);
+            let mut farthest = 0;
+            while let Some(u) = queue.pop_front() {
+                let d = dist[&u];
+                for v in graph.neighbors(u) {
+                    if let std::collections::hash_map::Entry::Vacant(slot) = dist.entry(v) {
+                        slot.insert(d + 1);
+                        farthest = farthest.max(d + 1);
+                        table.total += d + 1;
+                        table.pairs += 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+            if dist.len() != n {
+                table.eccentricities = None;
+                return table;
+            }
+            if let Some(e) = table.eccentricities.as_mut() {
+                e.push(farthest);
+            }
+        }
+        table
+    }
+
+    fn measure(&self, measure: Distance) -> Result<AnalysisResult> {
+        let ecc = self.eccentricities.as_ref().ok_or_else(|| {
+            GraphinaError::invalid_graph("Distance measures need a non-empty, connected graph")
+        })?;
+        let value = match measure {
+            Distance::Diameter => ecc.iter().copied().max().unwrap_or(0) as f64,
+            Distance::Radius => ecc.iter().copied().min().unwrap_or(0) as f64,
+            Distance::AveragePathLength if self.pairs == 0 => 0.0,
+            Distance::AveragePathLength => self.total as f64 / self.pairs as f64,
+        };
+        Ok(AnalysisResult::Scalar(value))
+    }
+}
+
+/// Nodes of the largest weakly connected component, as a set.
+fn largest_component_nodes<A, Ty>(graph: &BaseGraph<A, f64, Ty>) -> NodeMap<()>
+where
+    Ty: GraphConstructor<A, f64> + EdgeType,
+{
+    let mut seen: NodeMap<()> = NodeMap::default();
+    let mut best: Vec<NodeId> = Vec::new();
+    for start in graph.node_ids() {
+        if seen.contains_key(&start) {
+            continue;
+        }
+        seen.insert(start, ());
+        let mut component = vec![start];
+        let mut stack = vec![start];
+        while let Some(u) = stack.pop() {
+            for v in graph.inner.neighbors_undirected(u.0).map(NodeId::new) {
+                if seen.insert(v, ()).is_none() {
+                    component.push(v);
+                    stack.push(v);
+                }
+            }
+        }
+        if component.len() > best.len() {
+            best = component;
+        }
+    }
+    best.into_iter().map(|v| (v, ())).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    fn two_paths() -> Graph<u32, f64> {
+        // A 4-node path and a separate edge.
+        let mut g = Graph::new();
+        let n: Vec<_> = (0..6).map(|i| g.add_node(i)).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], f64::INFINITY);
+        g.add_edge(n[2], n[3], 1.0);
+        g.add_edge(n[4], n[5], 1.0);
+        g
+    }
+
+    #[test]
+    fn test_pipeline_cleans_narrows_and_shares_distances() {
+        let output = Pipeline::with_registry(Registry::new())
+            .clean(WeightRepair::DropEdge)
+            .filter_nodes(|_, &attr| attr != 5)
+            .largest_component()
+            .analyze("diameter")
+            .analyze_with("apl", "average_path_length", Params::new())
+            .analyze("radius")
+            .run(two_paths())
+            .unwrap();
+        // Dropping the infinite edge splits the path, leaving components {0, 1}, {2, 3}, {4}.
+        assert_eq!(output.graph.node_count(), 2);
+        assert_eq!(
+            output.get("diameter").and_then(|r| r.as_scalar()),
+            Some(1.0)
+        );
+        assert_eq!(output.get("apl").and_then(|r| r.as_scalar()), Some(1.0));
+        assert_eq!(output.get("radius").and_then(|r| r.as_scalar()), Some(1.0));
+    }
+
+    #[test]
+    fn test_pipeline_errors_stop_the_run() {
+        let disconnected = Pipeline::with_registry(Registry::new()).analyze("diameter");
+        assert!(disconnected.run(two_paths()).is_err());
+
+        let unknown = Pipeline::with_registry(Registry::new()).analyze("no_such_algorithm");
+        assert!(unknown.run(two_paths()).is_err());
+
+        let missing = Pipeline::with_registry(Registry::new()).export_scores_csv("x", "x.csv");
+        assert!(missing.run(two_paths()).is_err());
+
+        // Directed distances follow edge direction, so a one-way path is not strongly connected.
+        let mut d = Digraph::<u32, f64>::new();
+        let a = d.add_node(0);
+        let b = d.add_node(1);
+        d.add_edge(a, b, 1.0);
+        let result = Pipeline::with_registry(Registry::new())
+            .largest_component()
+            .analyze("radius")
+            .run(d);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_pipeline_caches_repeated_analyses() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let mut registry = Registry::new();
+        registry.register("order", "Node count", &[], |g, _| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            Ok(AnalysisResult::Scalar(g.node_count() as f64))
+        });
+        let output = Pipeline::with_registry(registry)
+            .analyze_with("before", "order", Params::new())
+            .analyze_with("again", "order", Params::new())
+            .filter_nodes(|_, &attr| attr < 3)
+            .analyze_with("after", "order", Params::new())
+            .run(two_paths())
+            .unwrap();
+        assert_eq!(output.get("before").and_then(|r| r.as_scalar()), Some(6.0));
+        assert_eq!(output.get("again"), output.get("before"));
+        assert_eq!(output.get("after").and_then(|r| r.as_scalar()), Some(3.0));
+        // The repeated step is served from the cache; the filter invalidates it.
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_pipeline_run_cached_reuses_results_across_runs() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let mut registry = Registry::new();
+        registry.register("order", "Node count", &[], |g, _| {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            Ok(AnalysisResult::Scalar(g.node_count() as f64))
+        });
+        let pipeline = Pipeline::with_registry(registry)
+            .analyze("order")
+            .largest_component()
+            .analyze_with("largest", "order", Params::new());
+        let dir = std::env::temp_dir().join(format!("graphina-pipeline-{}", std::process::id()));
+        let cache = ResultCache::new(&dir).unwrap();
+
+        let first = pipeline.run_cached(two_paths(), &cache).unwrap();
+        let second = pipeline.run_cached(two_paths(), &cache).unwrap();
+        // Both graph versions are computed once, the second run is served from disk.
+        assert_eq!(CALLS.load(Ordering::SeqCst), 2);
+        assert_eq!(first.results, second.results);
+        assert_eq!(second.get("order").and_then(|r| r.as_scalar()), Some(6.0));
+        assert_eq!(second.get("largest").and_then(|r| r.as_scalar()), Some(4.0));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,401 @@
+/*!
+# Background Tasks
+
+Runs heavy algorithms off the caller's thread and hands back a [`TaskHandle`]. The handle is
+a [`Future`], so an async service can `.await` it on any executor (Tokio, async-std, or a
+hand-rolled one) without parking an executor thread, and it also offers blocking
+[`wait`](TaskHandle::wait) and non-blocking [`progress`](TaskHandle::progress) and
+[`is_finished`](TaskHandle::is_finished) checks.
+
+```rust
+use std::sync::Arc;
+use graphina::core::types::Graph;
+use graphina::registry::{spawn, Params, Registry};
+
+let mut g = Graph::<(), f64>::new();
+let a = g.add_node(());
+let b = g.add_node(());
+g.add_edge(a, b, 1.0);
+let graph = Arc::new(g);
+
+// Any registered algorithm, by name.
+let registry = Registry::with_defaults();
+let handle = registry.spawn("degree", Arc::clone(&graph), Params::new());
+
+// Or any closure, reporting its own progress.
+let count = spawn(move |progress| {
+    progress.set(0.5);
+    Ok(graph.node_count())
+});
+assert_eq!(count.wait().unwrap(), 2);
+assert!(handle.wait().unwrap().as_node_scores().is_some());
+```
+
+Tasks run on a pool of background threads owned by this module, one per available core,
+created on first use. It is separate from Rayon's global pool, so a long task never
+starves the `parallel` algorithms, which can still be called from inside a task. Graphs are
+passed as [`Arc`]s because a task can outlive the caller's stack frame.
+
+Cancellation is cooperative. [`TaskHandle::cancel`] stops a task that has not started yet,
+and a closure passed to [`spawn`] can poll [`Progress::is_cancelled`] to stop early; the
+registry algorithms themselves run to completion once started. A panic inside a task is
+caught and surfaces as an `AlgorithmError` instead of taking down a pool thread.
+*/
+
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, OnceLock};
+use std::task::{Context, Poll, Waker};
+
+use super::{AnalysisResult, Params, Registry};
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, GraphinaGraph};
+use petgraph::EdgeType;
+use std::fmt::Debug;
+
+/// Progress and cancellation state shared between a task and its handle.
+#[derive(Debug, Default)]
+pub struct Progress {
+    fraction: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl Progress {
+    /// Records how much of the task is done, as a fraction clamped to `[0, 1]`.
+    ///
+    /// NaN is ignored. Tasks that never call this report `0.0` until they finish.
+    pub fn set(&self, fraction: f64) {
+        if !fraction.is_nan() {
+            self.fraction
+                .store(fraction.clamp(0.0, 1.0).to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// The fraction last recorded with [`set`](Self::set).
+    pub fn fraction(&self) -> f64 {
+        f64::from_bits(self.fraction.load(Ordering::Relaxed))
+    }
+
+    /// Whether the handle asked the task to stop.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+struct State<T> {
+    result: Option<Result<T>>,
+    finished: bool,
+    waker: Option<Waker>,
+}
+
+struct Shared<T> {
+    progress: Progress,
+    state: Mutex<State<T>>,
+    done: Condvar,
+}
+
+impl<T> Shared<T> {
+    fn new() -> Self {
+        Shared {
+            progress: Progress::default(),
+            state: Mutex::new(State {
+                result: None,
+                finished: false,
+                waker: None,
+            }),
+            done: Condvar::new(),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State<T>> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn finish(&self, result: Result<T>) {
+        let waker = {
+            let mut state = self.lock();
+            state.result = Some(result);
+            state.finished = true;
+            state.waker.take()
+        };
+        self.done.notify_all();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// A handle to a task running in the background.
+///
+/// Await it, or call [`wait`](Self::wait), to get the task's result. Dropping the handle
+/// does not stop the task; call [`cancel`](Self::cancel) first for that.
+pub struct TaskHandle<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T> TaskHandle<T> {
+    /// A handle that has already finished with `result`.
+    fn finished(result: Result<T>) -> Self {
+        let shared = Shared::new();
+        shared.finish(result);
+        TaskHandle {
+            shared: Arc::new(shared),
+        }
+    }
+
+    /// The fraction of the task reported done so far, `1.0` once it succeeded.
+    pub fn progress(&self) -> f64 {
+        self.shared.progress.fraction()
+    }
+
+    /// Whether the task has finished, successfully or not.
+    pub fn is_finished(&self) -> bool {
+        self.shared.lock().finished
+    }
+
+    /// Asks the task to stop.
+    ///
+    /// A task that has not started yet finishes with an `AlgorithmError` without running. A
+    /// running task only stops if it checks [`Progress::is_cancelled`].
+    pub fn cancel(&self) {
+        self.shared
+            .progress
+            .cancelled
+            .store(true, Ordering::Relaxed);
+    }
+
+    /// Blocks the calling thread until the task finishes and returns its result.
+    ///
+    /// Do not call this from an async context; `.await` the handle there instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns the task's own error, or `AlgorithmError` if it was cancelled or panicked.
+    pub fn wait(self) -> Result<T> {
+        let mut state = self.shared.lock();
+        while !state.finished {
+            state = self
+                .shared
+                .done
+                .wait(state)
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+        }
+        take_result(&mut state)
+    }
+}
+
+impl<T> Future for TaskHandle<T> {
+    type Output = Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock();
+        if state.finished {
+            return Poll::Ready(take_result(&mut state));
+        }
+        match &state.waker {
+            Some(waker) if waker.will_wake(cx.waker()) => {}
+            _ => state.waker = Some(cx.waker().clone()),
+        }
+        Poll::Pending
+    }
+}
+
+impl<T> Debug for TaskHandle<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskHandle")
+            .field("progress", &self.progress())
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
+
+fn take_result<T>(state: &mut State<T>) -> Result<T> {
+    state.result.take().unwrap_or_else(|| {
+        Err(GraphinaError::algorithm_error(
+            "Task result was already taken",
+        ))
+    })
+}
+
+/// The background pool, or `None` if it could not be created.
+fn pool() -> Option<&'static rayon::ThreadPool> {
+    static POOL: OnceLock<Option<rayon::ThreadPool>> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("graphina-task-{}", i))
+            .build()
+            .ok()
+    })
+    .as_ref()
+}
+
+/// Runs `task` on the background pool and returns a handle to its result.
+///
+/// The closure receives the task's [`Progress`], through which it can report how far it
+/// got and check whether it was cancelled. If the pool cannot be created, the task runs on
+/// a thread of its own instead.
+pub fn spawn<T, F>(task: F) -> TaskHandle<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&Progress) -> Result<T> + Send + 'static,
+{
+    let shared = Arc::new(Shared::new());
+    let worker = Arc::clone(&shared);
+    let job = move || {
+        let progress = &worker.progress;
+        let result = if progress.is_cancelled() {
+            Err(GraphinaError::algorithm_error(
+                "Task was cancelled before it started",
+            ))
+        } else {
+            catch_unwind(AssertUnwindSafe(|| task(progress))).unwrap_or_else(|payload| {
+                let reason = payload
+                    .downcast_ref::<&str>()
+                    .map(|s| s.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                Err(GraphinaError::algorithm_error(format!(
+                    "Task panicked: {}",
+                    reason
+                )))
+            })
+        };
+        if result.is_ok() {
+            progress.set(1.0);
+        }
+        worker.finish(result);
+    };
+    match pool() {
+        Some(pool) => pool.spawn(job),
+        None => {
+            std::thread::spawn(job);
+        }
+    }
+    TaskHandle { shared }
+}
+
+impl<A, Ty> Registry<A, Ty>
+where
+    A: Send + Sync + 'static,
+    Ty: GraphConstructor<A, f64> + Send + Sync + 'static,
+{
+    /// Runs the algorithm called `name` on `graph` in the background.
+    ///
+    /// The name and parameters are checked before anything is spawned, so those errors come
+    /// back from the handle immediately, without a detour through the pool.
+    pub fn spawn(
+        &self,
+        name: &str,
+        graph: Arc<BaseGraph<A, f64, Ty>>,
+        params: Params,
+    ) -> TaskHandle<AnalysisResult> {
+        match self.checked_entry(name, &params) {
+            Ok(entry) => spawn(move |_| (entry.run)(&graph, &params)),
+            Err(error) => TaskHandle::finished(Err(error)),
+        }
+    }
+}
+
+/// Runs the built-in algorithm called `name` on `graph` in the background.
+///
+/// The background counterpart of [`run`](super::run).
+pub fn spawn_run<A, Ty>(
+    name: &str,
+    graph: Arc<BaseGraph<A, f64, Ty>>,
+    params: Params,
+) -> TaskHandle<AnalysisResult>
+where
+    A: Debug + Send + Sync + 'static,
+    Ty: GraphConstructor<A, f64> + EdgeType + Send + Sync + 'static,
+    BaseGraph<A, f64, Ty>: GraphinaGraph<A, f64>,
+{
+    Registry::with_defaults().spawn(name, graph, params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+    use std::sync::mpsc;
+    use std::task::Wake;
+
+    /// Wakes a blocked `block_on` by sending on a channel.
+    struct ChannelWaker(Mutex<mpsc::Sender<()>>);
+
+    impl Wake for ChannelWaker {
+        fn wake(self: Arc<Self>) {
+            let _ = self.0.lock().unwrap().send(());
+        }
+    }
+
+    /// A minimal executor that polls `future` until it is ready.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let (tx, rx) = mpsc::channel();
+        let waker = Waker::from(Arc::new(ChannelWaker(Mutex::new(tx))));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+                return output;
+            }
+            rx.recv().unwrap();
+        }
+    }
+
+    #[test]
+    fn test_spawned_tasks_resolve_as_futures_and_report_progress() {
+        let (release, gate) = mpsc::channel::<()>();
+        let handle = spawn(move |progress| {
+            progress.set(0.25);
+            gate.recv().unwrap();
+            Ok(7)
+        });
+        while handle.progress() < 0.25 {
+            std::thread::yield_now();
+        }
+        assert!(!handle.is_finished());
+        release.send(()).unwrap();
+        assert_eq!(block_on(handle).unwrap(), 7);
+
+        let panicked = spawn(|_| -> Result<()> { panic!("boom") });
+        let error = panicked.wait().unwrap_err();
+        assert!(error.to_string().contains("boom"));
+
+        let stopped = spawn(|progress| {
+            while !progress.is_cancelled() {
+                std::thread::yield_now();
+            }
+            Err::<(), _>(GraphinaError::algorithm_error("stopped"))
+        });
+        stopped.cancel();
+        assert!(stopped.wait().is_err());
+    }
+
+    #[test]
+    fn test_registry_spawn_matches_run() {
+        let mut g = Graph::<(), f64>::new();
+        let a = g.add_node(());
+        let b = g.add_node(());
+        g.add_edge(a, b, 1.0);
+        let graph = Arc::new(g);
+
+        let mut registry = Registry::new();
+        registry.register("order", "Number of nodes", &[], |g, _| {
+            Ok(AnalysisResult::Scalar(g.node_count() as f64))
+        });
+        let handle = registry.spawn("order", Arc::clone(&graph), Params::new());
+        assert_eq!(handle.wait().unwrap(), AnalysisResult::Scalar(2.0));
+
+        let unknown = registry.spawn("missing", Arc::clone(&graph), Params::new());
+        assert!(unknown.is_finished());
+        assert!(block_on(unknown).is_err());
+        let rejected = registry.spawn("order", graph, Params::new().with("k", 1));
+        assert!(rejected.wait().is_err());
+    }
+}
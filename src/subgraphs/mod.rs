@@ -1,9 +1,10 @@
 //! Subgraph operations module.
 //!
-//! Extract and manipulate subgraphs.
+//! Extract and manipulate subgraphs, and rewrite graphs by pattern rules.
 //! All operations depend only on the core module for basic graph operations.
 
 pub mod operations;
+pub mod rewrite;
 
 // Re-export subgraph operations as extension methods
 pub use operations::SubgraphOps;
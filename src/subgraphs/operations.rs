@@ -8,10 +8,12 @@ Work efficiently with graph subsets:
 - Induced subgraphs
 */
 
-use std::collections::{HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet, VecDeque};
 
 use crate::core::error::{GraphinaError, Result};
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap, NodeSet};
+use ordered_float::OrderedFloat;
 use petgraph::EdgeType;
 
 /// Trait for subgraph operations on graphs.
@@ -30,6 +32,17 @@ where
     /// Extracts an ego network centered on a node with a given radius.
     fn ego_graph(&self, center: NodeId, radius: usize) -> Result<BaseGraph<A, W, Ty>>;
 
+    /// Extracts an ego network containing every node within `max_distance` total edge cost
+    /// of the center.
+    fn weighted_ego_graph<F>(
+        &self,
+        center: NodeId,
+        max_distance: f64,
+        cost: F,
+    ) -> Result<BaseGraph<A, W, Ty>>
+    where
+        F: Fn(NodeId, NodeId, &W) -> Option<f64>;
+
     /// Filters nodes based on a predicate and returns a new subgraph.
     fn filter_nodes<F>(&self, predicate: F) -> BaseGraph<A, W, Ty>
     where
@@ -43,6 +56,16 @@ where
     /// Returns the k-hop neighborhood of a node.
     fn k_hop_neighbors(&self, start: NodeId, k: usize) -> Vec<NodeId>;
 
+    /// Returns the nodes within `max_distance` total edge cost of a node, with their distances.
+    fn weighted_k_hop_neighbors<F>(
+        &self,
+        start: NodeId,
+        max_distance: f64,
+        cost: F,
+    ) -> Result<Vec<(NodeId, f64)>>
+    where
+        F: Fn(NodeId, NodeId, &W) -> Option<f64>;
+
     /// Returns nodes connected to the given node (including itself).
     fn connected_component(&self, start: NodeId) -> Vec<NodeId>;
 
@@ -50,6 +73,71 @@ where
     fn component_subgraph(&self, start: NodeId) -> Result<BaseGraph<A, W, Ty>>;
 }
 
+/// Dijkstra from `start` that stops expanding past `max_distance`.
+///
+/// Returns the reached nodes in the order they are settled, so distances are nondecreasing.
+/// `cost` gives the length of the edge from `u` to `v`, or `None` to treat it as impassable.
+fn nodes_within_distance<A, W, Ty, F>(
+    graph: &BaseGraph<A, W, Ty>,
+    start: NodeId,
+    max_distance: f64,
+    cost: F,
+) -> Result<Vec<(NodeId, f64)>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+    F: Fn(NodeId, NodeId, &W) -> Option<f64>,
+{
+    if !graph.contains_node(start) {
+        return Err(GraphinaError::node_not_found(format!(
+            "Start node {} not found",
+            start.index()
+        )));
+    }
+    if max_distance.is_nan() || max_distance < 0.0 {
+        return Err(GraphinaError::invalid_argument(format!(
+            "max_distance must be nonnegative, got {}",
+            max_distance
+        )));
+    }
+
+    let mut best: NodeMap<f64> = NodeMap::default();
+    let mut settled = NodeSet::default();
+    let mut order = Vec::new();
+    let mut heap = BinaryHeap::new();
+    best.insert(start, 0.0);
+    heap.push(Reverse((OrderedFloat(0.0), start)));
+
+    while let Some(Reverse((OrderedFloat(dist), node))) = heap.pop() {
+        if !settled.insert(node) {
+            continue;
+        }
+        order.push((node, dist));
+        for (neighbor, weight) in graph.outgoing_edges(node) {
+            let Some(step) = cost(node, neighbor, weight) else {
+                continue;
+            };
+            if step.is_nan() || step < 0.0 {
+                return Err(GraphinaError::invalid_argument(format!(
+                    "Edge costs must be nonnegative, got {} on edge ({}, {})",
+                    step,
+                    node.index(),
+                    neighbor.index()
+                )));
+            }
+            let next = dist + step;
+            if next > max_distance || settled.contains(&neighbor) {
+                continue;
+            }
+            if best.get(&neighbor).is_none_or(|&d| next < d) {
+                best.insert(neighbor, next);
+                heap.push(Reverse((OrderedFloat(next), neighbor)));
+            }
+        }
+    }
+
+    Ok(order)
+}
+
 impl<A, W, Ty> SubgraphOps<A, W, Ty> for BaseGraph<A, W, Ty>
 where
     A: Clone,
@@ -200,6 +288,53 @@ where
         self.subgraph(&nodes_vec)
     }
 
+    /// Extracts an ego network containing every node within `max_distance` total edge cost
+    /// of the center.
+    ///
+    /// Where `ego_graph` counts hops, this sums edge costs along shortest paths, which suits
+    /// travel-time or distance neighborhoods. `cost(u, v, w)` gives the cost of traversing the
+    /// edge from `u` to `v`; returning `None` excludes the edge from the search, although it is
+    /// still copied into the result when both endpoints are reached. Directed graphs follow
+    /// outgoing edges only.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NodeNotFound` if the center is missing, and `InvalidArgument` if `max_distance`
+    /// or an edge cost is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    /// use graphina::subgraphs::SubgraphOps;
+    ///
+    /// let mut g = Graph::<i32, f64>::new();
+    /// let n1 = g.add_node(1);
+    /// let n2 = g.add_node(2);
+    /// let n3 = g.add_node(3);
+    /// g.add_edge(n1, n2, 2.0);
+    /// g.add_edge(n2, n3, 5.0);
+    ///
+    /// // Within 4.0 of n1 only n2 is reachable
+    /// let ego = g.weighted_ego_graph(n1, 4.0, |_, _, w| Some(*w)).unwrap();
+    /// assert_eq!(ego.node_count(), 2);
+    /// ```
+    fn weighted_ego_graph<F>(
+        &self,
+        center: NodeId,
+        max_distance: f64,
+        cost: F,
+    ) -> Result<BaseGraph<A, W, Ty>>
+    where
+        F: Fn(NodeId, NodeId, &W) -> Option<f64>,
+    {
+        let nodes: Vec<NodeId> = nodes_within_distance(self, center, max_distance, cost)?
+            .into_iter()
+            .map(|(node, _)| node)
+            .collect();
+        self.subgraph(&nodes)
+    }
+
     /// Filters nodes based on a predicate and returns a new subgraph.
     ///
     /// # Example
@@ -334,6 +469,44 @@ where
         result
     }
 
+    /// Returns the nodes within `max_distance` total edge cost of a node, with their distances.
+    ///
+    /// The start node comes first with distance zero, and the rest follow in nondecreasing
+    /// order of distance. `cost` works as in `weighted_ego_graph`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `NodeNotFound` if the start node is missing, and `InvalidArgument` if
+    /// `max_distance` or an edge cost is negative or NaN.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    /// use graphina::subgraphs::SubgraphOps;
+    ///
+    /// let mut g = Graph::<i32, f64>::new();
+    /// let n1 = g.add_node(1);
+    /// let n2 = g.add_node(2);
+    /// let n3 = g.add_node(3);
+    /// g.add_edge(n1, n2, 1.5);
+    /// g.add_edge(n2, n3, 1.0);
+    ///
+    /// let reached = g.weighted_k_hop_neighbors(n1, 3.0, |_, _, w| Some(*w)).unwrap();
+    /// assert_eq!(reached, vec![(n1, 0.0), (n2, 1.5), (n3, 2.5)]);
+    /// ```
+    fn weighted_k_hop_neighbors<F>(
+        &self,
+        start: NodeId,
+        max_distance: f64,
+        cost: F,
+    ) -> Result<Vec<(NodeId, f64)>>
+    where
+        F: Fn(NodeId, NodeId, &W) -> Option<f64>,
+    {
+        nodes_within_distance(self, start, max_distance, cost)
+    }
+
     /// Returns nodes connected to the given node (including itself).
     ///
     /// For undirected graphs, returns the connected component containing the node.
@@ -489,6 +662,44 @@ mod tests {
         assert_eq!(neighbors.len(), 3); // n1, n2, n3
     }
 
+    #[test]
+    fn test_weighted_ego_graph_and_k_hop() {
+        let mut g = Graph::<i32, f64>::new();
+        let n1 = g.add_node(1);
+        let n2 = g.add_node(2);
+        let n3 = g.add_node(3);
+        let n4 = g.add_node(4);
+
+        g.add_edge(n1, n2, 1.0);
+        g.add_edge(n2, n3, 1.0);
+        g.add_edge(n1, n3, 5.0);
+        g.add_edge(n3, n4, 3.0);
+
+        // n3 is reached through n2 at 2.0, not directly at 5.0
+        let reached = g
+            .weighted_k_hop_neighbors(n1, 4.0, |_, _, w| Some(*w))
+            .unwrap();
+        assert_eq!(reached, vec![(n1, 0.0), (n2, 1.0), (n3, 2.0)]);
+
+        let ego = g.weighted_ego_graph(n1, 4.0, |_, _, w| Some(*w)).unwrap();
+        assert_eq!(ego.node_count(), 3);
+        assert_eq!(ego.edge_count(), 3);
+
+        // Blocking n2 leaves only the direct edge, which is too long
+        let blocked = g
+            .weighted_k_hop_neighbors(n1, 4.0, |_, v, w| (v != n2).then_some(*w))
+            .unwrap();
+        assert_eq!(blocked, vec![(n1, 0.0)]);
+
+        assert!(g.weighted_ego_graph(n1, -1.0, |_, _, w| Some(*w)).is_err());
+        assert!(g.weighted_ego_graph(n1, 1.0, |_, _, _| Some(-1.0)).is_err());
+        g.remove_node(n4);
+        assert!(
+            g.weighted_k_hop_neighbors(n4, 1.0, |_, _, w| Some(*w))
+                .is_err()
+        );
+    }
+
     #[test]
     fn test_connected_component() {
         let mut g = Graph::<i32, f64>::new();
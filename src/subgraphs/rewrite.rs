@@ -0,0 +1,574 @@
+/*!
+# Graph Rewriting
+
+Rule-based rewriting for normalizing graphs in place, such as collapsing functional groups in
+a molecular graph or removing redundant edges from a dependency graph.
+
+A [`Pattern`] describes a small subgraph by tests on node attributes and edge weights, and
+[`find_matches`] lists its embeddings in a graph. A [`Rule`] pairs a pattern with a closure
+that edits the graph around a match, and a [`Rewriter`] applies its rules over and over until
+none matches or a step limit is reached.
+
+```rust
+use graphina::core::types::Graph;
+use graphina::subgraphs::rewrite::{Pattern, Rewriter, Rule};
+
+// Remove every hydrogen, and the bond that holds it.
+let mut molecule = Graph::<&str, u8>::new();
+let c = molecule.add_node("C");
+for _ in 0..4 {
+    let h = molecule.add_node("H");
+    molecule.add_edge(c, h, 1);
+}
+
+let mut hydrogen = Pattern::new();
+hydrogen.node(|&element: &&str| element == "H");
+let strip = Rule::new("strip_hydrogen", hydrogen, |graph, found| {
+    graph.remove_node(found.nodes[0]);
+    Ok(())
+});
+
+let report = Rewriter::new().rule(strip).apply(&mut molecule).unwrap();
+assert!(report.converged);
+assert_eq!(report.applied["strip_hydrogen"], 4);
+assert_eq!(molecule.node_count(), 1);
+```
+*/
+
+use std::collections::{HashMap, HashSet};
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, EdgeId, GraphConstructor, NodeId};
+use petgraph::graph::{EdgeIndex, NodeIndex};
+use petgraph::{Direction, EdgeType};
+
+type NodeTest<A> = Box<dyn Fn(&A) -> bool>;
+type EdgeTest<W> = Box<dyn Fn(&W) -> bool>;
+type Condition<A, W, Ty> = Box<dyn Fn(&BaseGraph<A, W, Ty>, &Match) -> bool>;
+type Replacement<A, W, Ty> = Box<dyn Fn(&mut BaseGraph<A, W, Ty>, &Match) -> Result<()>>;
+
+/// A subgraph to look for, built node by node and edge by edge.
+///
+/// Pattern nodes and edges are numbered in the order they are added, and a [`Match`] lists the
+/// graph nodes and edges they were matched to in the same order.
+pub struct Pattern<A, W> {
+    nodes: Vec<NodeTest<A>>,
+    edges: Vec<(usize, usize, EdgeTest<W>)>,
+}
+
+impl<A, W> Default for Pattern<A, W> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, W> Pattern<A, W> {
+    /// Creates an empty pattern.
+    pub fn new() -> Self {
+        Pattern {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Adds a node that matches graph nodes whose attribute passes `test`, and returns its
+    /// number.
+    pub fn node<F>(&mut self, test: F) -> usize
+    where
+        F: Fn(&A) -> bool + 'static,
+    {
+        self.nodes.push(Box::new(test));
+        self.nodes.len() - 1
+    }
+
+    /// Adds a node that matches any graph node, and returns its number.
+    pub fn any_node(&mut self) -> usize {
+        self.node(|_| true)
+    }
+
+    /// Adds an edge between pattern nodes `from` and `to` that matches graph edges whose
+    /// weight passes `test`, and returns its number.
+    ///
+    /// On a directed graph the edge must point from `from` to `to`; on an undirected one
+    /// either way round matches.
+    pub fn edge<F>(&mut self, from: usize, to: usize, test: F) -> usize
+    where
+        F: Fn(&W) -> bool + 'static,
+    {
+        self.edges.push((from, to, Box::new(test)));
+        self.edges.len() - 1
+    }
+
+    /// Adds an edge between pattern nodes `from` and `to` that matches any graph edge, and
+    /// returns its number.
+    pub fn any_edge(&mut self, from: usize, to: usize) -> usize {
+        self.edge(from, to, |_| true)
+    }
+
+    /// Returns the number of pattern nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns the number of pattern edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+}
+
+/// Where a pattern was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// `nodes[i]` is the graph node matched to pattern node `i`.
+    pub nodes: Vec<NodeId>,
+    /// `edges[j]` is the graph edge matched to pattern edge `j`.
+    pub edges: Vec<EdgeId>,
+}
+
+/// Lists every embedding of `pattern` in `graph`.
+///
+/// Distinct pattern nodes match distinct graph nodes, and distinct pattern edges distinct
+/// graph edges, but the graph may have edges the pattern does not mention. A pattern with
+/// symmetries is found once per symmetry: a pattern edge between two nodes that pass the same
+/// test matches each undirected graph edge twice, once each way round.
+///
+/// # Errors
+///
+/// Returns `InvalidArgument` if the pattern has no nodes or an edge names a node it does not
+/// have.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::subgraphs::rewrite::{Pattern, find_matches};
+///
+/// // Two-step chains `a -> b -> c`.
+/// let mut g = Digraph::<(), f64>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, 1.0);
+/// g.add_edge(b, c, 1.0);
+///
+/// let mut chain = Pattern::new();
+/// let [x, y, z] = [(); 3].map(|_| chain.any_node());
+/// chain.any_edge(x, y);
+/// chain.any_edge(y, z);
+/// let found = find_matches(&g, &chain).unwrap();
+/// assert_eq!(found.len(), 1);
+/// assert_eq!(found[0].nodes, vec![a, b, c]);
+/// ```
+pub fn find_matches<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    pattern: &Pattern<A, W>,
+) -> Result<Vec<Match>>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let mut found = Vec::new();
+    search(graph, pattern, &mut |m| {
+        found.push(m.clone());
+        false
+    })?;
+    Ok(found)
+}
+
+/// One step of the backtracking search.
+enum Step {
+    /// Place pattern node `node`, next to the already placed `anchor` if there is one: the
+    /// candidates are then the graph neighbors of the anchor's image in `direction`.
+    Node {
+        node: usize,
+        anchor: Option<(usize, Direction)>,
+    },
+    /// Place pattern edge `edge`, whose ends are both placed.
+    Edge(usize),
+}
+
+/// Orders the search so every node but the first of each pattern component is placed next to
+/// a placed neighbor, and every edge right after its second end.
+fn plan<A, W>(pattern: &Pattern<A, W>) -> Result<Vec<Step>> {
+    let n = pattern.nodes.len();
+    if n == 0 {
+        return Err(GraphinaError::invalid_argument(
+            "Rewrite pattern must have at least one node",
+        ));
+    }
+    if let Some(&(from, to, _)) = pattern.edges.iter().find(|(u, v, _)| *u >= n || *v >= n) {
+        return Err(GraphinaError::invalid_argument(format!(
+            "Rewrite pattern edge ({from}, {to}) names a node the pattern does not have"
+        )));
+    }
+    let mut placed = vec![false; n];
+    let mut steps = Vec::with_capacity(n + pattern.edges.len());
+    let mut edge_done = vec![false; pattern.edges.len()];
+    for start in 0..n {
+        if placed[start] {
+            continue;
+        }
+        let mut frontier = vec![(start, None)];
+        while let Some((node, anchor)) = frontier.pop() {
+            if placed[node] {
+                continue;
+            }
+            placed[node] = true;
+            steps.push(Step::Node { node, anchor });
+            for (j, &(from, to, _)) in pattern.edges.iter().enumerate() {
+                if !edge_done[j] && placed[from] && placed[to] {
+                    edge_done[j] = true;
+                    steps.push(Step::Edge(j));
+                }
+                if from == node && !placed[to] {
+                    frontier.push((to, Some((node, Direction::Outgoing))));
+                } else if to == node && !placed[from] {
+                    frontier.push((from, Some((node, Direction::Incoming))));
+                }
+            }
+        }
+    }
+    Ok(steps)
+}
+
+/// Runs the backtracking search, calling `visit` on each match until it returns `true`.
+fn search<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    pattern: &Pattern<A, W>,
+    visit: &mut dyn FnMut(&Match) -> bool,
+) -> Result<()>
+where
+    Ty: GraphConstructor<A, W> + EdgeType,
+{
+    let steps = plan(pattern)?;
+    let mut state = Search {
+        graph,
+        pattern,
+        steps: &steps,
+        current: Match {
+            // Placeholders, each overwritten before `visit` sees the match.
+            nodes: vec![NodeId::new(NodeIndex::end()); pattern.nodes.len()],
+            edges: vec![EdgeId::new(EdgeIndex::end()); pattern.edges.len()],
+        },
+        used_nodes: HashSet::new(),
+        used_edges: HashSet::new(),
+    };
+    state.extend(0, visit);
+    Ok(())
+}
+
+struct Search<'a, A, W, Ty: GraphConstructor<A, W> + EdgeType> {
+    graph: &'a BaseGraph<A, W, Ty>,
+    pattern: &'a Pattern<A, W>,
+    steps: &'a [Step],
+    current: Match,
+    used_nodes: HashSet<NodeId>,
+    used_edges: HashSet<EdgeId>,
+}
+
+impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Search<'_, A, W, Ty> {
+    /// Tries every candidate for step `at` and the steps after it; returns `true` once
+    /// `visit` asks to stop.
+    fn extend(&mut self, at: usize, visit: &mut dyn FnMut(&Match) -> bool) -> bool {
+        let Some(step) = self.steps.get(at) else {
+            return visit(&self.current);
+        };
+        match *step {
+            Step::Node { node, anchor } => {
+                let candidates: Vec<NodeId> = match anchor {
+                    Some((from, direction)) => {
+                        let mut seen = HashSet::new();
+                        self.graph
+                            .inner
+                            .neighbors_directed(self.current.nodes[from].0, direction)
+                            .map(NodeId::new)
+                            .filter(|v| seen.insert(*v))
+                            .collect()
+                    }
+                    None => self.graph.node_ids().collect(),
+                };
+                for v in candidates {
+                    if self.used_nodes.contains(&v) {
+                        continue;
+                    }
+                    let passes = self
+                        .graph
+                        .node_attr(v)
+                        .is_some_and(|attr| (self.pattern.nodes[node])(attr));
+                    if !passes {
+                        continue;
+                    }
+                    self.current.nodes[node] = v;
+                    self.used_nodes.insert(v);
+                    let stop = self.extend(at + 1, visit);
+                    self.used_nodes.remove(&v);
+                    if stop {
+                        return true;
+                    }
+                }
+                false
+            }
+            Step::Edge(edge) => {
+                let (from, to, ref test) = self.pattern.edges[edge];
+                let (u, v) = (self.current.nodes[from], self.current.nodes[to]);
+                let candidates: Vec<EdgeId> = self
+                    .graph
+                    .edges_between(u, v)
+                    .filter(|(_, w)| test(w))
+                    .map(|(e, _)| e)
+                    .collect();
+                for e in candidates {
+                    if !self.used_edges.insert(e) {
+                        continue;
+                    }
+                    self.current.edges[edge] = e;
+                    let stop = self.extend(at + 1, visit);
+                    self.used_edges.remove(&e);
+                    if stop {
+                        return true;
+                    }
+                }
+                false
+            }
+        }
+    }
+}
+
+/// A pattern and what to do where it is found.
+pub struct Rule<A, W, Ty: GraphConstructor<A, W> + EdgeType> {
+    name: String,
+    pattern: Pattern<A, W>,
+    condition: Option<Condition<A, W, Ty>>,
+    replace: Replacement<A, W, Ty>,
+}
+
+impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Rule<A, W, Ty> {
+    /// Creates a rule that calls `replace` with the graph and a match of `pattern`.
+    ///
+    /// `replace` edits the graph in any way it likes, usually by removing or relabeling the
+    /// matched nodes and edges and adding new ones around them.
+    pub fn new<F>(name: impl Into<String>, pattern: Pattern<A, W>, replace: F) -> Self
+    where
+        F: Fn(&mut BaseGraph<A, W, Ty>, &Match) -> Result<()> + 'static,
+    {
+        Rule {
+            name: name.into(),
+            pattern,
+            condition: None,
+            replace: Box::new(replace),
+        }
+    }
+
+    /// Applies the rule only to matches for which `condition` returns `true`, for checks
+    /// that node and edge tests cannot express, such as a node having no other edges.
+    pub fn when<F>(mut self, condition: F) -> Self
+    where
+        F: Fn(&BaseGraph<A, W, Ty>, &Match) -> bool + 'static,
+    {
+        self.condition = Some(Box::new(condition));
+        self
+    }
+
+    /// Returns the rule's name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the first match that satisfies the rule's condition, if any.
+    fn first_match(&self, graph: &BaseGraph<A, W, Ty>) -> Result<Option<Match>> {
+        let mut found = None;
+        search(graph, &self.pattern, &mut |m| {
+            if self.condition.as_ref().is_none_or(|c| c(graph, m)) {
+                found = Some(m.clone());
+                true
+            } else {
+                false
+            }
+        })?;
+        Ok(found)
+    }
+}
+
+/// What [`Rewriter::apply`] did.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RewriteReport {
+    /// Number of rewrites applied, over all rules.
+    pub steps: usize,
+    /// Number of rewrites applied by each rule, keyed by rule name; rules that never applied
+    /// have `0`.
+    pub applied: HashMap<String, usize>,
+    /// `true` if the rewriting stopped because no rule matched, `false` if it hit the step
+    /// limit.
+    pub converged: bool,
+}
+
+/// An ordered list of rules applied until none matches.
+pub struct Rewriter<A, W, Ty: GraphConstructor<A, W> + EdgeType> {
+    rules: Vec<Rule<A, W, Ty>>,
+    max_steps: usize,
+}
+
+impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Default for Rewriter<A, W, Ty> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A, W, Ty: GraphConstructor<A, W> + EdgeType> Rewriter<A, W, Ty> {
+    /// Creates a rewriter with no rules and a limit of 10,000 steps.
+    pub fn new() -> Self {
+        Rewriter {
+            rules: Vec::new(),
+            max_steps: 10_000,
+        }
+    }
+
+    /// Adds a rule, after the ones already added.
+    pub fn rule(mut self, rule: Rule<A, W, Ty>) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Sets the most rewrites [`apply`](Self::apply) performs.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Rewrites `graph` in place until no rule matches or the step limit is reached.
+    ///
+    /// Each step applies the first rule, in the order they were added, that has a match, at
+    /// the first match found, then starts again from the first rule. A rule whose rewrite
+    /// leaves its own pattern in place matches again at every step, so it should remove or
+    /// change what it matched, or carry a [`Rule::when`] condition that rejects it afterwards;
+    /// the step limit keeps such a rule from running forever.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidArgument` for an invalid pattern, and passes on any error from a
+    /// replacement closure. The rewrites made before the error stay in the graph.
+    pub fn apply(&self, graph: &mut BaseGraph<A, W, Ty>) -> Result<RewriteReport> {
+        let mut report = RewriteReport {
+            steps: 0,
+            applied: self.rules.iter().map(|r| (r.name.clone(), 0)).collect(),
+            converged: false,
+        };
+        while report.steps < self.max_steps {
+            let mut applied = false;
+            for rule in &self.rules {
+                let Some(found) = rule.first_match(graph)? else {
+                    continue;
+                };
+                (rule.replace)(graph, &found)?;
+                *report.applied.entry(rule.name.clone()).or_insert(0) += 1;
+                report.steps += 1;
+                applied = true;
+                break;
+            }
+            if !applied {
+                report.converged = true;
+                return Ok(report);
+            }
+        }
+        // Out of steps, but the graph may already be in normal form.
+        for rule in &self.rules {
+            if rule.first_match(graph)?.is_some() {
+                return Ok(report);
+            }
+        }
+        report.converged = true;
+        Ok(report)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::{Digraph, Graph};
+
+    #[test]
+    fn test_find_matches_respects_tests_direction_and_parallel_edges() {
+        // a -> b twice, b -> c, and c -> a.
+        let mut g = Digraph::<char, u8>::new();
+        let [a, b, c] = ['a', 'b', 'c'].map(|name| g.add_node(name));
+        let ab1 = g.add_edge(a, b, 1);
+        let ab2 = g.add_edge(a, b, 2);
+        g.add_edge(b, c, 1);
+        g.add_edge(c, a, 1);
+
+        // Two distinct parallel edges: only a -> b has them, in both orders.
+        let mut double = Pattern::new();
+        let [x, y] = [(); 2].map(|_| double.any_node());
+        double.any_edge(x, y);
+        double.any_edge(x, y);
+        let found = find_matches(&g, &double).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|m| m.nodes == vec![a, b]));
+        assert_eq!(found[0].edges, vec![ab1, ab2]);
+
+        // The directed triangle would be found once per rotation, but only one puts the
+        // weight-2 edge first.
+        let mut cycle = Pattern::new();
+        let [x, y, z] = [(); 3].map(|_| cycle.any_node());
+        let xy = cycle.edge(x, y, |&w| w == 2);
+        cycle.any_edge(y, z);
+        cycle.any_edge(z, x);
+        let found = find_matches(&g, &cycle).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].edges[xy], ab2);
+
+        let mut from_a = Pattern::new();
+        let x = from_a.node(|&n| n == 'a');
+        let y = from_a.any_node();
+        from_a.any_edge(y, x);
+        assert_eq!(find_matches(&g, &from_a).unwrap()[0].nodes, vec![a, c]);
+
+        let mut broken = Pattern::new();
+        broken.any_edge(0, 1);
+        assert!(find_matches(&g, &broken).is_err());
+        assert!(find_matches(&g, &Pattern::new()).is_err());
+    }
+
+    #[test]
+    fn test_rewriter_reaches_normal_form_or_step_limit() {
+        // A path of five nodes collapsed to one edge by contracting degree-two nodes.
+        let mut g = Graph::<u32, f64>::new();
+        let n: Vec<_> = (0..5).map(|i| g.add_node(i)).collect();
+        for pair in n.windows(2) {
+            g.add_edge(pair[0], pair[1], 1.0);
+        }
+        let mut chain = Pattern::new();
+        let [x, y, z] = [(); 3].map(|_| chain.any_node());
+        let xy = chain.any_edge(x, y);
+        let yz = chain.any_edge(y, z);
+        let smooth = Rule::new("smooth", chain, move |g: &mut Graph<u32, f64>, m| {
+            let w = g.edge_weight(m.edges[xy]).copied().unwrap_or(0.0)
+                + g.edge_weight(m.edges[yz]).copied().unwrap_or(0.0);
+            g.remove_node(m.nodes[y]);
+            g.add_edge(m.nodes[x], m.nodes[z], w);
+            Ok(())
+        })
+        .when(move |g, m| g.degree(m.nodes[y]) == Some(2));
+        let rewriter = Rewriter::new().rule(smooth);
+
+        let mut limited = g.clone();
+        let report = Rewriter::new()
+            .rule(Rule::new("noop", Pattern::new(), |_, _| Ok(())))
+            .apply(&mut limited);
+        assert!(report.is_err());
+        let report = rewriter.apply(&mut g).unwrap();
+        assert!(report.converged);
+        assert_eq!(report.steps, 3);
+        assert_eq!(g.node_count(), 2);
+        assert_eq!(g.edge_weight(g.edge_ids().next().unwrap()), Some(&4.0));
+
+        // A rule that keeps its own match runs until the limit.
+        let mut any = Pattern::new();
+        any.any_node();
+        let stuck = Rule::new("stuck", any, |_: &mut Graph<u32, f64>, _| Ok(()));
+        let report = Rewriter::new()
+            .rule(stuck)
+            .with_max_steps(5)
+            .apply(&mut limited)
+            .unwrap();
+        assert!(!report.converged);
+        assert_eq!(report.applied["stuck"], 5);
+    }
+}
@@ -9,6 +9,8 @@ The algorithms include:
 
 - **Depth-First Search (DFS):**
   Recursively traverses a graph depth-first starting from a specified node.
+  `bfs_on` and `dfs_on` do the same for anything implementing `GraphTraversal`, including
+  the reversed and undirected views in `core::views`.
 
 - **Iterative Deepening Depth-First Search (IDDFS):**
   Combines the space efficiency of DFS with the optimality of BFS by iteratively deepening the search.
@@ -22,6 +24,7 @@ if no valid path exists.
 */
 
 use crate::core::error::{GraphinaError, Result};
+use crate::core::traits::GraphTraversal;
 use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap, NodeSet};
 use petgraph::visit::NodeIndexable;
 use std::collections::VecDeque;
@@ -160,6 +163,103 @@ fn dfs_util<A, W, Ty>(
     }
 }
 
+/// Returns an upper bound on node indices of a [`GraphTraversal`], for visited flags.
+fn traversal_bound<A, W, G: GraphTraversal<A, W>>(graph: &G) -> usize {
+    graph.node_ids().map(|n| n.index() + 1).max().unwrap_or(0)
+}
+
+/// Performs a breadth-first search from `start` over any [`GraphTraversal`].
+///
+/// Visits nodes in the same order as [`bfs`] when given a `BaseGraph`, and also accepts the
+/// views in [`crate::core::views`], so a search against edge direction needs no reversed copy.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::traversal::bfs_on;
+///
+/// let mut g = Digraph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, ());
+/// g.add_edge(b, c, ());
+///
+/// assert_eq!(bfs_on(&g, a), vec![a, b, c]);
+/// assert_eq!(bfs_on(&g.reversed(), c), vec![c, b, a]);
+/// ```
+pub fn bfs_on<A, W, G>(graph: &G, start: NodeId) -> Vec<NodeId>
+where
+    G: GraphTraversal<A, W>,
+{
+    if !graph.contains_node(start) {
+        return Vec::new();
+    }
+    let mut visited = vec![false; traversal_bound(graph)];
+    let mut order = Vec::new();
+    let mut queue = VecDeque::new();
+
+    visited[start.index()] = true;
+    queue.push_back(start);
+
+    while let Some(node) = queue.pop_front() {
+        order.push(node);
+        for neighbor in graph.neighbors(node) {
+            let ni = neighbor.index();
+            if !visited[ni] {
+                visited[ni] = true;
+                queue.push_back(neighbor);
+            }
+        }
+    }
+    order
+}
+
+/// Performs a depth-first search from `start` over any [`GraphTraversal`].
+///
+/// Visits nodes in the same order as [`dfs`] when given a `BaseGraph`.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Digraph;
+/// use graphina::traversal::dfs_on;
+///
+/// let mut g = Digraph::<(), ()>::new();
+/// let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+/// g.add_edge(a, b, ());
+/// g.add_edge(c, b, ());
+///
+/// // c is unreachable from a along edge directions, but not when they are ignored.
+/// assert_eq!(dfs_on(&g, a), vec![a, b]);
+/// assert_eq!(dfs_on(&g.as_undirected(), a), vec![a, b, c]);
+/// ```
+pub fn dfs_on<A, W, G>(graph: &G, start: NodeId) -> Vec<NodeId>
+where
+    G: GraphTraversal<A, W>,
+{
+    if !graph.contains_node(start) {
+        return Vec::new();
+    }
+    let mut visited = vec![false; traversal_bound(graph)];
+    let mut order = Vec::new();
+    dfs_on_util(graph, start, &mut visited, &mut order);
+    order
+}
+
+/// Recursive helper for [`dfs_on`], mirroring `dfs_util`.
+fn dfs_on_util<A, W, G>(graph: &G, node: NodeId, visited: &mut [bool], order: &mut Vec<NodeId>)
+where
+    G: GraphTraversal<A, W>,
+{
+    visited[node.index()] = true;
+    order.push(node);
+    for neighbor in graph.neighbors(node) {
+        if !visited[neighbor.index()] {
+            dfs_on_util(graph, neighbor, visited, order);
+        }
+    }
+}
+
 /// Performs iterative deepening depth-first search (IDDFS) to find a path from `start` to `target`.
 ///
 /// The search is executed with increasing depth limits until `max_depth` is reached.
@@ -1,9 +1,14 @@
 //! Graph traversal algorithms module.
 //!
-//! Graph traversal algorithms: BFS, DFS, IDDFS, and bidirectional search.
+//! Graph traversal algorithms: BFS, DFS, IDDFS, bidirectional search, simple cycle enumeration, and
+//! a reachability index.
 //! All algorithms depend only on the core module for basic graph operations.
 
 pub mod algorithms;
+pub mod cycles;
+pub mod reachability;
 
 // Re-export commonly used functions
-pub use algorithms::{bfs, bidis, dfs, iddfs, try_bidirectional_search, try_iddfs};
+pub use algorithms::{bfs, bfs_on, bidis, dfs, dfs_on, iddfs, try_bidirectional_search, try_iddfs};
+pub use cycles::{simple_cycles, simple_cycles_up_to_length};
+pub use reachability::ReachabilityIndex;
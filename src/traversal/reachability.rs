@@ -0,0 +1,199 @@
+/*!
+# Reachability Index
+
+Answers "is there a path from `u` to `v`?" repeatedly without searching the graph each time.
+[`ReachabilityIndex`] collapses each strongly connected component to one node, which leaves a
+DAG, and covers that DAG with chains: paths along its edges, with every component on exactly
+one chain. Every component then stores, per chain, the earliest position on that chain it can
+reach. Reaching a chain position means reaching all the later ones, so a query is one lookup.
+
+The index is built once and does not follow later changes to the graph.
+*/
+
+use crate::core::types::{Digraph, NodeId};
+
+/// Marks a chain that a component cannot reach.
+const UNREACHABLE: u32 = u32::MAX;
+
+/// Precomputed reachability for a directed graph, answering [`reaches`](Self::reaches) in
+/// constant time.
+///
+/// Built in O(V + E·C) time and stored in O(V + K·C) space, for `K` strongly connected
+/// components and `C` chains. The chain cover is found greedily, so `C` is small when the
+/// condensation is narrow and long, as dependency graphs usually are, and can approach `K`
+/// for a wide, shallow one; [`chain_count`](Self::chain_count) reports it.
+#[derive(Debug, Clone)]
+pub struct ReachabilityIndex {
+    /// Component of each node, by node index; `UNREACHABLE` for indices with no node.
+    component: Vec<u32>,
+    /// Chain of each component.
+    chain: Vec<u32>,
+    /// Position of each component on its chain.
+    position: Vec<u32>,
+    /// Row `c` holds, per chain, the earliest position component `c` reaches.
+    earliest: Vec<u32>,
+    chains: usize,
+}
+
+impl ReachabilityIndex {
+    /// Builds the index for `graph`.
+    ///
+    /// The graph may contain cycles; nodes on a common cycle reach each other.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use graphina::core::types::Digraph;
+    /// use graphina::traversal::ReachabilityIndex;
+    ///
+    /// // a -> b -> c, with b and d on a cycle.
+    /// let mut g = Digraph::<&str, ()>::new();
+    /// let [a, b, c, d] = ["a", "b", "c", "d"].map(|name| g.add_node(name));
+    /// g.add_edge(a, b, ());
+    /// g.add_edge(b, c, ());
+    /// g.add_edge(b, d, ());
+    /// g.add_edge(d, b, ());
+    ///
+    /// let index = ReachabilityIndex::new(&g);
+    /// assert!(index.reaches(a, c));
+    /// assert!(!index.reaches(d, a));
+    /// assert!(index.reaches(d, c));
+    /// assert!(index.reaches(c, c));
+    /// ```
+    pub fn new<A, W>(graph: &Digraph<A, W>) -> Self {
+        // Tarjan lists components in reverse topological order; number them forwards.
+        let sccs = petgraph::algo::tarjan_scc(graph.as_petgraph());
+        let k = sccs.len();
+        let bound = graph.node_ids().map(|v| v.index() + 1).max().unwrap_or(0);
+        let mut component = vec![UNREACHABLE; bound];
+        for (i, members) in sccs.iter().enumerate() {
+            for node in members {
+                component[node.index()] = (k - 1 - i) as u32;
+            }
+        }
+
+        let mut successors: Vec<Vec<u32>> = vec![Vec::new(); k];
+        for (u, v, _) in graph.edges() {
+            let (cu, cv) = (component[u.index()], component[v.index()]);
+            if cu != cv {
+                successors[cu as usize].push(cv);
+            }
+        }
+        for list in &mut successors {
+            list.sort_unstable();
+            list.dedup();
+        }
+
+        // Greedy chain cover: start a chain at the first unassigned component in topological
+        // order and extend it through the earliest unassigned successor.
+        let mut chain = vec![UNREACHABLE; k];
+        let mut position = vec![0; k];
+        let mut chains = 0;
+        for start in 0..k {
+            if chain[start] != UNREACHABLE {
+                continue;
+            }
+            let mut current = start;
+            let mut length = 0;
+            loop {
+                chain[current] = chains as u32;
+                position[current] = length;
+                length += 1;
+                match successors[current]
+                    .iter()
+                    .find(|&&s| chain[s as usize] == UNREACHABLE)
+                {
+                    Some(&next) => current = next as usize,
+                    None => break,
+                }
+            }
+            chains += 1;
+        }
+
+        // Successors come later in topological order, so fill the rows from the back.
+        let mut earliest = vec![UNREACHABLE; k * chains];
+        for c in (0..k).rev() {
+            let (head, tail) = earliest.split_at_mut((c + 1) * chains);
+            let row = &mut head[c * chains..];
+            row[chain[c] as usize] = position[c];
+            for &s in &successors[c] {
+                let offset = (s as usize - c - 1) * chains;
+                for (mine, &theirs) in row.iter_mut().zip(&tail[offset..offset + chains]) {
+                    *mine = (*mine).min(theirs);
+                }
+            }
+        }
+
+        ReachabilityIndex {
+            component,
+            chain,
+            position,
+            earliest,
+            chains,
+        }
+    }
+
+    /// Returns whether a directed path leads from `u` to `v`.
+    ///
+    /// Every node reaches itself. Nodes that were not in the graph when the index was built
+    /// reach nothing and are reached by nothing.
+    pub fn reaches(&self, u: NodeId, v: NodeId) -> bool {
+        let (Some(&cu), Some(&cv)) = (self.component.get(u.index()), self.component.get(v.index()))
+        else {
+            return false;
+        };
+        if cu == UNREACHABLE || cv == UNREACHABLE {
+            return false;
+        }
+        if cu == cv {
+            return true;
+        }
+        if cu > cv {
+            return false;
+        }
+        let (cu, cv) = (cu as usize, cv as usize);
+        self.earliest[cu * self.chains + self.chain[cv] as usize] <= self.position[cv]
+    }
+
+    /// Returns the number of chains in the cover, which sets the index's size and build time.
+    pub fn chain_count(&self) -> usize {
+        self.chains
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traversal::bfs;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_reachability_matches_bfs() {
+        let mut rng = StdRng::seed_from_u64(11);
+        for round in 0..30 {
+            let mut g = Digraph::<(), ()>::new();
+            let nodes: Vec<_> = (0..25).map(|_| g.add_node(())).collect();
+            for _ in 0..(round + 10) {
+                let (u, v) = (rng.random_range(0..25), rng.random_range(0..25));
+                g.add_edge(nodes[u], nodes[v], ());
+            }
+            // Leave a hole in the node indices.
+            g.remove_node(nodes[round % 25]);
+
+            let index = ReachabilityIndex::new(&g);
+            assert!(index.chain_count() <= g.node_count());
+            for u in g.node_ids() {
+                let reached = bfs(&g, u);
+                for v in g.node_ids() {
+                    assert_eq!(index.reaches(u, v), reached.contains(&v), "{:?} {:?}", u, v);
+                }
+                assert!(!index.reaches(u, nodes[round % 25]));
+            }
+        }
+        assert_eq!(
+            ReachabilityIndex::new(&Digraph::<(), ()>::new()).chain_count(),
+            0
+        );
+    }
+}
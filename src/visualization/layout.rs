@@ -0,0 +1,478 @@
+/*!
+Node layouts: where each node is drawn.
+
+A [`Layout`] maps every node to planar `(x, y)` coordinates in arbitrary units; the renderers
+scale it to fit their canvas. [`compute_layout`] produces one with a [`LayoutAlgorithm`], or
+takes the coordinates a node already carries, so a road network or a sensor grid is drawn
+where it really is.
+*/
+
+use std::f64::consts::TAU;
+use std::fmt;
+
+use nalgebra::DMatrix;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap};
+
+use super::layered::layered;
+
+/// Planar coordinates for every node of a graph.
+pub type Layout = NodeMap<(f64, f64)>;
+
+/// Reads a node's `(x, y)` position from its attribute.
+pub type PositionFn<A> = Box<dyn Fn(&A) -> (f64, f64)>;
+
+/// How [`compute_layout`] places the nodes.
+pub enum LayoutAlgorithm<A> {
+    /// Evenly spaced on the unit circle, in node order.
+    Circular,
+    /// Fruchterman-Reingold force-directed placement: edges pull their ends together and
+    /// every pair of nodes pushes apart. Starts from random positions drawn with `seed`, so
+    /// the same seed gives the same layout.
+    ForceDirected {
+        /// Number of simulation steps; 50 to 500 is typical.
+        iterations: usize,
+        /// Seed for the starting positions.
+        seed: u64,
+    },
+    /// Sugiyama-style layers for dependency graphs: every edge points down from one layer
+    /// to a lower one, except the fewest needed to break cycles, and crossings are reduced
+    /// by barycenter ordering. Undirected graphs are layered by distance along a DFS tree.
+    Layered {
+        /// Number of crossing-reduction sweeps, alternating down and up; 8 to 24 is
+        /// typical, and 0 keeps the nodes in discovery order.
+        sweeps: usize,
+    },
+    /// Kamada-Kawai placement by stress majorization: the drawn distance between every pair
+    /// of nodes approaches their hop distance, with close pairs weighted most. Starts from a
+    /// circle, so it needs no seed.
+    KamadaKawai {
+        /// Maximum number of sweeps over the nodes; 100 to 300 is typical. The sweeps stop
+        /// early once the stress stops falling.
+        iterations: usize,
+    },
+    /// Spectral placement: each node's coordinates are its entries in the Laplacian
+    /// eigenvectors of the second- and third-smallest eigenvalues. It shows the large-scale
+    /// shape of meshes and other regular graphs, but draws each connected component of a
+    /// disconnected graph as a single point.
+    Spectral,
+    /// Reads each node's position from its attribute, for graphs with real coordinates
+    /// such as longitude and latitude.
+    FromAttributes(PositionFn<A>),
+}
+
+impl<A> fmt::Debug for LayoutAlgorithm<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Circular => f.write_str("Circular"),
+            Self::ForceDirected { iterations, seed } => f
+                .debug_struct("ForceDirected")
+                .field("iterations", iterations)
+                .field("seed", seed)
+                .finish(),
+            Self::Layered { sweeps } => f.debug_struct("Layered").field("sweeps", sweeps).finish(),
+            Self::KamadaKawai { iterations } => f
+                .debug_struct("KamadaKawai")
+                .field("iterations", iterations)
+                .finish(),
+            Self::Spectral => f.write_str("Spectral"),
+            Self::FromAttributes(_) => f.write_str("FromAttributes(..)"),
+        }
+    }
+}
+
+impl<A> LayoutAlgorithm<A> {
+    /// Reads positions from node attributes with `position`.
+    ///
+    /// ```rust
+    /// use graphina::visualization::LayoutAlgorithm;
+    ///
+    /// let by_coordinates = LayoutAlgorithm::from_attributes(|&(lon, lat): &(f64, f64)| (lon, lat));
+    /// ```
+    pub fn from_attributes(position: impl Fn(&A) -> (f64, f64) + 'static) -> Self {
+        Self::FromAttributes(Box::new(position))
+    }
+}
+
+/// Computes a layout of `graph` with `algorithm`.
+///
+/// # Errors
+///
+/// `InvalidArgument` if [`LayoutAlgorithm::FromAttributes`] gives a node a non-finite
+/// coordinate.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::visualization::{LayoutAlgorithm, compute_layout};
+///
+/// let mut g = Graph::<(f64, f64), f64>::new();
+/// let berlin = g.add_node((13.40, 52.52));
+/// let paris = g.add_node((2.35, 48.86));
+/// g.add_edge(berlin, paris, 878.0);
+///
+/// let layout = compute_layout(&g, &LayoutAlgorithm::from_attributes(|p| *p)).unwrap();
+/// assert_eq!(layout[&paris], (2.35, 48.86));
+/// ```
+pub fn compute_layout<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    algorithm: &LayoutAlgorithm<A>,
+) -> Result<Layout>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    match algorithm {
+        LayoutAlgorithm::Circular => Ok(circular(graph)),
+        LayoutAlgorithm::ForceDirected { iterations, seed } => {
+            Ok(force_directed(graph, *iterations, *seed))
+        }
+        LayoutAlgorithm::Layered { sweeps } => Ok(layered(graph, *sweeps)),
+        LayoutAlgorithm::KamadaKawai { iterations } => Ok(kamada_kawai(graph, *iterations)),
+        LayoutAlgorithm::Spectral => Ok(spectral(graph)),
+        LayoutAlgorithm::FromAttributes(position) => {
+            let mut layout = Layout::default();
+            for (node, attr) in graph.nodes() {
+                let (x, y) = position(attr);
+                if !x.is_finite() || !y.is_finite() {
+                    return Err(GraphinaError::invalid_argument(format!(
+                        "Node {} has a non-finite position ({x}, {y})",
+                        node.index()
+                    )));
+                }
+                layout.insert(node, (x, y));
+            }
+            Ok(layout)
+        }
+    }
+}
+
+fn circular<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Layout
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let n = graph.node_count();
+    if n == 1 {
+        return graph.node_ids().map(|node| (node, (0.0, 0.0))).collect();
+    }
+    graph
+        .node_ids()
+        .enumerate()
+        .map(|(i, node)| {
+            let angle = TAU * i as f64 / n as f64;
+            (node, (angle.cos(), angle.sin()))
+        })
+        .collect()
+}
+
+/// Fruchterman and Reingold (1991) in the unit square, with a temperature that cools
+/// linearly from a tenth of the square's side to zero.
+fn force_directed<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, iterations: usize, seed: u64) -> Layout
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let n = nodes.len();
+    let position: NodeMap<usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let edges: Vec<(usize, usize)> = graph
+        .edges()
+        .map(|(u, v, _)| (position[&u], position[&v]))
+        .filter(|(i, j)| i != j)
+        .collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut pos: Vec<(f64, f64)> = (0..n)
+        .map(|_| (rng.random::<f64>(), rng.random::<f64>()))
+        .collect();
+    if n < 2 {
+        return nodes.into_iter().zip(pos).collect();
+    }
+
+    let k = (1.0 / n as f64).sqrt();
+    let mut displacement = vec![(0.0, 0.0); n];
+    for step in 0..iterations {
+        displacement.fill((0.0, 0.0));
+        for i in 0..n {
+            for j in i + 1..n {
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let d = dx.hypot(dy).max(1e-9);
+                let push = k * k / d;
+                let (fx, fy) = (dx / d * push, dy / d * push);
+                displacement[i].0 += fx;
+                displacement[i].1 += fy;
+                displacement[j].0 -= fx;
+                displacement[j].1 -= fy;
+            }
+        }
+        for &(i, j) in &edges {
+            let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+            let d = dx.hypot(dy).max(1e-9);
+            let pull = d * d / k;
+            let (fx, fy) = (dx / d * pull, dy / d * pull);
+            displacement[i].0 -= fx;
+            displacement[i].1 -= fy;
+            displacement[j].0 += fx;
+            displacement[j].1 += fy;
+        }
+        let temperature = 0.1 * (1.0 - step as f64 / iterations as f64);
+        for (p, &(dx, dy)) in pos.iter_mut().zip(&displacement) {
+            let length = dx.hypot(dy);
+            if length > 0.0 {
+                let moved = length.min(temperature);
+                p.0 += dx / length * moved;
+                p.1 += dy / length * moved;
+            }
+        }
+    }
+    nodes.into_iter().zip(pos).collect()
+}
+
+/// The nodes in order, and each node's distinct neighbours by position, ignoring edge
+/// direction and self-loops.
+fn undirected_adjacency<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> (Vec<NodeId>, Vec<Vec<usize>>)
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let nodes: Vec<NodeId> = graph.node_ids().collect();
+    let position: NodeMap<usize> = nodes.iter().enumerate().map(|(i, &v)| (v, i)).collect();
+    let mut adjacent = vec![Vec::new(); nodes.len()];
+    for (u, v, _) in graph.edges() {
+        let (i, j) = (position[&u], position[&v]);
+        if i != j {
+            adjacent[i].push(j);
+            adjacent[j].push(i);
+        }
+    }
+    for list in &mut adjacent {
+        list.sort_unstable();
+        list.dedup();
+    }
+    (nodes, adjacent)
+}
+
+/// Kamada and Kawai (1989), minimized by stress majorization (Gansner, Koren, and North,
+/// 2004) with each node moved in turn, which never raises the stress. Hop distances are the
+/// ideal lengths; pairs in different components are kept one hop further apart than the
+/// farthest connected pair.
+fn kamada_kawai<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, iterations: usize) -> Layout
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let (nodes, adjacent) = undirected_adjacency(graph);
+    let n = nodes.len();
+    if n < 2 {
+        return nodes.into_iter().map(|node| (node, (0.0, 0.0))).collect();
+    }
+    let mut hops = vec![vec![usize::MAX; n]; n];
+    for (source, row) in hops.iter_mut().enumerate() {
+        row[source] = 0;
+        let mut queue = std::collections::VecDeque::from([source]);
+        while let Some(u) = queue.pop_front() {
+            for &v in &adjacent[u] {
+                if row[v] == usize::MAX {
+                    row[v] = row[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+    }
+    let far = hops
+        .iter()
+        .flatten()
+        .filter(|&&h| h != usize::MAX)
+        .max()
+        .map_or(1, |&h| h + 1);
+    let ideal: Vec<Vec<f64>> = hops
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|&h| if h == usize::MAX { far } else { h } as f64)
+                .collect()
+        })
+        .collect();
+    let stress = |pos: &[(f64, f64)]| {
+        let mut total = 0.0;
+        for i in 0..n {
+            for j in i + 1..n {
+                let d = ideal[i][j];
+                let gap = (pos[i].0 - pos[j].0).hypot(pos[i].1 - pos[j].1) - d;
+                total += gap * gap / (d * d);
+            }
+        }
+        total
+    };
+
+    // A circle whose diameter is the largest ideal distance.
+    let radius = far as f64 / 2.0;
+    let mut pos: Vec<(f64, f64)> = (0..n)
+        .map(|i| {
+            let angle = TAU * i as f64 / n as f64;
+            (radius * angle.cos(), radius * angle.sin())
+        })
+        .collect();
+    let mut previous = stress(&pos);
+    for _ in 0..iterations {
+        for i in 0..n {
+            let (mut x, mut y, mut total) = (0.0, 0.0, 0.0);
+            for j in (0..n).filter(|&j| j != i) {
+                let d = ideal[i][j];
+                let weight = 1.0 / (d * d);
+                let (dx, dy) = (pos[i].0 - pos[j].0, pos[i].1 - pos[j].1);
+                let length = dx.hypot(dy);
+                // Coincident nodes give no direction to push along.
+                let push = if length > 0.0 { d / length } else { 0.0 };
+                x += weight * (pos[j].0 + push * dx);
+                y += weight * (pos[j].1 + push * dy);
+                total += weight;
+            }
+            pos[i] = (x / total, y / total);
+        }
+        let current = stress(&pos);
+        if previous - current <= 1e-6 * previous {
+            break;
+        }
+        previous = current;
+    }
+    nodes.into_iter().zip(pos).collect()
+}
+
+/// The unweighted Laplacian's eigenvectors for the second- and third-smallest eigenvalues,
+/// each with its largest entry made positive so the drawing does not flip between runs. A
+/// graph with two nodes has only one such axis and is drawn on `y = 0`.
+fn spectral<A, W, Ty>(graph: &BaseGraph<A, W, Ty>) -> Layout
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let (nodes, adjacent) = undirected_adjacency(graph);
+    let n = nodes.len();
+    if n < 2 {
+        // No nontrivial eigenvector, and nalgebra cannot decompose an empty matrix.
+        return nodes.into_iter().map(|node| (node, (0.0, 0.0))).collect();
+    }
+    let mut laplacian = DMatrix::<f64>::zeros(n, n);
+    for (i, list) in adjacent.iter().enumerate() {
+        laplacian[(i, i)] = list.len() as f64;
+        for &j in list {
+            laplacian[(i, j)] = -1.0;
+        }
+    }
+    let eigen = laplacian.symmetric_eigen();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&a, &b| eigen.eigenvalues[a].total_cmp(&eigen.eigenvalues[b]));
+    let axis = |rank: usize| -> Vec<f64> {
+        let Some(&column) = order.get(rank) else {
+            return vec![0.0; n];
+        };
+        let vector: Vec<f64> = eigen.eigenvectors.column(column).iter().copied().collect();
+        let largest =
+            vector.iter().copied().fold(
+                0.0_f64,
+                |best, v| if v.abs() > best.abs() { v } else { best },
+            );
+        let sign = if largest < 0.0 { -1.0 } else { 1.0 };
+        vector.into_iter().map(|v| sign * v).collect()
+    };
+    let (xs, ys) = (axis(1), axis(2));
+    nodes
+        .into_iter()
+        .enumerate()
+        .map(|(i, node)| (node, (xs[i], ys[i])))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+
+    #[test]
+    fn test_layouts_place_every_node() {
+        let mut g = Graph::<(f64, f64), f64>::new();
+        let n: Vec<_> = (0..6)
+            .map(|i| g.add_node((i as f64, -(i as f64))))
+            .collect();
+        for i in 0..5 {
+            g.add_edge(n[i], n[i + 1], 1.0);
+        }
+        g.remove_node(n[5]);
+
+        let circle = compute_layout(&g, &LayoutAlgorithm::Circular).unwrap();
+        assert_eq!(circle.len(), 5);
+        assert!(
+            circle
+                .values()
+                .all(|&(x, y)| (x.hypot(y) - 1.0).abs() < 1e-12)
+        );
+
+        let force = LayoutAlgorithm::ForceDirected {
+            iterations: 100,
+            seed: 7,
+        };
+        let first = compute_layout(&g, &force).unwrap();
+        assert_eq!(first, compute_layout(&g, &force).unwrap());
+        // Neighbours along the path end up closer than the path's two ends.
+        let gap = |a: NodeId, b: NodeId| {
+            let (p, q) = (first[&a], first[&b]);
+            (p.0 - q.0).hypot(p.1 - q.1)
+        };
+        assert!(gap(n[0], n[1]) < gap(n[0], n[4]));
+
+        let attrs = compute_layout(&g, &LayoutAlgorithm::from_attributes(|p| *p)).unwrap();
+        assert_eq!(attrs[&n[3]], (3.0, -3.0));
+        let broken = LayoutAlgorithm::from_attributes(|_: &(f64, f64)| (f64::NAN, 0.0));
+        assert!(compute_layout(&g, &broken).is_err());
+    }
+
+    #[test]
+    fn test_distance_and_spectral_layouts() {
+        let gap = |layout: &Layout, a: NodeId, b: NodeId| {
+            let (p, q) = (layout[&a], layout[&b]);
+            (p.0 - q.0).hypot(p.1 - q.1)
+        };
+        // Kamada-Kawai straightens a path, so drawn distances match hop counts.
+        let mut path = Graph::<(), f64>::new();
+        let p: Vec<_> = (0..5).map(|_| path.add_node(())).collect();
+        for i in 0..4 {
+            path.add_edge(p[i], p[i + 1], 1.0);
+        }
+        let kk = compute_layout(&path, &LayoutAlgorithm::KamadaKawai { iterations: 300 }).unwrap();
+        assert_eq!(kk.len(), 5);
+        assert!((gap(&kk, p[0], p[1]) - 1.0).abs() < 0.1);
+        assert!((gap(&kk, p[0], p[4]) - 4.0).abs() < 0.2);
+        assert_eq!(
+            kk,
+            compute_layout(&path, &LayoutAlgorithm::KamadaKawai { iterations: 300 }).unwrap()
+        );
+
+        // The second and third Laplacian eigenvectors of a cycle trace a circle in order.
+        let mut cycle = Graph::<(), f64>::new();
+        let c: Vec<_> = (0..8).map(|_| cycle.add_node(())).collect();
+        for i in 0..8 {
+            cycle.add_edge(c[i], c[(i + 1) % 8], 1.0);
+        }
+        let spectral = compute_layout(&cycle, &LayoutAlgorithm::Spectral).unwrap();
+        let radius = gap(&spectral, c[0], c[4]) / 2.0;
+        for i in 0..8 {
+            let (x, y) = spectral[&c[i]];
+            assert!((x.hypot(y) - radius).abs() < 1e-9);
+            assert!(
+                (gap(&spectral, c[i], c[(i + 1) % 8]) - gap(&spectral, c[0], c[1])).abs() < 1e-9
+            );
+        }
+
+        let mut pair = Graph::<(), f64>::new();
+        let (a, b) = (pair.add_node(()), pair.add_node(()));
+        pair.add_edge(a, b, 1.0);
+        let line = compute_layout(&pair, &LayoutAlgorithm::Spectral).unwrap();
+        assert_eq!((line[&a].1, line[&b].1), (0.0, 0.0));
+        assert!(line[&a].0 != line[&b].0);
+        let empty = Graph::<(), f64>::new();
+        assert!(
+            compute_layout(&empty, &LayoutAlgorithm::Spectral)
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,19 @@
+//! Graph visualization module.
+//!
+//! Node layouts, static SVG and HTML renderings of laid-out graphs, character-grid drawings
+//! for the terminal, adjacency-matrix heatmaps, and step-by-step algorithm traces. All of it
+//! depends only on the core module for basic graph operations.
+
+pub mod heatmap;
+mod layered;
+pub mod layout;
+pub mod render;
+pub mod terminal;
+pub mod trace;
+
+// Re-export all public items
+pub use heatmap::{MatrixOrder, adjacency_heatmap, save_adjacency_heatmap};
+pub use layout::{Layout, LayoutAlgorithm, PositionFn, compute_layout};
+pub use render::{RenderOptions, to_html, to_svg};
+pub use terminal::{TerminalOptions, to_terminal};
+pub use trace::{Frame, Trace, bfs_trace, trace_frames_svg, trace_to_html};
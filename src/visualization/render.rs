@@ -0,0 +1,298 @@
+/*!
+Static renderings of a laid-out graph.
+
+[`to_svg`] draws the graph as a standalone SVG image and [`to_html`] wraps that image in a page
+that opens in any browser without scripts or a network connection. Layout coordinates are
+scaled uniformly to fit the canvas, keeping their aspect ratio, and the `y` axis points up so
+that latitudes and other map coordinates come out the right way round.
+*/
+
+use std::fmt::Write;
+
+use crate::core::io::escape_xml;
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId};
+
+use super::layout::Layout;
+
+/// Canvas size and styling for [`to_svg`] and [`to_html`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::visualization::RenderOptions;
+///
+/// let options = RenderOptions::new().with_size(400.0, 300.0).with_labels(true);
+/// assert_eq!(options.width, 400.0);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderOptions {
+    /// Canvas width in pixels.
+    pub width: f64,
+    /// Canvas height in pixels.
+    pub height: f64,
+    /// Empty border kept around the drawing, in pixels.
+    pub margin: f64,
+    /// Radius of the node circles, in pixels.
+    pub node_radius: f64,
+    /// Whether to write each node's index next to it.
+    pub labels: bool,
+    /// Page title of [`to_html`], also used as the SVG title.
+    pub title: String,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            width: 800.0,
+            height: 600.0,
+            margin: 20.0,
+            node_radius: 5.0,
+            labels: false,
+            title: "Graphina".to_string(),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// Creates the default options: an 800 by 600 canvas without labels.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the canvas size.
+    pub fn with_size(mut self, width: f64, height: f64) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Sets whether node indices are drawn.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = title.into();
+        self
+    }
+}
+
+/// Maps layout coordinates onto the canvas described by `options`.
+pub(crate) struct Viewport {
+    min: (f64, f64),
+    scale: f64,
+    offset: (f64, f64),
+    height: f64,
+}
+
+impl Viewport {
+    pub(crate) fn fit(layout: &Layout, options: &RenderOptions) -> Self {
+        let (mut min, mut max) = (
+            (f64::INFINITY, f64::INFINITY),
+            (f64::NEG_INFINITY, f64::NEG_INFINITY),
+        );
+        for &(x, y) in layout.values() {
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
+        }
+        if layout.is_empty() {
+            (min, max) = ((0.0, 0.0), (0.0, 0.0));
+        }
+        let inner = (
+            (options.width - 2.0 * options.margin).max(0.0),
+            (options.height - 2.0 * options.margin).max(0.0),
+        );
+        let span = (max.0 - min.0, max.1 - min.1);
+        let scale = match (span.0 > 0.0, span.1 > 0.0) {
+            (true, true) => (inner.0 / span.0).min(inner.1 / span.1),
+            (true, false) => inner.0 / span.0,
+            (false, true) => inner.1 / span.1,
+            (false, false) => 0.0,
+        };
+        // Center the drawing in whichever direction has room to spare.
+        let offset = (
+            options.margin + (inner.0 - span.0 * scale) / 2.0,
+            options.margin + (inner.1 - span.1 * scale) / 2.0,
+        );
+        Self {
+            min,
+            scale,
+            offset,
+            height: options.height,
+        }
+    }
+
+    /// Canvas coordinates of a layout point, with `y` growing downwards as SVG expects.
+    pub(crate) fn map(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.offset.0 + (x - self.min.0) * self.scale,
+            self.height - (self.offset.1 + (y - self.min.1) * self.scale),
+        )
+    }
+}
+
+/// Draws `graph` at the positions in `layout` as an SVG document.
+///
+/// Nodes missing from `layout`, and edges touching them, are left out. Directed edges end
+/// in an arrowhead. Each node carries a `<title>` with its index, which browsers show as a
+/// tooltip.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::visualization::{LayoutAlgorithm, RenderOptions, compute_layout, to_svg};
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+///
+/// let layout = compute_layout(&g, &LayoutAlgorithm::Circular).unwrap();
+/// let svg = to_svg(&g, &layout, &RenderOptions::default());
+/// assert!(svg.starts_with("<svg"));
+/// assert_eq!(svg.matches("<circle").count(), 2);
+/// ```
+pub fn to_svg<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    options: &RenderOptions,
+) -> String
+where
+    Ty: GraphConstructor<A, W>,
+{
+    svg_with(graph, layout, options, |_| String::new(), |_| String::new())
+}
+
+/// [`to_svg`] with extra attributes on each node's `<circle>` and each edge's `<line>`,
+/// such as an `id` or a `fill`. Edges are numbered in the order of `graph.edges()`, and the
+/// attribute strings must start with a space.
+pub(crate) fn svg_with<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    options: &RenderOptions,
+    node_attrs: impl Fn(NodeId) -> String,
+    edge_attrs: impl Fn(usize) -> String,
+) -> String
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let view = Viewport::fit(layout, options);
+    let directed = graph.is_directed();
+    let mut svg = String::new();
+    // Writing to a `String` cannot fail, so the `fmt::Result`s below are ignored.
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+        w = options.width,
+        h = options.height
+    );
+    let _ = writeln!(svg, "<title>{}</title>", escape_xml(&options.title));
+    if directed {
+        let _ = writeln!(
+            svg,
+            r##"<defs><marker id="arrow" viewBox="0 -4 8 8" refX="{}" markerWidth="6" markerHeight="6" orient="auto"><path d="M0,-4L8,0L0,4" fill="#999"/></marker></defs>"##,
+            8.0 + options.node_radius
+        );
+    }
+    let _ = writeln!(svg, r##"<g stroke="#999" stroke-opacity="0.8">"##);
+    for (edge, (u, v, _)) in graph.edges().enumerate() {
+        let (Some(&p), Some(&q)) = (layout.get(&u), layout.get(&v)) else {
+            continue;
+        };
+        let ((x1, y1), (x2, y2)) = (view.map(p), view.map(q));
+        let extra = edge_attrs(edge);
+        let marker = if directed {
+            r#" marker-end="url(#arrow)""#
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x1:.2}" y1="{y1:.2}" x2="{x2:.2}" y2="{y2:.2}"{marker}{extra}/>"#
+        );
+    }
+    let _ = writeln!(svg, "</g>");
+    let _ = writeln!(svg, r##"<g fill="#4c78a8" stroke="#fff">"##);
+    for node in graph.node_ids() {
+        let Some(&p) = layout.get(&node) else {
+            continue;
+        };
+        let (x, y) = view.map(p);
+        let _ = writeln!(
+            svg,
+            r#"<circle cx="{x:.2}" cy="{y:.2}" r="{}"{}><title>{}</title></circle>"#,
+            options.node_radius,
+            node_attrs(node),
+            node.index()
+        );
+        if options.labels {
+            let _ = writeln!(
+                svg,
+                r##"<text x="{:.2}" y="{:.2}" font-size="11" fill="#333" stroke="none">{}</text>"##,
+                x + options.node_radius + 2.0,
+                y - options.node_radius - 2.0,
+                node.index()
+            );
+        }
+    }
+    let _ = writeln!(svg, "</g>");
+    svg.push_str("</svg>\n");
+    svg
+}
+
+/// Draws `graph` like [`to_svg`] and wraps the image in a standalone HTML page titled
+/// `options.title`.
+pub fn to_html<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    options: &RenderOptions,
+) -> String
+where
+    Ty: GraphConstructor<A, W>,
+{
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body style=\"margin: 0\">\n{}</body>\n</html>\n",
+        escape_xml(&options.title),
+        to_svg(graph, layout, options)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Digraph;
+    use crate::visualization::layout::{LayoutAlgorithm, compute_layout};
+
+    #[test]
+    fn test_svg_fits_coordinates_to_canvas() {
+        // Points spanning 10 by 5 units on a 120 by 70 canvas with a 10 pixel margin gives a
+        // scale of 10, with the drawing flipped so the larger y is at the top.
+        let mut g = Digraph::<(f64, f64), f64>::new();
+        let low = g.add_node((0.0, 0.0));
+        let high = g.add_node((10.0, 5.0));
+        g.add_edge(low, high, 1.0);
+        let layout = compute_layout(&g, &LayoutAlgorithm::from_attributes(|p| *p)).unwrap();
+        let mut options = RenderOptions::new()
+            .with_size(120.0, 70.0)
+            .with_title("a < b");
+        options.margin = 10.0;
+
+        let svg = to_svg(&g, &layout, &options);
+        assert!(svg.contains(r#"<circle cx="10.00" cy="60.00""#));
+        assert!(svg.contains(r#"<circle cx="110.00" cy="10.00""#));
+        assert!(svg.contains(r#"marker-end="url(#arrow)""#));
+        assert!(svg.contains("<title>a &lt; b</title>"));
+
+        let html = to_html(&g, &layout, &options);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains(&svg));
+
+        // A single node, or an empty layout, still renders without dividing by zero.
+        let one = compute_layout(&g, &LayoutAlgorithm::from_attributes(|_| (3.0, 3.0))).unwrap();
+        assert!(!to_svg(&g, &one, &options).contains("NaN"));
+        assert!(!to_svg(&g, &Layout::default(), &options).contains("<circle"));
+    }
+}
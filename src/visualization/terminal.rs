@@ -0,0 +1,309 @@
+/*!
+Character-grid drawings of a laid-out graph, for printing small graphs in a terminal.
+
+[`to_terminal`] places every node of a layout on a grid of at most `columns` by `rows`
+characters and draws each edge as a line of box-drawing characters between its ends. Terminal
+cells are about twice as tall as they are wide, so the layout is stretched horizontally by
+two to keep its proportions. Colors are plain ANSI escape codes, so they need no terminal
+library and show up in any modern terminal emulator.
+*/
+
+use crate::core::types::{BaseGraph, GraphConstructor};
+
+use super::layout::Layout;
+use super::render::{RenderOptions, Viewport};
+
+const NODE_COLOR: &str = "\x1b[1;34m";
+const EDGE_COLOR: &str = "\x1b[90m";
+const RESET: &str = "\x1b[0m";
+
+/// Grid size and styling for [`to_terminal`].
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::visualization::TerminalOptions;
+///
+/// let options = TerminalOptions::new().with_size(60, 20).with_unicode(false);
+/// assert_eq!(options.columns, 60);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TerminalOptions {
+    /// Maximum width of the drawing, in characters.
+    pub columns: usize,
+    /// Maximum height of the drawing, in lines.
+    pub rows: usize,
+    /// Whether to write each node's index next to it.
+    pub labels: bool,
+    /// Whether to color nodes and edges with ANSI escape codes.
+    pub colors: bool,
+    /// Whether to use Unicode box-drawing characters rather than plain ASCII.
+    pub unicode: bool,
+}
+
+impl Default for TerminalOptions {
+    fn default() -> Self {
+        Self {
+            columns: 80,
+            rows: 24,
+            labels: true,
+            colors: false,
+            unicode: true,
+        }
+    }
+}
+
+impl TerminalOptions {
+    /// Creates the default options: an 80 by 24 Unicode grid with labels and no colors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum grid size.
+    pub fn with_size(mut self, columns: usize, rows: usize) -> Self {
+        self.columns = columns;
+        self.rows = rows;
+        self
+    }
+
+    /// Sets whether node indices are written.
+    pub fn with_labels(mut self, labels: bool) -> Self {
+        self.labels = labels;
+        self
+    }
+
+    /// Sets whether ANSI colors are used.
+    pub fn with_colors(mut self, colors: bool) -> Self {
+        self.colors = colors;
+        self
+    }
+
+    /// Sets whether Unicode characters are used.
+    pub fn with_unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Cell {
+    Blank,
+    Edge(char),
+    Node,
+    Label(char),
+}
+
+/// The glyph for a step of `(dx, dy)` cells, with `y` growing downwards.
+fn stroke(step: (isize, isize), unicode: bool) -> char {
+    match (step, unicode) {
+        ((_, 0), true) => '─',
+        ((0, _), true) => '│',
+        ((dx, dy), true) if dx == dy => '╲',
+        (_, true) => '╱',
+        ((_, 0), false) => '-',
+        ((0, _), false) => '|',
+        ((dx, dy), false) if dx == dy => '\\',
+        (_, false) => '/',
+    }
+}
+
+fn arrow(step: (isize, isize), unicode: bool) -> char {
+    match (step, unicode) {
+        ((1, 0), true) => '→',
+        ((-1, 0), true) => '←',
+        ((0, 1), true) => '↓',
+        ((0, -1), true) => '↑',
+        ((1, 1), true) => '↘',
+        ((-1, -1), true) => '↖',
+        ((1, -1), true) => '↗',
+        (_, true) => '↙',
+        ((1, 0), false) => '>',
+        ((-1, 0), false) => '<',
+        ((_, dy), false) if dy < 0 => '^',
+        (_, false) => 'v',
+    }
+}
+
+/// Draws `graph` at the positions in `layout` on a character grid.
+///
+/// Each node is a `●` (`o` in ASCII mode) and each edge a Bresenham line between its ends,
+/// ending in an arrowhead just before the target on directed graphs. Where two edges meet
+/// the cell shows a crossing. Nodes that land on the same cell are drawn once. Nodes missing
+/// from `layout`, and edges touching them, are left out. Trailing spaces are trimmed from
+/// each line.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::visualization::{Layout, TerminalOptions, to_terminal};
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+/// let layout: Layout = [(a, (0.0, 0.0)), (b, (1.0, 0.0))].into_iter().collect();
+///
+/// let art = to_terminal(&g, &layout, &TerminalOptions::new().with_size(9, 1));
+/// // The second label would run off the grid, so it is dropped.
+/// assert_eq!(art, "●0──────●\n");
+/// ```
+pub fn to_terminal<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    options: &TerminalOptions,
+) -> String
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let (columns, rows) = (options.columns, options.rows);
+    if columns == 0 || rows == 0 {
+        return String::new();
+    }
+    // Fit into half the width, then double `x`, to make up for tall terminal cells.
+    let canvas = RenderOptions {
+        width: (columns - 1) as f64 / 2.0,
+        height: (rows - 1) as f64,
+        margin: 0.0,
+        ..RenderOptions::default()
+    };
+    let view = Viewport::fit(layout, &canvas);
+    let cell = |p: (f64, f64)| {
+        let (x, y) = view.map(p);
+        (
+            ((2.0 * x).round().max(0.0) as usize).min(columns - 1),
+            (y.round().max(0.0) as usize).min(rows - 1),
+        )
+    };
+    let mut grid = vec![vec![Cell::Blank; columns]; rows];
+    let crossing = if options.unicode { '┼' } else { '+' };
+
+    for (u, v, _) in graph.edges() {
+        let (Some(&p), Some(&q)) = (layout.get(&u), layout.get(&v)) else {
+            continue;
+        };
+        let ((x0, y0), (x1, y1)) = (cell(p), cell(q));
+        let path = line((x0 as isize, y0 as isize), (x1 as isize, y1 as isize));
+        // Skip both ends, which belong to the nodes.
+        for i in 1..path.len().saturating_sub(1) {
+            let (x, y) = path[i];
+            let step = (x - path[i - 1].0, y - path[i - 1].1);
+            let glyph = if graph.is_directed() && i == path.len() - 2 {
+                arrow(step, options.unicode)
+            } else {
+                stroke(step, options.unicode)
+            };
+            let slot = &mut grid[y as usize][x as usize];
+            *slot = match *slot {
+                Cell::Edge(old) if old != glyph => Cell::Edge(crossing),
+                _ => Cell::Edge(glyph),
+            };
+        }
+    }
+    let mut placed = Vec::new();
+    for node in graph.node_ids() {
+        if let Some(&p) = layout.get(&node) {
+            let (x, y) = cell(p);
+            grid[y][x] = Cell::Node;
+            placed.push((node.index(), x, y));
+        }
+    }
+    if options.labels {
+        for (index, x, y) in placed {
+            let row = &mut grid[y];
+            for (offset, digit) in index.to_string().chars().enumerate() {
+                match row.get_mut(x + 1 + offset) {
+                    Some(slot) if *slot != Cell::Node => *slot = Cell::Label(digit),
+                    _ => break,
+                }
+            }
+        }
+    }
+
+    let node = if options.unicode { '●' } else { 'o' };
+    let mut out = String::new();
+    for row in grid {
+        let end = row
+            .iter()
+            .rposition(|&c| c != Cell::Blank)
+            .map_or(0, |i| i + 1);
+        for &c in &row[..end] {
+            let (glyph, color) = match c {
+                Cell::Blank => (' ', None),
+                Cell::Edge(glyph) => (glyph, Some(EDGE_COLOR)),
+                Cell::Node => (node, Some(NODE_COLOR)),
+                Cell::Label(digit) => (digit, None),
+            };
+            match color.filter(|_| options.colors) {
+                Some(color) => {
+                    out.push_str(color);
+                    out.push(glyph);
+                    out.push_str(RESET);
+                }
+                None => out.push(glyph),
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The cells of a Bresenham line from `from` to `to`, both included.
+fn line(from: (isize, isize), to: (isize, isize)) -> Vec<(isize, isize)> {
+    let (dx, dy) = ((to.0 - from.0).abs(), -(to.1 - from.1).abs());
+    let (sx, sy) = ((to.0 - from.0).signum(), (to.1 - from.1).signum());
+    let (mut x, mut y) = from;
+    let mut error = dx + dy;
+    let mut cells = vec![from];
+    while (x, y) != to {
+        let twice = 2 * error;
+        if twice >= dy {
+            error += dy;
+            x += sx;
+        }
+        if twice <= dx {
+            error += dx;
+            y += sy;
+        }
+        cells.push((x, y));
+    }
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Digraph;
+
+    #[test]
+    fn test_terminal_drawing_places_nodes_and_edges() {
+        // A directed triangle in a 9 by 5 grid: corners at the top left, the top right, and
+        // the bottom middle.
+        let mut g = Digraph::<(), f64>::new();
+        let [a, b, c] = [(); 3].map(|_| g.add_node(()));
+        g.add_edge(a, b, 1.0);
+        g.add_edge(b, c, 1.0);
+        g.add_edge(a, c, 1.0);
+        let layout: Layout = [(a, (0.0, 2.0)), (b, (2.0, 2.0)), (c, (1.0, 0.0))]
+            .into_iter()
+            .collect();
+        let options = TerminalOptions::new().with_size(9, 5).with_labels(false);
+
+        let art = to_terminal(&g, &layout, &options);
+        assert_eq!(art, "●──────→●\n ╲     ╱\n  ╲   ╱\n   ↘ ↙\n    ●\n");
+        let ascii = to_terminal(&g, &layout, &options.clone().with_unicode(false));
+        assert_eq!(ascii, "o------>o\n \\     /\n  \\   /\n   v v\n    o\n");
+
+        let colored = to_terminal(&g, &layout, &options.clone().with_colors(true));
+        assert!(colored.contains(&format!("{NODE_COLOR}●{RESET}")));
+        assert_eq!(
+            colored.matches(RESET).count(),
+            art.matches(['●', '─', '╲', '╱', '→', '↘', '↙']).count()
+        );
+
+        // Labels stop at the grid's edge, and a zero-sized grid draws nothing.
+        let labeled = to_terminal(&g, &layout, &options.clone().with_labels(true));
+        assert!(labeled.starts_with("●0─────→●\n"));
+        assert_eq!(to_terminal(&g, &layout, &options.with_size(0, 5)), "");
+    }
+}
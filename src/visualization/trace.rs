@@ -0,0 +1,420 @@
+/*!
+Algorithm traces: step-by-step pictures of an algorithm at work, for teaching and debugging.
+
+A [`Trace`] is a list of [`Frame`]s, each saying which nodes to color and which edges are
+gone at that step. [`bfs_trace`] records one directly. For community detection, replay the
+per-level partitions of `community::louvain::louvain_levels` with [`Trace::from_partitions`]
+or the edge removals of `community::girvan_newman::girvan_newman_removals` with
+[`Trace::from_edge_removals`]; this module depends on the core module only, so it takes
+their output rather than calling them.
+
+[`trace_to_html`] turns a trace into a single page with a stepper, and [`trace_frames_svg`]
+into one SVG image per frame. Both draw every frame over the same layout.
+*/
+
+use crate::core::error::{GraphinaError, Result};
+use crate::core::io::escape_xml;
+use crate::core::types::{BaseGraph, GraphConstructor, NodeId, NodeMap, NodeSet};
+
+use super::layout::Layout;
+use super::render::{RenderOptions, svg_with};
+
+/// Colors of the groups of a frame, cycled when there are more groups.
+const PALETTE: [&str; 10] = [
+    "#4c78a8", "#f58518", "#54a24b", "#b279a2", "#72b7b2", "#eeca3b", "#9d755d", "#ff9da6",
+    "#bab0ac", "#439894",
+];
+/// Fill of marked nodes.
+const MARKED: &str = "#e45756";
+/// Fill of nodes in no group, when a frame has groups.
+const UNGROUPED: &str = "#dddddd";
+
+/// One step of a [`Trace`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Frame {
+    /// What happens at this step, shown next to the drawing.
+    pub caption: String,
+    /// Nodes to highlight, drawn in red, such as a BFS frontier.
+    pub marked: Vec<NodeId>,
+    /// Groups of nodes, each drawn in its own color, such as communities or visited nodes.
+    /// When there is at least one group, nodes in none are drawn in light gray.
+    pub groups: Vec<Vec<NodeId>>,
+    /// Edges that are gone at this step, drawn faded. A pair matches edges in either
+    /// direction.
+    pub removed: Vec<(NodeId, NodeId)>,
+}
+
+/// A recorded run of an algorithm, one [`Frame`] per step.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace {
+    /// The steps, in order.
+    pub frames: Vec<Frame>,
+}
+
+impl Trace {
+    /// Creates an empty trace.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a frame.
+    pub fn push(&mut self, frame: Frame) {
+        self.frames.push(frame);
+    }
+
+    /// One frame per partition, each community in its own color, for a hierarchy such as the
+    /// levels of Louvain.
+    ///
+    /// ```rust
+    /// use graphina::core::types::Graph;
+    /// use graphina::visualization::Trace;
+    ///
+    /// let mut g = Graph::<(), f64>::new();
+    /// let [a, b] = [(); 2].map(|_| g.add_node(()));
+    /// let trace = Trace::from_partitions(&[vec![vec![a], vec![b]], vec![vec![a, b]]]);
+    /// assert_eq!(trace.frames[1].caption, "Level 2: 1 community");
+    /// ```
+    pub fn from_partitions(partitions: &[Vec<Vec<NodeId>>]) -> Self {
+        let frames = partitions
+            .iter()
+            .enumerate()
+            .map(|(level, communities)| Frame {
+                caption: format!(
+                    "Level {}: {} {}",
+                    level + 1,
+                    communities.len(),
+                    if communities.len() == 1 {
+                        "community"
+                    } else {
+                        "communities"
+                    }
+                ),
+                groups: communities.clone(),
+                ..Frame::default()
+            })
+            .collect();
+        Self { frames }
+    }
+
+    /// A frame for the intact graph, then one per removed edge with all removals so far
+    /// faded and the ends of the latest one marked, for divisive methods such as
+    /// Girvan-Newman.
+    pub fn from_edge_removals(removals: &[(NodeId, NodeId)]) -> Self {
+        let mut frames = vec![Frame {
+            caption: "Initial graph".to_string(),
+            ..Frame::default()
+        }];
+        for (step, &(u, v)) in removals.iter().enumerate() {
+            frames.push(Frame {
+                caption: format!("Removal {}: edge {} - {}", step + 1, u.index(), v.index()),
+                marked: vec![u, v],
+                removed: removals[..=step].to_vec(),
+                ..Frame::default()
+            });
+        }
+        Self { frames }
+    }
+}
+
+/// Records a breadth-first search from `start`: one frame per level, with the level's
+/// frontier marked, in node index order, and the nodes visited before it in a group.
+///
+/// Follows edge directions on directed graphs. The last frame shows the final frontier, so
+/// the trace has one frame per distance from `start` that some node is at.
+///
+/// # Errors
+///
+/// `NodeNotFound` if `start` is not in the graph.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::visualization::bfs_trace;
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let n: Vec<_> = (0..4).map(|_| g.add_node(())).collect();
+/// g.add_edge(n[0], n[1], 1.0);
+/// g.add_edge(n[0], n[2], 1.0);
+/// g.add_edge(n[2], n[3], 1.0);
+///
+/// let trace = bfs_trace(&g, n[0]).unwrap();
+/// assert_eq!(trace.frames.len(), 3);
+/// assert_eq!(trace.frames[1].marked, vec![n[1], n[2]]);
+/// assert_eq!(trace.frames[2].groups, vec![vec![n[0], n[1], n[2]]]);
+/// ```
+pub fn bfs_trace<A, W, Ty>(graph: &BaseGraph<A, W, Ty>, start: NodeId) -> Result<Trace>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    if !graph.contains_node(start) {
+        return Err(GraphinaError::node_not_found(format!(
+            "BFS trace: node {} is not in the graph",
+            start.index()
+        )));
+    }
+    let mut seen: NodeSet = [start].into_iter().collect();
+    let mut visited = Vec::new();
+    let mut frontier = vec![start];
+    let mut trace = Trace::new();
+    while !frontier.is_empty() {
+        trace.push(Frame {
+            caption: format!(
+                "Distance {}: {} {}",
+                trace.frames.len(),
+                frontier.len(),
+                if frontier.len() == 1 { "node" } else { "nodes" }
+            ),
+            marked: frontier.clone(),
+            groups: vec![visited.clone()],
+            ..Frame::default()
+        });
+        let mut next = Vec::new();
+        for &u in &frontier {
+            for v in graph.neighbors(u) {
+                if seen.insert(v) {
+                    next.push(v);
+                }
+            }
+        }
+        next.sort_by_key(|v| v.index());
+        visited.append(&mut frontier);
+        frontier = next;
+    }
+    Ok(trace)
+}
+
+/// The node colors and faded edge numbers of `frame`, with edges numbered in the order of
+/// `graph.edges()`.
+fn frame_style<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    frame: &Frame,
+) -> (NodeMap<&'static str>, Vec<usize>)
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let mut fills = NodeMap::default();
+    if !frame.groups.is_empty() {
+        for node in graph.node_ids() {
+            fills.insert(node, UNGROUPED);
+        }
+    }
+    for (i, group) in frame.groups.iter().enumerate() {
+        for &node in group {
+            fills.insert(node, PALETTE[i % PALETTE.len()]);
+        }
+    }
+    for &node in &frame.marked {
+        fills.insert(node, MARKED);
+    }
+    let gone: std::collections::HashSet<(NodeId, NodeId)> = frame
+        .removed
+        .iter()
+        .flat_map(|&(u, v)| [(u, v), (v, u)])
+        .collect();
+    let removed = graph
+        .edges()
+        .enumerate()
+        .filter(|(_, (u, v, _))| gone.contains(&(*u, *v)))
+        .map(|(i, _)| i)
+        .collect();
+    (fills, removed)
+}
+
+/// Draws every frame of `trace` as its own SVG image, titled with the frame's caption.
+///
+/// Write them out as numbered files to flip through, or convert them to PNG with a tool such
+/// as `rsvg-convert`.
+pub fn trace_frames_svg<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    trace: &Trace,
+    options: &RenderOptions,
+) -> Vec<String>
+where
+    Ty: GraphConstructor<A, W>,
+{
+    trace
+        .frames
+        .iter()
+        .map(|frame| {
+            let (fills, removed) = frame_style(graph, frame);
+            let titled = RenderOptions {
+                title: format!("{}: {}", options.title, frame.caption),
+                ..options.clone()
+            };
+            svg_with(
+                graph,
+                layout,
+                &titled,
+                |node| {
+                    fills
+                        .get(&node)
+                        .map_or_else(String::new, |fill| format!(r#" fill="{fill}""#))
+                },
+                |edge| {
+                    if removed.contains(&edge) {
+                        r#" stroke-opacity="0.1""#.to_string()
+                    } else {
+                        String::new()
+                    }
+                },
+            )
+        })
+        .collect()
+}
+
+const STEPPER: &str = r#"<div style="font-family: sans-serif; padding: 8px">
+<button id="prev">&larr;</button>
+<input id="step" type="range" min="0" max="__LAST__" value="0">
+<button id="next">&rarr;</button>
+<span id="caption"></span>
+</div>
+"#;
+
+const SCRIPT: &str = r#"<script>
+const frames = __FRAMES__;
+let current = 0;
+function show(i) {
+  current = Math.max(0, Math.min(frames.length - 1, i));
+  const frame = frames[current];
+  document.querySelectorAll("circle[id]").forEach((c) => {
+    const fill = frame.fill[c.id.slice(1)];
+    if (fill) { c.setAttribute("fill", fill); } else { c.removeAttribute("fill"); }
+  });
+  document.querySelectorAll("line[id]").forEach((l) => {
+    l.style.opacity = frame.removed.includes(Number(l.id.slice(1))) ? "0.1" : "";
+  });
+  document.getElementById("caption").textContent =
+    `${current + 1} / ${frames.length}: ${frame.caption}`;
+  document.getElementById("step").value = current;
+}
+document.getElementById("prev").onclick = () => show(current - 1);
+document.getElementById("next").onclick = () => show(current + 1);
+document.getElementById("step").oninput = (event) => show(Number(event.target.value));
+document.addEventListener("keydown", (event) => {
+  if (event.key === "ArrowLeft") show(current - 1);
+  if (event.key === "ArrowRight") show(current + 1);
+});
+if (frames.length > 0) show(0);
+</script>
+"#;
+
+/// Turns `trace` into a standalone HTML page that steps through its frames.
+///
+/// The page holds a single drawing, as [`to_html`](super::to_html) would make it, and
+/// restyles it for each frame. Step with the buttons, the slider, or the left and right
+/// arrow keys. Like the other renderers it needs no network connection; the stepper is a few
+/// lines of inline script.
+///
+/// # Example
+///
+/// ```rust
+/// use graphina::core::types::Graph;
+/// use graphina::visualization::{
+///     LayoutAlgorithm, RenderOptions, bfs_trace, compute_layout, trace_to_html,
+/// };
+///
+/// let mut g = Graph::<(), f64>::new();
+/// let a = g.add_node(());
+/// let b = g.add_node(());
+/// g.add_edge(a, b, 1.0);
+///
+/// let layout = compute_layout(&g, &LayoutAlgorithm::Circular).unwrap();
+/// let trace = bfs_trace(&g, a).unwrap();
+/// let page = trace_to_html(&g, &layout, &trace, &RenderOptions::default());
+/// assert!(page.contains(r#" id="n1"><title>1</title></circle>"#));
+/// ```
+pub fn trace_to_html<A, W, Ty>(
+    graph: &BaseGraph<A, W, Ty>,
+    layout: &Layout,
+    trace: &Trace,
+    options: &RenderOptions,
+) -> String
+where
+    Ty: GraphConstructor<A, W>,
+{
+    let svg = svg_with(
+        graph,
+        layout,
+        options,
+        |node| format!(r#" id="n{}""#, node.index()),
+        |edge| format!(r#" id="e{edge}""#),
+    );
+    let frames: Vec<serde_json::Value> = trace
+        .frames
+        .iter()
+        .map(|frame| {
+            let (fills, removed) = frame_style(graph, frame);
+            let fill: serde_json::Map<String, serde_json::Value> = fills
+                .into_iter()
+                .map(|(node, color)| (node.index().to_string(), color.into()))
+                .collect();
+            serde_json::json!({
+                "caption": frame.caption,
+                "fill": fill,
+                "removed": removed,
+            })
+        })
+        .collect();
+    // `</` would end the script element early, wherever it appears in a caption.
+    let data = serde_json::Value::from(frames)
+        .to_string()
+        .replace("</", "<\\/");
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body style=\"margin: 0\">\n{}{}{}</body>\n</html>\n",
+        escape_xml(&options.title),
+        STEPPER.replace(
+            "__LAST__",
+            &trace.frames.len().saturating_sub(1).to_string()
+        ),
+        svg,
+        SCRIPT.replace("__FRAMES__", &data)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::types::Graph;
+    use crate::visualization::layout::{LayoutAlgorithm, compute_layout};
+
+    #[test]
+    fn test_trace_frames_style_the_drawing() {
+        let mut g = Graph::<(), f64>::new();
+        let n: Vec<_> = (0..3).map(|_| g.add_node(())).collect();
+        g.add_edge(n[0], n[1], 1.0);
+        g.add_edge(n[1], n[2], 1.0);
+        let layout = compute_layout(&g, &LayoutAlgorithm::Circular).unwrap();
+        let options = RenderOptions::default();
+
+        // Removing the first edge, listed the other way round, fades it in the second frame.
+        let trace = Trace::from_edge_removals(&[(n[1], n[0])]);
+        let frames = trace_frames_svg(&g, &layout, &trace, &options);
+        assert_eq!(frames.len(), 2);
+        assert!(!frames[0].contains("stroke-opacity=\"0.1\""));
+        assert_eq!(frames[1].matches("stroke-opacity=\"0.1\"").count(), 1);
+        assert_eq!(frames[1].matches(&format!("fill=\"{MARKED}\"")).count(), 2);
+        assert!(frames[1].contains("<title>Graphina: Removal 1: edge 1 - 0</title>"));
+
+        let bfs = bfs_trace(&g, n[2]).unwrap();
+        assert_eq!(bfs.frames.len(), 3);
+        let (fills, _) = frame_style(&g, &bfs.frames[1]);
+        assert_eq!(
+            (fills[&n[2]], fills[&n[1]], fills[&n[0]]),
+            (PALETTE[0], MARKED, UNGROUPED)
+        );
+        let ghost = NodeId::new(petgraph::graph::NodeIndex::new(9));
+        assert!(bfs_trace(&g, ghost).is_err());
+
+        let mut tricky = Trace::new();
+        tricky.push(Frame {
+            caption: "</script>".to_string(),
+            ..Frame::default()
+        });
+        let page = trace_to_html(&g, &layout, &tricky, &options);
+        assert_eq!(page.matches("</script>").count(), 1);
+        assert!(page.contains(r#"max="0""#));
+        assert!(page.contains(r#" id="e1"/>"#));
+    }
+}